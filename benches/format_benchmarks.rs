@@ -3,13 +3,14 @@
 //! the single-thread parser-pool path.
 //!
 //! Covers CSV, MODS, Dublin Core, BIBFRAME (RDF), and MARC-in-JSON
-//! serialization, the parse-from direction where one exists, and the
+//! serialization, the parse-from direction where one exists, BIBFRAME
+//! graph conversion in both directions, and the
 //! boundary-scan + batch-parse path used by the producer-consumer pipeline,
 //! pinned to one rayon worker so the per-record instruction cost is a
 //! deterministic single-thread signal.
 
 use criterion::{Criterion, black_box, criterion_group, criterion_main};
-use mrrc::bibframe::{BibframeConfig, RdfFormat, marc_to_bibframe};
+use mrrc::bibframe::{BibframeConfig, RdfFormat, bibframe_to_marc, marc_to_bibframe};
 use mrrc::boundary_scanner::RecordBoundaryScanner;
 use mrrc::rayon_parser_pool::parse_batch_parallel;
 use mrrc::{MarcReader, Record, csv, dublin_core, marcjson, mods};
@@ -157,6 +158,43 @@ fn benchmark_serialize_to_bibframe_turtle_1k(c: &mut Criterion) {
     });
 }
 
+/// Benchmark BIBFRAME graph construction alone for 1,000 MARC records.
+///
+/// Separates the MARC-to-RDF mapping from Turtle serialization, which
+/// dominates `serialize_1k_to_bibframe_turtle`.
+fn benchmark_convert_to_bibframe_1k(c: &mut Criterion) {
+    let records = black_box(parse_fixture("1k_records.mrc"));
+    let config = BibframeConfig::default();
+
+    c.bench_function("convert_1k_to_bibframe", |b| {
+        b.iter(|| {
+            records
+                .iter()
+                .map(|record| marc_to_bibframe(record, &config).len())
+                .sum::<usize>()
+        });
+    });
+}
+
+/// Benchmark converting 1,000 BIBFRAME graphs back to MARC.
+fn benchmark_convert_from_bibframe_1k(c: &mut Criterion) {
+    let config = BibframeConfig::default();
+    let graphs: Vec<_> = parse_fixture("1k_records.mrc")
+        .iter()
+        .map(|record| marc_to_bibframe(record, &config))
+        .collect();
+    let graphs = black_box(graphs);
+
+    c.bench_function("convert_1k_from_bibframe", |b| {
+        b.iter(|| {
+            graphs
+                .iter()
+                .filter(|graph| bibframe_to_marc(graph).is_ok())
+                .count()
+        });
+    });
+}
+
 /// Benchmark the single-thread parser-pool path over 1,000 records.
 ///
 /// Drives the boundary scan and `parse_batch_parallel` exactly as the
@@ -191,6 +229,8 @@ criterion_group!(
     benchmark_parse_from_mods_1k,
     benchmark_serialize_to_dublin_core_1k,
     benchmark_serialize_to_bibframe_turtle_1k,
+    benchmark_convert_to_bibframe_1k,
+    benchmark_convert_from_bibframe_1k,
     benchmark_parser_pool_single_thread_1k,
 );
 criterion_main!(benches);
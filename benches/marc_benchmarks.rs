@@ -5,7 +5,7 @@
 //! MARC records using Criterion.rs for statistical analysis.
 
 use criterion::{Criterion, black_box, criterion_group, criterion_main};
-use mrrc::{LinkageInfo, MarcReader, MarcWriter, RecordHelpers, json, marcxml};
+use mrrc::{LinkageInfo, MarcReader, MarcWriter, Record, RecordHelpers, json, marcxml};
 use std::io::Cursor;
 
 /// Load test fixtures from the test data directory.
@@ -228,6 +228,89 @@ fn benchmark_deserialize_marcxml_record(c: &mut Criterion) {
     });
 }
 
+/// Parse every record in a fixture once, for benches that time only the
+/// parse-from direction of another format.
+fn parse_fixture(filename: &str) -> Vec<Record> {
+    let mut reader = MarcReader::new(Cursor::new(load_fixture(filename)));
+    let mut records = Vec::new();
+    while let Ok(Some(record)) = reader.read_record() {
+        records.push(record);
+    }
+    records
+}
+
+/// Benchmark MARCXML parsing of a 1,000-record collection document.
+fn benchmark_deserialize_marcxml_1k(c: &mut Criterion) {
+    let records = parse_fixture("1k_records.mrc");
+    let mut xml = String::from("<collection xmlns=\"http://www.loc.gov/MARC21/slim\">");
+    for record in &records {
+        let doc = marcxml::record_to_marcxml(record).expect("serialize to MARCXML");
+        let start = doc.find("<record").expect("record element");
+        xml.push_str(&doc[start..]);
+    }
+    xml.push_str("</collection>");
+    let xml = black_box(xml);
+
+    c.bench_function("deserialize_1k_from_xml", |b| {
+        b.iter(|| marcxml::marcxml_to_records(&xml).map_or(0, |records| records.len()));
+    });
+}
+
+/// Benchmark JSON parsing of 1,000 records.
+fn benchmark_deserialize_json_1k(c: &mut Criterion) {
+    let values: Vec<_> = parse_fixture("1k_records.mrc")
+        .iter()
+        .map(|record| json::record_to_json(record).expect("serialize to JSON"))
+        .collect();
+    let values = black_box(values);
+
+    c.bench_function("deserialize_1k_from_json", |b| {
+        b.iter(|| {
+            values
+                .iter()
+                .filter(|value| json::json_to_record(value).is_ok())
+                .count()
+        });
+    });
+}
+
+/// Benchmark reading the 2,000-record realistic fixture.
+///
+/// The synthetic 1k/10k fixtures repeat a small set of record shapes; this
+/// one has the field counts and lengths of real catalog records, so it is
+/// the fixture to quote throughput figures from.
+fn benchmark_read_realistic(c: &mut Criterion) {
+    let fixture = black_box(load_fixture("realistic.mrc"));
+
+    c.bench_function("read_realistic_records", |b| {
+        b.iter(|| {
+            let mut reader = MarcReader::new(Cursor::new(fixture.as_slice()));
+            let mut count = 0;
+            while let Ok(Some(_record)) = reader.read_record() {
+                count += 1;
+            }
+            count
+        });
+    });
+}
+
+/// Benchmark writing the 2,000-record realistic fixture.
+fn benchmark_write_realistic(c: &mut Criterion) {
+    let records = parse_fixture("realistic.mrc");
+    let mut output = Vec::with_capacity(4 << 20);
+
+    c.bench_function("write_realistic_records", |b| {
+        b.iter(|| {
+            output.clear();
+            let mut writer = MarcWriter::new(&mut output);
+            for record in &records {
+                writer.write_record(record).unwrap();
+            }
+            black_box(output.len())
+        });
+    });
+}
+
 /// Benchmark MARC subfield-6 (880 linkage) parsing.
 ///
 /// `LinkageInfo::parse` runs per field during 880-linkage scans; PERF-7
@@ -307,6 +390,10 @@ criterion_group!(
     benchmark_serialization_to_json_1k,
     benchmark_serialization_to_xml_1k,
     benchmark_deserialize_marcxml_record,
+    benchmark_deserialize_marcxml_1k,
+    benchmark_deserialize_json_1k,
+    benchmark_read_realistic,
+    benchmark_write_realistic,
     benchmark_parse_linkage,
     benchmark_roundtrip_1k,
     benchmark_roundtrip_10k,
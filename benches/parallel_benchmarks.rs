@@ -8,6 +8,9 @@
 
 use criterion::{Criterion, black_box, criterion_group, criterion_main};
 use mrrc::MarcReader;
+use mrrc::boundary_scanner::RecordBoundaryScanner;
+use mrrc::producer_consumer_pipeline::{PipelineConfig, ProducerConsumerPipeline};
+use mrrc::rayon_parser_pool::parse_batch_parallel;
 use rayon::prelude::*;
 use std::io::Cursor;
 
@@ -213,6 +216,36 @@ fn benchmark_parallel_4x_10k(c: &mut Criterion) {
     });
 }
 
+/// Benchmark the rayon parser pool over one 10k buffer.
+///
+/// Unlike the file-level benches above, this splits a single buffer into
+/// records and parses them across the global rayon pool, which is how the
+/// Python bulk reader and the pipeline use it.
+fn benchmark_parser_pool_10k(c: &mut Criterion) {
+    let buffer = black_box(load_fixture("10k_records.mrc"));
+
+    c.bench_function("parser_pool_10k_records", |b| {
+        b.iter(|| {
+            let boundaries = RecordBoundaryScanner::new().scan(&buffer).unwrap();
+            parse_batch_parallel(&boundaries, &buffer).unwrap().len()
+        });
+    });
+}
+
+/// Benchmark the producer-consumer pipeline draining a 10k file.
+fn benchmark_pipeline_10k(c: &mut Criterion) {
+    let config = PipelineConfig::default();
+
+    c.bench_function("pipeline_10k_records", |b| {
+        b.iter(|| {
+            let pipeline =
+                ProducerConsumerPipeline::from_file("tests/data/fixtures/10k_records.mrc", &config)
+                    .unwrap();
+            pipeline.into_iter().flatten().count()
+        });
+    });
+}
+
 criterion_group!(
     parallel_benches,
     benchmark_sequential_2x_1k,
@@ -223,5 +256,7 @@ criterion_group!(
     benchmark_sequential_2x_10k,
     benchmark_parallel_2x_10k,
     benchmark_parallel_4x_10k,
+    benchmark_parser_pool_10k,
+    benchmark_pipeline_10k,
 );
 criterion_main!(parallel_benches);
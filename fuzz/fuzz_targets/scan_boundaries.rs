@@ -0,0 +1,44 @@
+//! Record boundary scan fuzz harness.
+//!
+//! Drives `RecordBoundaryScanner` and the rayon batch parser over arbitrary
+//! bytes — the path the producer-consumer pipeline and the Python bulk
+//! reader take before any record reaches `MarcReader`. Besides no-panic, it
+//! asserts the scanner's own contract: boundaries tile the buffer from
+//! offset 0 up to the last terminator, each ends in 0x1D, and
+//! `count_records` agrees with `scan`.
+//!
+//! An `Err(MarcError)` from the batch parse is correct behavior on
+//! malformed records, so it is discarded.
+//!
+//! See `docs/contributing/fuzzing.md` for triage.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use mrrc::boundary_scanner::RecordBoundaryScanner;
+use mrrc::rayon_parser_pool::{parse_batch_parallel, parse_batch_parallel_limited};
+
+fuzz_target!(|data: &[u8]| {
+    let mut scanner = RecordBoundaryScanner::new();
+    let count = scanner.count_records(data);
+    let Ok(boundaries) = scanner.scan(data) else {
+        assert_eq!(count, 0, "scan failed but count_records found terminators");
+        return;
+    };
+    assert_eq!(boundaries.len(), count);
+
+    let mut expected_offset = 0;
+    for &(offset, length) in &boundaries {
+        assert_eq!(offset, expected_offset, "boundaries must be contiguous");
+        assert!(length > 0);
+        assert_eq!(data[offset + length - 1], 0x1D, "boundary must end at a terminator");
+        expected_offset = offset + length;
+    }
+
+    let limit = usize::from(data[0]);
+    let limited = scanner.scan_limited(data, limit).unwrap();
+    assert_eq!(limited.len(), boundaries.len().min(limit));
+
+    let _ = parse_batch_parallel(&boundaries, data);
+    let _ = parse_batch_parallel_limited(&boundaries, data, limit);
+});
@@ -12,6 +12,7 @@ from typing import Any, ClassVar, Optional, Union
 
 from . import _mrrc
 from ._mrrc import (
+    ArrowColumnStream,
     AuthorityMARCReader,
     AuthorityRecord,
     # BIBFRAME conversion support (LOC linked data format)
@@ -20,16 +21,20 @@ from ._mrrc import (
     FieldQuery,
     HoldingsMARCReader,
     HoldingsRecord,
-    ProducerConsumerPipeline,
+    # Streaming writers for text formats
+    MARCJSONWriter,
+    MARCXMLWriter,
+    MODSWriter,
     RdfGraph,
     RecordBoundaryScanner,
+    # Throughput counters
+    Stats,
+    StatsSnapshot,
     Subfield,
     SubfieldPatternQuery,
     SubfieldValueQuery,
     TagRangeQuery,
     dublin_core_to_xml,
-    parse_batch_parallel,
-    parse_batch_parallel_limited,
     record_to_csv,
     record_to_dublin_core,
     record_to_dublin_core_xml,
@@ -43,6 +48,21 @@ from ._mrrc import (
 from ._mrrc import (
     Field as _Field,
 )
+from ._mrrc import (
+    ProducerConsumerPipeline as _ProducerConsumerPipeline,
+)
+from ._mrrc import (
+    parse_batch_parallel as _parse_batch_parallel,
+)
+from ._mrrc import (
+    parse_batch_parallel_limited as _parse_batch_parallel_limited,
+)
+from ._mrrc import (
+    parse_batch_parallel_map as _parse_batch_parallel_map,
+)
+from ._mrrc import (
+    parse_batch_parallel_results as _parse_batch_parallel_results,
+)
 from ._mrrc import (
     Leader as _Leader,
 )
@@ -144,6 +164,16 @@ def _wrap_field(
     return wrapper
 
 
+def _field_filter(filter):
+    """Adapt a Python predicate to receive wrapped :class:`Field` objects.
+
+    Query objects and dicts pass through unchanged for matching in Rust.
+    """
+    if callable(filter):
+        return lambda rust_field: filter(_wrap_field(rust_field))
+    return filter
+
+
 def _wrap_control_field(
     parent: "Record", tag: str, occurrence: int, value: str
 ) -> "Field":
@@ -1063,8 +1093,7 @@ class Record:
         records share no state, matching pymarc.
         """
         self._sync_leader()
-        new = Record.__new__(Record)
-        new._inner = self._inner.__deepcopy__(memo)
+        new = _wrap_record(self._inner.__deepcopy__(memo))
         memo[id(self)] = new
         return new
 
@@ -1207,6 +1236,48 @@ class Record:
         """
         return _wrap_field(self._inner.remove_field_at(tag, occurrence))
 
+    def remove_fields_where(self, filter) -> list["Field"]:
+        """Remove every data field matching ``filter`` and return them.
+
+        ``filter`` is a query object (:class:`FieldQuery`,
+        :class:`TagRangeQuery`, :class:`SubfieldPatternQuery`,
+        :class:`SubfieldValueQuery`), a dict of ``FieldQuery`` criteria
+        (``tag``, ``indicator1``, ``indicator2``, ``has_subfield``,
+        ``has_subfields``), or a callable taking a :class:`Field` and
+        returning a bool. Query objects and dicts are matched entirely in
+        Rust. The removed fields are returned detached.
+        """
+        removed = self._inner.remove_fields_where(_field_filter(filter))
+        return [_wrap_field(f) for f in removed]
+
+    def update_subfield_values(self, tag: str, code: str, value: str) -> None:
+        """Set every subfield ``code`` in fields tagged ``tag`` to ``value``."""
+        self._inner.update_subfield_values(tag, code, value)
+
+    def update_subfields_where(self, filter, code: str, value: str) -> int:
+        """Set every subfield ``code`` in fields matching ``filter`` to ``value``.
+
+        ``filter`` takes the same forms as in :meth:`remove_fields_where`.
+        Returns the number of fields that matched.
+        """
+        return self._inner.update_subfields_where(
+            _field_filter(filter), code, value
+        )
+
+    def update_fields_where(self, filter, operation) -> int:
+        """Apply ``operation`` to every data field matching ``filter``.
+
+        ``operation`` is called with a detached copy of each matching
+        :class:`Field` and may modify it in place or return a replacement
+        with the same tag. Returns the number of fields that matched.
+        """
+
+        def apply(rust_field):
+            result = operation(_wrap_field(rust_field))
+            return result._inner if isinstance(result, Field) else result
+
+        return self._inner.update_fields_where(_field_filter(filter), apply)
+
     def _remove_tag(self, tag: str) -> None:
         """Remove all fields with the given tag, control tags included."""
         if _is_control_tag(tag):
@@ -1345,6 +1416,16 @@ class Record:
         """Uniform title from 130 field."""
         return self._inner.uniform_title()
 
+    @property
+    def title_sort_key(self) -> str | None:
+        """Browse/sort key for the 245 title, skipping non-filing characters."""
+        return self._inner.title_sort_key()
+
+    @property
+    def uniform_title_sort_key(self) -> str | None:
+        """Browse/sort key for the uniform title (240, else 130)."""
+        return self._inner.uniform_title_sort_key()
+
     @property
     def sudoc(self) -> str | None:
         """SuDoc from 086 field."""
@@ -1971,6 +2052,68 @@ class MARCReader:
         """The backend type: ``"rust_file"``, ``"cursor"``, or ``"python_file"``."""
         return self._inner.backend_type
 
+    def close(self) -> None:
+        """Release the underlying source.
+
+        A file opened from a path is closed; a file object passed in by the
+        caller is left open. Iteration stops after ``close()``. Safe to call
+        more than once.
+        """
+        self._inner.close()
+
+    @property
+    def closed(self) -> bool:
+        """Whether the reader has been closed or exhausted."""
+        return self._inner.closed
+
+    @property
+    def stats(self) -> Stats:
+        """Live throughput counters: records read, bytes, parse errors."""
+        return self._inner.stats
+
+    def __enter__(self):
+        """Context manager support."""
+        return self
+
+    def __exit__(self, exc_type, exc_val, exc_tb):
+        """Context manager support (closes the reader)."""
+        self.close()
+        return False
+
+    def to_arrow_stream(
+        self, columns: list[str] | dict[str, str], batch_size: int = 65536
+    ) -> ArrowColumnStream:
+        """Extract columns from the remaining records as Arrow data.
+
+        ``columns`` is a list of extraction expressions (``"001"``,
+        ``"245$a"``, ``"008/35-37"``, ``"020$a | 022$a"``), each naming its
+        own column, or a dict mapping column names to expressions. Each cell
+        holds the first value the expression finds, or ``None``.
+
+        Parsing and extraction run in Rust straight into Arrow buffers; no
+        Python ``Record`` objects are created. The returned stream implements
+        ``__arrow_c_stream__``, so ``pyarrow``, ``polars`` and other Arrow
+        consumers import it without copying. Consumes the reader. With
+        ``permissive=True``, records that fail to parse are left out.
+        """
+        return self._inner.to_arrow(
+            columns, batch_size=batch_size, skip_errors=self._permissive
+        )
+
+    def to_arrow(
+        self, columns: list[str] | dict[str, str], batch_size: int = 65536
+    ) -> Any:
+        """Extract columns from the remaining records as a ``pyarrow.Table``.
+
+        See :meth:`to_arrow_stream` for the column syntax. Requires
+        ``pyarrow``; use ``table.column(name).to_numpy()`` for NumPy arrays.
+        """
+        try:
+            import pyarrow
+        except ImportError as e:
+            raise ImportError("MARCReader.to_arrow() requires pyarrow") from e
+        return pyarrow.table(self.to_arrow_stream(columns, batch_size=batch_size))
+
     def read_record(self) -> Record | None:
         """Read next record (pymarc compatibility)."""
         try:
@@ -2027,18 +2170,31 @@ class MARCWriter:
 
     def write(self, record: Record) -> None:
         """Write a record."""
-        # Sync any modifications to the leader before writing
-        record._sync_leader()
-        self._inner.write_record(record._inner)
+        # The Rust writer syncs pending leader edits on wrapped records
+        self._inner.write_record(record)
 
     def write_record(self, record: Record) -> None:
         """Write a record (alias for write)."""
         self.write(record)
 
     def close(self) -> None:
-        """Close the writer."""
+        """Flush and close the writer.
+
+        A file opened from a path is closed; a file object passed in by the
+        caller is flushed but left open. Safe to call more than once.
+        """
         self._inner.close()
 
+    @property
+    def closed(self) -> bool:
+        """Whether the writer has been closed."""
+        return self._inner.closed
+
+    @property
+    def stats(self) -> Stats:
+        """Live throughput counters: records written, bytes, write errors."""
+        return self._inner.stats
+
     def __enter__(self):
         """Context manager support."""
         return self
@@ -2067,6 +2223,106 @@ def _wrap_record(rust_record) -> Record:
     return wrapper
 
 
+def parse_batch_parallel(
+    boundaries: list[tuple[int, int]], buffer: bytes | bytearray
+) -> list[Record]:
+    """Parse record boundaries in parallel (see ``mrrc.rayon_parser_pool``)."""
+    return [_wrap_record(r) for r in _parse_batch_parallel(boundaries, buffer)]
+
+
+def parse_batch_parallel_limited(
+    boundaries: list[tuple[int, int]], buffer: bytes | bytearray, limit: int
+) -> list[Record]:
+    """Parse up to ``limit`` record boundaries in parallel."""
+    return [
+        _wrap_record(r)
+        for r in _parse_batch_parallel_limited(boundaries, buffer, limit)
+    ]
+
+
+def parse_batch_parallel_results(
+    boundaries: list[tuple[int, int]], buffer: bytes | bytearray
+) -> list[tuple[int, Record | Exception]]:
+    """Parse record boundaries in parallel, one result per record.
+
+    Each item is ``(index, value)`` where ``value`` is the ``Record`` or
+    the exception raised for that record; a bad record no longer fails
+    the whole batch.
+    """
+    return [
+        (i, _wrap_record(r) if isinstance(r, _Record) else r)
+        for i, r in _parse_batch_parallel_results(boundaries, buffer)
+    ]
+
+
+def parse_batch_parallel_map(
+    boundaries: list[tuple[int, int]],
+    buffer: bytes | bytearray,
+    func: Any,
+) -> list[tuple[int, Any]]:
+    """Parse record boundaries in parallel and apply ``func`` to each.
+
+    ``func`` receives a ``Record`` and runs on the worker threads. Each
+    item is ``(index, value)`` with ``func``'s return value, or the
+    exception raised while parsing or by ``func``.
+    """
+    return _parse_batch_parallel_map(
+        boundaries, buffer, lambda r: func(_wrap_record(r))
+    )
+
+
+class ProducerConsumerPipeline:
+    """Background-thread reading pipeline yielding ``Record`` objects.
+
+    A producer thread reads, scans and parses the file in parallel while the
+    caller iterates. See ``ProducerConsumerPipeline.from_file``.
+    """
+
+    def __init__(self, inner: _ProducerConsumerPipeline):
+        """Wrap a pipeline created by :meth:`from_file`."""
+        self._inner = inner
+
+    @classmethod
+    def from_file(
+        cls,
+        path: str,
+        buffer_size: int | None = None,
+        channel_capacity: int | None = None,
+    ) -> "ProducerConsumerPipeline":
+        """Start a pipeline reading the file at ``path``."""
+        return cls(
+            _ProducerConsumerPipeline.from_file(
+                path, buffer_size=buffer_size, channel_capacity=channel_capacity
+            )
+        )
+
+    def next(self) -> Record | None:
+        """Next record, blocking until one is ready; ``None`` at EOF."""
+        record = self._inner.next()
+        return None if record is None else _wrap_record(record)
+
+    def try_next(self) -> Record | None:
+        """Next record if one is ready now, else ``None``."""
+        record = self._inner.try_next()
+        return None if record is None else _wrap_record(record)
+
+    def __iter__(self):
+        """Iterate over records."""
+        return self
+
+    def __next__(self) -> Record:
+        """Next record; raises ``StopIteration`` at EOF."""
+        return _wrap_record(next(self._inner))
+
+    @property
+    def stats(self) -> Stats:
+        """Live throughput counters for the records handed out so far."""
+        return self._inner.stats
+
+    def __repr__(self) -> str:
+        return repr(self._inner)
+
+
 def json_to_record(json_str: str) -> Record:
     """Convert a MARC JSON string to a Record."""
     return _wrap_record(_json_to_record(json_str))
@@ -2231,6 +2487,9 @@ def read(path: str | Any, format: str | None = None):
         extension_map = {
             "mrc": "marc",
             "marc": "marc",
+            "xml": "marcxml",
+            "ndjson": "marcjson",
+            "jsonl": "marcjson",
         }
 
         format = extension_map.get(ext)
@@ -2245,6 +2504,9 @@ def read(path: str | Any, format: str | None = None):
     format = format.lower()
     format_aliases = {
         "mrc": "marc",
+        "xml": "marcxml",
+        "ndjson": "marcjson",
+        "jsonl": "marcjson",
     }
     format = format_aliases.get(format, format)
 
@@ -2257,6 +2519,13 @@ def read(path: str | Any, format: str | None = None):
         )
 
 
+_STREAMING_WRITERS = {
+    "marcxml": MARCXMLWriter,
+    "marcjson": MARCJSONWriter,
+    "mods": MODSWriter,
+}
+
+
 def write(records, path: str | Any, format: str | None = None) -> int:
     """Write MARC records to a file, auto-detecting format from extension.
 
@@ -2266,6 +2535,10 @@ def write(records, path: str | Any, format: str | None = None) -> int:
         format: Optional format override. If not specified, format is inferred
             from the file extension. Supported values:
             - "marc" or "mrc": ISO 2709 binary MARC
+            - "xml" or "marcxml": MARCXML collection (``.xml``)
+            - "marcjson", "ndjson" or "jsonl": one MARCJSON record per line
+              (``.ndjson``, ``.jsonl``)
+            - "mods": MODS collection
 
     Returns:
         The number of records written.
@@ -2294,6 +2567,9 @@ def write(records, path: str | Any, format: str | None = None) -> int:
         extension_map = {
             "mrc": "marc",
             "marc": "marc",
+            "xml": "marcxml",
+            "ndjson": "marcjson",
+            "jsonl": "marcjson",
         }
 
         format = extension_map.get(ext)
@@ -2308,6 +2584,9 @@ def write(records, path: str | Any, format: str | None = None) -> int:
     format = format.lower()
     format_aliases = {
         "mrc": "marc",
+        "xml": "marcxml",
+        "ndjson": "marcjson",
+        "jsonl": "marcjson",
     }
     format = format_aliases.get(format, format)
 
@@ -2320,9 +2599,15 @@ def write(records, path: str | Any, format: str | None = None) -> int:
                 writer.write(record)
                 count += 1
             writer.close()
+    elif format in _STREAMING_WRITERS:
+        with _STREAMING_WRITERS[format](path) as writer:
+            for record in records:
+                writer.write(record)
+                count += 1
     else:
         raise ValueError(
-            f"Unsupported format '{format}'. Supported formats: marc"
+            f"Unsupported format '{format}'. "
+            "Supported formats: marc, marcjson, marcxml, mods"
         )
 
     return count
@@ -2358,9 +2643,7 @@ def marc_to_bibframe(record, config: BibframeConfig | None = None) -> RdfGraph:
     """
     if config is None:
         config = BibframeConfig()
-    # Handle wrapped Record (get inner PyRecord)
-    inner_record = record._inner if hasattr(record, "_inner") else record
-    return _marc_to_bibframe(inner_record, config)
+    return _marc_to_bibframe(record, config)
 
 
 def bibframe_to_marc(graph: RdfGraph) -> "Record":
@@ -2389,11 +2672,7 @@ def bibframe_to_marc(graph: RdfGraph) -> "Record":
         >>> recovered = mrrc.bibframe_to_marc(graph)
         ```
     """
-    inner_record = _bibframe_to_marc(graph)
-    # Wrap in Python Record class
-    wrapped = Record.__new__(Record)
-    wrapped._inner = inner_record
-    return wrapped
+    return _wrap_record(_bibframe_to_marc(graph))
 
 
 def map_records(func, *files: str) -> None:
@@ -2444,6 +2723,7 @@ __all__ = [
     "MARC_XML_SCHEMA",
     "SUBFIELD_INDICATOR",
     # Core classes
+    "ArrowColumnStream",
     "AuthorityMARCReader",
     "AuthorityRecord",
     "BadSubfieldCodeWarning",
@@ -2462,8 +2742,11 @@ __all__ = [
     "HoldingsRecord",
     "Indicators",
     "Leader",
+    "MARCJSONWriter",
     "MARCReader",
     "MARCWriter",
+    "MARCXMLWriter",
+    "MODSWriter",
     # Exception hierarchy
     "MrrcException",
     "ProducerConsumerPipeline",
@@ -2474,6 +2757,8 @@ __all__ = [
     "RecordLeaderInvalid",
     "RecordLengthInvalid",
     "StaleFieldError",
+    "Stats",
+    "StatsSnapshot",
     "Subfield",
     "SubfieldPatternQuery",
     "SubfieldValueQuery",
@@ -2493,6 +2778,8 @@ __all__ = [
     # Functions
     "parse_batch_parallel",
     "parse_batch_parallel_limited",
+    "parse_batch_parallel_map",
+    "parse_batch_parallel_results",
     "parse_json_to_array",
     "parse_xml_to_array",
     # Format-agnostic helpers
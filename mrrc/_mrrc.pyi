@@ -1,6 +1,6 @@
 """Type stubs for the mrrc native extension module."""
 
-from collections.abc import Iterator
+from collections.abc import Callable, Iterator
 from typing import Any, final
 
 __version__: str
@@ -13,8 +13,11 @@ __all__ = [
     "HoldingsMARCReader",
     "HoldingsRecord",
     "Leader",
+    "MARCJSONWriter",
     "MARCReader",
     "MARCWriter",
+    "MARCXMLWriter",
+    "MODSWriter",
     "ProducerConsumerPipeline",
     "RdfGraph",
     "Record",
@@ -34,6 +37,8 @@ __all__ = [
     "mods_to_record",
     "parse_batch_parallel",
     "parse_batch_parallel_limited",
+    "parse_batch_parallel_map",
+    "parse_batch_parallel_results",
     "record_to_csv",
     "record_to_dublin_core",
     "record_to_dublin_core_xml",
@@ -51,6 +56,16 @@ __all__ = [
 # Core Data Types
 # =============================================================================
 
+# Filters accepted by the Record mutation methods.
+_FieldFilter = (
+    FieldQuery
+    | TagRangeQuery
+    | SubfieldPatternQuery
+    | SubfieldValueQuery
+    | dict[str, Any]
+    | Callable[[Field], bool]
+)
+
 @final
 class Subfield:
     """A subfield within a MARC field.
@@ -367,6 +382,31 @@ class Record:
         """Replace the control field value at (tag, occurrence). Returns
         False if no value exists at that position."""
         ...
+    def remove_fields_where(self, filter: _FieldFilter) -> list[Field]:
+        """Remove every data field matching ``filter`` and return them.
+
+        ``filter`` is a query object, a dict of ``FieldQuery`` criteria
+        (``tag``, ``indicator1``, ``indicator2``, ``has_subfield``,
+        ``has_subfields``) or a callable taking a ``Field``. Bumps
+        ``generation`` when anything was removed.
+        """
+        ...
+    def update_subfield_values(self, tag: str, code: str, value: str) -> None:
+        """Set every subfield ``code`` in fields tagged ``tag`` to ``value``."""
+        ...
+    def update_subfields_where(
+        self, filter: _FieldFilter, code: str, value: str
+    ) -> int:
+        """Set every subfield ``code`` in fields matching ``filter`` to
+        ``value``. Returns the number of fields that matched."""
+        ...
+    def update_fields_where(
+        self, filter: _FieldFilter, operation: Callable[[Field], Field | None]
+    ) -> int:
+        """Apply ``operation`` to a copy of every matching field; the
+        modified copy, or the ``Field`` returned, replaces it. The tag may
+        not change. Returns the number of fields that matched."""
+        ...
     def fields_by_indicator(
         self,
         tag: str,
@@ -410,6 +450,8 @@ class Record:
     def notes(self) -> list[str]: ...
     def publisher(self) -> str | None: ...
     def uniform_title(self) -> str | None: ...
+    def title_sort_key(self) -> str | None: ...
+    def uniform_title_sort_key(self) -> str | None: ...
     def sudoc(self) -> str | None: ...
     def issn_title(self) -> str | None: ...
     def issnl(self) -> str | None: ...
@@ -696,6 +738,42 @@ class MARCReader:
             RuntimeError: If the reader has been consumed.
         """
         ...
+    def __enter__(self) -> MARCReader: ...
+    def __exit__(
+        self, _exc_type: Any = None, _exc_val: Any = None, _exc_tb: Any = None
+    ) -> bool: ...
+    def close(self) -> None:
+        """Release the underlying source.
+
+        A file opened from a path is closed; a file object passed in by the
+        caller is left open. Safe to call multiple times.
+        """
+        ...
+    @property
+    def closed(self) -> bool:
+        """Whether the reader has been closed or exhausted."""
+        ...
+    @property
+    def stats(self) -> Stats:
+        """Live throughput counters: records read, bytes, parse errors."""
+        ...
+    def to_arrow(
+        self,
+        columns: list[str] | dict[str, str],
+        batch_size: int = 65536,
+        skip_errors: bool = False,
+    ) -> ArrowColumnStream:
+        """Extract columns from the remaining records into Arrow batches.
+
+        ``columns`` is a list of extraction expressions, each naming its own
+        column, or a dict mapping column names to expressions. Consumes the
+        reader.
+
+        Raises:
+            ValueError: If an expression is invalid, a column name repeats,
+                or ``batch_size`` is 0.
+        """
+        ...
     @property
     def last_chunk(self) -> bytes | None:
         """Bytes of the most recent record chunk read from the source.
@@ -708,6 +786,76 @@ class MARCReader:
         """
         ...
 
+@final
+class Stats:
+    """Live record, byte and error counters for a reader, writer or pipeline."""
+    @property
+    def records(self) -> int:
+        """Records processed so far."""
+        ...
+    @property
+    def bytes(self) -> int:
+        """Bytes processed so far."""
+        ...
+    @property
+    def errors(self) -> int:
+        """Records that failed so far."""
+        ...
+    @property
+    def elapsed(self) -> float:
+        """Seconds since the counters started."""
+        ...
+    def snapshot(self) -> StatsSnapshot:
+        """Freeze the current counters and rates.
+
+        Each call starts a new interval for ``current_rate``.
+        """
+        ...
+
+@final
+class StatsSnapshot:
+    """Counters and rates frozen at one moment."""
+    @property
+    def records(self) -> int: ...
+    @property
+    def bytes(self) -> int: ...
+    @property
+    def errors(self) -> int: ...
+    @property
+    def elapsed(self) -> float:
+        """Seconds since the counters started."""
+        ...
+    @property
+    def rate(self) -> float:
+        """Records per second over the whole run."""
+        ...
+    @property
+    def current_rate(self) -> float:
+        """Records per second since the previous snapshot."""
+        ...
+    @property
+    def per_record(self) -> float | None:
+        """Mean seconds per record, or ``None`` before the first record."""
+        ...
+
+@final
+class ArrowColumnStream:
+    """Record batches of extracted columns, exported via ``__arrow_c_stream__``.
+
+    Pass to ``pyarrow.table()``, ``polars.from_arrow()`` or any other
+    consumer of the Arrow PyCapsule interface. Every column is a nullable
+    UTF-8 string.
+    """
+
+    def __arrow_c_stream__(self, requested_schema: Any = None) -> Any: ...
+    @property
+    def column_names(self) -> list[str]: ...
+    @property
+    def num_rows(self) -> int: ...
+    @property
+    def num_batches(self) -> int: ...
+    def __repr__(self) -> str: ...
+
 @final
 class MARCWriter:
     """Writer for ISO 2709 binary MARC format.
@@ -743,9 +891,85 @@ class MARCWriter:
     def close(self) -> None:
         """Close the writer and flush the buffer.
 
-        This is automatically called when using the context manager.
+        A file opened from a path is closed; a file object passed in by the
+        caller is flushed but left open. This is automatically called when
+        using the context manager.
+
+        Raises:
+            IOError: If flushing fails
+        """
+        ...
+    @property
+    def stats(self) -> Stats:
+        """Live throughput counters: records written, bytes, write errors."""
+        ...
+    @property
+    def closed(self) -> bool:
+        """Whether the writer has been closed."""
+        ...
+
+class _StreamingWriter:
+    """Shared base of the streaming text-format writers.
+
+    Each accepts a file path (str or ``os.PathLike``) or a file object,
+    binary or text, and writes one record at a time.
+    """
+    def __enter__(self) -> _StreamingWriter: ...
+    def __exit__(
+        self, _exc_type: Any = None, _exc_val: Any = None, _exc_tb: Any = None
+    ) -> bool: ...
+    def write_record(self, record: Record) -> None:
+        """Serialize and write one record."""
+        ...
+    def write(self, record: Record) -> None: ...
+    def close(self) -> None:
+        """Write any closing element and flush.
+
+        A file opened from a path is closed; a file object passed in by the
+        caller is flushed but left open. Safe to call more than once.
         """
         ...
+    @property
+    def records_written(self) -> int:
+        """Number of records written."""
+        ...
+    @property
+    def stats(self) -> Stats:
+        """Live throughput counters: records written and write errors."""
+        ...
+    @property
+    def closed(self) -> bool:
+        """Whether the writer has been closed."""
+        ...
+
+@final
+class MARCXMLWriter(_StreamingWriter):
+    """Streaming MARCXML writer producing a ``<collection>`` document."""
+    def __new__(
+        cls,
+        target: Any,
+        *,
+        indent: int | None = None,
+        single_line: bool = False,
+        namespace_prefix: str | None = None,
+    ) -> MARCXMLWriter: ...
+
+@final
+class MARCJSONWriter(_StreamingWriter):
+    """Streaming MARCJSON writer producing one JSON record per line."""
+    def __new__(cls, target: Any) -> MARCJSONWriter: ...
+
+@final
+class MODSWriter(_StreamingWriter):
+    """Streaming MODS writer producing a ``<modsCollection>`` document."""
+    def __new__(
+        cls,
+        target: Any,
+        *,
+        indent: int | None = None,
+        single_line: bool = False,
+        namespace_prefix: str | None = None,
+    ) -> MODSWriter: ...
 
 @final
 class AuthorityMARCReader:
@@ -761,8 +985,15 @@ class AuthorityMARCReader:
     def __next__(self) -> AuthorityRecord: ...
     def __enter__(self) -> AuthorityMARCReader: ...
     def __exit__(
-        self, _exc_type: Any, _exc_val: Any, _exc_tb: Any
+        self, _exc_type: Any = None, _exc_val: Any = None, _exc_tb: Any = None
     ) -> bool: ...
+    def close(self) -> None:
+        """Release the underlying source (caller-owned file objects stay open)."""
+        ...
+    @property
+    def closed(self) -> bool:
+        """Whether the reader has been closed or exhausted."""
+        ...
     def read_record(self) -> AuthorityRecord | None: ...
 
 @final
@@ -779,8 +1010,15 @@ class HoldingsMARCReader:
     def __next__(self) -> HoldingsRecord: ...
     def __enter__(self) -> HoldingsMARCReader: ...
     def __exit__(
-        self, _exc_type: Any, _exc_val: Any, _exc_tb: Any
+        self, _exc_type: Any = None, _exc_val: Any = None, _exc_tb: Any = None
     ) -> bool: ...
+    def close(self) -> None:
+        """Release the underlying source (caller-owned file objects stay open)."""
+        ...
+    @property
+    def closed(self) -> bool:
+        """Whether the reader has been closed or exhausted."""
+        ...
     def read_record(self) -> HoldingsRecord | None: ...
 
 # =============================================================================
@@ -844,7 +1082,8 @@ class BibframeConfig:
         """Set the output format for RDF serialization.
 
         Args:
-            format: One of: "rdf-xml", "jsonld", "turtle", "ntriples"
+            format: One of: "rdf-xml", "jsonld", "turtle", "ntriples", "nquads",
+                "trig"
 
         Raises:
             ValueError: If format is not recognized
@@ -861,6 +1100,27 @@ class BibframeConfig:
     def authority_linking(self) -> bool:
         """Get the current authority linking setting."""
         ...
+    def set_subject_schemes(self, enabled: bool) -> None:
+        """Enable or disable subject vocabulary (bf:source) URIs."""
+        ...
+    @property
+    def subject_schemes(self) -> bool:
+        """Get the current subject vocabulary setting."""
+        ...
+    def set_reproduction_instances(self, enabled: bool) -> None:
+        """Enable or disable converting 533 to reproduction Instances."""
+        ...
+    @property
+    def reproduction_instances(self) -> bool:
+        """Get the current reproduction Instance setting."""
+        ...
+    def set_multi_instance(self, enabled: bool) -> None:
+        """Enable or disable one Instance of the Work per 776 physical form."""
+        ...
+    @property
+    def multi_instance(self) -> bool:
+        """Get the current multi-Instance setting."""
+        ...
     def set_include_bflc(self, enabled: bool) -> None:
         """Enable or disable BFLC extensions."""
         ...
@@ -868,6 +1128,13 @@ class BibframeConfig:
     def include_bflc(self) -> bool:
         """Get the current BFLC extension setting."""
         ...
+    def set_named_graphs(self, enabled: bool) -> None:
+        """Enable or disable a named graph per converted record."""
+        ...
+    @property
+    def named_graphs(self) -> bool:
+        """Get the current named graph setting."""
+        ...
     def set_strict(self, enabled: bool) -> None:
         """Enable or disable strict validation mode."""
         ...
@@ -912,7 +1179,8 @@ class RdfGraph:
         """Serialize the graph to a string in the specified format.
 
         Args:
-            format: One of: "rdf-xml", "jsonld", "turtle", "ntriples"
+            format: One of: "rdf-xml", "jsonld", "turtle", "ntriples", "nquads",
+                "trig"
 
         Returns:
             The serialized RDF as a string
@@ -927,7 +1195,8 @@ class RdfGraph:
 
         Args:
             data: The RDF data as a string
-            format: One of: "rdf-xml", "jsonld", "turtle", "ntriples"
+            format: One of: "rdf-xml", "jsonld", "turtle", "ntriples", "nquads",
+                "trig"
 
         Returns:
             A new RdfGraph instance
@@ -939,6 +1208,25 @@ class RdfGraph:
     def triples(self) -> list[tuple[str, str, str]]:
         """Get all triples as a list of (subject, predicate, object) tuples."""
         ...
+    def quads(self) -> list[tuple[str, str, str, str | None]]:
+        """Get all triples with their graph name (None for the default
+        graph)."""
+        ...
+    def graph_names(self) -> list[str]:
+        """Get the distinct named graphs, in order of first use."""
+        ...
+    def isomorphic_to(self, other: RdfGraph) -> bool:
+        """Check whether two graphs are equal up to blank node labels."""
+        ...
+    def diff(
+        self, other: RdfGraph
+    ) -> tuple[
+        list[tuple[str, str, str, str | None]],
+        list[tuple[str, str, str, str | None]],
+    ]:
+        """Return (removed, added) quads after canonical blank node
+        relabeling; both are empty for isomorphic graphs."""
+        ...
 
 def marc_to_bibframe(record: Record, config: BibframeConfig) -> RdfGraph:
     """Convert a MARC record to a BIBFRAME RDF graph.
@@ -994,6 +1282,10 @@ class ProducerConsumerPipeline:
     ) -> ProducerConsumerPipeline: ...
     def next(self) -> Record | None: ...
     def try_next(self) -> Record | None: ...
+    @property
+    def stats(self) -> Stats:
+        """Live throughput counters for the records handed out so far."""
+        ...
 
 def parse_batch_parallel(
     boundaries: list[tuple[int, int]],
@@ -1004,3 +1296,12 @@ def parse_batch_parallel_limited(
     buffer: bytes | bytearray,
     limit: int,
 ) -> list[Record]: ...
+def parse_batch_parallel_results(
+    boundaries: list[tuple[int, int]],
+    buffer: bytes | bytearray,
+) -> list[tuple[int, Record | Exception]]: ...
+def parse_batch_parallel_map(
+    boundaries: list[tuple[int, int]],
+    buffer: bytes | bytearray,
+    func: Callable[[Record], Any],
+) -> list[tuple[int, Any]]: ...
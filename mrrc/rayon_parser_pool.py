@@ -38,21 +38,39 @@ RAYON_NUM_THREADS=4 python my_script.py
 ```
 
 By default, Rayon will use all available CPU cores.
+
+# Partial Results
+
+`parse_batch_parallel` raises on the first malformed record. To keep the
+good records, use `parse_batch_parallel_results`, which returns an
+`(index, record_or_exception)` tuple per boundary, or
+`parse_batch_parallel_map`, which also applies a function to each record on
+the worker threads:
+
+```python
+from mrrc.rayon_parser_pool import parse_batch_parallel_map
+
+rows = parse_batch_parallel_map(boundaries, buffer, lambda r: r.to_json())
+good = [value for _, value in rows if not isinstance(value, Exception)]
+```
 """
 
-from ._mrrc import (
-    parse_batch_parallel as _parse_batch_parallel,
-)
-from ._mrrc import (
-    parse_batch_parallel_limited as _parse_batch_parallel_limited,
-)
+from . import Record
+from . import parse_batch_parallel as _parse_batch_parallel
+from . import parse_batch_parallel_limited as _parse_batch_parallel_limited
+from . import parse_batch_parallel_map, parse_batch_parallel_results
 
-__all__ = ["parse_batch_parallel", "parse_batch_parallel_limited"]
+__all__ = [
+    "parse_batch_parallel",
+    "parse_batch_parallel_limited",
+    "parse_batch_parallel_map",
+    "parse_batch_parallel_results",
+]
 
 
 def parse_batch_parallel(
     boundaries: list[tuple[int, int]], buffer: bytes | bytearray
-) -> list:
+) -> list[Record]:
     """Parse a batch of MARC record boundaries in parallel using Rayon.
 
     Given a buffer and a list of record boundaries (offset, length pairs),
@@ -112,7 +130,7 @@ def parse_batch_parallel(
 
 def parse_batch_parallel_limited(
     boundaries: list[tuple[int, int]], buffer: bytes | bytearray, limit: int
-) -> list:
+) -> list[Record]:
     """Parse a limited batch of MARC records in parallel.
 
     Like parse_batch_parallel(), but limits the number of records to parse.
@@ -0,0 +1,96 @@
+#!/usr/bin/env python3
+"""
+Compare Criterion results against the recorded baselines in
+benches/baselines.json.
+
+Run the benches first (`cargo bench --bench marc_benchmarks ...`), then:
+
+    python3 scripts/bench_compare.py                 # report, exit 1 on regression
+    python3 scripts/bench_compare.py --threshold 15  # tolerate up to +15%
+    python3 scripts/bench_compare.py --update        # record current results
+
+Each benchmark's median from target/criterion/<name>/new/estimates.json is
+compared with its baseline. Baselines are wall-clock numbers from one
+machine (recorded in the file), so compare like with like: re-record them
+with --update on your own machine before using the check as a gate.
+"""
+
+import argparse
+import json
+import sys
+from datetime import date
+from pathlib import Path
+
+ROOT = Path(__file__).resolve().parent.parent
+BASELINES = ROOT / "benches" / "baselines.json"
+CRITERION = ROOT / "target" / "criterion"
+
+
+def current_medians() -> dict[str, float]:
+    """Median time in nanoseconds for every benchmark Criterion has run."""
+    medians = {}
+    for estimates in sorted(CRITERION.glob("*/new/estimates.json")):
+        with open(estimates) as f:
+            medians[estimates.parent.parent.name] = json.load(f)["median"]["point_estimate"]
+    return medians
+
+
+def format_ns(ns: float) -> str:
+    for unit, scale in (("s", 1e9), ("ms", 1e6), ("µs", 1e3)):
+        if ns >= scale:
+            return f"{ns / scale:.2f} {unit}"
+    return f"{ns:.0f} ns"
+
+
+def main() -> int:
+    parser = argparse.ArgumentParser(description=__doc__.split("\n\n")[0])
+    parser.add_argument(
+        "--threshold",
+        type=float,
+        default=10.0,
+        help="percent slowdown that counts as a regression (default: 10)",
+    )
+    parser.add_argument(
+        "--update", action="store_true", help="write current results as the new baselines"
+    )
+    parser.add_argument("--machine", help="machine description stored with --update")
+    args = parser.parse_args()
+
+    medians = current_medians()
+    if not medians:
+        print(f"No Criterion results under {CRITERION}; run `cargo bench` first.")
+        return 2
+
+    if args.update:
+        baselines = json.loads(BASELINES.read_text()) if BASELINES.exists() else {}
+        baselines["recorded"] = date.today().isoformat()
+        if args.machine:
+            baselines["machine"] = args.machine
+        baselines.setdefault("benchmarks", {}).update(
+            {name: round(ns) for name, ns in medians.items()}
+        )
+        BASELINES.write_text(json.dumps(baselines, indent=2, sort_keys=True) + "\n")
+        print(f"Recorded {len(medians)} baselines in {BASELINES.relative_to(ROOT)}")
+        return 0
+
+    baselines = json.loads(BASELINES.read_text())
+    print(f"Baselines: {baselines.get('machine', 'unknown machine')}, {baselines.get('recorded')}")
+    regressions = []
+    for name, baseline in sorted(baselines["benchmarks"].items()):
+        if name not in medians:
+            continue
+        change = (medians[name] - baseline) / baseline * 100
+        flag = ""
+        if change > args.threshold:
+            flag = "  REGRESSION"
+            regressions.append(name)
+        print(f"{name:<40} {format_ns(baseline):>12} -> {format_ns(medians[name]):>12}  {change:+6.1f}%{flag}")
+
+    if regressions:
+        print(f"\n{len(regressions)} benchmark(s) slower than baseline by more than {args.threshold}%")
+        return 1
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
@@ -0,0 +1,108 @@
+// Arrow export of extracted columns (`MARCReader.to_arrow`)
+//
+// Records are parsed and their columns extracted entirely in Rust, straight
+// into Arrow buffers; no per-record Python objects are created. The result
+// is handed to Python through the Arrow PyCapsule interface
+// (`__arrow_c_stream__`), which pyarrow, polars and other consumers import
+// without copying.
+
+use std::sync::Arc;
+
+use arrow_array::ffi_stream::FFI_ArrowArrayStream;
+use arrow_array::{RecordBatch, RecordBatchIterator};
+use arrow_schema::SchemaRef;
+use pyo3::prelude::*;
+use pyo3::types::{PyCapsule, PyDict};
+
+/// Name the Arrow `PyCapsule` interface requires for stream capsules.
+const STREAM_CAPSULE_NAME: &std::ffi::CStr = c"arrow_array_stream";
+
+/// Build the column set from a list of expressions or a `{name: expression}`
+/// dict.
+pub fn columns_from_py(columns: &Bound<'_, PyAny>) -> PyResult<mrrc::arrow::ArrowColumns> {
+    let result = if let Ok(named) = columns.cast::<PyDict>() {
+        let pairs = named
+            .iter()
+            .map(|(name, expression)| {
+                Ok((name.extract::<String>()?, expression.extract::<String>()?))
+            })
+            .collect::<PyResult<Vec<(String, String)>>>()?;
+        mrrc::arrow::ArrowColumns::named(pairs)
+    } else {
+        let expressions: Vec<String> = columns.extract()?;
+        mrrc::arrow::ArrowColumns::new(&expressions)
+    };
+    result.map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+}
+
+/// Record batches of extracted columns, exported through the Arrow C stream
+/// interface.
+///
+/// Pass to `pyarrow.table()`, `pyarrow.RecordBatchReader.from_stream()`,
+/// `polars.from_arrow()` or any other consumer of `__arrow_c_stream__`.
+/// The stream can be exported more than once; each export shares the same
+/// buffers.
+#[pyclass(name = "ArrowColumnStream")]
+#[derive(Debug)]
+pub struct PyArrowColumnStream {
+    schema: SchemaRef,
+    batches: Vec<RecordBatch>,
+}
+
+impl PyArrowColumnStream {
+    pub fn new(schema: SchemaRef, batches: Vec<RecordBatch>) -> Self {
+        PyArrowColumnStream { schema, batches }
+    }
+}
+
+#[pymethods]
+impl PyArrowColumnStream {
+    /// Export as an `ArrowArrayStream` `PyCapsule`
+    ///
+    /// `requested_schema` is accepted for protocol conformance and ignored;
+    /// every column is a nullable UTF-8 string.
+    #[pyo3(signature = (requested_schema=None))]
+    fn __arrow_c_stream__<'py>(
+        &self,
+        py: Python<'py>,
+        requested_schema: Option<Py<PyAny>>,
+    ) -> PyResult<Bound<'py, PyCapsule>> {
+        let _ = requested_schema;
+        let reader = RecordBatchIterator::new(
+            self.batches.clone().into_iter().map(Ok),
+            Arc::clone(&self.schema),
+        );
+        let stream = FFI_ArrowArrayStream::new(Box::new(reader));
+        PyCapsule::new_with_value(py, stream, STREAM_CAPSULE_NAME)
+    }
+
+    /// Column names, in order
+    #[getter]
+    fn column_names(&self) -> Vec<String> {
+        self.schema
+            .fields()
+            .iter()
+            .map(|f| f.name().clone())
+            .collect()
+    }
+
+    /// Total number of rows (records)
+    #[getter]
+    fn num_rows(&self) -> usize {
+        self.batches.iter().map(RecordBatch::num_rows).sum()
+    }
+
+    /// Number of record batches
+    #[getter]
+    fn num_batches(&self) -> usize {
+        self.batches.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<ArrowColumnStream columns={} rows={}>",
+            self.schema.fields().len(),
+            self.num_rows()
+        )
+    }
+}
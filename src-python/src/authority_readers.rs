@@ -164,18 +164,36 @@ impl PyAuthorityMARCReader {
         }
     }
 
+    /// Release the underlying source
+    ///
+    /// File paths opened by the reader are closed; file objects passed in
+    /// by the caller are left open. Reading after `close()` stops
+    /// iteration. Safe to call multiple times (idempotent).
+    pub fn close(&mut self) {
+        self.backend = None;
+    }
+
+    /// Whether the reader has been closed or exhausted
+    #[getter]
+    pub fn closed(&self) -> bool {
+        self.backend.is_none()
+    }
+
+    /// Context manager support: enter
     pub fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
         slf
     }
 
+    /// Context manager support: exit (closes the reader)
+    #[pyo3(signature = (_exc_type=None, _exc_val=None, _exc_tb=None))]
     pub fn __exit__(
-        mut slf: PyRefMut<'_, Self>,
-        _exc_type: &Bound<'_, PyAny>,
-        _exc_val: &Bound<'_, PyAny>,
-        _exc_tb: &Bound<'_, PyAny>,
-    ) -> PyResult<bool> {
-        slf.backend = None;
-        Ok(false)
+        &mut self,
+        _exc_type: Option<Py<PyAny>>,
+        _exc_val: Option<Py<PyAny>>,
+        _exc_tb: Option<Py<PyAny>>,
+    ) -> bool {
+        self.close();
+        false
     }
 
     pub fn __repr__(&self) -> String {
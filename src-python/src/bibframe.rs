@@ -7,9 +7,9 @@
 // - bibframe_to_marc for BIBFRAME → MARC conversion
 
 use crate::error::marc_error_to_py_err;
-use crate::wrappers::PyRecord;
+use crate::wrappers::{PyRecord, extract_record};
 use mrrc::bibframe::{
-    BibframeConfig, RdfFormat, RdfGraph, RdfNode, bibframe_to_marc, marc_to_bibframe,
+    BibframeConfig, RdfFormat, RdfGraph, RdfNode, RdfTriple, bibframe_to_marc, marc_to_bibframe,
 };
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
@@ -72,7 +72,7 @@ impl PyBibframeConfig {
     /// Set the output format for RDF serialization.
     ///
     /// # Arguments
-    /// * `format` - One of: "rdf-xml", "jsonld", "turtle", "ntriples"
+    /// * `format` - One of: "rdf-xml", "jsonld", "turtle", "ntriples", "nquads", "trig"
     ///
     /// # Raises
     /// `ValueError`: If format is not recognized
@@ -92,6 +92,8 @@ impl PyBibframeConfig {
             RdfFormat::JsonLd => "jsonld",
             RdfFormat::Turtle => "turtle",
             RdfFormat::NTriples => "ntriples",
+            RdfFormat::NQuads => "nquads",
+            RdfFormat::TriG => "trig",
             // RdfFormat is #[non_exhaustive]: a format added in the core
             // crate before this getter learns its token reports as unknown
             // rather than failing to compile.
@@ -116,6 +118,57 @@ impl PyBibframeConfig {
         self.inner.link_authorities
     }
 
+    /// Enable or disable subject vocabulary URIs.
+    ///
+    /// When True (default), 6XX subjects get a `bf:source` URI for the
+    /// scheme named by the second indicator or $2 (LCSH, LC/NAF, `MeSH`, FAST, ...).
+    ///
+    /// # Arguments
+    /// * `enabled` - Whether to record subject vocabularies
+    fn set_subject_schemes(&mut self, enabled: bool) {
+        self.inner.subject_schemes = enabled;
+    }
+
+    /// Get the current subject vocabulary setting.
+    #[getter]
+    fn subject_schemes(&self) -> bool {
+        self.inner.subject_schemes
+    }
+
+    /// Enable or disable reproduction Instances.
+    ///
+    /// When True, each 533 becomes a second Instance of the Work linked to
+    /// the original with `bf:hasReproduction`. Off by default.
+    ///
+    /// # Arguments
+    /// * `enabled` - Whether to convert 533 to reproduction Instances
+    fn set_reproduction_instances(&mut self, enabled: bool) {
+        self.inner.reproduction_instances = enabled;
+    }
+
+    /// Get the current reproduction Instance setting.
+    #[getter]
+    fn reproduction_instances(&self) -> bool {
+        self.inner.reproduction_instances
+    }
+
+    /// Enable or disable one Instance per 776 physical form.
+    ///
+    /// When True, 776 entries become Instances of the Work as well as
+    /// `bf:otherPhysicalFormat` of the record's Instance. Off by default.
+    ///
+    /// # Arguments
+    /// * `enabled` - Whether to link 776 Instances to the Work
+    fn set_multi_instance(&mut self, enabled: bool) {
+        self.inner.multi_instance = enabled;
+    }
+
+    /// Get the current multi-Instance setting.
+    #[getter]
+    fn multi_instance(&self) -> bool {
+        self.inner.multi_instance
+    }
+
     /// Enable or disable BFLC extensions.
     ///
     /// BFLC extensions are required for practical LOC compatibility.
@@ -132,6 +185,23 @@ impl PyBibframeConfig {
         self.inner.include_bflc
     }
 
+    /// Enable or disable a named graph per converted record.
+    ///
+    /// The graph is `{base_uri}graph/{id}` when a base URI is set,
+    /// otherwise a blank node. Serialize with "nquads" or "trig" to keep it.
+    ///
+    /// # Arguments
+    /// * `enabled` - Whether to put each record in its own graph
+    fn set_named_graphs(&mut self, enabled: bool) {
+        self.inner.named_graphs = enabled;
+    }
+
+    /// Get the current named graph setting.
+    #[getter]
+    fn named_graphs(&self) -> bool {
+        self.inner.named_graphs
+    }
+
     /// Enable or disable strict validation mode.
     ///
     /// When True, questionable data causes errors.
@@ -228,7 +298,7 @@ impl PyRdfGraph {
     /// Serialize the graph to a string in the specified format.
     ///
     /// # Arguments
-    /// * `format` - One of: "rdf-xml", "jsonld", "turtle", "ntriples"
+    /// * `format` - One of: "rdf-xml", "jsonld", "turtle", "ntriples", "nquads", "trig"
     ///
     /// # Returns
     /// The serialized RDF as a string
@@ -246,7 +316,7 @@ impl PyRdfGraph {
     ///
     /// # Arguments
     /// * `data` - The RDF data as a string
-    /// * `format` - One of: "rdf-xml", "jsonld", "turtle", "ntriples"
+    /// * `format` - One of: "rdf-xml", "jsonld", "turtle", "ntriples", "nquads", "trig"
     ///
     /// # Returns
     /// A new `RdfGraph` instance
@@ -277,6 +347,49 @@ impl PyRdfGraph {
             .collect()
     }
 
+    /// Get all triples with their graph names, as (subject, predicate,
+    /// object, graph) tuples; graph is None for the default graph.
+    fn quads(&self) -> Vec<QuadTuple> {
+        self.inner
+            .quads()
+            .map(|(t, graph)| quad_tuple(t, graph))
+            .collect()
+    }
+
+    /// Check whether two graphs are the same up to blank node labels.
+    ///
+    /// Use this instead of comparing serialized output, whose blank node
+    /// labels change from one conversion to the next.
+    fn isomorphic_to(&self, other: &PyRdfGraph) -> bool {
+        self.inner.isomorphic_to(&other.inner)
+    }
+
+    /// Compare with another graph after relabeling blank nodes canonically.
+    ///
+    /// # Returns
+    /// A `(removed, added)` pair of quad lists, as from `quads()`: triples
+    /// only in this graph, and triples only in `other`. Both are empty for
+    /// isomorphic graphs.
+    fn diff(&self, other: &PyRdfGraph) -> (Vec<QuadTuple>, Vec<QuadTuple>) {
+        let diff = self.inner.diff(&other.inner);
+        let tuples = |quads: Vec<(RdfTriple, Option<RdfNode>)>| {
+            quads
+                .iter()
+                .map(|(t, graph)| quad_tuple(t, graph.as_ref()))
+                .collect()
+        };
+        (tuples(diff.removed), tuples(diff.added))
+    }
+
+    /// Get the distinct named graphs in the graph, in order of first use.
+    fn graph_names(&self) -> Vec<String> {
+        self.inner
+            .graph_names()
+            .into_iter()
+            .map(node_to_string)
+            .collect()
+    }
+
     fn __repr__(&self) -> String {
         format!("RdfGraph({} triples)", self.inner.len())
     }
@@ -311,9 +424,13 @@ impl PyRdfGraph {
 /// ```
 #[pyfunction]
 #[pyo3(name = "marc_to_bibframe")]
-pub fn py_marc_to_bibframe(record: &PyRecord, config: &PyBibframeConfig) -> PyRdfGraph {
-    let graph = marc_to_bibframe(&record.inner, &config.inner);
-    PyRdfGraph { inner: graph }
+pub fn py_marc_to_bibframe(
+    record: &Bound<'_, PyAny>,
+    config: &PyBibframeConfig,
+) -> PyResult<PyRdfGraph> {
+    let record = extract_record(record)?;
+    let graph = marc_to_bibframe(&record, &config.inner);
+    Ok(PyRdfGraph { inner: graph })
 }
 
 /// Convert a BIBFRAME RDF graph to a MARC record.
@@ -356,12 +473,26 @@ fn parse_rdf_format(format: &str) -> PyResult<RdfFormat> {
         "jsonld" | "json-ld" | "application/ld+json" => Ok(RdfFormat::JsonLd),
         "turtle" | "ttl" | "text/turtle" => Ok(RdfFormat::Turtle),
         "ntriples" | "nt" | "n-triples" | "application/n-triples" => Ok(RdfFormat::NTriples),
+        "nquads" | "nq" | "n-quads" | "application/n-quads" => Ok(RdfFormat::NQuads),
+        "trig" | "application/trig" => Ok(RdfFormat::TriG),
         _ => Err(PyValueError::new_err(format!(
-            "Unknown RDF format: '{format}'. Use one of: rdf-xml, jsonld, turtle, ntriples"
+            "Unknown RDF format: '{format}'. Use one of: rdf-xml, jsonld, turtle, ntriples, nquads, trig"
         ))),
     }
 }
 
+/// A triple and its graph name as Python sees them.
+type QuadTuple = (String, String, String, Option<String>);
+
+fn quad_tuple(triple: &RdfTriple, graph: Option<&RdfNode>) -> QuadTuple {
+    (
+        node_to_string(&triple.subject),
+        triple.predicate.clone(),
+        node_to_string(&triple.object),
+        graph.map(node_to_string),
+    )
+}
+
 /// Convert an `RdfNode` to a string representation.
 fn node_to_string(node: &RdfNode) -> String {
     match node {
@@ -443,6 +574,8 @@ mod tests {
             parse_rdf_format("ntriples"),
             Ok(RdfFormat::NTriples)
         ));
+        assert!(matches!(parse_rdf_format("nq"), Ok(RdfFormat::NQuads)));
+        assert!(matches!(parse_rdf_format("TriG"), Ok(RdfFormat::TriG)));
         assert!(parse_rdf_format("invalid").is_err());
     }
 
@@ -451,10 +584,13 @@ mod tests {
         let mut record = Record::new(make_test_leader());
         record.add_control_field("001".to_string(), "test123".to_string());
 
-        let py_record = PyRecord::from(record);
-        let config = PyBibframeConfig::new();
+        Python::initialize();
+        Python::attach(|py| {
+            let py_record = Bound::new(py, PyRecord::from(record)).unwrap().into_any();
+            let config = PyBibframeConfig::new();
 
-        let graph = py_marc_to_bibframe(&py_record, &config);
-        assert!(!graph.is_empty());
+            let graph = py_marc_to_bibframe(&py_record, &config).unwrap();
+            assert!(!graph.is_empty());
+        });
     }
 }
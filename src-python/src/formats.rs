@@ -9,31 +9,11 @@
 // - CSV export
 
 use crate::error::marc_error_to_py_err;
-use crate::wrappers::PyRecord;
+use crate::wrappers::{PyRecord, extract_record};
 use mrrc::iso2709::ParseContext;
-use mrrc::{Record, csv, dublin_core, json, marcjson, marcxml, mods};
-use pyo3::exceptions::PyTypeError;
+use mrrc::{csv, dublin_core, json, marcjson, marcxml, mods};
 use pyo3::prelude::*;
 
-/// Extract a Rust `Record` from either a raw `PyRecord` or a wrapped Python `Record`
-/// (which stores the inner `PyRecord` as `self._inner`).
-fn extract_record(record: &pyo3::Bound<'_, pyo3::PyAny>) -> PyResult<Record> {
-    // Try to extract as PyRecord directly
-    if let Ok(py_record) = record.extract::<pyo3::PyRef<'_, PyRecord>>() {
-        return Ok(py_record.inner.clone());
-    }
-
-    // Otherwise, try to get the _inner attribute (wrapped Record)
-    if let Ok(inner) = record.getattr("_inner")
-        && let Ok(py_record) = inner.extract::<pyo3::PyRef<'_, PyRecord>>()
-    {
-        return Ok(py_record.inner.clone());
-    }
-
-    Err(PyTypeError::new_err(
-        "argument 'record': expected a Record instance",
-    ))
-}
 use serde_json::Value;
 
 /// Convert a MARC record to JSON.
@@ -414,26 +394,10 @@ pub fn record_to_csv(record: &pyo3::Bound<'_, pyo3::PyAny>) -> PyResult<String>
 /// ```
 #[pyfunction]
 pub fn records_to_csv(records: &pyo3::Bound<'_, pyo3::types::PyList>) -> PyResult<String> {
-    let mut rust_records = Vec::new();
-    for item in records.iter() {
-        // Try PyRecord first
-        if let Ok(record) = item.extract::<pyo3::PyRef<'_, PyRecord>>() {
-            rust_records.push(record.inner.clone());
-            continue;
-        }
-
-        // Try wrapped Record with _inner attribute
-        if let Ok(inner) = item.getattr("_inner")
-            && let Ok(record) = inner.extract::<pyo3::PyRef<'_, PyRecord>>()
-        {
-            rust_records.push(record.inner.clone());
-            continue;
-        }
-
-        return Err(pyo3::exceptions::PyTypeError::new_err(
-            "All items must be PyRecord or wrapped Record",
-        ));
-    }
+    let rust_records = records
+        .iter()
+        .map(|item| extract_record(&item))
+        .collect::<PyResult<Vec<_>>>()?;
     csv::records_to_csv(&rust_records).map_err(marc_error_to_py_err)
 }
 
@@ -461,26 +425,10 @@ pub fn records_to_csv_filtered(
     records: &pyo3::Bound<'_, pyo3::types::PyList>,
     filter_fn: pyo3::Py<pyo3::PyAny>,
 ) -> PyResult<String> {
-    let mut rust_records = Vec::new();
-    for item in records.iter() {
-        // Try PyRecord first
-        if let Ok(record) = item.extract::<pyo3::PyRef<'_, PyRecord>>() {
-            rust_records.push(record.inner.clone());
-            continue;
-        }
-
-        // Try wrapped Record with _inner attribute
-        if let Ok(inner) = item.getattr("_inner")
-            && let Ok(record) = inner.extract::<pyo3::PyRef<'_, PyRecord>>()
-        {
-            rust_records.push(record.inner.clone());
-            continue;
-        }
-
-        return Err(pyo3::exceptions::PyTypeError::new_err(
-            "All items must be PyRecord or wrapped Record",
-        ));
-    }
+    let rust_records = records
+        .iter()
+        .map(|item| extract_record(&item))
+        .collect::<PyResult<Vec<_>>>()?;
 
     // Create a closure that calls the Python filter function
     Python::attach(|py| {
@@ -16,6 +16,7 @@
 #![allow(clippy::unnecessary_wraps)]
 #![allow(clippy::needless_pass_by_value)]
 
+mod arrow_export;
 mod authority_readers;
 mod backend;
 mod batched_reader;
@@ -31,9 +32,12 @@ mod query;
 mod rayon_parser_pool_wrapper;
 mod reader_helpers;
 mod readers;
+mod stats;
+mod stream_writers;
 mod wrappers;
 mod writers;
 
+use arrow_export::PyArrowColumnStream;
 use authority_readers::PyAuthorityMARCReader;
 use bibframe::{PyBibframeConfig, PyRdfGraph};
 use boundary_scanner_wrapper::PyRecordBoundaryScanner;
@@ -41,8 +45,13 @@ use holdings_readers::PyHoldingsMARCReader;
 use producer_consumer_pipeline_wrapper::PyProducerConsumerPipeline;
 use pyo3::prelude::*;
 use query::{PyFieldQuery, PySubfieldPatternQuery, PySubfieldValueQuery, PyTagRangeQuery};
-use rayon_parser_pool_wrapper::{parse_batch_parallel, parse_batch_parallel_limited};
+use rayon_parser_pool_wrapper::{
+    parse_batch_parallel, parse_batch_parallel_limited, parse_batch_parallel_map,
+    parse_batch_parallel_results,
+};
 use readers::PyMARCReader;
+use stats::{PyStats, PyStatsSnapshot};
+use stream_writers::{PyMARCJSONWriter, PyMARCXMLWriter, PyMODSWriter};
 use wrappers::{PyAuthorityRecord, PyField, PyHoldingsRecord, PyLeader, PyRecord, PySubfield};
 use writers::PyMARCWriter;
 
@@ -59,8 +68,14 @@ fn _mrrc(_py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyAuthorityMARCReader>()?;
     m.add_class::<PyHoldingsMARCReader>()?;
     m.add_class::<PyMARCWriter>()?;
+    m.add_class::<PyMARCXMLWriter>()?;
+    m.add_class::<PyMARCJSONWriter>()?;
+    m.add_class::<PyMODSWriter>()?;
     m.add_class::<PyRecordBoundaryScanner>()?;
     m.add_class::<PyProducerConsumerPipeline>()?;
+    m.add_class::<PyArrowColumnStream>()?;
+    m.add_class::<PyStats>()?;
+    m.add_class::<PyStatsSnapshot>()?;
 
     // Query DSL classes
     m.add_class::<PyFieldQuery>()?;
@@ -95,6 +110,8 @@ fn _mrrc(_py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
     // Rayon parser pool functions
     m.add_function(wrap_pyfunction!(parse_batch_parallel, m)?)?;
     m.add_function(wrap_pyfunction!(parse_batch_parallel_limited, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_batch_parallel_results, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_batch_parallel_map, m)?)?;
 
     m.add(
         "__doc__",
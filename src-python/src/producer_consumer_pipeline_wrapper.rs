@@ -3,6 +3,7 @@
 //! Exposes [`ProducerConsumerPipeline`] as a Python class, enabling high-performance
 //! batch reading with backpressure management from Python code.
 
+use crate::stats::PyStats;
 use crate::wrappers::PyRecord;
 use mrrc::producer_consumer_pipeline::{PipelineConfig, ProducerConsumerPipeline};
 use pyo3::exceptions::PyStopIteration;
@@ -38,6 +39,8 @@ use pyo3::prelude::*;
 #[derive(Debug)]
 pub struct PyProducerConsumerPipeline {
     inner: Option<ProducerConsumerPipeline>,
+    /// Shares the pipeline's counters; kept so they stay readable after close.
+    stats: mrrc::Stats,
 }
 
 #[pymethods]
@@ -89,6 +92,7 @@ impl PyProducerConsumerPipeline {
             .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
 
         Ok(PyProducerConsumerPipeline {
+            stats: pipeline.stats().clone(),
             inner: Some(pipeline),
         })
     }
@@ -210,6 +214,12 @@ impl PyProducerConsumerPipeline {
         }
     }
 
+    /// Throughput counters for the records handed out (records, bytes)
+    #[getter]
+    pub fn stats(&self) -> PyStats {
+        PyStats::from(self.stats.clone())
+    }
+
     /// Representation for debugging.
     pub fn __repr__(&self) -> String {
         format!(
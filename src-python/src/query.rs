@@ -542,3 +542,112 @@ impl PySubfieldValueQuery {
         self.__repr__()
     }
 }
+
+/// A field filter passed to the record mutation methods.
+///
+/// Accepts any of the query objects above, a dict of `FieldQuery`
+/// criteria (`tag`, `indicator1`, `indicator2`, `has_subfield`,
+/// `has_subfields`), or a callable taking a `Field` and returning a truthy
+/// value. Query objects and dicts are evaluated entirely in Rust.
+pub enum FieldFilter<'py> {
+    Query(FieldQuery),
+    TagRange(TagRangeQuery),
+    Pattern(SubfieldPatternQuery),
+    Value(SubfieldValueQuery),
+    Callable(Bound<'py, PyAny>),
+}
+
+impl<'py> FieldFilter<'py> {
+    /// Interpret `filter` as a query object, criteria dict or callable.
+    pub fn extract(filter: &Bound<'py, PyAny>) -> PyResult<Self> {
+        if let Ok(query) = filter.extract::<PyRef<'_, PyFieldQuery>>() {
+            return Ok(FieldFilter::Query(query.inner.clone()));
+        }
+        if let Ok(query) = filter.extract::<PyRef<'_, PyTagRangeQuery>>() {
+            return Ok(FieldFilter::TagRange(query.inner.clone()));
+        }
+        if let Ok(query) = filter.extract::<PyRef<'_, PySubfieldPatternQuery>>() {
+            return Ok(FieldFilter::Pattern(query.inner.clone()));
+        }
+        if let Ok(query) = filter.extract::<PyRef<'_, PySubfieldValueQuery>>() {
+            return Ok(FieldFilter::Value(query.inner.clone()));
+        }
+        if let Ok(criteria) = filter.cast::<pyo3::types::PyDict>() {
+            return query_from_dict(criteria).map(FieldFilter::Query);
+        }
+        if filter.is_callable() {
+            return Ok(FieldFilter::Callable(filter.clone()));
+        }
+        Err(pyo3::exceptions::PyTypeError::new_err(format!(
+            "expected a query, dict or callable filter, got {}",
+            filter.get_type().name()?
+        )))
+    }
+
+    /// Whether `field` passes the filter; errors raised by a callable
+    /// propagate.
+    pub fn matches(&self, field: &mrrc::Field) -> PyResult<bool> {
+        match self {
+            FieldFilter::Query(query) => Ok(query.matches(field)),
+            FieldFilter::TagRange(query) => Ok(query.matches(field)),
+            FieldFilter::Pattern(query) => Ok(query.matches(field)),
+            FieldFilter::Value(query) => Ok(query.matches(field)),
+            FieldFilter::Callable(predicate) => predicate
+                .call1((crate::wrappers::PyField {
+                    inner: field.clone(),
+                },))?
+                .is_truthy(),
+        }
+    }
+}
+
+/// Build a `FieldQuery` from a dict of criteria.
+fn query_from_dict(criteria: &Bound<'_, pyo3::types::PyDict>) -> PyResult<FieldQuery> {
+    fn code(value: &str) -> PyResult<char> {
+        let mut chars = value.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "expected a single character, got {value:?}"
+            ))),
+        }
+    }
+
+    let mut query = FieldQuery::new();
+    for (key, value) in criteria.iter() {
+        let key: String = key.extract()?;
+        query = match key.as_str() {
+            "tag" => query.tag(value.extract::<String>()?),
+            "indicator1" => query.indicator1(
+                value
+                    .extract::<Option<String>>()?
+                    .as_deref()
+                    .map(code)
+                    .transpose()?,
+            ),
+            "indicator2" => query.indicator2(
+                value
+                    .extract::<Option<String>>()?
+                    .as_deref()
+                    .map(code)
+                    .transpose()?,
+            ),
+            "has_subfield" => query.has_subfield(code(&value.extract::<String>()?)?),
+            "has_subfields" => {
+                let codes = value
+                    .extract::<Vec<String>>()?
+                    .iter()
+                    .map(|c| code(c))
+                    .collect::<PyResult<Vec<char>>>()?;
+                query.has_subfields(&codes)
+            },
+            other => {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "unknown filter key {other:?}; expected tag, indicator1, indicator2, \
+                     has_subfield or has_subfields"
+                )));
+            },
+        };
+    }
+    Ok(query)
+}
@@ -1,8 +1,10 @@
 //! `PyO3` bindings for the Rayon parser pool.
 //!
 //! Exposes [`parse_batch_parallel`] as a Python function, allowing
-//! parallel MARC record parsing from Python code.
+//! parallel MARC record parsing from Python code, along with the
+//! per-record result variants.
 
+use crate::error::marc_error_to_py_err;
 use crate::wrappers::PyRecord;
 use mrrc::rayon_parser_pool;
 use pyo3::prelude::*;
@@ -114,3 +116,76 @@ pub fn parse_batch_parallel_limited(
     // Convert to PyRecord (GIL re-acquired after detach)
     Ok(records.into_iter().map(PyRecord::from).collect())
 }
+
+/// Parse a batch of records in parallel, one result per record.
+///
+/// A malformed record does not fail the batch: each boundary yields an
+/// `(index, value)` tuple, in boundary order, whose value is the parsed
+/// `Record` or the `MrrcException` instance describing why that record
+/// failed.
+///
+/// # Example
+///
+/// ```python
+/// from mrrc.rayon_parser_pool import parse_batch_parallel_results
+///
+/// for index, result in parse_batch_parallel_results(boundaries, buffer):
+///     if isinstance(result, Exception):
+///         print(f"record {index}: {result}")
+/// ```
+#[pyfunction]
+pub fn parse_batch_parallel_results(
+    py: Python<'_>,
+    boundaries: Vec<(usize, usize)>,
+    buffer: Vec<u8>,
+) -> PyResult<Vec<(usize, Py<PyAny>)>> {
+    // Owned buffer for the detached parse; see `parse_batch_parallel`
+    let results =
+        py.detach(|| rayon_parser_pool::parse_batch_parallel_results(&boundaries, &buffer));
+    results
+        .into_iter()
+        .map(|(index, result)| {
+            let value = match result {
+                Ok(record) => Py::new(py, PyRecord::from(record))?.into_any(),
+                Err(e) => marc_error_to_py_err(e).into_value(py).into_any(),
+            };
+            Ok((index, value))
+        })
+        .collect()
+}
+
+/// Parse a batch of records in parallel and call `func` on each one from
+/// the worker threads.
+///
+/// Returns `(index, value)` tuples like `parse_batch_parallel_results`,
+/// where `value` is what `func` returned for the record, or the exception
+/// raised by the parse or by `func`. Parsing runs without the GIL; `func`
+/// takes it for each call, so it only runs concurrently on a free-threaded
+/// interpreter.
+#[pyfunction]
+// the closure's error type is mrrc's `MarcError`, as the Rust API requires
+#[allow(clippy::result_large_err)]
+pub fn parse_batch_parallel_map(
+    py: Python<'_>,
+    boundaries: Vec<(usize, usize)>,
+    buffer: Vec<u8>,
+    func: Py<PyAny>,
+) -> Vec<(usize, Py<PyAny>)> {
+    let results = py.detach(|| {
+        rayon_parser_pool::parse_batch_parallel_map(&boundaries, &buffer, |record| {
+            Ok(Python::attach(|py| {
+                func.call1(py, (PyRecord::from(record),))
+            }))
+        })
+    });
+    results
+        .into_iter()
+        .map(|(index, result)| {
+            let value = match result.map_err(marc_error_to_py_err).and_then(|r| r) {
+                Ok(value) => value,
+                Err(e) => e.into_value(py).into_any(),
+            };
+            (index, value)
+        })
+        .collect()
+}
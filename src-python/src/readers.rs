@@ -5,8 +5,10 @@
 // 2. Parse the whole batch in a single GIL release (py.detach)
 // 3. Serve parsed records from a queue, one per __next__ (GIL held)
 
+use crate::arrow_export::{PyArrowColumnStream, columns_from_py};
 use crate::backend::ReaderBackend;
 use crate::batched_reader::{BatchedReader, RecordOutcome};
+use crate::stats::PyStats;
 use crate::wrappers::PyRecord;
 use pyo3::prelude::*;
 
@@ -85,6 +87,8 @@ pub struct PyMARCReader {
     /// not a byte copy: the same allocation is borrowed by the parser
     /// (via `parse_record_from_shared_bytes`) and retained here.
     last_chunk: Option<std::sync::Arc<Vec<u8>>>,
+    /// Records yielded, their chunk bytes, and parse failures.
+    stats: mrrc::Stats,
 }
 
 #[pymethods]
@@ -142,6 +146,7 @@ impl PyMARCReader {
             accumulated_errors: 0,
             records_yielded: 0,
             last_chunk: None,
+            stats: mrrc::Stats::new(),
         })
     }
 
@@ -242,6 +247,60 @@ impl PyMARCReader {
         }
     }
 
+    /// Extract columns from the remaining records into Arrow record batches
+    ///
+    /// `columns` is a list of extraction expressions (`"001"`, `"245$a"`,
+    /// `"008/35-37"`), each naming its own column, or a dict mapping column
+    /// names to expressions. Each cell holds the first value the expression
+    /// finds, or null. Records are parsed and extracted in Rust with the GIL
+    /// released per batch; no Python record objects are created. Consumes
+    /// the reader.
+    ///
+    /// With `skip_errors`, records that fail to parse are left out instead
+    /// of raising.
+    #[pyo3(name = "to_arrow", signature = (columns, batch_size=65536, skip_errors=false))]
+    fn extract_arrow(
+        &mut self,
+        py: Python<'_>,
+        columns: &Bound<'_, PyAny>,
+        batch_size: usize,
+        skip_errors: bool,
+    ) -> PyResult<PyArrowColumnStream> {
+        if batch_size == 0 {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "batch_size must be at least 1",
+            ));
+        }
+        let columns = columns_from_py(columns)?;
+        let mut builder = columns.builder();
+        let mut batches = Vec::new();
+        let to_py = |e: mrrc::MarcError| pyo3::exceptions::PyValueError::new_err(e.to_string());
+
+        while let Some(outcome) = self.reader.as_mut().and_then(|r| r.next_record(py)) {
+            if skip_errors && matches!(outcome, RecordOutcome::ParseFailed { .. }) {
+                continue;
+            }
+            let Some(record) = self.take_outcome(outcome)? else {
+                continue;
+            };
+            builder.push(&record);
+            if builder.len() >= batch_size {
+                batches.push(builder.finish().map_err(to_py)?);
+            }
+        }
+        self.reader = None;
+        if !builder.is_empty() || batches.is_empty() {
+            batches.push(builder.finish().map_err(to_py)?);
+        }
+        Ok(PyArrowColumnStream::new(columns.schema(), batches))
+    }
+
+    /// Throughput counters for this reader (records, bytes, errors)
+    #[getter]
+    fn stats(&self) -> PyStats {
+        PyStats::from(self.stats.clone())
+    }
+
     /// Return the backend type: "`rust_file`", "cursor", or "`python_file`"
     #[getter]
     fn backend_type(&self) -> PyResult<String> {
@@ -251,6 +310,38 @@ impl PyMARCReader {
         }
     }
 
+    /// Release the underlying source
+    ///
+    /// File paths opened by the reader are closed; file objects passed in
+    /// by the caller are left open. Iterating after `close()` stops
+    /// immediately. Safe to call multiple times (idempotent).
+    pub fn close(&mut self) {
+        self.reader = None;
+    }
+
+    /// Whether the reader has been closed or exhausted
+    #[getter]
+    fn closed(&self) -> bool {
+        self.reader.is_none()
+    }
+
+    /// Context manager support: enter
+    fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    /// Context manager support: exit (closes the reader)
+    #[pyo3(signature = (_exc_type=None, _exc_val=None, _exc_tb=None))]
+    fn __exit__(
+        &mut self,
+        _exc_type: Option<Py<PyAny>>,
+        _exc_val: Option<Py<PyAny>>,
+        _exc_tb: Option<Py<PyAny>>,
+    ) -> bool {
+        self.close();
+        false
+    }
+
     fn __repr__(&self) -> String {
         if self.reader.is_some() {
             "<MARCReader active>".to_string()
@@ -269,6 +360,12 @@ impl PyMARCReader {
     /// outcome that carries bytes (success or failure), matching the prior
     /// per-record stash; a source-read error leaves it unchanged.
     fn apply_outcome(&mut self, outcome: RecordOutcome) -> PyResult<Option<PyRecord>> {
+        Ok(self.take_outcome(outcome)?.map(PyRecord::from))
+    }
+
+    /// [`apply_outcome`](Self::apply_outcome) without the Python wrapper,
+    /// for callers that consume records in Rust (`to_arrow`).
+    fn take_outcome(&mut self, outcome: RecordOutcome) -> PyResult<Option<mrrc::Record>> {
         match outcome {
             RecordOutcome::Parsed { bytes, record } => {
                 self.last_chunk = Some(bytes);
@@ -280,10 +377,14 @@ impl PyMARCReader {
                     return Err(crate::error::marc_error_to_py_err(*e));
                 }
                 self.records_yielded = self.records_yielded.saturating_add(1);
-                Ok(Some(PyRecord::from(record)))
+                if let Some(bytes) = &self.last_chunk {
+                    self.stats.add_record(bytes.len() as u64);
+                }
+                Ok(Some(record))
             },
             RecordOutcome::ParseFailed { bytes, error } => {
                 self.last_chunk = Some(bytes);
+                self.stats.add_error();
                 Err(crate::error::marc_error_to_py_err(*error))
             },
             RecordOutcome::ParseReturnedNone { bytes } => {
@@ -0,0 +1,133 @@
+// Python access to the shared throughput counters (`mrrc::Stats`)
+//
+// Readers, writers and the pipeline each expose a `stats` property
+// returning a `Stats` object that shares counters with the Rust side, so
+// it keeps updating as the stage runs. `snapshot()` freezes the current
+// values into a `StatsSnapshot`.
+
+use mrrc::{Stats, StatsSnapshot};
+use pyo3::prelude::*;
+
+/// Live record, byte and error counters for a reader, writer or pipeline
+///
+/// ```python
+/// reader = mrrc.MARCReader("in.mrc")
+/// for record in reader:
+///     ...
+/// print(reader.stats.records, reader.stats.snapshot().rate)
+/// ```
+#[pyclass(name = "Stats", skip_from_py_object)]
+#[derive(Debug, Clone)]
+pub struct PyStats {
+    inner: Stats,
+}
+
+impl From<Stats> for PyStats {
+    fn from(inner: Stats) -> Self {
+        PyStats { inner }
+    }
+}
+
+#[pymethods]
+impl PyStats {
+    /// Freeze the current counters and rates
+    ///
+    /// Each call starts a new interval for `current_rate`.
+    fn snapshot(&self) -> PyStatsSnapshot {
+        PyStatsSnapshot(self.inner.snapshot())
+    }
+
+    /// Records processed so far
+    #[getter]
+    fn records(&self) -> u64 {
+        self.inner.records()
+    }
+
+    /// Bytes processed so far
+    #[getter]
+    fn bytes(&self) -> u64 {
+        self.inner.bytes()
+    }
+
+    /// Records that failed so far
+    #[getter]
+    fn errors(&self) -> u64 {
+        self.inner.errors()
+    }
+
+    /// Seconds since the counters started
+    #[getter]
+    fn elapsed(&self) -> f64 {
+        self.inner.elapsed().as_secs_f64()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Stats(records={}, bytes={}, errors={})",
+            self.inner.records(),
+            self.inner.bytes(),
+            self.inner.errors()
+        )
+    }
+}
+
+/// Counters and rates frozen at one moment
+#[pyclass(name = "StatsSnapshot", frozen, skip_from_py_object)]
+#[derive(Debug, Clone, Copy)]
+pub struct PyStatsSnapshot(StatsSnapshot);
+
+#[pymethods]
+impl PyStatsSnapshot {
+    /// Records processed
+    #[getter]
+    fn records(&self) -> u64 {
+        self.0.records
+    }
+
+    /// Bytes processed
+    #[getter]
+    fn bytes(&self) -> u64 {
+        self.0.bytes
+    }
+
+    /// Records that failed
+    #[getter]
+    fn errors(&self) -> u64 {
+        self.0.errors
+    }
+
+    /// Seconds since the counters started
+    #[getter]
+    fn elapsed(&self) -> f64 {
+        self.0.elapsed.as_secs_f64()
+    }
+
+    /// Records per second over the whole run
+    #[getter]
+    fn rate(&self) -> f64 {
+        self.0.rate
+    }
+
+    /// Records per second since the previous snapshot
+    #[getter]
+    fn current_rate(&self) -> f64 {
+        self.0.current_rate
+    }
+
+    /// Mean seconds per record, or `None` before the first record
+    #[getter]
+    fn per_record(&self) -> Option<f64> {
+        self.0.per_record().map(|d| d.as_secs_f64())
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "StatsSnapshot(records={}, bytes={}, errors={}, elapsed={:.3}, rate={:.1})",
+            self.0.records,
+            self.0.bytes,
+            self.0.errors,
+            self.0.elapsed.as_secs_f64(),
+            self.0.rate
+        )
+    }
+}
@@ -0,0 +1,354 @@
+// Python streaming writers for MARCXML, newline-delimited MARCJSON and MODS
+//
+// Each writer serializes one record at a time through the Rust
+// `FormatWriter` for its format, so converting a large file from Python
+// never builds the whole output in memory. Output goes to a file opened in
+// Rust (path argument) or to a Python file object, binary or text.
+
+use crate::error::marc_error_to_py_err;
+use crate::stats::PyStats;
+use crate::wrappers::extract_record;
+use mrrc::MarcError;
+use mrrc::formats::FormatWriter;
+use mrrc::marcjson::MarcjsonWriter;
+use mrrc::marcxml::MarcxmlWriter;
+use mrrc::mods::ModsWriter;
+use mrrc::xml_style::{XmlNamespace, XmlStyle};
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyString};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+/// A Python file object used as an `io::Write`.
+///
+/// Text files (`io.TextIOBase`) are given `str`, anything else `bytes`.
+/// The format writers only ever write whole UTF-8 strings, so each chunk
+/// decodes on its own.
+struct PyFileSink {
+    file: Py<PyAny>,
+    text: bool,
+}
+
+impl Write for PyFileSink {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        Python::attach(|py| {
+            let file = self.file.bind(py);
+            let result = if self.text {
+                let text = std::str::from_utf8(buf)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                file.call_method1("write", (PyString::new(py, text),))
+            } else {
+                file.call_method1("write", (PyBytes::new(py, buf),))
+            };
+            result.map(|_| buf.len()).map_err(io::Error::other)
+        })
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Python::attach(|py| {
+            let file = self.file.bind(py);
+            if file.hasattr("flush").map_err(io::Error::other)? {
+                file.call_method0("flush").map_err(io::Error::other)?;
+            }
+            Ok(())
+        })
+    }
+}
+
+/// Where a streaming writer sends its output.
+enum Output {
+    File(BufWriter<File>),
+    Python(BufWriter<PyFileSink>),
+}
+
+impl std::fmt::Debug for Output {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Output::File(_) => f.write_str("Output::File"),
+            Output::Python(_) => f.write_str("Output::Python"),
+        }
+    }
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Output::File(w) => w.write(buf),
+            Output::Python(w) => w.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Output::File(w) => w.flush(),
+            Output::Python(w) => w.flush(),
+        }
+    }
+}
+
+impl Output {
+    /// Open `target`: a str or `os.PathLike` path, or a file object with
+    /// a `write()` method.
+    fn open(target: &Bound<'_, PyAny>) -> PyResult<Self> {
+        let path = if let Ok(path) = target.extract::<String>() {
+            Some(path)
+        } else if target.hasattr("__fspath__")? {
+            Some(target.call_method0("__fspath__")?.extract::<String>()?)
+        } else {
+            None
+        };
+        if let Some(path) = path {
+            let file = File::create(&path).map_err(|e| {
+                pyo3::exceptions::PyIOError::new_err(format!(
+                    "Failed to open file '{path}' for writing: {e}"
+                ))
+            })?;
+            return Ok(Output::File(BufWriter::new(file)));
+        }
+        if target.getattr("write").is_ok_and(|w| w.is_callable()) {
+            let py = target.py();
+            let text_base = py.import("io")?.getattr("TextIOBase")?;
+            let sink = PyFileSink {
+                file: target.clone().unbind(),
+                text: target.is_instance(&text_base)?,
+            };
+            return Ok(Output::Python(BufWriter::new(sink)));
+        }
+        Err(pyo3::exceptions::PyTypeError::new_err(
+            "writer argument must be a file path (str/Path) or file-like object with .write() method",
+        ))
+    }
+}
+
+/// Raise the Python exception behind a failed file-object write as is;
+/// anything else goes through the usual `MarcError` mapping.
+fn writer_error(err: MarcError) -> PyErr {
+    if let MarcError::IoError { cause, .. } = &err
+        && let Some(py_err) = cause.get_ref().and_then(|e| e.downcast_ref::<PyErr>())
+    {
+        return Python::attach(|py| py_err.clone_ref(py));
+    }
+    marc_error_to_py_err(err)
+}
+
+/// Shared implementation of the streaming writers.
+///
+/// Subclasses only choose the format; `write()`, `close()` and the
+/// context-manager protocol live here.
+#[pyclass(name = "_StreamingWriter", subclass)]
+// wraps a boxed FormatWriter over an Output that holds Python objects
+#[allow(missing_debug_implementations)]
+pub struct PyStreamingWriter {
+    inner: Option<Box<dyn FormatWriter + Send + Sync>>,
+    records_written: usize,
+    stats: mrrc::Stats,
+}
+
+impl PyStreamingWriter {
+    fn new(inner: Box<dyn FormatWriter + Send + Sync>) -> Self {
+        PyStreamingWriter {
+            inner: Some(inner),
+            records_written: 0,
+            stats: mrrc::Stats::new(),
+        }
+    }
+}
+
+#[pymethods]
+impl PyStreamingWriter {
+    /// Serialize and write one record
+    ///
+    /// Serialization runs with the GIL released when writing to a path.
+    pub fn write_record(&mut self, py: Python<'_>, record: &Bound<'_, PyAny>) -> PyResult<()> {
+        let Some(inner) = self.inner.as_mut() else {
+            return Err(pyo3::exceptions::PyRuntimeError::new_err(
+                "Writer has been closed",
+            ));
+        };
+        let record = extract_record(record)?;
+        if let Err(e) = py.detach(|| inner.write_record(&record).map_err(Box::new)) {
+            self.stats.add_error();
+            return Err(writer_error(*e));
+        }
+        self.stats.add_record(0);
+        self.records_written += 1;
+        Ok(())
+    }
+
+    /// Alias for `write_record` (for pymarc compatibility)
+    pub fn write(&mut self, py: Python<'_>, record: &Bound<'_, PyAny>) -> PyResult<()> {
+        self.write_record(py, record)
+    }
+
+    /// Finish the document and flush
+    ///
+    /// Writes any closing element, then closes a file opened from a path;
+    /// a file object passed in is flushed but left open. Safe to call
+    /// more than once.
+    pub fn close(&mut self, py: Python<'_>) -> PyResult<()> {
+        match self.inner.take() {
+            Some(mut inner) => py
+                .detach(|| inner.finish().map_err(Box::new))
+                .map_err(|e| writer_error(*e)),
+            None => Ok(()),
+        }
+    }
+
+    /// Number of records written
+    #[getter]
+    fn records_written(&self) -> usize {
+        self.records_written
+    }
+
+    /// Throughput counters for this writer (records and errors; bytes are
+    /// not tracked)
+    #[getter]
+    fn stats(&self) -> PyStats {
+        PyStats::from(self.stats.clone())
+    }
+
+    /// Whether the writer has been closed
+    #[getter]
+    fn closed(&self) -> bool {
+        self.inner.is_none()
+    }
+
+    /// Context manager support: enter
+    fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    /// Context manager support: exit
+    #[pyo3(signature = (_exc_type=None, _exc_val=None, _exc_tb=None))]
+    fn __exit__(
+        &mut self,
+        py: Python<'_>,
+        _exc_type: Option<Py<PyAny>>,
+        _exc_val: Option<Py<PyAny>>,
+        _exc_tb: Option<Py<PyAny>>,
+    ) -> PyResult<bool> {
+        self.close(py)?;
+        Ok(false)
+    }
+}
+
+/// The XML layout selected by the writers' keyword arguments, or `None`
+/// for the format's usual layout.
+///
+/// `namespace_prefix=""` means unprefixed elements in a default namespace.
+fn xml_style(
+    indent: Option<usize>,
+    single_line: bool,
+    namespace_prefix: Option<String>,
+) -> Option<XmlStyle> {
+    if indent.is_none() && !single_line && namespace_prefix.is_none() {
+        return None;
+    }
+    let style = if single_line {
+        XmlStyle::single_line()
+    } else {
+        XmlStyle::pretty(indent.unwrap_or(2))
+    };
+    Some(match namespace_prefix {
+        None => style,
+        Some(prefix) if prefix.is_empty() => style.with_namespace(XmlNamespace::Default),
+        Some(prefix) => style.with_namespace(XmlNamespace::Prefixed(prefix)),
+    })
+}
+
+/// Streaming MARCXML writer producing a `<collection>` document
+///
+/// ```python
+/// with mrrc.MARCXMLWriter("out.xml") as writer:
+///     for record in mrrc.MARCReader("in.mrc"):
+///         writer.write(record)
+/// ```
+#[pyclass(name = "MARCXMLWriter", extends = PyStreamingWriter)]
+#[allow(missing_debug_implementations)]
+pub struct PyMARCXMLWriter;
+
+#[pymethods]
+impl PyMARCXMLWriter {
+    /// Create a writer over a file path (str/Path) or file object
+    ///
+    /// `indent` pretty-prints with that many spaces per level,
+    /// `single_line=True` removes all whitespace between elements, and
+    /// `namespace_prefix` qualifies every element (`""` for a default
+    /// namespace).
+    #[new]
+    #[pyo3(signature = (target, *, indent=None, single_line=false, namespace_prefix=None))]
+    fn new(
+        target: &Bound<'_, PyAny>,
+        indent: Option<usize>,
+        single_line: bool,
+        namespace_prefix: Option<String>,
+    ) -> PyResult<PyClassInitializer<Self>> {
+        let mut writer = MarcxmlWriter::new(Output::open(target)?);
+        if let Some(style) = xml_style(indent, single_line, namespace_prefix) {
+            writer = writer.with_style(style);
+        }
+        Ok(
+            PyClassInitializer::from(PyStreamingWriter::new(Box::new(writer)))
+                .add_subclass(PyMARCXMLWriter),
+        )
+    }
+}
+
+/// Streaming MARCJSON writer producing one JSON record per line (NDJSON)
+///
+/// ```python
+/// with mrrc.MARCJSONWriter("out.ndjson") as writer:
+///     for record in mrrc.MARCReader("in.mrc"):
+///         writer.write(record)
+/// ```
+#[pyclass(name = "MARCJSONWriter", extends = PyStreamingWriter)]
+#[allow(missing_debug_implementations)]
+pub struct PyMARCJSONWriter;
+
+#[pymethods]
+impl PyMARCJSONWriter {
+    /// Create a writer over a file path (str/Path) or file object
+    #[new]
+    fn new(target: &Bound<'_, PyAny>) -> PyResult<PyClassInitializer<Self>> {
+        let writer = MarcjsonWriter::new(Output::open(target)?);
+        Ok(
+            PyClassInitializer::from(PyStreamingWriter::new(Box::new(writer)))
+                .add_subclass(PyMARCJSONWriter),
+        )
+    }
+}
+
+/// Streaming MODS writer producing a `<modsCollection>` document
+///
+/// ```python
+/// with mrrc.MODSWriter("out.xml") as writer:
+///     for record in mrrc.MARCReader("in.mrc"):
+///         writer.write(record)
+/// ```
+#[pyclass(name = "MODSWriter", extends = PyStreamingWriter)]
+#[allow(missing_debug_implementations)]
+pub struct PyMODSWriter;
+
+#[pymethods]
+impl PyMODSWriter {
+    /// Create a writer over a file path (str/Path) or file object
+    ///
+    /// Layout keywords are as for `MARCXMLWriter`.
+    #[new]
+    #[pyo3(signature = (target, *, indent=None, single_line=false, namespace_prefix=None))]
+    fn new(
+        target: &Bound<'_, PyAny>,
+        indent: Option<usize>,
+        single_line: bool,
+        namespace_prefix: Option<String>,
+    ) -> PyResult<PyClassInitializer<Self>> {
+        let mut writer = ModsWriter::new(Output::open(target)?);
+        if let Some(style) = xml_style(indent, single_line, namespace_prefix) {
+            writer = writer.with_style(style);
+        }
+        Ok(
+            PyClassInitializer::from(PyStreamingWriter::new(Box::new(writer)))
+                .add_subclass(PyMODSWriter),
+        )
+    }
+}
@@ -657,6 +657,35 @@ impl From<Record> for PyRecord {
     }
 }
 
+/// Extract a Rust `Record` from any Python record object.
+///
+/// This is the single entry point every binding that takes a record goes
+/// through, so a record is accepted the same way whether it came from a
+/// reader, a format conversion or a constructor. Accepts the raw `_mrrc.Record`
+/// (`PyRecord`) or the public `mrrc.Record` wrapper, which holds a `PyRecord`
+/// as `_inner`; for the wrapper, pending leader edits are synced first via
+/// its `_sync_leader()` so the copy matches what Python sees.
+pub fn extract_record(record: &Bound<'_, PyAny>) -> PyResult<Record> {
+    if let Ok(py_record) = record.extract::<PyRef<'_, PyRecord>>() {
+        return Ok(py_record.inner.clone());
+    }
+
+    if let Ok(inner) = record.getattr("_inner")
+        && inner.is_instance_of::<PyRecord>()
+    {
+        if let Ok(sync) = record.getattr("_sync_leader") {
+            sync.call0()?;
+        }
+        let py_record = inner.extract::<PyRef<'_, PyRecord>>()?;
+        return Ok(py_record.inner.clone());
+    }
+
+    Err(pyo3::exceptions::PyTypeError::new_err(format!(
+        "expected a Record instance, got {}",
+        record.get_type().name()?
+    )))
+}
+
 #[pymethods]
 impl PyRecord {
     /// Create a new Record with a given Leader
@@ -913,6 +942,105 @@ impl PyRecord {
         }
     }
 
+    /// Remove every data field matching `filter` and return them
+    ///
+    /// `filter` is a query object (`FieldQuery`, `TagRangeQuery`,
+    /// `SubfieldPatternQuery`, `SubfieldValueQuery`), a dict of
+    /// `FieldQuery` criteria such as `{"tag": "650", "indicator2": "7"}`,
+    /// or a callable taking a `Field` and returning a bool. Bumps
+    /// `generation` when anything was removed.
+    pub fn remove_fields_where(&mut self, filter: &Bound<'_, PyAny>) -> PyResult<Vec<PyField>> {
+        let filter = crate::query::FieldFilter::extract(filter)?;
+        let mut doomed = std::collections::HashSet::new();
+        for (i, field) in self.inner.fields().enumerate() {
+            if filter.matches(field)? {
+                doomed.insert(i);
+            }
+        }
+        if doomed.is_empty() {
+            return Ok(Vec::new());
+        }
+        // `remove_fields_where` visits fields in the same order as `fields()`
+        let position = std::cell::Cell::new(0);
+        let removed = self.inner.remove_fields_where(|_| {
+            let i = position.replace(position.get() + 1);
+            doomed.contains(&i)
+        });
+        self.generation = self.generation.wrapping_add(1);
+        Ok(removed.into_iter().map(|f| PyField { inner: f }).collect())
+    }
+
+    /// Set every subfield `code` in fields tagged `tag` to `value`
+    pub fn update_subfield_values(&mut self, tag: &str, code: &str, value: &str) -> PyResult<()> {
+        self.inner
+            .update_subfield_values(tag, subfield_code(code)?, value);
+        Ok(())
+    }
+
+    /// Set every subfield `code` in fields matching `filter` to `value`
+    ///
+    /// `filter` takes the same forms as in `remove_fields_where`. Returns
+    /// the number of fields that matched.
+    pub fn update_subfields_where(
+        &mut self,
+        filter: &Bound<'_, PyAny>,
+        code: &str,
+        value: &str,
+    ) -> PyResult<usize> {
+        let filter = crate::query::FieldFilter::extract(filter)?;
+        let code = subfield_code(code)?;
+        let mut matched = 0;
+        for field in self.inner.fields_mut() {
+            if filter.matches(field)? {
+                field.update_subfield_values(code, value);
+                matched += 1;
+            }
+        }
+        Ok(matched)
+    }
+
+    /// Apply `operation` to every data field matching `filter`
+    ///
+    /// `operation` is called with a copy of each matching field; the
+    /// copy, as modified, replaces the field, or the `Field` it returns
+    /// does. The tag may not change. Returns the number of fields that
+    /// matched.
+    pub fn update_fields_where(
+        &mut self,
+        py: Python<'_>,
+        filter: &Bound<'_, PyAny>,
+        operation: &Bound<'_, PyAny>,
+    ) -> PyResult<usize> {
+        let filter = crate::query::FieldFilter::extract(filter)?;
+        let mut matched = 0;
+        for field in self.inner.fields_mut() {
+            if !filter.matches(field)? {
+                continue;
+            }
+            let copy = Py::new(
+                py,
+                PyField {
+                    inner: field.clone(),
+                },
+            )?;
+            let returned = operation.call1((copy.clone_ref(py),))?;
+            let updated = if returned.is_none() {
+                copy.borrow(py).inner.clone()
+            } else {
+                returned.extract::<PyRef<'_, PyField>>()?.inner.clone()
+            };
+            if updated.tag != field.tag {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "update_fields_where cannot change a field's tag ({} to {})",
+                    field.tag, updated.tag
+                )));
+            }
+            *field = updated;
+            matched += 1;
+        }
+        Ok(matched)
+    }
+
     /// Get title from 245 field (first subfield $a)
     pub fn title(&self) -> Option<String> {
         self.inner.title().map(std::string::ToString::to_string)
@@ -967,6 +1095,16 @@ impl PyRecord {
             .map(std::string::ToString::to_string)
     }
 
+    /// Get a browse/sort key for the 245 title, skipping non-filing characters
+    pub fn title_sort_key(&self) -> Option<String> {
+        self.inner.title_sort_key()
+    }
+
+    /// Get a browse/sort key for the uniform title (240, else 130)
+    pub fn uniform_title_sort_key(&self) -> Option<String> {
+        self.inner.uniform_title_sort_key()
+    }
+
     /// Get `SuDoc` (government document classification) from 086 field
     pub fn sudoc(&self) -> Option<String> {
         self.inner.sudoc().map(std::string::ToString::to_string)
@@ -1750,3 +1888,14 @@ impl PyHoldingsRecord {
         )
     }
 }
+
+/// Parse a one-character subfield code.
+fn subfield_code(code: &str) -> PyResult<char> {
+    let mut chars = code.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Ok(c),
+        _ => Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "Subfield code must be a single character, got {code:?}"
+        ))),
+    }
+}
@@ -5,7 +5,8 @@
 // 2. Serialize record to MARC bytes (GIL released, CPU-intensive)
 // 3. Write serialized bytes to appropriate backend (GIL re-acquired if needed)
 
-use crate::wrappers::PyRecord;
+use crate::stats::PyStats;
+use crate::wrappers::extract_record;
 use mrrc::MarcWriter;
 use pyo3::prelude::*;
 use std::fs::File;
@@ -48,6 +49,8 @@ enum WriterBackend {
 pub struct PyMARCWriter {
     backend: Option<WriterBackend>,
     closed: bool,
+    /// Records serialized, their bytes, and serialization failures.
+    stats: mrrc::Stats,
 }
 
 #[pymethods]
@@ -76,6 +79,7 @@ impl PyMARCWriter {
             return Ok(PyMARCWriter {
                 backend: Some(WriterBackend::RustFile { writer }),
                 closed: false,
+                stats: mrrc::Stats::new(),
             });
         }
 
@@ -96,6 +100,7 @@ impl PyMARCWriter {
             return Ok(PyMARCWriter {
                 backend: Some(WriterBackend::RustFile { writer }),
                 closed: false,
+                stats: mrrc::Stats::new(),
             });
         }
 
@@ -108,6 +113,7 @@ impl PyMARCWriter {
             return Ok(PyMARCWriter {
                 backend: Some(WriterBackend::PythonFile { file_obj }),
                 closed: false,
+                stats: mrrc::Stats::new(),
             });
         }
 
@@ -135,7 +141,7 @@ impl PyMARCWriter {
     /// - Returns error if backend initialization failed
     /// - Returns error if serialization fails (corrupted record data)
     /// - Returns error if file I/O fails (disk full, permissions, etc.)
-    pub fn write_record(&mut self, record: &PyRecord) -> PyResult<()> {
+    pub fn write_record(&mut self, record: &Bound<'_, PyAny>) -> PyResult<()> {
         if self.closed {
             return Err(pyo3::exceptions::PyRuntimeError::new_err(
                 "Writer has been closed",
@@ -148,10 +154,10 @@ impl PyMARCWriter {
         let py = unsafe { Python::assume_attached() };
 
         // ===== PHASE 1: Extract record data (GIL held) =====
-        // We receive a PyRecord (the Rust wrapper around a Record)
-        // Clone the inner Rust record for Phase 2
+        // We receive any Python record object (raw PyRecord or the mrrc.Record
+        // wrapper) and clone the inner Rust record for Phase 2
         // This must happen with GIL held to safely extract Python object references
-        let record_copy = record.inner.clone();
+        let record_copy = extract_record(record)?;
 
         // ===== PHASE 2: Serialize to bytes (GIL released) =====
         // Serialize the record to MARC bytes without holding the GIL
@@ -164,11 +170,12 @@ impl PyMARCWriter {
         // (via marc_error_to_py_err) preserves the variant — same pattern
         // the readers use. Wrapping in std::io::Error here would collapse
         // E404 WriterError to OSError.
+        let stats = self.stats.clone();
         let serialize_result: Result<Vec<u8>, Box<mrrc::MarcError>> = py.detach(|| {
             // This closure runs WITHOUT the GIL held
             // Safe: record_copy is pure Rust, doesn't reference Python objects
             let mut buffer = Vec::new();
-            let mut writer = MarcWriter::new(&mut buffer);
+            let mut writer = MarcWriter::new(&mut buffer).with_stats(stats);
             writer
                 .write_record(&record_copy)
                 .map(|()| buffer)
@@ -217,44 +224,59 @@ impl PyMARCWriter {
         Ok(())
     }
 
+    /// Throughput counters for this writer (records, bytes, errors)
+    #[getter]
+    fn stats(&self) -> PyStats {
+        PyStats::from(self.stats.clone())
+    }
+
     /// Alias for `write_record` (for pymarc compatibility)
-    pub fn write(&mut self, record: &PyRecord) -> PyResult<()> {
+    pub fn write(&mut self, record: &Bound<'_, PyAny>) -> PyResult<()> {
         self.write_record(record)
     }
 
     /// Close the writer and flush the buffer
     ///
-    /// Flushes any buffered data to disk and closes the writer.
-    /// Safe to call multiple times (idempotent).
+    /// Flushes any buffered data and releases the backend: a file opened
+    /// from a path is closed, while a file object passed in by the caller
+    /// is flushed but left open. Safe to call multiple times (idempotent);
+    /// a flush failure is raised once and the writer is still closed.
     ///
     /// ## GIL Management
     /// - **`PythonFile`:** GIL is held while calling Python `flush()` method
     /// - **`RustFile`:** No GIL needed (pure Rust I/O)
     pub fn close(&mut self) -> PyResult<()> {
-        if !self.closed {
-            match &mut self.backend {
-                Some(WriterBackend::PythonFile { file_obj }) => {
-                    // PythonFile backend: flush via Python method (GIL required)
-                    let py = unsafe { Python::assume_attached() };
-                    let file_ref = file_obj.bind(py);
-                    if let Ok(flush_method) = file_ref.getattr("flush") {
-                        let _ = flush_method.call0();
-                    }
-                },
-                Some(WriterBackend::RustFile { writer }) => {
-                    // RustFile backend: flush via Rust I/O (no GIL)
-                    use std::io::Write;
-                    let _ = writer.flush();
-                },
-                None => {
-                    // Already closed, nothing to do
-                },
-            }
-            self.closed = true;
+        self.closed = true;
+        match self.backend.take() {
+            Some(WriterBackend::PythonFile { file_obj }) => {
+                // PythonFile backend: flush via Python method (GIL required)
+                let py = unsafe { Python::assume_attached() };
+                let file_ref = file_obj.bind(py);
+                if let Ok(flush_method) = file_ref.getattr("flush") {
+                    flush_method.call0()?;
+                }
+            },
+            Some(WriterBackend::RustFile { mut writer }) => {
+                // RustFile backend: flush via Rust I/O (no GIL); dropping
+                // the writer closes the file
+                use std::io::Write;
+                writer.flush().map_err(|e| {
+                    pyo3::exceptions::PyIOError::new_err(format!("Failed to flush writer: {e}"))
+                })?;
+            },
+            None => {
+                // Already closed, nothing to do
+            },
         }
         Ok(())
     }
 
+    /// Whether the writer has been closed
+    #[getter]
+    fn closed(&self) -> bool {
+        self.closed
+    }
+
     /// Context manager support: enter
     fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
         slf
@@ -0,0 +1,252 @@
+//! Columnar export of extracted values as Apache Arrow record batches.
+//!
+//! Analytics tools want a table with one row per record and one column per
+//! value of interest, not a stream of record objects. [`ArrowColumns`]
+//! names a set of [`Extractor`] expressions, and an [`ArrowBatchBuilder`]
+//! evaluates them against each record, appending straight into Arrow
+//! string buffers. Each column holds the first value of its expression
+//! (see [`Extractor::first`]), or null when the expression finds nothing.
+//!
+//! The batches are plain `arrow-array` values, so they can be handed to
+//! any Arrow consumer — Parquet writers, `DataFusion`, or Python through the
+//! Arrow C data interface — without copying.
+//!
+//! Available with the `arrow` cargo feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::arrow::ArrowColumns;
+//! use mrrc::{Leader, Record};
+//!
+//! let columns = ArrowColumns::new(&["001", "245$a", "008/35-37"]).unwrap();
+//!
+//! let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+//! record.add_control_field_str("001", "ocm01234567");
+//! record.add_control_field_str("008", "200101s1925    xxu           000 0 eng d");
+//!
+//! let batch = columns.record_batch([&record]).unwrap();
+//! assert_eq!(batch.num_rows(), 1);
+//! assert_eq!(batch.schema().field(2).name(), "008/35-37");
+//! assert!(batch.column(1).is_null(0)); // no 245
+//! ```
+
+use std::sync::Arc;
+
+use arrow_array::builder::StringBuilder;
+use arrow_array::{ArrayRef, RecordBatch};
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+
+use crate::error::{MarcError, Result};
+use crate::extract::Extractor;
+use crate::marc_record::MarcRecord;
+
+/// Named extraction expressions defining the columns of an Arrow table.
+#[derive(Debug, Clone)]
+pub struct ArrowColumns {
+    extractors: Vec<Extractor>,
+    schema: SchemaRef,
+}
+
+impl ArrowColumns {
+    /// Columns named after their expressions (`"245$a"`, `"008/35-37"`).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MarcError::InvalidField`] if an expression does not parse,
+    /// or if two columns would have the same name.
+    pub fn new<S: AsRef<str>>(expressions: &[S]) -> Result<Self> {
+        Self::named(expressions.iter().map(|e| (e.as_ref(), e.as_ref())))
+    }
+
+    /// Columns from `(name, expression)` pairs.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MarcError::InvalidField`] if an expression does not parse,
+    /// or if two columns have the same name.
+    pub fn named<N, E>(columns: impl IntoIterator<Item = (N, E)>) -> Result<Self>
+    where
+        N: Into<String>,
+        E: AsRef<str>,
+    {
+        let mut extractors = Vec::new();
+        let mut fields: Vec<Field> = Vec::new();
+        for (name, expression) in columns {
+            let name = name.into();
+            if fields.iter().any(|f| f.name() == &name) {
+                return Err(MarcError::invalid_field_msg(format!(
+                    "Duplicate column name '{name}'"
+                )));
+            }
+            extractors.push(Extractor::new(expression.as_ref())?);
+            fields.push(Field::new(name, DataType::Utf8, true));
+        }
+        Ok(ArrowColumns {
+            extractors,
+            schema: Arc::new(Schema::new(fields)),
+        })
+    }
+
+    /// The Arrow schema: one nullable `Utf8` field per column.
+    #[must_use]
+    pub fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+
+    /// Number of columns.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.extractors.len()
+    }
+
+    /// Whether there are no columns.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.extractors.is_empty()
+    }
+
+    /// A builder for batches of these columns.
+    #[must_use]
+    pub fn builder(&self) -> ArrowBatchBuilder<'_> {
+        ArrowBatchBuilder {
+            columns: self,
+            builders: self
+                .extractors
+                .iter()
+                .map(|_| StringBuilder::new())
+                .collect(),
+            rows: 0,
+        }
+    }
+
+    /// Build one batch from `records`.
+    ///
+    /// # Errors
+    ///
+    /// See [`ArrowBatchBuilder::finish`].
+    pub fn record_batch<'a, R, I>(&self, records: I) -> Result<RecordBatch>
+    where
+        R: MarcRecord + ?Sized + 'a,
+        I: IntoIterator<Item = &'a R>,
+    {
+        let mut builder = self.builder();
+        for record in records {
+            builder.push(record);
+        }
+        builder.finish()
+    }
+}
+
+/// Accumulates rows for an [`ArrowColumns`] table.
+#[derive(Debug)]
+pub struct ArrowBatchBuilder<'a> {
+    columns: &'a ArrowColumns,
+    builders: Vec<StringBuilder>,
+    rows: usize,
+}
+
+impl ArrowBatchBuilder<'_> {
+    /// Append one row extracted from `record`.
+    pub fn push<R: MarcRecord + ?Sized>(&mut self, record: &R) {
+        for (extractor, builder) in self.columns.extractors.iter().zip(&mut self.builders) {
+            builder.append_option(extractor.first(record));
+        }
+        self.rows += 1;
+    }
+
+    /// Rows appended since the last [`finish`](Self::finish).
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.rows
+    }
+
+    /// Whether no rows are pending.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.rows == 0
+    }
+
+    /// Take the pending rows as a record batch and start a new one.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MarcError::InvalidField`] if Arrow rejects the batch.
+    pub fn finish(&mut self) -> Result<RecordBatch> {
+        let arrays: Vec<ArrayRef> = self
+            .builders
+            .iter_mut()
+            .map(|builder| Arc::new(builder.finish()) as ArrayRef)
+            .collect();
+        self.rows = 0;
+        if arrays.is_empty() {
+            return Ok(RecordBatch::new_empty(self.columns.schema()));
+        }
+        RecordBatch::try_new(self.columns.schema(), arrays)
+            .map_err(|e| MarcError::invalid_field_msg(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::leader::Leader;
+    use crate::record::{Field as MarcField, Record};
+    use arrow_array::{Array, StringArray};
+
+    fn record(id: &str, title: Option<&str>) -> Record {
+        let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+        record.add_control_field_str("001", id);
+        record.add_control_field_str("008", "200101s1925    xxu           000 0 eng d");
+        if let Some(title) = title {
+            record.add_field(
+                MarcField::builder("245".to_string(), '1', '0')
+                    .subfield_str('a', title)
+                    .build(),
+            );
+        }
+        record
+    }
+
+    fn strings(batch: &RecordBatch, column: usize) -> Vec<Option<String>> {
+        let array = batch
+            .column(column)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        (0..array.len())
+            .map(|i| (!array.is_null(i)).then(|| array.value(i).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_columns_from_expressions() {
+        let columns = ArrowColumns::new(&["001", "245$a", "008/35-37"]).unwrap();
+        let records = [record("1", Some("First")), record("2", None)];
+        let batch = columns.record_batch(&records).unwrap();
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(strings(&batch, 0), [Some("1".into()), Some("2".into())]);
+        assert_eq!(strings(&batch, 1), [Some("First".into()), None]);
+        assert_eq!(strings(&batch, 2), [Some("eng".into()), Some("eng".into())]);
+    }
+
+    #[test]
+    fn test_builder_batches_and_names() {
+        let columns = ArrowColumns::named([("id", "001"), ("lang", "041$a | 008/35-37")]).unwrap();
+        assert_eq!(columns.schema().field(1).name(), "lang");
+        let mut builder = columns.builder();
+        for i in 0..3 {
+            builder.push(&record(&i.to_string(), None));
+        }
+        assert_eq!(builder.len(), 3);
+        assert_eq!(builder.finish().unwrap().num_rows(), 3);
+        assert!(builder.is_empty());
+        assert_eq!(builder.finish().unwrap().num_rows(), 0);
+    }
+
+    #[test]
+    fn test_invalid_columns() {
+        assert!(ArrowColumns::new(&["24"]).is_err());
+        assert!(ArrowColumns::named([("a", "001"), ("a", "003")]).is_err());
+    }
+}
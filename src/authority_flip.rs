@@ -0,0 +1,460 @@
+//! Authority heading flips: rewriting variant headings to the established form.
+//!
+//! When an authority record gains a new 4XX *see from* tracing — typically
+//! because the established 1XX heading changed and the old form was kept as
+//! a reference — bibliographic records that still carry the old form need
+//! to be updated. [`AuthorityFlipper`] indexes the 4XX tracings of a set of
+//! authority records, then walks bibliographic 1XX/6XX/7XX headings and
+//! rewrites any that match a see-from reference to the established heading,
+//! reporting every change as a [`HeadingFlip`].
+//!
+//! Headings are compared in NACO-normalized form (see
+//! [`naco_normalize_heading`]), so differences in case, diacritics and
+//! punctuation do not prevent a match. Name, uniform title and subject
+//! headings only match tracings of the same kind: a bibliographic `600`
+//! matches a `400`, a `650` matches a `450`, and so on. Subject
+//! subdivisions, relator terms and control subfields that follow the
+//! matched heading are preserved.
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::authority_flip::AuthorityFlipper;
+//! use mrrc::{AuthorityRecord, Field, Leader, Record};
+//!
+//! let leader = Leader::from_bytes(b"00000nz  a2200000n  4500").unwrap();
+//! let mut authority = AuthorityRecord::new(leader);
+//! authority.add_control_field("001".to_string(), "n79021164".to_string());
+//! authority.set_heading(
+//!     Field::builder("100".to_string(), '1', ' ')
+//!         .subfield_str('a', "Twain, Mark,")
+//!         .subfield_str('d', "1835-1910")
+//!         .build(),
+//! );
+//! authority.add_see_from_tracing(
+//!     Field::builder("400".to_string(), '1', ' ')
+//!         .subfield_str('a', "Clemens, Samuel Langhorne,")
+//!         .subfield_str('d', "1835-1910")
+//!         .build(),
+//! );
+//!
+//! let flipper = AuthorityFlipper::from_authorities([&authority]);
+//!
+//! let leader = Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap();
+//! let mut record = Record::new(leader);
+//! record.add_field(
+//!     Field::builder("600".to_string(), '1', '0')
+//!         .subfield_str('a', "Clemens, Samuel Langhorne,")
+//!         .subfield_str('d', "1835-1910")
+//!         .subfield_str('v', "Correspondence.")
+//!         .build(),
+//! );
+//!
+//! let flips = flipper.flip_record(&mut record);
+//! assert_eq!(flips.len(), 1);
+//! assert_eq!(flips[0].authority_control_number.as_deref(), Some("n79021164"));
+//! let subject = record.get_field("600").unwrap();
+//! assert_eq!(subject.get_subfield('a'), Some("Twain, Mark,"));
+//! assert_eq!(subject.get_subfield('v'), Some("Correspondence."));
+//! ```
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::authority_record::AuthorityRecord;
+use crate::normalize::naco_normalize_heading;
+use crate::record::{Field, Record, Subfield};
+
+/// Heading kinds (last two tag digits) that take part in flipping.
+const HEADING_KINDS: &[&str] = &["00", "10", "11", "30", "48", "50", "51", "55"];
+
+/// A single heading rewritten by [`AuthorityFlipper`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HeadingFlip {
+    /// Control number (001) of the bibliographic record, if present.
+    pub record_control_number: Option<String>,
+    /// Tag of the rewritten field (e.g. `"650"`).
+    pub tag: String,
+    /// Zero-based occurrence of the field among fields with the same tag.
+    pub occurrence: usize,
+    /// The heading as it appeared before the flip.
+    pub from: String,
+    /// The heading after the flip.
+    pub to: String,
+    /// Control number (001) of the authority record that supplied the
+    /// established form, if present.
+    pub authority_control_number: Option<String>,
+}
+
+/// Established heading recorded against a normalized see-from key.
+#[derive(Debug, Clone)]
+struct Established {
+    indicator1: char,
+    subfields: Vec<Subfield>,
+    control_number: Option<String>,
+}
+
+/// Index of authority see-from references used to flip bibliographic headings.
+///
+/// Build one from an authority file with [`AuthorityFlipper::from_authorities`]
+/// or [`AuthorityFlipper::add_authority`], then apply it to bibliographic
+/// records with [`AuthorityFlipper::flip_record`] or
+/// [`AuthorityFlipper::flip_records`].
+///
+/// A see-from form that normalizes to an established heading of another
+/// authority record is never flipped, and a see-from form traced on two
+/// authority records with different established headings is treated as
+/// ambiguous and left alone.
+#[derive(Debug, Clone, Default)]
+pub struct AuthorityFlipper {
+    /// Normalized see-from key → established heading (`None` if ambiguous).
+    variants: HashMap<(String, String), Option<Established>>,
+    /// Normalized keys of established 1XX headings.
+    established: HashSet<(String, String)>,
+}
+
+impl AuthorityFlipper {
+    /// Create an empty flipper.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a flipper from a stream of authority records.
+    #[must_use]
+    pub fn from_authorities<'a, I>(authorities: I) -> Self
+    where
+        I: IntoIterator<Item = &'a AuthorityRecord>,
+    {
+        let mut flipper = Self::new();
+        for authority in authorities {
+            flipper.add_authority(authority);
+        }
+        flipper
+    }
+
+    /// Index the 1XX heading and 4XX see-from tracings of an authority record.
+    ///
+    /// Records without a 1XX heading are ignored.
+    pub fn add_authority(&mut self, authority: &AuthorityRecord) {
+        let Some(heading) = authority.heading() else {
+            return;
+        };
+        let Some(kind) = heading_kind(&heading.tag) else {
+            return;
+        };
+        let established = heading_subfields(heading);
+        self.established.insert((
+            kind.to_string(),
+            normalized_key(established.iter().copied()),
+        ));
+        let target = Established {
+            indicator1: heading.indicator1,
+            subfields: established.into_iter().cloned().collect(),
+            control_number: authority.get_control_field("001").map(str::to_string),
+        };
+
+        for tracing in authority.see_from_tracings() {
+            if heading_kind(&tracing.tag) != Some(kind) {
+                continue;
+            }
+            let key = (kind.to_string(), normalized_key(heading_subfields(tracing)));
+            self.variants
+                .entry(key)
+                .and_modify(|existing| {
+                    if existing
+                        .as_ref()
+                        .is_some_and(|e| e.subfields != target.subfields)
+                    {
+                        *existing = None;
+                    }
+                })
+                .or_insert_with(|| Some(target.clone()));
+        }
+    }
+
+    /// Number of indexed see-from references.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.variants.len()
+    }
+
+    /// Whether no see-from references have been indexed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.variants.is_empty()
+    }
+
+    /// Rewrite matching 1XX/6XX/7XX headings in `record` to their established
+    /// form, returning one [`HeadingFlip`] per rewritten field.
+    pub fn flip_record(&self, record: &mut Record) -> Vec<HeadingFlip> {
+        let record_control_number = record.get_control_field("001").map(str::to_string);
+        let mut flips = Vec::new();
+        for (tag, fields) in &mut record.fields {
+            if !matches!(tag.as_bytes().first(), Some(b'1' | b'6' | b'7')) {
+                continue;
+            }
+            let Some(kind) = heading_kind(tag) else {
+                continue;
+            };
+            for (occurrence, field) in fields.iter_mut().enumerate() {
+                if let Some((from, to, authority_control_number)) = self.flip_field(kind, field) {
+                    flips.push(HeadingFlip {
+                        record_control_number: record_control_number.clone(),
+                        tag: tag.clone(),
+                        occurrence,
+                        from,
+                        to,
+                        authority_control_number,
+                    });
+                }
+            }
+        }
+        flips
+    }
+
+    /// Flip every record in a stream of bibliographic records, returning the
+    /// combined report.
+    pub fn flip_records<'a, I>(&self, records: I) -> Vec<HeadingFlip>
+    where
+        I: IntoIterator<Item = &'a mut Record>,
+    {
+        records
+            .into_iter()
+            .flat_map(|record| self.flip_record(record))
+            .collect()
+    }
+
+    /// Flip a single field, returning the old and new display forms and the
+    /// authority control number on success.
+    fn flip_field(
+        &self,
+        kind: &str,
+        field: &mut Field,
+    ) -> Option<(String, String, Option<String>)> {
+        let positions: Vec<usize> = field
+            .subfields
+            .iter()
+            .enumerate()
+            .filter(|(_, sf)| is_heading_code(kind, sf.code))
+            .map(|(i, _)| i)
+            .collect();
+
+        // Try the longest run of heading subfields first so that a tracing
+        // carrying its own subdivisions wins over a shorter one.
+        for len in (1..=positions.len()).rev() {
+            let prefix = &positions[..len];
+            let key = (
+                kind.to_string(),
+                normalized_key(prefix.iter().map(|&i| &field.subfields[i])),
+            );
+            if self.established.contains(&key) {
+                return None;
+            }
+            match self.variants.get(&key) {
+                Some(Some(target)) => {
+                    let from = field.value();
+                    replace_heading(field, prefix, target);
+                    if matches!(kind, "00" | "10" | "11") {
+                        field.indicator1 = target.indicator1;
+                    }
+                    return Some((from, field.value(), target.control_number.clone()));
+                },
+                Some(None) => return None,
+                None => {},
+            }
+        }
+        None
+    }
+}
+
+/// Last two digits of a heading tag, if it is a flippable heading kind.
+pub(crate) fn heading_kind(tag: &str) -> Option<&'static str> {
+    let suffix = tag.get(1..3)?;
+    HEADING_KINDS.iter().copied().find(|kind| *kind == suffix)
+}
+
+/// Whether a subfield code is part of the heading itself rather than a
+/// relator term, relationship phrase or control subfield.
+fn is_heading_code(kind: &str, code: char) -> bool {
+    match code {
+        '0'..='9' | 'i' | 'w' => false,
+        'e' => !matches!(kind, "00" | "10"),
+        'j' => kind != "11",
+        _ => true,
+    }
+}
+
+pub(crate) fn heading_subfields(field: &Field) -> Vec<&Subfield> {
+    let kind = heading_kind(&field.tag).unwrap_or_default();
+    field
+        .subfields
+        .iter()
+        .filter(|sf| is_heading_code(kind, sf.code))
+        .collect()
+}
+
+fn normalized_key<'a, I>(subfields: I) -> String
+where
+    I: IntoIterator<Item = &'a Subfield>,
+{
+    let mut field = Field::new(String::new(), ' ', ' ');
+    field.subfields.extend(subfields.into_iter().cloned());
+    naco_normalize_heading(&field)
+}
+
+/// Replace the subfields at `positions` with the established heading,
+/// leaving every other subfield in place.
+fn replace_heading(field: &mut Field, positions: &[usize], target: &Established) {
+    let first = positions[0];
+    let old = std::mem::take(&mut field.subfields);
+    for (i, subfield) in old.into_iter().enumerate() {
+        if i == first {
+            field.subfields.extend(target.subfields.iter().cloned());
+        } else if !positions.contains(&i) {
+            field.subfields.push(subfield);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::leader::Leader;
+
+    fn authority(id: &str, heading: Field, see_from: Vec<Field>) -> AuthorityRecord {
+        let leader = Leader::from_bytes(b"00000nz  a2200000n  4500").unwrap();
+        let mut record = AuthorityRecord::new(leader);
+        record.add_control_field("001".to_string(), id.to_string());
+        record.set_heading(heading);
+        for field in see_from {
+            record.add_see_from_tracing(field);
+        }
+        record
+    }
+
+    fn bib(fields: Vec<Field>) -> Record {
+        let leader = Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap();
+        let mut record = Record::new(leader);
+        record.add_control_field_str("001", "b1");
+        for field in fields {
+            record.add_field(field);
+        }
+        record
+    }
+
+    fn topical(tag: &str, terms: &[(char, &str)]) -> Field {
+        let mut field = Field::new(tag.to_string(), ' ', '0');
+        for (code, value) in terms {
+            field.add_subfield_str(*code, value);
+        }
+        field
+    }
+
+    #[test]
+    fn test_flips_subject_and_keeps_subdivisions() {
+        let auth = authority(
+            "sh1",
+            topical("150", &[('a', "Motion pictures")]),
+            vec![topical("450", &[('a', "Cinema")])],
+        );
+        let flipper = AuthorityFlipper::from_authorities([&auth]);
+        let mut record = bib(vec![topical(
+            "650",
+            &[
+                ('a', "Cinéma."),
+                ('z', "France"),
+                ('0', "http://example.org/x"),
+            ],
+        )]);
+
+        let flips = flipper.flip_record(&mut record);
+        assert_eq!(flips.len(), 1);
+        assert_eq!(flips[0].record_control_number.as_deref(), Some("b1"));
+        assert_eq!(flips[0].tag, "650");
+        assert_eq!(flips[0].from, "Cinéma. France http://example.org/x");
+        assert_eq!(flips[0].to, "Motion pictures France http://example.org/x");
+
+        let field = record.get_field("650").unwrap();
+        assert_eq!(field.indicator2, '0');
+        let codes: Vec<char> = field.subfields.iter().map(|sf| sf.code).collect();
+        assert_eq!(codes, vec!['a', 'z', '0']);
+    }
+
+    #[test]
+    fn test_longest_tracing_wins() {
+        let auth_short = authority(
+            "sh1",
+            topical("150", &[('a', "Motion pictures")]),
+            vec![topical("450", &[('a', "Cinema")])],
+        );
+        let auth_long = authority(
+            "sh2",
+            topical("150", &[('a', "Film criticism")]),
+            vec![topical("450", &[('a', "Cinema"), ('x', "Criticism")])],
+        );
+        let flipper = AuthorityFlipper::from_authorities([&auth_short, &auth_long]);
+        let mut record = bib(vec![topical("650", &[('a', "Cinema"), ('x', "Criticism")])]);
+
+        let flips = flipper.flip_record(&mut record);
+        assert_eq!(flips.len(), 1);
+        assert_eq!(flips[0].authority_control_number.as_deref(), Some("sh2"));
+        assert_eq!(record.get_field("650").unwrap().value(), "Film criticism");
+    }
+
+    #[test]
+    fn test_kind_mismatch_and_established_forms_are_not_flipped() {
+        let auth = authority(
+            "sh1",
+            topical("150", &[('a', "Motion pictures")]),
+            vec![topical("450", &[('a', "Cinema")])],
+        );
+        let flipper = AuthorityFlipper::from_authorities([&auth]);
+        let mut record = bib(vec![
+            topical("651", &[('a', "Cinema")]),
+            topical("650", &[('a', "Motion pictures")]),
+        ]);
+
+        assert!(flipper.flip_record(&mut record).is_empty());
+        assert_eq!(record.get_field("651").unwrap().value(), "Cinema");
+    }
+
+    #[test]
+    fn test_ambiguous_tracing_is_skipped() {
+        let first = authority(
+            "n1",
+            topical("100", &[('a', "Smith, John,"), ('d', "1900-1970")]),
+            vec![topical("400", &[('a', "Smith, J.")])],
+        );
+        let second = authority(
+            "n2",
+            topical("100", &[('a', "Smith, John,"), ('d', "1950-")]),
+            vec![topical("400", &[('a', "Smith, J.")])],
+        );
+        let flipper = AuthorityFlipper::from_authorities([&first, &second]);
+        let mut records = [bib(vec![topical("700", &[('a', "Smith, J.")])])];
+
+        assert!(flipper.flip_records(records.iter_mut()).is_empty());
+    }
+
+    #[test]
+    fn test_name_flip_keeps_relator_and_takes_name_type() {
+        let mut heading = topical("100", &[('a', "Twain, Mark,"), ('d', "1835-1910")]);
+        heading.indicator1 = '1';
+        let mut variant = topical("400", &[('a', "Mark Twain")]);
+        variant.indicator1 = '0';
+        let auth = authority("n79021164", heading, vec![variant]);
+        let flipper = AuthorityFlipper::from_authorities([&auth]);
+
+        let mut name = topical("100", &[('a', "Mark Twain,"), ('e', "author.")]);
+        name.indicator1 = '0';
+        let mut record = bib(vec![name]);
+
+        let flips = flipper.flip_record(&mut record);
+        assert_eq!(flips.len(), 1);
+        let field = record.get_field("100").unwrap();
+        assert_eq!(field.indicator1, '1');
+        assert_eq!(field.value(), "Twain, Mark, 1835-1910 author.");
+    }
+}
@@ -0,0 +1,287 @@
+//! Authority identifiers in subfields `$0` and `$1`.
+//!
+//! Access points carry links to authority data in two subfields: `$0`
+//! (authority record control number or standard number) and `$1` (real
+//! world object URI). The same heading is often linked to several
+//! vocabularies, each written in one of a few forms:
+//!
+//! | Source | Forms |
+//! |--------|-------|
+//! | id.loc.gov | `http://id.loc.gov/authorities/names/n79021164`, `(DLC)n  79021164` |
+//! | VIAF | `http://viaf.org/viaf/102333412`, `(viaf)102333412` |
+//! | FAST | `http://id.worldcat.org/fast/1204155`, `(OCoLC)fst01204155` |
+//! | local | `n12345`, with no source prefix |
+//!
+//! [`AuthorityId::parse`] classifies a value and extracts a normalized
+//! identifier, so `(OCoLC)fst01204155` and `http://id.worldcat.org/fast/1204155`
+//! compare equal by [`AuthorityId::identifier`]. [`Field::authority_ids`]
+//! parses every `$0` and `$1` of a field, and [`Field::set_authority_id`]
+//! adds or replaces one in the conventional position.
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::Field;
+//! use mrrc::authority_ids::AuthoritySource;
+//!
+//! let mut field = Field::builder("650".to_string(), ' ', '7')
+//!     .subfield_str('a', "Cats.")
+//!     .subfield_str('2', "fast")
+//!     .build();
+//! field.set_authority_id('0', "(OCoLC)fst00849392");
+//! field.set_authority_id('0', "http://id.worldcat.org/fast/849393");
+//!
+//! let ids = field.authority_ids();
+//! assert_eq!(ids.len(), 1);
+//! assert_eq!(ids[0].source, AuthoritySource::Fast);
+//! assert_eq!(ids[0].identifier, "fst00849393");
+//! ```
+
+use crate::record::{Field, Subfield};
+use crate::subfield_order::SubfieldOrder;
+
+/// The vocabulary an authority identifier belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AuthoritySource {
+    /// Library of Congress authorities and vocabularies (id.loc.gov, `(DLC)`).
+    LibraryOfCongress,
+    /// Virtual International Authority File (viaf.org, `(viaf)`).
+    Viaf,
+    /// Faceted Application of Subject Terminology (id.worldcat.org/fast,
+    /// `(OCoLC)fst`).
+    Fast,
+    /// A bare control number with no source prefix, taken to be local.
+    Local,
+    /// Any other URI or prefixed source, such as `(DE-588)` or Wikidata.
+    Other,
+}
+
+/// A parsed `$0` or `$1` value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthorityId {
+    /// Subfield code the value came from (`'0'` or `'1'`).
+    pub code: char,
+    /// Vocabulary the identifier belongs to.
+    pub source: AuthoritySource,
+    /// Identifier within the source: the last URI path segment or the text
+    /// after the source prefix. LC control numbers have spaces removed and
+    /// FAST numbers use the zero-padded `fst` form.
+    pub identifier: String,
+    /// The subfield value as written.
+    pub value: String,
+}
+
+impl AuthorityId {
+    /// Classify a `$0`/`$1` value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mrrc::authority_ids::{AuthorityId, AuthoritySource};
+    ///
+    /// let id = AuthorityId::parse('0', "(DLC)n  79021164");
+    /// assert_eq!(id.source, AuthoritySource::LibraryOfCongress);
+    /// assert_eq!(id.identifier, "n79021164");
+    /// assert!(!id.is_uri());
+    /// ```
+    #[must_use]
+    pub fn parse(code: char, value: &str) -> Self {
+        let trimmed = value.trim();
+        // "(uri)" is the older way of marking a URI in $0.
+        let trimmed = trimmed.strip_prefix("(uri)").unwrap_or(trimmed);
+        let (source, identifier) = if let Some(rest) = strip_scheme(trimmed) {
+            classify_uri(rest)
+        } else if let Some((prefix, rest)) = split_prefix(trimmed) {
+            classify_prefixed(prefix, rest)
+        } else {
+            (AuthoritySource::Local, trimmed.to_string())
+        };
+        AuthorityId {
+            code,
+            source,
+            identifier,
+            value: value.to_string(),
+        }
+    }
+
+    /// Whether the value is an HTTP(S) URI.
+    #[must_use]
+    pub fn is_uri(&self) -> bool {
+        strip_scheme(self.value.trim().trim_start_matches("(uri)")).is_some()
+    }
+}
+
+/// The URI without `http://` or `https://`, if it has either.
+fn strip_scheme(value: &str) -> Option<&str> {
+    value
+        .strip_prefix("http://")
+        .or_else(|| value.strip_prefix("https://"))
+}
+
+/// Split `(ORG)rest` into `ORG` and `rest`.
+fn split_prefix(value: &str) -> Option<(&str, &str)> {
+    let (prefix, rest) = value.strip_prefix('(')?.split_once(')')?;
+    Some((prefix, rest.trim()))
+}
+
+fn classify_uri(rest: &str) -> (AuthoritySource, String) {
+    let rest = rest.split(['?', '#']).next().unwrap_or_default();
+    let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+    let host = host.trim_start_matches("www.").to_ascii_lowercase();
+    let last = path
+        .split('/')
+        .rfind(|segment| !segment.is_empty())
+        .unwrap_or_default()
+        .trim_end_matches(".html");
+    match host.as_str() {
+        "id.loc.gov" => (AuthoritySource::LibraryOfCongress, last.to_string()),
+        "viaf.org" => (AuthoritySource::Viaf, last.to_string()),
+        "id.worldcat.org" | "experimental.worldcat.org" if path.starts_with("fast/") => {
+            (AuthoritySource::Fast, fast_identifier(last))
+        },
+        _ => (AuthoritySource::Other, last.to_string()),
+    }
+}
+
+fn classify_prefixed(prefix: &str, rest: &str) -> (AuthoritySource, String) {
+    if prefix == "DLC" {
+        let identifier = rest.chars().filter(|c| !c.is_whitespace()).collect();
+        (AuthoritySource::LibraryOfCongress, identifier)
+    } else if prefix.eq_ignore_ascii_case("viaf") {
+        (AuthoritySource::Viaf, rest.to_string())
+    } else if prefix == "OCoLC" && rest.starts_with("fst") {
+        (AuthoritySource::Fast, fast_identifier(rest))
+    } else {
+        (AuthoritySource::Other, rest.to_string())
+    }
+}
+
+/// FAST numbers as `fst` plus eight digits, whichever form they came in.
+fn fast_identifier(raw: &str) -> String {
+    let digits = raw.strip_prefix("fst").unwrap_or(raw);
+    match digits.parse::<u64>() {
+        Ok(n) => format!("fst{n:08}"),
+        Err(_) => raw.to_string(),
+    }
+}
+
+/// See [`Field::set_authority_id`].
+pub(crate) fn set_authority_id(field: &mut Field, code: char, value: &str) -> Option<String> {
+    let source = AuthorityId::parse(code, value).source;
+    let existing = field
+        .subfields
+        .iter()
+        .position(|sf| sf.code == code && AuthorityId::parse(code, &sf.value).source == source);
+    if let Some(index) = existing {
+        return Some(std::mem::replace(
+            &mut field.subfields[index].value,
+            value.to_string(),
+        ));
+    }
+
+    let index = SubfieldOrder::for_tag(&field.tag)
+        .and_then(|order| order.insert_position(field, code))
+        .unwrap_or_else(|| {
+            // Without a built-in order: ahead of any trailing subfields that
+            // conventionally follow this one ($0 before $1, both before $5).
+            let followers: &[char] = match code {
+                '0' => &['1', '5'],
+                '1' => &['5'],
+                _ => &[],
+            };
+            field
+                .subfields
+                .iter()
+                .rposition(|sf| !followers.contains(&sf.code))
+                .map_or(0, |i| i + 1)
+        });
+    field.subfields.insert(
+        index,
+        Subfield {
+            code,
+            value: value.to_string(),
+        },
+    );
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(value: &str) -> (AuthoritySource, String) {
+        let id = AuthorityId::parse('0', value);
+        (id.source, id.identifier)
+    }
+
+    fn codes(field: &Field) -> String {
+        field.subfields.iter().map(|sf| sf.code).collect()
+    }
+
+    #[test]
+    fn test_classify_forms() {
+        use AuthoritySource::{Fast, LibraryOfCongress, Local, Other, Viaf};
+        let cases = [
+            (
+                "http://id.loc.gov/authorities/names/n79021164",
+                LibraryOfCongress,
+                "n79021164",
+            ),
+            (
+                "https://id.loc.gov/rwo/agents/n79021164.html",
+                LibraryOfCongress,
+                "n79021164",
+            ),
+            ("(DLC)sh 85021262", LibraryOfCongress, "sh85021262"),
+            ("(uri)http://viaf.org/viaf/102333412/", Viaf, "102333412"),
+            ("(viaf)102333412", Viaf, "102333412"),
+            ("(OCoLC)fst00849392", Fast, "fst00849392"),
+            ("http://id.worldcat.org/fast/849392", Fast, "fst00849392"),
+            ("(OCoLC)12345678", Other, "12345678"),
+            ("(DE-588)118540238", Other, "118540238"),
+            ("https://www.wikidata.org/wiki/Q7243", Other, "Q7243"),
+            (" n12345 ", Local, "n12345"),
+        ];
+        for (value, source, identifier) in cases {
+            assert_eq!(parse(value), (source, identifier.to_string()), "{value}");
+        }
+        assert!(AuthorityId::parse('1', "(uri)https://viaf.org/viaf/1").is_uri());
+        assert!(!AuthorityId::parse('0', "(viaf)1").is_uri());
+    }
+
+    #[test]
+    fn test_set_uses_conventional_position() {
+        let mut field = Field::builder("100".to_string(), '1', ' ')
+            .subfield_str('a', "Twain, Mark,")
+            .subfield_str('d', "1835-1910,")
+            .subfield_str('e', "author.")
+            .subfield_str('1', "http://www.wikidata.org/entity/Q7245")
+            .build();
+        assert_eq!(
+            field.set_authority_id('0', "http://id.loc.gov/authorities/names/n79021164"),
+            None
+        );
+        assert_eq!(field.set_authority_id('0', "(OCoLC)fst00029811"), None);
+        assert_eq!(codes(&field), "ade001");
+
+        let old = field.set_authority_id('0', "(DLC)n 79021165");
+        assert_eq!(
+            old.as_deref(),
+            Some("http://id.loc.gov/authorities/names/n79021164")
+        );
+        assert_eq!(field.subfields[3].value, "(DLC)n 79021165");
+        assert_eq!(field.authority_ids().len(), 3);
+    }
+
+    #[test]
+    fn test_set_without_builtin_order() {
+        let mut field = Field::builder("386".to_string(), ' ', ' ')
+            .subfield_str('a', "Americans")
+            .subfield_str('2', "lcdgt")
+            .subfield_str('5', "DLC")
+            .build();
+        field.set_authority_id('1', "http://example.org/americans");
+        field.set_authority_id('0', "(DLC)dg2015060003");
+        assert_eq!(codes(&field), "a2015");
+    }
+}
@@ -117,6 +117,17 @@ impl<R: Read> AuthorityMarcReader<R> {
         self.cap.set_max(n);
         self
     }
+
+    /// Reject records larger than `limits` allow.
+    ///
+    /// Records over a limit fail with a descriptive error in every
+    /// recovery mode; the reader stays positioned on the next record. See
+    /// [`ReaderLimits`](crate::ReaderLimits) for the defaults.
+    #[must_use]
+    pub fn with_limits(mut self, limits: crate::recovery::ReaderLimits) -> Self {
+        self.ctx.limits = limits;
+        self
+    }
 }
 
 impl AuthorityMarcReader<std::io::BufReader<std::fs::File>> {
@@ -4,6 +4,7 @@
 //! for use as access points in bibliographic records. They differ fundamentally
 //! from bibliographic records in structure and purpose.
 
+use crate::authority_reference::{Reference, ReferenceKind};
 use crate::leader::Leader;
 use crate::marc_record::MarcRecord;
 use crate::record::{Field, TagIndexMap};
@@ -85,6 +86,182 @@ pub enum LevelOfEstablishment {
     NotApplicable,
 }
 
+/// Type of subject subdivision (008/17)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SubdivisionType {
+    /// a - Topical
+    Topical,
+    /// b - Form
+    Form,
+    /// c - Chronological
+    Chronological,
+    /// d - Geographic
+    Geographic,
+    /// e - Language
+    Language,
+    /// n - Not applicable
+    NotApplicable,
+}
+
+impl SubdivisionType {
+    /// Decode an 008/17 value; `None` for fill or unknown characters.
+    #[must_use]
+    pub fn from_code(code: char) -> Option<Self> {
+        match code {
+            'a' => Some(SubdivisionType::Topical),
+            'b' => Some(SubdivisionType::Form),
+            'c' => Some(SubdivisionType::Chronological),
+            'd' => Some(SubdivisionType::Geographic),
+            'e' => Some(SubdivisionType::Language),
+            'n' => Some(SubdivisionType::NotApplicable),
+            _ => None,
+        }
+    }
+
+    /// The 008/17 code.
+    #[must_use]
+    pub fn code(self) -> char {
+        match self {
+            SubdivisionType::Topical => 'a',
+            SubdivisionType::Form => 'b',
+            SubdivisionType::Chronological => 'c',
+            SubdivisionType::Geographic => 'd',
+            SubdivisionType::Language => 'e',
+            SubdivisionType::NotApplicable => 'n',
+        }
+    }
+}
+
+/// Where a heading may be used, from 008/14-17
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct SubdivisionUsage {
+    /// 008/14 - Appropriate as a main or added entry
+    pub main_or_added_entry: bool,
+    /// 008/15 - Appropriate as a subject added entry
+    pub subject_added_entry: bool,
+    /// 008/16 - Appropriate as a series added entry
+    pub series_added_entry: bool,
+    /// 008/17 - Type of subject subdivision, if coded
+    pub subdivision_type: Option<SubdivisionType>,
+}
+
+/// A heading/reference consistency problem found by
+/// [`AuthorityRecord::heading_issues`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum AuthorityIssue {
+    /// The record has no 1XX heading.
+    MissingHeading,
+    /// The record has more than one 1XX heading.
+    MultipleHeadings {
+        /// Tags of the 1XX fields, in record order
+        tags: Vec<String>,
+    },
+    /// A 4XX or 5XX tracing that NACO-normalizes to the 1XX heading.
+    TracingDuplicatesHeading {
+        /// Tracing tag
+        tag: String,
+        /// Position among fields with this tag
+        index: usize,
+    },
+    /// An 008 code that does not fit the heading's tag.
+    FixedFieldMismatch {
+        /// 008 character position
+        position: usize,
+        /// The coded value
+        value: char,
+        /// Tag of the 1XX heading
+        heading_tag: String,
+    },
+}
+
+impl std::fmt::Display for AuthorityIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuthorityIssue::MissingHeading => write!(f, "record has no 1XX heading"),
+            AuthorityIssue::MultipleHeadings { tags } => {
+                write!(
+                    f,
+                    "record has {} 1XX headings ({})",
+                    tags.len(),
+                    tags.join(", ")
+                )
+            },
+            AuthorityIssue::TracingDuplicatesHeading { tag, index } => write!(
+                f,
+                "field {tag} (occurrence {index}) duplicates the 1XX heading"
+            ),
+            AuthorityIssue::FixedFieldMismatch {
+                position,
+                value,
+                heading_tag,
+            } => write!(
+                f,
+                "008/{position:02} '{value}' does not fit a {heading_tag} heading"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AuthorityIssue {}
+
+/// A source consulted in establishing a heading (670)
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct SourceCitation {
+    /// $a - Source citation
+    pub citation: String,
+    /// $b - Information found
+    pub information: Vec<String>,
+    /// $u - Uniform Resource Identifier
+    pub uri: Option<String>,
+}
+
+impl SourceCitation {
+    /// A citation with no information or URI.
+    #[must_use]
+    pub fn new(citation: &str) -> Self {
+        SourceCitation {
+            citation: citation.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Add a piece of information found in the source.
+    #[must_use]
+    pub fn with_information(mut self, information: &str) -> Self {
+        self.information.push(information.to_string());
+        self
+    }
+
+    /// Read a 670 field.
+    #[must_use]
+    pub fn from_field(field: &Field) -> Self {
+        SourceCitation {
+            citation: field.get_subfield('a').unwrap_or_default().to_string(),
+            information: field
+                .get_subfield_values('b')
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+            uri: field.get_subfield('u').map(str::to_string),
+        }
+    }
+
+    /// Write the citation as a 670 field.
+    #[must_use]
+    pub fn to_field(&self) -> Field {
+        let mut field = Field::new("670".to_string(), ' ', ' ');
+        field.add_subfield('a', self.citation.clone());
+        for information in &self.information {
+            field.add_subfield('b', information.clone());
+        }
+        if let Some(uri) = &self.uri {
+            field.add_subfield('u', uri.clone());
+        }
+        field
+    }
+}
+
 impl AuthorityRecord {
     /// Create a new authority record with the given leader
     #[must_use]
@@ -193,6 +370,27 @@ impl AuthorityRecord {
             .collect()
     }
 
+    /// All 4XX and 5XX tracings, decoded as [`Reference`]s in record order.
+    ///
+    /// Unlike [`see_from_tracings`](Self::see_from_tracings) and
+    /// [`see_also_tracings`](Self::see_also_tracings), the `$w` control
+    /// subfield, `$i` relationship phrase and `$0` control number are
+    /// decoded into typed fields.
+    #[must_use]
+    pub fn references(&self) -> Vec<Reference> {
+        self.fields
+            .iter()
+            .filter(|(tag, _)| ReferenceKind::from_tag(tag).is_some())
+            .flat_map(|(_, fields)| fields.iter())
+            .filter_map(Reference::from_field)
+            .collect()
+    }
+
+    /// Encode `reference` as a 4XX/5XX tracing and add it to the record.
+    pub fn add_reference(&mut self, reference: &Reference) {
+        self.add_field(reference.to_field());
+    }
+
     /// Add a note field
     pub fn add_note(&mut self, field: Field) {
         self.fields
@@ -223,15 +421,131 @@ impl AuthorityRecord {
             .unwrap_or_default()
     }
 
-    /// Get source data not found notes (671)
+    /// Get source data not found notes (675)
     #[must_use]
     pub fn source_data_not_found(&self) -> Vec<&Field> {
         self.fields
-            .get("671")
+            .get("675")
             .map(|fields| fields.iter().collect())
             .unwrap_or_default()
     }
 
+    /// The text of the 1XX heading if the record establishes it, with
+    /// subdivisions joined by `--`.
+    ///
+    /// Reference records (008/09 `b`, `c` or `g`) have no established
+    /// heading; a record without an 008 is taken as established.
+    #[must_use]
+    pub fn established_heading(&self) -> Option<String> {
+        if self.is_reference() {
+            return None;
+        }
+        crate::subjects::heading_string(self.heading()?)
+    }
+
+    /// Scope notes (680), each with its `$a` terms and `$i` text joined by
+    /// spaces.
+    #[must_use]
+    pub fn scope_notes(&self) -> Vec<String> {
+        self.fields
+            .get("680")
+            .into_iter()
+            .flatten()
+            .map(|field| {
+                field
+                    .subfields
+                    .iter()
+                    .filter(|sf| matches!(sf.code, 'a' | 'i'))
+                    .map(|sf| sf.value.trim())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .filter(|note| !note.is_empty())
+            .collect()
+    }
+
+    /// Add a scope note (680 `$i`).
+    pub fn add_scope_note(&mut self, text: &str) {
+        let mut field = Field::new("680".to_string(), ' ', ' ');
+        field.add_subfield('i', text.to_string());
+        self.add_field(field);
+    }
+
+    /// Sources in which the heading was found (670).
+    #[must_use]
+    pub fn sources_found(&self) -> Vec<SourceCitation> {
+        self.source_data_found()
+            .into_iter()
+            .map(SourceCitation::from_field)
+            .collect()
+    }
+
+    /// Add a source in which the heading was found (670).
+    pub fn add_source_found(&mut self, source: &SourceCitation) {
+        self.add_field(source.to_field());
+    }
+
+    /// Citations of sources searched without finding the heading (675
+    /// `$a`).
+    #[must_use]
+    pub fn sources_not_found(&self) -> Vec<&str> {
+        self.source_data_not_found()
+            .into_iter()
+            .flat_map(|field| field.get_subfield_values('a'))
+            .collect()
+    }
+
+    /// Add a source searched without finding the heading, to the record's
+    /// 675 (created if absent).
+    pub fn add_source_not_found(&mut self, citation: &str) {
+        let fields = self.fields.entry("675".to_string()).or_default();
+        if fields.is_empty() {
+            fields.push(Field::new("675".to_string(), ' ', ' '));
+        }
+        fields[0].add_subfield('a', citation.to_string());
+    }
+
+    /// Where the heading may be used, from 008/14-17; `None` without an
+    /// 008 that long.
+    #[must_use]
+    pub fn subdivision_usage(&self) -> Option<SubdivisionUsage> {
+        let fixed = self.get_control_field("008")?;
+        let codes: Vec<char> = fixed.chars().skip(14).take(4).collect();
+        let [main, subject, series, subdivision] = codes[..] else {
+            return None;
+        };
+        Some(SubdivisionUsage {
+            main_or_added_entry: main == 'a',
+            subject_added_entry: subject == 'a',
+            series_added_entry: series == 'a',
+            subdivision_type: SubdivisionType::from_code(subdivision),
+        })
+    }
+
+    /// Code 008/14-17 from `usage`, padding the 008 with blanks to its
+    /// 40 characters (creating it if absent).
+    ///
+    /// An unset subdivision type is coded `n` (not applicable).
+    pub fn set_subdivision_usage(&mut self, usage: SubdivisionUsage) {
+        let flag = |appropriate: bool| if appropriate { 'a' } else { 'b' };
+        let codes = [
+            flag(usage.main_or_added_entry),
+            flag(usage.subject_added_entry),
+            flag(usage.series_added_entry),
+            usage.subdivision_type.map_or('n', SubdivisionType::code),
+        ];
+        let values = self.control_fields.entry("008".to_string()).or_default();
+        if values.is_empty() {
+            values.push(String::new());
+        }
+        let mut fixed: Vec<char> = values[0].chars().collect();
+        if fixed.len() < 40 {
+            fixed.resize(40, ' ');
+        }
+        fixed[14..18].copy_from_slice(&codes);
+        values[0] = fixed.into_iter().collect();
+    }
+
     /// Add a heading linking entry field (7XX)
     pub fn add_linking_entry(&mut self, field: Field) {
         self.fields
@@ -319,6 +633,91 @@ impl AuthorityRecord {
         }
     }
 
+    /// Check the heading against the rest of the record: exactly one 1XX,
+    /// no 4XX/5XX tracing that NACO-normalizes to it (ignoring `$i` and
+    /// `$w`), and 008/09-14 codes that fit its tag.
+    ///
+    /// The 008 checks are that subdivision records (008/09 `d`) and only
+    /// they have an 18X heading, node labels (`e`) a 150; that a series
+    /// type (008/12 `a`-`c`) and numbering (008/13 `a`-`c`) are only coded
+    /// for a 130 or a name/title heading; and that topical, chronological, genre/form and subdivision
+    /// headings are not coded as main or added entries (008/14 `a`).
+    /// Fill characters and missing positions are not checked.
+    #[must_use]
+    pub fn heading_issues(&self) -> Vec<AuthorityIssue> {
+        let mut issues = Vec::new();
+        let headings: Vec<&Field> = self
+            .fields
+            .iter()
+            .filter(|(tag, _)| tag.starts_with('1'))
+            .flat_map(|(_, fields)| fields.iter())
+            .collect();
+        let heading = match headings[..] {
+            [] => {
+                issues.push(AuthorityIssue::MissingHeading);
+                return issues;
+            },
+            [heading] => heading,
+            [heading, ..] => {
+                issues.push(AuthorityIssue::MultipleHeadings {
+                    tags: headings.iter().map(|f| f.tag.clone()).collect(),
+                });
+                heading
+            },
+        };
+
+        let normalized = comparison_form(heading);
+        for (tag, fields) in &self.fields {
+            if !(tag.starts_with('4') || tag.starts_with('5')) {
+                continue;
+            }
+            for (index, field) in fields.iter().enumerate() {
+                if comparison_form(field) == normalized {
+                    issues.push(AuthorityIssue::TracingDuplicatesHeading {
+                        tag: tag.clone(),
+                        index,
+                    });
+                }
+            }
+        }
+
+        let tag = heading.tag.as_str();
+        let is_subdivision = tag.starts_with("18");
+        let is_series_heading = tag == "130"
+            || (matches!(tag, "100" | "110" | "111") && heading.get_subfield('t').is_some());
+        let mismatch = |position: usize, fits: &dyn Fn(char) -> bool| {
+            control_field_char_at(self, "008", position)
+                .filter(|&value| value != '|' && !fits(value))
+                .map(|value| AuthorityIssue::FixedFieldMismatch {
+                    position,
+                    value,
+                    heading_tag: tag.to_string(),
+                })
+        };
+        issues.extend(
+            [
+                mismatch(9, &|kind| match kind {
+                    'd' => is_subdivision,
+                    'e' => tag == "150",
+                    _ => !is_subdivision,
+                }),
+                mismatch(12, &|series| {
+                    !matches!(series, 'a'..='c') || is_series_heading
+                }),
+                mismatch(13, &|numbering| {
+                    !matches!(numbering, 'a'..='c') || is_series_heading
+                }),
+                mismatch(14, &|entry| {
+                    entry != 'a'
+                        || !(is_subdivision || matches!(tag, "148" | "150" | "155" | "162"))
+                }),
+            ]
+            .into_iter()
+            .flatten(),
+        );
+        issues
+    }
+
     /// Check if this is an established heading
     #[must_use]
     pub fn is_established(&self) -> bool {
@@ -342,6 +741,14 @@ impl AuthorityRecord {
     }
 }
 
+/// A heading's NACO comparison form, without the relationship subfields
+/// of tracings.
+fn comparison_form(field: &Field) -> String {
+    let mut field = field.clone();
+    field.subfields.retain(|s| !matches!(s.code, 'i' | 'w'));
+    crate::normalize::naco_normalize_heading(&field)
+}
+
 impl MarcRecord for AuthorityRecord {
     fn leader(&self) -> &Leader {
         &self.leader
@@ -417,6 +824,13 @@ impl AuthorityRecordBuilder {
         self
     }
 
+    /// Add a see-from or see-also reference (4XX/5XX)
+    #[must_use]
+    pub fn add_reference(mut self, reference: &Reference) -> Self {
+        self.record.add_reference(reference);
+        self
+    }
+
     /// Add a note field
     #[must_use]
     pub fn add_note(mut self, field: Field) -> Self {
@@ -424,6 +838,34 @@ impl AuthorityRecordBuilder {
         self
     }
 
+    /// Add a scope note (680)
+    #[must_use]
+    pub fn scope_note(mut self, text: &str) -> Self {
+        self.record.add_scope_note(text);
+        self
+    }
+
+    /// Add a source in which the heading was found (670)
+    #[must_use]
+    pub fn source_found(mut self, source: &SourceCitation) -> Self {
+        self.record.add_source_found(source);
+        self
+    }
+
+    /// Add a source searched without finding the heading (675)
+    #[must_use]
+    pub fn source_not_found(mut self, citation: &str) -> Self {
+        self.record.add_source_not_found(citation);
+        self
+    }
+
+    /// Code where the heading may be used (008/14-17)
+    #[must_use]
+    pub fn subdivision_usage(mut self, usage: SubdivisionUsage) -> Self {
+        self.record.set_subdivision_usage(usage);
+        self
+    }
+
     /// Add a heading linking entry field (7XX)
     #[must_use]
     pub fn add_linking_entry(mut self, field: Field) -> Self {
@@ -443,6 +885,22 @@ impl AuthorityRecordBuilder {
     pub fn build(self) -> AuthorityRecord {
         self.record
     }
+
+    /// Build the record after checking its heading with
+    /// [`AuthorityRecord::heading_issues`].
+    ///
+    /// # Errors
+    ///
+    /// Returns every [`AuthorityIssue`] found: a missing or repeated 1XX, a
+    /// 4XX/5XX that duplicates it, or 008/09-14 codes that do not fit it.
+    pub fn build_validated(self) -> std::result::Result<AuthorityRecord, Vec<AuthorityIssue>> {
+        let issues = self.record.heading_issues();
+        if issues.is_empty() {
+            Ok(self.record)
+        } else {
+            Err(issues)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -706,6 +1164,127 @@ mod tests {
         assert_eq!(record.source_data_found().len(), 1);
     }
 
+    #[test]
+    fn test_heading_issues() {
+        let heading = Field::builder("100".to_string(), '1', ' ')
+            .subfield_str('a', "Twain, Mark,")
+            .subfield_str('d', "1835-1910.")
+            .build();
+        let see_from = Field::builder("400".to_string(), '1', ' ')
+            .subfield_str('a', "Clemens, Samuel Langhorne,")
+            .subfield_str('d', "1835-1910")
+            .build();
+        let record = AuthorityRecord::builder(create_test_leader())
+            .control_field(
+                "008".to_string(),
+                "850101n| azannaabn          |a aaa      ".to_string(),
+            )
+            .heading(heading)
+            .add_see_from(see_from)
+            .build_validated()
+            .unwrap();
+        assert!(record.heading_issues().is_empty());
+
+        let issues = AuthorityRecord::builder(create_test_leader())
+            .build_validated()
+            .unwrap_err();
+        assert_eq!(issues, [AuthorityIssue::MissingHeading]);
+
+        let issues = AuthorityRecord::builder(create_test_leader())
+            .control_field(
+                "008".to_string(),
+                "850101n| dza|aaabn          |a aaa      ".to_string(),
+            )
+            .heading(
+                Field::builder("150".to_string(), ' ', ' ')
+                    .subfield_str('a', "Whaling")
+                    .build(),
+            )
+            .heading(
+                Field::builder("151".to_string(), ' ', ' ')
+                    .subfield_str('a', "Nantucket")
+                    .build(),
+            )
+            .add_see_also(
+                Field::builder("550".to_string(), ' ', ' ')
+                    .subfield_str('w', "g")
+                    .subfield_str('a', "WHALING.")
+                    .build(),
+            )
+            .build_validated()
+            .unwrap_err();
+        let fixed = |position, value| AuthorityIssue::FixedFieldMismatch {
+            position,
+            value,
+            heading_tag: "150".to_string(),
+        };
+        assert_eq!(
+            issues,
+            [
+                AuthorityIssue::MultipleHeadings {
+                    tags: vec!["150".to_string(), "151".to_string()]
+                },
+                AuthorityIssue::TracingDuplicatesHeading {
+                    tag: "550".to_string(),
+                    index: 0
+                },
+                fixed(9, 'd'),
+                fixed(13, 'a'),
+                fixed(14, 'a'),
+            ]
+        );
+        assert_eq!(
+            issues[4].to_string(),
+            "008/14 'a' does not fit a 150 heading"
+        );
+    }
+
+    #[test]
+    fn test_heading_notes_sources_and_usage() {
+        let heading = Field::builder("150".to_string(), ' ', ' ')
+            .subfield_str('a', "Whaling")
+            .subfield_str('z', "Massachusetts")
+            .build();
+        let mut record = AuthorityRecord::builder(create_test_leader())
+            .heading(heading)
+            .scope_note("Here are entered works on the hunting of whales.")
+            .source_found(
+                &SourceCitation::new("Work cat.: Whaling days, 1999").with_information("p. 3"),
+            )
+            .source_not_found("LCSH")
+            .source_not_found("Web. 3")
+            .subdivision_usage(SubdivisionUsage {
+                main_or_added_entry: false,
+                subject_added_entry: true,
+                series_added_entry: false,
+                subdivision_type: Some(SubdivisionType::Topical),
+            })
+            .build();
+
+        assert_eq!(
+            record.established_heading().as_deref(),
+            Some("Whaling--Massachusetts")
+        );
+        assert_eq!(
+            record.scope_notes(),
+            ["Here are entered works on the hunting of whales."]
+        );
+        assert_eq!(record.sources_found()[0].information, ["p. 3"]);
+        assert_eq!(record.sources_not_found(), ["LCSH", "Web. 3"]);
+        assert_eq!(record.source_data_not_found().len(), 1);
+
+        let usage = record.subdivision_usage().unwrap();
+        assert!(usage.subject_added_entry && !usage.series_added_entry);
+        assert_eq!(usage.subdivision_type, Some(SubdivisionType::Topical));
+        assert_eq!(record.get_control_field("008").unwrap().len(), 40);
+
+        // Reference records establish nothing.
+        let mut fixed: Vec<char> = record.get_control_field("008").unwrap().chars().collect();
+        fixed[9] = 'b';
+        record.control_fields.get_mut("008").unwrap()[0] = fixed.into_iter().collect();
+        assert_eq!(record.established_heading(), None);
+    }
+
     #[test]
     fn test_control_field_operations() {
         let leader = create_test_leader();
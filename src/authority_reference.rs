@@ -0,0 +1,447 @@
+//! Typed see-from (4XX) and see-also (5XX) references for authority records.
+//!
+//! Authority tracings carry their relationship semantics in control
+//! subfield `$w`, a positional code whose characters mean, in order:
+//!
+//! | Position | Meaning                  | Example values                       |
+//! |----------|--------------------------|--------------------------------------|
+//! | `$w/0`   | Special relationship     | `a` earlier, `b` later, `g` broader  |
+//! | `$w/1`   | Tracing use restriction  | `a` name, `b` subject, `n` n/a       |
+//! | `$w/2`   | Earlier form of heading  | `e` earlier established, `o` pre-AACR2 |
+//! | `$w/3`   | Reference display        | `a` not displayed, `b` see-also not displayed |
+//!
+//! [`Reference`] decodes these (together with `$i` relationship phrases and
+//! `$0` record control numbers) so callers can walk an authority's
+//! cross-reference structure without picking apart raw subfields, and
+//! [`ReferenceBuilder`] assembles the same structure back into 4XX/5XX
+//! fields.
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::authority_reference::{Reference, ReferenceKind, SpecialRelationship};
+//! use mrrc::{AuthorityRecord, Field, Leader};
+//!
+//! let leader = Leader::from_bytes(b"00000nz  a2200000n  4500").unwrap();
+//! let mut record = AuthorityRecord::new(leader);
+//! let later = Reference::builder(
+//!     ReferenceKind::SeeAlso,
+//!     Field::builder("110".to_string(), '2', ' ')
+//!         .subfield_str('a', "Library of Congress. Office of Digital Strategy")
+//!         .build(),
+//! )
+//! .relationship(SpecialRelationship::LaterHeading)
+//! .control_number("n2018000001")
+//! .build();
+//! record.add_reference(&later);
+//!
+//! let references = record.references();
+//! assert_eq!(references.len(), 1);
+//! assert_eq!(references[0].kind, ReferenceKind::SeeAlso);
+//! assert_eq!(references[0].tag, "510");
+//! assert_eq!(references[0].relationship, SpecialRelationship::LaterHeading);
+//! assert_eq!(references[0].control_number.as_deref(), Some("n2018000001"));
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::record::Field;
+
+/// Whether a reference is a see-from (4XX) or see-also-from (5XX) tracing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ReferenceKind {
+    /// 4XX - the traced form is a non-preferred variant of the heading
+    SeeFrom,
+    /// 5XX - the traced form is a related, separately established heading
+    SeeAlso,
+}
+
+impl ReferenceKind {
+    /// Tag prefix digit for this kind of tracing (`'4'` or `'5'`).
+    #[must_use]
+    pub fn tag_digit(self) -> char {
+        match self {
+            ReferenceKind::SeeFrom => '4',
+            ReferenceKind::SeeAlso => '5',
+        }
+    }
+
+    /// Kind of tracing carried by a tag, if it is a 4XX or 5XX tag.
+    #[must_use]
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        match tag.chars().next()? {
+            '4' => Some(ReferenceKind::SeeFrom),
+            '5' => Some(ReferenceKind::SeeAlso),
+            _ => None,
+        }
+    }
+}
+
+/// Special relationship coded in `$w/0`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SpecialRelationship {
+    /// a - Earlier heading
+    EarlierHeading,
+    /// b - Later heading
+    LaterHeading,
+    /// d - Acronym
+    Acronym,
+    /// f - Musical composition
+    MusicalComposition,
+    /// g - Broader term
+    BroaderTerm,
+    /// h - Narrower term
+    NarrowerTerm,
+    /// i - Reference instruction phrase in subfield `$i`
+    InstructionPhrase,
+    /// r - Relationship designator in subfield `$i` or `$4`
+    RelationshipDesignator,
+    /// t - Immediate parent body
+    ParentBody,
+    /// n or absent - Not applicable
+    NotApplicable,
+    /// Any other code
+    Other(char),
+}
+
+impl SpecialRelationship {
+    /// Decode a `$w/0` character.
+    #[must_use]
+    pub fn from_code(code: char) -> Self {
+        match code {
+            'a' => SpecialRelationship::EarlierHeading,
+            'b' => SpecialRelationship::LaterHeading,
+            'd' => SpecialRelationship::Acronym,
+            'f' => SpecialRelationship::MusicalComposition,
+            'g' => SpecialRelationship::BroaderTerm,
+            'h' => SpecialRelationship::NarrowerTerm,
+            'i' => SpecialRelationship::InstructionPhrase,
+            'r' => SpecialRelationship::RelationshipDesignator,
+            't' => SpecialRelationship::ParentBody,
+            'n' | ' ' => SpecialRelationship::NotApplicable,
+            other => SpecialRelationship::Other(other),
+        }
+    }
+
+    /// The `$w/0` character for this relationship.
+    #[must_use]
+    pub fn code(self) -> char {
+        match self {
+            SpecialRelationship::EarlierHeading => 'a',
+            SpecialRelationship::LaterHeading => 'b',
+            SpecialRelationship::Acronym => 'd',
+            SpecialRelationship::MusicalComposition => 'f',
+            SpecialRelationship::BroaderTerm => 'g',
+            SpecialRelationship::NarrowerTerm => 'h',
+            SpecialRelationship::InstructionPhrase => 'i',
+            SpecialRelationship::RelationshipDesignator => 'r',
+            SpecialRelationship::ParentBody => 't',
+            SpecialRelationship::NotApplicable => 'n',
+            SpecialRelationship::Other(c) => c,
+        }
+    }
+}
+
+/// A decoded see-from or see-also tracing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Reference {
+    /// See-from or see-also
+    pub kind: ReferenceKind,
+    /// Tag of the tracing field (e.g. `"450"`)
+    pub tag: String,
+    /// The traced heading: indicators and heading subfields only, without
+    /// `$w`, `$i` or numeric control subfields. Its tag is the
+    /// corresponding 1XX tag.
+    pub heading: Field,
+    /// Record control number of the referenced authority (`$0`)
+    pub control_number: Option<String>,
+    /// Special relationship (`$w/0`)
+    pub relationship: SpecialRelationship,
+    /// Relationship phrase or designator (`$i`)
+    pub designator: Option<String>,
+    /// Tracing use restriction (`$w/1`), `'n'` when not coded
+    pub tracing_restriction: char,
+    /// Earlier form of heading (`$w/2`), `'n'` when not coded
+    pub earlier_form: char,
+    /// Reference display (`$w/3`), `'n'` when not coded
+    pub display: char,
+}
+
+impl Reference {
+    /// Start building a reference to `heading`.
+    ///
+    /// The heading's tag only needs the right last two digits; `100`,
+    /// `400` and `500` all produce an X00 tracing.
+    #[must_use]
+    pub fn builder(kind: ReferenceKind, heading: Field) -> ReferenceBuilder {
+        ReferenceBuilder {
+            reference: Reference {
+                kind,
+                tag: tracing_tag(kind, &heading.tag),
+                heading: Field {
+                    tag: tracing_tag_with('1', &heading.tag),
+                    ..heading
+                },
+                control_number: None,
+                relationship: SpecialRelationship::NotApplicable,
+                designator: None,
+                tracing_restriction: 'n',
+                earlier_form: 'n',
+                display: 'n',
+            },
+        }
+    }
+
+    /// Decode a 4XX or 5XX tracing field.
+    ///
+    /// Returns `None` if the field is not a 4XX/5XX tracing.
+    #[must_use]
+    pub fn from_field(field: &Field) -> Option<Self> {
+        let kind = ReferenceKind::from_tag(&field.tag)?;
+        let mut control = field.get_subfield('w').unwrap_or_default().chars();
+        let mut position = || control.next().filter(|c| *c != ' ').unwrap_or('n');
+
+        let mut heading = Field::new(
+            tracing_tag_with('1', &field.tag),
+            field.indicator1,
+            field.indicator2,
+        );
+        heading.subfields.extend(
+            field
+                .subfields
+                .iter()
+                .filter(|sf| !sf.code.is_ascii_digit() && !matches!(sf.code, 'w' | 'i'))
+                .cloned(),
+        );
+
+        Some(Reference {
+            kind,
+            tag: field.tag.clone(),
+            heading,
+            control_number: field.get_subfield('0').map(str::to_string),
+            relationship: SpecialRelationship::from_code(position()),
+            tracing_restriction: position(),
+            earlier_form: position(),
+            display: position(),
+            designator: field.get_subfield('i').map(str::to_string),
+        })
+    }
+
+    /// Whether `$w/2` marks this as an earlier established form of the heading.
+    #[must_use]
+    pub fn is_earlier_form(&self) -> bool {
+        self.earlier_form == 'e'
+    }
+
+    /// Whether the reference should be displayed to users (`$w/3`).
+    #[must_use]
+    pub fn is_displayed(&self) -> bool {
+        !matches!(self.display, 'a' | 'b')
+    }
+
+    /// Encode the reference as a 4XX/5XX tracing field.
+    ///
+    /// `$w` is only written when at least one position is coded, and is
+    /// trimmed to the last coded position. `$i` precedes the heading
+    /// subfields and `$0` follows them.
+    #[must_use]
+    pub fn to_field(&self) -> Field {
+        let mut field = Field::new(
+            self.tag.clone(),
+            self.heading.indicator1,
+            self.heading.indicator2,
+        );
+        let mut control: Vec<char> = vec![
+            self.relationship.code(),
+            self.tracing_restriction,
+            self.earlier_form,
+            self.display,
+        ];
+        while control.last() == Some(&'n') {
+            control.pop();
+        }
+        if !control.is_empty() {
+            field.add_subfield('w', control.into_iter().collect());
+        }
+        if let Some(designator) = &self.designator {
+            field.add_subfield_str('i', designator);
+        }
+        field
+            .subfields
+            .extend(self.heading.subfields.iter().cloned());
+        if let Some(control_number) = &self.control_number {
+            field.add_subfield_str('0', control_number);
+        }
+        field
+    }
+}
+
+/// Builder for [`Reference`], created by [`Reference::builder`].
+#[derive(Debug, Clone)]
+pub struct ReferenceBuilder {
+    reference: Reference,
+}
+
+impl ReferenceBuilder {
+    /// Set the special relationship (`$w/0`)
+    #[must_use]
+    pub fn relationship(mut self, relationship: SpecialRelationship) -> Self {
+        self.reference.relationship = relationship;
+        self
+    }
+
+    /// Set the relationship phrase or designator (`$i`)
+    #[must_use]
+    pub fn designator(mut self, designator: &str) -> Self {
+        self.reference.designator = Some(designator.to_string());
+        self
+    }
+
+    /// Set the referenced authority's record control number (`$0`)
+    #[must_use]
+    pub fn control_number(mut self, control_number: &str) -> Self {
+        self.reference.control_number = Some(control_number.to_string());
+        self
+    }
+
+    /// Set the tracing use restriction (`$w/1`)
+    #[must_use]
+    pub fn tracing_restriction(mut self, code: char) -> Self {
+        self.reference.tracing_restriction = code;
+        self
+    }
+
+    /// Mark the reference as an earlier established form of the heading
+    /// (`$w/2` = `e`)
+    #[must_use]
+    pub fn earlier_form(mut self) -> Self {
+        self.reference.earlier_form = 'e';
+        self
+    }
+
+    /// Suppress display of the reference (`$w/3` = `a`)
+    #[must_use]
+    pub fn suppress_display(mut self) -> Self {
+        self.reference.display = 'a';
+        self
+    }
+
+    /// Build the reference
+    #[must_use]
+    pub fn build(self) -> Reference {
+        self.reference
+    }
+}
+
+fn tracing_tag(kind: ReferenceKind, heading_tag: &str) -> String {
+    tracing_tag_with(kind.tag_digit(), heading_tag)
+}
+
+fn tracing_tag_with(digit: char, tag: &str) -> String {
+    let mut out = String::with_capacity(3);
+    out.push(digit);
+    out.push_str(tag.get(1..).unwrap_or_default());
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authority_record::AuthorityRecord;
+    use crate::leader::Leader;
+
+    fn record() -> AuthorityRecord {
+        AuthorityRecord::new(Leader::from_bytes(b"00000nz  a2200000n  4500").unwrap())
+    }
+
+    #[test]
+    fn test_decode_control_subfield() {
+        let mut record = record();
+        record.add_see_from_tracing(
+            Field::builder("410".to_string(), '2', ' ')
+                .subfield_str('w', "nnna")
+                .subfield_str('a', "United States. Dept. of Agriculture")
+                .build(),
+        );
+        record.add_see_also_tracing(
+            Field::builder("510".to_string(), '2', ' ')
+                .subfield_str('w', "a")
+                .subfield_str('a', "United States. Bureau of Agriculture")
+                .subfield_str('0', "n79000001")
+                .build(),
+        );
+        record.add_see_also_tracing(
+            Field::builder("500".to_string(), '1', ' ')
+                .subfield_str('w', "r")
+                .subfield_str('i', "Alter ego:")
+                .subfield_str('a', "Smith, Jane")
+                .build(),
+        );
+
+        let references = record.references();
+        assert_eq!(references.len(), 3);
+
+        assert_eq!(references[0].kind, ReferenceKind::SeeFrom);
+        assert_eq!(
+            references[0].relationship,
+            SpecialRelationship::NotApplicable
+        );
+        assert!(!references[0].is_earlier_form());
+        assert!(!references[0].is_displayed());
+        assert_eq!(references[0].heading.tag, "110");
+
+        assert_eq!(
+            references[1].relationship,
+            SpecialRelationship::EarlierHeading
+        );
+        assert_eq!(references[1].tracing_restriction, 'n');
+        assert_eq!(references[1].control_number.as_deref(), Some("n79000001"));
+        assert!(references[1].is_displayed());
+
+        assert_eq!(
+            references[2].relationship,
+            SpecialRelationship::RelationshipDesignator
+        );
+        assert_eq!(references[2].designator.as_deref(), Some("Alter ego:"));
+        assert_eq!(references[2].heading.subfields.len(), 1);
+    }
+
+    #[test]
+    fn test_builder_round_trip() {
+        let reference = Reference::builder(
+            ReferenceKind::SeeFrom,
+            Field::builder("100".to_string(), '1', ' ')
+                .subfield_str('a', "Clemens, Samuel Langhorne,")
+                .subfield_str('d', "1835-1910")
+                .build(),
+        )
+        .earlier_form()
+        .build();
+
+        let field = reference.to_field();
+        assert_eq!(field.tag, "400");
+        assert_eq!(field.get_subfield('w'), Some("nne"));
+        assert_eq!(field.subfields[1].code, 'a');
+
+        let mut record = record();
+        record.add_reference(&reference);
+        let decoded = record.references();
+        assert_eq!(decoded, vec![reference]);
+        assert!(decoded[0].is_earlier_form());
+    }
+
+    #[test]
+    fn test_uncoded_reference_has_no_control_subfield() {
+        let reference = Reference::builder(
+            ReferenceKind::SeeAlso,
+            Field::builder("150".to_string(), ' ', ' ')
+                .subfield_str('a', "Motion pictures")
+                .build(),
+        )
+        .build();
+        let field = reference.to_field();
+        assert_eq!(field.tag, "550");
+        assert_eq!(field.get_subfield('w'), None);
+        assert_eq!(field.value(), "Motion pictures");
+    }
+}
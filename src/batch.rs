@@ -0,0 +1,350 @@
+//! One-call parallel conversion of an ISO 2709 stream.
+//!
+//! [`Batch`] wires the pipeline pieces together: the input is read in
+//! chunks, split at record terminators with the
+//! [`RecordBoundaryScanner`], parsed in parallel on a Rayon pool
+//! ([`parse_batch_parallel_map`]), and handed to any [`FormatWriter`] in
+//! input order. A progress callback runs after every chunk.
+//!
+//! The output format is chosen by the writer passed to [`Batch::to`], so any
+//! writer and its own options (a styled [`MarcxmlWriter`], a
+//! [`MarcWriter`] with an oversize policy, ...) can be used as-is.
+//!
+//! [`MarcxmlWriter`]: crate::marcxml::MarcxmlWriter
+//! [`MarcWriter`]: crate::MarcWriter
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use mrrc::batch::Batch;
+//! use mrrc::marcxml::MarcxmlWriter;
+//! use std::fs::File;
+//! use std::io::BufWriter;
+//!
+//! let input = File::open("records.mrc")?;
+//! let output = MarcxmlWriter::new(BufWriter::new(File::create("records.xml")?));
+//! let stats = Batch::new(input)
+//!     .to(output)
+//!     .threads(4)
+//!     .on_progress(|stats| eprint!("\r{} records", stats.records_written))
+//!     .run()?;
+//! eprintln!("\n{:.0} records/s", stats.records_per_second());
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::io::Read;
+use std::time::{Duration, Instant};
+
+use crate::boundary_scanner::RecordBoundaryScanner;
+use crate::error::{MarcError, Result};
+use crate::formats::FormatWriter;
+use crate::rayon_parser_pool::parse_batch_parallel_map;
+use crate::stats::Stats;
+
+/// Default number of input bytes read per chunk.
+pub const DEFAULT_CHUNK_BYTES: usize = 8 * 1024 * 1024;
+
+/// Placeholder output of a [`Batch`] that has not been given a writer yet.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoOutput;
+
+/// Counters reported to the progress callback and returned by
+/// [`Batch::run`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BatchStats {
+    /// Input bytes consumed so far
+    pub bytes_read: u64,
+    /// Records handed to the writer so far
+    pub records_written: u64,
+    /// Records that failed to parse or write and were skipped (see
+    /// [`Batch::skip_errors`])
+    pub records_skipped: u64,
+    /// Time since the run started
+    pub elapsed: Duration,
+}
+
+impl BatchStats {
+    /// Records written per second of elapsed time; zero before any time
+    /// has passed.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn records_per_second(&self) -> f64 {
+        let seconds = self.elapsed.as_secs_f64();
+        if seconds > 0.0 {
+            self.records_written as f64 / seconds
+        } else {
+            0.0
+        }
+    }
+}
+
+/// A configured conversion from an ISO 2709 input to a [`FormatWriter`].
+///
+/// Build one with [`Batch::new`], set the output with [`Batch::to`], and
+/// start it with [`Batch::run`].
+pub struct Batch<'a, R, W = NoOutput> {
+    input: R,
+    writer: W,
+    threads: Option<usize>,
+    chunk_bytes: usize,
+    skip_errors: bool,
+    progress: Option<Box<dyn FnMut(&BatchStats) + 'a>>,
+    stats: Option<Stats>,
+}
+
+impl<R, W: std::fmt::Debug> std::fmt::Debug for Batch<'_, R, W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Batch")
+            .field("writer", &self.writer)
+            .field("threads", &self.threads)
+            .field("chunk_bytes", &self.chunk_bytes)
+            .field("skip_errors", &self.skip_errors)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<R: Read> Batch<'_, R> {
+    /// Start a conversion reading ISO 2709 records from `input`.
+    #[must_use]
+    pub fn new(input: R) -> Self {
+        Batch {
+            input,
+            writer: NoOutput,
+            threads: None,
+            chunk_bytes: DEFAULT_CHUNK_BYTES,
+            skip_errors: false,
+            progress: None,
+            stats: None,
+        }
+    }
+
+    /// Convert everything in `input` to `writer` with the default settings;
+    /// shorthand for `Batch::new(input).to(writer).run()`.
+    ///
+    /// # Errors
+    ///
+    /// See [`Batch::run`].
+    pub fn convert<W: FormatWriter>(input: R, writer: W) -> Result<BatchStats> {
+        Batch::new(input).to(writer).run()
+    }
+}
+
+impl<'a, R: Read, W> Batch<'a, R, W> {
+    /// Write the converted records to `writer`, which also fixes the output
+    /// format.
+    #[must_use]
+    pub fn to<W2: FormatWriter>(self, writer: W2) -> Batch<'a, R, W2> {
+        Batch {
+            input: self.input,
+            writer,
+            threads: self.threads,
+            chunk_bytes: self.chunk_bytes,
+            skip_errors: self.skip_errors,
+            progress: self.progress,
+            stats: self.stats,
+        }
+    }
+
+    /// Parse on a dedicated pool of `n` threads instead of Rayon's global
+    /// pool.
+    #[must_use]
+    pub fn threads(mut self, n: usize) -> Self {
+        self.threads = Some(n.max(1));
+        self
+    }
+
+    /// Read the input `bytes` at a time (default [`DEFAULT_CHUNK_BYTES`]).
+    /// Larger chunks give the pool more records per round; a record longer
+    /// than the chunk is still read whole.
+    #[must_use]
+    pub fn chunk_bytes(mut self, bytes: usize) -> Self {
+        self.chunk_bytes = bytes.max(1);
+        self
+    }
+
+    /// Count and skip records that fail to parse or write instead of
+    /// stopping at the first one.
+    #[must_use]
+    pub fn skip_errors(mut self, skip: bool) -> Self {
+        self.skip_errors = skip;
+        self
+    }
+
+    /// Also count written records (with their input length) and skipped
+    /// records in `stats`, for monitoring from another thread.
+    #[must_use]
+    pub fn with_stats(mut self, stats: Stats) -> Self {
+        self.stats = Some(stats);
+        self
+    }
+
+    /// Call `progress` after every chunk and once more when the run ends.
+    #[must_use]
+    pub fn on_progress(mut self, progress: impl FnMut(&BatchStats) + 'a) -> Self {
+        self.progress = Some(Box::new(progress));
+        self
+    }
+}
+
+impl<R: Read, W: FormatWriter> Batch<'_, R, W> {
+    /// Run the conversion to the end of the input and finish the writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns I/O errors from the input, errors from the writer, and —
+    /// unless [`skip_errors`](Batch::skip_errors) is set — the first record
+    /// that fails to parse or write, with its 1-based index and byte offset
+    /// in the whole input.
+    pub fn run(mut self) -> Result<BatchStats> {
+        let pool = match self.threads {
+            Some(n) => Some(
+                rayon::ThreadPoolBuilder::new()
+                    .num_threads(n)
+                    .build()
+                    .map_err(|e| MarcError::from(std::io::Error::other(e)))?,
+            ),
+            None => None,
+        };
+        let started = Instant::now();
+        let mut stats = BatchStats::default();
+        let mut scanner = RecordBoundaryScanner::new();
+        let mut buffer: Vec<u8> = Vec::new();
+        // Byte offset of `buffer[0]` and number of records before it.
+        let mut base_offset = 0;
+        let mut base_index = 0;
+        loop {
+            let read = (&mut self.input)
+                .take(self.chunk_bytes as u64)
+                .read_to_end(&mut buffer)?;
+            stats.bytes_read += read as u64;
+            let at_end = read == 0;
+            let end = if at_end {
+                buffer.len()
+            } else {
+                memchr::memrchr(0x1D, &buffer).map_or(0, |pos| pos + 1)
+            };
+            if end > 0 {
+                let mut boundaries = scanner.scan(&buffer[..end]).unwrap_or_default();
+                let scanned: usize = boundaries.iter().map(|(_, len)| len).sum();
+                if scanned < end {
+                    // Trailing bytes without a terminator at end of input.
+                    boundaries.push((scanned, end - scanned));
+                }
+                let parsed = match &pool {
+                    Some(pool) => {
+                        pool.install(|| parse_batch_parallel_map(&boundaries, &buffer, Ok))
+                    },
+                    None => parse_batch_parallel_map(&boundaries, &buffer, Ok),
+                };
+                for (idx, result) in parsed {
+                    let length = boundaries[idx].1 as u64;
+                    let result = result
+                        .map_err(|e| {
+                            let byte_offset = e.metadata().byte_offset.map(|b| b + base_offset);
+                            e.with_record_index(Some(base_index + idx + 1))
+                                .with_byte_offset(byte_offset)
+                        })
+                        .and_then(|record| self.writer.write_record(&record));
+                    if let Some(shared) = &self.stats {
+                        match &result {
+                            Ok(()) => shared.add_record(length),
+                            Err(_) => shared.add_error(),
+                        }
+                    }
+                    match result {
+                        Ok(()) => stats.records_written += 1,
+                        Err(_) if self.skip_errors => stats.records_skipped += 1,
+                        Err(e) => return Err(e),
+                    }
+                }
+                base_offset += end;
+                base_index += boundaries.len();
+                buffer.drain(..end);
+                stats.elapsed = started.elapsed();
+                if let Some(progress) = &mut self.progress {
+                    progress(&stats);
+                }
+            }
+            if at_end {
+                break;
+            }
+        }
+        self.writer.finish()?;
+        stats.elapsed = started.elapsed();
+        if let Some(progress) = &mut self.progress {
+            progress(&stats);
+        }
+        Ok(stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::leader::Leader;
+    use crate::marcxml::MarcxmlWriter;
+    use crate::record::{Field, Record};
+    use crate::writer::MarcWriter;
+    use std::io::Cursor;
+
+    fn input(count: usize) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let mut writer = MarcWriter::new(&mut bytes);
+        for i in 0..count {
+            let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+            record.add_control_field_str("001", &format!("rec{i:03}"));
+            record.add_field(
+                Field::builder("245".to_string(), '0', '0')
+                    .subfield_str('a', "Batch title")
+                    .build(),
+            );
+            writer.write_record(&record).unwrap();
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_converts_in_order_across_chunks() {
+        let bytes = input(50);
+        let mut calls = 0;
+        let mut xml = Vec::new();
+        let stats = Batch::new(Cursor::new(&bytes))
+            .to(MarcxmlWriter::new(&mut xml))
+            .threads(3)
+            .chunk_bytes(100)
+            .on_progress(|_| calls += 1)
+            .run()
+            .unwrap();
+        assert_eq!(stats.records_written, 50);
+        assert_eq!(stats.bytes_read, bytes.len() as u64);
+        assert!(calls > 2);
+
+        let records = crate::marcxml::marcxml_to_records(&String::from_utf8(xml).unwrap()).unwrap();
+        let ids: Vec<_> = records
+            .iter()
+            .map(|r| r.get_control_field("001").unwrap().to_string())
+            .collect();
+        let expected: Vec<_> = (0..50).map(|i| format!("rec{i:03}")).collect();
+        assert_eq!(ids, expected);
+    }
+
+    #[test]
+    fn test_bad_records_error_or_skip() {
+        let mut bytes = input(3);
+        bytes.extend_from_slice(b"garbage\x1D");
+        bytes.extend(input(1));
+        bytes.extend_from_slice(b"trailing");
+
+        let err = Batch::convert(Cursor::new(&bytes), MarcWriter::new(Vec::new())).unwrap_err();
+        assert_eq!(err.metadata().record_index, Some(4));
+
+        let mut out = Vec::new();
+        let stats = Batch::new(Cursor::new(&bytes))
+            .to(MarcWriter::new(&mut out))
+            .skip_errors(true)
+            .run()
+            .unwrap();
+        assert_eq!(stats.records_written, 4);
+        assert_eq!(stats.records_skipped, 2);
+    }
+}
@@ -0,0 +1,591 @@
+//! Parallel reading and writing of block-gzipped ISO 2709 files.
+//!
+//! A BGZF file (the `bgzip` format) is a series of independent gzip
+//! members, each holding at most 64 KiB of uncompressed data and recording
+//! its own compressed size in a `BC` extra subfield. Because member
+//! boundaries can be found from the headers alone, a batch of members can
+//! be inflated concurrently and the records they contain parsed on the
+//! Rayon pool, without decompressing the whole file to disk first.
+//!
+//! [`BgzfReader`] reads batches of records this way. Ordinary gzip input,
+//! including multi-member files written by concatenating `.gz` files, is
+//! accepted too: it is inflated sequentially and only the parsing runs in
+//! parallel. [`BgzfWriter`] produces BGZF output together with a
+//! [`GziIndex`], the block index `bgzip -r` writes as a `.gzi` file.
+//!
+//! ```no_run
+//! use mrrc::bgzf::BgzfReader;
+//! use std::fs::File;
+//!
+//! # fn main() -> mrrc::Result<()> {
+//! let mut reader = BgzfReader::new(File::open("dump.mrc.gz")?).members_per_batch(256);
+//! while let Some(batch) = reader.read_batch()? {
+//!     println!("{} records", batch.len());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Requires the `gzip` cargo feature.
+
+use std::fmt;
+use std::io::{Chain, Cursor, Read, Write};
+
+use flate2::Compression;
+use flate2::Crc;
+use flate2::read::{GzDecoder, MultiGzDecoder};
+use flate2::write::DeflateEncoder;
+use rayon::prelude::*;
+
+use crate::error::{MarcError, Result};
+use crate::formats::FormatWriter;
+use crate::rayon_parser_pool::parse_batch_parallel;
+use crate::record::Record;
+
+/// Largest uncompressed payload written into one BGZF block.
+///
+/// Matches `bgzip`, which leaves headroom below 64 KiB so that even
+/// incompressible data fits the 16-bit block size.
+pub const BLOCK_DATA_LEN: usize = 0xff00;
+
+/// The empty block that terminates a BGZF file.
+const EOF_BLOCK: [u8; 28] = [
+    0x1f, 0x8b, 0x08, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff, 0x06, 0x00, 0x42, 0x43, 0x02, 0x00,
+    0x1b, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+
+/// Fixed part of a gzip member header, up to and including XLEN.
+const HEADER_LEN: usize = 12;
+const FLAG_EXTRA: u8 = 0x04;
+const RECORD_TERMINATOR: u8 = 0x1D;
+
+/// Uncompressed bytes inflated per batch when the input is not BGZF.
+const STREAM_BLOCK_LEN: usize = 0x10000;
+
+/// The next gzip member of the input.
+enum Member {
+    /// A complete BGZF block, header included
+    Block(Vec<u8>),
+    /// A member without a BGZF size; holds the header bytes already read
+    Plain(Vec<u8>),
+}
+
+enum Source<R: Read> {
+    Blocks(R),
+    Stream(Box<MultiGzDecoder<Chain<Cursor<Vec<u8>>, R>>>),
+}
+
+/// Reads ISO 2709 records from gzip input in parallel batches.
+///
+/// Each call to [`read_batch`](Self::read_batch) reads up to
+/// `members_per_batch` members, inflates them concurrently, and parses the
+/// complete records found so far in parallel. Records that straddle a
+/// member boundary are carried over into the next batch.
+pub struct BgzfReader<R: Read> {
+    source: Option<Source<R>>,
+    members_per_batch: usize,
+    carry: Vec<u8>,
+    finished: bool,
+    records_read: usize,
+}
+
+impl<R: Read> fmt::Debug for BgzfReader<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BgzfReader")
+            .field("members_per_batch", &self.members_per_batch)
+            .field("carry", &self.carry.len())
+            .field("finished", &self.finished)
+            .field("records_read", &self.records_read)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<R: Read> BgzfReader<R> {
+    /// Create a reader over gzip or BGZF input.
+    pub fn new(reader: R) -> Self {
+        BgzfReader {
+            source: Some(Source::Blocks(reader)),
+            members_per_batch: 64,
+            carry: Vec::new(),
+            finished: false,
+            records_read: 0,
+        }
+    }
+
+    /// Set how many gzip members are inflated per batch (default 64).
+    ///
+    /// Larger batches give the thread pool more work at once at the cost of
+    /// memory: each BGZF member holds up to 64 KiB.
+    #[must_use]
+    pub fn members_per_batch(mut self, members: usize) -> Self {
+        self.members_per_batch = members.max(1);
+        self
+    }
+
+    /// Number of records returned so far.
+    #[must_use]
+    pub fn records_read(&self) -> usize {
+        self.records_read
+    }
+
+    /// Read the next batch of records, or `None` at the end of the input.
+    ///
+    /// A batch may be empty when the members read so far hold no complete
+    /// record; keep calling until `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input is not valid gzip, a member fails its
+    /// CRC check, a record fails to parse, or the input ends partway
+    /// through a record.
+    pub fn read_batch(&mut self) -> Result<Option<Vec<Record>>> {
+        if self.finished {
+            return Ok(None);
+        }
+        let at_end = self.fill()?;
+        let complete = memchr::memrchr(RECORD_TERMINATOR, &self.carry).map_or(0, |pos| pos + 1);
+        if at_end {
+            self.finished = true;
+            if complete < self.carry.len() {
+                return Err(MarcError::invalid_field_msg(format!(
+                    "compressed input ends inside a record ({} trailing bytes)",
+                    self.carry.len() - complete
+                )));
+            }
+            if complete == 0 {
+                return Ok(None);
+            }
+        }
+
+        let boundaries = record_boundaries(&self.carry[..complete]);
+        let records = parse_batch_parallel(&boundaries, &self.carry[..complete])?;
+        self.carry.drain(..complete);
+        self.records_read += records.len();
+        crate::telemetry::records_read(records.len() as u64);
+        Ok(Some(records))
+    }
+
+    /// Append the next batch of inflated bytes to `carry`; returns `true`
+    /// once the input is exhausted.
+    fn fill(&mut self) -> Result<bool> {
+        match self.source.take() {
+            Some(Source::Blocks(mut reader)) => {
+                let mut blocks = Vec::with_capacity(self.members_per_batch);
+                let mut plain = None;
+                while blocks.len() < self.members_per_batch {
+                    match next_member(&mut reader)? {
+                        Some(Member::Block(block)) => blocks.push(block),
+                        Some(Member::Plain(header)) => {
+                            plain = Some(header);
+                            break;
+                        },
+                        None => break,
+                    }
+                }
+                let at_end = blocks.len() < self.members_per_batch && plain.is_none();
+                let inflated = blocks
+                    .par_iter()
+                    .map(|block| inflate_member(block))
+                    .collect::<Result<Vec<_>>>()?;
+                for data in inflated {
+                    self.carry.extend_from_slice(&data);
+                }
+                self.source = Some(match plain {
+                    Some(header) => Source::Stream(Box::new(MultiGzDecoder::new(
+                        Cursor::new(header).chain(reader),
+                    ))),
+                    None => Source::Blocks(reader),
+                });
+                Ok(at_end)
+            },
+            Some(Source::Stream(mut decoder)) => {
+                let limit = (self.members_per_batch * STREAM_BLOCK_LEN) as u64;
+                let read = (&mut decoder).take(limit).read_to_end(&mut self.carry)?;
+                self.source = Some(Source::Stream(decoder));
+                Ok((read as u64) < limit)
+            },
+            None => Ok(true),
+        }
+    }
+}
+
+impl<R: Read> Iterator for BgzfReader<R> {
+    type Item = Result<Vec<Record>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_batch().transpose()
+    }
+}
+
+/// Boundaries of the records in `buffer`, which ends with a terminator.
+fn record_boundaries(buffer: &[u8]) -> Vec<(usize, usize)> {
+    let mut boundaries = Vec::new();
+    let mut offset = 0;
+    for end in memchr::memchr_iter(RECORD_TERMINATOR, buffer) {
+        boundaries.push((offset, end + 1 - offset));
+        offset = end + 1;
+    }
+    boundaries
+}
+
+/// Read one gzip member header and, if it carries a BGZF block size, the
+/// rest of the block.
+fn next_member<R: Read>(reader: &mut R) -> Result<Option<Member>> {
+    let mut header = vec![0u8; HEADER_LEN];
+    let first = read_up_to(reader, &mut header)?;
+    if first == 0 {
+        return Ok(None);
+    }
+    header.truncate(first);
+    if first < HEADER_LEN || header[3] & FLAG_EXTRA == 0 {
+        return Ok(Some(Member::Plain(header)));
+    }
+
+    let extra_len = usize::from(u16::from_le_bytes([header[10], header[11]]));
+    let mut extra = vec![0u8; extra_len];
+    let got = read_up_to(reader, &mut extra)?;
+    header.extend_from_slice(&extra[..got]);
+    if got < extra_len {
+        return Ok(Some(Member::Plain(header)));
+    }
+    let Some(block_size) = bgzf_block_size(&extra) else {
+        return Ok(Some(Member::Plain(header)));
+    };
+
+    let total = usize::from(block_size) + 1;
+    if total < header.len() {
+        return Err(MarcError::invalid_field_msg(format!(
+            "BGZF block size {total} is smaller than its header"
+        )));
+    }
+    let mut block = header;
+    let start = block.len();
+    block.resize(total, 0);
+    reader.read_exact(&mut block[start..])?;
+    Ok(Some(Member::Block(block)))
+}
+
+/// The BSIZE value of a `BC` subfield in a gzip extra field.
+fn bgzf_block_size(extra: &[u8]) -> Option<u16> {
+    let mut rest = extra;
+    while rest.len() >= 4 {
+        let len = usize::from(u16::from_le_bytes([rest[2], rest[3]]));
+        let data = rest.get(4..4 + len)?;
+        if rest[0] == b'B' && rest[1] == b'C' && len == 2 {
+            return Some(u16::from_le_bytes([data[0], data[1]]));
+        }
+        rest = &rest[4 + len..];
+    }
+    None
+}
+
+fn read_up_to<R: Read>(reader: &mut R, buf: &mut [u8]) -> Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {},
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(filled)
+}
+
+fn inflate_member(block: &[u8]) -> Result<Vec<u8>> {
+    let mut data = Vec::with_capacity(BLOCK_DATA_LEN);
+    GzDecoder::new(block).read_to_end(&mut data)?;
+    Ok(data)
+}
+
+/// Block index of a BGZF file, in the `.gzi` layout used by `bgzip`.
+///
+/// Each entry maps the compressed offset of a block to the uncompressed
+/// offset of its first byte. The first block (at offset 0, 0) is implied
+/// and not stored.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GziIndex {
+    /// (compressed offset, uncompressed offset) of every block after the first
+    pub entries: Vec<(u64, u64)>,
+}
+
+impl GziIndex {
+    /// The block containing `uncompressed_offset`, as its
+    /// (compressed offset, uncompressed offset) pair.
+    #[must_use]
+    pub fn block_for(&self, uncompressed_offset: u64) -> (u64, u64) {
+        let idx = self
+            .entries
+            .partition_point(|&(_, start)| start <= uncompressed_offset);
+        if idx == 0 {
+            (0, 0)
+        } else {
+            self.entries[idx - 1]
+        }
+    }
+
+    /// Write the index in `.gzi` form: a little-endian entry count followed
+    /// by the offset pairs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    pub fn write_to<W: Write>(&self, mut writer: W) -> Result<()> {
+        writer.write_all(&(self.entries.len() as u64).to_le_bytes())?;
+        for (compressed, uncompressed) in &self.entries {
+            writer.write_all(&compressed.to_le_bytes())?;
+            writer.write_all(&uncompressed.to_le_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Read an index in `.gzi` form.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input is truncated or reading fails.
+    pub fn read_from<R: Read>(mut reader: R) -> Result<Self> {
+        let count = read_u64(&mut reader)?;
+        let mut entries = Vec::new();
+        for _ in 0..count {
+            entries.push((read_u64(&mut reader)?, read_u64(&mut reader)?));
+        }
+        Ok(GziIndex { entries })
+    }
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> Result<u64> {
+    let mut buf = [0u8; 8];
+    reader.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+/// Writes ISO 2709 records as BGZF and keeps a block index.
+///
+/// Records are packed into blocks of up to [`BLOCK_DATA_LEN`] bytes, and a
+/// record may span blocks. [`finish`](FormatWriter::finish) flushes the
+/// last block and writes the empty end-of-file block; the index is then
+/// available from [`index`](Self::index).
+pub struct BgzfWriter<W: Write> {
+    writer: W,
+    level: Compression,
+    block: Vec<u8>,
+    compressed_offset: u64,
+    uncompressed_offset: u64,
+    index: GziIndex,
+    records_written: usize,
+    finished: bool,
+}
+
+impl<W: Write> fmt::Debug for BgzfWriter<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BgzfWriter")
+            .field("level", &self.level)
+            .field("compressed_offset", &self.compressed_offset)
+            .field("records_written", &self.records_written)
+            .field("finished", &self.finished)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<W: Write> BgzfWriter<W> {
+    /// Create a writer using the default compression level.
+    pub fn new(writer: W) -> Self {
+        BgzfWriter {
+            writer,
+            level: Compression::default(),
+            block: Vec::with_capacity(BLOCK_DATA_LEN),
+            compressed_offset: 0,
+            uncompressed_offset: 0,
+            index: GziIndex::default(),
+            records_written: 0,
+            finished: false,
+        }
+    }
+
+    /// Set the deflate compression level, from 0 (none) to 9 (best).
+    #[must_use]
+    pub fn with_level(mut self, level: u32) -> Self {
+        self.level = Compression::new(level.min(9));
+        self
+    }
+
+    /// The block index of everything written so far.
+    #[must_use]
+    pub fn index(&self) -> &GziIndex {
+        &self.index
+    }
+
+    /// Consume the writer and return the underlying output.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn flush_block(&mut self) -> Result<()> {
+        if self.block.is_empty() {
+            return Ok(());
+        }
+        let len = self.block.len().min(BLOCK_DATA_LEN);
+        let data: Vec<u8> = self.block.drain(..len).collect();
+
+        let mut encoder = DeflateEncoder::new(Vec::new(), self.level);
+        encoder.write_all(&data)?;
+        let deflated = encoder.finish()?;
+        let mut crc = Crc::new();
+        crc.update(&data);
+
+        let total = deflated.len() + 26;
+        let block_size = u16::try_from(total - 1).map_err(|_| {
+            MarcError::invalid_field_msg(format!("BGZF block of {total} bytes exceeds 64 KiB"))
+        })?;
+        if self.compressed_offset > 0 {
+            self.index
+                .entries
+                .push((self.compressed_offset, self.uncompressed_offset));
+        }
+
+        let mut out = Vec::with_capacity(total);
+        out.extend_from_slice(&EOF_BLOCK[..16]);
+        out.extend_from_slice(&block_size.to_le_bytes());
+        out.extend_from_slice(&deflated);
+        out.extend_from_slice(&crc.sum().to_le_bytes());
+        // `data` holds at most BLOCK_DATA_LEN bytes, so its length fits ISIZE.
+        out.extend_from_slice(&(data.len() as u64).to_le_bytes()[..4]);
+        self.writer.write_all(&out)?;
+
+        self.compressed_offset += total as u64;
+        self.uncompressed_offset += data.len() as u64;
+        Ok(())
+    }
+}
+
+impl<W: Write> FormatWriter for BgzfWriter<W> {
+    fn write_record(&mut self, record: &Record) -> Result<()> {
+        if self.finished {
+            return Err(MarcError::invalid_field_msg(
+                "cannot write to a finished BGZF writer",
+            ));
+        }
+        self.block.extend_from_slice(&record.to_iso2709_bytes()?);
+        while self.block.len() >= BLOCK_DATA_LEN {
+            self.flush_block()?;
+        }
+        self.records_written += 1;
+        crate::telemetry::records_written(1);
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        if self.finished {
+            return Ok(());
+        }
+        self.flush_block()?;
+        self.writer.write_all(&EOF_BLOCK)?;
+        self.writer.flush()?;
+        self.finished = true;
+        Ok(())
+    }
+
+    fn records_written(&self) -> Option<usize> {
+        Some(self.records_written)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reader::MarcReader;
+    use flate2::write::GzEncoder;
+
+    fn fixture_records() -> Vec<Record> {
+        let mut reader = MarcReader::from_path("tests/data/fixtures/1k_records.mrc").unwrap();
+        let mut records = Vec::new();
+        while let Some(record) = reader.read_record().unwrap() {
+            records.push(record);
+        }
+        records
+    }
+
+    fn read_all<R: Read>(reader: BgzfReader<R>) -> Vec<Record> {
+        reader
+            .collect::<Result<Vec<_>>>()
+            .unwrap()
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    fn control_numbers(records: &[Record]) -> Vec<Option<String>> {
+        records
+            .iter()
+            .map(|r| r.get_control_field("001").map(str::to_string))
+            .collect()
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn bgzf_round_trip_with_index() {
+        let records = fixture_records();
+        let mut writer = BgzfWriter::new(Vec::new());
+        for record in &records {
+            writer.write_record(record).unwrap();
+        }
+        writer.finish().unwrap();
+        let index = writer.index().clone();
+        let bytes = writer.into_inner();
+        assert!(
+            index.entries.len() > 2,
+            "fixture should span several blocks"
+        );
+        assert!(bytes.ends_with(&EOF_BLOCK));
+
+        // Each index entry points at the start of a gzip member.
+        for &(compressed, _) in &index.entries {
+            let start = usize::try_from(compressed).unwrap();
+            assert_eq!(&bytes[start..start + 2], &[0x1f, 0x8b]);
+        }
+        assert_eq!(index.block_for(0), (0, 0));
+        assert_eq!(index.block_for(index.entries[1].1), index.entries[1]);
+
+        let mut gzi = Vec::new();
+        index.write_to(&mut gzi).unwrap();
+        assert_eq!(GziIndex::read_from(gzi.as_slice()).unwrap(), index);
+
+        let read = read_all(BgzfReader::new(bytes.as_slice()).members_per_batch(2));
+        assert_eq!(control_numbers(&read), control_numbers(&records));
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn reads_plain_multi_member_gzip() {
+        let records = fixture_records();
+        let (first, second) = records.split_at(records.len() / 2);
+        let mut bytes = Vec::new();
+        for part in [first, second] {
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::fast());
+            for record in part {
+                encoder
+                    .write_all(&record.to_iso2709_bytes().unwrap())
+                    .unwrap();
+            }
+            bytes.extend_from_slice(&encoder.finish().unwrap());
+        }
+
+        let read = read_all(BgzfReader::new(bytes.as_slice()).members_per_batch(1));
+        assert_eq!(control_numbers(&read), control_numbers(&records));
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn truncated_record_is_an_error() {
+        let records = fixture_records();
+        let mut data = records[0].to_iso2709_bytes().unwrap();
+        data.truncate(data.len() - 10);
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::fast());
+        encoder.write_all(&data).unwrap();
+        let bytes = encoder.finish().unwrap();
+
+        let mut reader = BgzfReader::new(bytes.as_slice());
+        assert!(reader.any(|batch| batch.is_err()));
+    }
+}
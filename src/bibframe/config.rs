@@ -18,6 +18,10 @@ pub enum RdfFormat {
     Turtle,
     /// N-Triples format (application/n-triples) - Simple, line-based
     NTriples,
+    /// N-Quads format (application/n-quads) - N-Triples with a graph name per line
+    NQuads,
+    /// `TriG` format (application/trig) - Turtle with named graphs
+    TriG,
 }
 
 impl fmt::Display for RdfFormat {
@@ -27,6 +31,8 @@ impl fmt::Display for RdfFormat {
             Self::JsonLd => write!(f, "JSON-LD"),
             Self::Turtle => write!(f, "Turtle"),
             Self::NTriples => write!(f, "N-Triples"),
+            Self::NQuads => write!(f, "N-Quads"),
+            Self::TriG => write!(f, "TriG"),
         }
     }
 }
@@ -40,6 +46,8 @@ impl RdfFormat {
             Self::JsonLd => "application/ld+json",
             Self::Turtle => "text/turtle",
             Self::NTriples => "application/n-triples",
+            Self::NQuads => "application/n-quads",
+            Self::TriG => "application/trig",
         }
     }
 
@@ -51,6 +59,8 @@ impl RdfFormat {
             Self::JsonLd => "jsonld",
             Self::Turtle => "ttl",
             Self::NTriples => "nt",
+            Self::NQuads => "nq",
+            Self::TriG => "trig",
         }
     }
 }
@@ -98,6 +108,30 @@ pub struct BibframeConfig {
     /// When false, all entities use local URIs or blank nodes.
     pub link_authorities: bool,
 
+    /// Record the vocabulary of each subject heading.
+    ///
+    /// When true (default), 6XX subjects get a `bf:source` URI for the
+    /// scheme named by the second indicator or `$2` (LCSH, LC/NAF, `MeSH`,
+    /// FAST, ...) and, for schemes published by LC or OCLC, a
+    /// `madsrdf:isMemberOfMADSScheme` link.
+    pub subject_schemes: bool,
+
+    /// Describe 533 reproductions as Instances of their own.
+    ///
+    /// When true, each 533 becomes a second Instance of the Work, linked to
+    /// the original with `bf:hasReproduction`/`bf:reproductionOf` and
+    /// carrying the reproduction's type, provision activity, extent and
+    /// note. When false (default), 533 is not converted.
+    pub reproduction_instances: bool,
+
+    /// Describe 776 additional physical forms as Instances of the Work.
+    ///
+    /// When true, each 776 Instance is also linked to the Work with
+    /// `bf:hasInstance`/`bf:instanceOf`, so the Work has one Instance per
+    /// format. When false (default), 776 entries are only related to the
+    /// record's Instance through `bf:otherPhysicalFormat`.
+    pub multi_instance: bool,
+
     // === Output Control ===
     /// Output format for RDF serialization.
     pub output_format: RdfFormat,
@@ -115,6 +149,13 @@ pub struct BibframeConfig {
     /// for debugging and provenance tracking. Increases output size.
     pub include_source: bool,
 
+    /// Put the record's triples in a named graph of their own.
+    ///
+    /// The graph is `{base}graph/{id}` when a base URI is set (`id` as for
+    /// entity URIs), otherwise a blank node. Serialize with N-Quads or `TriG`
+    /// to keep the graph names.
+    pub named_graphs: bool,
+
     // === Error Handling ===
     /// Stop on first conversion error.
     ///
@@ -135,9 +176,13 @@ impl Default for BibframeConfig {
             base_uri: None,
             use_control_number: true,
             link_authorities: false,
+            subject_schemes: true,
+            reproduction_instances: false,
+            multi_instance: false,
             output_format: RdfFormat::default(),
             include_bflc: true,
             include_source: false,
+            named_graphs: false,
             fail_fast: false,
             strict: false,
         }
@@ -172,6 +217,34 @@ impl BibframeConfig {
         self
     }
 
+    /// Enables subject vocabulary (`bf:source`) URIs.
+    #[must_use]
+    pub const fn with_subject_schemes(mut self, enabled: bool) -> Self {
+        self.subject_schemes = enabled;
+        self
+    }
+
+    /// Converts 533 reproductions to Instances of their own.
+    #[must_use]
+    pub const fn with_reproduction_instances(mut self, enabled: bool) -> Self {
+        self.reproduction_instances = enabled;
+        self
+    }
+
+    /// Makes 776 additional physical forms Instances of the Work.
+    #[must_use]
+    pub const fn with_multi_instance(mut self, enabled: bool) -> Self {
+        self.multi_instance = enabled;
+        self
+    }
+
+    /// Places each converted record in its own named graph.
+    #[must_use]
+    pub const fn with_named_graphs(mut self, enabled: bool) -> Self {
+        self.named_graphs = enabled;
+        self
+    }
+
     /// Enables strict validation mode.
     #[must_use]
     pub const fn with_strict_mode(mut self, enabled: bool) -> Self {
@@ -197,6 +270,9 @@ mod tests {
         assert!(config.base_uri.is_none());
         assert!(config.use_control_number);
         assert!(!config.link_authorities);
+        assert!(config.subject_schemes);
+        assert!(!config.reproduction_instances);
+        assert!(!config.multi_instance);
         assert_eq!(config.output_format, RdfFormat::JsonLd);
         assert!(config.include_bflc);
         assert!(!config.include_source);
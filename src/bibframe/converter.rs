@@ -3,10 +3,16 @@
 //! This module implements the core conversion from MARC bibliographic records
 //! to BIBFRAME 2.0 RDF graphs following LOC specifications.
 
+use crate::codes::CodeList;
+use crate::holdings_item::Item;
 use crate::record::{Field, Record};
 
 use super::config::BibframeConfig;
-use super::namespaces::{BF, BFLC, RDF, RDFS, RELATORS, classes, properties};
+use super::namespaces::{
+    BF, BFLC, CARRIER_TYPES, CONTENT_TYPES, FAST, GENRE_FORM_SCHEMES, LANGUAGES,
+    LC_CHILDRENS_SUBJECTS, LC_GENRE_FORMS, LC_NAMES, LC_SUBJECTS, MADSRDF, MEDIA_TYPES, RDF, RDFS,
+    RELATORS, SUBJECT_SCHEMES, classes, properties,
+};
 use super::rdf::{RdfGraph, RdfNode};
 
 /// Converts a MARC record to a BIBFRAME RDF graph.
@@ -17,6 +23,46 @@ pub fn convert_marc_to_bibframe(record: &Record, config: &BibframeConfig) -> Rdf
     converter.convert()
 }
 
+/// URI of the named graph for `record`: `{base}graph/{id}`, with the same
+/// `id` as the record's entity URIs. `None` without a base URI.
+pub(super) fn record_graph_uri(record: &Record, config: &BibframeConfig) -> Option<String> {
+    let base = config.base_uri.as_ref()?;
+    let id = if config.use_control_number {
+        record
+            .control_fields
+            .get("001")
+            .and_then(|v| v.first())
+            .map_or("unknown", String::as_str)
+    } else {
+        "unknown"
+    };
+    Some(format!("{base}graph/{id}"))
+}
+
+pub(super) use crate::subjects::SUBJECT_INDICATOR_SOURCES;
+
+/// Source code of the vocabulary a 6XX heading comes from, when it is
+/// usable as a URI path segment.
+fn subject_source_code(field: &Field) -> Option<String> {
+    crate::subjects::heading_source(field).filter(|code| {
+        code.chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    })
+}
+
+/// MADS/RDF scheme of the authority files published as linked data.
+fn mads_scheme(code: &str) -> Option<&'static str> {
+    let namespace = match code {
+        "lcsh" => LC_SUBJECTS,
+        "naf" => LC_NAMES,
+        "lcshac" => LC_CHILDRENS_SUBJECTS,
+        "lcgft" => LC_GENRE_FORMS,
+        "fast" => FAST,
+        _ => return None,
+    };
+    Some(namespace.trim_end_matches('/'))
+}
+
 /// Internal converter state.
 struct MarcToBibframeConverter<'a> {
     record: &'a Record,
@@ -60,6 +106,7 @@ impl<'a> MarcToBibframeConverter<'a> {
         // Process field groups
         self.process_uniform_title(); // 240 uniform title -> Hub
         self.process_titles();
+        self.process_languages();
         self.process_creators();
         self.process_contributors();
         self.process_subjects();
@@ -67,11 +114,13 @@ impl<'a> MarcToBibframeConverter<'a> {
         self.process_classification();
         self.process_provision_activity();
         self.process_physical_description();
+        self.process_content_types();
         self.process_notes();
 
         // Edge case handling (uab.4.4)
         self.process_880_linked_fields();
         self.process_linking_entries();
+        self.process_reproductions();
         self.process_series();
         self.process_format_specific_fields();
 
@@ -83,6 +132,12 @@ impl<'a> MarcToBibframeConverter<'a> {
             self.add_admin_metadata();
         }
 
+        if self.config.named_graphs {
+            let name = record_graph_uri(self.record, self.config)
+                .map_or_else(|| self.graph.new_blank_node(), RdfNode::uri);
+            self.graph.set_graph_name(Some(name));
+        }
+
         self.graph
     }
 
@@ -215,6 +270,12 @@ impl<'a> MarcToBibframeConverter<'a> {
 
     /// Generates a URI or blank node for an entity.
     fn generate_entity_uri(&mut self, entity_type: &str) -> RdfNode {
+        self.generate_entity_uri_with_suffix(entity_type, "")
+    }
+
+    /// Generates a URI or blank node for an additional entity of the record,
+    /// distinguished from the main one by `suffix` (`{base}{type}/{id}{suffix}`).
+    fn generate_entity_uri_with_suffix(&mut self, entity_type: &str, suffix: &str) -> RdfNode {
         if let Some(ref base) = self.config.base_uri {
             let id = if self.config.use_control_number {
                 self.record
@@ -225,7 +286,7 @@ impl<'a> MarcToBibframeConverter<'a> {
             } else {
                 "unknown"
             };
-            RdfNode::uri(format!("{base}{entity_type}/{id}"))
+            RdfNode::uri(format!("{base}{entity_type}/{id}{suffix}"))
         } else {
             self.graph.new_blank_node()
         }
@@ -540,32 +601,57 @@ impl<'a> MarcToBibframeConverter<'a> {
         );
     }
 
-    /// Adds relator role from $4 (code) or $e (term).
+    /// Adds relator roles from $4 (code or URI) and $e (term; $j in X11
+    /// meeting fields).
+    ///
+    /// Codes and terms are normalized through the MARC relator list, so
+    /// `$e author.` and `$4 aut` both give the `relators/aut` role, and a
+    /// role given both ways is added once. Terms that match no relator are
+    /// kept as literals when the field has no `$4`.
     fn add_relator_role(&mut self, contribution: &RdfNode, field: &Field) {
+        let term_code = if field.tag.ends_with("11") { 'j' } else { 'e' };
+        let has_codes = field.subfields.iter().any(|s| s.code == '4');
+        let mut roles: Vec<RdfNode> = Vec::new();
         for subfield in &field.subfields {
-            match subfield.code {
-                '4' => {
-                    // Relator code - link to id.loc.gov vocabulary
-                    let code = subfield.value.trim().to_lowercase();
-                    if !code.is_empty() {
-                        self.graph.add(
-                            contribution.clone(),
-                            format!("{BF}{}", properties::ROLE),
-                            RdfNode::uri(format!("{RELATORS}{code}")),
-                        );
+            let value = subfield.value.trim();
+            if value.is_empty() {
+                continue;
+            }
+            if subfield.code == '4' {
+                // Unrecognized codes and other vocabularies' URIs pass through
+                let role = match crate::codes::normalize_relator(value) {
+                    Some(code) => RdfNode::uri(format!("{RELATORS}{code}")),
+                    None if value.contains("://") => RdfNode::uri(value),
+                    None => RdfNode::uri(format!("{RELATORS}{}", value.to_lowercase())),
+                };
+                roles.push(role);
+            } else if subfield.code == term_code {
+                let codes = crate::codes::normalize_relator_terms(value);
+                if codes.is_empty() {
+                    if !has_codes {
+                        roles.push(RdfNode::literal(&subfield.value));
                     }
-                },
-                'e' if !field.subfields.iter().any(|s| s.code == '4') => {
-                    // Relator term - use as literal if no $4
-                    self.graph.add(
-                        contribution.clone(),
-                        format!("{BF}{}", properties::ROLE),
-                        RdfNode::literal(&subfield.value),
+                } else {
+                    roles.extend(
+                        codes
+                            .into_iter()
+                            .map(|code| RdfNode::uri(format!("{RELATORS}{code}"))),
                     );
-                },
-                _ => {},
+                }
             }
         }
+        let mut seen: Vec<&RdfNode> = Vec::new();
+        for role in &roles {
+            if seen.contains(&role) {
+                continue;
+            }
+            seen.push(role);
+            self.graph.add(
+                contribution.clone(),
+                format!("{BF}{}", properties::ROLE),
+                role.clone(),
+            );
+        }
     }
 
     /// Processes 6XX fields (subjects).
@@ -636,6 +722,10 @@ impl<'a> MarcToBibframeConverter<'a> {
             RdfNode::bf_class(subject_type),
         );
 
+        if self.config.subject_schemes {
+            self.add_subject_source(&subject_node, field);
+        }
+
         // Extract label from $a (and subdivisions)
         let mut label_parts = Vec::new();
         for subfield in &field.subfields {
@@ -664,6 +754,32 @@ impl<'a> MarcToBibframeConverter<'a> {
         );
     }
 
+    /// Adds the vocabulary of a subject heading as a `bf:source` code URI
+    /// and, for LCSH, LC/NAF, LCGFT and FAST, its MADS/RDF scheme.
+    fn add_subject_source(&mut self, subject: &RdfNode, field: &Field) {
+        let Some(code) = subject_source_code(field) else {
+            return;
+        };
+        // 655 $2 codes come from the genre/form source list
+        let codes = if field.tag == "655" && field.indicator2 == '7' {
+            GENRE_FORM_SCHEMES
+        } else {
+            SUBJECT_SCHEMES
+        };
+        self.graph.add(
+            subject.clone(),
+            format!("{BF}{}", properties::SOURCE),
+            RdfNode::uri(format!("{codes}{code}")),
+        );
+        if let Some(scheme) = mads_scheme(&code) {
+            self.graph.add(
+                subject.clone(),
+                format!("{MADSRDF}isMemberOfMADSScheme"),
+                RdfNode::uri(scheme),
+            );
+        }
+    }
+
     /// Processes identifier fields (020, 022, 024, 035, etc.).
     fn process_identifiers(&mut self) {
         let instance = match &self.instance_node {
@@ -1382,6 +1498,199 @@ impl<'a> MarcToBibframeConverter<'a> {
         }
     }
 
+    /// Processes 008/35-37 and 041 into Work languages.
+    ///
+    /// The 008 language and 041 `$a`/`$d` (text, sung or spoken) are the
+    /// Work's languages, linked directly to their `id.loc.gov` URIs. The
+    /// other 041 subfields describe a part of the resource (original,
+    /// subtitles, libretto, ...) and become `bf:Language` nodes naming the
+    /// part. Codes run together in one subfield (`engfre`), as older
+    /// records have them, are split; codes not in the MARC language list,
+    /// or from a `$2` source other than ISO 639-2, are kept as literals.
+    fn process_languages(&mut self) {
+        let Some(work) = self.work_node.clone() else {
+            return;
+        };
+        let property = format!("{BF}{}", properties::LANGUAGE);
+        let mut seen = Vec::new();
+
+        if let Some(fixed) = self.record.get_control_field("008")
+            && let Some(code) = fixed.get(35..38)
+            && let Some(entry) = CodeList::Language.get(code)
+        {
+            seen.push(entry.code);
+            self.graph.add(
+                work.clone(),
+                property.clone(),
+                RdfNode::uri(format!("{LANGUAGES}{}", entry.code)),
+            );
+        }
+
+        let Some(fields) = self.record.fields.get("041") else {
+            return;
+        };
+        for field in fields {
+            let marc_codes = field.indicator2 != '7'
+                || field
+                    .get_subfield('2')
+                    .is_some_and(|source| source.trim().starts_with("iso639-2"));
+            for subfield in &field.subfields {
+                let part = match subfield.code {
+                    'a' | 'd' => None,
+                    'b' => Some("summary"),
+                    'e' => Some("libretto"),
+                    'f' => Some("table of contents"),
+                    'g' => Some("accompanying material"),
+                    'h' => Some("original"),
+                    'i' => Some("intertitles"),
+                    'j' => Some("subtitles"),
+                    'k' => Some("intermediate translation"),
+                    'm' => Some("original accompanying material"),
+                    'n' => Some("original libretto"),
+                    'p' => Some("captions"),
+                    'q' => Some("accessible audio"),
+                    'r' => Some("accessible visual"),
+                    't' => Some("transcript"),
+                    _ => continue,
+                };
+                let value = subfield.value.trim();
+                let codes: Vec<&str> =
+                    if marc_codes && value.len() > 3 && value.len() % 3 == 0 && value.is_ascii() {
+                        (0..value.len())
+                            .step_by(3)
+                            .map(|i| &value[i..i + 3])
+                            .collect()
+                    } else {
+                        vec![value]
+                    };
+                for code in codes {
+                    let entry = CodeList::Language.get(code).filter(|_| marc_codes);
+                    let language = entry.map_or_else(
+                        || RdfNode::literal(code),
+                        |entry| RdfNode::uri(format!("{LANGUAGES}{}", entry.code)),
+                    );
+                    match (part, entry) {
+                        (None, Some(entry)) if seen.contains(&entry.code) => {},
+                        (None, entry) => {
+                            seen.extend(entry.map(|entry| entry.code));
+                            self.graph.add(work.clone(), property.clone(), language);
+                        },
+                        (Some(part), _) => {
+                            let node = self.graph.new_blank_node();
+                            self.graph.add(
+                                node.clone(),
+                                format!("{RDF}type"),
+                                RdfNode::bf_class(classes::LANGUAGE),
+                            );
+                            self.graph
+                                .add(node.clone(), format!("{RDF}value"), language);
+                            self.graph.add(
+                                node.clone(),
+                                format!("{BF}{}", properties::PART),
+                                RdfNode::literal(part),
+                            );
+                            self.graph.add(work.clone(), property.clone(), node);
+                        },
+                    }
+                }
+            }
+        }
+    }
+
+    /// Processes 336/337/338 into RDA content (Work), media and carrier
+    /// (Instance) types.
+    ///
+    /// Each `$b` code, or `$a` term when there is no code, becomes a URI
+    /// in the LC vocabulary. Terms from other vocabulary sources (`$2`
+    /// other than RDA) or not in the RDA lists are kept as labeled blank
+    /// nodes.
+    fn process_content_types(&mut self) {
+        let types = [
+            (
+                "336",
+                properties::CONTENT,
+                classes::CONTENT,
+                CodeList::ContentType,
+                CONTENT_TYPES,
+            ),
+            (
+                "337",
+                properties::MEDIA,
+                classes::MEDIA,
+                CodeList::MediaType,
+                MEDIA_TYPES,
+            ),
+            (
+                "338",
+                properties::CARRIER,
+                classes::CARRIER,
+                CodeList::CarrierType,
+                CARRIER_TYPES,
+            ),
+        ];
+        for (tag, property, class, list, namespace) in types {
+            let target = if tag == "336" {
+                &self.work_node
+            } else {
+                &self.instance_node
+            };
+            let Some(target) = target.clone() else {
+                continue;
+            };
+            let Some(fields) = self.record.fields.get(tag) else {
+                continue;
+            };
+            let mut seen = Vec::new();
+            for field in fields {
+                let rda = field.get_subfield('2').is_none_or(|source| {
+                    let source = source.trim().trim_end_matches('.').to_ascii_lowercase();
+                    source.starts_with("rda") || source.starts_with("isbd")
+                });
+                let codes = field.get_subfield_values('b');
+                let entries: Vec<Result<_, &str>> = if codes.is_empty() {
+                    field
+                        .get_subfield_values('a')
+                        .into_iter()
+                        .map(|term| list.find_by_name(term).filter(|_| rda).ok_or(term))
+                        .collect()
+                } else {
+                    codes
+                        .into_iter()
+                        .map(|code| list.get(code).filter(|_| rda).ok_or(code))
+                        .collect()
+                };
+                for entry in entries {
+                    let node = match entry {
+                        Ok(entry) if seen.contains(&entry.code) => continue,
+                        Ok(entry) => {
+                            seen.push(entry.code);
+                            let node = RdfNode::uri(format!("{namespace}{}", entry.code));
+                            self.graph.add(
+                                node.clone(),
+                                format!("{RDFS}label"),
+                                RdfNode::literal(entry.name),
+                            );
+                            node
+                        },
+                        Err(value) => {
+                            let node = self.graph.new_blank_node();
+                            self.graph.add(
+                                node.clone(),
+                                format!("{RDFS}label"),
+                                RdfNode::literal(value),
+                            );
+                            node
+                        },
+                    };
+                    self.graph
+                        .add(node.clone(), format!("{RDF}type"), RdfNode::bf_class(class));
+                    self.graph
+                        .add(target.clone(), format!("{BF}{property}"), node);
+                }
+            }
+        }
+    }
+
     /// Processes 5XX note fields.
     fn process_notes(&mut self) {
         let instance = match &self.instance_node {
@@ -1556,73 +1865,65 @@ impl<'a> MarcToBibframeConverter<'a> {
             }
         }
 
-        // Process 876 - Item Information (basic)
+        // Process 876-878 - Item Information
         // These often supplement 852 or provide additional item details
-        if let Some(fields) = self.record.fields.get("876") {
-            for field in fields {
-                // Try to find associated item or create new one
-                let item_node = if self.item_nodes.is_empty() {
-                    let node = self.generate_item_uri(0);
-                    self.graph.add(
-                        node.clone(),
-                        format!("{RDF}type"),
-                        RdfNode::bf_class(classes::ITEM),
-                    );
-                    self.graph.add(
-                        instance.clone(),
-                        format!("{BF}{}", properties::HAS_ITEM),
-                        node.clone(),
-                    );
-                    self.item_nodes.push(node.clone());
-                    node
-                } else {
-                    self.item_nodes[0].clone()
-                };
-
-                // $a - Internal item number
-                if let Some(num) = field.subfields.iter().find(|s| s.code == 'a') {
-                    let id_node = self.graph.new_blank_node();
-                    self.graph.add(
-                        id_node.clone(),
-                        format!("{RDF}type"),
-                        RdfNode::bf_class(classes::LOCAL),
-                    );
-                    self.graph.add(
-                        id_node.clone(),
-                        format!("{RDF}value"),
-                        RdfNode::literal(&num.value),
-                    );
-                    self.graph.add(
-                        item_node.clone(),
-                        format!("{BF}{}", properties::IDENTIFIED_BY),
-                        id_node,
-                    );
-                }
-
-                // $c - Cost
-                if let Some(cost) = field.subfields.iter().find(|s| s.code == 'c') {
-                    self.graph.add(
-                        item_node.clone(),
-                        format!("{BF}acquisitionSource"),
-                        RdfNode::literal(&cost.value),
-                    );
-                }
+        let items: Vec<Item> = ["876", "877", "878"]
+            .iter()
+            .filter_map(|tag| self.record.fields.get(*tag))
+            .flatten()
+            .map(Item::from_field)
+            .collect();
+        for item in items {
+            // Try to find associated item or create new one
+            let item_node = if self.item_nodes.is_empty() {
+                let node = self.generate_item_uri(0);
+                self.graph.add(
+                    node.clone(),
+                    format!("{RDF}type"),
+                    RdfNode::bf_class(classes::ITEM),
+                );
+                self.graph.add(
+                    instance.clone(),
+                    format!("{BF}{}", properties::HAS_ITEM),
+                    node.clone(),
+                );
+                self.item_nodes.push(node.clone());
+                node
+            } else {
+                self.item_nodes[0].clone()
+            };
 
-                // $d - Date acquired
-                if let Some(date) = field.subfields.iter().find(|s| s.code == 'd') {
-                    self.graph.add(
-                        item_node.clone(),
-                        format!("{BF}acquisitionDate"),
-                        RdfNode::literal(&date.value),
-                    );
-                }
+            // $a - Internal item number
+            if let Some(num) = &item.internal_number {
+                let id_node = self.graph.new_blank_node();
+                self.graph.add(
+                    id_node.clone(),
+                    format!("{RDF}type"),
+                    RdfNode::bf_class(classes::LOCAL),
+                );
+                self.graph.add(
+                    id_node.clone(),
+                    format!("{RDF}value"),
+                    RdfNode::literal(num),
+                );
+                self.graph.add(
+                    item_node.clone(),
+                    format!("{BF}{}", properties::IDENTIFIED_BY),
+                    id_node,
+                );
+            }
 
-                // $j - Item status
-                if let Some(status) = field.subfields.iter().find(|s| s.code == 'j') {
+            // $c - Cost, $d - Date acquired, $j - Item status
+            for (property, value) in [
+                ("acquisitionSource", &item.cost),
+                ("acquisitionDate", &item.acquisition_date),
+                ("status", &item.status),
+            ] {
+                if let Some(value) = value {
                     self.graph.add(
                         item_node.clone(),
-                        format!("{BF}status"),
-                        RdfNode::literal(&status.value),
+                        format!("{BF}{property}"),
+                        RdfNode::literal(value),
                     );
                 }
             }
@@ -1726,12 +2027,12 @@ impl<'a> MarcToBibframeConverter<'a> {
                     .map_or("", |s| &s.value[..3.min(s.value.len())]);
 
                 // Determine language tag from script code if present
-                let lang_tag = self.extract_language_from_880(field);
+                let lang_tag = crate::scripts::linked_language(field);
 
                 match linked_tag {
                     // Title fields
                     "245" | "246" | "247" => {
-                        self.add_880_title(&instance, field, lang_tag.as_deref());
+                        self.add_880_title(&instance, field, lang_tag);
                     },
                     // Edition statement
                     "250" => {
@@ -1750,27 +2051,27 @@ impl<'a> MarcToBibframeConverter<'a> {
                     },
                     // Publication fields
                     "260" | "264" => {
-                        self.add_880_provision(&instance, field, lang_tag.as_deref());
+                        self.add_880_provision(&instance, field, lang_tag);
                     },
                     // Series statement
                     "490" => {
-                        self.add_880_series(&instance, field, lang_tag.as_deref());
+                        self.add_880_series(&instance, field, lang_tag);
                     },
                     // Note fields (5XX)
                     tag if tag.starts_with('5') => {
-                        self.add_880_note(&instance, field, lang_tag.as_deref());
+                        self.add_880_note(&instance, field, lang_tag);
                     },
                     // Subject fields (6XX) - link to Work
                     tag if tag.starts_with('6') => {
-                        self.add_880_subject(&work, field, lang_tag.as_deref());
+                        self.add_880_subject(&work, field, lang_tag);
                     },
                     // Added entry fields (7XX)
                     "740" => {
-                        self.add_880_related_title(&instance, field, lang_tag.as_deref());
+                        self.add_880_related_title(&instance, field, lang_tag);
                     },
                     // Linking fields (78X)
                     "780" | "785" | "787" => {
-                        self.add_880_linking(&instance, field, linked_tag, lang_tag.as_deref());
+                        self.add_880_linking(&instance, field, linked_tag, lang_tag);
                     },
                     _ => {
                         // For unhandled linked fields, just skip with no action
@@ -1780,62 +2081,6 @@ impl<'a> MarcToBibframeConverter<'a> {
         }
     }
 
-    /// Extracts language tag from 880 field's $6 subfield.
-    fn extract_language_from_880(&self, field: &Field) -> Option<String> {
-        field
-            .subfields
-            .iter()
-            .find(|s| s.code == '6')
-            .and_then(|s| {
-                // Format: "TAG-occurrence/script" or "TAG-occurrence"
-                if let Some(slash_pos) = s.value.find('/') {
-                    let script = &s.value[slash_pos + 1..];
-                    // Map MARC-8 script codes to language tags
-                    match script {
-                        "(3" | "arab" => Some("ar".to_string()),
-                        "(N" | "cyrl" => Some("ru".to_string()), // Cyrillic -> Russian (could be others)
-                        "hang" => Some("ko".to_string()),        // Korean Hangul
-                        "hani" => Some("zh".to_string()),        // CJK ideographs -> Chinese
-                        "jpan" => Some("ja".to_string()),        // Japanese
-                        "(2" | "hebr" => Some("he".to_string()),
-                        "(S" | "grek" => Some("el".to_string()),
-                        // Latin "(B"/"latn" is default, "$1" CJK detects from content, others fallthrough
-                        _ => None,
-                    }
-                    .or_else(|| self.detect_script_from_content(field))
-                } else {
-                    // Try to detect script from content
-                    self.detect_script_from_content(field)
-                }
-            })
-    }
-
-    /// Attempts to detect script from field content.
-    #[allow(clippy::unused_self)]
-    fn detect_script_from_content(&self, field: &Field) -> Option<String> {
-        let text: String = field
-            .subfields
-            .iter()
-            .filter(|s| s.code != '6') // Skip the linking subfield
-            .map(|s| &s.value[..])
-            .collect();
-
-        // Simple heuristic based on Unicode ranges
-        for ch in text.chars() {
-            match ch {
-                '\u{3040}'..='\u{309F}' | '\u{30A0}'..='\u{30FF}' => return Some("ja".to_string()), // Hiragana/Katakana
-                '\u{AC00}'..='\u{D7AF}' => return Some("ko".to_string()), // Hangul
-                '\u{4E00}'..='\u{9FFF}' => return Some("zh".to_string()), // CJK (default to Chinese)
-                '\u{0400}'..='\u{04FF}' => return Some("ru".to_string()), // Cyrillic
-                '\u{0590}'..='\u{05FF}' => return Some("he".to_string()), // Hebrew
-                '\u{0600}'..='\u{06FF}' => return Some("ar".to_string()), // Arabic
-                '\u{0370}'..='\u{03FF}' => return Some("el".to_string()), // Greek
-                _ => {},
-            }
-        }
-        None
-    }
-
     /// Adds an 880 title variant.
     fn add_880_title(&mut self, instance: &RdfNode, field: &Field, lang: Option<&str>) {
         let title_node = self.graph.new_blank_node();
@@ -2127,8 +2372,17 @@ impl<'a> MarcToBibframeConverter<'a> {
 
         for (tag, relationship, is_instance_rel) in linking_tags {
             if let Some(fields) = self.record.fields.get(tag) {
-                for field in fields {
-                    let related_node = self.graph.new_blank_node();
+                for (n, field) in fields.iter().enumerate() {
+                    // Other physical forms can be Instances of this Work
+                    let other_format = tag == "776" && self.config.multi_instance;
+                    let related_node = if other_format {
+                        self.generate_entity_uri_with_suffix(
+                            "instance",
+                            &format!("-format{}", n + 1),
+                        )
+                    } else {
+                        self.graph.new_blank_node()
+                    };
 
                     // Determine if linking to Work or Instance
                     let related_type = if is_instance_rel {
@@ -2248,6 +2502,10 @@ impl<'a> MarcToBibframeConverter<'a> {
                         );
                     }
 
+                    if other_format {
+                        self.link_instance_to_work(&related_node);
+                    }
+
                     // Link from source entity
                     let source = if is_instance_rel { &instance } else { &work };
                     self.graph
@@ -2257,6 +2515,112 @@ impl<'a> MarcToBibframeConverter<'a> {
         }
     }
 
+    /// Links an additional Instance to the Work (or Hub) the way
+    /// [`Self::link_work_instance`] links the main one.
+    fn link_instance_to_work(&mut self, instance: &RdfNode) {
+        let Some(parent) = self.hub_node.as_ref().or(self.work_node.as_ref()).cloned() else {
+            return;
+        };
+        self.graph.add(
+            parent.clone(),
+            format!("{BF}{}", properties::HAS_INSTANCE),
+            instance.clone(),
+        );
+        self.graph.add(
+            instance.clone(),
+            format!("{BF}{}", properties::INSTANCE_OF),
+            parent,
+        );
+    }
+
+    /// Processes 533 (reproduction note) into reproduction Instances when
+    /// [`BibframeConfig::reproduction_instances`] is set.
+    ///
+    /// - $a: Type of reproduction (sets the Instance class)
+    /// - $b/$c/$d: Place, agency and date of reproduction
+    /// - $e: Physical description
+    /// - $f: Series statement
+    /// - $n: Note
+    fn process_reproductions(&mut self) {
+        if !self.config.reproduction_instances {
+            return;
+        }
+        let Some(original) = self.instance_node.clone() else {
+            return;
+        };
+        let Some(fields) = self.record.fields.get("533") else {
+            return;
+        };
+
+        for (n, field) in fields.iter().enumerate() {
+            let reproduction = self
+                .generate_entity_uri_with_suffix("instance", &format!("-reproduction{}", n + 1));
+
+            let kind = field.get_subfield('a').unwrap_or_default();
+            let lower = kind.to_lowercase();
+            let instance_type = if lower.contains("micro") {
+                classes::MICROFORM
+            } else if lower.contains("electronic") || lower.contains("digital") {
+                classes::ELECTRONIC
+            } else {
+                classes::INSTANCE
+            };
+            self.graph.add(
+                reproduction.clone(),
+                format!("{RDF}type"),
+                RdfNode::bf_class(instance_type),
+            );
+            if !kind.is_empty() {
+                self.graph.add(
+                    reproduction.clone(),
+                    format!("{RDFS}label"),
+                    RdfNode::literal(kind),
+                );
+            }
+
+            // $b place, $c agency, $d date read like 260 $a/$b/$c
+            let mut activity = Field::new("533".to_string(), ' ', ' ');
+            for subfield in &field.subfields {
+                let code = match subfield.code {
+                    'b' => 'a',
+                    'c' => 'b',
+                    'd' => 'c',
+                    _ => continue,
+                };
+                activity.add_subfield(code, subfield.value.clone());
+            }
+            if !activity.subfields.is_empty() {
+                self.add_provision_activity(&reproduction, &activity, classes::PUBLICATION);
+            }
+
+            for subfield in &field.subfields {
+                let property = match subfield.code {
+                    'e' => properties::EXTENT,
+                    'f' => "seriesStatement",
+                    'n' => properties::NOTE,
+                    _ => continue,
+                };
+                self.graph.add(
+                    reproduction.clone(),
+                    format!("{BF}{property}"),
+                    RdfNode::literal(&subfield.value),
+                );
+            }
+
+            self.link_instance_to_work(&reproduction);
+            self.graph.add(
+                original.clone(),
+                format!("{BF}{}", properties::HAS_REPRODUCTION),
+                reproduction.clone(),
+            );
+            self.graph.add(
+                reproduction,
+                format!("{BF}{}", properties::REPRODUCTION_OF),
+                original.clone(),
+            );
+        }
+    }
+
     /// Processes series fields (490 and 8XX).
     ///
     /// - 490: Series statement (transcribed)
@@ -2721,6 +3085,44 @@ mod tests {
         assert!(serialized.contains("relators/aut"));
     }
 
+    #[test]
+    fn test_relator_terms_and_codes_are_normalized() {
+        let mut record = Record::new(make_test_leader());
+        let contributors: [&[(char, &str)]; 4] = [
+            &[('a', "Doe, Jane,"), ('e', "editor,"), ('e', "translator.")],
+            &[('a', "Roe, Rick,"), ('e', "author."), ('4', "AUT")],
+            &[('a', "Poe, Pat,"), ('e', "mystery role.")],
+            &[('a', "Congress."), ('j', "jt. author.")],
+        ];
+        for (i, subfields) in contributors.iter().enumerate() {
+            let tag = if i == 3 { "711" } else { "700" };
+            let mut field = Field::new(tag.to_string(), '1', ' ');
+            for (code, value) in *subfields {
+                field.add_subfield(*code, (*value).to_string());
+            }
+            record.add_field(field);
+        }
+
+        let graph = convert_marc_to_bibframe(&record, &BibframeConfig::default());
+        let role = format!("{BF}{}", properties::ROLE);
+        let roles: Vec<&RdfNode> = graph
+            .triples()
+            .filter(|t| t.predicate == role)
+            .map(|t| &t.object)
+            .collect();
+        let relator = |code: &str| RdfNode::uri(format!("{RELATORS}{code}"));
+        assert_eq!(
+            roles,
+            vec![
+                &relator("edt"),
+                &relator("trl"),
+                &relator("aut"),
+                &RdfNode::literal("mystery role."),
+                &relator("aut"),
+            ]
+        );
+    }
+
     #[test]
     fn test_subject_conversion() {
         let mut record = Record::new(make_test_leader());
@@ -2741,6 +3143,230 @@ mod tests {
         assert!(serialized.contains("Computer science"));
     }
 
+    #[test]
+    fn test_subject_sources_follow_indicator_and_2() {
+        let mut record = Record::new(make_test_leader());
+        let subjects: [(&str, char, &[(char, &str)]); 6] = [
+            ("650", '0', &[('a', "Cats.")]),
+            ("600", '0', &[('a', "Twain, Mark,"), ('d', "1835-1910.")]),
+            ("650", '2', &[('a', "Neoplasms.")]),
+            ("650", '7', &[('a', "Cats."), ('2', "fast")]),
+            ("655", '7', &[('a', "Comics."), ('2', "lcgft")]),
+            ("650", '4', &[('a', "Local heading.")]),
+        ];
+        for (tag, ind2, subfields) in subjects {
+            let mut field = Field::new(tag.to_string(), ' ', ind2);
+            for (code, value) in subfields {
+                field.add_subfield(*code, (*value).to_string());
+            }
+            record.add_field(field);
+        }
+
+        let graph = convert_marc_to_bibframe(&record, &BibframeConfig::default());
+        let objects = |predicate: String| -> Vec<String> {
+            graph
+                .triples()
+                .filter(|t| t.predicate == predicate)
+                .filter_map(|t| match &t.object {
+                    RdfNode::Uri(uri) => Some(uri.clone()),
+                    _ => None,
+                })
+                .collect()
+        };
+        let sources = objects(format!("{BF}{}", properties::SOURCE));
+        assert_eq!(
+            sources,
+            vec![
+                format!("{SUBJECT_SCHEMES}naf"),
+                format!("{SUBJECT_SCHEMES}lcsh"),
+                format!("{SUBJECT_SCHEMES}mesh"),
+                format!("{SUBJECT_SCHEMES}fast"),
+                format!("{GENRE_FORM_SCHEMES}lcgft"),
+            ]
+        );
+        let schemes = objects(format!("{MADSRDF}isMemberOfMADSScheme"));
+        assert_eq!(
+            schemes,
+            vec![
+                "http://id.loc.gov/authorities/names",
+                "http://id.loc.gov/authorities/subjects",
+                "http://id.worldcat.org/fast",
+                "http://id.loc.gov/authorities/genreForms",
+            ]
+        );
+
+        let config = BibframeConfig::default().with_subject_schemes(false);
+        let graph = convert_marc_to_bibframe(&record, &config);
+        let source = format!("{BF}{}", properties::SOURCE);
+        assert!(!graph.triples().any(|t| t.predicate == source));
+    }
+
+    #[test]
+    fn test_reproduction_and_other_format_instances() {
+        let mut record = Record::new(make_test_leader());
+        record.add_control_field("001".to_string(), "rec1".to_string());
+        let mut f533 = Field::new("533".to_string(), ' ', ' ');
+        for (code, value) in [
+            ('a', "Microfilm."),
+            ('b', "Washington, D.C. :"),
+            ('c', "Library of Congress,"),
+            ('d', "1990."),
+            ('e', "1 microfilm reel ; 35 mm."),
+        ] {
+            f533.add_subfield(code, value.to_string());
+        }
+        record.add_field(f533);
+        let mut f776 = Field::new("776".to_string(), '0', '8');
+        f776.add_subfield('i', "Online version:".to_string());
+        f776.add_subfield('t', "Test title".to_string());
+        record.add_field(f776);
+
+        let base = BibframeConfig::new().with_base_uri("http://example.org/");
+        let has_instance = format!("{BF}{}", properties::HAS_INSTANCE);
+        let instances = |graph: &RdfGraph| -> Vec<RdfNode> {
+            graph
+                .triples()
+                .filter(|t| t.predicate == has_instance)
+                .map(|t| t.object.clone())
+                .collect()
+        };
+
+        // Off by default: 533 ignored, 776 only related to the Instance
+        let graph = convert_marc_to_bibframe(&record, &base);
+        assert_eq!(instances(&graph).len(), 1);
+        assert!(
+            !graph
+                .triples()
+                .any(|t| t.predicate.ends_with("hasReproduction"))
+        );
+
+        let config = base
+            .with_reproduction_instances(true)
+            .with_multi_instance(true);
+        let graph = convert_marc_to_bibframe(&record, &config);
+        let reproduction = RdfNode::uri("http://example.org/instance/rec1-reproduction1");
+        let other_format = RdfNode::uri("http://example.org/instance/rec1-format1");
+        assert_eq!(
+            instances(&graph),
+            vec![
+                RdfNode::uri("http://example.org/instance/rec1"),
+                other_format.clone(),
+                reproduction.clone(),
+            ]
+        );
+        let about = |node: &RdfNode, predicate: &str| -> Vec<RdfNode> {
+            graph
+                .triples()
+                .filter(|t| &t.subject == node && t.predicate == format!("{BF}{predicate}"))
+                .map(|t| t.object.clone())
+                .collect()
+        };
+        assert_eq!(
+            about(&reproduction, properties::REPRODUCTION_OF),
+            vec![RdfNode::uri("http://example.org/instance/rec1")]
+        );
+        assert_eq!(
+            about(&reproduction, properties::EXTENT),
+            vec![RdfNode::literal("1 microfilm reel ; 35 mm.")]
+        );
+        assert_eq!(
+            about(&reproduction, properties::PROVISION_ACTIVITY).len(),
+            1
+        );
+        assert!(graph.triples().any(
+            |t| t.subject == reproduction && t.object == RdfNode::bf_class(classes::MICROFORM)
+        ));
+        assert_eq!(about(&other_format, properties::INSTANCE_OF).len(), 1);
+    }
+
+    #[test]
+    fn test_content_media_carrier_types() {
+        let mut record = Record::new(make_test_leader());
+        let fields: [(&str, &[(char, &str)]); 5] = [
+            ("336", &[('a', "text"), ('b', "txt"), ('2', "rdacontent")]),
+            ("336", &[('a', "still image"), ('2', "rdacontent")]),
+            ("337", &[('a', "computer"), ('b', "c"), ('2', "rdamedia")]),
+            (
+                "338",
+                &[('a', "online resource"), ('b', "cr"), ('2', "rdacarrier")],
+            ),
+            ("338", &[('a', "Digital file"), ('2', "local")]),
+        ];
+        for (tag, subfields) in fields {
+            let mut field = Field::new(tag.to_string(), ' ', ' ');
+            for (code, value) in subfields {
+                field.add_subfield(*code, (*value).to_string());
+            }
+            record.add_field(field);
+        }
+
+        let graph = convert_marc_to_bibframe(&record, &BibframeConfig::default());
+        let objects = |property: &str| -> Vec<RdfNode> {
+            graph
+                .triples()
+                .filter(|t| t.predicate == format!("{BF}{property}"))
+                .map(|t| t.object.clone())
+                .collect()
+        };
+        assert_eq!(
+            objects(properties::CONTENT),
+            vec![
+                RdfNode::uri(format!("{CONTENT_TYPES}txt")),
+                RdfNode::uri(format!("{CONTENT_TYPES}sti")),
+            ]
+        );
+        assert_eq!(
+            objects(properties::MEDIA),
+            vec![RdfNode::uri(format!("{MEDIA_TYPES}c"))]
+        );
+        let carriers = objects(properties::CARRIER);
+        assert_eq!(carriers[0], RdfNode::uri(format!("{CARRIER_TYPES}cr")));
+        assert!(matches!(carriers[1], RdfNode::BlankNode(_)));
+        assert!(
+            graph
+                .triples()
+                .any(|t| t.subject == carriers[1] && t.object == RdfNode::literal("Digital file"))
+        );
+    }
+
+    #[test]
+    fn test_languages_from_008_and_041() {
+        let mut record = Record::new(make_test_leader());
+        record.add_control_field(
+            "008".to_string(),
+            "040520s2001    xxu           000 0 eng  ".to_string(),
+        );
+        let mut f041 = Field::new("041".to_string(), '1', ' ');
+        f041.add_subfield('a', "engfre".to_string());
+        f041.add_subfield('h', "ger".to_string());
+        record.add_field(f041);
+        let mut iso = Field::new("041".to_string(), ' ', '7');
+        iso.add_subfield('a', "en".to_string());
+        iso.add_subfield('2', "iso639-1".to_string());
+        record.add_field(iso);
+
+        let graph = convert_marc_to_bibframe(&record, &BibframeConfig::default());
+        let language = format!("{BF}{}", properties::LANGUAGE);
+        let languages: Vec<&RdfNode> = graph
+            .triples()
+            .filter(|t| t.predicate == language)
+            .map(|t| &t.object)
+            .collect();
+        assert_eq!(languages.len(), 4);
+        assert_eq!(languages[0], &RdfNode::uri(format!("{LANGUAGES}eng")));
+        assert_eq!(languages[1], &RdfNode::uri(format!("{LANGUAGES}fre")));
+        assert_eq!(languages[3], &RdfNode::literal("en"));
+
+        let original = languages[2];
+        let about: Vec<&RdfNode> = graph
+            .triples()
+            .filter(|t| &t.subject == original)
+            .map(|t| &t.object)
+            .collect();
+        assert!(about.contains(&&RdfNode::uri(format!("{LANGUAGES}ger"))));
+        assert!(about.contains(&&RdfNode::literal("original")));
+    }
+
     #[test]
     fn test_identifier_conversion() {
         let mut record = Record::new(make_test_leader());
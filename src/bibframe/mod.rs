@@ -37,6 +37,13 @@
 //! let record = bibframe_to_marc(&graph)?;
 //! ```
 //!
+//! ## Whole catalogs
+//!
+//! [`marc_to_bibframe_stream`] and [`marc_to_bibframe_stream_parallel`]
+//! convert a reader's records one at a time and write N-Triples or N-Quads
+//! (one named graph per record) as they go, instead of holding every graph
+//! in memory.
+//!
 //! # Configuration
 //!
 //! Use [`BibframeConfig`] to control conversion behavior:
@@ -55,19 +62,25 @@
 //! - `config`: Configuration options for BIBFRAME conversion
 //! - `namespaces`: BIBFRAME namespace prefixes and vocabulary terms
 //! - `rdf`: RDF graph representation and serialization
+//! - `stream`: Record-at-a-time conversion to N-Triples and N-Quads
 
 mod config;
 mod converter;
 mod namespaces;
 mod rdf;
 mod reverse_converter;
+mod stream;
 
 pub use config::{BibframeConfig, RdfFormat};
 pub use namespaces::{
-    BF, BFLC, CARRIER_TYPES, CONTENT_TYPES, COUNTRIES, LANGUAGES, LC_NAMES, LC_SUBJECTS, MADSRDF,
-    MEDIA_TYPES, RDF, RDFS, RELATORS, XSD, bflc, classes, properties,
+    BF, BFLC, CARRIER_TYPES, CONTENT_TYPES, COUNTRIES, FAST, GENRE_FORM_SCHEMES, LANGUAGES,
+    LC_CHILDRENS_SUBJECTS, LC_GENRE_FORMS, LC_NAMES, LC_SUBJECTS, MADSRDF, MEDIA_TYPES, RDF, RDFS,
+    RELATORS, SUBJECT_SCHEMES, XSD, bflc, classes, properties,
+};
+pub use rdf::{RdfGraph, RdfGraphDiff, RdfNode, RdfTriple};
+pub use stream::{
+    DEFAULT_STREAM_BATCH_SIZE, marc_to_bibframe_stream, marc_to_bibframe_stream_parallel,
 };
-pub use rdf::{RdfGraph, RdfNode, RdfTriple};
 
 use crate::error::Result;
 use crate::record::Record;
@@ -97,6 +110,7 @@ use crate::record::Record;
 /// ```
 #[must_use]
 pub fn marc_to_bibframe(record: &Record, config: &BibframeConfig) -> RdfGraph {
+    crate::telemetry::stage_span!("mrrc.convert", from = "marc", to = "bibframe");
     converter::convert_marc_to_bibframe(record, config)
 }
 
@@ -128,6 +142,7 @@ pub fn marc_to_bibframe(record: &Record, config: &BibframeConfig) -> RdfGraph {
 /// let record = bibframe_to_marc(&graph)?;
 /// ```
 pub fn bibframe_to_marc(graph: &RdfGraph) -> Result<Record> {
+    crate::telemetry::stage_span!("mrrc.convert", from = "bibframe", to = "marc");
     reverse_converter::convert_bibframe_to_marc(graph)
 }
 
@@ -206,4 +221,26 @@ mod tests {
             .serialize(RdfFormat::Turtle)
             .expect("Turtle serialization failed");
     }
+
+    #[test]
+    fn test_marc_to_bibframe_named_graph() {
+        let mut record = Record::new(make_test_leader());
+        record.add_control_field("001".to_string(), "12345".to_string());
+
+        let config = BibframeConfig::new()
+            .with_base_uri("http://example.org/")
+            .with_named_graphs(true);
+        let graph = marc_to_bibframe(&record, &config);
+
+        let graph_uri = RdfNode::uri("http://example.org/graph/12345");
+        assert_eq!(graph.graph_names(), vec![&graph_uri]);
+        assert!(graph.quads().all(|(_, g)| g == Some(&graph_uri)));
+        let nq = graph
+            .serialize(RdfFormat::NQuads)
+            .expect("N-Quads serialization failed");
+        assert!(
+            nq.lines()
+                .all(|l| l.ends_with("<http://example.org/graph/12345> ."))
+        );
+    }
 }
@@ -45,6 +45,21 @@ pub const LC_NAMES: &str = "http://id.loc.gov/authorities/names/";
 /// LOC subjects authority namespace.
 pub const LC_SUBJECTS: &str = "http://id.loc.gov/authorities/subjects/";
 
+/// LOC children's subjects authority namespace.
+pub const LC_CHILDRENS_SUBJECTS: &str = "http://id.loc.gov/authorities/childrensSubjects/";
+
+/// LOC genre/form terms authority namespace.
+pub const LC_GENRE_FORMS: &str = "http://id.loc.gov/authorities/genreForms/";
+
+/// FAST (Faceted Application of Subject Terminology) namespace.
+pub const FAST: &str = "http://id.worldcat.org/fast/";
+
+/// LOC subject heading and thesaurus source codes namespace.
+pub const SUBJECT_SCHEMES: &str = "http://id.loc.gov/vocabulary/subjectSchemes/";
+
+/// LOC genre/form source codes namespace.
+pub const GENRE_FORM_SCHEMES: &str = "http://id.loc.gov/vocabulary/genreFormSchemes/";
+
 /// Common BIBFRAME class local names.
 pub mod classes {
     /// Work - the conceptual essence of a resource.
@@ -89,6 +104,8 @@ pub mod classes {
     pub const ELECTRONIC: &str = "Electronic";
     /// Print instance type.
     pub const PRINT: &str = "Print";
+    /// Microform instance type.
+    pub const MICROFORM: &str = "Microform";
 
     // Agent types
     /// Person agent type.
@@ -127,6 +144,14 @@ pub mod classes {
     pub const MANUFACTURE: &str = "Manufacture";
     /// `AdminMetadata` class.
     pub const ADMIN_METADATA: &str = "AdminMetadata";
+    /// Language class.
+    pub const LANGUAGE: &str = "Language";
+    /// Content type (RDA content, 336).
+    pub const CONTENT: &str = "Content";
+    /// Media type (RDA media, 337).
+    pub const MEDIA: &str = "Media";
+    /// Carrier type (RDA carrier, 338).
+    pub const CARRIER: &str = "Carrier";
 
     // Identifier types
     /// ISBN identifier type.
@@ -162,6 +187,10 @@ pub mod properties {
     pub const HAS_EXPRESSION: &str = "hasExpression";
     /// expressionOf - links Hub to Work.
     pub const EXPRESSION_OF: &str = "expressionOf";
+    /// hasReproduction - links an Instance to a reproduction of it.
+    pub const HAS_REPRODUCTION: &str = "hasReproduction";
+    /// reproductionOf - links a reproduction to the Instance it reproduces.
+    pub const REPRODUCTION_OF: &str = "reproductionOf";
     /// hasItem - links Instance to Item.
     pub const HAS_ITEM: &str = "hasItem";
     /// itemOf - links Item to Instance.
@@ -187,6 +216,12 @@ pub mod properties {
     /// role - relator role.
     pub const ROLE: &str = "role";
 
+    // Language properties
+    /// language - language of the content.
+    pub const LANGUAGE: &str = "language";
+    /// part - part of the resource a language applies to.
+    pub const PART: &str = "part";
+
     // Subject properties
     /// subject - general subject property.
     pub const SUBJECT: &str = "subject";
@@ -4,9 +4,13 @@
 //! It wraps the library's functionality in a higher-level API tailored for
 //! BIBFRAME conversion.
 
+use std::collections::{HashMap, HashSet};
 use std::io::{Read, Write};
 
-use oxrdf::{BlankNode, Literal, NamedNode, NamedOrBlankNode, Quad, Term, Triple};
+use oxrdf::dataset::CanonicalizationAlgorithm;
+use oxrdf::{
+    BlankNode, Dataset, GraphName, Literal, NamedNode, NamedOrBlankNode, Quad, Term, Triple,
+};
 use oxrdfio::{JsonLdProfileSet, RdfFormat as OxRdfFormat, RdfParser, RdfSerializer};
 
 use crate::error::{MarcError, Result};
@@ -130,10 +134,17 @@ impl RdfNode {
 }
 
 /// An RDF graph containing triples.
+///
+/// A graph can also act as a dataset: each triple may carry the name of the
+/// graph it belongs to (see [`add_quad`](Self::add_quad) and
+/// [`set_graph_name`](Self::set_graph_name)). Triples added with
+/// [`add`](Self::add) live in the default graph, and a graph with no named
+/// triples behaves exactly as a plain triple graph.
 #[derive(Debug, Clone, Default)]
 pub struct RdfGraph {
-    /// The triples in this graph.
-    triples: Vec<RdfTriple>,
+    /// The triples in this graph, each with its named graph (`None` for the
+    /// default graph).
+    triples: Vec<(RdfTriple, Option<RdfNode>)>,
     /// Counter for generating unique blank node IDs.
     blank_node_counter: usize,
 }
@@ -145,9 +156,92 @@ impl RdfGraph {
         Self::default()
     }
 
-    /// Adds a triple to the graph.
+    /// Adds a triple to the default graph.
     pub fn add_triple(&mut self, triple: RdfTriple) {
-        self.triples.push(triple);
+        self.triples.push((triple, None));
+    }
+
+    /// Adds a triple to the named graph `graph`, or to the default graph
+    /// when `None`.
+    pub fn add_quad(&mut self, triple: RdfTriple, graph: Option<RdfNode>) {
+        self.triples.push((triple, graph));
+    }
+
+    /// Moves every triple into the named graph `graph` (or back to the
+    /// default graph when `None`).
+    ///
+    /// The usual way to give a converted record its own graph for
+    /// provenance, so that it can later be replaced or dropped as a unit in
+    /// a triplestore.
+    pub fn set_graph_name(&mut self, graph: Option<RdfNode>) {
+        let Some(((_, last), rest)) = self.triples.split_last_mut() else {
+            return;
+        };
+        for (_, name) in rest {
+            name.clone_from(&graph);
+        }
+        *last = graph;
+    }
+
+    /// Distinct named graphs in the graph, in order of first use.
+    #[must_use]
+    pub fn graph_names(&self) -> Vec<&RdfNode> {
+        let mut names: Vec<&RdfNode> = Vec::new();
+        for name in self.triples.iter().filter_map(|(_, name)| name.as_ref()) {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        names
+    }
+
+    /// Returns true if any triple is in a named graph.
+    #[must_use]
+    pub fn is_dataset(&self) -> bool {
+        self.triples.iter().any(|(_, name)| name.is_some())
+    }
+
+    /// Appends the triples of `other`, keeping their graph names.
+    ///
+    /// Blank nodes from `other` are relabeled so they cannot merge with
+    /// blank nodes already in this graph; two records converted separately
+    /// both start their blank nodes at `b1`.
+    pub fn merge(&mut self, other: RdfGraph) {
+        let mut taken: HashSet<String> = self
+            .triples
+            .iter()
+            .flat_map(|(triple, name)| [Some(&triple.subject), Some(&triple.object), name.as_ref()])
+            .filter_map(|node| match node {
+                Some(RdfNode::BlankNode(id)) => Some(id.clone()),
+                _ => None,
+            })
+            .collect();
+        let mut renamed: HashMap<String, String> = HashMap::new();
+        let mut relabel = |node: RdfNode, graph: &mut Self| match node {
+            RdfNode::BlankNode(id) => {
+                let fresh = renamed
+                    .entry(id)
+                    .or_insert_with(|| {
+                        loop {
+                            let RdfNode::BlankNode(candidate) = graph.new_blank_node() else {
+                                unreachable!("new_blank_node returns a blank node")
+                            };
+                            if taken.insert(candidate.clone()) {
+                                break candidate;
+                            }
+                        }
+                    })
+                    .clone();
+                RdfNode::BlankNode(fresh)
+            },
+            other => other,
+        };
+        for (triple, name) in other.triples {
+            let subject = relabel(triple.subject, self);
+            let object = relabel(triple.object, self);
+            let name = name.map(|n| relabel(n, self));
+            self.add_quad(RdfTriple::new(subject, triple.predicate, object), name);
+        }
     }
 
     /// Adds a triple from components.
@@ -173,9 +267,17 @@ impl RdfGraph {
         self.triples.is_empty()
     }
 
-    /// Returns an iterator over the triples.
+    /// Returns an iterator over the triples, in every graph.
     pub fn triples(&self) -> impl Iterator<Item = &RdfTriple> {
-        self.triples.iter()
+        self.triples.iter().map(|(triple, _)| triple)
+    }
+
+    /// Returns an iterator over the triples with their graph names (`None`
+    /// for the default graph).
+    pub fn quads(&self) -> impl Iterator<Item = (&RdfTriple, Option<&RdfNode>)> {
+        self.triples
+            .iter()
+            .map(|(triple, name)| (triple, name.as_ref()))
     }
 
     /// Serializes the graph to a string in the specified format.
@@ -191,17 +293,21 @@ impl RdfGraph {
 
     /// Serializes the graph to a writer in the specified format.
     ///
+    /// Named graphs are written with [`RdfFormat::NQuads`] and
+    /// [`RdfFormat::TriG`]; the triple-only formats cannot express them.
+    ///
     /// # Errors
     ///
-    /// Returns an error if serialization fails.
+    /// Returns an error if serialization fails, including when the graph
+    /// has named graphs and `format` is a triple-only format.
     pub fn serialize_to_writer<W: Write>(&self, writer: W, format: RdfFormat) -> Result<()> {
         let ox_format = to_oxrdf_format(format);
         let mut serializer = RdfSerializer::from_format(ox_format).for_writer(writer);
 
-        for triple in &self.triples {
-            let ox_triple = to_oxrdf_triple(triple)?;
+        for (triple, name) in &self.triples {
+            let ox_quad = to_oxrdf_triple(triple)?.in_graph(to_oxrdf_graph_name(name.as_ref())?);
             serializer
-                .serialize_triple(&ox_triple)
+                .serialize_quad(&ox_quad)
                 .map_err(|e| MarcError::from(std::io::Error::other(e.to_string())))?;
         }
 
@@ -212,6 +318,89 @@ impl RdfGraph {
         Ok(())
     }
 
+    /// Serializes the graph as N-Triples or N-Quads lines for one record of
+    /// a stream.
+    ///
+    /// Blank node labels, including blank graph names, get `blank_prefix`
+    /// prepended so that labels from different records cannot collide.
+    pub(super) fn serialize_stream_chunk<W: Write>(
+        &self,
+        writer: W,
+        format: RdfFormat,
+        blank_prefix: &str,
+    ) -> Result<()> {
+        let relabel = |node: &RdfNode| match node {
+            RdfNode::BlankNode(id) => RdfNode::BlankNode(format!("{blank_prefix}{id}")),
+            other => other.clone(),
+        };
+
+        let mut serializer = RdfSerializer::from_format(to_oxrdf_format(format)).for_writer(writer);
+        for (triple, name) in &self.triples {
+            let relabeled = RdfTriple {
+                subject: relabel(&triple.subject),
+                predicate: triple.predicate.clone(),
+                object: relabel(&triple.object),
+            };
+            let graph_name = to_oxrdf_graph_name(name.as_ref().map(&relabel).as_ref())?;
+            serializer
+                .serialize_quad(&to_oxrdf_triple(&relabeled)?.in_graph(graph_name))
+                .map_err(|e| MarcError::from(std::io::Error::other(e.to_string())))?;
+        }
+        serializer
+            .finish()
+            .map_err(|e| MarcError::from(std::io::Error::other(e.to_string())))?;
+        Ok(())
+    }
+
+    /// Returns true if the two graphs are the same up to blank node labels.
+    ///
+    /// Compares as RDF does: triples are a set (duplicates count once),
+    /// graph names are part of each triple, and `_:b1` in one graph may
+    /// match `_:b7` in the other. Use this rather than comparing serialized
+    /// output, whose blank node labels shift whenever the converter visits
+    /// fields in a different order.
+    #[must_use]
+    pub fn isomorphic_to(&self, other: &RdfGraph) -> bool {
+        let mut ours = to_oxrdf_dataset_unchecked(self);
+        let mut theirs = to_oxrdf_dataset_unchecked(other);
+        if ours.len() != theirs.len() {
+            return false;
+        }
+        ours.canonicalize(CanonicalizationAlgorithm::Unstable);
+        theirs.canonicalize(CanonicalizationAlgorithm::Unstable);
+        ours == theirs
+    }
+
+    /// Triples in this graph but not `other` ([`RdfGraphDiff::removed`]),
+    /// and in `other` but not this graph ([`RdfGraphDiff::added`]).
+    ///
+    /// Both graphs' blank nodes are relabeled canonically first, so
+    /// isomorphic graphs give an empty diff. When the graphs differ, the
+    /// canonical labels of blank nodes close to a change may differ too, so
+    /// triples about those blank nodes can show up on both sides; triples
+    /// without blank nodes are always reported exactly.
+    #[must_use]
+    pub fn diff(&self, other: &RdfGraph) -> RdfGraphDiff {
+        let mut ours = to_oxrdf_dataset_unchecked(self);
+        let mut theirs = to_oxrdf_dataset_unchecked(other);
+        ours.canonicalize(CanonicalizationAlgorithm::Unstable);
+        theirs.canonicalize(CanonicalizationAlgorithm::Unstable);
+        let only_in = |a: &Dataset, b: &Dataset| {
+            a.iter()
+                .filter(|quad| !b.contains(*quad))
+                .filter_map(|quad| {
+                    let quad = quad.into_owned();
+                    let triple = from_oxrdf_quad(&quad).ok()?;
+                    Some((triple, from_oxrdf_graph_name(&quad.graph_name)))
+                })
+                .collect()
+        };
+        RdfGraphDiff {
+            removed: only_in(&ours, &theirs),
+            added: only_in(&theirs, &ours),
+        }
+    }
+
     /// Parses an RDF graph from a reader in the specified format.
     ///
     /// # Errors
@@ -226,7 +415,7 @@ impl RdfGraph {
         for result in parser {
             let quad = result.map_err(|e| MarcError::invalid_field_msg(e.to_string()))?;
             let triple = from_oxrdf_quad(&quad)?;
-            graph.add_triple(triple);
+            graph.add_quad(triple, from_oxrdf_graph_name(&quad.graph_name));
         }
 
         Ok(graph)
@@ -242,6 +431,27 @@ impl RdfGraph {
     }
 }
 
+/// Differences between two graphs, from [`RdfGraph::diff`].
+///
+/// Each entry is a triple with its graph name (`None` for the default
+/// graph). Blank nodes carry canonical labels, not the labels of either
+/// input graph.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RdfGraphDiff {
+    /// Triples only in the graph `diff` was called on.
+    pub removed: Vec<(RdfTriple, Option<RdfNode>)>,
+    /// Triples only in the graph passed to `diff`.
+    pub added: Vec<(RdfTriple, Option<RdfNode>)>,
+}
+
+impl RdfGraphDiff {
+    /// Returns true if the graphs are isomorphic.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.removed.is_empty() && self.added.is_empty()
+    }
+}
+
 /// Converts our [`RdfFormat`] to oxrdfio's format.
 fn to_oxrdf_format(format: RdfFormat) -> OxRdfFormat {
     match format {
@@ -251,6 +461,28 @@ fn to_oxrdf_format(format: RdfFormat) -> OxRdfFormat {
         },
         RdfFormat::Turtle => OxRdfFormat::Turtle,
         RdfFormat::NTriples => OxRdfFormat::NTriples,
+        RdfFormat::NQuads => OxRdfFormat::NQuads,
+        RdfFormat::TriG => OxRdfFormat::TriG,
+    }
+}
+
+/// Converts a graph name to oxrdf's, `None` being the default graph.
+fn to_oxrdf_graph_name(name: Option<&RdfNode>) -> Result<GraphName> {
+    match name {
+        None => Ok(GraphName::DefaultGraph),
+        Some(RdfNode::Uri(uri)) => {
+            Ok(GraphName::NamedNode(NamedNode::new(uri).map_err(|e| {
+                MarcError::invalid_field_msg(format!("Invalid URI: {e}"))
+            })?))
+        },
+        Some(RdfNode::BlankNode(id)) => {
+            Ok(GraphName::BlankNode(BlankNode::new(id).map_err(|e| {
+                MarcError::invalid_field_msg(format!("Invalid blank node ID: {e}"))
+            })?))
+        },
+        Some(RdfNode::Literal { .. }) => Err(MarcError::invalid_field_msg(
+            "Literals cannot be graph names",
+        )),
     }
 }
 
@@ -308,6 +540,59 @@ fn to_oxrdf_triple(triple: &RdfTriple) -> Result<Triple> {
     Ok(Triple::new(subject, predicate, object))
 }
 
+/// Converts an oxrdf graph name back to ours, `None` being the default graph.
+fn from_oxrdf_graph_name(name: &GraphName) -> Option<RdfNode> {
+    match name {
+        GraphName::NamedNode(n) => Some(RdfNode::Uri(n.as_str().to_string())),
+        GraphName::BlankNode(b) => Some(RdfNode::BlankNode(b.as_str().to_string())),
+        GraphName::DefaultGraph => None,
+    }
+}
+
+/// Converts a node to an oxrdf term without validating it, for comparisons
+/// that must not fail on a malformed IRI or language tag.
+fn to_oxrdf_term_unchecked(node: &RdfNode) -> Term {
+    match node {
+        RdfNode::Uri(uri) => Term::NamedNode(NamedNode::new_unchecked(uri)),
+        RdfNode::BlankNode(id) => Term::BlankNode(BlankNode::new_unchecked(id)),
+        RdfNode::Literal {
+            value,
+            language,
+            datatype,
+        } => Term::Literal(match (language, datatype) {
+            (Some(lang), _) => Literal::new_language_tagged_literal_unchecked(value, lang),
+            (None, Some(dt)) => Literal::new_typed_literal(value, NamedNode::new_unchecked(dt)),
+            (None, None) => Literal::new_simple_literal(value),
+        }),
+    }
+}
+
+/// Converts a graph to an oxrdf dataset for comparison. Literal subjects
+/// and graph names, which RDF does not allow, are dropped.
+fn to_oxrdf_dataset_unchecked(graph: &RdfGraph) -> Dataset {
+    let mut dataset = Dataset::new();
+    for (triple, name) in &graph.triples {
+        let subject = match to_oxrdf_term_unchecked(&triple.subject) {
+            Term::NamedNode(n) => NamedOrBlankNode::NamedNode(n),
+            Term::BlankNode(b) => NamedOrBlankNode::BlankNode(b),
+            Term::Literal(_) => continue,
+        };
+        let graph_name = match name.as_ref().map(to_oxrdf_term_unchecked) {
+            None => GraphName::DefaultGraph,
+            Some(Term::NamedNode(n)) => GraphName::NamedNode(n),
+            Some(Term::BlankNode(b)) => GraphName::BlankNode(b),
+            Some(Term::Literal(_)) => continue,
+        };
+        dataset.insert(&Quad::new(
+            subject,
+            NamedNode::new_unchecked(&triple.predicate),
+            to_oxrdf_term_unchecked(&triple.object),
+            graph_name,
+        ));
+    }
+    dataset
+}
+
 /// Converts an oxrdf Quad back to our [`RdfTriple`].
 fn from_oxrdf_quad(quad: &Quad) -> Result<RdfTriple> {
     let subject = match &quad.subject {
@@ -436,4 +721,108 @@ mod tests {
 
         assert_eq!(parsed.len(), graph.len());
     }
+
+    fn record_graph(id: &str) -> RdfGraph {
+        let mut graph = RdfGraph::new();
+        let title = graph.new_blank_node();
+        let work = RdfNode::uri(format!("http://example.org/work/{id}"));
+        graph.add(
+            work.clone(),
+            format!("{}type", namespaces::RDF),
+            RdfNode::bf_class("Work"),
+        );
+        graph.add(work, format!("{}title", namespaces::BF), title.clone());
+        graph.add(
+            title,
+            format!("{}label", namespaces::RDFS),
+            RdfNode::literal(id),
+        );
+        graph
+    }
+
+    #[test]
+    fn test_named_graphs_roundtrip_nquads_and_trig() {
+        let mut dataset = record_graph("a");
+        dataset.set_graph_name(Some(RdfNode::uri("http://example.org/graph/a")));
+        let mut second = record_graph("b");
+        second.set_graph_name(Some(RdfNode::uri("http://example.org/graph/b")));
+        dataset.merge(second);
+        assert!(dataset.is_dataset());
+        assert_eq!(dataset.graph_names().len(), 2);
+
+        for format in [RdfFormat::NQuads, RdfFormat::TriG] {
+            let text = dataset.serialize(format).expect("serialization failed");
+            let parsed = RdfGraph::parse(&text, format).expect("parsing failed");
+            assert_eq!(parsed.len(), 6, "{format}");
+            let in_b = parsed
+                .quads()
+                .filter(|(_, g)| *g == Some(&RdfNode::uri("http://example.org/graph/b")))
+                .count();
+            assert_eq!(in_b, 3, "{format}");
+        }
+
+        let err = dataset.serialize(RdfFormat::Turtle);
+        assert!(err.is_err(), "Turtle cannot carry named graphs");
+        dataset.set_graph_name(None);
+        assert!(!dataset.is_dataset());
+        assert!(dataset.serialize(RdfFormat::Turtle).is_ok());
+    }
+
+    #[test]
+    fn test_merge_relabels_blank_nodes() {
+        let mut merged = record_graph("a");
+        merged.merge(record_graph("b"));
+        let titles: Vec<&RdfNode> = merged
+            .triples()
+            .filter(|t| t.predicate.ends_with("title"))
+            .map(|t| &t.object)
+            .collect();
+        assert_eq!(titles.len(), 2);
+        assert_ne!(titles[0], titles[1]);
+        let label_subjects: Vec<&RdfNode> = merged
+            .triples()
+            .filter(|t| t.predicate.ends_with("label"))
+            .map(|t| &t.subject)
+            .collect();
+        assert_eq!(titles, label_subjects);
+    }
+
+    #[test]
+    fn test_isomorphism_ignores_blank_node_labels() {
+        let a = record_graph("a");
+        let mut relabeled = RdfGraph::new();
+        relabeled.new_blank_node();
+        relabeled.merge(record_graph("a"));
+        assert_ne!(
+            a.triples().collect::<Vec<_>>(),
+            relabeled.triples().collect::<Vec<_>>()
+        );
+        assert!(a.isomorphic_to(&relabeled));
+        assert!(a.diff(&relabeled).is_empty());
+
+        assert!(!a.isomorphic_to(&record_graph("b")));
+        let mut named = record_graph("a");
+        named.set_graph_name(Some(RdfNode::uri("http://example.org/g")));
+        assert!(!a.isomorphic_to(&named));
+    }
+
+    #[test]
+    fn test_diff_reports_changed_triples() {
+        let a = record_graph("a");
+        let mut b = record_graph("a");
+        b.add(
+            RdfNode::uri("http://example.org/work/a"),
+            format!("{}language", namespaces::BF),
+            RdfNode::uri("http://id.loc.gov/vocabulary/languages/eng"),
+        );
+        let diff = a.diff(&b);
+        assert!(diff.removed.is_empty());
+        assert_eq!(diff.added.len(), 1);
+        assert!(diff.added[0].0.predicate.ends_with("language"));
+        assert_eq!(diff.added[0].1, None);
+
+        let reverse = b.diff(&a);
+        assert_eq!(reverse.removed, diff.added);
+        assert!(reverse.added.is_empty());
+    }
 }
@@ -10,7 +10,10 @@ use crate::error::Result;
 use crate::leader::Leader;
 use crate::record::{Field, Record};
 
-use super::namespaces::{BF, RDF, RDFS, RELATORS, classes, properties};
+use super::converter::SUBJECT_INDICATOR_SOURCES;
+use super::namespaces::{
+    BF, GENRE_FORM_SCHEMES, LANGUAGES, RDF, RDFS, RELATORS, SUBJECT_SCHEMES, classes, properties,
+};
 use super::rdf::{RdfGraph, RdfNode};
 
 /// Converts a BIBFRAME RDF graph to a MARC record.
@@ -230,6 +233,22 @@ impl<'a> BibframeToMarcConverter<'a> {
         None
     }
 
+    /// First `id.loc.gov` language code linked directly from the Work.
+    fn extract_language_code(&self) -> Option<&str> {
+        let work_key = self.work_node.as_ref()?;
+        let language = format!("{BF}{}", properties::LANGUAGE);
+        self.subject_index
+            .get(work_key)?
+            .iter()
+            .filter(|(pred, _)| *pred == language)
+            .find_map(|(_, obj)| match obj {
+                RdfNode::Uri(uri) => uri
+                    .strip_prefix(LANGUAGES)
+                    .filter(|code| code.len() == 3 && code.is_ascii()),
+                _ => None,
+            })
+    }
+
     /// Creates a minimal 008 field.
     fn create_008_field(&self) -> String {
         // Minimal 008: 40 characters
@@ -294,8 +313,8 @@ impl<'a> BibframeToMarcConverter<'a> {
         // Biography
         field.push(' ');
 
-        // Language (extract if available)
-        field.push_str("eng");
+        // Language: the Work's first language code, English if it has none
+        field.push_str(self.extract_language_code().unwrap_or("eng"));
 
         // Modified record
         field.push(' ');
@@ -560,7 +579,27 @@ impl<'a> BibframeToMarcConverter<'a> {
             }
         }
 
-        let mut field = Field::new(tag.to_string(), ' ', '0');
+        // Vocabulary: second indicator, or 7 with the code in $2
+        let mut source = None;
+        for (pred, obj) in subject_props {
+            if pred == &format!("{BF}{}", properties::SOURCE)
+                && let RdfNode::Uri(uri) = obj
+            {
+                source = uri
+                    .strip_prefix(SUBJECT_SCHEMES)
+                    .or_else(|| uri.strip_prefix(GENRE_FORM_SCHEMES));
+            }
+        }
+        let indicator2 = match source {
+            // No source recorded: the converter's long-standing LCSH default
+            None | Some("naf") => '0',
+            Some(code) => SUBJECT_INDICATOR_SOURCES
+                .iter()
+                .find(|(_, c)| *c == code)
+                .map_or('7', |(i, _)| *i),
+        };
+
+        let mut field = Field::new(tag.to_string(), ' ', indicator2);
 
         // Extract label
         for (pred, obj) in subject_props {
@@ -577,6 +616,11 @@ impl<'a> BibframeToMarcConverter<'a> {
                 }
             }
         }
+        if indicator2 == '7'
+            && let Some(code) = source
+        {
+            field.add_subfield('2', code.to_string());
+        }
 
         Some(field)
     }
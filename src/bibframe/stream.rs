@@ -0,0 +1,247 @@
+//! Record-at-a-time BIBFRAME conversion to line-based RDF.
+//!
+//! [`marc_to_bibframe`](super::marc_to_bibframe) builds an [`RdfGraph`] per
+//! record; collecting those for a whole catalog keeps every triple in memory.
+//! The functions here convert each record and write its triples straight to
+//! the output as N-Triples or N-Quads, so memory use is bounded by one record
+//! (or one batch, for the parallel variant) however large the input.
+//!
+//! Each record's blank node labels are prefixed with `r{n}_` so labels from
+//! different records never merge. With N-Quads each record's triples go in
+//! their own named graph: `{base_uri}graph/{001}` when a base URI is
+//! configured (falling back to `record{n}` without a control number), or the
+//! blank node `_:r{n}_graph` otherwise.
+
+use std::io::Write;
+
+use crate::error::{MarcError, Result};
+use crate::formats::FormatReader;
+use crate::record::Record;
+
+use super::config::{BibframeConfig, RdfFormat};
+use super::rdf::{RdfGraph, RdfNode};
+
+/// Records converted per batch by [`marc_to_bibframe_stream_parallel`] when
+/// the caller passes a batch size of zero.
+pub const DEFAULT_STREAM_BATCH_SIZE: usize = 1024;
+
+/// Converts every record from `reader` to BIBFRAME and writes the triples to
+/// `writer` as they are produced.
+///
+/// `config.output_format` must be [`RdfFormat::NTriples`] or
+/// [`RdfFormat::NQuads`]; the other formats need the whole graph before they
+/// can be written. Returns the number of records converted.
+///
+/// # Errors
+///
+/// Returns an error if the output format cannot be streamed, if reading a
+/// record fails, or if writing fails. Records converted before the error
+/// have already been written.
+///
+/// # Examples
+///
+/// ```no_run
+/// use mrrc::MarcReader;
+/// use mrrc::bibframe::{marc_to_bibframe_stream, BibframeConfig, RdfFormat};
+/// use std::fs::File;
+/// use std::io::BufWriter;
+///
+/// let reader = MarcReader::new(File::open("catalog.mrc")?);
+/// let writer = BufWriter::new(File::create("catalog.nq")?);
+/// let config = BibframeConfig::new()
+///     .with_base_uri("http://example.org/")
+///     .with_output_format(RdfFormat::NQuads);
+/// let count = marc_to_bibframe_stream(reader, writer, &config)?;
+/// println!("converted {count} records");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn marc_to_bibframe_stream<R: FormatReader, W: Write>(
+    mut reader: R,
+    mut writer: W,
+    config: &BibframeConfig,
+) -> Result<usize> {
+    check_stream_format(config.output_format)?;
+    let mut count = 0;
+    while let Some(record) = reader.read_record()? {
+        count += 1;
+        write_record(&record, count, config, &mut writer)?;
+    }
+    writer.flush()?;
+    Ok(count)
+}
+
+/// Like [`marc_to_bibframe_stream`], but converts records on the Rayon pool
+/// in batches of `batch_size` (zero means [`DEFAULT_STREAM_BATCH_SIZE`]).
+///
+/// Output is identical to the sequential version, in input order. Memory use
+/// is bounded by one batch of records and their serialized triples.
+///
+/// # Errors
+///
+/// As for [`marc_to_bibframe_stream`].
+pub fn marc_to_bibframe_stream_parallel<R: FormatReader, W: Write>(
+    mut reader: R,
+    mut writer: W,
+    config: &BibframeConfig,
+    batch_size: usize,
+) -> Result<usize> {
+    use rayon::prelude::*;
+
+    check_stream_format(config.output_format)?;
+    let batch_size = if batch_size == 0 {
+        DEFAULT_STREAM_BATCH_SIZE
+    } else {
+        batch_size
+    };
+    let mut count = 0;
+    let mut batch = Vec::with_capacity(batch_size);
+    loop {
+        batch.clear();
+        while batch.len() < batch_size {
+            match reader.read_record()? {
+                Some(record) => batch.push(record),
+                None => break,
+            }
+        }
+        if batch.is_empty() {
+            break;
+        }
+        let chunks = batch
+            .par_iter()
+            .enumerate()
+            .map(|(i, record)| {
+                let mut chunk = Vec::new();
+                write_record(record, count + i + 1, config, &mut chunk)?;
+                Ok(chunk)
+            })
+            .collect::<Result<Vec<Vec<u8>>>>()?;
+        for chunk in chunks {
+            writer.write_all(&chunk)?;
+        }
+        count += batch.len();
+    }
+    writer.flush()?;
+    Ok(count)
+}
+
+fn check_stream_format(format: RdfFormat) -> Result<()> {
+    match format {
+        RdfFormat::NTriples | RdfFormat::NQuads => Ok(()),
+        other => Err(MarcError::invalid_field_msg(format!(
+            "streaming BIBFRAME output must be N-Triples or N-Quads, not {other}"
+        ))),
+    }
+}
+
+/// Converts `record`, the `index`th (1-based) in the stream, and writes its
+/// triples.
+fn write_record<W: Write>(
+    record: &Record,
+    index: usize,
+    config: &BibframeConfig,
+    writer: W,
+) -> Result<()> {
+    let mut graph: RdfGraph = super::marc_to_bibframe(record, config);
+    // Every record gets its own graph in N-Quads; N-Triples has no graphs
+    let name =
+        (config.output_format == RdfFormat::NQuads).then(|| graph_name(record, index, config));
+    graph.set_graph_name(name);
+    graph.serialize_stream_chunk(writer, config.output_format, &format!("r{index}_"))
+}
+
+/// Named graph holding one record's triples.
+fn graph_name(record: &Record, index: usize, config: &BibframeConfig) -> RdfNode {
+    let has_id = config.use_control_number
+        && record
+            .get_control_field("001")
+            .is_some_and(|id| !id.trim().is_empty());
+    match &config.base_uri {
+        Some(_) if has_id => {
+            RdfNode::uri(super::converter::record_graph_uri(record, config).unwrap_or_default())
+        },
+        Some(base) => RdfNode::uri(format!("{base}graph/record{index}")),
+        // Relabeled to `_:r{index}_graph` with the record's other blank nodes
+        None => RdfNode::blank("graph"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::leader::Leader;
+    use crate::reader::MarcReader;
+    use crate::record::Field;
+    use crate::writer::MarcWriter;
+
+    fn catalog(count: usize) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        let mut writer = MarcWriter::new(&mut buffer);
+        for i in 0..count {
+            let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+            record.add_control_field_str("001", &format!("rec{i}"));
+            record.add_field(
+                Field::builder("245".to_string(), '1', '0')
+                    .subfield_str('a', &format!("Title {i}"))
+                    .build(),
+            );
+            writer.write_record(&record).unwrap();
+        }
+        buffer
+    }
+
+    #[test]
+    fn nquads_put_each_record_in_its_own_graph() {
+        let data = catalog(3);
+        let config = BibframeConfig::new()
+            .with_base_uri("http://example.org/")
+            .with_output_format(RdfFormat::NQuads);
+        let mut out = Vec::new();
+        let count = marc_to_bibframe_stream(MarcReader::new(&data[..]), &mut out, &config).unwrap();
+        assert_eq!(count, 3);
+
+        let text = String::from_utf8(out).unwrap();
+        for i in 0..3 {
+            let graph = format!("<http://example.org/graph/rec{i}> .");
+            let lines: Vec<_> = text.lines().filter(|l| l.ends_with(&graph)).collect();
+            assert!(lines.iter().any(|l| l.contains(&format!("\"Title {i}\""))));
+            assert!(lines.iter().any(|l| l.contains(&format!("_:r{}_b", i + 1))));
+        }
+        assert!(text.lines().all(|l| l.ends_with("> .")));
+    }
+
+    #[test]
+    fn parallel_output_matches_sequential() {
+        let data = catalog(7);
+        let config = BibframeConfig::new().with_output_format(RdfFormat::NQuads);
+        let mut sequential = Vec::new();
+        marc_to_bibframe_stream(MarcReader::new(&data[..]), &mut sequential, &config).unwrap();
+        let mut parallel = Vec::new();
+        let count =
+            marc_to_bibframe_stream_parallel(MarcReader::new(&data[..]), &mut parallel, &config, 3)
+                .unwrap();
+        assert_eq!(count, 7);
+        assert_eq!(sequential, parallel);
+        let text = String::from_utf8(parallel).unwrap();
+        assert!(text.lines().any(|l| l.ends_with("_:r7_graph .")));
+    }
+
+    #[test]
+    fn ntriples_stream_and_unstreamable_formats() {
+        let data = catalog(2);
+        let config = BibframeConfig::new().with_output_format(RdfFormat::NTriples);
+        let mut out = Vec::new();
+        marc_to_bibframe_stream(MarcReader::new(&data[..]), &mut out, &config).unwrap();
+        let graph = RdfGraph::parse(std::str::from_utf8(&out).unwrap(), RdfFormat::NTriples)
+            .expect("valid N-Triples");
+        let single = super::super::marc_to_bibframe(
+            &MarcReader::new(&data[..]).read_record().unwrap().unwrap(),
+            &config,
+        );
+        assert_eq!(graph.len(), single.len() * 2);
+
+        let turtle = BibframeConfig::new().with_output_format(RdfFormat::Turtle);
+        let err =
+            marc_to_bibframe_stream(MarcReader::new(&data[..]), Vec::new(), &turtle).unwrap_err();
+        assert!(err.to_string().contains("N-Quads"));
+    }
+}
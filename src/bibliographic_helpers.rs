@@ -122,6 +122,147 @@ impl IsbnValidator {
     pub fn normalize(isbn: &str) -> String {
         isbn.replace(['-', ' '], "")
     }
+
+    /// Convert a valid ISBN-10 or ISBN-13 to ISBN-13 form
+    ///
+    /// Returns `None` if the ISBN is not valid. Equivalent to
+    /// [`normalize_isbn`](crate::normalize::normalize_isbn).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mrrc::IsbnValidator;
+    ///
+    /// assert_eq!(IsbnValidator::to_isbn13("0-306-40615-2").as_deref(), Some("9780306406157"));
+    /// assert_eq!(IsbnValidator::to_isbn13("9780306406157").as_deref(), Some("9780306406157"));
+    /// assert_eq!(IsbnValidator::to_isbn13("0306406153"), None);
+    /// ```
+    #[must_use]
+    pub fn to_isbn13(isbn: &str) -> Option<String> {
+        crate::normalize::normalize_isbn(isbn)
+    }
+}
+
+/// ISSN (International Standard Serial Number) validator
+#[derive(Debug)]
+pub struct IssnValidator;
+
+impl IssnValidator {
+    /// Validate an ISSN's structure and check digit
+    ///
+    /// An ISSN is seven digits and a check digit (0-9 or 'X'), usually
+    /// written with a hyphen after the fourth (`0378-5955`). The check
+    /// digit makes the digits, weighted 8 down to 1, a multiple of 11.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mrrc::IssnValidator;
+    ///
+    /// assert!(IssnValidator::validate("0378-5955"));
+    /// assert!(IssnValidator::validate("2434-561x"));
+    /// assert!(!IssnValidator::validate("0378-5954"));
+    /// ```
+    #[must_use]
+    pub fn validate(issn: &str) -> bool {
+        let clean = Self::normalize(issn);
+        let bytes = clean.as_bytes();
+        if bytes.len() != 8 || !bytes[..7].iter().all(u8::is_ascii_digit) {
+            return false;
+        }
+        let sum: u32 = bytes[..7]
+            .iter()
+            .zip((2..=8).rev())
+            .map(|(b, weight)| u32::from(b - b'0') * weight)
+            .sum();
+        let check = match bytes[7] {
+            b'X' => 10,
+            b @ b'0'..=b'9' => u32::from(b - b'0'),
+            _ => return false,
+        };
+        (sum + check).is_multiple_of(11)
+    }
+
+    /// The ISSN without hyphens or spaces, uppercased
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mrrc::IssnValidator;
+    ///
+    /// assert_eq!(IssnValidator::normalize("2434-561x"), "2434561X");
+    /// ```
+    #[must_use]
+    pub fn normalize(issn: &str) -> String {
+        issn.replace(['-', ' '], "").to_ascii_uppercase()
+    }
+}
+
+/// LCCN (Library of Congress Control Number) validator and normalizer
+#[derive(Debug)]
+pub struct LccnValidator;
+
+impl LccnValidator {
+    /// Normalize an LCCN following the Library of Congress rules
+    ///
+    /// Equivalent to [`normalize_lccn`](crate::normalize::normalize_lccn):
+    /// blanks are removed, along with a forward slash and anything after it
+    /// (`/AC` and similar suffixes); a hyphenated serial number is
+    /// zero-padded to six digits and the hyphen dropped. Prefix letters keep
+    /// their case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mrrc::LccnValidator;
+    ///
+    /// assert_eq!(LccnValidator::normalize("   85000002 "), "85000002");
+    /// assert_eq!(LccnValidator::normalize("n 78-890351 "), "n78890351");
+    /// assert_eq!(LccnValidator::normalize("85-2 "), "85000002");
+    /// assert_eq!(LccnValidator::normalize("75-425165//r75"), "75425165");
+    /// ```
+    #[must_use]
+    pub fn normalize(lccn: &str) -> String {
+        crate::normalize::normalize_lccn(lccn)
+    }
+
+    /// Validate the structure of an LCCN
+    ///
+    /// After [`normalize`](Self::normalize), a valid LCCN is an optional
+    /// alphabetic prefix followed by a two-digit year and six-digit serial
+    /// number (prefix up to three letters), or a four-digit year and
+    /// six-digit serial (prefix up to two letters). A hyphenated serial
+    /// number must be all digits. LCCNs carry no check digit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mrrc::LccnValidator;
+    ///
+    /// assert!(LccnValidator::validate("   85000002 "));
+    /// assert!(LccnValidator::validate("sh 85026371 "));
+    /// assert!(LccnValidator::validate("2001-000002"));
+    /// assert!(!LccnValidator::validate("85-00000x"));
+    /// assert!(!LccnValidator::validate("abcd85000002"));
+    /// ```
+    #[must_use]
+    pub fn validate(lccn: &str) -> bool {
+        let body = lccn.split('/').next().unwrap_or_default();
+        if let Some((_, serial)) = body.split_once('-')
+            && !serial.trim().chars().all(|c| c.is_ascii_digit())
+        {
+            return false;
+        }
+        let clean = Self::normalize(lccn);
+        let prefix = clean.chars().take_while(char::is_ascii_alphabetic).count();
+        let digits = &clean[prefix..];
+        digits.chars().all(|c| c.is_ascii_digit())
+            && match digits.len() {
+                8 => prefix <= 3,
+                10 => prefix <= 2,
+                _ => false,
+            }
+    }
 }
 
 /// Publication information parser
@@ -307,6 +448,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_isbn10_to_isbn13() {
+        assert_eq!(
+            IsbnValidator::to_isbn13("043942089X").as_deref(),
+            Some("9780439420891")
+        );
+        assert_eq!(IsbnValidator::to_isbn13("9780306406158"), None);
+    }
+
+    #[test]
+    fn test_issn_and_lccn_validation() {
+        assert!(IssnValidator::validate("0317-8471"));
+        assert!(!IssnValidator::validate("0317-847"));
+        assert!(!IssnValidator::validate("X317-8471"));
+
+        assert_eq!(LccnValidator::normalize("agr 62-298"), "agr62000298");
+        assert!(LccnValidator::validate("agr 62-298"));
+        assert!(!LccnValidator::validate("agr2001000002"));
+        assert!(!LccnValidator::validate(""));
+
+        // One normalization rule, shared with `normalize::normalize_lccn`
+        for lccn in ["SH 85-26371", "85-00000x", "n 78-890351 /AC"] {
+            assert_eq!(
+                LccnValidator::normalize(lccn),
+                crate::normalize::normalize_lccn(lccn)
+            );
+        }
+        assert_eq!(LccnValidator::normalize("SH 85-26371"), "SH85026371");
+        assert!(LccnValidator::validate("SH 85-26371"));
+    }
+
     #[test]
     fn test_publication_year_extraction() {
         let info = PublicationInfo::new(None, None, Some("2020".to_string()));
@@ -0,0 +1,545 @@
+//! Field- and subfield-level change tracking for automated processing.
+//!
+//! A [`TrackedRecord`] holds a record and a [`ChangeLog`] of what each
+//! transformation changed: the rule that ran, where (tag, occurrence,
+//! subfield), and the old and new values. [`TrackedRecord::track`] runs any
+//! edit — the library's own rewriting passes such as [`Record::overlay`],
+//! [`repair_mojibake`](crate::mojibake::repair_mojibake),
+//! [`EncodingValidator::repair`](crate::encoding_validation::EncodingValidator::repair),
+//! [`fix_record`](crate::fixed_field::fix_record) and
+//! [`normalize_urls`](crate::urls::normalize_urls), or a closure of the
+//! caller's — and logs its changes under a rule id of the caller's choosing.
+//! Records that are not wrapped cost nothing; each tracked step compares
+//! the record before and after, so it costs one record clone.
+//!
+//! The log serializes with serde, and [`ChangeLogWriter`] writes one JSON
+//! line per changed record next to the records themselves.
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::change_log::{ChangeKind, TrackedRecord};
+//! use mrrc::{Field, Leader, Record};
+//!
+//! let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+//! record.add_field(
+//!     Field::builder("245".to_string(), '1', '0')
+//!         .subfield_str('a', "Old title")
+//!         .build(),
+//! );
+//! let mut tracked = TrackedRecord::new(record);
+//!
+//! tracked.track("retitle", |r| {
+//!     r.get_fields_mut("245").unwrap()[0].subfields[0].value = "New title".to_string();
+//! });
+//!
+//! let entry = &tracked.change_log().entries()[0];
+//! assert_eq!(entry.rule, "retitle");
+//! assert_eq!(entry.kind, ChangeKind::Modified);
+//! assert_eq!(entry.subfield, Some('a'));
+//! assert_eq!(entry.old.as_deref(), Some("Old title"));
+//! assert_eq!(entry.new.as_deref(), Some("New title"));
+//! ```
+
+use std::io::Write;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{MarcError, Result};
+use crate::formats::FormatWriter;
+use crate::record::{Field, Record};
+
+/// Tag used in change entries for the leader.
+pub const LEADER_TAG: &str = "LDR";
+
+/// What happened to the changed element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    /// The element was added; `old` is `None`.
+    Added,
+    /// The element was removed; `new` is `None`.
+    Removed,
+    /// The element's value changed.
+    Modified,
+}
+
+/// One change made by a transformation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangeEntry {
+    /// Id of the rule or step that made the change
+    pub rule: String,
+    /// Field tag, or [`LEADER_TAG`] for the leader
+    pub tag: String,
+    /// 0-based occurrence of the tag in the record
+    pub occurrence: usize,
+    /// Subfield code, for a subfield change
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subfield: Option<char>,
+    /// 0-based position of the subfield within the field
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<usize>,
+    /// True for a change to the indicators only
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub indicators: bool,
+    /// Kind of change
+    pub kind: ChangeKind,
+    /// Value before the change
+    pub old: Option<String>,
+    /// Value after the change
+    pub new: Option<String>,
+}
+
+/// Changes recorded on one record, in the order they were made.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangeLog {
+    entries: Vec<ChangeEntry>,
+}
+
+impl ChangeLog {
+    /// All entries, oldest first.
+    #[must_use]
+    pub fn entries(&self) -> &[ChangeEntry] {
+        &self.entries
+    }
+
+    /// Entries made by `rule`.
+    pub fn by_rule<'a>(&'a self, rule: &'a str) -> impl Iterator<Item = &'a ChangeEntry> + 'a {
+        self.entries.iter().filter(move |entry| entry.rule == rule)
+    }
+
+    /// Number of entries.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether nothing has been recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Record the differences between `before` and `after` under `rule`.
+    pub fn record_diff(&mut self, rule: &str, before: &Record, after: &Record) {
+        self.entries.extend(diff_records(rule, before, after));
+    }
+}
+
+/// A record and the log of the tracked changes made to it.
+///
+/// Edits made through [`TrackedRecord::track`] are logged; edits through
+/// [`TrackedRecord::record_mut`] are not.
+#[derive(Debug, Clone)]
+pub struct TrackedRecord {
+    record: Record,
+    log: ChangeLog,
+}
+
+impl TrackedRecord {
+    /// Start tracking changes to `record`, with an empty log.
+    #[must_use]
+    pub fn new(record: Record) -> Self {
+        TrackedRecord {
+            record,
+            log: ChangeLog::default(),
+        }
+    }
+
+    /// The record as it stands.
+    #[must_use]
+    pub fn record(&self) -> &Record {
+        &self.record
+    }
+
+    /// The record, for edits that should not be logged.
+    pub fn record_mut(&mut self) -> &mut Record {
+        &mut self.record
+    }
+
+    /// The changes logged so far.
+    #[must_use]
+    pub fn change_log(&self) -> &ChangeLog {
+        &self.log
+    }
+
+    /// Stop tracking, returning the record and its log.
+    #[must_use]
+    pub fn into_parts(self) -> (Record, ChangeLog) {
+        (self.record, self.log)
+    }
+
+    /// Run `edit` on the record, logging what it changes under `rule`.
+    pub fn track<T>(&mut self, rule: &str, edit: impl FnOnce(&mut Record) -> T) -> T {
+        let before = self.record.clone();
+        let result = edit(&mut self.record);
+        self.log.record_diff(rule, &before, &self.record);
+        result
+    }
+}
+
+/// The changes that turn `before` into `after`, attributed to `rule`.
+///
+/// Fields are matched by tag and occurrence, and subfields by position, so
+/// inserting a field in the middle of a run of repeated tags reports the
+/// later occurrences as modified.
+#[must_use]
+pub fn diff_records(rule: &str, before: &Record, after: &Record) -> Vec<ChangeEntry> {
+    let mut entries = Vec::new();
+    let entry =
+        |tag: &str, occurrence, kind, old: Option<String>, new: Option<String>| ChangeEntry {
+            rule: rule.to_string(),
+            tag: tag.to_string(),
+            occurrence,
+            subfield: None,
+            position: None,
+            indicators: false,
+            kind,
+            old,
+            new,
+        };
+
+    let (old_leader, new_leader) = (before.leader.to_string(), after.leader.to_string());
+    if old_leader != new_leader {
+        entries.push(entry(
+            LEADER_TAG,
+            0,
+            ChangeKind::Modified,
+            Some(old_leader),
+            Some(new_leader),
+        ));
+    }
+
+    let control_tags = before.control_fields.keys().chain(
+        after
+            .control_fields
+            .keys()
+            .filter(|t| !before.control_fields.contains_key(*t)),
+    );
+    for tag in control_tags {
+        let old = before
+            .control_fields
+            .get(tag)
+            .map_or(&[][..], Vec::as_slice);
+        let new = after.control_fields.get(tag).map_or(&[][..], Vec::as_slice);
+        for occurrence in 0..old.len().max(new.len()) {
+            match (old.get(occurrence), new.get(occurrence)) {
+                (Some(a), Some(b)) if a != b => entries.push(entry(
+                    tag,
+                    occurrence,
+                    ChangeKind::Modified,
+                    Some(a.clone()),
+                    Some(b.clone()),
+                )),
+                (Some(a), None) => entries.push(entry(
+                    tag,
+                    occurrence,
+                    ChangeKind::Removed,
+                    Some(a.clone()),
+                    None,
+                )),
+                (None, Some(b)) => entries.push(entry(
+                    tag,
+                    occurrence,
+                    ChangeKind::Added,
+                    None,
+                    Some(b.clone()),
+                )),
+                _ => {},
+            }
+        }
+    }
+
+    let field_tags = before.fields.keys().chain(
+        after
+            .fields
+            .keys()
+            .filter(|t| !before.fields.contains_key(*t)),
+    );
+    for tag in field_tags {
+        let old = before.fields.get(tag).map_or(&[][..], Vec::as_slice);
+        let new = after.fields.get(tag).map_or(&[][..], Vec::as_slice);
+        for occurrence in 0..old.len().max(new.len()) {
+            match (old.get(occurrence), new.get(occurrence)) {
+                (Some(a), Some(b)) => diff_fields(rule, occurrence, a, b, &mut entries),
+                (Some(a), None) => entries.push(entry(
+                    tag,
+                    occurrence,
+                    ChangeKind::Removed,
+                    Some(field_text(a)),
+                    None,
+                )),
+                (None, Some(b)) => entries.push(entry(
+                    tag,
+                    occurrence,
+                    ChangeKind::Added,
+                    None,
+                    Some(field_text(b)),
+                )),
+                (None, None) => {},
+            }
+        }
+    }
+    entries
+}
+
+fn diff_fields(
+    rule: &str,
+    occurrence: usize,
+    old: &Field,
+    new: &Field,
+    out: &mut Vec<ChangeEntry>,
+) {
+    let base = ChangeEntry {
+        rule: rule.to_string(),
+        tag: new.tag.clone(),
+        occurrence,
+        subfield: None,
+        position: None,
+        indicators: false,
+        kind: ChangeKind::Modified,
+        old: None,
+        new: None,
+    };
+    if (old.indicator1, old.indicator2) != (new.indicator1, new.indicator2) {
+        out.push(ChangeEntry {
+            indicators: true,
+            old: Some(format!("{}{}", old.indicator1, old.indicator2)),
+            new: Some(format!("{}{}", new.indicator1, new.indicator2)),
+            ..base.clone()
+        });
+    }
+    for position in 0..old.subfields.len().max(new.subfields.len()) {
+        let subfield = |code, kind, old, new| ChangeEntry {
+            subfield: Some(code),
+            position: Some(position),
+            kind,
+            old,
+            new,
+            ..base.clone()
+        };
+        match (old.subfields.get(position), new.subfields.get(position)) {
+            (Some(a), Some(b)) if a.code == b.code => {
+                if a.value != b.value {
+                    out.push(subfield(
+                        a.code,
+                        ChangeKind::Modified,
+                        Some(a.value.clone()),
+                        Some(b.value.clone()),
+                    ));
+                }
+            },
+            (a, b) => {
+                if let Some(a) = a {
+                    out.push(subfield(
+                        a.code,
+                        ChangeKind::Removed,
+                        Some(a.value.clone()),
+                        None,
+                    ));
+                }
+                if let Some(b) = b {
+                    out.push(subfield(
+                        b.code,
+                        ChangeKind::Added,
+                        None,
+                        Some(b.value.clone()),
+                    ));
+                }
+            },
+        }
+    }
+}
+
+/// A field as `ind1ind2$avalue$bvalue`, the form used for whole-field
+/// entries.
+fn field_text(field: &Field) -> String {
+    let mut text = format!("{}{}", field.indicator1, field.indicator2);
+    for subfield in &field.subfields {
+        text.push('$');
+        text.push(subfield.code);
+        text.push_str(&subfield.value);
+    }
+    text
+}
+
+/// The JSON line [`ChangeLogWriter`] writes for one record.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordChanges {
+    /// 1-based position of the record in the output
+    pub record: usize,
+    /// The record's 001, if any
+    pub control_number: Option<String>,
+    /// What changed
+    pub changes: Vec<ChangeEntry>,
+}
+
+/// Writes records to an inner writer and their change logs, as JSON
+/// lines, to a second output.
+///
+/// Records written with [`ChangeLogWriter::write_tracked`] get a line when
+/// they have logged changes; records written through [`FormatWriter`] get
+/// none, but still count towards the record numbers.
+#[derive(Debug)]
+pub struct ChangeLogWriter<W, L: Write> {
+    inner: W,
+    log: L,
+    records: usize,
+}
+
+impl<W: FormatWriter, L: Write + std::fmt::Debug> ChangeLogWriter<W, L> {
+    /// Write records to `inner` and change logs to `log`.
+    pub fn new(inner: W, log: L) -> Self {
+        ChangeLogWriter {
+            inner,
+            log,
+            records: 0,
+        }
+    }
+
+    /// Return the record writer and the log output.
+    pub fn into_inner(self) -> (W, L) {
+        (self.inner, self.log)
+    }
+
+    /// Write a tracked record and, if it has changes, its log line.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the record or the log line cannot be written.
+    pub fn write_tracked(&mut self, tracked: &TrackedRecord) -> Result<()> {
+        self.write(tracked.record(), Some(tracked.change_log()))
+    }
+
+    fn write(&mut self, record: &Record, log: Option<&ChangeLog>) -> Result<()> {
+        self.inner.write_record(record)?;
+        self.records += 1;
+        if let Some(log) = log.filter(|log| !log.is_empty()) {
+            let line = RecordChanges {
+                record: self.records,
+                control_number: record.get_control_field("001").map(str::to_string),
+                changes: log.entries().to_vec(),
+            };
+            let json = serde_json::to_string(&line)
+                .map_err(|e| MarcError::invalid_field_msg(format!("change log: {e}")))?;
+            writeln!(self.log, "{json}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<W: FormatWriter, L: Write + std::fmt::Debug> FormatWriter for ChangeLogWriter<W, L> {
+    fn write_record(&mut self, record: &Record) -> Result<()> {
+        self.write(record, None)
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        self.inner.finish()?;
+        self.log.flush()?;
+        Ok(())
+    }
+
+    fn records_written(&self) -> Option<usize> {
+        self.inner.records_written()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field_protection::FieldProtection;
+    use crate::leader::Leader;
+    use crate::writer::MarcWriter;
+
+    fn record(title: &str) -> Record {
+        let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+        record.add_control_field_str("001", "ocm1");
+        record.add_field(
+            Field::builder("245".to_string(), '1', '0')
+                .subfield_str('a', title)
+                .subfield_str('c', "Author")
+                .build(),
+        );
+        record
+    }
+
+    #[test]
+    fn overlay_and_encoding_fixes_are_logged() {
+        let mut local = record("Caf\u{c3}\u{a9}");
+        local.add_field(
+            Field::builder("949".to_string(), ' ', ' ')
+                .subfield_str('a', "local")
+                .build(),
+        );
+        let mut local = TrackedRecord::new(local);
+        local.track("mojibake", crate::mojibake::repair_mojibake);
+
+        let mut vendor = record("Café");
+        vendor.add_field(
+            Field::builder("650".to_string(), ' ', '0')
+                .subfield_str('a', "Coffee")
+                .build(),
+        );
+        local.track("overlay", |r| {
+            r.overlay(vendor, &FieldProtection::local_fields());
+        });
+
+        let log = local.change_log();
+        let fixes: Vec<_> = log.by_rule("mojibake").collect();
+        assert_eq!(fixes.len(), 1);
+        assert_eq!(
+            (fixes[0].tag.as_str(), fixes[0].subfield, fixes[0].position),
+            ("245", Some('a'), Some(0))
+        );
+        assert_eq!(fixes[0].new.as_deref(), Some("Café"));
+
+        let merged: Vec<_> = log.by_rule("overlay").collect();
+        assert_eq!(merged.len(), 1, "{merged:?}");
+        assert_eq!(merged[0].tag, "650");
+        assert_eq!(merged[0].kind, ChangeKind::Added);
+        assert_eq!(merged[0].new.as_deref(), Some(" 0$aCoffee"));
+    }
+
+    #[test]
+    fn only_tracked_edits_are_logged() {
+        let mut tracked = TrackedRecord::new(record("Title"));
+        tracked.record_mut().add_control_field_str("003", "OCoLC");
+        assert!(tracked.change_log().is_empty());
+
+        tracked.track("edit", |r| {
+            let field = &mut r.get_fields_mut("245").unwrap()[0];
+            field.indicator2 = '4';
+            field.subfields.remove(1);
+        });
+        let (record, log) = tracked.into_parts();
+        let entries = log.entries();
+        assert_eq!(entries.len(), 2);
+        assert!(entries[0].indicators);
+        assert_eq!(entries[0].old.as_deref(), Some("10"));
+        assert_eq!(entries[1].kind, ChangeKind::Removed);
+        assert_eq!(entries[1].subfield, Some('c'));
+        assert_eq!(record.get_control_field("003"), Some("OCoLC"));
+    }
+
+    #[test]
+    fn writer_emits_json_lines_for_changed_records() {
+        let mut changed = TrackedRecord::new(record("Title"));
+        changed.track("stamp", |r| r.add_control_field_str("003", "OCoLC"));
+        let unchanged = TrackedRecord::new(record("Other"));
+
+        let mut writer = ChangeLogWriter::new(MarcWriter::new(Vec::new()), Vec::new());
+        writer.write_tracked(&unchanged).unwrap();
+        writer.write_tracked(&changed).unwrap();
+        writer.finish().unwrap();
+        let (_, log) = writer.into_inner();
+
+        let text = String::from_utf8(log).unwrap();
+        let lines: Vec<_> = text.lines().collect();
+        assert_eq!(lines.len(), 1);
+        let parsed: RecordChanges = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(parsed.record, 2);
+        assert_eq!(parsed.control_number.as_deref(), Some("ocm1"));
+        assert_eq!(parsed.changes[0].tag, "003");
+        assert_eq!(parsed.changes[0].kind, ChangeKind::Added);
+    }
+}
@@ -0,0 +1,443 @@
+//! Resumable processing of long record streams.
+//!
+//! A multi-hour conversion that dies near the end should not have to start
+//! over. A [`Checkpointer`] remembers the byte offset just past the last
+//! record that was fully processed and saves it through a
+//! [`CheckpointStore`] (a file with [`FileCheckpointStore`]) every so many
+//! records. On the next run the saved offset is where reading resumes.
+//!
+//! [`CheckpointedReader`] does this for any seekable ISO 2709 source: a
+//! record counts as processed once the next one is requested, or when
+//! [`CheckpointedReader::commit`] is called. For the parallel
+//! [`ProducerConsumerPipeline`], start it with
+//! [`ProducerConsumerPipeline::from_file_at`] at the resume offset and
+//! report [`ProducerConsumerPipeline::position`] to a [`Checkpointer`]
+//! after each record:
+//!
+//! ```no_run
+//! use mrrc::checkpoint::{Checkpointer, FileCheckpointStore};
+//! use mrrc::producer_consumer_pipeline::{PipelineConfig, ProducerConsumerPipeline};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut checkpointer = Checkpointer::new(FileCheckpointStore::new("convert.ckpt"))?.every(10_000);
+//! let pipeline = ProducerConsumerPipeline::from_file_at(
+//!     "catalog.mrc",
+//!     checkpointer.offset(),
+//!     &PipelineConfig::default(),
+//! )?;
+//! while let Some(record) = pipeline.next()? {
+//!     // ... convert and write `record` ...
+//!     checkpointer.processed(pipeline.position())?;
+//! }
+//! checkpointer.commit()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Output written after the last saved checkpoint is written again on
+//! resume, so writers should either be idempotent or be truncated to a
+//! matching point, for example by checkpointing only after flushing.
+//!
+//! [`ProducerConsumerPipeline`]: crate::producer_consumer_pipeline::ProducerConsumerPipeline
+//! [`ProducerConsumerPipeline::from_file_at`]: crate::producer_consumer_pipeline::ProducerConsumerPipeline::from_file_at
+//! [`ProducerConsumerPipeline::position`]: crate::producer_consumer_pipeline::ProducerConsumerPipeline::position
+
+use std::fs;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::PathBuf;
+
+use crate::error::{MarcError, Result};
+use crate::reader::parse_record_from_bytes;
+use crate::record::Record;
+use crate::record_index::read_raw_record;
+use crate::recovery::{RecoveryMode, ValidationLevel};
+
+/// First line of a checkpoint file.
+const CHECKPOINT_HEADER: &str = "mrrc-checkpoint\t1";
+
+/// Progress through a record stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Checkpoint {
+    /// Byte offset just past the last processed record
+    pub byte_offset: u64,
+    /// Records processed up to that offset
+    pub records: u64,
+}
+
+/// Where checkpoints are persisted.
+pub trait CheckpointStore {
+    /// The last saved checkpoint, or `None` for a fresh start.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a saved checkpoint exists but cannot be read.
+    fn load(&mut self) -> Result<Option<Checkpoint>>;
+
+    /// Persist `checkpoint`, replacing the previous one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the checkpoint cannot be saved.
+    fn save(&mut self, checkpoint: &Checkpoint) -> Result<()>;
+}
+
+/// Keeps the checkpoint in a small text file, replaced atomically on save.
+#[derive(Debug, Clone)]
+pub struct FileCheckpointStore {
+    path: PathBuf,
+}
+
+impl FileCheckpointStore {
+    /// Store checkpoints at `path`. A missing file means a fresh start.
+    #[must_use]
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        FileCheckpointStore { path: path.into() }
+    }
+
+    /// Delete the checkpoint file, e.g. once the job has completed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be removed.
+    pub fn clear(&self) -> Result<()> {
+        match fs::remove_file(&self.path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl CheckpointStore for FileCheckpointStore {
+    fn load(&mut self) -> Result<Option<Checkpoint>> {
+        let text = match fs::read_to_string(&self.path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        let mut lines = text.lines();
+        let checkpoint = if lines.next() == Some(CHECKPOINT_HEADER) {
+            lines.next().and_then(|line| {
+                let (offset, records) = line.split_once('\t')?;
+                Some(Checkpoint {
+                    byte_offset: offset.parse().ok()?,
+                    records: records.parse().ok()?,
+                })
+            })
+        } else {
+            None
+        };
+        checkpoint.map(Some).ok_or_else(|| {
+            MarcError::invalid_field_msg(format!("Invalid checkpoint file {}", self.path.display()))
+        })
+    }
+
+    fn save(&mut self, checkpoint: &Checkpoint) -> Result<()> {
+        let mut temp = self.path.clone().into_os_string();
+        temp.push(".tmp");
+        fs::write(
+            &temp,
+            format!(
+                "{CHECKPOINT_HEADER}\n{}\t{}\n",
+                checkpoint.byte_offset, checkpoint.records
+            ),
+        )?;
+        fs::rename(&temp, &self.path)?;
+        Ok(())
+    }
+}
+
+/// Tracks processed records and saves a checkpoint every `n` of them.
+#[derive(Debug)]
+pub struct Checkpointer<S> {
+    store: S,
+    current: Checkpoint,
+    saved: Checkpoint,
+    interval: u64,
+}
+
+impl<S: CheckpointStore> Checkpointer<S> {
+    /// Load the last checkpoint from `store`, if any. Saves every 1000
+    /// records by default.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store cannot be read.
+    pub fn new(mut store: S) -> Result<Self> {
+        let saved = store.load()?.unwrap_or_default();
+        Ok(Checkpointer {
+            store,
+            current: saved,
+            saved,
+            interval: 1000,
+        })
+    }
+
+    /// Save after every `n` processed records (at least 1).
+    #[must_use]
+    pub fn every(mut self, n: u64) -> Self {
+        self.interval = n.max(1);
+        self
+    }
+
+    /// Byte offset to resume reading from.
+    #[must_use]
+    pub fn offset(&self) -> u64 {
+        self.saved.byte_offset
+    }
+
+    /// Progress so far, including records not yet saved.
+    #[must_use]
+    pub fn current(&self) -> Checkpoint {
+        self.current
+    }
+
+    /// The underlying store.
+    pub fn store_mut(&mut self) -> &mut S {
+        &mut self.store
+    }
+
+    /// Note that the record ending at `end_offset` has been processed,
+    /// saving a checkpoint if the interval has been reached.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a due checkpoint cannot be saved.
+    pub fn processed(&mut self, end_offset: u64) -> Result<()> {
+        self.current.byte_offset = end_offset;
+        self.current.records += 1;
+        if self.current.records - self.saved.records >= self.interval {
+            self.commit()?;
+        }
+        Ok(())
+    }
+
+    /// Save the current progress now.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the checkpoint cannot be saved.
+    pub fn commit(&mut self) -> Result<()> {
+        if self.current != self.saved {
+            self.store.save(&self.current)?;
+            self.saved = self.current;
+        }
+        Ok(())
+    }
+}
+
+/// Reads ISO 2709 records from a seekable source, resuming from and saving
+/// checkpoints.
+#[derive(Debug)]
+pub struct CheckpointedReader<R, S> {
+    source: R,
+    checkpointer: Checkpointer<S>,
+    /// Offset just past the record most recently returned.
+    position: u64,
+    /// Whether that record still has to be reported as processed.
+    pending: bool,
+    recovery_mode: RecoveryMode,
+}
+
+impl<R: Read + Seek, S: CheckpointStore> CheckpointedReader<R, S> {
+    /// Open `source` at the offset saved in `store`, or at its current
+    /// position if there is no checkpoint.
+    ///
+    /// Offsets are absolute positions in `source`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store cannot be read or the source cannot
+    /// be positioned.
+    pub fn new(mut source: R, store: S) -> Result<Self> {
+        let checkpointer = Checkpointer::new(store)?;
+        let position = if checkpointer.offset() > 0 {
+            source.seek(SeekFrom::Start(checkpointer.offset()))?
+        } else {
+            source.stream_position()?
+        };
+        Ok(CheckpointedReader {
+            source,
+            checkpointer,
+            position,
+            pending: false,
+            recovery_mode: RecoveryMode::Strict,
+        })
+    }
+
+    /// Save a checkpoint every `n` records (default 1000).
+    #[must_use]
+    pub fn every(mut self, n: u64) -> Self {
+        self.checkpointer = self.checkpointer.every(n);
+        self
+    }
+
+    /// Set the recovery mode used to parse records (default strict).
+    #[must_use]
+    pub fn with_recovery_mode(mut self, mode: RecoveryMode) -> Self {
+        self.recovery_mode = mode;
+        self
+    }
+
+    /// Read the next record, first counting the previous one as processed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error on I/O failure, on a malformed or truncated record,
+    /// or if a due checkpoint cannot be saved.
+    pub fn read_record(&mut self) -> Result<Option<Record>> {
+        self.mark_processed()?;
+        let Some(bytes) = read_raw_record(&mut self.source, self.position)? else {
+            return Ok(None);
+        };
+        let record_index =
+            usize::try_from(self.checkpointer.current().records).unwrap_or(usize::MAX);
+        self.position += bytes.len() as u64;
+        let record =
+            parse_record_from_bytes(bytes, self.recovery_mode, ValidationLevel::Structural)
+                .map_err(|e| e.with_record_index(Some(record_index)))?;
+        self.pending = record.is_some();
+        Ok(record)
+    }
+
+    /// Count the last record returned as processed and save a checkpoint
+    /// now. Call this when the job finishes or before a planned stop.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the checkpoint cannot be saved.
+    pub fn commit(&mut self) -> Result<()> {
+        self.mark_processed()?;
+        self.checkpointer.commit()
+    }
+
+    /// Progress so far.
+    #[must_use]
+    pub fn checkpoint(&self) -> Checkpoint {
+        self.checkpointer.current()
+    }
+
+    /// Give back the source and the checkpointer.
+    pub fn into_parts(self) -> (R, Checkpointer<S>) {
+        (self.source, self.checkpointer)
+    }
+
+    fn mark_processed(&mut self) -> Result<()> {
+        if std::mem::take(&mut self.pending) {
+            self.checkpointer.processed(self.position)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::leader::Leader;
+    use crate::writer::MarcWriter;
+    use std::io::Cursor;
+
+    /// Store that keeps the checkpoint in memory and counts saves.
+    #[derive(Debug, Default)]
+    struct Memory {
+        checkpoint: Option<Checkpoint>,
+        saves: usize,
+    }
+
+    impl CheckpointStore for &mut Memory {
+        fn load(&mut self) -> Result<Option<Checkpoint>> {
+            Ok(self.checkpoint)
+        }
+
+        fn save(&mut self, checkpoint: &Checkpoint) -> Result<()> {
+            self.checkpoint = Some(*checkpoint);
+            self.saves += 1;
+            Ok(())
+        }
+    }
+
+    fn data(n: usize) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let mut writer = MarcWriter::new(&mut bytes);
+        for i in 0..n {
+            let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+            record.add_control_field_str("001", &format!("rec{i}"));
+            writer.write_record(&record).unwrap();
+        }
+        drop(writer);
+        bytes
+    }
+
+    fn id(record: &Record) -> String {
+        record.get_control_field("001").unwrap().to_string()
+    }
+
+    #[test]
+    fn test_resume_after_crash() {
+        let bytes = data(10);
+        let mut store = Memory::default();
+
+        // First run "crashes" after processing four records.
+        let mut reader = CheckpointedReader::new(Cursor::new(&bytes), &mut store)
+            .unwrap()
+            .every(2);
+        for _ in 0..5 {
+            reader.read_record().unwrap().unwrap();
+        }
+        assert_eq!(reader.checkpoint().records, 4);
+        assert_eq!(store.saves, 2);
+        assert_eq!(store.checkpoint.unwrap().records, 4);
+
+        // Second run picks up at the fifth record.
+        let mut reader = CheckpointedReader::new(Cursor::new(&bytes), &mut store)
+            .unwrap()
+            .every(2);
+        let mut ids = Vec::new();
+        while let Some(record) = reader.read_record().unwrap() {
+            ids.push(id(&record));
+        }
+        reader.commit().unwrap();
+        assert_eq!(ids, ["rec4", "rec5", "rec6", "rec7", "rec8", "rec9"]);
+        let done = store.checkpoint.unwrap();
+        assert_eq!(done.records, 10);
+        assert_eq!(done.byte_offset, bytes.len() as u64);
+    }
+
+    #[test]
+    fn test_file_store_and_pipeline_resume() {
+        use crate::producer_consumer_pipeline::{PipelineConfig, ProducerConsumerPipeline};
+        use std::io::Write;
+
+        let bytes = data(20);
+        let mut input = tempfile::NamedTempFile::new().unwrap();
+        input.write_all(&bytes).unwrap();
+        let path = input.path().to_str().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let store = FileCheckpointStore::new(dir.path().join("job.ckpt"));
+        let config = PipelineConfig {
+            buffer_size: 200,
+            ..PipelineConfig::default()
+        };
+
+        let mut checkpointer = Checkpointer::new(store.clone()).unwrap().every(3);
+        let pipeline =
+            ProducerConsumerPipeline::from_file_at(path, checkpointer.offset(), &config).unwrap();
+        for _ in 0..7 {
+            pipeline.next().unwrap().unwrap();
+            checkpointer.processed(pipeline.position()).unwrap();
+        }
+        drop(pipeline);
+
+        let checkpointer = Checkpointer::new(store.clone()).unwrap();
+        assert_eq!(checkpointer.current().records, 6);
+        let pipeline =
+            ProducerConsumerPipeline::from_file_at(path, checkpointer.offset(), &config).unwrap();
+        let ids: Vec<String> = pipeline.into_iter().map(|r| id(&r.unwrap())).collect();
+        assert_eq!(ids.first().map(String::as_str), Some("rec6"));
+        assert_eq!(ids.len(), 14);
+
+        store.clear().unwrap();
+        assert_eq!(Checkpointer::new(store).unwrap().offset(), 0);
+        fs::write(dir.path().join("bad.ckpt"), "nonsense").unwrap();
+        assert!(Checkpointer::new(FileCheckpointStore::new(dir.path().join("bad.ckpt"))).is_err());
+    }
+}
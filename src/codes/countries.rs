@@ -0,0 +1,390 @@
+//! MARC Code List for Countries, as embedded lookup data for [`super::CodeList::Country`].
+//!
+//! Sorted by code; obsolete codes are kept so older records still resolve.
+
+use super::CodeEntry;
+
+pub(super) const COUNTRIES: &[CodeEntry] = &[
+    CodeEntry::current("aa", "Albania"),
+    CodeEntry::current("abc", "Alberta"),
+    CodeEntry::obsolete("ac", "Ashmore and Cartier Islands"),
+    CodeEntry::current("aca", "Australian Capital Territory"),
+    CodeEntry::current("ae", "Algeria"),
+    CodeEntry::current("af", "Afghanistan"),
+    CodeEntry::current("ag", "Argentina"),
+    CodeEntry::current("ai", "Armenia (Republic)"),
+    CodeEntry::obsolete("air", "Armenian S.S.R."),
+    CodeEntry::current("aj", "Azerbaijan"),
+    CodeEntry::obsolete("ajr", "Azerbaijan S.S.R."),
+    CodeEntry::current("aku", "Alaska"),
+    CodeEntry::current("alu", "Alabama"),
+    CodeEntry::current("am", "Anguilla"),
+    CodeEntry::current("an", "Andorra"),
+    CodeEntry::current("ao", "Angola"),
+    CodeEntry::current("aq", "Antigua and Barbuda"),
+    CodeEntry::current("aru", "Arkansas"),
+    CodeEntry::current("as", "American Samoa"),
+    CodeEntry::current("at", "Australia"),
+    CodeEntry::current("au", "Austria"),
+    CodeEntry::current("aw", "Aruba"),
+    CodeEntry::current("ay", "Antarctica"),
+    CodeEntry::current("azu", "Arizona"),
+    CodeEntry::current("ba", "Bahrain"),
+    CodeEntry::current("bb", "Barbados"),
+    CodeEntry::current("bcc", "British Columbia"),
+    CodeEntry::current("bd", "Burundi"),
+    CodeEntry::current("be", "Belgium"),
+    CodeEntry::current("bf", "Bahamas"),
+    CodeEntry::current("bg", "Bangladesh"),
+    CodeEntry::current("bh", "Belize"),
+    CodeEntry::current("bi", "British Indian Ocean Territory"),
+    CodeEntry::current("bl", "Brazil"),
+    CodeEntry::current("bm", "Bermuda Islands"),
+    CodeEntry::current("bn", "Bosnia and Herzegovina"),
+    CodeEntry::current("bo", "Bolivia"),
+    CodeEntry::current("bp", "Solomon Islands"),
+    CodeEntry::current("br", "Burma"),
+    CodeEntry::current("bs", "Botswana"),
+    CodeEntry::current("bt", "Bhutan"),
+    CodeEntry::current("bu", "Bulgaria"),
+    CodeEntry::current("bv", "Bouvet Island"),
+    CodeEntry::current("bw", "Belarus"),
+    CodeEntry::obsolete("bwr", "Byelorussian S.S.R."),
+    CodeEntry::current("bx", "Brunei"),
+    CodeEntry::current("ca", "Caribbean Netherlands"),
+    CodeEntry::current("cau", "California"),
+    CodeEntry::current("cb", "Cambodia"),
+    CodeEntry::current("cc", "China"),
+    CodeEntry::current("cd", "Chad"),
+    CodeEntry::current("ce", "Sri Lanka"),
+    CodeEntry::current("cf", "Congo (Brazzaville)"),
+    CodeEntry::current("cg", "Congo (Democratic Republic)"),
+    CodeEntry::current("ch", "China (Republic : 1949- )"),
+    CodeEntry::current("ci", "Croatia"),
+    CodeEntry::current("cj", "Cayman Islands"),
+    CodeEntry::current("ck", "Colombia"),
+    CodeEntry::current("cl", "Chile"),
+    CodeEntry::current("cm", "Cameroon"),
+    CodeEntry::obsolete("cn", "Canada"),
+    CodeEntry::current("co", "Curaçao"),
+    CodeEntry::current("cou", "Colorado"),
+    CodeEntry::obsolete("cp", "Canton and Enderbury Islands"),
+    CodeEntry::current("cq", "Comoros"),
+    CodeEntry::current("cr", "Costa Rica"),
+    CodeEntry::obsolete("cs", "Czechoslovakia"),
+    CodeEntry::current("ctu", "Connecticut"),
+    CodeEntry::current("cu", "Cuba"),
+    CodeEntry::current("cv", "Cabo Verde"),
+    CodeEntry::current("cw", "Cook Islands"),
+    CodeEntry::current("cx", "Central African Republic"),
+    CodeEntry::current("cy", "Cyprus"),
+    CodeEntry::obsolete("cz", "Canal Zone"),
+    CodeEntry::current("dcu", "District of Columbia"),
+    CodeEntry::current("deu", "Delaware"),
+    CodeEntry::current("dk", "Denmark"),
+    CodeEntry::current("dm", "Benin"),
+    CodeEntry::current("dq", "Dominica"),
+    CodeEntry::current("dr", "Dominican Republic"),
+    CodeEntry::current("ea", "Eritrea"),
+    CodeEntry::current("ec", "Ecuador"),
+    CodeEntry::current("eg", "Equatorial Guinea"),
+    CodeEntry::current("em", "Timor-Leste"),
+    CodeEntry::current("enk", "England"),
+    CodeEntry::current("er", "Estonia"),
+    CodeEntry::obsolete("err", "Estonia"),
+    CodeEntry::current("es", "El Salvador"),
+    CodeEntry::current("et", "Ethiopia"),
+    CodeEntry::current("fa", "Faroe Islands"),
+    CodeEntry::current("fg", "French Guiana"),
+    CodeEntry::current("fi", "Finland"),
+    CodeEntry::current("fj", "Fiji"),
+    CodeEntry::current("fk", "Falkland Islands"),
+    CodeEntry::current("flu", "Florida"),
+    CodeEntry::current("fm", "Micronesia (Federated States)"),
+    CodeEntry::current("fp", "French Polynesia"),
+    CodeEntry::current("fr", "France"),
+    CodeEntry::current("fs", "Terres australes et antarctiques françaises"),
+    CodeEntry::current("ft", "Djibouti"),
+    CodeEntry::current("gau", "Georgia"),
+    CodeEntry::current("gb", "Kiribati"),
+    CodeEntry::current("gd", "Grenada"),
+    CodeEntry::obsolete("ge", "Germany (East)"),
+    CodeEntry::current("gg", "Guernsey"),
+    CodeEntry::current("gh", "Ghana"),
+    CodeEntry::current("gi", "Gibraltar"),
+    CodeEntry::current("gl", "Greenland"),
+    CodeEntry::current("gm", "Gambia"),
+    CodeEntry::obsolete("gn", "Gilbert and Ellice Islands"),
+    CodeEntry::current("go", "Gabon"),
+    CodeEntry::current("gp", "Guadeloupe"),
+    CodeEntry::current("gr", "Greece"),
+    CodeEntry::current("gs", "Georgia (Republic)"),
+    CodeEntry::obsolete("gsr", "Georgian S.S.R."),
+    CodeEntry::current("gt", "Guatemala"),
+    CodeEntry::current("gu", "Guam"),
+    CodeEntry::current("gv", "Guinea"),
+    CodeEntry::current("gw", "Germany"),
+    CodeEntry::current("gy", "Guyana"),
+    CodeEntry::current("gz", "Gaza Strip"),
+    CodeEntry::current("hiu", "Hawaii"),
+    CodeEntry::obsolete("hk", "Hong Kong"),
+    CodeEntry::current("hm", "Heard and McDonald Islands"),
+    CodeEntry::current("ho", "Honduras"),
+    CodeEntry::current("ht", "Haiti"),
+    CodeEntry::current("hu", "Hungary"),
+    CodeEntry::current("iau", "Iowa"),
+    CodeEntry::current("ic", "Iceland"),
+    CodeEntry::current("idu", "Idaho"),
+    CodeEntry::current("ie", "Ireland"),
+    CodeEntry::current("ii", "India"),
+    CodeEntry::current("ilu", "Illinois"),
+    CodeEntry::current("im", "Isle of Man"),
+    CodeEntry::current("inu", "Indiana"),
+    CodeEntry::current("io", "Indonesia"),
+    CodeEntry::current("iq", "Iraq"),
+    CodeEntry::current("ir", "Iran"),
+    CodeEntry::current("is", "Israel"),
+    CodeEntry::current("it", "Italy"),
+    CodeEntry::obsolete("iu", "Israel-Syria Demilitarized Zones"),
+    CodeEntry::current("iv", "Côte d'Ivoire"),
+    CodeEntry::obsolete("iw", "Israel-Jordan Demilitarized Zones"),
+    CodeEntry::current("iy", "Iraq-Saudi Arabia Neutral Zone"),
+    CodeEntry::current("ja", "Japan"),
+    CodeEntry::current("je", "Jersey"),
+    CodeEntry::current("ji", "Johnston Atoll"),
+    CodeEntry::current("jm", "Jamaica"),
+    CodeEntry::obsolete("jn", "Jan Mayen"),
+    CodeEntry::current("jo", "Jordan"),
+    CodeEntry::current("ke", "Kenya"),
+    CodeEntry::current("kg", "Kyrgyzstan"),
+    CodeEntry::obsolete("kgr", "Kirghiz S.S.R."),
+    CodeEntry::current("kn", "Korea (North)"),
+    CodeEntry::current("ko", "Korea (South)"),
+    CodeEntry::current("ksu", "Kansas"),
+    CodeEntry::current("ku", "Kuwait"),
+    CodeEntry::current("kv", "Kosovo"),
+    CodeEntry::current("kyu", "Kentucky"),
+    CodeEntry::current("kz", "Kazakhstan"),
+    CodeEntry::obsolete("kzr", "Kazakh S.S.R."),
+    CodeEntry::current("lau", "Louisiana"),
+    CodeEntry::current("lb", "Liberia"),
+    CodeEntry::current("le", "Lebanon"),
+    CodeEntry::current("lh", "Liechtenstein"),
+    CodeEntry::current("li", "Lithuania"),
+    CodeEntry::obsolete("lir", "Lithuania"),
+    CodeEntry::obsolete("ln", "Central and Southern Line Islands"),
+    CodeEntry::current("lo", "Lesotho"),
+    CodeEntry::current("ls", "Laos"),
+    CodeEntry::current("lu", "Luxembourg"),
+    CodeEntry::current("lv", "Latvia"),
+    CodeEntry::obsolete("lvr", "Latvia"),
+    CodeEntry::current("ly", "Libya"),
+    CodeEntry::current("mau", "Massachusetts"),
+    CodeEntry::current("mbc", "Manitoba"),
+    CodeEntry::current("mc", "Monaco"),
+    CodeEntry::current("mdu", "Maryland"),
+    CodeEntry::current("meu", "Maine"),
+    CodeEntry::current("mf", "Mauritius"),
+    CodeEntry::current("mg", "Madagascar"),
+    CodeEntry::obsolete("mh", "Macao"),
+    CodeEntry::current("miu", "Michigan"),
+    CodeEntry::current("mj", "Montserrat"),
+    CodeEntry::current("mk", "Oman"),
+    CodeEntry::current("ml", "Mali"),
+    CodeEntry::current("mm", "Malta"),
+    CodeEntry::current("mnu", "Minnesota"),
+    CodeEntry::current("mo", "Montenegro"),
+    CodeEntry::current("mou", "Missouri"),
+    CodeEntry::current("mp", "Mongolia"),
+    CodeEntry::current("mq", "Martinique"),
+    CodeEntry::current("mr", "Morocco"),
+    CodeEntry::current("msu", "Mississippi"),
+    CodeEntry::current("mtu", "Montana"),
+    CodeEntry::current("mu", "Mauritania"),
+    CodeEntry::current("mv", "Moldova"),
+    CodeEntry::obsolete("mvr", "Moldavian S.S.R."),
+    CodeEntry::current("mw", "Malawi"),
+    CodeEntry::current("mx", "Mexico"),
+    CodeEntry::current("my", "Malaysia"),
+    CodeEntry::current("mz", "Mozambique"),
+    CodeEntry::obsolete("na", "Netherlands Antilles"),
+    CodeEntry::current("nbu", "Nebraska"),
+    CodeEntry::current("ncu", "North Carolina"),
+    CodeEntry::current("ndu", "North Dakota"),
+    CodeEntry::current("ne", "Netherlands"),
+    CodeEntry::current("nfc", "Newfoundland and Labrador"),
+    CodeEntry::current("ng", "Niger"),
+    CodeEntry::current("nhu", "New Hampshire"),
+    CodeEntry::current("nik", "Northern Ireland"),
+    CodeEntry::current("nju", "New Jersey"),
+    CodeEntry::current("nkc", "New Brunswick"),
+    CodeEntry::current("nl", "New Caledonia"),
+    CodeEntry::obsolete("nm", "Northern Mariana Islands"),
+    CodeEntry::current("nmu", "New Mexico"),
+    CodeEntry::current("nn", "Vanuatu"),
+    CodeEntry::current("no", "Norway"),
+    CodeEntry::current("np", "Nepal"),
+    CodeEntry::current("nq", "Nicaragua"),
+    CodeEntry::current("nr", "Nigeria"),
+    CodeEntry::current("nsc", "Nova Scotia"),
+    CodeEntry::current("ntc", "Northwest Territories"),
+    CodeEntry::current("nu", "Nauru"),
+    CodeEntry::current("nuc", "Nunavut"),
+    CodeEntry::current("nvu", "Nevada"),
+    CodeEntry::current("nw", "Northern Mariana Islands"),
+    CodeEntry::current("nx", "Norfolk Island"),
+    CodeEntry::current("nyu", "New York (State)"),
+    CodeEntry::current("nz", "New Zealand"),
+    CodeEntry::current("ohu", "Ohio"),
+    CodeEntry::current("oku", "Oklahoma"),
+    CodeEntry::current("onc", "Ontario"),
+    CodeEntry::current("oru", "Oregon"),
+    CodeEntry::current("ot", "Mayotte"),
+    CodeEntry::current("pau", "Pennsylvania"),
+    CodeEntry::current("pc", "Pitcairn Island"),
+    CodeEntry::current("pe", "Peru"),
+    CodeEntry::current("pf", "Paracel Islands"),
+    CodeEntry::current("pg", "Guinea-Bissau"),
+    CodeEntry::current("ph", "Philippines"),
+    CodeEntry::current("pic", "Prince Edward Island"),
+    CodeEntry::current("pk", "Pakistan"),
+    CodeEntry::current("pl", "Poland"),
+    CodeEntry::current("pn", "Panama"),
+    CodeEntry::current("po", "Portugal"),
+    CodeEntry::current("pp", "Papua New Guinea"),
+    CodeEntry::current("pr", "Puerto Rico"),
+    CodeEntry::obsolete("pt", "Portuguese Timor"),
+    CodeEntry::current("pw", "Palau"),
+    CodeEntry::current("py", "Paraguay"),
+    CodeEntry::current("qa", "Qatar"),
+    CodeEntry::current("qea", "Queensland"),
+    CodeEntry::current("quc", "Québec (Province)"),
+    CodeEntry::current("rb", "Serbia"),
+    CodeEntry::current("re", "Réunion"),
+    CodeEntry::current("rh", "Zimbabwe"),
+    CodeEntry::current("riu", "Rhode Island"),
+    CodeEntry::current("rm", "Romania"),
+    CodeEntry::current("ru", "Russia (Federation)"),
+    CodeEntry::obsolete("rur", "Russian S.F.S.R."),
+    CodeEntry::current("rw", "Rwanda"),
+    CodeEntry::obsolete("ry", "Ryukyu Islands, Southern"),
+    CodeEntry::current("sa", "South Africa"),
+    CodeEntry::obsolete("sb", "Svalbard"),
+    CodeEntry::current("sc", "Saint-Barthélemy"),
+    CodeEntry::current("scu", "South Carolina"),
+    CodeEntry::current("sd", "South Sudan"),
+    CodeEntry::current("sdu", "South Dakota"),
+    CodeEntry::current("se", "Seychelles"),
+    CodeEntry::current("sf", "Sao Tome and Principe"),
+    CodeEntry::current("sg", "Senegal"),
+    CodeEntry::current("sh", "Spanish North Africa"),
+    CodeEntry::current("si", "Singapore"),
+    CodeEntry::current("sj", "Sudan"),
+    CodeEntry::obsolete("sk", "Sikkim"),
+    CodeEntry::current("sl", "Sierra Leone"),
+    CodeEntry::current("sm", "San Marino"),
+    CodeEntry::current("sn", "Sint Maarten"),
+    CodeEntry::current("snc", "Saskatchewan"),
+    CodeEntry::current("so", "Somalia"),
+    CodeEntry::current("sp", "Spain"),
+    CodeEntry::current("sq", "Eswatini"),
+    CodeEntry::current("sr", "Surinam"),
+    CodeEntry::current("ss", "Western Sahara"),
+    CodeEntry::current("st", "Saint-Martin"),
+    CodeEntry::current("stk", "Scotland"),
+    CodeEntry::current("su", "Saudi Arabia"),
+    CodeEntry::obsolete("sv", "Swan Islands"),
+    CodeEntry::current("sw", "Sweden"),
+    CodeEntry::current("sx", "Namibia"),
+    CodeEntry::current("sy", "Syria"),
+    CodeEntry::current("sz", "Switzerland"),
+    CodeEntry::current("ta", "Tajikistan"),
+    CodeEntry::obsolete("tar", "Tajik S.S.R."),
+    CodeEntry::current("tc", "Turks and Caicos Islands"),
+    CodeEntry::current("tg", "Togo"),
+    CodeEntry::current("th", "Thailand"),
+    CodeEntry::current("ti", "Tunisia"),
+    CodeEntry::current("tk", "Turkmenistan"),
+    CodeEntry::obsolete("tkr", "Turkmen S.S.R."),
+    CodeEntry::current("tl", "Tokelau"),
+    CodeEntry::current("tma", "Tasmania"),
+    CodeEntry::current("tnu", "Tennessee"),
+    CodeEntry::current("to", "Tonga"),
+    CodeEntry::current("tr", "Trinidad and Tobago"),
+    CodeEntry::current("ts", "United Arab Emirates"),
+    CodeEntry::obsolete("tt", "Trust Territory of the Pacific Islands"),
+    CodeEntry::current("tu", "Turkey"),
+    CodeEntry::current("tv", "Tuvalu"),
+    CodeEntry::current("txu", "Texas"),
+    CodeEntry::current("tz", "Tanzania"),
+    CodeEntry::current("ua", "Egypt"),
+    CodeEntry::current("uc", "United States Misc. Caribbean Islands"),
+    CodeEntry::current("ug", "Uganda"),
+    CodeEntry::obsolete("ui", "United Kingdom Misc. Islands"),
+    CodeEntry::obsolete("uik", "United Kingdom Misc. Islands"),
+    CodeEntry::obsolete("uk", "United Kingdom"),
+    CodeEntry::current("un", "Ukraine"),
+    CodeEntry::obsolete("unr", "Ukraine"),
+    CodeEntry::current("up", "United States Misc. Pacific Islands"),
+    CodeEntry::obsolete("ur", "Soviet Union"),
+    CodeEntry::obsolete("us", "United States"),
+    CodeEntry::current("utu", "Utah"),
+    CodeEntry::current("uv", "Burkina Faso"),
+    CodeEntry::current("uy", "Uruguay"),
+    CodeEntry::current("uz", "Uzbekistan"),
+    CodeEntry::obsolete("uzr", "Uzbek S.S.R."),
+    CodeEntry::current("vau", "Virginia"),
+    CodeEntry::current("vb", "British Virgin Islands"),
+    CodeEntry::current("vc", "Vatican City"),
+    CodeEntry::current("ve", "Venezuela"),
+    CodeEntry::current("vi", "Virgin Islands of the United States"),
+    CodeEntry::current("vm", "Vietnam"),
+    CodeEntry::obsolete("vn", "Vietnam, North"),
+    CodeEntry::current("vp", "Various places"),
+    CodeEntry::current("vra", "Victoria"),
+    CodeEntry::obsolete("vs", "Vietnam, South"),
+    CodeEntry::current("vtu", "Vermont"),
+    CodeEntry::current("wau", "Washington (State)"),
+    CodeEntry::obsolete("wb", "West Berlin"),
+    CodeEntry::current("wea", "Western Australia"),
+    CodeEntry::current("wf", "Wallis and Futuna"),
+    CodeEntry::current("wiu", "Wisconsin"),
+    CodeEntry::current("wj", "West Bank of the Jordan River"),
+    CodeEntry::current("wk", "Wake Island"),
+    CodeEntry::current("wlk", "Wales"),
+    CodeEntry::current("ws", "Samoa"),
+    CodeEntry::current("wvu", "West Virginia"),
+    CodeEntry::current("wyu", "Wyoming"),
+    CodeEntry::current("xa", "Christmas Island (Indian Ocean)"),
+    CodeEntry::current("xb", "Cocos (Keeling) Islands"),
+    CodeEntry::current("xc", "Maldives"),
+    CodeEntry::current("xd", "Saint Kitts-Nevis"),
+    CodeEntry::current("xe", "Marshall Islands"),
+    CodeEntry::current("xf", "Midway Islands"),
+    CodeEntry::current("xga", "Coral Sea Islands Territory"),
+    CodeEntry::current("xh", "Niue"),
+    CodeEntry::obsolete("xi", "Saint Kitts-Nevis-Anguilla"),
+    CodeEntry::current("xj", "Saint Helena"),
+    CodeEntry::current("xk", "Saint Lucia"),
+    CodeEntry::current("xl", "Saint Pierre and Miquelon"),
+    CodeEntry::current("xm", "Saint Vincent and the Grenadines"),
+    CodeEntry::current("xn", "North Macedonia"),
+    CodeEntry::current("xna", "New South Wales"),
+    CodeEntry::current("xo", "Slovakia"),
+    CodeEntry::current("xoa", "Northern Territory"),
+    CodeEntry::current("xp", "Spratly Island"),
+    CodeEntry::current("xr", "Czech Republic"),
+    CodeEntry::current("xra", "South Australia"),
+    CodeEntry::current("xs", "South Georgia and the South Sandwich Islands"),
+    CodeEntry::current("xv", "Slovenia"),
+    CodeEntry::current("xx", "No place, unknown, or undetermined"),
+    CodeEntry::current("xxc", "Canada"),
+    CodeEntry::current("xxk", "United Kingdom"),
+    CodeEntry::obsolete("xxr", "Soviet Union"),
+    CodeEntry::current("xxu", "United States"),
+    CodeEntry::current("ye", "Yemen"),
+    CodeEntry::current("ykc", "Yukon Territory"),
+    CodeEntry::obsolete("ys", "Yemen (People's Democratic Republic)"),
+    CodeEntry::obsolete("yu", "Serbia and Montenegro"),
+    CodeEntry::current("za", "Zambia"),
+];
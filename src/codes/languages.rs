@@ -0,0 +1,530 @@
+//! MARC Code List for Languages, as embedded lookup data for [`super::CodeList::Language`].
+//!
+//! Sorted by code; obsolete codes are kept so older records still resolve.
+
+use super::CodeEntry;
+
+pub(super) const LANGUAGES: &[CodeEntry] = &[
+    CodeEntry::current("aar", "Afar"),
+    CodeEntry::current("abk", "Abkhaz"),
+    CodeEntry::current("ace", "Achinese"),
+    CodeEntry::current("ach", "Acoli"),
+    CodeEntry::current("ada", "Adangme"),
+    CodeEntry::current("ady", "Adygei"),
+    CodeEntry::current("afa", "Afroasiatic (Other)"),
+    CodeEntry::current("afh", "Afrihili (Artificial language)"),
+    CodeEntry::current("afr", "Afrikaans"),
+    CodeEntry::current("ain", "Ainu"),
+    CodeEntry::obsolete("ajm", "Aljamía"),
+    CodeEntry::current("aka", "Akan"),
+    CodeEntry::current("akk", "Akkadian"),
+    CodeEntry::current("alb", "Albanian"),
+    CodeEntry::current("ale", "Aleut"),
+    CodeEntry::current("alg", "Algonquian (Other)"),
+    CodeEntry::current("alt", "Altai"),
+    CodeEntry::current("amh", "Amharic"),
+    CodeEntry::current("ang", "English, Old (ca. 450-1100)"),
+    CodeEntry::current("anp", "Angika"),
+    CodeEntry::current("apa", "Apache languages"),
+    CodeEntry::current("ara", "Arabic"),
+    CodeEntry::current("arc", "Aramaic"),
+    CodeEntry::current("arg", "Aragonese"),
+    CodeEntry::current("arm", "Armenian"),
+    CodeEntry::current("arn", "Mapuche"),
+    CodeEntry::current("arp", "Arapaho"),
+    CodeEntry::current("art", "Artificial (Other)"),
+    CodeEntry::current("arw", "Arawak"),
+    CodeEntry::current("asm", "Assamese"),
+    CodeEntry::current("ast", "Bable"),
+    CodeEntry::current("ath", "Athapascan (Other)"),
+    CodeEntry::current("aus", "Australian languages"),
+    CodeEntry::current("ava", "Avaric"),
+    CodeEntry::current("ave", "Avestan"),
+    CodeEntry::current("awa", "Awadhi"),
+    CodeEntry::current("aym", "Aymara"),
+    CodeEntry::current("aze", "Azerbaijani"),
+    CodeEntry::current("bad", "Banda languages"),
+    CodeEntry::current("bai", "Bamileke languages"),
+    CodeEntry::current("bak", "Bashkir"),
+    CodeEntry::current("bal", "Baluchi"),
+    CodeEntry::current("bam", "Bambara"),
+    CodeEntry::current("ban", "Balinese"),
+    CodeEntry::current("baq", "Basque"),
+    CodeEntry::current("bas", "Basa"),
+    CodeEntry::current("bat", "Baltic (Other)"),
+    CodeEntry::current("bej", "Beja"),
+    CodeEntry::current("bel", "Belarusian"),
+    CodeEntry::current("bem", "Bemba"),
+    CodeEntry::current("ben", "Bengali"),
+    CodeEntry::current("ber", "Berber (Other)"),
+    CodeEntry::current("bho", "Bhojpuri"),
+    CodeEntry::current("bih", "Bihari (Other)"),
+    CodeEntry::current("bik", "Bikol"),
+    CodeEntry::current("bin", "Edo"),
+    CodeEntry::current("bis", "Bislama"),
+    CodeEntry::current("bla", "Siksika"),
+    CodeEntry::current("bnt", "Bantu (Other)"),
+    CodeEntry::current("bos", "Bosnian"),
+    CodeEntry::current("bra", "Braj"),
+    CodeEntry::current("bre", "Breton"),
+    CodeEntry::current("btk", "Batak"),
+    CodeEntry::current("bua", "Buriat"),
+    CodeEntry::current("bug", "Bugis"),
+    CodeEntry::current("bul", "Bulgarian"),
+    CodeEntry::current("bur", "Burmese"),
+    CodeEntry::current("byn", "Bilin"),
+    CodeEntry::current("cad", "Caddo"),
+    CodeEntry::current("cai", "Central American Indian (Other)"),
+    CodeEntry::obsolete("cam", "Khmer"),
+    CodeEntry::current("car", "Carib"),
+    CodeEntry::current("cat", "Catalan"),
+    CodeEntry::current("cau", "Caucasian (Other)"),
+    CodeEntry::current("ceb", "Cebuano"),
+    CodeEntry::current("cel", "Celtic (Other)"),
+    CodeEntry::current("cha", "Chamorro"),
+    CodeEntry::current("chb", "Chibcha"),
+    CodeEntry::current("che", "Chechen"),
+    CodeEntry::current("chg", "Chagatai"),
+    CodeEntry::current("chi", "Chinese"),
+    CodeEntry::current("chk", "Chuukese"),
+    CodeEntry::current("chm", "Mari"),
+    CodeEntry::current("chn", "Chinook jargon"),
+    CodeEntry::current("cho", "Choctaw"),
+    CodeEntry::current("chp", "Chipewyan"),
+    CodeEntry::current("chr", "Cherokee"),
+    CodeEntry::current("chu", "Church Slavic"),
+    CodeEntry::current("chv", "Chuvash"),
+    CodeEntry::current("chy", "Cheyenne"),
+    CodeEntry::current("cmc", "Chamic languages"),
+    CodeEntry::current("cnr", "Montenegrin"),
+    CodeEntry::current("cop", "Coptic"),
+    CodeEntry::current("cor", "Cornish"),
+    CodeEntry::current("cos", "Corsican"),
+    CodeEntry::current("cpe", "Creoles and Pidgins, English-based (Other)"),
+    CodeEntry::current("cpf", "Creoles and Pidgins, French-based (Other)"),
+    CodeEntry::current("cpp", "Creoles and Pidgins, Portuguese-based (Other)"),
+    CodeEntry::current("cre", "Cree"),
+    CodeEntry::current("crh", "Crimean Tatar"),
+    CodeEntry::current("crp", "Creoles and Pidgins (Other)"),
+    CodeEntry::current("csb", "Kashubian"),
+    CodeEntry::current("cus", "Cushitic (Other)"),
+    CodeEntry::current("cze", "Czech"),
+    CodeEntry::current("dak", "Dakota"),
+    CodeEntry::current("dan", "Danish"),
+    CodeEntry::current("dar", "Dargwa"),
+    CodeEntry::current("day", "Dayak"),
+    CodeEntry::current("del", "Delaware"),
+    CodeEntry::current("den", "Slavey"),
+    CodeEntry::current("dgr", "Dogrib"),
+    CodeEntry::current("din", "Dinka"),
+    CodeEntry::current("div", "Divehi"),
+    CodeEntry::current("doi", "Dogri"),
+    CodeEntry::current("dra", "Dravidian (Other)"),
+    CodeEntry::current("dsb", "Lower Sorbian"),
+    CodeEntry::current("dua", "Duala"),
+    CodeEntry::current("dum", "Dutch, Middle (ca. 1050-1350)"),
+    CodeEntry::current("dut", "Dutch"),
+    CodeEntry::current("dyu", "Dyula"),
+    CodeEntry::current("dzo", "Dzongkha"),
+    CodeEntry::current("efi", "Efik"),
+    CodeEntry::current("egy", "Egyptian"),
+    CodeEntry::current("eka", "Ekajuk"),
+    CodeEntry::current("elx", "Elamite"),
+    CodeEntry::current("eng", "English"),
+    CodeEntry::current("enm", "English, Middle (1100-1500)"),
+    CodeEntry::current("epo", "Esperanto"),
+    CodeEntry::obsolete("esk", "Eskimo languages"),
+    CodeEntry::obsolete("esp", "Esperanto"),
+    CodeEntry::current("est", "Estonian"),
+    CodeEntry::obsolete("eth", "Ethiopic"),
+    CodeEntry::current("ewe", "Ewe"),
+    CodeEntry::current("ewo", "Ewondo"),
+    CodeEntry::current("fan", "Fang"),
+    CodeEntry::current("fao", "Faroese"),
+    CodeEntry::obsolete("far", "Faroese"),
+    CodeEntry::current("fat", "Fanti"),
+    CodeEntry::current("fij", "Fijian"),
+    CodeEntry::current("fil", "Filipino"),
+    CodeEntry::current("fin", "Finnish"),
+    CodeEntry::current("fiu", "Finno-Ugrian (Other)"),
+    CodeEntry::current("fon", "Fon"),
+    CodeEntry::current("fre", "French"),
+    CodeEntry::obsolete("fri", "Frisian"),
+    CodeEntry::current("frm", "French, Middle (ca. 1300-1600)"),
+    CodeEntry::current("fro", "French, Old (ca. 842-1300)"),
+    CodeEntry::current("frr", "North Frisian"),
+    CodeEntry::current("frs", "East Frisian"),
+    CodeEntry::current("fry", "Frisian"),
+    CodeEntry::current("ful", "Fula"),
+    CodeEntry::current("fur", "Friulian"),
+    CodeEntry::current("gaa", "Gã"),
+    CodeEntry::obsolete("gae", "Scottish Gaelic"),
+    CodeEntry::obsolete("gag", "Galician"),
+    CodeEntry::obsolete("gal", "Oromo"),
+    CodeEntry::current("gay", "Gayo"),
+    CodeEntry::current("gba", "Gbaya"),
+    CodeEntry::current("gem", "Germanic (Other)"),
+    CodeEntry::current("geo", "Georgian"),
+    CodeEntry::current("ger", "German"),
+    CodeEntry::current("gez", "Ethiopic"),
+    CodeEntry::current("gil", "Gilbertese"),
+    CodeEntry::current("gla", "Scottish Gaelic"),
+    CodeEntry::current("gle", "Irish"),
+    CodeEntry::current("glg", "Galician"),
+    CodeEntry::current("glv", "Manx"),
+    CodeEntry::current("gmh", "German, Middle High (ca. 1050-1500)"),
+    CodeEntry::current("goh", "German, Old High (ca. 750-1050)"),
+    CodeEntry::current("gon", "Gondi"),
+    CodeEntry::current("gor", "Gorontalo"),
+    CodeEntry::current("got", "Gothic"),
+    CodeEntry::current("grb", "Grebo"),
+    CodeEntry::current("grc", "Greek, Ancient (to 1453)"),
+    CodeEntry::current("gre", "Greek, Modern (1453-)"),
+    CodeEntry::current("grn", "Guarani"),
+    CodeEntry::current("gsw", "Swiss German"),
+    CodeEntry::obsolete("gua", "Guarani"),
+    CodeEntry::current("guj", "Gujarati"),
+    CodeEntry::current("gwi", "Gwich'in"),
+    CodeEntry::current("hai", "Haida"),
+    CodeEntry::current("hat", "Haitian French Creole"),
+    CodeEntry::current("hau", "Hausa"),
+    CodeEntry::current("haw", "Hawaiian"),
+    CodeEntry::current("heb", "Hebrew"),
+    CodeEntry::current("her", "Herero"),
+    CodeEntry::current("hil", "Hiligaynon"),
+    CodeEntry::current("him", "Western Pahari languages"),
+    CodeEntry::current("hin", "Hindi"),
+    CodeEntry::current("hit", "Hittite"),
+    CodeEntry::current("hmn", "Hmong"),
+    CodeEntry::current("hmo", "Hiri Motu"),
+    CodeEntry::current("hrv", "Croatian"),
+    CodeEntry::current("hsb", "Upper Sorbian"),
+    CodeEntry::current("hun", "Hungarian"),
+    CodeEntry::current("hup", "Hupa"),
+    CodeEntry::current("iba", "Iban"),
+    CodeEntry::current("ibo", "Igbo"),
+    CodeEntry::current("ice", "Icelandic"),
+    CodeEntry::current("ido", "Ido"),
+    CodeEntry::current("iii", "Sichuan Yi"),
+    CodeEntry::current("ijo", "Ijo"),
+    CodeEntry::current("iku", "Inuktitut"),
+    CodeEntry::current("ile", "Interlingue"),
+    CodeEntry::current("ilo", "Iloko"),
+    CodeEntry::current(
+        "ina",
+        "Interlingua (International Auxiliary Language Association)",
+    ),
+    CodeEntry::current("inc", "Indic (Other)"),
+    CodeEntry::current("ind", "Indonesian"),
+    CodeEntry::current("ine", "Indo-European (Other)"),
+    CodeEntry::current("inh", "Ingush"),
+    CodeEntry::obsolete(
+        "int",
+        "Interlingua (International Auxiliary Language Association)",
+    ),
+    CodeEntry::current("ipk", "Inupiaq"),
+    CodeEntry::current("ira", "Iranian (Other)"),
+    CodeEntry::obsolete("iri", "Irish"),
+    CodeEntry::current("iro", "Iroquoian (Other)"),
+    CodeEntry::current("ita", "Italian"),
+    CodeEntry::current("jav", "Javanese"),
+    CodeEntry::current("jbo", "Lojban (Artificial language)"),
+    CodeEntry::current("jpn", "Japanese"),
+    CodeEntry::current("jpr", "Judeo-Persian"),
+    CodeEntry::current("jrb", "Judeo-Arabic"),
+    CodeEntry::current("kaa", "Kara-Kalpak"),
+    CodeEntry::current("kab", "Kabyle"),
+    CodeEntry::current("kac", "Kachin"),
+    CodeEntry::current("kal", "Kalâtdlisut"),
+    CodeEntry::current("kam", "Kamba"),
+    CodeEntry::current("kan", "Kannada"),
+    CodeEntry::current("kar", "Karen languages"),
+    CodeEntry::current("kas", "Kashmiri"),
+    CodeEntry::current("kau", "Kanuri"),
+    CodeEntry::current("kaw", "Kawi"),
+    CodeEntry::current("kaz", "Kazakh"),
+    CodeEntry::current("kbd", "Kabardian"),
+    CodeEntry::current("kha", "Khasi"),
+    CodeEntry::current("khi", "Khoisan (Other)"),
+    CodeEntry::current("khm", "Khmer"),
+    CodeEntry::current("kho", "Khotanese"),
+    CodeEntry::current("kik", "Kikuyu"),
+    CodeEntry::current("kin", "Kinyarwanda"),
+    CodeEntry::current("kir", "Kyrgyz"),
+    CodeEntry::current("kmb", "Kimbundu"),
+    CodeEntry::current("kok", "Konkani"),
+    CodeEntry::current("kom", "Komi"),
+    CodeEntry::current("kon", "Kongo"),
+    CodeEntry::current("kor", "Korean"),
+    CodeEntry::current("kos", "Kosraean"),
+    CodeEntry::current("kpe", "Kpelle"),
+    CodeEntry::current("krc", "Karachay-Balkar"),
+    CodeEntry::current("krl", "Karelian"),
+    CodeEntry::current("kro", "Kru (Other)"),
+    CodeEntry::current("kru", "Kurukh"),
+    CodeEntry::current("kua", "Kuanyama"),
+    CodeEntry::current("kum", "Kumyk"),
+    CodeEntry::current("kur", "Kurdish"),
+    CodeEntry::obsolete("kus", "Kusaie"),
+    CodeEntry::current("kut", "Kootenai"),
+    CodeEntry::current("lad", "Ladino"),
+    CodeEntry::current("lah", "Lahndā"),
+    CodeEntry::current("lam", "Lamba (Zambia and Congo)"),
+    CodeEntry::obsolete("lan", "Occitan (post 1500)"),
+    CodeEntry::current("lao", "Lao"),
+    CodeEntry::obsolete("lap", "Sami"),
+    CodeEntry::current("lat", "Latin"),
+    CodeEntry::current("lav", "Latvian"),
+    CodeEntry::current("lez", "Lezgian"),
+    CodeEntry::current("lim", "Limburgish"),
+    CodeEntry::current("lin", "Lingala"),
+    CodeEntry::current("lit", "Lithuanian"),
+    CodeEntry::current("lol", "Mongo-Nkundu"),
+    CodeEntry::current("loz", "Lozi"),
+    CodeEntry::current("ltz", "Luxembourgish"),
+    CodeEntry::current("lua", "Luba-Lulua"),
+    CodeEntry::current("lub", "Luba-Katanga"),
+    CodeEntry::current("lug", "Ganda"),
+    CodeEntry::current("lui", "Luiseño"),
+    CodeEntry::current("lun", "Lunda"),
+    CodeEntry::current("luo", "Luo (Kenya and Tanzania)"),
+    CodeEntry::current("lus", "Lushai"),
+    CodeEntry::current("mac", "Macedonian"),
+    CodeEntry::current("mad", "Madurese"),
+    CodeEntry::current("mag", "Magahi"),
+    CodeEntry::current("mah", "Marshallese"),
+    CodeEntry::current("mai", "Maithili"),
+    CodeEntry::current("mak", "Makasar"),
+    CodeEntry::current("mal", "Malayalam"),
+    CodeEntry::current("man", "Mandingo"),
+    CodeEntry::current("mao", "Maori"),
+    CodeEntry::current("map", "Austronesian (Other)"),
+    CodeEntry::current("mar", "Marathi"),
+    CodeEntry::current("mas", "Maasai"),
+    CodeEntry::obsolete("max", "Manx"),
+    CodeEntry::current("may", "Malay"),
+    CodeEntry::current("mdf", "Moksha"),
+    CodeEntry::current("mdr", "Mandar"),
+    CodeEntry::current("men", "Mende"),
+    CodeEntry::current("mga", "Irish, Middle (ca. 1100-1550)"),
+    CodeEntry::current("mic", "Micmac"),
+    CodeEntry::current("min", "Minangkabau"),
+    CodeEntry::current("mis", "Miscellaneous languages"),
+    CodeEntry::current("mkh", "Mon-Khmer (Other)"),
+    CodeEntry::obsolete("mla", "Malagasy"),
+    CodeEntry::current("mlg", "Malagasy"),
+    CodeEntry::current("mlt", "Maltese"),
+    CodeEntry::current("mnc", "Manchu"),
+    CodeEntry::current("mni", "Manipuri"),
+    CodeEntry::current("mno", "Manobo languages"),
+    CodeEntry::current("moh", "Mohawk"),
+    CodeEntry::obsolete("mol", "Moldavian"),
+    CodeEntry::current("mon", "Mongolian"),
+    CodeEntry::current("mos", "Mooré"),
+    CodeEntry::current("mul", "Multiple languages"),
+    CodeEntry::current("mun", "Munda (Other)"),
+    CodeEntry::current("mus", "Creek"),
+    CodeEntry::current("mwl", "Mirandese"),
+    CodeEntry::current("mwr", "Marwari"),
+    CodeEntry::current("myn", "Mayan languages"),
+    CodeEntry::current("myv", "Erzya"),
+    CodeEntry::current("nah", "Nahuatl"),
+    CodeEntry::current("nai", "North American Indian (Other)"),
+    CodeEntry::current("nap", "Neapolitan Italian"),
+    CodeEntry::current("nau", "Nauru"),
+    CodeEntry::current("nav", "Navajo"),
+    CodeEntry::current("nbl", "Ndebele (South Africa)"),
+    CodeEntry::current("nde", "Ndebele (Zimbabwe)"),
+    CodeEntry::current("ndo", "Ndonga"),
+    CodeEntry::current("nds", "Low German"),
+    CodeEntry::current("nep", "Nepali"),
+    CodeEntry::current("new", "Newari"),
+    CodeEntry::current("nia", "Nias"),
+    CodeEntry::current("nic", "Niger-Kordofanian (Other)"),
+    CodeEntry::current("niu", "Niuean"),
+    CodeEntry::current("nno", "Norwegian (Nynorsk)"),
+    CodeEntry::current("nob", "Norwegian (Bokmål)"),
+    CodeEntry::current("nog", "Nogai"),
+    CodeEntry::current("non", "Old Norse"),
+    CodeEntry::current("nor", "Norwegian"),
+    CodeEntry::current("nqo", "N'Ko"),
+    CodeEntry::current("nso", "Northern Sotho"),
+    CodeEntry::current("nub", "Nubian languages"),
+    CodeEntry::current("nwc", "Newari, Old"),
+    CodeEntry::current("nya", "Nyanja"),
+    CodeEntry::current("nym", "Nyamwezi"),
+    CodeEntry::current("nyn", "Nyankole"),
+    CodeEntry::current("nyo", "Nyoro"),
+    CodeEntry::current("nzi", "Nzima"),
+    CodeEntry::current("oci", "Occitan (post-1500)"),
+    CodeEntry::current("oji", "Ojibwa"),
+    CodeEntry::current("ori", "Oriya"),
+    CodeEntry::current("orm", "Oromo"),
+    CodeEntry::current("osa", "Osage"),
+    CodeEntry::current("oss", "Ossetic"),
+    CodeEntry::current("ota", "Turkish, Ottoman"),
+    CodeEntry::current("oto", "Otomian languages"),
+    CodeEntry::current("paa", "Papuan (Other)"),
+    CodeEntry::current("pag", "Pangasinan"),
+    CodeEntry::current("pal", "Pahlavi"),
+    CodeEntry::current("pam", "Pampanga"),
+    CodeEntry::current("pan", "Panjabi"),
+    CodeEntry::current("pap", "Papiamento"),
+    CodeEntry::current("pau", "Palauan"),
+    CodeEntry::current("peo", "Old Persian (ca. 600-400 B.C.)"),
+    CodeEntry::current("per", "Persian"),
+    CodeEntry::current("phi", "Philippine (Other)"),
+    CodeEntry::current("phn", "Phoenician"),
+    CodeEntry::current("pli", "Pali"),
+    CodeEntry::current("pol", "Polish"),
+    CodeEntry::current("pon", "Pohnpeian"),
+    CodeEntry::current("por", "Portuguese"),
+    CodeEntry::current("pra", "Prakrit languages"),
+    CodeEntry::current("pro", "Provençal (to 1500)"),
+    CodeEntry::current("pus", "Pushto"),
+    CodeEntry::current("que", "Quechua"),
+    CodeEntry::current("raj", "Rajasthani"),
+    CodeEntry::current("rap", "Rapanui"),
+    CodeEntry::current("rar", "Rarotongan"),
+    CodeEntry::current("roa", "Romance (Other)"),
+    CodeEntry::current("roh", "Raeto-Romance"),
+    CodeEntry::current("rom", "Romani"),
+    CodeEntry::current("rum", "Romanian"),
+    CodeEntry::current("run", "Rundi"),
+    CodeEntry::current("rup", "Aromanian"),
+    CodeEntry::current("rus", "Russian"),
+    CodeEntry::current("sad", "Sandawe"),
+    CodeEntry::current("sag", "Sango (Ubangi Creole)"),
+    CodeEntry::current("sah", "Yakut"),
+    CodeEntry::current("sai", "South American Indian (Other)"),
+    CodeEntry::current("sal", "Salishan languages"),
+    CodeEntry::current("sam", "Samaritan Aramaic"),
+    CodeEntry::current("san", "Sanskrit"),
+    CodeEntry::obsolete("sao", "Samoan"),
+    CodeEntry::current("sas", "Sasak"),
+    CodeEntry::current("sat", "Santali"),
+    CodeEntry::obsolete("scc", "Serbian"),
+    CodeEntry::current("scn", "Sicilian Italian"),
+    CodeEntry::current("sco", "Scots"),
+    CodeEntry::obsolete("scr", "Croatian"),
+    CodeEntry::current("sel", "Selkup"),
+    CodeEntry::current("sem", "Semitic (Other)"),
+    CodeEntry::current("sga", "Irish, Old (to 1100)"),
+    CodeEntry::current("sgn", "Sign languages"),
+    CodeEntry::current("shn", "Shan"),
+    CodeEntry::obsolete("sho", "Shona"),
+    CodeEntry::current("sid", "Sidamo"),
+    CodeEntry::current("sin", "Sinhalese"),
+    CodeEntry::current("sio", "Siouan (Other)"),
+    CodeEntry::current("sit", "Sino-Tibetan (Other)"),
+    CodeEntry::current("sla", "Slavic (Other)"),
+    CodeEntry::current("slo", "Slovak"),
+    CodeEntry::current("slv", "Slovenian"),
+    CodeEntry::current("sma", "Southern Sami"),
+    CodeEntry::current("sme", "Northern Sami"),
+    CodeEntry::current("smi", "Sami"),
+    CodeEntry::current("smj", "Lule Sami"),
+    CodeEntry::current("smn", "Inari Sami"),
+    CodeEntry::current("smo", "Samoan"),
+    CodeEntry::current("sms", "Skolt Sami"),
+    CodeEntry::current("sna", "Shona"),
+    CodeEntry::current("snd", "Sindhi"),
+    CodeEntry::obsolete("snh", "Sinhalese"),
+    CodeEntry::current("snk", "Soninke"),
+    CodeEntry::current("sog", "Sogdian"),
+    CodeEntry::current("som", "Somali"),
+    CodeEntry::current("son", "Songhai"),
+    CodeEntry::current("sot", "Sotho"),
+    CodeEntry::current("spa", "Spanish"),
+    CodeEntry::current("srd", "Sardinian"),
+    CodeEntry::current("srn", "Sranan"),
+    CodeEntry::current("srp", "Serbian"),
+    CodeEntry::current("srr", "Serer"),
+    CodeEntry::current("ssa", "Nilo-Saharan (Other)"),
+    CodeEntry::obsolete("sso", "Sotho"),
+    CodeEntry::current("ssw", "Swazi"),
+    CodeEntry::current("suk", "Sukuma"),
+    CodeEntry::current("sun", "Sundanese"),
+    CodeEntry::current("sus", "Susu"),
+    CodeEntry::current("sux", "Sumerian"),
+    CodeEntry::current("swa", "Swahili"),
+    CodeEntry::current("swe", "Swedish"),
+    CodeEntry::obsolete("swz", "Swazi"),
+    CodeEntry::current("syc", "Syriac"),
+    CodeEntry::current("syr", "Syriac, Modern"),
+    CodeEntry::obsolete("tag", "Tagalog"),
+    CodeEntry::current("tah", "Tahitian"),
+    CodeEntry::current("tai", "Tai (Other)"),
+    CodeEntry::obsolete("taj", "Tajik"),
+    CodeEntry::current("tam", "Tamil"),
+    CodeEntry::obsolete("tar", "Tatar"),
+    CodeEntry::current("tat", "Tatar"),
+    CodeEntry::current("tel", "Telugu"),
+    CodeEntry::current("tem", "Temne"),
+    CodeEntry::current("ter", "Terena"),
+    CodeEntry::current("tet", "Tetum"),
+    CodeEntry::current("tgk", "Tajik"),
+    CodeEntry::current("tgl", "Tagalog"),
+    CodeEntry::current("tha", "Thai"),
+    CodeEntry::current("tib", "Tibetan"),
+    CodeEntry::current("tig", "Tigré"),
+    CodeEntry::current("tir", "Tigrinya"),
+    CodeEntry::current("tiv", "Tiv"),
+    CodeEntry::current("tkl", "Tokelauan"),
+    CodeEntry::current("tlh", "Klingon (Artificial language)"),
+    CodeEntry::current("tli", "Tlingit"),
+    CodeEntry::current("tmh", "Tamashek"),
+    CodeEntry::current("tog", "Tonga (Lake Nyasa)"),
+    CodeEntry::current("ton", "Tongan"),
+    CodeEntry::current("tpi", "Tok Pisin"),
+    CodeEntry::obsolete("tru", "Truk"),
+    CodeEntry::current("tsi", "Tsimshian"),
+    CodeEntry::current("tsn", "Tswana"),
+    CodeEntry::current("tso", "Tsonga"),
+    CodeEntry::obsolete("tsw", "Tswana"),
+    CodeEntry::current("tuk", "Turkmen"),
+    CodeEntry::current("tum", "Tumbuka"),
+    CodeEntry::current("tup", "Tupi languages"),
+    CodeEntry::current("tur", "Turkish"),
+    CodeEntry::current("tut", "Altaic (Other)"),
+    CodeEntry::current("tvl", "Tuvaluan"),
+    CodeEntry::current("twi", "Twi"),
+    CodeEntry::current("tyv", "Tuvinian"),
+    CodeEntry::current("udm", "Udmurt"),
+    CodeEntry::current("uga", "Ugaritic"),
+    CodeEntry::current("uig", "Uighur"),
+    CodeEntry::current("ukr", "Ukrainian"),
+    CodeEntry::current("umb", "Umbundu"),
+    CodeEntry::current("und", "Undetermined"),
+    CodeEntry::current("urd", "Urdu"),
+    CodeEntry::current("uzb", "Uzbek"),
+    CodeEntry::current("vai", "Vai"),
+    CodeEntry::current("ven", "Venda"),
+    CodeEntry::current("vie", "Vietnamese"),
+    CodeEntry::current("vol", "Volapük"),
+    CodeEntry::current("vot", "Votic"),
+    CodeEntry::current("wak", "Wakashan languages"),
+    CodeEntry::current("wal", "Wolayta"),
+    CodeEntry::current("war", "Waray"),
+    CodeEntry::current("was", "Washoe"),
+    CodeEntry::current("wel", "Welsh"),
+    CodeEntry::current("wen", "Sorbian (Other)"),
+    CodeEntry::current("wln", "Walloon"),
+    CodeEntry::current("wol", "Wolof"),
+    CodeEntry::current("xal", "Oirat"),
+    CodeEntry::current("xho", "Xhosa"),
+    CodeEntry::current("yao", "Yao (Africa)"),
+    CodeEntry::current("yap", "Yapese"),
+    CodeEntry::current("yid", "Yiddish"),
+    CodeEntry::current("yor", "Yoruba"),
+    CodeEntry::current("ypk", "Yupik languages"),
+    CodeEntry::current("zap", "Zapotec"),
+    CodeEntry::current("zbl", "Blissymbolics"),
+    CodeEntry::current("zen", "Zenaga"),
+    CodeEntry::current("zha", "Zhuang"),
+    CodeEntry::current("znd", "Zande languages"),
+    CodeEntry::current("zul", "Zulu"),
+    CodeEntry::current("zun", "Zuni"),
+    CodeEntry::current("zxx", "No linguistic content"),
+    CodeEntry::current("zza", "Zaza"),
+];
@@ -0,0 +1,436 @@
+//! MARC language, country, relator and RDA type code lists.
+//!
+//! The Library of Congress maintains the code lists that MARC records use
+//! in fixed fields and coded subfields: languages (008/35-37, 041),
+//! countries of publication (008/15-17, 044), relator codes (`$4`) and
+//! the RDA content, media and carrier types (336/337/338).
+//! This module embeds those lists so codes can be turned into display
+//! labels or facet values, and so records carrying obsolete codes can be
+//! flagged.
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::codes::{self, CodeList, CodeStatus};
+//!
+//! assert_eq!(codes::language_name("eng"), Some("English"));
+//! assert_eq!(codes::country_name("nyu"), Some("New York (State)"));
+//! assert_eq!(codes::relator_name("ill"), Some("Illustrator"));
+//! assert_eq!(codes::relator_code("Illustrator."), Some("ill"));
+//! assert_eq!(codes::normalize_relator("ill."), Some("ill"));
+//!
+//! assert_eq!(CodeList::Language.status("fre"), CodeStatus::Current);
+//! assert_eq!(CodeList::Language.status("esk"), CodeStatus::Obsolete);
+//! assert_eq!(CodeList::Language.status("xyz"), CodeStatus::Unknown);
+//! ```
+
+mod countries;
+mod languages;
+mod rda;
+mod relators;
+
+use serde::{Deserialize, Serialize};
+
+use crate::record::Record;
+
+/// One entry in a code list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CodeEntry {
+    /// The code (`"eng"`, `"nyu"`, `"aut"`)
+    pub code: &'static str,
+    /// The label from the code list
+    pub name: &'static str,
+    /// Whether the code has been discontinued
+    pub obsolete: bool,
+}
+
+impl CodeEntry {
+    const fn current(code: &'static str, name: &'static str) -> Self {
+        CodeEntry {
+            code,
+            name,
+            obsolete: false,
+        }
+    }
+
+    const fn obsolete(code: &'static str, name: &'static str) -> Self {
+        CodeEntry {
+            code,
+            name,
+            obsolete: true,
+        }
+    }
+}
+
+/// The embedded code lists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CodeList {
+    /// MARC Code List for Languages
+    Language,
+    /// MARC Code List for Countries
+    Country,
+    /// MARC Code List for Relators
+    Relator,
+    /// RDA content types (336)
+    ContentType,
+    /// RDA media types (337)
+    MediaType,
+    /// RDA carrier types (338)
+    CarrierType,
+}
+
+/// Whether a code is in a list, and if so whether it is still valid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CodeStatus {
+    /// The code is in the list and current
+    Current,
+    /// The code is in the list but has been discontinued
+    Obsolete,
+    /// The code is not in the list
+    Unknown,
+}
+
+impl CodeList {
+    /// All entries in the list, sorted by code.
+    #[must_use]
+    pub fn entries(self) -> &'static [CodeEntry] {
+        match self {
+            CodeList::Language => languages::LANGUAGES,
+            CodeList::Country => countries::COUNTRIES,
+            CodeList::Relator => relators::RELATORS,
+            CodeList::ContentType => rda::CONTENT_TYPES,
+            CodeList::MediaType => rda::MEDIA_TYPES,
+            CodeList::CarrierType => rda::CARRIER_TYPES,
+        }
+    }
+
+    /// Look up a code. Surrounding blanks (as in the two-character
+    /// country codes of 008/15-17) and case are ignored.
+    #[must_use]
+    pub fn get(self, code: &str) -> Option<&'static CodeEntry> {
+        let code = code.trim().to_ascii_lowercase();
+        let entries = self.entries();
+        entries
+            .binary_search_by(|entry| entry.code.cmp(code.as_str()))
+            .ok()
+            .map(|i| &entries[i])
+    }
+
+    /// The label for a code.
+    #[must_use]
+    pub fn name(self, code: &str) -> Option<&'static str> {
+        self.get(code).map(|entry| entry.name)
+    }
+
+    /// Whether a code is current, obsolete or unknown.
+    #[must_use]
+    pub fn status(self, code: &str) -> CodeStatus {
+        match self.get(code) {
+            Some(entry) if entry.obsolete => CodeStatus::Obsolete,
+            Some(_) => CodeStatus::Current,
+            None => CodeStatus::Unknown,
+        }
+    }
+
+    /// Find the current code whose label matches `name`, ignoring case and
+    /// trailing punctuation (so relator terms like `"editor."` match).
+    #[must_use]
+    pub fn find_by_name(self, name: &str) -> Option<&'static CodeEntry> {
+        let name = name.trim().trim_end_matches(['.', ',', ';', ':']).trim();
+        self.entries()
+            .iter()
+            .filter(|entry| !entry.obsolete)
+            .find(|entry| entry.name.eq_ignore_ascii_case(name))
+    }
+}
+
+/// Label for a MARC language code (`"eng"` → `"English"`).
+#[must_use]
+pub fn language_name(code: &str) -> Option<&'static str> {
+    CodeList::Language.name(code)
+}
+
+/// Label for a MARC country code (`"nyu"` → `"New York (State)"`).
+#[must_use]
+pub fn country_name(code: &str) -> Option<&'static str> {
+    CodeList::Country.name(code)
+}
+
+/// Label for a MARC relator code (`"aut"` → `"Author"`).
+#[must_use]
+pub fn relator_name(code: &str) -> Option<&'static str> {
+    CodeList::Relator.name(code)
+}
+
+/// Relator code for a relator term (`"author."` → `"aut"`).
+#[must_use]
+pub fn relator_code(term: &str) -> Option<&'static str> {
+    CodeList::Relator.find_by_name(term).map(|entry| entry.code)
+}
+
+/// Relator code for a `$4` code, a relator vocabulary URI, or a `$e`/`$j`
+/// term as catalogers actually write them.
+///
+/// Accepts codes in any case (`"AUT"`), `id.loc.gov` relator URIs, the
+/// terms of the code list with any case and punctuation (`"author."`,
+/// `"Author of introduction, etc."`) and common abbreviations (`"ed."`,
+/// `"tr."`, `"ill."`). Obsolete codes are returned as they are; it is
+/// [`check_record`]'s job to flag them. Returns `None` for anything else.
+///
+/// ```
+/// use mrrc::codes::normalize_relator;
+///
+/// assert_eq!(normalize_relator("author."), Some("aut"));
+/// assert_eq!(normalize_relator("ED."), Some("edt"));
+/// assert_eq!(normalize_relator("http://id.loc.gov/vocabulary/relators/ill"), Some("ill"));
+/// assert_eq!(normalize_relator("Trl"), Some("trl"));
+/// assert_eq!(normalize_relator("cataloger's friend"), None);
+/// ```
+#[must_use]
+pub fn normalize_relator(value: &str) -> Option<&'static str> {
+    let value = value.trim();
+    let code = value
+        .strip_prefix("http://id.loc.gov/vocabulary/relators/")
+        .or_else(|| value.strip_prefix("https://id.loc.gov/vocabulary/relators/"))
+        .unwrap_or(value);
+    if let Some(entry) = CodeList::Relator.get(code) {
+        return Some(entry.code);
+    }
+    let term = relator_term_key(value);
+    CodeList::Relator
+        .entries()
+        .iter()
+        .filter(|entry| !entry.obsolete)
+        .find(|entry| relator_term_key(entry.name) == term)
+        .map(|entry| entry.code)
+        .or_else(|| {
+            relators::RELATOR_ALIASES
+                .iter()
+                .find(|(alias, _)| *alias == term)
+                .map(|(_, code)| *code)
+        })
+}
+
+/// Relator codes for a `$e`/`$j` value, which may list several terms
+/// (`"editor, translator."`, `"author and illustrator"`).
+///
+/// The whole value is tried first, so terms containing commas still
+/// match. Returns codes in order without duplicates; empty if any part is
+/// not recognized.
+#[must_use]
+pub fn normalize_relator_terms(value: &str) -> Vec<&'static str> {
+    if let Some(code) = normalize_relator(value) {
+        return vec![code];
+    }
+    let mut codes = Vec::new();
+    for part in value
+        .split([',', ';', '&'])
+        .flat_map(|part| part.split(" and "))
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+    {
+        match normalize_relator(part) {
+            Some(code) if !codes.contains(&code) => codes.push(code),
+            Some(_) => {},
+            None => return Vec::new(),
+        }
+    }
+    codes
+}
+
+/// A relator term lowercased, with periods dropped, whitespace collapsed
+/// and trailing punctuation and ", etc" removed, for comparison.
+fn relator_term_key(term: &str) -> String {
+    let lowered = term.to_lowercase().replace('.', " ");
+    let key = lowered.split_whitespace().collect::<Vec<_>>().join(" ");
+    let key = key.trim_end_matches([',', ';', ':', ' ']);
+    key.strip_suffix(", etc")
+        .or_else(|| key.strip_suffix(" etc"))
+        .unwrap_or(key)
+        .trim_end_matches([',', ' '])
+        .to_string()
+}
+
+/// A coded value in a record that is obsolete or not in its code list.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CodeIssue {
+    /// Tag the code was found in (`"008"`, `"041"`, ...)
+    pub tag: String,
+    /// The list the code should come from
+    pub list: CodeList,
+    /// The code as it appears in the record
+    pub code: String,
+    /// [`CodeStatus::Obsolete`] or [`CodeStatus::Unknown`]
+    pub status: CodeStatus,
+}
+
+/// Check the language, country and relator codes in a record.
+///
+/// Examines 008/15-17 and 008/35-37, the language subfields of 041 (unless
+/// the second indicator says another code source is used), 044 `$a`, and
+/// `$4` in 1XX/6XX/7XX/8XX fields. Blank and fill-character values, and
+/// `$4` values that are URIs, are skipped.
+#[must_use]
+pub fn check_record(record: &Record) -> Vec<CodeIssue> {
+    let mut issues = Vec::new();
+    let mut check = |tag: &str, list: CodeList, code: &str| {
+        let trimmed = code.trim();
+        if trimmed.is_empty() || trimmed.chars().all(|c| c == '|') {
+            return;
+        }
+        let status = list.status(trimmed);
+        if status != CodeStatus::Current {
+            issues.push(CodeIssue {
+                tag: tag.to_string(),
+                list,
+                code: code.to_string(),
+                status,
+            });
+        }
+    };
+
+    if let Some(fixed) = record.get_control_field("008") {
+        if let Some(country) = fixed.get(15..18) {
+            check("008", CodeList::Country, country);
+        }
+        if let Some(language) = fixed.get(35..38) {
+            check("008", CodeList::Language, language);
+        }
+    }
+    for field in record.fields_by_tag("041") {
+        if field.indicator2 == '7' {
+            continue;
+        }
+        for subfield in field
+            .subfields
+            .iter()
+            .filter(|sf| sf.code.is_ascii_lowercase())
+        {
+            check("041", CodeList::Language, &subfield.value);
+        }
+    }
+    for field in record.fields_by_tag("044") {
+        for code in field.subfields_by_code('a') {
+            check("044", CodeList::Country, code);
+        }
+    }
+    for field in record.fields() {
+        if !matches!(
+            field.tag.as_bytes().first(),
+            Some(b'1' | b'6' | b'7' | b'8')
+        ) {
+            continue;
+        }
+        for code in field.subfields_by_code('4') {
+            if !code.contains([':', '/']) {
+                check(&field.tag, CodeList::Relator, code);
+            }
+        }
+    }
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::leader::Leader;
+    use crate::record::Field;
+
+    #[test]
+    fn test_lists_are_sorted_and_unique() {
+        for list in [
+            CodeList::Language,
+            CodeList::Country,
+            CodeList::Relator,
+            CodeList::ContentType,
+            CodeList::MediaType,
+            CodeList::CarrierType,
+        ] {
+            let entries = list.entries();
+            assert!(
+                entries.windows(2).all(|w| w[0].code < w[1].code),
+                "{list:?} is not sorted"
+            );
+        }
+    }
+
+    #[test]
+    fn test_lookups_ignore_case_and_padding() {
+        assert_eq!(country_name("at "), Some("Australia"));
+        assert_eq!(language_name("FRE"), Some("French"));
+        assert_eq!(CodeList::Country.status("us"), CodeStatus::Obsolete);
+        assert_eq!(relator_code("Editor of compilation"), Some("edc"));
+        assert_eq!(relator_code("Vocalist"), None);
+        assert_eq!(CodeList::ContentType.name("TXT"), Some("text"));
+        assert_eq!(
+            CodeList::CarrierType
+                .find_by_name("Online resource.")
+                .map(|e| e.code),
+            Some("cr")
+        );
+    }
+
+    #[test]
+    fn test_normalize_relator_codes_uris_and_terms() {
+        assert_eq!(normalize_relator(" AUT "), Some("aut"));
+        assert_eq!(normalize_relator("voc"), Some("voc"));
+        assert_eq!(
+            normalize_relator("https://id.loc.gov/vocabulary/relators/edt"),
+            Some("edt")
+        );
+        assert_eq!(normalize_relator("Author,"), Some("aut"));
+        assert_eq!(
+            normalize_relator("author of introduction, etc."),
+            Some("aui")
+        );
+        assert_eq!(normalize_relator("joint author."), Some("aut"));
+        assert_eq!(normalize_relator("Vocalist"), None);
+        assert_eq!(normalize_relator(""), None);
+
+        assert_eq!(
+            normalize_relator_terms("editor, translator."),
+            vec!["edt", "trl"]
+        );
+        assert_eq!(
+            normalize_relator_terms("author and illustrator."),
+            vec!["aut", "ill"]
+        );
+        assert_eq!(normalize_relator_terms("ed. ; ed."), vec!["edt"]);
+        assert!(normalize_relator_terms("editor, mystery role").is_empty());
+    }
+
+    #[test]
+    fn test_check_record_flags_obsolete_and_unknown_codes() {
+        let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+        record.add_control_field_str("008", "240101s2024    us            000 0 esk d");
+        record.add_field(
+            Field::builder("041".to_string(), '0', ' ')
+                .subfield_str('a', "eng")
+                .subfield_str('h', "qqq")
+                .build(),
+        );
+        record.add_field(
+            Field::builder("700".to_string(), '1', ' ')
+                .subfield_str('a', "Smith, Jane")
+                .subfield_str('4', "trl")
+                .subfield_str('4', "http://id.loc.gov/vocabulary/relators/trl")
+                .subfield_str('4', "voc")
+                .build(),
+        );
+
+        let issues = check_record(&record);
+        let found: Vec<(&str, &str, CodeStatus)> = issues
+            .iter()
+            .map(|i| (i.tag.as_str(), i.code.as_str(), i.status))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("008", "us ", CodeStatus::Obsolete),
+                ("008", "esk", CodeStatus::Obsolete),
+                ("041", "qqq", CodeStatus::Unknown),
+                ("700", "voc", CodeStatus::Obsolete),
+            ]
+        );
+    }
+}
@@ -0,0 +1,103 @@
+//! RDA content, media and carrier type terms and their MARC source codes
+//! (336/337/338 `$b`), as embedded lookup data for
+//! [`super::CodeList::ContentType`], [`super::CodeList::MediaType`] and
+//! [`super::CodeList::CarrierType`].
+//!
+//! Sorted by code. Labels are the RDA terms as recorded in `$a`.
+
+use super::CodeEntry;
+
+/// Term and Code List for RDA Content Types (`rdacontent`).
+pub(super) const CONTENT_TYPES: &[CodeEntry] = &[
+    CodeEntry::current("cod", "computer dataset"),
+    CodeEntry::current("cop", "computer program"),
+    CodeEntry::current("crd", "cartographic dataset"),
+    CodeEntry::current("crf", "cartographic three-dimensional form"),
+    CodeEntry::current("cri", "cartographic image"),
+    CodeEntry::current("crm", "cartographic moving image"),
+    CodeEntry::current("crn", "cartographic tactile three-dimensional form"),
+    CodeEntry::current("crt", "cartographic tactile image"),
+    CodeEntry::current("ntm", "notated music"),
+    CodeEntry::current("ntv", "notated movement"),
+    CodeEntry::current("prm", "performed music"),
+    CodeEntry::current("snd", "sounds"),
+    CodeEntry::current("spw", "spoken word"),
+    CodeEntry::current("sti", "still image"),
+    CodeEntry::current("tcf", "tactile three-dimensional form"),
+    CodeEntry::current("tci", "tactile image"),
+    CodeEntry::current("tcm", "tactile notated music"),
+    CodeEntry::current("tcn", "tactile notated movement"),
+    CodeEntry::current("tct", "tactile text"),
+    CodeEntry::current("tdf", "three-dimensional form"),
+    CodeEntry::current("tdi", "two-dimensional moving image"),
+    CodeEntry::current("tdm", "three-dimensional moving image"),
+    CodeEntry::current("txt", "text"),
+    CodeEntry::current("xxx", "other"),
+    CodeEntry::current("zzz", "unspecified"),
+];
+
+/// Term and Code List for RDA Media Types (`rdamedia`).
+pub(super) const MEDIA_TYPES: &[CodeEntry] = &[
+    CodeEntry::current("c", "computer"),
+    CodeEntry::current("e", "stereographic"),
+    CodeEntry::current("g", "projected"),
+    CodeEntry::current("h", "microform"),
+    CodeEntry::current("n", "unmediated"),
+    CodeEntry::current("p", "microscopic"),
+    CodeEntry::current("s", "audio"),
+    CodeEntry::current("v", "video"),
+    CodeEntry::current("x", "other"),
+    CodeEntry::current("z", "unspecified"),
+];
+
+/// Term and Code List for RDA Carrier Types (`rdacarrier`).
+pub(super) const CARRIER_TYPES: &[CodeEntry] = &[
+    CodeEntry::current("ca", "computer tape cartridge"),
+    CodeEntry::current("cb", "computer chip cartridge"),
+    CodeEntry::current("cd", "computer disc"),
+    CodeEntry::current("ce", "computer disc cartridge"),
+    CodeEntry::current("cf", "computer tape cassette"),
+    CodeEntry::current("ch", "computer tape reel"),
+    CodeEntry::current("ck", "computer card"),
+    CodeEntry::current("cr", "online resource"),
+    CodeEntry::current("eh", "stereograph card"),
+    CodeEntry::current("es", "stereograph disc"),
+    CodeEntry::current("gc", "filmstrip cartridge"),
+    CodeEntry::current("gd", "filmslip"),
+    CodeEntry::current("gf", "filmstrip"),
+    CodeEntry::current("gs", "slide"),
+    CodeEntry::current("gt", "overhead transparency"),
+    CodeEntry::current("ha", "aperture card"),
+    CodeEntry::current("hb", "microfilm cartridge"),
+    CodeEntry::current("hc", "microfilm cassette"),
+    CodeEntry::current("hd", "microfilm reel"),
+    CodeEntry::current("he", "microfiche"),
+    CodeEntry::current("hf", "microfiche cassette"),
+    CodeEntry::current("hg", "microopaque"),
+    CodeEntry::current("hh", "microfilm slip"),
+    CodeEntry::current("hj", "microfilm roll"),
+    CodeEntry::current("mc", "film cartridge"),
+    CodeEntry::current("mf", "film cassette"),
+    CodeEntry::current("mo", "film roll"),
+    CodeEntry::current("mr", "film reel"),
+    CodeEntry::current("na", "roll"),
+    CodeEntry::current("nb", "sheet"),
+    CodeEntry::current("nc", "volume"),
+    CodeEntry::current("nn", "flipchart"),
+    CodeEntry::current("no", "card"),
+    CodeEntry::current("nr", "object"),
+    CodeEntry::current("pp", "microscope slide"),
+    CodeEntry::current("sd", "audio disc"),
+    CodeEntry::current("se", "audio cylinder"),
+    CodeEntry::current("sg", "audio cartridge"),
+    CodeEntry::current("si", "sound-track reel"),
+    CodeEntry::current("sq", "audio roll"),
+    CodeEntry::current("ss", "audiocassette"),
+    CodeEntry::current("st", "audiotape reel"),
+    CodeEntry::current("sw", "audio wire reel"),
+    CodeEntry::current("vc", "video cartridge"),
+    CodeEntry::current("vd", "videodisc"),
+    CodeEntry::current("vf", "videocassette"),
+    CodeEntry::current("vr", "videotape reel"),
+    CodeEntry::current("zu", "unspecified"),
+];
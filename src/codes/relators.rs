@@ -0,0 +1,332 @@
+//! MARC Code List for Relators, as embedded lookup data for [`super::CodeList::Relator`].
+//!
+//! Sorted by code; obsolete codes are kept so older records still resolve.
+
+use super::CodeEntry;
+
+pub(super) const RELATORS: &[CodeEntry] = &[
+    CodeEntry::current("abr", "Abridger"),
+    CodeEntry::current("acp", "Art copyist"),
+    CodeEntry::current("act", "Actor"),
+    CodeEntry::current("adi", "Art director"),
+    CodeEntry::current("adp", "Adapter"),
+    CodeEntry::current("aft", "Author of afterword, colophon, etc."),
+    CodeEntry::current("anc", "Announcer"),
+    CodeEntry::current("anl", "Analyst"),
+    CodeEntry::current("anm", "Animator"),
+    CodeEntry::current("ann", "Annotator"),
+    CodeEntry::current("ant", "Bibliographic antecedent"),
+    CodeEntry::current("ape", "Appellee"),
+    CodeEntry::current("apl", "Appellant"),
+    CodeEntry::current("app", "Applicant"),
+    CodeEntry::current("aqt", "Author in quotations or text abstracts"),
+    CodeEntry::current("arc", "Architect"),
+    CodeEntry::current("ard", "Artistic director"),
+    CodeEntry::current("arr", "Arranger"),
+    CodeEntry::current("art", "Artist"),
+    CodeEntry::current("asg", "Assignee"),
+    CodeEntry::current("asn", "Associated name"),
+    CodeEntry::current("ato", "Autographer"),
+    CodeEntry::current("att", "Attributed name"),
+    CodeEntry::current("auc", "Auctioneer"),
+    CodeEntry::current("aud", "Author of dialog"),
+    CodeEntry::current("aue", "Audio engineer"),
+    CodeEntry::current("aui", "Author of introduction, etc."),
+    CodeEntry::current("aup", "Audio producer"),
+    CodeEntry::current("aus", "Screenwriter"),
+    CodeEntry::current("aut", "Author"),
+    CodeEntry::current("bdd", "Binding designer"),
+    CodeEntry::current("bjd", "Bookjacket designer"),
+    CodeEntry::current("bka", "Book artist"),
+    CodeEntry::current("bkd", "Book designer"),
+    CodeEntry::current("bkp", "Book producer"),
+    CodeEntry::current("blw", "Blurb writer"),
+    CodeEntry::current("bnd", "Binder"),
+    CodeEntry::current("bpd", "Bookplate designer"),
+    CodeEntry::current("brd", "Broadcaster"),
+    CodeEntry::current("brl", "Braille embosser"),
+    CodeEntry::current("bsl", "Bookseller"),
+    CodeEntry::current("cad", "Casting director"),
+    CodeEntry::current("cas", "Caster"),
+    CodeEntry::current("ccp", "Conceptor"),
+    CodeEntry::current("chr", "Choreographer"),
+    CodeEntry::obsolete("clb", "Collaborator"),
+    CodeEntry::current("cli", "Client"),
+    CodeEntry::current("cll", "Calligrapher"),
+    CodeEntry::current("clr", "Colorist"),
+    CodeEntry::current("clt", "Collotyper"),
+    CodeEntry::current("cmm", "Commentator"),
+    CodeEntry::current("cmp", "Composer"),
+    CodeEntry::current("cmt", "Compositor"),
+    CodeEntry::current("cnd", "Conductor"),
+    CodeEntry::current("cng", "Cinematographer"),
+    CodeEntry::current("cns", "Censor"),
+    CodeEntry::current("coe", "Contestant-appellee"),
+    CodeEntry::current("col", "Collector"),
+    CodeEntry::current("com", "Compiler"),
+    CodeEntry::current("con", "Conservator"),
+    CodeEntry::current("cop", "Camera operator"),
+    CodeEntry::current("cor", "Collection registrar"),
+    CodeEntry::current("cos", "Contestant"),
+    CodeEntry::current("cot", "Contestant-appellant"),
+    CodeEntry::current("cou", "Court governed"),
+    CodeEntry::current("cov", "Cover designer"),
+    CodeEntry::current("cpc", "Copyright claimant"),
+    CodeEntry::current("cpe", "Complainant-appellee"),
+    CodeEntry::current("cph", "Copyright holder"),
+    CodeEntry::current("cpl", "Complainant"),
+    CodeEntry::current("cpt", "Complainant-appellant"),
+    CodeEntry::current("cre", "Creator"),
+    CodeEntry::current("crp", "Correspondent"),
+    CodeEntry::current("crr", "Corrector"),
+    CodeEntry::current("crt", "Court reporter"),
+    CodeEntry::current("csl", "Consultant"),
+    CodeEntry::current("csp", "Consultant to a project"),
+    CodeEntry::current("cst", "Costume designer"),
+    CodeEntry::current("ctb", "Contributor"),
+    CodeEntry::current("cte", "Contestee-appellee"),
+    CodeEntry::current("ctg", "Cartographer"),
+    CodeEntry::current("ctr", "Contractor"),
+    CodeEntry::current("cts", "Contestee"),
+    CodeEntry::current("ctt", "Contestee-appellant"),
+    CodeEntry::current("cur", "Curator"),
+    CodeEntry::current("cwt", "Commentator for written text"),
+    CodeEntry::current("dbd", "Dubbing director"),
+    CodeEntry::current("dbp", "Distribution place"),
+    CodeEntry::current("dfd", "Defendant"),
+    CodeEntry::current("dfe", "Defendant-appellee"),
+    CodeEntry::current("dft", "Defendant-appellant"),
+    CodeEntry::current("dgc", "Degree committee member"),
+    CodeEntry::current("dgg", "Degree granting institution"),
+    CodeEntry::current("dgs", "Degree supervisor"),
+    CodeEntry::current("dis", "Dissertant"),
+    CodeEntry::current("djo", "DJ"),
+    CodeEntry::current("dln", "Delineator"),
+    CodeEntry::current("dnc", "Dancer"),
+    CodeEntry::current("dnr", "Donor"),
+    CodeEntry::current("dpc", "Depicted"),
+    CodeEntry::current("dpt", "Depositor"),
+    CodeEntry::current("drm", "Draftsman"),
+    CodeEntry::current("drt", "Director"),
+    CodeEntry::current("dsr", "Designer"),
+    CodeEntry::current("dst", "Distributor"),
+    CodeEntry::current("dtc", "Data contributor"),
+    CodeEntry::current("dte", "Dedicatee"),
+    CodeEntry::current("dtm", "Data manager"),
+    CodeEntry::current("dto", "Dedicator"),
+    CodeEntry::current("dub", "Dubious author"),
+    CodeEntry::current("edc", "Editor of compilation"),
+    CodeEntry::current("edd", "Editorial director"),
+    CodeEntry::current("edm", "Editor of moving image work"),
+    CodeEntry::current("edt", "Editor"),
+    CodeEntry::current("egr", "Engraver"),
+    CodeEntry::current("elg", "Electrician"),
+    CodeEntry::current("elt", "Electrotyper"),
+    CodeEntry::current("eng", "Engineer"),
+    CodeEntry::current("enj", "Enacting jurisdiction"),
+    CodeEntry::current("etr", "Etcher"),
+    CodeEntry::current("evp", "Event place"),
+    CodeEntry::current("exp", "Expert"),
+    CodeEntry::current("fac", "Facsimilist"),
+    CodeEntry::current("fds", "Film distributor"),
+    CodeEntry::current("fld", "Field director"),
+    CodeEntry::current("flm", "Film editor"),
+    CodeEntry::current("fmd", "Film director"),
+    CodeEntry::current("fmk", "Filmmaker"),
+    CodeEntry::current("fmo", "Former owner"),
+    CodeEntry::current("fmp", "Film producer"),
+    CodeEntry::current("fnd", "Funder"),
+    CodeEntry::current("fon", "Founder"),
+    CodeEntry::current("fpy", "First party"),
+    CodeEntry::current("frg", "Forger"),
+    CodeEntry::current("gdv", "Game developer"),
+    CodeEntry::current("gis", "Geographic information specialist"),
+    CodeEntry::obsolete("grt", "Graphic technician"),
+    CodeEntry::current("his", "Host institution"),
+    CodeEntry::current("hnr", "Honoree"),
+    CodeEntry::current("hst", "Host"),
+    CodeEntry::current("ill", "Illustrator"),
+    CodeEntry::current("ilu", "Illuminator"),
+    CodeEntry::current("ins", "Inscriber"),
+    CodeEntry::current("inv", "Inventor"),
+    CodeEntry::current("isb", "Issuing body"),
+    CodeEntry::current("itr", "Instrumentalist"),
+    CodeEntry::current("ive", "Interviewee"),
+    CodeEntry::current("ivr", "Interviewer"),
+    CodeEntry::current("jud", "Judge"),
+    CodeEntry::current("jug", "Jurisdiction governed"),
+    CodeEntry::current("lbr", "Laboratory"),
+    CodeEntry::current("lbt", "Librettist"),
+    CodeEntry::current("ldr", "Laboratory director"),
+    CodeEntry::current("led", "Lead"),
+    CodeEntry::current("lee", "Libelee-appellee"),
+    CodeEntry::current("lel", "Libelee"),
+    CodeEntry::current("len", "Lender"),
+    CodeEntry::current("let", "Libelee-appellant"),
+    CodeEntry::current("lgd", "Lighting designer"),
+    CodeEntry::current("lie", "Libelant-appellee"),
+    CodeEntry::current("lil", "Libelant"),
+    CodeEntry::current("lit", "Libelant-appellant"),
+    CodeEntry::current("lsa", "Landscape architect"),
+    CodeEntry::current("lse", "Licensee"),
+    CodeEntry::current("lso", "Licensor"),
+    CodeEntry::current("ltg", "Lithographer"),
+    CodeEntry::current("lyr", "Lyricist"),
+    CodeEntry::current("mcp", "Music copyist"),
+    CodeEntry::current("mdc", "Metadata contact"),
+    CodeEntry::current("med", "Medium"),
+    CodeEntry::current("mfp", "Manufacture place"),
+    CodeEntry::current("mfr", "Manufacturer"),
+    CodeEntry::current("mka", "Makeup artist"),
+    CodeEntry::current("mod", "Moderator"),
+    CodeEntry::current("mon", "Monitor"),
+    CodeEntry::current("mrb", "Marbler"),
+    CodeEntry::current("mrk", "Markup editor"),
+    CodeEntry::current("msd", "Musical director"),
+    CodeEntry::current("mte", "Metal-engraver"),
+    CodeEntry::current("mtk", "Minute taker"),
+    CodeEntry::current("mup", "Music programmer"),
+    CodeEntry::current("mus", "Musician"),
+    CodeEntry::current("mxe", "Mixing engineer"),
+    CodeEntry::current("nan", "News anchor"),
+    CodeEntry::current("nrt", "Narrator"),
+    CodeEntry::current("onp", "Onscreen participant"),
+    CodeEntry::current("opn", "Opponent"),
+    CodeEntry::current("org", "Originator"),
+    CodeEntry::current("orm", "Organizer"),
+    CodeEntry::current("osp", "Onscreen presenter"),
+    CodeEntry::current("oth", "Other"),
+    CodeEntry::current("own", "Owner"),
+    CodeEntry::current("pad", "Place of address"),
+    CodeEntry::current("pan", "Panelist"),
+    CodeEntry::current("pat", "Patron"),
+    CodeEntry::current("pbd", "Publishing director"),
+    CodeEntry::current("pbl", "Publisher"),
+    CodeEntry::current("pdr", "Project director"),
+    CodeEntry::current("pfr", "Proofreader"),
+    CodeEntry::current("pht", "Photographer"),
+    CodeEntry::current("plt", "Platemaker"),
+    CodeEntry::current("pma", "Permitting agency"),
+    CodeEntry::current("pmn", "Production manager"),
+    CodeEntry::current("pop", "Printer of plates"),
+    CodeEntry::current("ppm", "Papermaker"),
+    CodeEntry::current("ppt", "Puppeteer"),
+    CodeEntry::current("pra", "Praeses"),
+    CodeEntry::current("prc", "Process contact"),
+    CodeEntry::current("prd", "Production personnel"),
+    CodeEntry::current("pre", "Presenter"),
+    CodeEntry::current("prf", "Performer"),
+    CodeEntry::current("prg", "Programmer"),
+    CodeEntry::current("prm", "Printmaker"),
+    CodeEntry::current("prn", "Production company"),
+    CodeEntry::current("pro", "Producer"),
+    CodeEntry::current("prp", "Production place"),
+    CodeEntry::current("prs", "Production designer"),
+    CodeEntry::current("prt", "Printer"),
+    CodeEntry::current("prv", "Provider"),
+    CodeEntry::current("pta", "Patent applicant"),
+    CodeEntry::current("pte", "Plaintiff-appellee"),
+    CodeEntry::current("ptf", "Plaintiff"),
+    CodeEntry::current("pth", "Patent holder"),
+    CodeEntry::current("ptt", "Plaintiff-appellant"),
+    CodeEntry::current("pup", "Publication place"),
+    CodeEntry::current("rap", "Rapporteur"),
+    CodeEntry::current("rbr", "Rubricator"),
+    CodeEntry::current("rcd", "Recordist"),
+    CodeEntry::current("rce", "Recording engineer"),
+    CodeEntry::current("rcp", "Addressee"),
+    CodeEntry::current("rdd", "Radio director"),
+    CodeEntry::current("red", "Redaktor"),
+    CodeEntry::current("ren", "Renderer"),
+    CodeEntry::current("res", "Researcher"),
+    CodeEntry::current("rev", "Reviewer"),
+    CodeEntry::current("rpc", "Radio producer"),
+    CodeEntry::current("rps", "Repository"),
+    CodeEntry::current("rpt", "Reporter"),
+    CodeEntry::current("rpy", "Responsible party"),
+    CodeEntry::current("rse", "Respondent-appellee"),
+    CodeEntry::current("rsg", "Restager"),
+    CodeEntry::current("rsp", "Respondent"),
+    CodeEntry::current("rsr", "Restorationist"),
+    CodeEntry::current("rst", "Respondent-appellant"),
+    CodeEntry::current("rth", "Research team head"),
+    CodeEntry::current("rtm", "Research team member"),
+    CodeEntry::current("rxa", "Remix artist"),
+    CodeEntry::current("sad", "Scientific advisor"),
+    CodeEntry::current("sce", "Scenarist"),
+    CodeEntry::current("scl", "Sculptor"),
+    CodeEntry::current("scr", "Scribe"),
+    CodeEntry::current("sde", "Sound engineer"),
+    CodeEntry::current("sds", "Sound designer"),
+    CodeEntry::current("sec", "Secretary"),
+    CodeEntry::current("sfx", "Special effects provider"),
+    CodeEntry::current("sgd", "Stage director"),
+    CodeEntry::current("sgn", "Signer"),
+    CodeEntry::current("sht", "Supporting host"),
+    CodeEntry::current("sll", "Seller"),
+    CodeEntry::current("sng", "Singer"),
+    CodeEntry::current("spk", "Speaker"),
+    CodeEntry::current("spn", "Sponsor"),
+    CodeEntry::current("spy", "Second party"),
+    CodeEntry::current("srv", "Surveyor"),
+    CodeEntry::current("std", "Set designer"),
+    CodeEntry::current("stg", "Setting"),
+    CodeEntry::current("stl", "Storyteller"),
+    CodeEntry::current("stm", "Stage manager"),
+    CodeEntry::current("stn", "Standards body"),
+    CodeEntry::current("str", "Stereotyper"),
+    CodeEntry::current("swd", "Software developer"),
+    CodeEntry::current("tau", "Television writer"),
+    CodeEntry::current("tcd", "Technical director"),
+    CodeEntry::current("tch", "Teacher"),
+    CodeEntry::current("ths", "Thesis advisor"),
+    CodeEntry::current("tld", "Television director"),
+    CodeEntry::current("tlg", "Television guest"),
+    CodeEntry::current("tlh", "Television host"),
+    CodeEntry::current("tlp", "Television producer"),
+    CodeEntry::current("trc", "Transcriber"),
+    CodeEntry::current("trl", "Translator"),
+    CodeEntry::current("tyd", "Type designer"),
+    CodeEntry::current("tyg", "Typographer"),
+    CodeEntry::current("uvp", "University place"),
+    CodeEntry::current("vac", "Voice actor"),
+    CodeEntry::current("vdg", "Videographer"),
+    CodeEntry::obsolete("voc", "Vocalist"),
+    CodeEntry::current("wac", "Writer of added commentary"),
+    CodeEntry::current("wal", "Writer of added lyrics"),
+    CodeEntry::current("wam", "Writer of accompanying material"),
+    CodeEntry::current("wat", "Writer of added text"),
+    CodeEntry::current("wdc", "Woodcutter"),
+    CodeEntry::current("wde", "Wood engraver"),
+    CodeEntry::current("wfs", "Writer of film story"),
+    CodeEntry::current("wft", "Writer of intertitles"),
+    CodeEntry::current("win", "Writer of introduction"),
+    CodeEntry::current("wit", "Witness"),
+    CodeEntry::current("wpr", "Writer of preface"),
+    CodeEntry::current("wst", "Writer of supplementary textual content"),
+    CodeEntry::current("wts", "Writer of television story"),
+];
+
+/// Abbreviations and older forms of relator terms found in `$e` and `$j`,
+/// keyed as [`super::normalize_relator`] normalizes them.
+pub(super) const RELATOR_ALIASES: &[(&str, &str)] = &[
+    ("arr", "arr"),
+    ("author of afterword", "aft"),
+    ("comp", "com"),
+    ("cond", "cnd"),
+    ("dir", "drt"),
+    ("ed", "edt"),
+    ("eds", "edt"),
+    ("editors", "edt"),
+    ("ill", "ill"),
+    ("illus", "ill"),
+    ("illustrations", "ill"),
+    ("joint author", "aut"),
+    ("jt author", "aut"),
+    ("narr", "nrt"),
+    ("prod", "pro"),
+    ("tr", "trl"),
+    ("trans", "trl"),
+    ("translators", "trl"),
+    ("writer of foreword", "aui"),
+];
@@ -0,0 +1,373 @@
+//! Formatted contents notes (505) as structured tables of contents.
+//!
+//! A 505 comes in two forms. The basic form puts the whole table of
+//! contents in `$a`, entries separated by ` -- ` and responsibility after
+//! ` / `. The enhanced form (second indicator `0`) codes each entry's title
+//! in `$t`, its statement of responsibility in `$r` and other information,
+//! such as volume numbers or durations, in `$g`. [`Contents::from_field`]
+//! reads either form into [`ContentsEntry`] values with ISBD punctuation
+//! removed, and [`Contents::to_field`] writes them back in either form, so
+//! a basic note can be upgraded to an enhanced one for chapter-level
+//! indexing.
+//!
+//! A `$g` is taken as an entry's [`label`](ContentsEntry::label) when it
+//! comes first in the entry (`$g v. 1. $t ...`) and as its
+//! [`note`](ContentsEntry::note) otherwise (`$t ... $g (3:20)`).
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::contents::Contents;
+//! use mrrc::Field;
+//!
+//! let field = Field::builder("505".to_string(), '0', ' ')
+//!     .subfield_str('a', "Quarks / J. Smith -- Leptons / A. Jones -- Bosons.")
+//!     .build();
+//! let contents = Contents::from_field(&field);
+//! assert_eq!(contents.entries.len(), 3);
+//! assert_eq!(contents.entries[1].title, "Leptons");
+//! assert_eq!(contents.entries[1].responsibility.as_deref(), Some("A. Jones"));
+//!
+//! let enhanced = contents.to_field(true);
+//! assert_eq!(enhanced.indicator2, '0');
+//! assert_eq!(enhanced.get_subfield_values('t'), ["Quarks /", "Leptons /", "Bosons."]);
+//! assert_eq!(enhanced.get_subfield_values('r'), ["J. Smith --", "A. Jones --"]);
+//! ```
+
+use crate::punctuation::strip_value;
+use crate::record::{Field, Record};
+
+/// The display constant a 505 asks for (first indicator).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum ContentsDisplay {
+    /// "Contents" (0).
+    #[default]
+    Complete,
+    /// "Incomplete contents" (1).
+    Incomplete,
+    /// "Partial contents" (2).
+    Partial,
+    /// No display constant generated (8).
+    NoDisplayConstant,
+}
+
+impl ContentsDisplay {
+    /// Decode a 505 first indicator; unknown values read as complete.
+    #[must_use]
+    pub fn from_indicator(indicator: char) -> Self {
+        match indicator {
+            '1' => ContentsDisplay::Incomplete,
+            '2' => ContentsDisplay::Partial,
+            '8' => ContentsDisplay::NoDisplayConstant,
+            _ => ContentsDisplay::Complete,
+        }
+    }
+
+    /// The 505 first indicator for this display constant.
+    #[must_use]
+    pub fn indicator(self) -> char {
+        match self {
+            ContentsDisplay::Complete => '0',
+            ContentsDisplay::Incomplete => '1',
+            ContentsDisplay::Partial => '2',
+            ContentsDisplay::NoDisplayConstant => '8',
+        }
+    }
+}
+
+/// One entry (chapter, track, volume) of a contents note.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ContentsEntry {
+    /// Information before the title, such as `v. 1.` or `Disc 2.`
+    pub label: Option<String>,
+    /// Title of the part.
+    pub title: String,
+    /// Statement of responsibility.
+    pub responsibility: Option<String>,
+    /// Information after the title, such as a duration or page range.
+    pub note: Option<String>,
+}
+
+impl ContentsEntry {
+    /// An entry with only a title.
+    #[must_use]
+    pub fn new(title: &str) -> Self {
+        ContentsEntry {
+            title: title.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Set the statement of responsibility.
+    #[must_use]
+    pub fn with_responsibility(mut self, responsibility: &str) -> Self {
+        self.responsibility = Some(responsibility.to_string());
+        self
+    }
+
+    /// The entry as it appears in a basic note, without trailing
+    /// punctuation: `label title / responsibility note`.
+    #[must_use]
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        if let Some(label) = &self.label {
+            text.push_str(label);
+            text.push(' ');
+        }
+        text.push_str(&self.title);
+        if let Some(responsibility) = &self.responsibility {
+            text.push_str(" / ");
+            text.push_str(responsibility);
+        }
+        if let Some(note) = &self.note {
+            text.push(' ');
+            text.push_str(note);
+        }
+        text
+    }
+}
+
+/// A parsed 505.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Contents {
+    /// Display constant from the first indicator.
+    pub display: ContentsDisplay,
+    /// Entries in note order.
+    pub entries: Vec<ContentsEntry>,
+}
+
+impl Contents {
+    /// Parse a 505 in basic (`$a`) or enhanced (`$t`/`$r`/`$g`) form.
+    ///
+    /// A field with both forms, as continuation notes sometimes have, yields
+    /// the entries of each subfield in field order.
+    #[must_use]
+    pub fn from_field(field: &Field) -> Self {
+        let mut entries: Vec<ContentsEntry> = Vec::new();
+        // Whether the next $t or $g starts a new entry.
+        let mut boundary = true;
+        let mut pending_label = None;
+
+        for subfield in &field.subfields {
+            let raw = subfield.value.trim();
+            let ends_entry = raw.ends_with("--");
+            let value = clean(raw);
+            match subfield.code {
+                'a' => {
+                    entries.extend(raw.split(" -- ").filter_map(parse_basic_entry));
+                    boundary = true;
+                    continue;
+                },
+                't' => {
+                    entries.push(ContentsEntry {
+                        label: pending_label.take(),
+                        title: value,
+                        ..ContentsEntry::default()
+                    });
+                },
+                'r' => {
+                    if let Some(entry) = entries.last_mut().filter(|_| !boundary) {
+                        entry.responsibility = Some(value);
+                    }
+                },
+                'g' if boundary => pending_label = Some(value),
+                'g' => {
+                    if let Some(entry) = entries.last_mut() {
+                        entry.note = Some(value);
+                    }
+                },
+                _ => continue,
+            }
+            boundary = ends_entry || (subfield.code == 'g' && pending_label.is_some());
+        }
+        if let Some(label) = pending_label {
+            entries.push(ContentsEntry {
+                label: Some(label),
+                ..ContentsEntry::default()
+            });
+        }
+
+        Contents {
+            display: ContentsDisplay::from_indicator(field.indicator1),
+            entries,
+        }
+    }
+
+    /// Parse and concatenate every 505 of `record`; `None` if it has none.
+    #[must_use]
+    pub fn from_record(record: &Record) -> Option<Self> {
+        let mut fields = record.fields_by_tag("505");
+        let mut contents = Contents::from_field(fields.next()?);
+        for field in fields {
+            contents.entries.extend(Contents::from_field(field).entries);
+        }
+        Some(contents)
+    }
+
+    /// Write the contents as a 505 with ISBD punctuation: enhanced
+    /// (`$g`/`$t`/`$r`, second indicator `0`) when `enhanced` is set and
+    /// basic (`$a`, second indicator blank) otherwise.
+    #[must_use]
+    pub fn to_field(&self, enhanced: bool) -> Field {
+        let ind2 = if enhanced { '0' } else { ' ' };
+        let mut field = Field::new("505".to_string(), self.display.indicator(), ind2);
+        if !enhanced {
+            let mut text = self.to_text();
+            terminate(&mut text);
+            field.add_subfield('a', text);
+            return field;
+        }
+
+        for (i, entry) in self.entries.iter().enumerate() {
+            let start = field.subfields.len();
+            if let Some(label) = &entry.label {
+                let mut label = label.clone();
+                terminate(&mut label);
+                field.add_subfield('g', label);
+            }
+            if !entry.title.is_empty() {
+                let mut title = entry.title.clone();
+                if entry.responsibility.is_some() {
+                    title.push_str(" /");
+                }
+                field.add_subfield('t', title);
+            }
+            if let Some(responsibility) = &entry.responsibility {
+                field.add_subfield('r', responsibility.clone());
+            }
+            if let Some(note) = &entry.note {
+                field.add_subfield('g', note.clone());
+            }
+            if let Some(last) = field.subfields[start..].last_mut() {
+                if i + 1 < self.entries.len() {
+                    last.value.push_str(" --");
+                } else {
+                    terminate(&mut last.value);
+                }
+            }
+        }
+        field
+    }
+
+    /// The entries as a basic note, joined with ` -- `, without a terminal
+    /// period.
+    #[must_use]
+    pub fn to_text(&self) -> String {
+        self.entries
+            .iter()
+            .map(ContentsEntry::to_text)
+            .collect::<Vec<_>>()
+            .join(" -- ")
+    }
+}
+
+/// A value without a trailing ` --` or ISBD punctuation.
+fn clean(value: &str) -> String {
+    let value = value.trim_end();
+    let value = value.strip_suffix("--").unwrap_or(value);
+    strip_value(value.trim_end()).to_string()
+}
+
+/// One ` -- `-separated entry of a basic note.
+fn parse_basic_entry(text: &str) -> Option<ContentsEntry> {
+    let text = clean(text.trim());
+    if text.is_empty() {
+        return None;
+    }
+    Some(match text.split_once(" / ") {
+        Some((title, responsibility)) => {
+            ContentsEntry::new(title.trim()).with_responsibility(responsibility.trim())
+        },
+        None => ContentsEntry::new(&text),
+    })
+}
+
+/// Add a period unless `text` already ends with punctuation.
+fn terminate(text: &mut String) {
+    if !text.is_empty() && !text.ends_with(['.', '?', '!']) {
+        text.push('.');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(ind2: char, subfields: &[(char, &str)]) -> Field {
+        let mut builder = Field::builder("505".to_string(), '0', ind2);
+        for (code, value) in subfields {
+            builder = builder.subfield_str(*code, value);
+        }
+        builder.build()
+    }
+
+    #[test]
+    fn test_enhanced_with_labels_and_notes() {
+        let contents = Contents::from_field(&field(
+            '0',
+            &[
+                ('g', "v. 1."),
+                ('t', "The fellowship of the ring /"),
+                ('r', "J.R.R. Tolkien --"),
+                ('g', "v. 2."),
+                ('t', "The two towers --"),
+                ('t', "Interlude"),
+                ('g', "(3:20)."),
+            ],
+        ));
+        assert_eq!(contents.display, ContentsDisplay::Complete);
+        assert_eq!(
+            contents.entries,
+            vec![
+                ContentsEntry {
+                    label: Some("v. 1".to_string()),
+                    title: "The fellowship of the ring".to_string(),
+                    responsibility: Some("J.R.R. Tolkien".to_string()),
+                    note: None,
+                },
+                ContentsEntry {
+                    label: Some("v. 2".to_string()),
+                    ..ContentsEntry::new("The two towers")
+                },
+                ContentsEntry {
+                    note: Some("(3:20)".to_string()),
+                    ..ContentsEntry::new("Interlude")
+                },
+            ]
+        );
+
+        // Enhanced output parses back to the same entries.
+        let rewritten = contents.to_field(true);
+        assert_eq!(rewritten.get_subfield('g'), Some("v. 1."));
+        assert_eq!(Contents::from_field(&rewritten), contents);
+        assert_eq!(rewritten.subfields.last().unwrap().value, "(3:20).");
+    }
+
+    #[test]
+    fn test_basic_round_trip_and_record() {
+        let basic = field(
+            ' ',
+            &[('a', "Introduction -- Methods / R. Lee -- Results.")],
+        );
+        let contents = Contents::from_field(&basic);
+        assert_eq!(contents.entries.len(), 3);
+        assert_eq!(contents.entries[2].title, "Results");
+        let rewritten = contents.to_field(false);
+        assert_eq!(
+            rewritten.get_subfield('a'),
+            Some("Introduction -- Methods / R. Lee -- Results.")
+        );
+
+        let mut record =
+            Record::new(crate::Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+        assert!(Contents::from_record(&record).is_none());
+        record.add_field(basic);
+        let mut more = field('0', &[('t', "Appendix.")]);
+        more.indicator1 = '1';
+        record.add_field(more);
+        let all = Contents::from_record(&record).unwrap();
+        assert_eq!(all.entries.len(), 4);
+        assert_eq!(all.entries[3].title, "Appendix");
+    }
+}
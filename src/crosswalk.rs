@@ -0,0 +1,470 @@
+//! Field mappings from MARC to other metadata schemas, declared as data.
+//!
+//! A [`Crosswalk`] is an ordered list of [`Mapping`]s, each pairing a target
+//! element name with an [extraction expression](crate::extract) that pulls
+//! its values out of a record. The Dublin Core, MODS and CSV writers all take
+//! their simple element mappings from a crosswalk, so a local variation
+//! (OCLC numbers as identifiers, 246 as an alternative title, no rights
+//! statement) is a few lines of TOML or JSON rather than a code change.
+//!
+//! The built-in crosswalks, [`Crosswalk::dublin_core`] and
+//! [`Crosswalk::mods`], are the defaults the writers use.
+//! [`Crosswalk::with_overrides`] layers a user-supplied crosswalk on top of
+//! one of them: an element named in the overrides replaces every built-in
+//! mapping for that element, new elements are appended, and elements listed
+//! under `omit` are dropped.
+//!
+//! # Definition format
+//!
+//! Definitions are JSON, or TOML with the `toml` cargo feature:
+//!
+//! ```toml
+//! name = "Local Dublin Core"
+//! omit = ["rights"]
+//!
+//! [[mapping]]
+//! element = "identifier"
+//! source = "035$a"
+//! prefix = "OCLC: "
+//!
+//! [[mapping]]
+//! element = "language"
+//! source = "041$a | 008/35-37"
+//! split_whitespace = true
+//! ```
+//!
+//! The JSON form has the same shape, with the mappings in a `"mapping"`
+//! array.
+//!
+//! Element names are interpreted by the writer: Dublin Core takes the
+//! fifteen element names (`title`, `creator`, ...), CSV uses them as column
+//! headers, and MODS takes a `/`-separated element path with optional
+//! attributes, such as `subject/topic` or `identifier[type=isbn]`.
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::crosswalk::Crosswalk;
+//! use mrrc::dublin_core::record_to_dublin_core_with;
+//! use mrrc::{Field, Leader, Record};
+//!
+//! let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+//! record.add_field(
+//!     Field::builder("035".to_string(), ' ', ' ')
+//!         .subfield_str('a', "(OCoLC)12345")
+//!         .build(),
+//! );
+//!
+//! let local = Crosswalk::from_json(
+//!     r#"{"name": "local", "mapping": [{"element": "identifier", "source": "035$a"}]}"#,
+//! )?;
+//! let crosswalk = Crosswalk::dublin_core().with_overrides(local);
+//! let dc = record_to_dublin_core_with(&record, &crosswalk)?;
+//! assert_eq!(dc.identifier, vec!["(OCoLC)12345"]);
+//! # Ok::<(), mrrc::MarcError>(())
+//! ```
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{MarcError, Result};
+use crate::extract::Extractor;
+use crate::marc_record::MarcRecord;
+
+/// One target element and where its values come from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "MappingDef", into = "MappingDef")]
+pub struct Mapping {
+    /// Target element name, interpreted by the writer.
+    pub element: String,
+    /// Extraction expression producing the element's values.
+    pub source: Extractor,
+    /// Text prepended to every value.
+    pub prefix: Option<String>,
+    /// Split each value on whitespace into several values (for example
+    /// 041$a language codes run together as `"eng fre"`).
+    pub split_whitespace: bool,
+}
+
+/// Serialized form of a [`Mapping`], with the source as an expression string.
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MappingDef {
+    element: String,
+    source: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    prefix: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    split_whitespace: bool,
+}
+
+impl TryFrom<MappingDef> for Mapping {
+    type Error = MarcError;
+
+    fn try_from(def: MappingDef) -> Result<Self> {
+        Ok(Mapping {
+            source: Extractor::new(&def.source)?,
+            element: def.element,
+            prefix: def.prefix,
+            split_whitespace: def.split_whitespace,
+        })
+    }
+}
+
+impl From<Mapping> for MappingDef {
+    fn from(mapping: Mapping) -> Self {
+        MappingDef {
+            element: mapping.element,
+            source: mapping.source.to_string(),
+            prefix: mapping.prefix,
+            split_whitespace: mapping.split_whitespace,
+        }
+    }
+}
+
+impl Mapping {
+    /// Map the values of `source` to `element`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MarcError::InvalidField`] if `source` is not a valid
+    /// extraction expression.
+    pub fn new(element: &str, source: &str) -> Result<Self> {
+        Ok(Mapping {
+            element: element.to_string(),
+            source: Extractor::new(source)?,
+            prefix: None,
+            split_whitespace: false,
+        })
+    }
+
+    /// Prepend `prefix` to every value.
+    #[must_use]
+    pub fn with_prefix(mut self, prefix: &str) -> Self {
+        self.prefix = Some(prefix.to_string());
+        self
+    }
+
+    /// Split values on whitespace.
+    #[must_use]
+    pub fn split_on_whitespace(mut self) -> Self {
+        self.split_whitespace = true;
+        self
+    }
+
+    /// The values this mapping produces for `record`.
+    #[must_use]
+    pub fn values<R: MarcRecord + ?Sized>(&self, record: &R) -> Vec<String> {
+        let mut values = self.source.all(record);
+        if self.split_whitespace {
+            values = values
+                .iter()
+                .flat_map(|value| value.split_whitespace().map(str::to_string))
+                .collect();
+        }
+        if let Some(prefix) = &self.prefix {
+            for value in &mut values {
+                value.insert_str(0, prefix);
+            }
+        }
+        values
+    }
+}
+
+/// A named, ordered set of element mappings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Crosswalk {
+    /// Crosswalk name, for messages.
+    pub name: String,
+    /// Mappings, in output order.
+    #[serde(default, rename = "mapping")]
+    pub mappings: Vec<Mapping>,
+    /// Elements removed by [`with_overrides`](Crosswalk::with_overrides).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub omit: Vec<String>,
+}
+
+impl Crosswalk {
+    /// Create an empty crosswalk.
+    #[must_use]
+    pub fn new(name: &str) -> Self {
+        Crosswalk {
+            name: name.to_string(),
+            mappings: Vec::new(),
+            omit: Vec::new(),
+        }
+    }
+
+    /// Add a mapping.
+    #[must_use]
+    pub fn mapping(mut self, mapping: Mapping) -> Self {
+        self.mappings.push(mapping);
+        self
+    }
+
+    /// Mark an element to be removed when this crosswalk is applied as
+    /// overrides.
+    #[must_use]
+    pub fn omit(mut self, element: &str) -> Self {
+        self.omit.push(element.to_string());
+        self
+    }
+
+    /// Parse a crosswalk from TOML.
+    ///
+    /// The document has a top-level `name`, an optional `omit` list, and a
+    /// `[[mapping]]` table per mapping with `element`, `source`, and
+    /// optionally `prefix` and `split_whitespace`. Requires the `toml` cargo
+    /// feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MarcError::InvalidField`] if the TOML is malformed, does
+    /// not describe a crosswalk, or contains an invalid source expression.
+    #[cfg(feature = "toml")]
+    pub fn from_toml(source: &str) -> Result<Self> {
+        toml::from_str(source)
+            .map_err(|e| MarcError::invalid_field_msg(format!("Invalid crosswalk definition: {e}")))
+    }
+
+    /// Parse a crosswalk from JSON, in the same shape as the TOML
+    /// definition (see the [module documentation](self)).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MarcError::InvalidField`] if the JSON is malformed, does
+    /// not describe a crosswalk, or contains an invalid source expression.
+    pub fn from_json(source: &str) -> Result<Self> {
+        serde_json::from_str(source)
+            .map_err(|e| MarcError::invalid_field_msg(format!("Invalid crosswalk definition: {e}")))
+    }
+
+    /// Serialize the crosswalk as TOML. Requires the `toml` cargo feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MarcError::InvalidField`] if serialization fails.
+    #[cfg(feature = "toml")]
+    pub fn to_toml(&self) -> Result<String> {
+        toml::to_string(self)
+            .map_err(|e| MarcError::invalid_field_msg(format!("Cannot serialize crosswalk: {e}")))
+    }
+
+    /// Layer `overrides` on top of this crosswalk.
+    ///
+    /// Each element mapped in `overrides` replaces all of this crosswalk's
+    /// mappings for that element, at the position of the first one; elements
+    /// this crosswalk does not map are appended. Elements listed in the
+    /// overrides' `omit` are then removed.
+    #[must_use]
+    pub fn with_overrides(mut self, overrides: Crosswalk) -> Self {
+        let mut replaced: IndexMap<String, Vec<Mapping>> = IndexMap::new();
+        for mapping in overrides.mappings {
+            replaced
+                .entry(mapping.element.clone())
+                .or_default()
+                .push(mapping);
+        }
+
+        let mut mappings = Vec::with_capacity(self.mappings.len());
+        for mapping in self.mappings {
+            match replaced.get_mut(&mapping.element) {
+                // The first built-in mapping takes every replacement; later
+                // ones find the list already drained.
+                Some(replacement) => mappings.append(replacement),
+                None => mappings.push(mapping),
+            }
+        }
+        mappings.extend(replaced.into_values().flatten());
+        mappings.retain(|m| !overrides.omit.contains(&m.element));
+        self.mappings = mappings;
+        self
+    }
+
+    /// The distinct element names, in order of first mapping.
+    #[must_use]
+    pub fn elements(&self) -> Vec<&str> {
+        let mut elements: Vec<&str> = Vec::new();
+        for mapping in &self.mappings {
+            if !elements.contains(&mapping.element.as_str()) {
+                elements.push(&mapping.element);
+            }
+        }
+        elements
+    }
+
+    /// Evaluate every mapping against `record`.
+    ///
+    /// The result has an entry for every element, in [`elements`] order,
+    /// holding the values of all of that element's mappings in mapping
+    /// order; elements with no values map to an empty list.
+    ///
+    /// [`elements`]: Crosswalk::elements
+    #[must_use]
+    pub fn apply<R: MarcRecord + ?Sized>(&self, record: &R) -> IndexMap<String, Vec<String>> {
+        let mut values: IndexMap<String, Vec<String>> = IndexMap::new();
+        for mapping in &self.mappings {
+            values
+                .entry(mapping.element.clone())
+                .or_default()
+                .extend(mapping.values(record));
+        }
+        values
+    }
+
+    /// The Dublin Core mappings used by
+    /// [`record_to_dublin_core`](crate::dublin_core::record_to_dublin_core).
+    #[must_use]
+    pub fn dublin_core() -> Self {
+        Crosswalk::new("Dublin Core")
+            .mapping(builtin("title", "245$abc"))
+            .mapping(builtin("creator", "100$a"))
+            .mapping(builtin("creator", "110$a"))
+            .mapping(builtin("subject", "600$a"))
+            .mapping(builtin("subject", "610$a"))
+            .mapping(builtin("subject", "650$a"))
+            .mapping(builtin("description", "520$a"))
+            .mapping(builtin("description", "500$a"))
+            .mapping(builtin("publisher", "260$a"))
+            .mapping(builtin("contributor", "700$a"))
+            .mapping(builtin("contributor", "710$a"))
+            .mapping(builtin("date", "260$c"))
+            .mapping(builtin("identifier", "020$a").with_prefix("ISBN: "))
+            .mapping(builtin("identifier", "001").with_prefix("Control#: "))
+            .mapping(builtin("format", "300$a"))
+            .mapping(builtin("language", "041$a").split_on_whitespace())
+            .mapping(builtin("coverage", "651$a"))
+            .mapping(builtin("rights", "540$a"))
+    }
+
+    /// The simple element mappings used by
+    /// [`record_to_mods_xml`](crate::mods::record_to_mods_xml). Titles,
+    /// names, origin information and the other structured elements are
+    /// always written by the MODS writer itself.
+    #[must_use]
+    pub fn mods() -> Self {
+        Crosswalk::new("MODS")
+            .mapping(builtin("abstract", "520$a"))
+            .mapping(builtin("note", "500$a"))
+            .mapping(builtin("targetAudience", "521$a"))
+            .mapping(builtin("subject/topic", "650$a"))
+            .mapping(builtin("subject/geographic", "651$a"))
+            .mapping(builtin("identifier[type=isbn]", "020$a"))
+            .mapping(builtin("identifier[type=issn]", "022$a"))
+            .mapping(builtin("identifier[type=local]", "001"))
+            .mapping(builtin(
+                "accessCondition[type=restriction on access]",
+                "506$a",
+            ))
+            .mapping(builtin(
+                "accessCondition[type=use and reproduction]",
+                "540$a",
+            ))
+    }
+}
+
+/// A built-in mapping; the expressions are fixed and covered by tests.
+fn builtin(element: &str, source: &str) -> Mapping {
+    Mapping::new(element, source).expect("built-in crosswalk expression")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::leader::Leader;
+    use crate::record::{Field, Record};
+
+    fn record() -> Record {
+        let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+        record.add_control_field_str("001", "ocm1");
+        record.add_field(
+            Field::builder("041".to_string(), '0', ' ')
+                .subfield_str('a', "eng fre")
+                .build(),
+        );
+        record.add_field(
+            Field::builder("245".to_string(), '1', '0')
+                .subfield_str('a', "Title :")
+                .subfield_str('b', "sub.")
+                .build(),
+        );
+        record.add_field(
+            Field::builder("540".to_string(), ' ', ' ')
+                .subfield_str('a', "Public domain.")
+                .build(),
+        );
+        record
+    }
+
+    #[test]
+    fn test_builtin_crosswalks_apply() {
+        let values = Crosswalk::dublin_core().apply(&record());
+        assert_eq!(values["title"], ["Title : sub."]);
+        assert_eq!(values["language"], ["eng", "fre"]);
+        assert_eq!(values["identifier"], ["Control#: ocm1"]);
+        assert!(values["creator"].is_empty());
+        assert_eq!(values.len(), 12);
+
+        let mods = Crosswalk::mods();
+        assert_eq!(mods.elements().len(), mods.mappings.len());
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_overrides_replace_append_and_omit() {
+        let overrides = Crosswalk::from_toml(
+            r#"
+            name = "local"
+            omit = ["rights", "coverage"]
+
+            [[mapping]]
+            element = "language"
+            source = "008/35-37"
+
+            [[mapping]]
+            element = "identifier"
+            source = "001"
+            prefix = "local:"
+
+            [[mapping]]
+            element = "alternative"
+            source = "246$a"
+            "#,
+        )
+        .unwrap();
+        let crosswalk = Crosswalk::dublin_core().with_overrides(overrides);
+        let elements = crosswalk.elements();
+        assert!(!elements.contains(&"rights"));
+        assert!(!elements.contains(&"coverage"));
+        assert_eq!(elements.last(), Some(&"alternative"));
+        // Replaced elements keep their built-in position.
+        let position = |name| elements.iter().position(|e| *e == name).unwrap();
+        assert!(position("identifier") < position("format"));
+
+        let values = crosswalk.apply(&record());
+        assert_eq!(values["identifier"], ["local:ocm1"]);
+        assert!(values["language"].is_empty());
+    }
+
+    #[test]
+    fn test_json_and_toml_round_trip() {
+        let json = r#"{"name": "x", "mapping": [
+            {"element": "date", "source": "260$c | 008/7-10", "prefix": "c"}
+        ]}"#;
+        let crosswalk = Crosswalk::from_json(json).unwrap();
+        assert_eq!(crosswalk.mappings[0].source.selectors().len(), 2);
+        #[cfg(feature = "toml")]
+        {
+            let toml = crosswalk.to_toml().unwrap();
+            assert_eq!(Crosswalk::from_toml(&toml).unwrap(), crosswalk);
+        }
+
+        let bad =
+            Crosswalk::from_json(r#"{"name": "x", "mapping": [{"element": "a", "source": "24"}]}"#);
+        assert!(
+            bad.unwrap_err()
+                .to_string()
+                .contains("Invalid crosswalk definition")
+        );
+    }
+}
@@ -8,6 +8,8 @@
 //! - **Single record**: [`record_to_csv`] - Converts a single `Record` to CSV
 //! - **Batch records**: [`records_to_csv`] - Converts a slice of `Record`s to CSV with combined output
 //! - **Filtered batch**: [`records_to_csv_filtered`] - Converts records to CSV with field filtering
+//! - **Crosswalk columns**: [`records_to_csv_with`] - One row per record, one column per
+//!   [`Crosswalk`] element
 //!
 //! # Examples
 //!
@@ -41,6 +43,7 @@
 
 use std::fmt::Write;
 
+use crate::crosswalk::Crosswalk;
 use crate::error::Result;
 use crate::record::Record;
 
@@ -76,6 +79,7 @@ use crate::record::Record;
 ///
 /// Returns an error if the CSV cannot be written.
 pub fn record_to_csv(record: &Record) -> Result<String> {
+    crate::telemetry::stage_span!("mrrc.convert", from = "marc", to = "csv");
     records_to_csv(std::slice::from_ref(record))
 }
 
@@ -224,6 +228,49 @@ where
     Ok(output)
 }
 
+/// Convert MARC records to one CSV row per record, with a column per
+/// crosswalk element.
+///
+/// The header row holds the element names of `crosswalk` in order; an
+/// element with several values has them joined with `"; "`. Pair this with
+/// [`Crosswalk::dublin_core`] for a flat Dublin Core spreadsheet, or with a
+/// crosswalk loaded from TOML for a local export layout.
+///
+/// # Examples
+///
+/// ```ignore
+/// use mrrc::crosswalk::Crosswalk;
+/// use mrrc::csv;
+///
+/// let csv = csv::records_to_csv_with(&records, &Crosswalk::dublin_core())?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the CSV cannot be written.
+pub fn records_to_csv_with(records: &[Record], crosswalk: &Crosswalk) -> Result<String> {
+    let mut output = String::new();
+
+    let header: Vec<String> = crosswalk
+        .elements()
+        .into_iter()
+        .map(escape_csv_value)
+        .collect();
+    writeln!(output, "{}", header.join(",")).ok();
+
+    for record in records {
+        let row: Vec<String> = crosswalk
+            .apply(record)
+            .values()
+            .map(|values| escape_csv_value(&values.join("; ")))
+            .collect();
+        writeln!(output, "{}", row.join(",")).ok();
+    }
+
+    Ok(output)
+}
+
 /// Escape a value for CSV output.
 ///
 /// Wraps values in quotes if they contain commas, quotes, or newlines.
@@ -344,4 +391,23 @@ mod tests {
         assert!(csv.contains("001,,,11111"));
         assert!(csv.contains("001,,,22222"));
     }
+
+    #[test]
+    fn test_csv_with_crosswalk() {
+        let mut record = Record::new(make_test_leader());
+        record.add_control_field("001".to_string(), "12345".to_string());
+        for subject in ["Cats", "Dogs, domestic"] {
+            let mut field = Field::new("650".to_string(), ' ', '0');
+            field.add_subfield('a', subject.to_string());
+            record.add_field(field);
+        }
+
+        let crosswalk = Crosswalk::new("export")
+            .mapping(crate::crosswalk::Mapping::new("id", "001").unwrap())
+            .mapping(crate::crosswalk::Mapping::new("subjects", "650$a").unwrap())
+            .mapping(crate::crosswalk::Mapping::new("title", "245$a").unwrap());
+        let csv = records_to_csv_with(&[record], &crosswalk).expect("Failed to generate CSV");
+
+        assert_eq!(csv, "id,subjects,title\n12345,\"Cats; Dogs, domestic\",\n");
+    }
 }
@@ -0,0 +1,499 @@
+//! Date interpretation for 008/06-14 and 260/264 `$c` (EDTF output).
+//!
+//! A bibliographic record carries its dates twice: coded in 008 as a
+//! [`DateType`] with Date1 and Date2, and transcribed in 260 or 264 `$c`
+//! as free text (`[1998?]`, `c1925`, `19--`, `[between 1846 and 1853]`).
+//! [`FixedDates`] interprets the coded form according to the date type and
+//! [`parse_date_statement`] reads the transcribed form; both produce a
+//! [`ParsedDate`] holding an [Extended Date/Time Format] string and the
+//! numeric year range it covers, ready for display and range search.
+//!
+//! EDTF conventions used: `X` for unspecified digits (`19XX`), `?`, `~`
+//! and `%` for questionable, approximate, and both; `a/b` for a range,
+//! `a/..` for an open (ongoing) end and `a/` for an unknown end; and
+//! `[a..b]` or `[a,b]` for "one of" a set of dates.
+//!
+//! [Extended Date/Time Format]: https://www.loc.gov/standards/datetime/
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::dates::{parse_date_statement, DateType, FixedDates};
+//!
+//! let date = parse_date_statement("[199-?]").unwrap();
+//! assert_eq!(date.edtf, "199X?");
+//! assert_eq!((date.start, date.end), (1990, Some(1999)));
+//!
+//! let fixed = FixedDates::from_008("850101c19859999nyumr p       0   a0eng  ").unwrap();
+//! assert_eq!(fixed.date_type, DateType::Continuing);
+//! let date = fixed.interpret().unwrap();
+//! assert_eq!(date.edtf, "1985/..");
+//! assert_eq!(date.end, None);
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::record::Record;
+
+/// The type of date or publication status in 008/06.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DateType {
+    /// b - No dates given; B.C. date involved
+    NoDatesBc,
+    /// c - Continuing resource currently published
+    Continuing,
+    /// d - Continuing resource ceased publication
+    Ceased,
+    /// e - Detailed date (Date2 holds month and day)
+    Detailed,
+    /// i - Inclusive dates of collection
+    Inclusive,
+    /// k - Range of years of bulk of collection
+    Bulk,
+    /// m - Multiple dates
+    Multiple,
+    /// n - Dates unknown
+    Unknown,
+    /// p - Date of distribution and date of production
+    Distribution,
+    /// q - Questionable date (Date1 earliest, Date2 latest possible)
+    Questionable,
+    /// r - Reprint date and original date
+    Reprint,
+    /// s - Single known or probable date
+    Single,
+    /// t - Publication date and copyright date
+    Copyright,
+    /// u - Continuing resource status unknown
+    ContinuingStatusUnknown,
+    /// | - No attempt to code
+    NotCoded,
+    /// Any other code
+    Other(char),
+}
+
+impl DateType {
+    /// Decode an 008/06 character.
+    #[must_use]
+    pub fn from_code(code: char) -> Self {
+        match code {
+            'b' => DateType::NoDatesBc,
+            'c' => DateType::Continuing,
+            'd' => DateType::Ceased,
+            'e' => DateType::Detailed,
+            'i' => DateType::Inclusive,
+            'k' => DateType::Bulk,
+            'm' => DateType::Multiple,
+            'n' => DateType::Unknown,
+            'p' => DateType::Distribution,
+            'q' => DateType::Questionable,
+            'r' => DateType::Reprint,
+            's' => DateType::Single,
+            't' => DateType::Copyright,
+            'u' => DateType::ContinuingStatusUnknown,
+            '|' => DateType::NotCoded,
+            other => DateType::Other(other),
+        }
+    }
+
+    /// The 008/06 character for this date type.
+    #[must_use]
+    pub fn code(self) -> char {
+        match self {
+            DateType::NoDatesBc => 'b',
+            DateType::Continuing => 'c',
+            DateType::Ceased => 'd',
+            DateType::Detailed => 'e',
+            DateType::Inclusive => 'i',
+            DateType::Bulk => 'k',
+            DateType::Multiple => 'm',
+            DateType::Unknown => 'n',
+            DateType::Distribution => 'p',
+            DateType::Questionable => 'q',
+            DateType::Reprint => 'r',
+            DateType::Single => 's',
+            DateType::Copyright => 't',
+            DateType::ContinuingStatusUnknown => 'u',
+            DateType::NotCoded => '|',
+            DateType::Other(c) => c,
+        }
+    }
+}
+
+/// A date in EDTF form together with the years it covers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParsedDate {
+    /// Extended Date/Time Format string (`"1998"`, `"19XX?"`, `"1985/.."`)
+    pub edtf: String,
+    /// Earliest year covered
+    pub start: i32,
+    /// Latest year covered, or `None` when the range is open or its end
+    /// is unknown
+    pub end: Option<i32>,
+}
+
+/// One year, possibly with unspecified digits.
+struct Year {
+    edtf: String,
+    earliest: i32,
+    latest: i32,
+}
+
+impl Year {
+    /// Parse four characters of digits and the given unknown-digit marks
+    /// (`u` in 008, `-` in transcribed dates). The first digit must be
+    /// known.
+    fn parse(s: &str, unknown: &[char]) -> Option<Year> {
+        if s.chars().count() != 4 || !s.starts_with(|c: char| c.is_ascii_digit()) {
+            return None;
+        }
+        let mut edtf = String::with_capacity(4);
+        for c in s.chars() {
+            if c.is_ascii_digit() {
+                edtf.push(c);
+            } else if unknown.contains(&c) {
+                edtf.push('X');
+            } else {
+                return None;
+            }
+        }
+        Some(Year {
+            earliest: edtf.replace('X', "0").parse().ok()?,
+            latest: edtf.replace('X', "9").parse().ok()?,
+            edtf,
+        })
+    }
+
+    fn single(&self, qualifier: &str) -> ParsedDate {
+        ParsedDate {
+            edtf: format!("{}{qualifier}", self.edtf),
+            start: self.earliest,
+            end: Some(self.latest),
+        }
+    }
+
+    fn range(&self, to: &Year, qualifier: &str) -> ParsedDate {
+        ParsedDate {
+            edtf: format!("{}{qualifier}/{}{qualifier}", self.edtf, to.edtf),
+            start: self.earliest,
+            end: Some(to.latest),
+        }
+    }
+
+    /// An open range: `a/..` when ongoing, `a/` when the end is unknown.
+    fn open(&self, ongoing: bool, qualifier: &str) -> ParsedDate {
+        ParsedDate {
+            edtf: format!(
+                "{}{qualifier}/{}",
+                self.edtf,
+                if ongoing { ".." } else { "" }
+            ),
+            start: self.earliest,
+            end: None,
+        }
+    }
+
+    /// One of a set of dates: `[a..b]` for a span, `[a,b]` for a choice.
+    /// A span ends with `other`, even when `self` could be later.
+    fn one_of(&self, other: &Year, span: bool) -> ParsedDate {
+        let (start, end) = if span {
+            (self.earliest, other.latest)
+        } else {
+            (
+                self.earliest.min(other.earliest),
+                self.latest.max(other.latest),
+            )
+        };
+        ParsedDate {
+            edtf: format!(
+                "[{}{}{}]",
+                self.edtf,
+                if span { ".." } else { "," },
+                other.edtf
+            ),
+            start,
+            end: Some(end),
+        }
+    }
+}
+
+/// The coded dates of 008/06-14.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FixedDates {
+    /// 008/06
+    pub date_type: DateType,
+    /// 008/07-10
+    pub date1: String,
+    /// 008/11-14
+    pub date2: String,
+}
+
+impl FixedDates {
+    /// Read the date positions of a bibliographic 008 value. Returns
+    /// `None` if the value is shorter than 15 characters.
+    #[must_use]
+    pub fn from_008(value: &str) -> Option<Self> {
+        let date_type = value.get(6..7)?.chars().next()?;
+        Some(FixedDates {
+            date_type: DateType::from_code(date_type),
+            date1: value.get(7..11)?.to_string(),
+            date2: value.get(11..15)?.to_string(),
+        })
+    }
+
+    /// The 008 dates of `record`.
+    #[must_use]
+    pub fn from_record(record: &Record) -> Option<Self> {
+        record.get_control_field("008").and_then(Self::from_008)
+    }
+
+    /// Interpret Date1 and Date2 according to the date type.
+    ///
+    /// Single-date types (and `r`, `p`, `t`, whose Date2 is an original,
+    /// production or copyright date) give Date1. Range types (`d`, `i`,
+    /// `k`, `m`) give Date1–Date2, open when Date2 is `9999` and with an
+    /// unknown end when it is `uuuu`. `c` and `u` give an open and an
+    /// unknown end; `q` gives "one of" Date1..Date2; `e` adds the month and
+    /// day from Date2. `b`, `n` and uncoded dates give `None`, as does a
+    /// Date1 with no known digits.
+    #[must_use]
+    pub fn interpret(&self) -> Option<ParsedDate> {
+        let date1 = Year::parse(&self.date1, &['u'])?;
+        let date2 = Year::parse(&self.date2, &['u']);
+        let date2_is = |value: &str| self.date2 == value;
+
+        Some(match self.date_type {
+            DateType::Single | DateType::Reprint | DateType::Distribution | DateType::Copyright => {
+                date1.single("")
+            },
+            DateType::Detailed => {
+                let mut date = date1.single("");
+                let month = self.date2.get(0..2).filter(|m| is_digits(m));
+                let day = self.date2.get(2..4).filter(|d| is_digits(d));
+                if let Some(month) = month {
+                    date.edtf = format!("{}-{month}", date.edtf);
+                    if let Some(day) = day {
+                        date.edtf = format!("{}-{day}", date.edtf);
+                    }
+                }
+                date
+            },
+            DateType::Questionable => match date2 {
+                Some(date2) => date1.one_of(&date2, true),
+                None => date1.single("?"),
+            },
+            DateType::Continuing => date1.open(true, ""),
+            DateType::ContinuingStatusUnknown => date1.open(false, ""),
+            DateType::Ceased | DateType::Inclusive | DateType::Bulk | DateType::Multiple => {
+                if date2_is("9999") {
+                    date1.open(true, "")
+                } else if let Some(date2) = date2 {
+                    date1.range(&date2, "")
+                } else if date2_is("uuuu") {
+                    date1.open(false, "")
+                } else {
+                    date1.single("")
+                }
+            },
+            DateType::NoDatesBc | DateType::Unknown | DateType::NotCoded | DateType::Other(_) => {
+                return None;
+            },
+        })
+    }
+}
+
+fn is_digits(s: &str) -> bool {
+    s.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Parse a transcribed publication date (260/264 `$c`).
+///
+/// Handles the common AACR2 and RDA forms: plain and bracketed years,
+/// copyright and phonogram prefixes (`c1925`, `©1925`, `p1990`), unknown
+/// decades and centuries (`199-`, `19--`), questionable and approximate
+/// dates (`[1998?]`, `[ca. 1850]`), ranges (`1998-2002`, `1985-`), and
+/// "one of" dates (`[1998 or 1999]`, `[between 1846 and 1853]`). When a
+/// statement has several unrelated dates (`2018, ©2017`) the first is
+/// used. Returns `None` if no year can be found.
+#[must_use]
+pub fn parse_date_statement(statement: &str) -> Option<ParsedDate> {
+    let lower = statement.to_lowercase();
+    let questionable = lower.contains('?');
+    let approximate = ["ca.", "circa", "approximately"]
+        .iter()
+        .any(|marker| lower.contains(marker));
+    let qualifier = match (questionable, approximate) {
+        (true, true) => "%",
+        (true, false) => "?",
+        (false, true) => "~",
+        (false, false) => "",
+    };
+
+    let years = find_years(&lower);
+    let (first, first_end) = years.first()?;
+    if let Some((second, second_start)) = years.get(1).map(|(y, end)| (y, end - 4)) {
+        let between = lower[*first_end..second_start].trim();
+        if between == "-" {
+            return Some(first.range(second, qualifier));
+        }
+        if between == "or" {
+            return Some(first.one_of(second, false));
+        }
+        if between == "and" && lower.contains("between") {
+            return Some(first.one_of(second, true));
+        }
+    }
+
+    // Skip the qualifier and bracket that can close the year (`[2005?]-`).
+    let rest =
+        lower[*first_end..].trim_start_matches(|c: char| c == '?' || c == ']' || c.is_whitespace());
+    if let Some(after) = rest.strip_prefix('-')
+        && !after.contains(|c: char| c.is_ascii_digit())
+    {
+        return Some(first.open(true, qualifier));
+    }
+    Some(first.single(qualifier))
+}
+
+/// Find four-character years in `text`, with the byte offset just past
+/// each. A year starts with two digits, not preceded by a digit, and may
+/// end in hyphens for unknown digits (`19--`, `199-`).
+fn find_years(text: &str) -> Vec<(Year, usize)> {
+    let bytes = text.as_bytes();
+    let mut years = Vec::new();
+    let mut i = 0;
+    while i + 4 <= bytes.len() {
+        let candidate = &bytes[i..i + 4];
+        let preceded_by_digit = i > 0 && bytes[i - 1].is_ascii_digit();
+        let followed_by_digit = bytes.get(i + 4).is_some_and(u8::is_ascii_digit);
+        let known = candidate.iter().take_while(|b| b.is_ascii_digit()).count();
+        let shape_ok = known >= 2 && candidate[known..].iter().all(|&b| b == b'-');
+        if !preceded_by_digit
+            && !followed_by_digit
+            && shape_ok
+            && let Some(year) = std::str::from_utf8(candidate)
+                .ok()
+                .and_then(|s| Year::parse(s, &['-']))
+        {
+            years.push((year, i + 4));
+            i += 4;
+        } else {
+            i += 1;
+        }
+    }
+    years
+}
+
+/// The publication date of a record: the 008 dates when they can be
+/// interpreted, otherwise the first of 260 `$c` and 264 (second indicator
+/// `1`) `$c` that yields a year.
+#[must_use]
+pub fn publication_dates(record: &Record) -> Option<ParsedDate> {
+    if let Some(date) = FixedDates::from_record(record).and_then(|dates| dates.interpret()) {
+        return Some(date);
+    }
+    record
+        .fields_by_tag("260")
+        .chain(record.fields_by_tag("264").filter(|f| f.indicator2 == '1'))
+        .flat_map(|field| field.subfields_by_code('c'))
+        .find_map(parse_date_statement)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::leader::Leader;
+    use crate::record::Field;
+
+    fn statement(s: &str) -> (String, i32, Option<i32>) {
+        let date = parse_date_statement(s).unwrap_or_else(|| panic!("no date in {s:?}"));
+        (date.edtf, date.start, date.end)
+    }
+
+    fn fixed(date_type: char, date1: &str, date2: &str) -> Option<(String, i32, Option<i32>)> {
+        FixedDates {
+            date_type: DateType::from_code(date_type),
+            date1: date1.to_string(),
+            date2: date2.to_string(),
+        }
+        .interpret()
+        .map(|d| (d.edtf, d.start, d.end))
+    }
+
+    #[test]
+    fn test_statement_forms() {
+        let cases = [
+            ("1998.", "1998", 1998, Some(1998)),
+            ("[1998?]", "1998?", 1998, Some(1998)),
+            ("c1925.", "1925", 1925, Some(1925)),
+            ("©2017", "2017", 2017, Some(2017)),
+            ("[19--]", "19XX", 1900, Some(1999)),
+            ("[197-?]", "197X?", 1970, Some(1979)),
+            ("[ca. 1850]", "1850~", 1850, Some(1850)),
+            ("[ca. 1850?]", "1850%", 1850, Some(1850)),
+            ("1998-2002.", "1998/2002", 1998, Some(2002)),
+            ("1985-", "1985/..", 1985, None),
+            ("[2005?]-", "2005?/..", 2005, None),
+            ("[1998 or 1999]", "[1998,1999]", 1998, Some(1999)),
+            ("[between 1846 and 1853?]", "[1846..1853]", 1846, Some(1853)),
+            ("[2018], ©2017", "2018", 2018, Some(2018)),
+            ("MDCCLXXX [1780]", "1780", 1780, Some(1780)),
+        ];
+        for (input, edtf, start, end) in cases {
+            assert_eq!(statement(input), (edtf.to_string(), start, end), "{input}");
+        }
+        assert!(parse_date_statement("[date of publication not identified]").is_none());
+        assert!(parse_date_statement("12345").is_none());
+    }
+
+    #[test]
+    fn test_fixed_date_types() {
+        let s = |edtf: &str, start, end| Some((edtf.to_string(), start, end));
+        assert_eq!(fixed('s', "1998", "    "), s("1998", 1998, Some(1998)));
+        assert_eq!(fixed('s', "19uu", "    "), s("19XX", 1900, Some(1999)));
+        assert_eq!(fixed('r', "1990", "1850"), s("1990", 1990, Some(1990)));
+        assert_eq!(
+            fixed('e', "1998", "0512"),
+            s("1998-05-12", 1998, Some(1998))
+        );
+        assert_eq!(fixed('e', "1998", "05  "), s("1998-05", 1998, Some(1998)));
+        assert_eq!(
+            fixed('q', "1950", "1959"),
+            s("[1950..1959]", 1950, Some(1959))
+        );
+        assert_eq!(
+            fixed('q', "19uu", "1925"),
+            s("[19XX..1925]", 1900, Some(1925))
+        );
+        assert_eq!(fixed('d', "1950", "1975"), s("1950/1975", 1950, Some(1975)));
+        assert_eq!(fixed('m', "1950", "9999"), s("1950/..", 1950, None));
+        assert_eq!(fixed('u', "1950", "uuuu"), s("1950/", 1950, None));
+        assert_eq!(fixed('i', "1870", "    "), s("1870", 1870, Some(1870)));
+        assert_eq!(fixed('n', "uuuu", "uuuu"), None);
+        assert_eq!(fixed('s', "uuuu", "    "), None);
+        assert_eq!(fixed('|', "1998", "    "), None);
+    }
+
+    #[test]
+    fn test_publication_dates_falls_back_to_264() {
+        let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 i 4500").unwrap());
+        record.add_control_field_str("008", "240101nuuuuuuuunyu           000 0 eng d");
+        record.add_field(
+            Field::builder("264".to_string(), ' ', '4')
+                .subfield_str('c', "©2010")
+                .build(),
+        );
+        record.add_field(
+            Field::builder("264".to_string(), ' ', '1')
+                .subfield_str('c', "[2011]")
+                .build(),
+        );
+        assert_eq!(publication_dates(&record).unwrap().edtf, "2011");
+
+        record.control_fields.insert(
+            "008".to_string(),
+            vec!["240101s2012    nyu           000 0 eng d".to_string()],
+        );
+        assert_eq!(publication_dates(&record).unwrap().start, 2012);
+    }
+}
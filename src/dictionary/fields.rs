@@ -0,0 +1,1166 @@
+//! MARC 21 Bibliographic field and subfield definitions, as embedded
+//! lookup data for [`super::field`].
+//!
+//! Sorted by tag. Covers the fields most records carry rather than the
+//! whole format; each field listed has its complete set of subfields.
+
+use super::{FieldDefinition as F, SubfieldDefinition as S};
+
+const LINKAGE: S = S::nr('6', "Linkage");
+const FIELD_LINK: S = S::r('8', "Field link and sequence number");
+const AUTHORITY_ID: S = S::r('0', "Authority record control number or standard number");
+const RWO_URI: S = S::r('1', "Real World Object URI");
+const MATERIALS: S = S::nr('3', "Materials specified");
+const RELATIONSHIP: S = S::r('4', "Relationship");
+const INSTITUTION: S = S::nr('5', "Institution to which field applies");
+const HEADING_SOURCE: S = S::nr('2', "Source of heading or term");
+const FORM_SUBDIVISION: S = S::r('v', "Form subdivision");
+const GENERAL_SUBDIVISION: S = S::r('x', "General subdivision");
+const CHRONOLOGICAL_SUBDIVISION: S = S::r('y', "Chronological subdivision");
+const GEOGRAPHIC_SUBDIVISION: S = S::r('z', "Geographic subdivision");
+const URI: S = S::r('u', "Uniform Resource Identifier");
+
+/// Subfields shared by the 76X-78X linking entry fields.
+const LINKING_ENTRY: &[S] = &[
+    S::nr('a', "Main entry heading"),
+    S::nr('b', "Edition"),
+    S::nr('c', "Qualifying information"),
+    S::nr('d', "Place, publisher, and date of publication"),
+    S::r('g', "Related parts"),
+    S::nr('h', "Physical description"),
+    S::r('i', "Relationship information"),
+    S::r('k', "Series data for related item"),
+    S::nr('m', "Material-specific details"),
+    S::r('n', "Note"),
+    S::r('o', "Other item identifier"),
+    S::r('r', "Report number"),
+    S::nr('s', "Uniform title"),
+    S::nr('t', "Title"),
+    S::nr('u', "Standard Technical Report Number"),
+    S::r('w', "Record control number"),
+    S::nr('x', "International Standard Serial Number"),
+    S::nr('y', "CODEN designation"),
+    S::r('z', "International Standard Book Number"),
+    RELATIONSHIP,
+    LINKAGE,
+    S::nr('7', "Control subfield"),
+    FIELD_LINK,
+];
+
+/// Subfields of the 336/337/338 RDA type fields.
+const RDA_TYPE: &[S] = &[
+    S::r('a', "Term"),
+    S::r('b', "Code"),
+    AUTHORITY_ID,
+    RWO_URI,
+    S::nr('2', "Source"),
+    MATERIALS,
+    LINKAGE,
+    FIELD_LINK,
+];
+
+pub(super) const FIELDS: &[F] = &[
+    F::nr("001", "Control number", &[]),
+    F::nr("003", "Control number identifier", &[]),
+    F::nr("005", "Date and time of latest transaction", &[]),
+    F::r(
+        "006",
+        "Fixed-length data elements - additional material characteristics",
+        &[],
+    ),
+    F::r("007", "Physical description fixed field", &[]),
+    F::nr("008", "Fixed-length data elements", &[]),
+    F::nr(
+        "010",
+        "Library of Congress Control Number",
+        &[
+            S::nr('a', "LC control number"),
+            S::r('b', "NUCMC control number"),
+            S::r('z', "Canceled/invalid LC control number"),
+            FIELD_LINK,
+        ],
+    ),
+    F::r(
+        "015",
+        "National bibliography number",
+        &[
+            S::r('a', "National bibliography number"),
+            S::r('q', "Qualifying information"),
+            S::r('z', "Canceled/invalid national bibliography number"),
+            S::nr('2', "Source"),
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::r(
+        "016",
+        "National bibliographic agency control number",
+        &[
+            S::nr('a', "Record control number"),
+            S::r('z', "Canceled/invalid control number"),
+            S::nr('2', "Source"),
+            FIELD_LINK,
+        ],
+    ),
+    F::r(
+        "017",
+        "Copyright or legal deposit number",
+        &[
+            S::r('a', "Copyright or legal deposit number"),
+            S::nr('b', "Assigning agency"),
+            S::nr('d', "Date"),
+            S::nr('i', "Display text"),
+            S::r('z', "Canceled/invalid copyright or legal deposit number"),
+            S::nr('2', "Source"),
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::r(
+        "020",
+        "International Standard Book Number",
+        &[
+            S::nr('a', "International Standard Book Number"),
+            S::nr('c', "Terms of availability"),
+            S::r('q', "Qualifying information"),
+            S::r('z', "Canceled/invalid ISBN"),
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::r(
+        "022",
+        "International Standard Serial Number",
+        &[
+            S::nr('a', "International Standard Serial Number"),
+            S::nr('l', "ISSN-L"),
+            S::r('m', "Canceled ISSN-L"),
+            S::r('y', "Incorrect ISSN"),
+            S::r('z', "Canceled ISSN"),
+            AUTHORITY_ID,
+            RWO_URI,
+            S::nr('2', "Source"),
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::r(
+        "024",
+        "Other standard identifier",
+        &[
+            S::nr('a', "Standard number or code"),
+            S::nr('c', "Terms of availability"),
+            S::nr(
+                'd',
+                "Additional codes following the standard number or code",
+            ),
+            S::r('q', "Qualifying information"),
+            S::r('z', "Canceled/invalid standard number or code"),
+            S::nr('2', "Source of number or code"),
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::r(
+        "028",
+        "Publisher or distributor number",
+        &[
+            S::nr('a', "Publisher or distributor number"),
+            S::nr('b', "Source"),
+            S::r('q', "Qualifying information"),
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::r(
+        "035",
+        "System control number",
+        &[
+            S::nr('a', "System control number"),
+            S::r('z', "Canceled/invalid control number"),
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::nr(
+        "040",
+        "Cataloging source",
+        &[
+            S::nr('a', "Original cataloging agency"),
+            S::nr('b', "Language of cataloging"),
+            S::nr('c', "Transcribing agency"),
+            S::r('d', "Modifying agency"),
+            S::r('e', "Description conventions"),
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::r(
+        "041",
+        "Language code",
+        &[
+            S::r('a', "Language code of text/sound track or separate title"),
+            S::r('b', "Language code of summary or abstract"),
+            S::r('d', "Language code of sung or spoken text"),
+            S::r('e', "Language code of librettos"),
+            S::r('f', "Language code of table of contents"),
+            S::r(
+                'g',
+                "Language code of accompanying material other than librettos and transcripts",
+            ),
+            S::r('h', "Language code of original"),
+            S::r('i', "Language code of intertitles"),
+            S::r('j', "Language code of subtitles"),
+            S::r('k', "Language code of intermediate translations"),
+            S::r(
+                'm',
+                "Language code of original accompanying materials other than librettos",
+            ),
+            S::r('n', "Language code of original libretto"),
+            S::r('p', "Language code of captions"),
+            S::r('q', "Language code of accessible audio"),
+            S::r(
+                'r',
+                "Language code of accessible visual language (non-textual)",
+            ),
+            S::r('s', "Language code of transcripts"),
+            S::r(
+                't',
+                "Language code of accompanying transcripts for moving image materials",
+            ),
+            S::nr('2', "Source of code"),
+            MATERIALS,
+            LINKAGE,
+            S::r('7', "Data provenance"),
+            FIELD_LINK,
+        ],
+    ),
+    F::nr(
+        "042",
+        "Authentication code",
+        &[S::r('a', "Authentication code")],
+    ),
+    F::nr(
+        "043",
+        "Geographic area code",
+        &[
+            S::r('a', "Geographic area code"),
+            S::r('b', "Local GAC code"),
+            S::r('c', "ISO code"),
+            AUTHORITY_ID,
+            RWO_URI,
+            S::r('2', "Source of local code"),
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::nr(
+        "044",
+        "Country of publishing/producing entity code",
+        &[
+            S::r('a', "MARC country code"),
+            S::r('b', "Local subentity code"),
+            S::r('c', "ISO country code"),
+            S::r('2', "Source of local subentity code"),
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::r(
+        "050",
+        "Library of Congress call number",
+        &[
+            S::r('a', "Classification number"),
+            S::nr('b', "Item number"),
+            AUTHORITY_ID,
+            RWO_URI,
+            MATERIALS,
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::r(
+        "082",
+        "Dewey Decimal Classification number",
+        &[
+            S::r('a', "Classification number"),
+            S::nr('b', "Item number"),
+            S::nr('m', "Standard or optional designation"),
+            S::nr('q', "Assigning agency"),
+            AUTHORITY_ID,
+            RWO_URI,
+            S::nr('2', "Edition number"),
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::r(
+        "084",
+        "Other classification number",
+        &[
+            S::r('a', "Classification number"),
+            S::nr('b', "Item number"),
+            S::nr('q', "Assigning agency"),
+            AUTHORITY_ID,
+            RWO_URI,
+            S::nr('2', "Number source"),
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::nr(
+        "100",
+        "Main entry - personal name",
+        &[
+            S::nr('a', "Personal name"),
+            S::nr('b', "Numeration"),
+            S::r('c', "Titles and other words associated with a name"),
+            S::nr('d', "Dates associated with a name"),
+            S::r('e', "Relator term"),
+            S::nr('f', "Date of a work"),
+            S::r('g', "Miscellaneous information"),
+            S::r('j', "Attribution qualifier"),
+            S::r('k', "Form subheading"),
+            S::nr('l', "Language of a work"),
+            S::r('n', "Number of part/section of a work"),
+            S::r('p', "Name of part/section of a work"),
+            S::nr('q', "Fuller form of name"),
+            S::nr('t', "Title of a work"),
+            S::nr('u', "Affiliation"),
+            AUTHORITY_ID,
+            RWO_URI,
+            RELATIONSHIP,
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::nr(
+        "110",
+        "Main entry - corporate name",
+        &[
+            S::nr('a', "Corporate name or jurisdiction name as entry element"),
+            S::r('b', "Subordinate unit"),
+            S::r('c', "Location of meeting"),
+            S::r('d', "Date of meeting or treaty signing"),
+            S::r('e', "Relator term"),
+            S::nr('f', "Date of a work"),
+            S::r('g', "Miscellaneous information"),
+            S::r('k', "Form subheading"),
+            S::nr('l', "Language of a work"),
+            S::r('n', "Number of part/section/meeting"),
+            S::r('p', "Name of part/section of a work"),
+            S::nr('t', "Title of a work"),
+            S::nr('u', "Affiliation"),
+            AUTHORITY_ID,
+            RWO_URI,
+            RELATIONSHIP,
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::nr(
+        "111",
+        "Main entry - meeting name",
+        &[
+            S::nr('a', "Meeting name or jurisdiction name as entry element"),
+            S::r('c', "Location of meeting"),
+            S::r('d', "Date of meeting or treaty signing"),
+            S::r('e', "Subordinate unit"),
+            S::nr('f', "Date of a work"),
+            S::r('g', "Miscellaneous information"),
+            S::r('j', "Relator term"),
+            S::r('k', "Form subheading"),
+            S::nr('l', "Language of a work"),
+            S::r('n', "Number of part/section/meeting"),
+            S::r('p', "Name of part/section of a work"),
+            S::nr(
+                'q',
+                "Name of meeting following jurisdiction name entry element",
+            ),
+            S::nr('t', "Title of a work"),
+            S::nr('u', "Affiliation"),
+            AUTHORITY_ID,
+            RWO_URI,
+            RELATIONSHIP,
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::nr(
+        "130",
+        "Main entry - uniform title",
+        &[
+            S::nr('a', "Uniform title"),
+            S::r('d', "Date of treaty signing"),
+            S::nr('f', "Date of a work"),
+            S::r('g', "Miscellaneous information"),
+            S::nr('h', "Medium"),
+            S::r('k', "Form subheading"),
+            S::nr('l', "Language of a work"),
+            S::r('m', "Medium of performance for music"),
+            S::r('n', "Number of part/section of a work"),
+            S::nr('o', "Arranged statement for music"),
+            S::r('p', "Name of part/section of a work"),
+            S::nr('r', "Key for music"),
+            S::r('s', "Version"),
+            S::nr('t', "Title of a work"),
+            AUTHORITY_ID,
+            RWO_URI,
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::nr(
+        "240",
+        "Uniform title",
+        &[
+            S::nr('a', "Uniform title"),
+            S::r('d', "Date of treaty signing"),
+            S::nr('f', "Date of a work"),
+            S::r('g', "Miscellaneous information"),
+            S::nr('h', "Medium"),
+            S::r('k', "Form subheading"),
+            S::nr('l', "Language of a work"),
+            S::r('m', "Medium of performance for music"),
+            S::r('n', "Number of part/section of a work"),
+            S::nr('o', "Arranged statement for music"),
+            S::r('p', "Name of part/section of a work"),
+            S::nr('r', "Key for music"),
+            S::r('s', "Version"),
+            AUTHORITY_ID,
+            RWO_URI,
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::nr(
+        "245",
+        "Title statement",
+        &[
+            S::nr('a', "Title proper"),
+            S::nr('b', "Remainder of title"),
+            S::nr('c', "Statement of responsibility, etc."),
+            S::nr('f', "Inclusive dates"),
+            S::nr('g', "Bulk dates"),
+            S::nr('h', "Medium"),
+            S::r('k', "Form"),
+            S::r('n', "Number of part/section of a work"),
+            S::r('p', "Name of part/section of a work"),
+            S::nr('s', "Version"),
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::r(
+        "246",
+        "Varying form of title",
+        &[
+            S::nr('a', "Title proper/short title"),
+            S::nr('b', "Remainder of title"),
+            S::nr('f', "Date or sequential designation"),
+            S::r('g', "Miscellaneous information"),
+            S::nr('h', "Medium"),
+            S::nr('i', "Display text"),
+            S::r('n', "Number of part/section of a work"),
+            S::r('p', "Name of part/section of a work"),
+            INSTITUTION,
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::r(
+        "250",
+        "Edition statement",
+        &[
+            S::nr('a', "Edition statement"),
+            S::nr('b', "Remainder of edition statement"),
+            MATERIALS,
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::r(
+        "260",
+        "Publication, distribution, etc. (imprint)",
+        &[
+            S::r('a', "Place of publication, distribution, etc."),
+            S::r('b', "Name of publisher, distributor, etc."),
+            S::r('c', "Date of publication, distribution, etc."),
+            S::r('e', "Place of manufacture"),
+            S::r('f', "Manufacturer"),
+            S::r('g', "Date of manufacture"),
+            MATERIALS,
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::r(
+        "264",
+        "Production, publication, distribution, manufacture, and copyright notice",
+        &[
+            S::r(
+                'a',
+                "Place of production, publication, distribution, manufacture",
+            ),
+            S::r(
+                'b',
+                "Name of producer, publisher, distributor, manufacturer",
+            ),
+            S::r(
+                'c',
+                "Date of production, publication, distribution, manufacture, or copyright notice",
+            ),
+            MATERIALS,
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::r(
+        "300",
+        "Physical description",
+        &[
+            S::r('a', "Extent"),
+            S::nr('b', "Other physical details"),
+            S::r('c', "Dimensions"),
+            S::nr('e', "Accompanying material"),
+            S::r('f', "Type of unit"),
+            S::r('g', "Size of unit"),
+            MATERIALS,
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::r("336", "Content type", RDA_TYPE),
+    F::r("337", "Media type", RDA_TYPE),
+    F::r("338", "Carrier type", RDA_TYPE),
+    F::r(
+        "490",
+        "Series statement",
+        &[
+            S::r('a', "Series statement"),
+            S::nr('l', "Library of Congress call number"),
+            S::r('v', "Volume/sequential designation"),
+            S::r('x', "International Standard Serial Number"),
+            S::r('y', "Incorrect ISSN"),
+            S::r('z', "Canceled ISSN"),
+            MATERIALS,
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::r(
+        "500",
+        "General note",
+        &[
+            S::nr('a', "General note"),
+            MATERIALS,
+            INSTITUTION,
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::r(
+        "502",
+        "Dissertation note",
+        &[
+            S::nr('a', "Dissertation note"),
+            S::nr('b', "Degree type"),
+            S::nr('c', "Name of granting institution"),
+            S::nr('d', "Year degree granted"),
+            S::r('g', "Miscellaneous information"),
+            S::r('o', "Dissertation identifier"),
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::r(
+        "504",
+        "Bibliography, etc. note",
+        &[
+            S::nr('a', "Bibliography, etc. note"),
+            S::nr('b', "Number of references"),
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::r(
+        "505",
+        "Formatted contents note",
+        &[
+            S::nr('a', "Formatted contents note"),
+            S::r('g', "Miscellaneous information"),
+            S::r('r', "Statement of responsibility"),
+            S::r('t', "Title"),
+            URI,
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::r(
+        "520",
+        "Summary, etc.",
+        &[
+            S::nr('a', "Summary, etc."),
+            S::nr('b', "Expansion of summary note"),
+            S::nr('c', "Assigning source"),
+            URI,
+            S::nr('2', "Source"),
+            MATERIALS,
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::r(
+        "530",
+        "Additional physical form available note",
+        &[
+            S::nr('a', "Additional physical form available note"),
+            S::nr('b', "Availability source"),
+            S::nr('c', "Availability conditions"),
+            S::nr('d', "Order number"),
+            URI,
+            MATERIALS,
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::r(
+        "533",
+        "Reproduction note",
+        &[
+            S::nr('a', "Type of reproduction"),
+            S::r('b', "Place of reproduction"),
+            S::r('c', "Agency responsible for reproduction"),
+            S::nr('d', "Date of reproduction"),
+            S::nr('e', "Physical description of reproduction"),
+            S::r('f', "Series statement of reproduction"),
+            S::r(
+                'm',
+                "Dates and/or sequential designation of issues reproduced",
+            ),
+            S::r('n', "Note about reproduction"),
+            MATERIALS,
+            INSTITUTION,
+            LINKAGE,
+            S::nr('7', "Fixed-length data elements of reproduction"),
+            FIELD_LINK,
+        ],
+    ),
+    F::r(
+        "538",
+        "System details note",
+        &[
+            S::nr('a', "System details note"),
+            S::nr('i', "Display text"),
+            URI,
+            MATERIALS,
+            INSTITUTION,
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::r(
+        "546",
+        "Language note",
+        &[
+            S::nr('a', "Language note"),
+            S::r('b', "Information code or alphabet"),
+            MATERIALS,
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::r(
+        "588",
+        "Source of description note",
+        &[
+            S::nr('a', "Source of description note"),
+            INSTITUTION,
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::r(
+        "600",
+        "Subject added entry - personal name",
+        &[
+            S::nr('a', "Personal name"),
+            S::nr('b', "Numeration"),
+            S::r('c', "Titles and other words associated with a name"),
+            S::nr('d', "Dates associated with a name"),
+            S::r('e', "Relator term"),
+            S::nr('f', "Date of a work"),
+            S::r('g', "Miscellaneous information"),
+            S::nr('h', "Medium"),
+            S::r('j', "Attribution qualifier"),
+            S::r('k', "Form subheading"),
+            S::nr('l', "Language of a work"),
+            S::r('m', "Medium of performance for music"),
+            S::r('n', "Number of part/section of a work"),
+            S::nr('o', "Arranged statement for music"),
+            S::r('p', "Name of part/section of a work"),
+            S::nr('q', "Fuller form of name"),
+            S::nr('r', "Key for music"),
+            S::nr('s', "Version"),
+            S::nr('t', "Title of a work"),
+            S::nr('u', "Affiliation"),
+            FORM_SUBDIVISION,
+            GENERAL_SUBDIVISION,
+            CHRONOLOGICAL_SUBDIVISION,
+            GEOGRAPHIC_SUBDIVISION,
+            AUTHORITY_ID,
+            RWO_URI,
+            HEADING_SOURCE,
+            MATERIALS,
+            RELATIONSHIP,
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::r(
+        "610",
+        "Subject added entry - corporate name",
+        &[
+            S::nr('a', "Corporate name or jurisdiction name as entry element"),
+            S::r('b', "Subordinate unit"),
+            S::r('c', "Location of meeting"),
+            S::r('d', "Date of meeting or treaty signing"),
+            S::r('e', "Relator term"),
+            S::nr('f', "Date of a work"),
+            S::r('g', "Miscellaneous information"),
+            S::nr('h', "Medium"),
+            S::r('k', "Form subheading"),
+            S::nr('l', "Language of a work"),
+            S::r('m', "Medium of performance for music"),
+            S::r('n', "Number of part/section/meeting"),
+            S::nr('o', "Arranged statement for music"),
+            S::r('p', "Name of part/section of a work"),
+            S::nr('r', "Key for music"),
+            S::nr('s', "Version"),
+            S::nr('t', "Title of a work"),
+            S::nr('u', "Affiliation"),
+            FORM_SUBDIVISION,
+            GENERAL_SUBDIVISION,
+            CHRONOLOGICAL_SUBDIVISION,
+            GEOGRAPHIC_SUBDIVISION,
+            AUTHORITY_ID,
+            RWO_URI,
+            HEADING_SOURCE,
+            MATERIALS,
+            RELATIONSHIP,
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::r(
+        "611",
+        "Subject added entry - meeting name",
+        &[
+            S::nr('a', "Meeting name or jurisdiction name as entry element"),
+            S::r('c', "Location of meeting"),
+            S::r('d', "Date of meeting or treaty signing"),
+            S::r('e', "Subordinate unit"),
+            S::nr('f', "Date of a work"),
+            S::r('g', "Miscellaneous information"),
+            S::nr('h', "Medium"),
+            S::r('j', "Relator term"),
+            S::r('k', "Form subheading"),
+            S::nr('l', "Language of a work"),
+            S::r('n', "Number of part/section/meeting"),
+            S::r('p', "Name of part/section of a work"),
+            S::nr(
+                'q',
+                "Name of meeting following jurisdiction name entry element",
+            ),
+            S::nr('s', "Version"),
+            S::nr('t', "Title of a work"),
+            S::nr('u', "Affiliation"),
+            FORM_SUBDIVISION,
+            GENERAL_SUBDIVISION,
+            CHRONOLOGICAL_SUBDIVISION,
+            GEOGRAPHIC_SUBDIVISION,
+            AUTHORITY_ID,
+            RWO_URI,
+            HEADING_SOURCE,
+            MATERIALS,
+            RELATIONSHIP,
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::r(
+        "630",
+        "Subject added entry - uniform title",
+        &[
+            S::nr('a', "Uniform title"),
+            S::r('d', "Date of treaty signing"),
+            S::r('e', "Relator term"),
+            S::nr('f', "Date of a work"),
+            S::r('g', "Miscellaneous information"),
+            S::nr('h', "Medium"),
+            S::r('k', "Form subheading"),
+            S::nr('l', "Language of a work"),
+            S::r('m', "Medium of performance for music"),
+            S::r('n', "Number of part/section of a work"),
+            S::nr('o', "Arranged statement for music"),
+            S::r('p', "Name of part/section of a work"),
+            S::nr('r', "Key for music"),
+            S::r('s', "Version"),
+            S::nr('t', "Title of a work"),
+            FORM_SUBDIVISION,
+            GENERAL_SUBDIVISION,
+            CHRONOLOGICAL_SUBDIVISION,
+            GEOGRAPHIC_SUBDIVISION,
+            AUTHORITY_ID,
+            RWO_URI,
+            HEADING_SOURCE,
+            MATERIALS,
+            RELATIONSHIP,
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::r(
+        "650",
+        "Subject added entry - topical term",
+        &[
+            S::nr('a', "Topical term or geographic name entry element"),
+            S::nr('b', "Topical term following geographic name entry element"),
+            S::nr('c', "Location of event"),
+            S::nr('d', "Active dates"),
+            S::r('e', "Relator term"),
+            S::r('g', "Miscellaneous information"),
+            FORM_SUBDIVISION,
+            GENERAL_SUBDIVISION,
+            CHRONOLOGICAL_SUBDIVISION,
+            GEOGRAPHIC_SUBDIVISION,
+            AUTHORITY_ID,
+            RWO_URI,
+            HEADING_SOURCE,
+            MATERIALS,
+            RELATIONSHIP,
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::r(
+        "651",
+        "Subject added entry - geographic name",
+        &[
+            S::nr('a', "Geographic name"),
+            S::r('e', "Relator term"),
+            S::r('g', "Miscellaneous information"),
+            FORM_SUBDIVISION,
+            GENERAL_SUBDIVISION,
+            CHRONOLOGICAL_SUBDIVISION,
+            GEOGRAPHIC_SUBDIVISION,
+            AUTHORITY_ID,
+            RWO_URI,
+            HEADING_SOURCE,
+            MATERIALS,
+            RELATIONSHIP,
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::r(
+        "655",
+        "Index term - genre/form",
+        &[
+            S::nr('a', "Genre/form data or focus term"),
+            S::r('b', "Non-focus term"),
+            S::r('c', "Facet/hierarchy designation"),
+            FORM_SUBDIVISION,
+            GENERAL_SUBDIVISION,
+            CHRONOLOGICAL_SUBDIVISION,
+            GEOGRAPHIC_SUBDIVISION,
+            AUTHORITY_ID,
+            RWO_URI,
+            S::nr('2', "Source of term"),
+            MATERIALS,
+            INSTITUTION,
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::r(
+        "700",
+        "Added entry - personal name",
+        &[
+            S::nr('a', "Personal name"),
+            S::nr('b', "Numeration"),
+            S::r('c', "Titles and other words associated with a name"),
+            S::nr('d', "Dates associated with a name"),
+            S::r('e', "Relator term"),
+            S::nr('f', "Date of a work"),
+            S::r('g', "Miscellaneous information"),
+            S::nr('h', "Medium"),
+            S::r('i', "Relationship information"),
+            S::r('j', "Attribution qualifier"),
+            S::r('k', "Form subheading"),
+            S::nr('l', "Language of a work"),
+            S::r('m', "Medium of performance for music"),
+            S::r('n', "Number of part/section of a work"),
+            S::nr('o', "Arranged statement for music"),
+            S::r('p', "Name of part/section of a work"),
+            S::nr('q', "Fuller form of name"),
+            S::nr('r', "Key for music"),
+            S::r('s', "Version"),
+            S::nr('t', "Title of a work"),
+            S::nr('u', "Affiliation"),
+            S::nr('x', "International Standard Serial Number"),
+            AUTHORITY_ID,
+            RWO_URI,
+            MATERIALS,
+            RELATIONSHIP,
+            INSTITUTION,
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::r(
+        "710",
+        "Added entry - corporate name",
+        &[
+            S::nr('a', "Corporate name or jurisdiction name as entry element"),
+            S::r('b', "Subordinate unit"),
+            S::r('c', "Location of meeting"),
+            S::r('d', "Date of meeting or treaty signing"),
+            S::r('e', "Relator term"),
+            S::nr('f', "Date of a work"),
+            S::r('g', "Miscellaneous information"),
+            S::nr('h', "Medium"),
+            S::r('i', "Relationship information"),
+            S::r('k', "Form subheading"),
+            S::nr('l', "Language of a work"),
+            S::r('m', "Medium of performance for music"),
+            S::r('n', "Number of part/section/meeting"),
+            S::nr('o', "Arranged statement for music"),
+            S::r('p', "Name of part/section of a work"),
+            S::nr('r', "Key for music"),
+            S::r('s', "Version"),
+            S::nr('t', "Title of a work"),
+            S::nr('u', "Affiliation"),
+            S::nr('x', "International Standard Serial Number"),
+            AUTHORITY_ID,
+            RWO_URI,
+            MATERIALS,
+            RELATIONSHIP,
+            INSTITUTION,
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::r(
+        "711",
+        "Added entry - meeting name",
+        &[
+            S::nr('a', "Meeting name or jurisdiction name as entry element"),
+            S::r('c', "Location of meeting"),
+            S::r('d', "Date of meeting or treaty signing"),
+            S::r('e', "Subordinate unit"),
+            S::nr('f', "Date of a work"),
+            S::r('g', "Miscellaneous information"),
+            S::nr('h', "Medium"),
+            S::r('i', "Relationship information"),
+            S::r('j', "Relator term"),
+            S::r('k', "Form subheading"),
+            S::nr('l', "Language of a work"),
+            S::r('n', "Number of part/section/meeting"),
+            S::r('p', "Name of part/section of a work"),
+            S::nr(
+                'q',
+                "Name of meeting following jurisdiction name entry element",
+            ),
+            S::r('s', "Version"),
+            S::nr('t', "Title of a work"),
+            S::nr('u', "Affiliation"),
+            S::nr('x', "International Standard Serial Number"),
+            AUTHORITY_ID,
+            RWO_URI,
+            MATERIALS,
+            RELATIONSHIP,
+            INSTITUTION,
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::r(
+        "730",
+        "Added entry - uniform title",
+        &[
+            S::nr('a', "Uniform title"),
+            S::r('d', "Date of treaty signing"),
+            S::nr('f', "Date of a work"),
+            S::r('g', "Miscellaneous information"),
+            S::nr('h', "Medium"),
+            S::r('i', "Relationship information"),
+            S::r('k', "Form subheading"),
+            S::nr('l', "Language of a work"),
+            S::r('m', "Medium of performance for music"),
+            S::r('n', "Number of part/section of a work"),
+            S::nr('o', "Arranged statement for music"),
+            S::r('p', "Name of part/section of a work"),
+            S::nr('r', "Key for music"),
+            S::r('s', "Version"),
+            S::nr('t', "Title of a work"),
+            S::nr('x', "International Standard Serial Number"),
+            AUTHORITY_ID,
+            RWO_URI,
+            MATERIALS,
+            RELATIONSHIP,
+            INSTITUTION,
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::r(
+        "740",
+        "Added entry - uncontrolled related/analytical title",
+        &[
+            S::nr('a', "Uncontrolled related/analytical title"),
+            S::nr('h', "Medium"),
+            S::r('n', "Number of part/section of a work"),
+            S::r('p', "Name of part/section of a work"),
+            INSTITUTION,
+            LINKAGE,
+            FIELD_LINK,
+        ],
+    ),
+    F::r(
+        "773",
+        "Host item entry",
+        &[
+            S::nr('a', "Main entry heading"),
+            S::nr('b', "Edition"),
+            S::nr('d', "Place, publisher, and date of publication"),
+            S::r('g', "Related parts"),
+            S::nr('h', "Physical description"),
+            S::r('i', "Relationship information"),
+            S::r('k', "Series data for related item"),
+            S::nr('m', "Material-specific details"),
+            S::r('n', "Note"),
+            S::r('o', "Other item identifier"),
+            S::nr('p', "Abbreviated title"),
+            S::nr('q', "Enumeration and first page"),
+            S::r('r', "Report number"),
+            S::nr('s', "Uniform title"),
+            S::nr('t', "Title"),
+            S::nr('u', "Standard Technical Report Number"),
+            S::r('w', "Record control number"),
+            S::nr('x', "International Standard Serial Number"),
+            S::nr('y', "CODEN designation"),
+            S::r('z', "International Standard Book Number"),
+            MATERIALS,
+            RELATIONSHIP,
+            LINKAGE,
+            S::nr('7', "Control subfield"),
+            FIELD_LINK,
+        ],
+    ),
+    F::r("776", "Additional physical form entry", LINKING_ENTRY),
+    F::r("780", "Preceding entry", LINKING_ENTRY),
+    F::r("785", "Succeeding entry", LINKING_ENTRY),
+    F::r(
+        "800",
+        "Series added entry - personal name",
+        &[
+            S::nr('a', "Personal name"),
+            S::nr('b', "Numeration"),
+            S::r('c', "Titles and other words associated with a name"),
+            S::nr('d', "Dates associated with a name"),
+            S::r('e', "Relator term"),
+            S::nr('f', "Date of a work"),
+            S::r('g', "Miscellaneous information"),
+            S::nr('h', "Medium"),
+            S::r('j', "Attribution qualifier"),
+            S::r('k', "Form subheading"),
+            S::nr('l', "Language of a work"),
+            S::r('m', "Medium of performance for music"),
+            S::r('n', "Number of part/section of a work"),
+            S::nr('o', "Arranged statement for music"),
+            S::r('p', "Name of part/section of a work"),
+            S::nr('q', "Fuller form of name"),
+            S::nr('r', "Key for music"),
+            S::r('s', "Version"),
+            S::nr('t', "Title of a work"),
+            S::nr('u', "Affiliation"),
+            S::nr('v', "Volume/sequential designation"),
+            S::r('w', "Bibliographic record control number"),
+            S::nr('x', "International Standard Serial Number"),
+            AUTHORITY_ID,
+            RWO_URI,
+            MATERIALS,
+            RELATIONSHIP,
+            INSTITUTION,
+            LINKAGE,
+            S::nr('7', "Control subfield"),
+            FIELD_LINK,
+        ],
+    ),
+    F::r(
+        "830",
+        "Series added entry - uniform title",
+        &[
+            S::nr('a', "Uniform title"),
+            S::r('d', "Date of treaty signing"),
+            S::nr('f', "Date of a work"),
+            S::r('g', "Miscellaneous information"),
+            S::nr('h', "Medium"),
+            S::r('k', "Form subheading"),
+            S::nr('l', "Language of a work"),
+            S::r('m', "Medium of performance for music"),
+            S::r('n', "Number of part/section of a work"),
+            S::nr('o', "Arranged statement for music"),
+            S::r('p', "Name of part/section of a work"),
+            S::nr('r', "Key for music"),
+            S::r('s', "Version"),
+            S::nr('t', "Title of a work"),
+            S::nr('v', "Volume/sequential designation"),
+            S::r('w', "Bibliographic record control number"),
+            S::nr('x', "International Standard Serial Number"),
+            AUTHORITY_ID,
+            RWO_URI,
+            MATERIALS,
+            INSTITUTION,
+            LINKAGE,
+            S::nr('7', "Control subfield"),
+            FIELD_LINK,
+        ],
+    ),
+    F::r(
+        "856",
+        "Electronic location and access",
+        &[
+            S::r('a', "Host name"),
+            S::r('b', "Access number"),
+            S::r('c', "Compression information"),
+            S::r('d', "Path"),
+            S::r('f', "Electronic name"),
+            S::nr('h', "Processor of request"),
+            S::r('i', "Instruction"),
+            S::nr('j', "Bits per second"),
+            S::nr('k', "Password"),
+            S::nr('l', "Logon"),
+            S::r('m', "Contact for access assistance"),
+            S::nr('n', "Name of location of host"),
+            S::nr('o', "Operating system"),
+            S::nr('p', "Port"),
+            S::nr('q', "Electronic format type"),
+            S::nr('r', "Settings"),
+            S::r('s', "File size"),
+            S::r('t', "Terminal emulation"),
+            URI,
+            S::r('v', "Hours access method available"),
+            S::r('w', "Record control number"),
+            S::r('x', "Nonpublic note"),
+            S::r('y', "Link text"),
+            S::r('z', "Public note"),
+            S::nr('2', "Access method"),
+            MATERIALS,
+            LINKAGE,
+            S::nr('7', "Access status"),
+            FIELD_LINK,
+        ],
+    ),
+];
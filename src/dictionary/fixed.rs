@@ -0,0 +1,116 @@
+//! MARC 21 Bibliographic leader and 008 character positions, as embedded
+//! lookup data for [`super::leader_positions`] and
+//! [`super::positions_008`].
+//!
+//! 008/18-34 depend on the type of material; the other positions are the
+//! same for every record.
+
+use super::PositionDefinition as P;
+use crate::codes::CodeList;
+
+pub(super) const LEADER: &[P] = &[
+    P::span(0, 4, "Record length"),
+    P::at(5, "Record status"),
+    P::at(6, "Type of record"),
+    P::at(7, "Bibliographic level"),
+    P::at(8, "Type of control"),
+    P::at(9, "Character coding scheme"),
+    P::at(10, "Indicator count"),
+    P::at(11, "Subfield code count"),
+    P::span(12, 16, "Base address of data"),
+    P::at(17, "Encoding level"),
+    P::at(18, "Descriptive cataloging form"),
+    P::at(19, "Multipart resource record level"),
+    P::span(20, 23, "Entry map"),
+];
+
+pub(super) const ALL_START: &[P] = &[
+    P::span(0, 5, "Date entered on file"),
+    P::at(6, "Type of date/publication status"),
+    P::span(7, 10, "Date 1"),
+    P::span(11, 14, "Date 2"),
+    P::coded(
+        15,
+        17,
+        "Place of publication, production, or execution",
+        CodeList::Country,
+    ),
+];
+
+pub(super) const ALL_END: &[P] = &[
+    P::coded(35, 37, "Language", CodeList::Language),
+    P::at(38, "Modified record"),
+    P::at(39, "Cataloging source"),
+];
+
+pub(super) const BOOKS: &[P] = &[
+    P::span(18, 21, "Illustrations"),
+    P::at(22, "Target audience"),
+    P::at(23, "Form of item"),
+    P::span(24, 27, "Nature of contents"),
+    P::at(28, "Government publication"),
+    P::at(29, "Conference publication"),
+    P::at(30, "Festschrift"),
+    P::at(31, "Index"),
+    P::at(33, "Literary form"),
+    P::at(34, "Biography"),
+];
+
+pub(super) const CONTINUING_RESOURCES: &[P] = &[
+    P::at(18, "Frequency"),
+    P::at(19, "Regularity"),
+    P::at(21, "Type of continuing resource"),
+    P::at(22, "Form of original item"),
+    P::at(23, "Form of item"),
+    P::at(24, "Nature of entire work"),
+    P::span(25, 27, "Nature of contents"),
+    P::at(28, "Government publication"),
+    P::at(29, "Conference publication"),
+    P::at(33, "Original alphabet or script of title"),
+    P::at(34, "Entry convention"),
+];
+
+pub(super) const MUSIC: &[P] = &[
+    P::span(18, 19, "Form of composition"),
+    P::at(20, "Format of music"),
+    P::at(21, "Music parts"),
+    P::at(22, "Target audience"),
+    P::at(23, "Form of item"),
+    P::span(24, 29, "Accompanying matter"),
+    P::span(30, 31, "Literary text for sound recordings"),
+    P::at(33, "Transposition and arrangement"),
+];
+
+pub(super) const MAPS: &[P] = &[
+    P::span(18, 21, "Relief"),
+    P::span(22, 23, "Projection"),
+    P::at(25, "Type of cartographic material"),
+    P::at(28, "Government publication"),
+    P::at(29, "Form of item"),
+    P::at(31, "Index"),
+    P::span(33, 34, "Special format characteristics"),
+];
+
+pub(super) const VISUAL_MATERIALS: &[P] = &[
+    P::span(
+        18,
+        20,
+        "Running time for motion pictures and videorecordings",
+    ),
+    P::at(22, "Target audience"),
+    P::at(28, "Government publication"),
+    P::at(29, "Form of item"),
+    P::at(33, "Type of visual material"),
+    P::at(34, "Technique"),
+];
+
+pub(super) const COMPUTER_FILES: &[P] = &[
+    P::at(22, "Target audience"),
+    P::at(23, "Form of item"),
+    P::at(26, "Type of computer file"),
+    P::at(28, "Government publication"),
+];
+
+pub(super) const MIXED_MATERIALS: &[P] = &[P::at(23, "Form of item")];
+
+pub(super) const UNKNOWN_MATERIAL: &[P] = &[P::span(18, 34, "Material specific coded elements")];
@@ -0,0 +1,417 @@
+//! Embedded MARC 21 Bibliographic dictionary: field, subfield, leader and
+//! 008 position names with repeatability.
+//!
+//! [`explain`] (also [`Record::explain`]) uses it to print a record with a
+//! label on every element, for training and for checking conversions, and
+//! [`RecordStructureValidator::dictionary_issues`] uses it as a schema for
+//! repeatability and subfield codes.
+//!
+//! The dictionary covers the fields most records carry, not the whole
+//! format; [`field`] returns `None` for the rest, and local fields (9XX
+//! and X9X) are never listed.
+//!
+//! [`RecordStructureValidator::dictionary_issues`]: crate::RecordStructureValidator::dictionary_issues
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::{Field, Leader, Record, dictionary};
+//!
+//! let title = dictionary::field("245").unwrap();
+//! assert_eq!(title.name, "Title statement");
+//! assert!(!title.repeatable);
+//! assert_eq!(title.subfield('a').unwrap().name, "Title proper");
+//!
+//! let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+//! record.add_control_field_str("008", "920219s1993    nyu           000 0 eng d");
+//! record.add_field(
+//!     Field::builder("245".to_string(), '1', '0')
+//!         .subfield_str('a', "Cats /")
+//!         .build(),
+//! );
+//! let explained = record.explain();
+//! assert!(explained.contains("008/35-37 Language: eng (English)"));
+//! assert!(explained.contains("245 $a    Title proper: Cats /"));
+//! ```
+
+mod fields;
+mod fixed;
+
+use std::fmt::Write;
+
+use crate::codes::CodeList;
+use crate::field_linkage::LinkageInfo;
+use crate::leader::Leader;
+use crate::record::{Field, Record};
+
+/// A field in the dictionary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldDefinition {
+    /// Field tag
+    pub tag: &'static str,
+    /// Field name
+    pub name: &'static str,
+    /// Whether the field may occur more than once in a record
+    pub repeatable: bool,
+    /// Defined subfields; empty for control fields
+    pub subfields: &'static [SubfieldDefinition],
+}
+
+impl FieldDefinition {
+    const fn nr(
+        tag: &'static str,
+        name: &'static str,
+        subfields: &'static [SubfieldDefinition],
+    ) -> Self {
+        FieldDefinition {
+            tag,
+            name,
+            repeatable: false,
+            subfields,
+        }
+    }
+
+    const fn r(
+        tag: &'static str,
+        name: &'static str,
+        subfields: &'static [SubfieldDefinition],
+    ) -> Self {
+        FieldDefinition {
+            tag,
+            name,
+            repeatable: true,
+            subfields,
+        }
+    }
+
+    /// The definition of subfield `code`, if the field defines it.
+    #[must_use]
+    pub fn subfield(&self, code: char) -> Option<&'static SubfieldDefinition> {
+        self.subfields.iter().find(|subfield| subfield.code == code)
+    }
+}
+
+/// A subfield of a [`FieldDefinition`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SubfieldDefinition {
+    /// Subfield code
+    pub code: char,
+    /// Subfield name
+    pub name: &'static str,
+    /// Whether the subfield may occur more than once in a field
+    pub repeatable: bool,
+}
+
+impl SubfieldDefinition {
+    const fn nr(code: char, name: &'static str) -> Self {
+        SubfieldDefinition {
+            code,
+            name,
+            repeatable: false,
+        }
+    }
+
+    const fn r(code: char, name: &'static str) -> Self {
+        SubfieldDefinition {
+            code,
+            name,
+            repeatable: true,
+        }
+    }
+}
+
+/// A character position, or run of positions, in the leader or 008.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PositionDefinition {
+    /// First position
+    pub start: usize,
+    /// Last position (equal to `start` for a single character)
+    pub end: usize,
+    /// Element name
+    pub name: &'static str,
+    /// Code list the value is drawn from, when it has labels
+    pub codes: Option<CodeList>,
+}
+
+impl PositionDefinition {
+    const fn at(position: usize, name: &'static str) -> Self {
+        Self::span(position, position, name)
+    }
+
+    const fn span(start: usize, end: usize, name: &'static str) -> Self {
+        PositionDefinition {
+            start,
+            end,
+            name,
+            codes: None,
+        }
+    }
+
+    const fn coded(start: usize, end: usize, name: &'static str, codes: CodeList) -> Self {
+        PositionDefinition {
+            start,
+            end,
+            name,
+            codes: Some(codes),
+        }
+    }
+
+    /// The positions as written in MARC documentation: `06` or `35-37`.
+    #[must_use]
+    pub fn positions(&self) -> String {
+        if self.start == self.end {
+            format!("{:02}", self.start)
+        } else {
+            format!("{:02}-{:02}", self.start, self.end)
+        }
+    }
+
+    /// This element's characters in `value`, if `value` is long enough.
+    #[must_use]
+    pub fn extract<'a>(&self, value: &'a str) -> Option<&'a str> {
+        value.get(self.start..=self.end)
+    }
+}
+
+/// All fields in the dictionary, sorted by tag.
+#[must_use]
+pub fn fields() -> &'static [FieldDefinition] {
+    fields::FIELDS
+}
+
+/// The definition of field `tag`, if the dictionary has one.
+#[must_use]
+pub fn field(tag: &str) -> Option<&'static FieldDefinition> {
+    fields::FIELDS
+        .binary_search_by(|field| field.tag.cmp(tag))
+        .ok()
+        .map(|index| &fields::FIELDS[index])
+}
+
+/// The definition of subfield `code` of field `tag`.
+#[must_use]
+pub fn subfield(tag: &str, code: char) -> Option<&'static SubfieldDefinition> {
+    field(tag)?.subfield(code)
+}
+
+/// Leader character positions.
+#[must_use]
+pub fn leader_positions() -> &'static [PositionDefinition] {
+    fixed::LEADER
+}
+
+/// 008 character positions for the type of material `leader` describes:
+/// 00-17 and 35-39 are shared, 18-34 depend on leader/06 and /07.
+#[must_use]
+pub fn positions_008(leader: &Leader) -> Vec<&'static PositionDefinition> {
+    let material = match leader.record_type {
+        'a' | 't' if matches!(leader.bibliographic_level, 'b' | 'i' | 's') => {
+            fixed::CONTINUING_RESOURCES
+        },
+        'a' | 't' => fixed::BOOKS,
+        'c' | 'd' | 'i' | 'j' => fixed::MUSIC,
+        'e' | 'f' => fixed::MAPS,
+        'g' | 'k' | 'o' | 'r' => fixed::VISUAL_MATERIALS,
+        'm' => fixed::COMPUTER_FILES,
+        'p' => fixed::MIXED_MATERIALS,
+        _ => fixed::UNKNOWN_MATERIAL,
+    };
+    fixed::ALL_START
+        .iter()
+        .chain(material)
+        .chain(fixed::ALL_END)
+        .collect()
+}
+
+/// Whether `tag` is a local field (9XX or X9X), which the dictionary
+/// leaves undefined.
+#[must_use]
+pub fn is_local(tag: &str) -> bool {
+    let bytes = tag.as_bytes();
+    bytes.len() == 3 && (bytes[0] == b'9' || bytes[1] == b'9')
+}
+
+/// Width of the label column in [`explain`] output.
+const LABEL_WIDTH: usize = 10;
+
+/// Render `record` one element per line, each labeled from the
+/// dictionary.
+///
+/// The leader and 008 are broken into their character positions
+/// (`008/35-37 Language: eng (English)`), data fields get a heading line
+/// with their indicators and one line per subfield (`245 $a Title proper:
+/// ...`), and relator codes in `$4` are spelled out. An 880 is labeled
+/// from the field its `$6` links to. Blanks in coded values are shown as
+/// `#`, as in MARC documentation.
+#[must_use]
+pub fn explain(record: &Record) -> String {
+    let mut out = String::new();
+    let leader = record.leader.to_string();
+    line(&mut out, "LDR", "Leader", &leader);
+    explain_positions(&mut out, "LDR", fixed::LEADER.iter(), &leader);
+
+    for (tag, values) in &record.control_fields {
+        let name = field(tag).map_or(unknown_field(tag), |field| field.name);
+        for value in values {
+            line(&mut out, tag, name, value);
+            if tag == "008" {
+                explain_positions(&mut out, tag, positions_008(&record.leader), value);
+            }
+        }
+    }
+
+    for (tag, fields) in &record.fields {
+        for data_field in fields {
+            explain_field(&mut out, tag, data_field);
+        }
+    }
+    out
+}
+
+fn explain_positions<'a>(
+    out: &mut String,
+    tag: &str,
+    positions: impl IntoIterator<Item = &'a PositionDefinition>,
+    value: &str,
+) {
+    for position in positions {
+        let Some(code) = position.extract(value) else {
+            continue;
+        };
+        let mut shown = code.replace(' ', "#");
+        if let Some(name) = position.codes.and_then(|list| list.name(code)) {
+            let _ = write!(shown, " ({name})");
+        }
+        line(
+            out,
+            &format!("{tag}/{}", position.positions()),
+            position.name,
+            &shown,
+        );
+    }
+}
+
+fn explain_field(out: &mut String, tag: &str, data_field: &Field) {
+    // An 880 carries the content designation of the field it links to.
+    let linked = (tag == "880")
+        .then(|| data_field.get_subfield('6'))
+        .flatten()
+        .and_then(LinkageInfo::parse)
+        .map(|info| info.tag);
+    let definition = field(linked.as_deref().unwrap_or(tag));
+    let name = match (&linked, definition) {
+        (Some(linked), Some(definition)) => {
+            format!(
+                "Alternate graphic representation of {linked} {}",
+                definition.name
+            )
+        },
+        (None, Some(definition)) => definition.name.to_string(),
+        (_, None) if tag == "880" => "Alternate graphic representation".to_string(),
+        (_, None) => unknown_field(tag).to_string(),
+    };
+    let indicators =
+        format!("{}{}", data_field.indicator1, data_field.indicator2).replace(' ', "#");
+    let _ = writeln!(out, "{:<LABEL_WIDTH$}{name}", format!("{tag} {indicators}"));
+
+    for subfield in &data_field.subfields {
+        let name = definition
+            .and_then(|definition| definition.subfield(subfield.code))
+            .map_or("(not in dictionary)", |subfield| subfield.name);
+        let mut value = subfield.value.clone();
+        if subfield.code == '4'
+            && let Some(relator) = crate::codes::relator_name(&value)
+        {
+            let _ = write!(value, " ({relator})");
+        }
+        line(out, &format!("{tag} ${}", subfield.code), name, &value);
+    }
+}
+
+fn unknown_field(tag: &str) -> &'static str {
+    if is_local(tag) {
+        "Local field"
+    } else {
+        "(not in dictionary)"
+    }
+}
+
+fn line(out: &mut String, label: &str, name: &str, value: &str) {
+    let _ = writeln!(out, "{label:<LABEL_WIDTH$}{name}: {value}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fields_sorted_with_unique_subfields() {
+        for pair in fields().windows(2) {
+            assert!(pair[0].tag < pair[1].tag, "{} out of order", pair[1].tag);
+        }
+        for definition in fields() {
+            let mut codes: Vec<char> = definition.subfields.iter().map(|s| s.code).collect();
+            codes.sort_unstable();
+            codes.dedup();
+            assert_eq!(
+                codes.len(),
+                definition.subfields.len(),
+                "{}",
+                definition.tag
+            );
+        }
+    }
+
+    #[test]
+    fn test_positions_008_by_material() {
+        let books = Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap();
+        let serial = Leader::from_bytes(b"00000nas a2200000 a 4500").unwrap();
+        let name = |leader: &Leader, start| {
+            positions_008(leader)
+                .into_iter()
+                .find(|p| p.start == start)
+                .map(|p| p.name)
+        };
+        assert_eq!(name(&books, 33), Some("Literary form"));
+        assert_eq!(name(&serial, 18), Some("Frequency"));
+        assert_eq!(name(&serial, 35), Some("Language"));
+    }
+
+    #[test]
+    fn test_explain_labels_fields_and_880() {
+        let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+        record.add_control_field_str("001", "ocm1");
+        record.add_field(
+            Field::builder("100".to_string(), '1', ' ')
+                .subfield_str('a', "Cat, A.,")
+                .subfield_str('4', "ill")
+                .subfield_str('6', "880-01")
+                .build(),
+        );
+        record.add_field(
+            Field::builder("880".to_string(), '1', ' ')
+                .subfield_str('6', "100-01/(N")
+                .subfield_str('a', "Кот")
+                .build(),
+        );
+        record.add_field(
+            Field::builder("949".to_string(), ' ', ' ')
+                .subfield_str('a', "local")
+                .build(),
+        );
+        let explained = explain(&record);
+        let lines: Vec<&str> = explained.lines().collect();
+        assert_eq!(lines[0], "LDR       Leader: 00000nam a2200000 a 4500");
+        assert!(lines.contains(&"LDR/07    Bibliographic level: m"));
+        assert!(lines.contains(&"LDR/08    Type of control: #"));
+        assert!(lines.contains(&"001       Control number: ocm1"));
+        assert!(lines.contains(&"100 1#    Main entry - personal name"));
+        assert!(lines.contains(&"100 $4    Relationship: ill (Illustrator)"));
+        assert!(lines.contains(
+            &"880 1#    Alternate graphic representation of 100 Main entry - personal name"
+        ));
+        assert!(lines.contains(&"880 $a    Personal name: Кот"));
+        assert!(lines.contains(&"949 ##    Local field"));
+        assert!(lines.contains(&"949 $a    (not in dictionary): local"));
+    }
+}
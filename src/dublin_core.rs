@@ -59,8 +59,10 @@
 //! ```
 
 use std::fmt::Write;
+use std::sync::LazyLock;
 
-use crate::error::Result;
+use crate::crosswalk::Crosswalk;
+use crate::error::{MarcError, Result};
 use crate::record::Record;
 
 /// Dublin Core metadata record
@@ -100,7 +102,8 @@ pub struct DublinCoreRecord {
 
 /// Convert a MARC record to Dublin Core metadata.
 ///
-/// Maps MARC fields to Dublin Core elements based on standard crosswalks.
+/// Maps MARC fields to Dublin Core elements using the built-in
+/// [`Crosswalk::dublin_core`] mappings.
 /// This function returns an intermediate `DublinCoreRecord` struct that can be
 /// serialized to XML using [`dublin_core_to_xml()`] or used directly for programmatic
 /// access to the 15 Dublin Core elements.
@@ -128,23 +131,64 @@ pub struct DublinCoreRecord {
 ///
 /// Returns an error if the record cannot be converted.
 pub fn record_to_dublin_core(record: &Record) -> Result<DublinCoreRecord> {
-    let mut dc = DublinCoreRecord::default();
-
-    extract_titles(record, &mut dc);
-    extract_creators(record, &mut dc);
-    extract_subjects(record, &mut dc);
-    extract_descriptions(record, &mut dc);
-    extract_publishers_and_dates(record, &mut dc);
-    extract_contributors(record, &mut dc);
-    extract_identifiers(record, &mut dc);
-    extract_language(record, &mut dc);
-    extract_formats(record, &mut dc);
-    extract_coverage(record, &mut dc);
-    extract_rights(record, &mut dc);
+    static DEFAULT: LazyLock<Crosswalk> = LazyLock::new(Crosswalk::dublin_core);
+    record_to_dublin_core_with(record, &DEFAULT)
+}
 
+/// Convert a MARC record to Dublin Core metadata using `crosswalk`.
+///
+/// Element names in the crosswalk are the fifteen Dublin Core element names
+/// (`title`, `creator`, ..., `rights`). Use
+/// [`Crosswalk::dublin_core`] with [`Crosswalk::with_overrides`] to adjust
+/// the default mappings.
+///
+/// # Errors
+///
+/// Returns [`MarcError::InvalidField`] if the crosswalk maps an element that
+/// is not a Dublin Core element.
+pub fn record_to_dublin_core_with(
+    record: &Record,
+    crosswalk: &Crosswalk,
+) -> Result<DublinCoreRecord> {
+    crate::telemetry::stage_span!("mrrc.convert", from = "marc", to = "dublin_core");
+    let mut dc = DublinCoreRecord::default();
+    for (element, values) in crosswalk.apply(record) {
+        dc.element_mut(&element)
+            .ok_or_else(|| {
+                MarcError::invalid_field_msg(format!(
+                    "Crosswalk '{}' maps unknown Dublin Core element '{element}'",
+                    crosswalk.name
+                ))
+            })?
+            .extend(values);
+    }
     Ok(dc)
 }
 
+impl DublinCoreRecord {
+    /// The values of the element named `name`.
+    fn element_mut(&mut self, name: &str) -> Option<&mut Vec<String>> {
+        Some(match name {
+            "title" => &mut self.title,
+            "creator" => &mut self.creator,
+            "subject" => &mut self.subject,
+            "description" => &mut self.description,
+            "publisher" => &mut self.publisher,
+            "contributor" => &mut self.contributor,
+            "date" => &mut self.date,
+            "type" => &mut self.dc_type,
+            "format" => &mut self.format,
+            "identifier" => &mut self.identifier,
+            "source" => &mut self.source,
+            "language" => &mut self.language,
+            "relation" => &mut self.relation,
+            "coverage" => &mut self.coverage,
+            "rights" => &mut self.rights,
+            _ => return None,
+        })
+    }
+}
+
 /// Convert a MARC record directly to Dublin Core XML format.
 ///
 /// Convenience function that combines [`record_to_dublin_core()`] and [`dublin_core_to_xml()`]
@@ -178,189 +222,6 @@ pub fn record_to_dublin_core_xml(record: &Record) -> Result<String> {
     Ok(dublin_core_to_xml(&dc))
 }
 
-fn extract_titles(record: &Record, dc: &mut DublinCoreRecord) {
-    if let Some(fields_245) = record.fields.get("245") {
-        for field in fields_245 {
-            let mut title_parts = Vec::new();
-            for subfield in &field.subfields {
-                match subfield.code {
-                    'a' | 'b' | 'c' => title_parts.push(subfield.value.clone()),
-                    _ => {},
-                }
-            }
-            if !title_parts.is_empty() {
-                dc.title.push(title_parts.join(" "));
-            }
-        }
-    }
-}
-
-fn extract_creators(record: &Record, dc: &mut DublinCoreRecord) {
-    // Main entry - personal name (100)
-    if let Some(fields) = record.fields.get("100") {
-        for field in fields {
-            if let Some(subfield) = field.subfields.iter().find(|s| s.code == 'a') {
-                dc.creator.push(subfield.value.clone());
-            }
-        }
-    }
-
-    // Main entry - corporate name (110)
-    if let Some(fields) = record.fields.get("110") {
-        for field in fields {
-            if let Some(subfield) = field.subfields.iter().find(|s| s.code == 'a') {
-                dc.creator.push(subfield.value.clone());
-            }
-        }
-    }
-}
-
-fn extract_subjects(record: &Record, dc: &mut DublinCoreRecord) {
-    // Subject - personal name (600)
-    if let Some(fields) = record.fields.get("600") {
-        for field in fields {
-            if let Some(subfield) = field.subfields.iter().find(|s| s.code == 'a') {
-                dc.subject.push(subfield.value.clone());
-            }
-        }
-    }
-
-    // Subject - corporate name (610)
-    if let Some(fields) = record.fields.get("610") {
-        for field in fields {
-            if let Some(subfield) = field.subfields.iter().find(|s| s.code == 'a') {
-                dc.subject.push(subfield.value.clone());
-            }
-        }
-    }
-
-    // Subject - topical term (650)
-    if let Some(fields) = record.fields.get("650") {
-        for field in fields {
-            if let Some(subfield) = field.subfields.iter().find(|s| s.code == 'a') {
-                dc.subject.push(subfield.value.clone());
-            }
-        }
-    }
-}
-
-fn extract_descriptions(record: &Record, dc: &mut DublinCoreRecord) {
-    // Summary (520)
-    if let Some(fields) = record.fields.get("520") {
-        for field in fields {
-            if let Some(subfield) = field.subfields.iter().find(|s| s.code == 'a') {
-                dc.description.push(subfield.value.clone());
-            }
-        }
-    }
-
-    // General note (500)
-    if let Some(fields) = record.fields.get("500") {
-        for field in fields {
-            if let Some(subfield) = field.subfields.iter().find(|s| s.code == 'a') {
-                dc.description.push(subfield.value.clone());
-            }
-        }
-    }
-}
-
-fn extract_publishers_and_dates(record: &Record, dc: &mut DublinCoreRecord) {
-    if let Some(fields) = record.fields.get("260") {
-        for field in fields {
-            for subfield in &field.subfields {
-                match subfield.code {
-                    'a' => dc.publisher.push(subfield.value.clone()),
-                    'c' => dc.date.push(subfield.value.clone()),
-                    _ => {},
-                }
-            }
-        }
-    }
-}
-
-fn extract_contributors(record: &Record, dc: &mut DublinCoreRecord) {
-    // Added entry - personal name (700)
-    if let Some(fields) = record.fields.get("700") {
-        for field in fields {
-            if let Some(subfield) = field.subfields.iter().find(|s| s.code == 'a') {
-                dc.contributor.push(subfield.value.clone());
-            }
-        }
-    }
-
-    // Added entry - corporate name (710)
-    if let Some(fields) = record.fields.get("710") {
-        for field in fields {
-            if let Some(subfield) = field.subfields.iter().find(|s| s.code == 'a') {
-                dc.contributor.push(subfield.value.clone());
-            }
-        }
-    }
-}
-
-fn extract_identifiers(record: &Record, dc: &mut DublinCoreRecord) {
-    // ISBN (020)
-    if let Some(fields) = record.fields.get("020") {
-        for field in fields {
-            if let Some(subfield) = field.subfields.iter().find(|s| s.code == 'a') {
-                dc.identifier.push(format!("ISBN: {}", subfield.value));
-            }
-        }
-    }
-
-    // Control number (001)
-    if let Some(control_001) = record.control_fields.get("001").and_then(|v| v.first()) {
-        dc.identifier.push(format!("Control#: {control_001}"));
-    }
-}
-
-fn extract_language(record: &Record, dc: &mut DublinCoreRecord) {
-    if let Some(fields) = record.fields.get("041") {
-        for field in fields {
-            for subfield in &field.subfields {
-                if subfield.code == 'a' {
-                    let langs: Vec<&str> = subfield.value.split_whitespace().collect();
-                    for lang in langs {
-                        if !lang.is_empty() {
-                            dc.language.push(lang.to_string());
-                        }
-                    }
-                }
-            }
-        }
-    }
-}
-
-fn extract_formats(record: &Record, dc: &mut DublinCoreRecord) {
-    if let Some(fields) = record.fields.get("300") {
-        for field in fields {
-            if let Some(subfield) = field.subfields.iter().find(|s| s.code == 'a') {
-                dc.format.push(subfield.value.clone());
-            }
-        }
-    }
-}
-
-fn extract_coverage(record: &Record, dc: &mut DublinCoreRecord) {
-    if let Some(fields) = record.fields.get("651") {
-        for field in fields {
-            if let Some(subfield) = field.subfields.iter().find(|s| s.code == 'a') {
-                dc.coverage.push(subfield.value.clone());
-            }
-        }
-    }
-}
-
-fn extract_rights(record: &Record, dc: &mut DublinCoreRecord) {
-    if let Some(fields) = record.fields.get("540") {
-        for field in fields {
-            if let Some(subfield) = field.subfields.iter().find(|s| s.code == 'a') {
-                dc.rights.push(subfield.value.clone());
-            }
-        }
-    }
-}
-
 /// Serialize Dublin Core record to XML format.
 ///
 /// Produces RDF/XML serialization compatible with the Dublin Core vocabulary.
@@ -554,4 +415,27 @@ mod tests {
         let dc = record_to_dublin_core(&record).expect("Failed to convert");
         assert!(dc.description.iter().any(|d| d.contains("summary")));
     }
+
+    #[test]
+    fn test_custom_crosswalk() {
+        let mut record = Record::new(make_test_leader());
+        let mut field = Field::new("246".to_string(), '3', '0');
+        field.add_subfield('a', "Other title".to_string());
+        record.add_field(field);
+        let mut field = Field::new("540".to_string(), ' ', ' ');
+        field.add_subfield('a', "Restricted".to_string());
+        record.add_field(field);
+
+        let overrides = Crosswalk::new("local")
+            .mapping(crate::crosswalk::Mapping::new("title", "245$a | 246$a").unwrap())
+            .omit("rights");
+        let crosswalk = Crosswalk::dublin_core().with_overrides(overrides);
+        let dc = record_to_dublin_core_with(&record, &crosswalk).expect("Failed to convert");
+        assert_eq!(dc.title, vec!["Other title"]);
+        assert!(dc.rights.is_empty());
+
+        let unknown = Crosswalk::new("bad")
+            .mapping(crate::crosswalk::Mapping::new("alternative", "246$a").unwrap());
+        assert!(record_to_dublin_core_with(&record, &unknown).is_err());
+    }
 }
@@ -11,8 +11,12 @@
 //! This module provides automatic encoding detection and conversion, including full
 //! support for MARC-8 escape sequences and character set switching.
 
+use std::borrow::Cow;
+use std::fmt::Write as _;
+
 use crate::error::{MarcError, Result};
 use crate::marc8_tables::{CharacterSetId, get_charset_table};
+use crate::record::Record;
 
 /// Character encoding for MARC records.
 ///
@@ -415,6 +419,169 @@ fn encode_marc8(s: &str) -> Result<Vec<u8>> {
     Ok(bytes)
 }
 
+/// How text and XML writers treat control characters in field data.
+///
+/// A record decoded under the wrong leader/09, or built from raw MARC-8
+/// strings, can carry the ESC (0x1B) that starts a MARC-8 escape sequence,
+/// or other C0 control characters. XML 1.0 cannot represent them at all and
+/// line-based formats misread them, so writers never pass them through:
+/// they reject, escape or decode them as chosen here. Tab, line feed and
+/// carriage return are not affected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ControlCharacters {
+    /// Fail with [`MarcError::EncodingError`] naming the field
+    #[default]
+    Reject,
+    /// Write each control character as `{XX}` hex notation, and `{` itself
+    /// as `{7B}` so the notation is unambiguous; [`restore_control_characters`]
+    /// reverses it after reading
+    Hex,
+    /// Decode MARC-8 escape sequences to Unicode; control characters outside
+    /// an escape sequence are still rejected
+    Decode,
+}
+
+impl ControlCharacters {
+    /// Prepare a value from field `tag` for writing.
+    ///
+    /// Values without control characters (or, for [`Hex`](Self::Hex),
+    /// braces) are returned borrowed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MarcError::EncodingError`] for a control character under
+    /// [`Reject`](Self::Reject), and under [`Decode`](Self::Decode) for a
+    /// control character outside an escape sequence or an escape sequence
+    /// that does not decode.
+    pub fn apply<'a>(self, tag: &str, value: &'a str) -> Result<Cow<'a, str>> {
+        let needs_hex = |c: char| is_control(c) || c == '{';
+        match self {
+            ControlCharacters::Hex if value.contains(needs_hex) => {
+                let mut out = String::with_capacity(value.len() + 8);
+                for c in value.chars() {
+                    if needs_hex(c) {
+                        let _ = write!(out, "{{{:02X}}}", u32::from(c));
+                    } else {
+                        out.push(c);
+                    }
+                }
+                Ok(Cow::Owned(out))
+            },
+            ControlCharacters::Decode if value.contains('\x1B') => {
+                let decoded = decode_escape_sequences(tag, value)?;
+                reject_controls(tag, &decoded)?;
+                Ok(Cow::Owned(decoded))
+            },
+            ControlCharacters::Reject | ControlCharacters::Decode => {
+                reject_controls(tag, value)?;
+                Ok(Cow::Borrowed(value))
+            },
+            ControlCharacters::Hex => Ok(Cow::Borrowed(value)),
+        }
+    }
+}
+
+/// C0 control characters other than tab, line feed and carriage return.
+fn is_control(c: char) -> bool {
+    c < ' ' && !matches!(c, '\t' | '\n' | '\r')
+}
+
+fn reject_controls(tag: &str, value: &str) -> Result<()> {
+    match value.chars().find(|&c| is_control(c)) {
+        None => Ok(()),
+        Some(c) => Err(MarcError::EncodingError {
+            record_index: None,
+            byte_offset: None,
+            source_name: None,
+            record_control_number: None,
+            field_tag: Some(tag.to_string()),
+            message: format!(
+                "control character U+{:04X} in field data cannot be written; \
+                 choose ControlCharacters::Hex or ControlCharacters::Decode",
+                u32::from(c)
+            ),
+            bytes_near: None,
+        }),
+    }
+}
+
+/// Decode each run from an ESC up to the next return to Basic Latin
+/// (`ESC s` or `ESC ( B`) as MARC-8 bytes.
+fn decode_escape_sequences(tag: &str, value: &str) -> Result<String> {
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find('\x1B') {
+        out.push_str(&rest[..start]);
+        let run = &rest[start..];
+        let end = ["\x1Bs", "\x1B(B"]
+            .iter()
+            .filter_map(|reset| run[1..].find(reset).map(|i| 1 + i + reset.len()))
+            .min()
+            .unwrap_or(run.len());
+        let bytes = run[..end]
+            .chars()
+            .map(u8::try_from)
+            .collect::<std::result::Result<Vec<u8>, _>>()
+            .map_err(|_| {
+                MarcError::encoding_msg(format!(
+                    "field {tag}: escape sequence contains non-MARC-8 characters"
+                ))
+            })?;
+        let decoded = decode_marc8(&bytes)
+            .map_err(|e| MarcError::encoding_msg(format!("field {tag}: {e}")))?;
+        out.push_str(&decoded);
+        rest = &run[end..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Reverse [`ControlCharacters::Hex`] in every control field and subfield
+/// value of `record`, after reading it back from a text or XML format.
+///
+/// `{XX}` with two hex digits becomes the character it names; any other
+/// brace is left as it is.
+pub fn restore_control_characters(record: &mut Record) {
+    for values in record.control_fields.values_mut() {
+        for value in values {
+            if let Cow::Owned(restored) = restore_hex(value) {
+                *value = restored;
+            }
+        }
+    }
+    for field in record.fields_mut() {
+        for subfield in &mut field.subfields {
+            if let Cow::Owned(restored) = restore_hex(&subfield.value) {
+                subfield.value = restored;
+            }
+        }
+    }
+}
+
+fn restore_hex(value: &str) -> Cow<'_, str> {
+    if !value.contains('{') {
+        return Cow::Borrowed(value);
+    }
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let candidate = rest.get(start..start + 4);
+        let code = candidate
+            .filter(|c| c.ends_with('}'))
+            .and_then(|c| u8::from_str_radix(&c[1..3], 16).ok());
+        if let Some(code) = code {
+            out.push(char::from(code));
+            rest = &rest[start + 4..];
+        } else {
+            out.push('{');
+            rest = &rest[start + 1..];
+        }
+    }
+    out.push_str(rest);
+    Cow::Owned(out)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -859,4 +1026,34 @@ mod tests {
         );
         assert!(decoded.contains('A'), "Should contain ASCII 'A'");
     }
+
+    #[test]
+    fn test_control_characters_modes() {
+        let raw = "H\x1Bb2\x1BsO {x}";
+        let err = ControlCharacters::Reject.apply("245", raw).unwrap_err();
+        assert!(err.to_string().contains("U+001B"), "{err}");
+
+        let hex = ControlCharacters::Hex.apply("245", raw).unwrap();
+        assert_eq!(hex, "H{1B}b2{1B}sO {7B}x}");
+        let mut record =
+            crate::Record::new(crate::Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+        let mut field = crate::Field::new("245".to_string(), '0', '0');
+        field.add_subfield('a', hex.into_owned());
+        record.add_field(field);
+        restore_control_characters(&mut record);
+        assert_eq!(
+            record.get_field("245").unwrap().get_subfield('a'),
+            Some(raw)
+        );
+
+        let decoded = ControlCharacters::Decode.apply("245", raw).unwrap();
+        assert_eq!(decoded, "H\u{2082}O {x}");
+        assert!(ControlCharacters::Decode.apply("245", "a\x07b").is_err());
+        assert!(matches!(
+            ControlCharacters::Reject
+                .apply("245", "plain\ttext")
+                .unwrap(),
+            Cow::Borrowed(_)
+        ));
+    }
 }
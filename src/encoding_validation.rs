@@ -3,10 +3,26 @@
 //! This module provides tools for detecting and validating character encodings
 //! in MARC records, including support for mixed-encoding records and encoding
 //! consistency checks.
+//!
+//! [`EncodingValidator::diagnose`] and [`EncodingValidator::diagnose_bytes`]
+//! locate character-level problems by field tag, subfield code and byte
+//! index, and [`EncodingValidator::repair`] rewrites them with a log of
+//! every change.
+//!
+//! [`CharsetAudit`] scans a stream of undecoded records and counts MARC-8
+//! escape sequences, G0/G1 character set designations and characters
+//! outside each record's declared coding, per record and in total.
+
+use std::collections::BTreeMap;
+use std::io::Read;
+
+use serde::Serialize;
 
 use crate::encoding::MarcEncoding;
 use crate::error::{MarcError, Result};
+use crate::marc8_tables::{CharacterSetId, get_charset_table, get_eacc_character};
 use crate::record::Record;
+use crate::record_index::read_raw_record;
 
 /// Result of encoding validation analysis
 #[non_exhaustive]
@@ -27,6 +43,337 @@ pub enum EncodingAnalysis {
     Undetermined,
 }
 
+/// Kind of problem reported by [`EncodingValidator::diagnose`]
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EncodingIssueKind {
+    /// Bytes that do not form valid UTF-8 in a record declared as UTF-8
+    InvalidUtf8,
+    /// U+FFFD left behind by an earlier lossy conversion
+    ReplacementCharacter,
+    /// MARC-8 escape sequence surviving in decoded data
+    Marc8Escape,
+    /// C0 or C1 control character; C1 characters are usually Windows-1252
+    /// punctuation decoded as Latin-1
+    ControlCharacter,
+    /// Unicode noncharacter (U+FDD0–U+FDEF or U+xxFFFE/U+xxFFFF)
+    Noncharacter,
+    /// Combining mark at the start of a value, ahead of its base letter as
+    /// MARC-8 orders diacritics
+    LeadingCombiningMark,
+}
+
+impl EncodingIssueKind {
+    fn description(self) -> &'static str {
+        match self {
+            EncodingIssueKind::InvalidUtf8 => "invalid UTF-8",
+            EncodingIssueKind::ReplacementCharacter => "replacement character",
+            EncodingIssueKind::Marc8Escape => "MARC-8 escape sequence",
+            EncodingIssueKind::ControlCharacter => "control character",
+            EncodingIssueKind::Noncharacter => "Unicode noncharacter",
+            EncodingIssueKind::LeadingCombiningMark => "combining mark before its base",
+        }
+    }
+}
+
+/// A character-level encoding problem, located down to the byte
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncodingIssue {
+    /// Field tag
+    pub tag: String,
+    /// Subfield code, or `None` for a control field
+    pub subfield_code: Option<char>,
+    /// Byte index of the problem within the field or subfield value
+    pub byte_index: usize,
+    /// Offset of the problem from the start of the record, when diagnosed
+    /// from raw bytes
+    pub record_offset: Option<usize>,
+    /// The offending byte sequence
+    pub bytes: Vec<u8>,
+    /// What is wrong with it
+    pub kind: EncodingIssueKind,
+}
+
+impl std::fmt::Display for EncodingIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.tag)?;
+        if let Some(code) = self.subfield_code {
+            write!(f, " ${code}")?;
+        }
+        write!(f, " byte {}:", self.byte_index)?;
+        for byte in &self.bytes {
+            write!(f, " {byte:02X}")?;
+        }
+        write!(f, " ({})", self.kind.description())
+    }
+}
+
+/// How [`EncodingValidator::repair`] rewrites problem characters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RepairMode {
+    /// Substitute U+FFFD for each problem, leaving misplaced combining
+    /// marks alone
+    #[default]
+    Replace,
+    /// Best guess at the intended text: C1 controls become their
+    /// Windows-1252 characters, tabs and line breaks become spaces, other
+    /// controls and MARC-8 escapes are dropped, and leading combining marks
+    /// move after their base letter. Anything without a guess gets U+FFFD.
+    Transliterate,
+}
+
+/// One change made by [`EncodingValidator::repair`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncodingRepair {
+    /// The problem that was repaired; `byte_index` refers to the value
+    /// before the repair
+    pub issue: EncodingIssue,
+    /// Text that replaced [`EncodingIssue::bytes`]
+    pub replacement: String,
+}
+
+/// A character that does not belong to a record's declared coding, as
+/// counted by [`EncodingValidator::charset_usage`]
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub enum OutsideCoding {
+    /// Bytes that do not form valid UTF-8 in a record declared as UTF-8
+    InvalidUtf8,
+    /// MARC-8 escape sequence in a record declared as UTF-8
+    Marc8Escape,
+    /// MARC-8 escape sequence with an unknown or missing final character
+    UnknownEscape,
+    /// MARC-8 byte with no character in the active G0 or G1 set
+    UnmappedMarc8,
+    /// UTF-8 multibyte character in a record declared as MARC-8, counted
+    /// in fields whose bytes are all valid UTF-8 and hold no escapes
+    Utf8InMarc8,
+    /// C0 control other than ESC, DEL, or a C1 control
+    ControlCharacter,
+}
+
+impl OutsideCoding {
+    fn description(self) -> &'static str {
+        match self {
+            OutsideCoding::InvalidUtf8 => "invalid UTF-8",
+            OutsideCoding::Marc8Escape => "MARC-8 escape in UTF-8 record",
+            OutsideCoding::UnknownEscape => "unknown escape sequence",
+            OutsideCoding::UnmappedMarc8 => "unmapped MARC-8 character",
+            OutsideCoding::Utf8InMarc8 => "UTF-8 in MARC-8 record",
+            OutsideCoding::ControlCharacter => "control character",
+        }
+    }
+}
+
+impl std::fmt::Display for OutsideCoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.description())
+    }
+}
+
+/// Character set usage counted in one record or, summed, in a stream
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct CharsetUsage {
+    /// Escape sequences by their bytes, written as `ESC ( N`
+    pub escape_sequences: BTreeMap<String, usize>,
+    /// G0 designations by character set name
+    pub g0_sets: BTreeMap<String, usize>,
+    /// G1 designations by character set name
+    pub g1_sets: BTreeMap<String, usize>,
+    /// Characters outside the declared coding by kind
+    pub outside_coding: BTreeMap<OutsideCoding, usize>,
+    /// Fields holding an escape sequence or a character outside the
+    /// declared coding, by tag
+    pub tags: BTreeMap<String, usize>,
+}
+
+impl CharsetUsage {
+    /// Number of escape sequences
+    #[must_use]
+    pub fn escape_count(&self) -> usize {
+        self.escape_sequences.values().sum()
+    }
+
+    /// Number of characters outside the declared coding
+    #[must_use]
+    pub fn outside_count(&self) -> usize {
+        self.outside_coding.values().sum()
+    }
+
+    /// Whether there are no escape sequences and no characters outside the
+    /// declared coding
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.marked_count() == 0
+    }
+
+    fn marked_count(&self) -> usize {
+        self.escape_count() + self.outside_count()
+    }
+
+    /// Add `other`'s counts to these
+    pub fn merge(&mut self, other: &CharsetUsage) {
+        fn add<K: Ord + Clone>(into: &mut BTreeMap<K, usize>, from: &BTreeMap<K, usize>) {
+            for (key, count) in from {
+                *into.entry(key.clone()).or_default() += count;
+            }
+        }
+        add(&mut self.escape_sequences, &other.escape_sequences);
+        add(&mut self.g0_sets, &other.g0_sets);
+        add(&mut self.g1_sets, &other.g1_sets);
+        add(&mut self.outside_coding, &other.outside_coding);
+        add(&mut self.tags, &other.tags);
+    }
+}
+
+/// [`CharsetUsage`] of one record in a [`CharsetAudit`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RecordCharsetUsage {
+    /// Zero-based position of the record in the stream
+    pub position: usize,
+    /// The record's 001, if any
+    pub control_number: Option<String>,
+    /// Leader/09 as found
+    pub declared_coding: char,
+    /// What the record holds
+    pub usage: CharsetUsage,
+}
+
+/// Character set statistics for a stream of ISO 2709 records, for
+/// planning a MARC-8 to UTF-8 conversion
+///
+/// Works on undecoded bytes, so escape sequences and stray bytes are seen
+/// as written. Per-record usage is kept only for records that are not
+/// [clean](CharsetUsage::is_clean); every record counts toward the totals.
+///
+/// # Examples
+///
+/// ```
+/// use mrrc::encoding_validation::CharsetAudit;
+/// use mrrc::{Leader, MarcWriter, Record};
+///
+/// let mut record = Record::new(Leader::from_bytes(b"00000nam  2200000 a 4500").unwrap());
+/// record.add_control_field_str("001", "ocm1");
+/// let mut bytes = Vec::new();
+/// MarcWriter::new(&mut bytes).write_record(&record)?;
+///
+/// let audit = CharsetAudit::scan(bytes.as_slice())?;
+/// assert_eq!(audit.records, 1);
+/// assert!(audit.totals.is_clean());
+/// # Ok::<(), mrrc::MarcError>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct CharsetAudit {
+    /// Records scanned
+    pub records: usize,
+    /// Records by leader/09
+    pub declared_coding: BTreeMap<char, usize>,
+    /// Records with at least one escape sequence
+    pub records_with_escapes: usize,
+    /// Records with at least one character outside the declared coding
+    pub records_outside_coding: usize,
+    /// Usage summed over every record
+    pub totals: CharsetUsage,
+    /// Usage of each record that is not clean, in stream order
+    pub per_record: Vec<RecordCharsetUsage>,
+}
+
+impl CharsetAudit {
+    /// An empty audit
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Audit every record read from `source`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error on I/O failure, a malformed leader, or a record
+    /// cut short by the end of input.
+    pub fn scan<R: Read>(mut source: R) -> Result<Self> {
+        let mut audit = Self::new();
+        let mut offset = 0u64;
+        while let Some(raw) = read_raw_record(&mut source, offset)? {
+            offset += raw.len() as u64;
+            audit.add(&raw);
+        }
+        Ok(audit)
+    }
+
+    /// Add one undecoded record to the audit
+    pub fn add(&mut self, raw: &[u8]) {
+        let position = self.records;
+        self.records += 1;
+        let declared = raw.get(9).map_or(' ', |&b| char::from(b));
+        *self.declared_coding.entry(declared).or_default() += 1;
+        let usage = EncodingValidator::charset_usage(raw);
+        if usage.is_clean() {
+            return;
+        }
+        if usage.escape_count() > 0 {
+            self.records_with_escapes += 1;
+        }
+        if usage.outside_count() > 0 {
+            self.records_outside_coding += 1;
+        }
+        self.totals.merge(&usage);
+        let control_number = raw_fields(raw)
+            .into_iter()
+            .find(|(tag, _, _)| tag == "001")
+            .map(|(_, _, data)| String::from_utf8_lossy(data).trim().to_string());
+        self.per_record.push(RecordCharsetUsage {
+            position,
+            control_number,
+            declared_coding: declared,
+            usage,
+        });
+    }
+}
+
+impl std::fmt::Display for CharsetAudit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "records: {}", self.records)?;
+        for (coding, count) in &self.declared_coding {
+            writeln!(f, "  leader/09 '{coding}': {count}")?;
+        }
+        writeln!(
+            f,
+            "records with escape sequences: {}",
+            self.records_with_escapes
+        )?;
+        writeln!(
+            f,
+            "records with characters outside declared coding: {}",
+            self.records_outside_coding
+        )?;
+        let sections: [(&str, Vec<(String, usize)>); 5] = [
+            ("escape sequences", counts(&self.totals.escape_sequences)),
+            ("G0 sets", counts(&self.totals.g0_sets)),
+            ("G1 sets", counts(&self.totals.g1_sets)),
+            (
+                "outside declared coding",
+                counts(&self.totals.outside_coding),
+            ),
+            ("fields by tag", counts(&self.totals.tags)),
+        ];
+        for (title, rows) in sections {
+            if rows.is_empty() {
+                continue;
+            }
+            writeln!(f, "{title}:")?;
+            for (key, count) in rows {
+                writeln!(f, "  {key}: {count}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn counts<K: std::fmt::Display>(map: &BTreeMap<K, usize>) -> Vec<(String, usize)> {
+    map.iter().map(|(k, &v)| (k.to_string(), v)).collect()
+}
+
 /// Validator for MARC record encodings
 #[derive(Debug)]
 pub struct EncodingValidator;
@@ -177,6 +524,165 @@ impl EncodingValidator {
             )),
         }
     }
+
+    /// List character-level problems in a record's control fields and
+    /// subfields, in field order
+    ///
+    /// Values are already decoded, so this finds what decoding left behind:
+    /// replacement characters, surviving MARC-8 escapes, control
+    /// characters, noncharacters and combining marks ahead of their base.
+    /// Use [`EncodingValidator::diagnose_bytes`] to find invalid UTF-8
+    /// before it is decoded away.
+    #[must_use]
+    pub fn diagnose(record: &Record) -> Vec<EncodingIssue> {
+        let mut issues = Vec::new();
+        for (tag, values) in &record.control_fields {
+            for value in values {
+                for (byte_index, bytes, kind) in scan_value(value) {
+                    issues.push(EncodingIssue {
+                        tag: tag.clone(),
+                        subfield_code: None,
+                        byte_index,
+                        record_offset: None,
+                        bytes,
+                        kind,
+                    });
+                }
+            }
+        }
+        for (tag, fields) in &record.fields {
+            for subfield in fields.iter().flat_map(|field| &field.subfields) {
+                for (byte_index, bytes, kind) in scan_value(&subfield.value) {
+                    issues.push(EncodingIssue {
+                        tag: tag.clone(),
+                        subfield_code: Some(subfield.code),
+                        byte_index,
+                        record_offset: None,
+                        bytes,
+                        kind,
+                    });
+                }
+            }
+        }
+        issues
+    }
+
+    /// List character-level problems in one undecoded ISO 2709 record
+    ///
+    /// Fields are located through the directory, and each issue carries
+    /// its offset from the start of the record as well as its index within
+    /// the value. Records declared as UTF-8 (leader/09 `a`) are checked for
+    /// invalid UTF-8 and for everything [`EncodingValidator::diagnose`]
+    /// reports; MARC-8 records, whose high bytes cannot be judged without
+    /// decoding, are checked for stray control bytes only. Directory
+    /// entries that point outside the record are skipped.
+    #[must_use]
+    pub fn diagnose_bytes(raw: &[u8]) -> Vec<EncodingIssue> {
+        let mut issues = Vec::new();
+        let utf8 = raw.get(9) == Some(&b'a');
+        for (tag, start, data) in raw_fields(raw) {
+            let mut push = |code, value_start: usize, value: &[u8]| {
+                for (byte_index, bytes, kind) in scan_bytes(value, utf8) {
+                    issues.push(EncodingIssue {
+                        tag: tag.clone(),
+                        subfield_code: code,
+                        byte_index,
+                        record_offset: Some(start + value_start + byte_index),
+                        bytes,
+                        kind,
+                    });
+                }
+            };
+            if tag.starts_with("00") {
+                push(None, 0, data);
+                continue;
+            }
+            let mut position = 0;
+            for (i, chunk) in data.split(|&b| b == SUBFIELD_DELIMITER).enumerate() {
+                // The first chunk holds the indicators.
+                if i > 0
+                    && let Some((&code, value)) = chunk.split_first()
+                {
+                    push(Some(char::from(code)), position + 1, value);
+                }
+                position += chunk.len() + 1;
+            }
+        }
+        issues
+    }
+
+    /// Count the MARC-8 escape sequences, G0/G1 character set
+    /// designations and characters outside the declared coding in one
+    /// undecoded ISO 2709 record
+    ///
+    /// [`CharsetAudit`] runs this over a whole stream.
+    #[must_use]
+    pub fn charset_usage(raw: &[u8]) -> CharsetUsage {
+        let mut usage = CharsetUsage::default();
+        let utf8 = raw.get(9) == Some(&b'a');
+        for (tag, _, data) in raw_fields(raw) {
+            let before = usage.marked_count();
+            let data = if tag.starts_with("00") {
+                data
+            } else {
+                data.get(2..).unwrap_or_default()
+            };
+            if utf8 {
+                scan_utf8_usage(data, &mut usage);
+            } else {
+                scan_marc8_usage(data, &mut usage);
+            }
+            if usage.marked_count() > before {
+                *usage.tags.entry(tag).or_default() += 1;
+            }
+        }
+        usage
+    }
+
+    /// Rewrite the problems [`EncodingValidator::diagnose`] finds and
+    /// return a log of every change
+    ///
+    /// Replacement characters are already as repaired as they can be and
+    /// are left in place and out of the log.
+    pub fn repair(record: &mut Record, mode: RepairMode) -> Vec<EncodingRepair> {
+        let mut log = Vec::new();
+        for (tag, values) in &mut record.control_fields {
+            for value in values {
+                repair_value(value, mode, |issue, replacement| {
+                    log.push(EncodingRepair {
+                        issue: EncodingIssue {
+                            tag: tag.clone(),
+                            subfield_code: None,
+                            byte_index: issue.0,
+                            record_offset: None,
+                            bytes: issue.1,
+                            kind: issue.2,
+                        },
+                        replacement,
+                    });
+                });
+            }
+        }
+        for (tag, fields) in &mut record.fields {
+            for subfield in fields.iter_mut().flat_map(|field| &mut field.subfields) {
+                let code = subfield.code;
+                repair_value(&mut subfield.value, mode, |issue, replacement| {
+                    log.push(EncodingRepair {
+                        issue: EncodingIssue {
+                            tag: tag.clone(),
+                            subfield_code: Some(code),
+                            byte_index: issue.0,
+                            record_offset: None,
+                            bytes: issue.1,
+                            kind: issue.2,
+                        },
+                        replacement,
+                    });
+                });
+            }
+        }
+        log
+    }
 }
 
 /// Check if a string contains MARC-8 escape sequences
@@ -240,6 +746,425 @@ fn count_utf8_indicators(bytes: &[u8]) -> usize {
     bytes.iter().filter(|&&b| matches!(b, 0xC0..=0xF7)).count()
 }
 
+const FIELD_TERMINATOR: u8 = 0x1E;
+const SUBFIELD_DELIMITER: u8 = 0x1F;
+
+/// The fields of an undecoded ISO 2709 record as (tag, offset of the
+/// field data from the start of the record, data without its terminator).
+/// Directory entries that point outside the record are skipped.
+fn raw_fields(raw: &[u8]) -> Vec<(String, usize, &[u8])> {
+    let mut fields = Vec::new();
+    if raw.len() < 24 {
+        return fields;
+    }
+    let parse = |bytes: &[u8]| {
+        std::str::from_utf8(bytes)
+            .ok()
+            .and_then(|s| s.parse::<usize>().ok())
+    };
+    let Some(base) = parse(&raw[12..17]) else {
+        return fields;
+    };
+    let directory_end = base.min(raw.len()).saturating_sub(1);
+    for entry in raw
+        .get(24..directory_end)
+        .unwrap_or_default()
+        .chunks_exact(12)
+    {
+        let (Some(length), Some(start)) = (parse(&entry[3..7]), parse(&entry[7..12])) else {
+            continue;
+        };
+        let start = base + start;
+        let Some(data) = raw.get(start..start + length) else {
+            continue;
+        };
+        let data = data.strip_suffix(&[FIELD_TERMINATOR]).unwrap_or(data);
+        fields.push((
+            String::from_utf8_lossy(&entry[0..3]).into_owned(),
+            start,
+            data,
+        ));
+    }
+    fields
+}
+
+/// A problem within one value: byte index, offending bytes and kind
+type ValueIssue = (usize, Vec<u8>, EncodingIssueKind);
+
+/// Count usage in one field's bytes from a record declared as UTF-8
+fn scan_utf8_usage(data: &[u8], usage: &mut CharsetUsage) {
+    let mut rest = data;
+    while !rest.is_empty() {
+        let (valid, invalid_len) = match std::str::from_utf8(rest) {
+            Ok(valid) => (valid, 0),
+            Err(e) => (
+                std::str::from_utf8(&rest[..e.valid_up_to()]).unwrap_or_default(),
+                e.error_len().unwrap_or(rest.len() - e.valid_up_to()),
+            ),
+        };
+        let bytes = valid.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i] == 0x1B {
+                let (len, _) = escape_sequence(&bytes[i..], usage);
+                *usage
+                    .outside_coding
+                    .entry(OutsideCoding::Marc8Escape)
+                    .or_default() += 1;
+                i += len;
+                continue;
+            }
+            if let Some(c) = valid[i..].chars().next() {
+                if is_stray_control(c) {
+                    count_outside(usage, OutsideCoding::ControlCharacter);
+                }
+                i += c.len_utf8();
+            }
+        }
+        if invalid_len > 0 {
+            count_outside(usage, OutsideCoding::InvalidUtf8);
+        }
+        rest = &rest[valid.len() + invalid_len..];
+    }
+}
+
+/// Count usage in one field's bytes from a record declared as MARC-8,
+/// tracking G0 and G1 from their defaults (Basic Latin and ANSEL) at the
+/// start of the field
+fn scan_marc8_usage(data: &[u8], usage: &mut CharsetUsage) {
+    if !data.contains(&0x1B)
+        && !data.is_ascii()
+        && let Ok(text) = std::str::from_utf8(data)
+    {
+        let multibyte = text.chars().filter(|c| !c.is_ascii()).count();
+        *usage
+            .outside_coding
+            .entry(OutsideCoding::Utf8InMarc8)
+            .or_default() += multibyte;
+        return;
+    }
+    let mut g0 = CharacterSetId::BasicLatin;
+    let mut g1 = CharacterSetId::AnselExtendedLatin;
+    let mut i = 0;
+    while i < data.len() {
+        let byte = data[i];
+        if byte == 0x1B {
+            let (len, designation) = escape_sequence(&data[i..], usage);
+            match designation {
+                Some(Designation::G0(set)) => g0 = set,
+                Some(Designation::G1(set)) => g1 = set,
+                None => {},
+            }
+            i += len;
+            continue;
+        }
+        if byte == SUBFIELD_DELIMITER {
+            // Skip the delimiter and its subfield code.
+            i += 2;
+            continue;
+        }
+        if byte < 0x20 || (0x7F..0xA0).contains(&byte) {
+            count_outside(usage, OutsideCoding::ControlCharacter);
+            i += 1;
+            continue;
+        }
+        let set = if byte >= 0xA0 { g1 } else { g0 };
+        if set == CharacterSetId::EACC {
+            let mapped = data
+                .get(i..i + 3)
+                .map(|c| {
+                    (u32::from(c[0] & 0x7F) << 16)
+                        | (u32::from(c[1] & 0x7F) << 8)
+                        | u32::from(c[2] & 0x7F)
+                })
+                .and_then(get_eacc_character)
+                .is_some();
+            if !mapped {
+                count_outside(usage, OutsideCoding::UnmappedMarc8);
+            }
+            i += 3;
+            continue;
+        }
+        // Sets may be designated as G0 or G1, so accept either half.
+        let table = get_charset_table(set);
+        if byte != b' ' && !table.contains_key(&byte) && !table.contains_key(&(byte ^ 0x80)) {
+            count_outside(usage, OutsideCoding::UnmappedMarc8);
+        }
+        i += 1;
+    }
+}
+
+/// A character set designated by an escape sequence
+enum Designation {
+    G0(CharacterSetId),
+    G1(CharacterSetId),
+}
+
+/// Count the escape sequence at the start of `bytes` and return its length
+/// and the designation it makes, if any
+fn escape_sequence(bytes: &[u8], usage: &mut CharsetUsage) -> (usize, Option<Designation>) {
+    let (len, designation) = match bytes.get(1..).unwrap_or_default() {
+        [b'(' | b',', f, ..] => (3, CharacterSetId::from_byte(*f).map(Designation::G0)),
+        [b')' | b'-', f, ..] => (3, CharacterSetId::from_byte(*f).map(Designation::G1)),
+        [b'$', b'1', ..] => (3, Some(Designation::G0(CharacterSetId::EACC))),
+        [b'$', b'(' | b',', b'1', ..] => (4, Some(Designation::G0(CharacterSetId::EACC))),
+        [b'$', b')' | b'-', b'1', ..] => (4, Some(Designation::G1(CharacterSetId::EACC))),
+        [b's', ..] => (2, Some(Designation::G0(CharacterSetId::BasicLatin))),
+        [b'g', ..] => (2, Some(Designation::G0(CharacterSetId::GreekSymbols))),
+        [b'b', ..] => (2, Some(Designation::G0(CharacterSetId::Subscript))),
+        [b'p', ..] => (2, Some(Designation::G0(CharacterSetId::Superscript))),
+        [b'$', ..] => (bytes.len().min(4), None),
+        _ => (bytes.len().min(2), None),
+    };
+    let written = std::iter::once("ESC".to_string())
+        .chain(bytes[1..len].iter().map(|&b| {
+            if b.is_ascii_graphic() {
+                char::from(b).to_string()
+            } else {
+                format!("{b:02X}")
+            }
+        }))
+        .collect::<Vec<_>>()
+        .join(" ");
+    *usage.escape_sequences.entry(written).or_default() += 1;
+    match &designation {
+        Some(Designation::G0(set)) => *usage.g0_sets.entry(set_name(*set).into()).or_default() += 1,
+        Some(Designation::G1(set)) => *usage.g1_sets.entry(set_name(*set).into()).or_default() += 1,
+        None => count_outside(usage, OutsideCoding::UnknownEscape),
+    }
+    (len, designation)
+}
+
+fn count_outside(usage: &mut CharsetUsage, kind: OutsideCoding) {
+    *usage.outside_coding.entry(kind).or_default() += 1;
+}
+
+/// C0 and C1 controls other than the ones MARC uses as structure
+fn is_stray_control(c: char) -> bool {
+    c.is_control() && !matches!(c, '\u{1B}' | '\u{1E}' | '\u{1F}')
+}
+
+fn set_name(set: CharacterSetId) -> &'static str {
+    match set {
+        CharacterSetId::BasicLatin => "Basic Latin",
+        CharacterSetId::AnselExtendedLatin => "Extended Latin (ANSEL)",
+        CharacterSetId::BasicHebrew => "Basic Hebrew",
+        CharacterSetId::BasicArabic => "Basic Arabic",
+        CharacterSetId::ExtendedArabic => "Extended Arabic",
+        CharacterSetId::BasicCyrillic => "Basic Cyrillic",
+        CharacterSetId::ExtendedCyrillic => "Extended Cyrillic",
+        CharacterSetId::BasicGreek => "Basic Greek",
+        CharacterSetId::Subscript => "Subscripts",
+        CharacterSetId::Superscript => "Superscripts",
+        CharacterSetId::GreekSymbols => "Greek Symbols",
+        CharacterSetId::EACC => "East Asian (EACC)",
+    }
+}
+
+/// Find the character-level problems in a decoded value
+fn scan_value(value: &str) -> Vec<ValueIssue> {
+    let mut issues = Vec::new();
+    let mut chars = value.char_indices().peekable();
+
+    if chars.peek().is_some_and(|&(_, c)| is_combining_mark(c)) {
+        let mut end = 0;
+        while let Some(&(i, c)) = chars.peek() {
+            if !is_combining_mark(c) {
+                break;
+            }
+            end = i + c.len_utf8();
+            chars.next();
+        }
+        // Take the base letter along so the issue covers what gets reordered.
+        if let Some(&(i, c)) = chars.peek()
+            && c.is_alphanumeric()
+        {
+            end = i + c.len_utf8();
+            chars.next();
+        }
+        issues.push((
+            0,
+            value.as_bytes()[..end].to_vec(),
+            EncodingIssueKind::LeadingCombiningMark,
+        ));
+    }
+
+    while let Some((i, c)) = chars.next() {
+        let kind = match c {
+            '\u{FFFD}' => EncodingIssueKind::ReplacementCharacter,
+            '\u{1B}' => {
+                let end = i + escape_sequence_len(&value[i..]);
+                while chars.peek().is_some_and(|&(j, _)| j < end) {
+                    chars.next();
+                }
+                issues.push((
+                    i,
+                    value.as_bytes()[i..end].to_vec(),
+                    EncodingIssueKind::Marc8Escape,
+                ));
+                continue;
+            },
+            c if c.is_control() => EncodingIssueKind::ControlCharacter,
+            c if is_noncharacter(c) => EncodingIssueKind::Noncharacter,
+            _ => continue,
+        };
+        issues.push((i, value.as_bytes()[i..i + c.len_utf8()].to_vec(), kind));
+    }
+    issues
+}
+
+/// Find the problems in an undecoded value
+fn scan_bytes(value: &[u8], utf8: bool) -> Vec<ValueIssue> {
+    if !utf8 {
+        return value
+            .iter()
+            .enumerate()
+            .filter(|&(_, &b)| b < 0x20 && b != 0x1B || b == 0x7F)
+            .map(|(i, &b)| (i, vec![b], EncodingIssueKind::ControlCharacter))
+            .collect();
+    }
+
+    let mut issues = Vec::new();
+    let mut offset = 0;
+    loop {
+        let rest = &value[offset..];
+        let (valid, invalid_len) = match std::str::from_utf8(rest) {
+            Ok(valid) => (valid, None),
+            Err(e) => {
+                let valid = std::str::from_utf8(&rest[..e.valid_up_to()]).unwrap_or_default();
+                (
+                    valid,
+                    Some(e.error_len().unwrap_or(rest.len() - e.valid_up_to())),
+                )
+            },
+        };
+        issues.extend(
+            scan_value(valid)
+                .into_iter()
+                .filter(|(i, _, kind)| {
+                    // Only the very start of the value can hold a leading mark.
+                    offset == 0 || *i > 0 || *kind != EncodingIssueKind::LeadingCombiningMark
+                })
+                .map(|(i, bytes, kind)| (offset + i, bytes, kind)),
+        );
+        let Some(len) = invalid_len else {
+            break;
+        };
+        let start = offset + valid.len();
+        issues.push((
+            start,
+            value[start..start + len].to_vec(),
+            EncodingIssueKind::InvalidUtf8,
+        ));
+        offset = start + len;
+    }
+    issues
+}
+
+/// Length in bytes of the MARC-8 escape sequence at the start of `s`: ESC,
+/// any intermediate characters, and the final character
+fn escape_sequence_len(s: &str) -> usize {
+    let mut len = 1;
+    for c in s[1..].chars() {
+        len += c.len_utf8();
+        if !matches!(c, '(' | ')' | ',' | '$' | '-' | '!') {
+            break;
+        }
+    }
+    len
+}
+
+fn is_combining_mark(c: char) -> bool {
+    unicode_normalization::char::is_combining_mark(c)
+}
+
+fn is_noncharacter(c: char) -> bool {
+    matches!(u32::from(c), 0xFDD0..=0xFDEF) || u32::from(c) & 0xFFFE == 0xFFFE
+}
+
+/// Repair one value in place, reporting each change to `log`
+fn repair_value(value: &mut String, mode: RepairMode, mut log: impl FnMut(ValueIssue, String)) {
+    let issues = scan_value(value);
+    if issues.is_empty() {
+        return;
+    }
+    let mut repaired = String::with_capacity(value.len());
+    let mut copied = 0;
+    for (index, bytes, kind) in issues {
+        let Some(replacement) = repair_text(&value[index..index + bytes.len()], kind, mode) else {
+            continue;
+        };
+        repaired.push_str(&value[copied..index]);
+        repaired.push_str(&replacement);
+        copied = index + bytes.len();
+        log((index, bytes, kind), replacement);
+    }
+    repaired.push_str(&value[copied..]);
+    *value = repaired;
+}
+
+/// Replacement for one problem, or `None` to leave it alone
+fn repair_text(text: &str, kind: EncodingIssueKind, mode: RepairMode) -> Option<String> {
+    const REPLACEMENT: &str = "\u{FFFD}";
+    match (kind, mode) {
+        (EncodingIssueKind::ReplacementCharacter, _)
+        | (EncodingIssueKind::LeadingCombiningMark, RepairMode::Replace) => None,
+        (EncodingIssueKind::LeadingCombiningMark, RepairMode::Transliterate) => {
+            let split = text
+                .char_indices()
+                .find(|&(_, c)| !is_combining_mark(c))
+                .map(|(i, _)| i)?;
+            Some(format!("{}{}", &text[split..], &text[..split]))
+        },
+        (EncodingIssueKind::Marc8Escape, RepairMode::Transliterate) => Some(String::new()),
+        (EncodingIssueKind::ControlCharacter, RepairMode::Transliterate) => {
+            let c = text.chars().next()?;
+            Some(match c {
+                '\t' | '\n' | '\r' => " ".to_string(),
+                '\u{80}'..='\u{9F}' => {
+                    windows_1252(c).map_or(REPLACEMENT.to_string(), String::from)
+                },
+                _ => String::new(),
+            })
+        },
+        _ => Some(REPLACEMENT.to_string()),
+    }
+}
+
+/// The Windows-1252 character for a C1 control decoded as Latin-1
+pub(crate) fn windows_1252(c: char) -> Option<char> {
+    Some(match c {
+        '\u{80}' => '€',
+        '\u{82}' => '‚',
+        '\u{83}' => 'ƒ',
+        '\u{84}' => '„',
+        '\u{85}' => '…',
+        '\u{86}' => '†',
+        '\u{87}' => '‡',
+        '\u{88}' => 'ˆ',
+        '\u{89}' => '‰',
+        '\u{8A}' => 'Š',
+        '\u{8B}' => '‹',
+        '\u{8C}' => 'Œ',
+        '\u{8E}' => 'Ž',
+        '\u{91}' => '\u{2018}',
+        '\u{92}' => '\u{2019}',
+        '\u{93}' => '\u{201C}',
+        '\u{94}' => '\u{201D}',
+        '\u{95}' => '•',
+        '\u{96}' => '–',
+        '\u{97}' => '—',
+        '\u{98}' => '˜',
+        '\u{99}' => '™',
+        '\u{9A}' => 'š',
+        '\u{9B}' => '›',
+        '\u{9C}' => 'œ',
+        '\u{9E}' => 'ž',
+        '\u{9F}' => 'Ÿ',
+        _ => return None,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -287,4 +1212,186 @@ mod tests {
         // ASCII alone is ambiguous
         assert!(result.is_none() || result == Some(MarcEncoding::Utf8));
     }
+
+    fn record_with(value: &str) -> Record {
+        let mut record =
+            Record::new(crate::leader::Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+        record.add_control_field_str("001", "ocm1\u{7}");
+        record.add_field(
+            crate::record::Field::builder("245".to_string(), '1', '0')
+                .subfield_str('a', "Clean")
+                .subfield_str('b', value)
+                .build(),
+        );
+        record
+    }
+
+    #[test]
+    fn test_diagnose_locates_issues() {
+        let record = record_with("Don\u{92}t \u{1B}(BQuixote\u{FFFD}");
+        let issues = EncodingValidator::diagnose(&record);
+        assert_eq!(issues.len(), 4);
+
+        assert_eq!(issues[0].tag, "001");
+        assert_eq!(issues[0].subfield_code, None);
+        assert_eq!(issues[0].byte_index, 4);
+        assert_eq!(issues[0].kind, EncodingIssueKind::ControlCharacter);
+
+        assert_eq!(issues[1].subfield_code, Some('b'));
+        assert_eq!(issues[1].byte_index, 3);
+        assert_eq!(issues[1].bytes, vec![0xC2, 0x92]);
+        assert_eq!(
+            issues[1].to_string(),
+            "245 $b byte 3: C2 92 (control character)"
+        );
+
+        assert_eq!(issues[2].kind, EncodingIssueKind::Marc8Escape);
+        assert_eq!(issues[2].bytes, b"\x1B(B");
+        assert_eq!(issues[3].kind, EncodingIssueKind::ReplacementCharacter);
+    }
+
+    #[test]
+    fn test_diagnose_bytes_finds_invalid_utf8() {
+        let mut record = record_with("caf\u{301}e");
+        record.control_fields.clear();
+        let mut raw = Vec::new();
+        crate::writer::MarcWriter::new(&mut raw)
+            .write_record(&record)
+            .unwrap();
+        // Corrupt the second byte of the combining acute (CC 81).
+        let at = raw.windows(2).position(|w| w == [0xCC, 0x81]).unwrap();
+        raw[at + 1] = b'X';
+
+        let issues = EncodingValidator::diagnose_bytes(&raw);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].tag, "245");
+        assert_eq!(issues[0].subfield_code, Some('b'));
+        assert_eq!(issues[0].byte_index, 3);
+        assert_eq!(issues[0].record_offset, Some(at));
+        assert_eq!(issues[0].bytes, vec![0xCC]);
+        assert_eq!(issues[0].kind, EncodingIssueKind::InvalidUtf8);
+    }
+
+    #[test]
+    fn test_repair_replace() {
+        let mut record = record_with("Don\u{92}t\u{FFFD}");
+        let log = EncodingValidator::repair(&mut record, RepairMode::Replace);
+        assert_eq!(log.len(), 2);
+        assert_eq!(record.get_control_field("001"), Some("ocm1\u{FFFD}"));
+        assert_eq!(
+            record.get_field("245").unwrap().get_subfield('b'),
+            Some("Don\u{FFFD}t\u{FFFD}")
+        );
+        assert_eq!(log[1].issue.byte_index, 3);
+        assert_eq!(log[1].replacement, "\u{FFFD}");
+    }
+
+    #[test]
+    fn test_repair_transliterate() {
+        let mut record = record_with("\u{301}Etude\tdon\u{92}t \u{1B}(BQuixote");
+        let log = EncodingValidator::repair(&mut record, RepairMode::Transliterate);
+        assert_eq!(record.get_control_field("001"), Some("ocm1"));
+        assert_eq!(
+            record.get_field("245").unwrap().get_subfield('b'),
+            Some("E\u{301}tude don\u{2019}t Quixote")
+        );
+        assert_eq!(log.len(), 5);
+        assert_eq!(log[1].issue.kind, EncodingIssueKind::LeadingCombiningMark);
+        assert_eq!(log[1].replacement, "E\u{301}");
+        assert!(EncodingValidator::diagnose(&record).is_empty());
+    }
+
+    /// An undecoded record with leader/09 `coding` and `(tag, data)` fields
+    fn raw_record(coding: u8, fields: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut directory = Vec::new();
+        let mut data = Vec::new();
+        for (tag, body) in fields {
+            directory.extend_from_slice(
+                format!("{tag}{:04}{:05}", body.len() + 1, data.len()).as_bytes(),
+            );
+            data.extend_from_slice(body);
+            data.push(FIELD_TERMINATOR);
+        }
+        directory.push(FIELD_TERMINATOR);
+        let base = 24 + directory.len();
+        let mut raw = format!(
+            "{:05}nam {}22{base:05} a 4500",
+            base + data.len() + 1,
+            char::from(coding)
+        )
+        .into_bytes();
+        raw.extend_from_slice(&directory);
+        raw.extend_from_slice(&data);
+        raw.push(0x1D);
+        raw
+    }
+
+    #[test]
+    fn test_charset_usage_marc8() {
+        let raw = raw_record(
+            b' ',
+            &[
+                ("001", b"ocm1"),
+                ("245", b"10\x1faTitle \x1b(NAB\x1b(B \xe2e\x80\x1b(Z"),
+                ("500", b"  \x1facaf\xc3\xa9"),
+                ("650", b" 0\x1faPlain"),
+            ],
+        );
+        let usage = EncodingValidator::charset_usage(&raw);
+        assert_eq!(usage.escape_sequences["ESC ( N"], 1);
+        assert_eq!(usage.escape_sequences["ESC ( B"], 1);
+        assert_eq!(usage.escape_sequences["ESC ( Z"], 1);
+        assert_eq!(usage.g0_sets["Basic Cyrillic"], 1);
+        assert_eq!(usage.g0_sets["Basic Latin"], 1);
+        assert!(usage.g1_sets.is_empty());
+        assert_eq!(usage.outside_coding[&OutsideCoding::ControlCharacter], 1);
+        assert_eq!(usage.outside_coding[&OutsideCoding::UnknownEscape], 1);
+        assert_eq!(usage.outside_coding[&OutsideCoding::Utf8InMarc8], 1);
+        assert!(
+            !usage
+                .outside_coding
+                .contains_key(&OutsideCoding::UnmappedMarc8)
+        );
+        assert_eq!(usage.tags.keys().collect::<Vec<_>>(), ["245", "500"]);
+    }
+
+    #[test]
+    fn test_charset_usage_utf8() {
+        let raw = raw_record(b'a', &[("245", b"10\x1faCaf\xc3\xa9 \x1b)E\xff")]);
+        let usage = EncodingValidator::charset_usage(&raw);
+        assert_eq!(usage.g1_sets["Extended Latin (ANSEL)"], 1);
+        assert_eq!(usage.outside_coding[&OutsideCoding::Marc8Escape], 1);
+        assert_eq!(usage.outside_coding[&OutsideCoding::InvalidUtf8], 1);
+        assert_eq!(usage.outside_count(), 2);
+    }
+
+    #[test]
+    fn test_charset_audit_stream() {
+        let mut stream = raw_record(b'a', &[("001", b"clean"), ("245", b"10\x1faCaf\xc3\xa9")]);
+        stream.extend(raw_record(
+            b' ',
+            &[("001", b"cyr"), ("245", b"10\x1fa\x1b(NAB\x1b(B")],
+        ));
+        stream.extend(raw_record(
+            b' ',
+            &[("001", b"hebrew"), ("245", b"10\x1fa\x1b)2\xa1")],
+        ));
+
+        let audit = CharsetAudit::scan(stream.as_slice()).unwrap();
+        assert_eq!(audit.records, 3);
+        assert_eq!(audit.declared_coding[&' '], 2);
+        assert_eq!(audit.declared_coding[&'a'], 1);
+        assert_eq!(audit.records_with_escapes, 2);
+        assert_eq!(audit.records_outside_coding, 0);
+        assert_eq!(audit.totals.escape_count(), 3);
+        assert_eq!(audit.totals.g1_sets["Basic Hebrew"], 1);
+        assert_eq!(audit.totals.tags["245"], 2);
+        let positions: Vec<_> = audit.per_record.iter().map(|r| r.position).collect();
+        assert_eq!(positions, [1, 2]);
+        assert_eq!(
+            audit.per_record[1].control_number.as_deref(),
+            Some("hebrew")
+        );
+        assert!(audit.to_string().contains("  ESC ( N: 1\n"));
+    }
 }
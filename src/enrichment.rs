@@ -0,0 +1,429 @@
+//! Linking headings to authority data through a pluggable resolver.
+//!
+//! mrrc does not talk to reconciliation services itself. Instead a
+//! [`HeadingResolver`] (or, for network clients built on an async runtime,
+//! an [`AsyncHeadingResolver`]) turns a heading string and its
+//! [`HeadingType`] (the same type authority records use for their 1XX)
+//! into candidate authority URIs or identifiers, and a
+//! [`RecordEnricher`] handles the MARC side: finding the 1XX/6XX/7XX
+//! headings, building the search string, and writing the chosen candidate
+//! into `$0` with [`Field::set_authority_id`](crate::record::Field::set_authority_id).
+//!
+//! Headings are passed to the resolver without relator terms, control
+//! subfields or trailing ISBD punctuation, with subject subdivisions joined
+//! by `--` as id.loc.gov labels are (`Art--France--History`). Fields that
+//! already have a `$0` are skipped unless [`RecordEnricher::with_overwrite`]
+//! is set.
+//!
+//! The async trait needs no runtime or extra dependency: it is an ordinary
+//! trait returning a future, and [`RecordEnricher::enrich_record_async`]
+//! awaits it.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::convert::Infallible;
+//!
+//! use mrrc::enrichment::{HeadingResolver, RecordEnricher};
+//! use mrrc::{Field, HeadingType, Leader, Record};
+//!
+//! struct Fixed;
+//!
+//! impl HeadingResolver for Fixed {
+//!     type Error = Infallible;
+//!
+//!     fn resolve(&self, heading: &str, heading_type: HeadingType) -> Result<Vec<String>, Infallible> {
+//!         Ok(match (heading, heading_type) {
+//!             ("Twain, Mark, 1835-1910", HeadingType::PersonalName) => {
+//!                 vec!["http://id.loc.gov/authorities/names/n79021164".to_string()]
+//!             },
+//!             _ => Vec::new(),
+//!         })
+//!     }
+//! }
+//!
+//! let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+//! record.add_field(
+//!     Field::builder("100".to_string(), '1', ' ')
+//!         .subfield_str('a', "Twain, Mark,")
+//!         .subfield_str('d', "1835-1910,")
+//!         .subfield_str('e', "author.")
+//!         .build(),
+//! );
+//!
+//! let enrichments = RecordEnricher::new(Fixed).enrich_record(&mut record).unwrap();
+//! assert_eq!(enrichments.len(), 1);
+//! assert_eq!(
+//!     record.get_field("100").unwrap().get_subfield('0'),
+//!     Some("http://id.loc.gov/authorities/names/n79021164")
+//! );
+//! ```
+
+use std::future::Future;
+
+use serde::{Deserialize, Serialize};
+
+use crate::authority_flip::heading_kind;
+use crate::authority_record::HeadingType;
+use crate::record::Record;
+use crate::subjects::heading_string;
+
+/// Looks up authority URIs or identifiers for a heading.
+pub trait HeadingResolver {
+    /// Error returned when a lookup fails.
+    type Error;
+
+    /// Candidate `$0` values for `heading`, best match first. An empty list
+    /// means no match.
+    ///
+    /// # Errors
+    ///
+    /// Returns the resolver's error if the lookup could not be made; the
+    /// enricher stops and passes it on.
+    fn resolve(&self, heading: &str, heading_type: HeadingType)
+    -> Result<Vec<String>, Self::Error>;
+}
+
+/// Async counterpart of [`HeadingResolver`], for resolvers that call remote
+/// services.
+pub trait AsyncHeadingResolver {
+    /// Error returned when a lookup fails.
+    type Error;
+
+    /// Candidate `$0` values for `heading`, best match first.
+    fn resolve(
+        &self,
+        heading: &str,
+        heading_type: HeadingType,
+    ) -> impl Future<Output = Result<Vec<String>, Self::Error>>;
+}
+
+/// A `$0` written by [`RecordEnricher`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Enrichment {
+    /// Control number (001) of the record, if present.
+    pub record_control_number: Option<String>,
+    /// Tag of the enriched field.
+    pub tag: String,
+    /// Zero-based occurrence of the field among fields with the same tag.
+    pub occurrence: usize,
+    /// The heading string passed to the resolver.
+    pub heading: String,
+    /// The value written to `$0`.
+    pub id: String,
+}
+
+/// A heading waiting to be resolved.
+#[derive(Debug)]
+struct Lookup {
+    tag: String,
+    occurrence: usize,
+    heading: String,
+    heading_type: HeadingType,
+}
+
+/// Walks 1XX/6XX/7XX headings and fills in `$0` from a resolver.
+#[derive(Debug, Clone)]
+pub struct RecordEnricher<R> {
+    resolver: R,
+    overwrite: bool,
+    unique_match: bool,
+}
+
+impl<R> RecordEnricher<R> {
+    /// Create an enricher around `resolver`.
+    #[must_use]
+    pub fn new(resolver: R) -> Self {
+        RecordEnricher {
+            resolver,
+            overwrite: false,
+            unique_match: false,
+        }
+    }
+
+    /// Also resolve fields that already have a `$0`. The new value replaces
+    /// an existing one from the same source and is added otherwise.
+    #[must_use]
+    pub fn with_overwrite(mut self, enabled: bool) -> Self {
+        self.overwrite = enabled;
+        self
+    }
+
+    /// Only write `$0` when the resolver returns exactly one candidate,
+    /// instead of taking the first.
+    #[must_use]
+    pub fn with_unique_match(mut self, enabled: bool) -> Self {
+        self.unique_match = enabled;
+        self
+    }
+
+    /// The wrapped resolver.
+    #[must_use]
+    pub fn resolver(&self) -> &R {
+        &self.resolver
+    }
+
+    fn lookups(&self, record: &Record) -> Vec<Lookup> {
+        let mut lookups = Vec::new();
+        for (tag, fields) in &record.fields {
+            if !matches!(tag.as_bytes().first(), Some(b'1' | b'6' | b'7')) {
+                continue;
+            }
+            let Some(heading_type) = heading_type(tag) else {
+                continue;
+            };
+            for (occurrence, field) in fields.iter().enumerate() {
+                if !self.overwrite && field.get_subfield('0').is_some() {
+                    continue;
+                }
+                if let Some(heading) = heading_string(field) {
+                    lookups.push(Lookup {
+                        tag: tag.clone(),
+                        occurrence,
+                        heading,
+                        heading_type,
+                    });
+                }
+            }
+        }
+        lookups
+    }
+
+    fn apply(
+        &self,
+        record: &mut Record,
+        lookup: Lookup,
+        mut candidates: Vec<String>,
+    ) -> Option<Enrichment> {
+        if candidates.is_empty() || (self.unique_match && candidates.len() > 1) {
+            return None;
+        }
+        let id = candidates.swap_remove(0);
+        let field = record
+            .get_fields_mut(&lookup.tag)?
+            .get_mut(lookup.occurrence)?;
+        field.set_authority_id('0', &id);
+        Some(Enrichment {
+            record_control_number: record.get_control_field("001").map(str::to_string),
+            tag: lookup.tag,
+            occurrence: lookup.occurrence,
+            heading: lookup.heading,
+            id,
+        })
+    }
+}
+
+impl<R: HeadingResolver> RecordEnricher<R> {
+    /// Resolve the headings of `record` and write `$0` where the resolver
+    /// finds a match, returning one [`Enrichment`] per field changed.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from the resolver. Fields enriched before
+    /// the failure keep their new `$0`.
+    pub fn enrich_record(&self, record: &mut Record) -> Result<Vec<Enrichment>, R::Error> {
+        let mut enrichments = Vec::new();
+        for lookup in self.lookups(record) {
+            let candidates = self
+                .resolver
+                .resolve(&lookup.heading, lookup.heading_type)?;
+            enrichments.extend(self.apply(record, lookup, candidates));
+        }
+        Ok(enrichments)
+    }
+}
+
+impl<R: AsyncHeadingResolver> RecordEnricher<R> {
+    /// Async version of [`RecordEnricher::enrich_record`]. Headings are
+    /// resolved one at a time, in field order.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from the resolver.
+    pub async fn enrich_record_async(
+        &self,
+        record: &mut Record,
+    ) -> Result<Vec<Enrichment>, R::Error> {
+        let mut enrichments = Vec::new();
+        for lookup in self.lookups(record) {
+            let candidates = self
+                .resolver
+                .resolve(&lookup.heading, lookup.heading_type)
+                .await?;
+            enrichments.extend(self.apply(record, lookup, candidates));
+        }
+        Ok(enrichments)
+    }
+}
+
+/// The heading type of a 1XX/6XX/7XX tag, if it is a heading.
+fn heading_type(tag: &str) -> Option<HeadingType> {
+    Some(match heading_kind(tag)? {
+        "00" => HeadingType::PersonalName,
+        "10" => HeadingType::CorporateName,
+        "11" => HeadingType::MeetingName,
+        "30" => HeadingType::UniformTitle,
+        "48" => HeadingType::ChronologicalTerm,
+        "50" => HeadingType::TopicalTerm,
+        "51" => HeadingType::GeographicName,
+        _ => HeadingType::GenreFormTerm,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::leader::Leader;
+    use crate::record::Field;
+    use std::cell::RefCell;
+    use std::convert::Infallible;
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    /// Records every query and answers from a fixed table.
+    #[derive(Default)]
+    struct Mock {
+        answers: Vec<(&'static str, Vec<&'static str>)>,
+        queries: RefCell<Vec<(String, HeadingType)>>,
+    }
+
+    impl Mock {
+        fn answer(&self, heading: &str, heading_type: HeadingType) -> Vec<String> {
+            self.queries
+                .borrow_mut()
+                .push((heading.to_string(), heading_type));
+            self.answers
+                .iter()
+                .find(|(h, _)| *h == heading)
+                .map(|(_, ids)| ids.iter().map(ToString::to_string).collect())
+                .unwrap_or_default()
+        }
+    }
+
+    impl HeadingResolver for Mock {
+        type Error = Infallible;
+
+        fn resolve(
+            &self,
+            heading: &str,
+            heading_type: HeadingType,
+        ) -> Result<Vec<String>, Infallible> {
+            Ok(self.answer(heading, heading_type))
+        }
+    }
+
+    impl AsyncHeadingResolver for Mock {
+        type Error = Infallible;
+
+        async fn resolve(
+            &self,
+            heading: &str,
+            heading_type: HeadingType,
+        ) -> Result<Vec<String>, Infallible> {
+            Ok(self.answer(heading, heading_type))
+        }
+    }
+
+    fn record() -> Record {
+        let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+        record.add_control_field_str("001", "b1");
+        record.add_field(
+            Field::builder("100".to_string(), '1', ' ')
+                .subfield_str('a', "Twain, Mark,")
+                .subfield_str('d', "1835-1910,")
+                .subfield_str('e', "author.")
+                .build(),
+        );
+        record.add_field(
+            Field::builder("245".to_string(), '1', '0')
+                .subfield_str('a', "Roughing it.")
+                .build(),
+        );
+        record.add_field(
+            Field::builder("650".to_string(), ' ', '0')
+                .subfield_str('a', "Frontier and pioneer life")
+                .subfield_str('z', "West (U.S.)")
+                .subfield_str('v', "Fiction.")
+                .build(),
+        );
+        record.add_field(
+            Field::builder("651".to_string(), ' ', '0')
+                .subfield_str('a', "Nevada.")
+                .subfield_str('0', "http://id.loc.gov/authorities/names/n79041961")
+                .build(),
+        );
+        record
+    }
+
+    fn mock() -> Mock {
+        Mock {
+            answers: vec![
+                (
+                    "Twain, Mark, 1835-1910",
+                    vec!["(DLC)n  79021164", "(viaf)50566653"],
+                ),
+                (
+                    "Frontier and pioneer life--West (U.S.)--Fiction",
+                    vec!["http://id.loc.gov/authorities/subjects/sh2008103934"],
+                ),
+                ("Nevada", vec!["(OCoLC)fst01204155"]),
+            ],
+            ..Mock::default()
+        }
+    }
+
+    #[test]
+    fn test_enrich_record() {
+        let mut record = record();
+        let enricher = RecordEnricher::new(mock());
+        let enrichments = enricher.enrich_record(&mut record).unwrap();
+
+        let queries = enricher.resolver().queries.borrow();
+        assert_eq!(
+            *queries,
+            vec![
+                (
+                    "Twain, Mark, 1835-1910".to_string(),
+                    HeadingType::PersonalName
+                ),
+                (
+                    "Frontier and pioneer life--West (U.S.)--Fiction".to_string(),
+                    HeadingType::TopicalTerm
+                ),
+            ]
+        );
+        assert_eq!(enrichments.len(), 2);
+        assert_eq!(enrichments[0].record_control_number.as_deref(), Some("b1"));
+        assert_eq!(enrichments[0].id, "(DLC)n  79021164");
+        let name = record.get_field("100").unwrap();
+        let codes: String = name.subfields.iter().map(|sf| sf.code).collect();
+        assert_eq!(codes, "ade0");
+    }
+
+    #[test]
+    fn test_unique_match_and_overwrite() {
+        let mut record = record();
+        let enricher = RecordEnricher::new(mock())
+            .with_unique_match(true)
+            .with_overwrite(true);
+        let enrichments = enricher.enrich_record(&mut record).unwrap();
+
+        let tags: Vec<&str> = enrichments.iter().map(|e| e.tag.as_str()).collect();
+        assert_eq!(tags, vec!["650", "651"]);
+        assert_eq!(record.get_field("100").unwrap().get_subfield('0'), None);
+        let place = record.get_field("651").unwrap();
+        assert_eq!(place.get_subfield_values('0').len(), 2);
+    }
+
+    #[test]
+    fn test_enrich_record_async() {
+        let mut record = record();
+        let enricher = RecordEnricher::new(mock());
+        let mut future = pin!(enricher.enrich_record_async(&mut record));
+        let mut cx = Context::from_waker(Waker::noop());
+        let Poll::Ready(result) = future.as_mut().poll(&mut cx) else {
+            panic!("mock resolver never pends");
+        };
+        assert_eq!(result.unwrap().len(), 2);
+    }
+}
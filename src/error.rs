@@ -450,6 +450,9 @@ pub enum MarcError {
         record_index: Option<usize>,
         /// 001 control number of the record being written, when known.
         record_control_number: Option<String>,
+        /// Field tag that could not be written, when the problem is confined
+        /// to one field.
+        field_tag: Option<String>,
         /// Human-readable description of the problem.
         message: String,
     },
@@ -750,10 +753,12 @@ impl Clone for MarcError {
             MarcError::WriterError {
                 record_index,
                 record_control_number,
+                field_tag,
                 message,
             } => MarcError::WriterError {
                 record_index: *record_index,
                 record_control_number: record_control_number.clone(),
+                field_tag: field_tag.clone(),
                 message: message.clone(),
             },
             MarcError::FatalReaderError {
@@ -1120,6 +1125,7 @@ impl MarcError {
             MarcError::WriterError {
                 record_index,
                 record_control_number,
+                field_tag,
                 message,
             } => ErrorMetadata {
                 code: "E404",
@@ -1127,6 +1133,7 @@ impl MarcError {
                 kind: "WriterError",
                 record_index: *record_index,
                 record_control_number: record_control_number.as_deref(),
+                field_tag: field_tag.as_deref(),
                 message: Some(message),
                 ..ErrorMetadata::default()
             },
@@ -1800,6 +1807,23 @@ impl MarcError {
         self
     }
 
+    /// Set the field tag involved. No-op for variants that do not carry the
+    /// field (`InvalidIndicator`, `BadSubfieldCode`, `InvalidField`,
+    /// `EncodingError` and `WriterError` do); an existing value is
+    /// overwritten.
+    #[must_use]
+    pub fn with_field_tag(mut self, tag: Option<String>) -> Self {
+        match &mut self {
+            MarcError::InvalidIndicator { field_tag, .. }
+            | MarcError::BadSubfieldCode { field_tag, .. }
+            | MarcError::InvalidField { field_tag, .. }
+            | MarcError::EncodingError { field_tag, .. }
+            | MarcError::WriterError { field_tag, .. } => *field_tag = tag,
+            _ => {},
+        }
+        self
+    }
+
     /// Set an already-captured byte window. No-op for variants that do not
     /// carry the field; an existing value is overwritten. Use
     /// [`MarcError::with_bytes_near`] instead when the parse buffer is
@@ -2399,6 +2423,7 @@ mod tests {
         let err = MarcError::WriterError {
             record_index: Some(99),
             record_control_number: Some("oc00000099".into()),
+            field_tag: None,
             message: "Record length exceeds 4GB limit (5000000000 bytes)".into(),
         };
         insta::assert_snapshot!(err.to_string());
@@ -2732,6 +2757,7 @@ mod tests {
             MarcError::WriterError {
                 record_index: Some(5),
                 record_control_number: Some("rec0005".into()),
+                field_tag: None,
                 message: "exceeds 99999 bytes".into(),
             },
             MarcError::FatalReaderError {
@@ -0,0 +1,472 @@
+//! Extraction expressions that pull values, not fields, out of records.
+//!
+//! Discovery-layer feeds (facets, sort keys, format/date/language columns)
+//! almost always want "the first usable value from this list of places",
+//! e.g. a date from 260$c, else 264$c, else 008/07-10. An [`Extractor`]
+//! compiles such a fallback chain once and evaluates it against any
+//! [`MarcRecord`].
+//!
+//! # Expression syntax
+//!
+//! An expression is one or more *selectors* separated by `|`. Selectors are
+//! tried left to right; the first one that yields a non-empty value wins.
+//!
+//! | Selector       | Meaning                                                   |
+//! |----------------|-----------------------------------------------------------|
+//! | `245`          | All subfields of each 245, joined with a space            |
+//! | `245$ab`       | Subfields `a` and `b` of each 245, joined with a space    |
+//! | `264[ 1]$c`    | 264 with indicator 2 = `1` (`*` matches any indicator)    |
+//! | `008/35-37`    | Characters 35 through 37 (inclusive) of the 008           |
+//! | `007/0`        | Character 0 of each 007                                   |
+//! | `LDR/6`        | Leader position 6                                         |
+//! | `260$c/0-3`    | Slicing also applies to data-field values                 |
+//!
+//! Positions are 0-based and inclusive, matching the MARC 21 documentation
+//! (`008/07-10` is the four-character Date 1). Values are trimmed; a value
+//! that is empty, or that consists only of blanks and fill characters (`|`),
+//! counts as "no value" so the chain falls through to the next selector.
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::extract::Extractor;
+//! use mrrc::{Field, Record, Leader};
+//!
+//! let leader = Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap();
+//! let mut record = Record::new(leader);
+//! record.add_control_field_str("008", "200101s1925    xxu           000 0 eng d");
+//!
+//! let date = Extractor::new("260$c | 264[*1]$c | 008/7-10").unwrap();
+//! assert_eq!(date.first(&record), Some("1925".to_string()));
+//!
+//! let mut field = Field::new("260".to_string(), ' ', ' ');
+//! field.add_subfield_str('c', "c1926.");
+//! record.add_field(field);
+//! assert_eq!(date.first(&record), Some("c1926.".to_string()));
+//! ```
+
+use crate::error::{MarcError, Result};
+use crate::marc_record::MarcRecord;
+use std::fmt;
+use std::str::FromStr;
+
+/// Where a [`Selector`] reads its raw value from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SelectorSource {
+    /// The 24-character leader (`LDR`).
+    Leader,
+    /// A control field (`001`–`009`); repeated tags yield one value each.
+    Control {
+        /// Control field tag.
+        tag: String,
+    },
+    /// A data field, optionally filtered by indicators and restricted to
+    /// a set of subfield codes.
+    Data {
+        /// Data field tag.
+        tag: String,
+        /// Required first indicator, or `None` for any.
+        indicator1: Option<char>,
+        /// Required second indicator, or `None` for any.
+        indicator2: Option<char>,
+        /// Subfield codes to keep, in any order; empty means all subfields.
+        codes: Vec<char>,
+    },
+}
+
+/// One alternative in an extraction expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selector {
+    /// Where the value comes from.
+    pub source: SelectorSource,
+    /// Optional inclusive character range applied to each value.
+    pub range: Option<(usize, usize)>,
+}
+
+impl Selector {
+    /// Parse a single selector such as `245$ab` or `008/7-10`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MarcError::InvalidField`] describing the problem when the
+    /// selector is malformed.
+    pub fn parse(input: &str) -> Result<Self> {
+        let input = input.trim();
+        let (head, range) = match input.split_once('/') {
+            Some((head, positions)) => (head, Some(parse_range(positions, input)?)),
+            None => (input, None),
+        };
+        let (head, codes) = match head.split_once('$') {
+            Some((head, codes)) => {
+                if codes.is_empty() {
+                    return Err(selector_error(
+                        input,
+                        "'$' must be followed by subfield codes",
+                    ));
+                }
+                (head, Some(codes.chars().collect::<Vec<_>>()))
+            },
+            None => (head, None),
+        };
+        let (tag, indicators) = match head.split_once('[') {
+            Some((tag, rest)) => {
+                let inds = rest
+                    .strip_suffix(']')
+                    .ok_or_else(|| selector_error(input, "unterminated indicator filter"))?;
+                let chars: Vec<char> = inds.chars().collect();
+                if chars.len() != 2 {
+                    return Err(selector_error(
+                        input,
+                        "indicator filter must have exactly two characters",
+                    ));
+                }
+                (tag, Some((chars[0], chars[1])))
+            },
+            None => (head, None),
+        };
+
+        let source = if tag.eq_ignore_ascii_case("LDR") {
+            if codes.is_some() || indicators.is_some() {
+                return Err(selector_error(
+                    input,
+                    "the leader has no subfields or indicators",
+                ));
+            }
+            SelectorSource::Leader
+        } else {
+            if tag.len() != 3 || !tag.chars().all(|c| c.is_ascii_alphanumeric()) {
+                return Err(selector_error(
+                    input,
+                    "tag must be three alphanumeric characters",
+                ));
+            }
+            if is_control_tag(tag) {
+                if codes.is_some() || indicators.is_some() {
+                    return Err(selector_error(
+                        input,
+                        "control fields have no subfields or indicators",
+                    ));
+                }
+                SelectorSource::Control {
+                    tag: tag.to_string(),
+                }
+            } else {
+                let wildcard = |c: char| if c == '*' { None } else { Some(c) };
+                let (indicator1, indicator2) =
+                    indicators.map_or((None, None), |(i1, i2)| (wildcard(i1), wildcard(i2)));
+                SelectorSource::Data {
+                    tag: tag.to_string(),
+                    indicator1,
+                    indicator2,
+                    codes: codes.unwrap_or_default(),
+                }
+            }
+        };
+
+        Ok(Selector { source, range })
+    }
+
+    /// All non-empty values this selector yields for `record`, in record order.
+    #[must_use]
+    pub fn values<R: MarcRecord + ?Sized>(&self, record: &R) -> Vec<String> {
+        let mut raw: Vec<String> = Vec::new();
+        match &self.source {
+            SelectorSource::Leader => {
+                if let Ok(bytes) = record.leader().as_bytes() {
+                    raw.push(String::from_utf8_lossy(&bytes).into_owned());
+                }
+            },
+            SelectorSource::Control { tag } => {
+                raw.extend(
+                    record
+                        .control_fields_iter()
+                        .filter(|(t, _)| t == tag)
+                        .map(|(_, v)| v.to_string()),
+                );
+            },
+            SelectorSource::Data {
+                tag,
+                indicator1,
+                indicator2,
+                codes,
+            } => {
+                for field in record.get_fields(tag).unwrap_or_default() {
+                    if indicator1.is_some_and(|i| i != field.indicator1)
+                        || indicator2.is_some_and(|i| i != field.indicator2)
+                    {
+                        continue;
+                    }
+                    let joined = field
+                        .subfields
+                        .iter()
+                        .filter(|sf| codes.is_empty() || codes.contains(&sf.code))
+                        .map(|sf| sf.value.as_str())
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    raw.push(joined);
+                }
+            },
+        }
+
+        raw.into_iter()
+            .filter_map(|value| {
+                let sliced = match self.range {
+                    Some((start, end)) => value
+                        .chars()
+                        .skip(start)
+                        .take((end - start).saturating_add(1))
+                        .collect(),
+                    None => value,
+                };
+                let trimmed = sliced.trim();
+                if trimmed.chars().all(|c| c == ' ' || c == '|') {
+                    None
+                } else {
+                    Some(trimmed.to_string())
+                }
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for Selector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.source {
+            SelectorSource::Leader => write!(f, "LDR")?,
+            SelectorSource::Control { tag } => write!(f, "{tag}")?,
+            SelectorSource::Data {
+                tag,
+                indicator1,
+                indicator2,
+                codes,
+            } => {
+                write!(f, "{tag}")?;
+                if indicator1.is_some() || indicator2.is_some() {
+                    write!(
+                        f,
+                        "[{}{}]",
+                        indicator1.unwrap_or('*'),
+                        indicator2.unwrap_or('*')
+                    )?;
+                }
+                if !codes.is_empty() {
+                    write!(f, "${}", codes.iter().collect::<String>())?;
+                }
+            },
+        }
+        match self.range {
+            Some((start, end)) if start == end => write!(f, "/{start}"),
+            Some((start, end)) => write!(f, "/{start}-{end}"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A compiled extraction expression: an ordered fallback chain of
+/// [`Selector`]s.
+///
+/// See the [module documentation](self) for the expression syntax.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Extractor {
+    selectors: Vec<Selector>,
+}
+
+impl Extractor {
+    /// Compile an extraction expression such as `"260$c | 264$c | 008/7-10"`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MarcError::InvalidField`] if the expression is empty or any
+    /// selector is malformed.
+    pub fn new(expression: &str) -> Result<Self> {
+        if expression.trim().is_empty() {
+            return Err(MarcError::invalid_field_msg(
+                "extraction expression is empty".to_string(),
+            ));
+        }
+        let selectors = expression
+            .split('|')
+            .map(Selector::parse)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Extractor { selectors })
+    }
+
+    /// Build an extractor from already-constructed selectors.
+    #[must_use]
+    pub fn from_selectors(selectors: Vec<Selector>) -> Self {
+        Extractor { selectors }
+    }
+
+    /// The selectors in fallback order.
+    #[must_use]
+    pub fn selectors(&self) -> &[Selector] {
+        &self.selectors
+    }
+
+    /// The first non-empty value produced by the chain, or `None`.
+    #[must_use]
+    pub fn first<R: MarcRecord + ?Sized>(&self, record: &R) -> Option<String> {
+        self.selectors
+            .iter()
+            .find_map(|selector| selector.values(record).into_iter().next())
+    }
+
+    /// Every value produced by the first selector that yields anything.
+    ///
+    /// Useful for multi-valued facets (e.g. all 650$a, falling back to
+    /// 653$a) where the fallback applies to the whole set, not to each value.
+    #[must_use]
+    pub fn all<R: MarcRecord + ?Sized>(&self, record: &R) -> Vec<String> {
+        self.selectors
+            .iter()
+            .map(|selector| selector.values(record))
+            .find(|values| !values.is_empty())
+            .unwrap_or_default()
+    }
+}
+
+impl FromStr for Extractor {
+    type Err = MarcError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Extractor::new(s)
+    }
+}
+
+impl fmt::Display for Extractor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, selector) in self.selectors.iter().enumerate() {
+            if i > 0 {
+                write!(f, " | ")?;
+            }
+            write!(f, "{selector}")?;
+        }
+        Ok(())
+    }
+}
+
+fn is_control_tag(tag: &str) -> bool {
+    tag.starts_with("00")
+}
+
+fn parse_range(positions: &str, input: &str) -> Result<(usize, usize)> {
+    let parse = |s: &str| {
+        s.trim()
+            .parse::<usize>()
+            .map_err(|_| selector_error(input, "positions must be non-negative integers"))
+    };
+    let (start, end) = if let Some((start, end)) = positions.split_once('-') {
+        (parse(start)?, parse(end)?)
+    } else {
+        let pos = parse(positions)?;
+        (pos, pos)
+    };
+    if end < start {
+        return Err(selector_error(input, "range end is before range start"));
+    }
+    Ok((start, end))
+}
+
+fn selector_error(input: &str, problem: &str) -> MarcError {
+    MarcError::invalid_field_msg(format!("invalid extraction selector '{input}': {problem}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::leader::Leader;
+    use crate::record::{Field, Record};
+
+    fn record() -> Record {
+        let leader = Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap();
+        let mut record = Record::new(leader);
+        record.add_control_field_str("001", "ocm123");
+        record.add_control_field_str("007", "ta");
+        record.add_control_field_str("007", "cr");
+        record.add_control_field_str("008", "200101s1925    xxu           000 0 eng d");
+        let mut f264 = Field::new("264".to_string(), ' ', '4');
+        f264.add_subfield_str('c', "©1924");
+        record.add_field(f264);
+        let mut f264 = Field::new("264".to_string(), ' ', '1');
+        f264.add_subfield_str('a', "New York :");
+        f264.add_subfield_str('b', "Scribner,");
+        f264.add_subfield_str('c', "1925.");
+        record.add_field(f264);
+        record
+    }
+
+    #[test]
+    fn test_fallback_chain_skips_missing_fields() {
+        let ex = Extractor::new("260$c | 264[*1]$c | 008/7-10").unwrap();
+        assert_eq!(ex.first(&record()), Some("1925.".to_string()));
+    }
+
+    #[test]
+    fn test_control_field_slice() {
+        let ex = Extractor::new("008/35-37").unwrap();
+        assert_eq!(ex.first(&record()), Some("eng".to_string()));
+        let ex = Extractor::new("LDR/6").unwrap();
+        assert_eq!(ex.first(&record()), Some("a".to_string()));
+        let ex = Extractor::new(&format!("008/35-{}", usize::MAX)).unwrap();
+        assert_eq!(ex.first(&record()), Some("eng d".to_string()));
+    }
+
+    #[test]
+    fn test_blank_and_fill_values_fall_through() {
+        let mut rec = record();
+        rec.control_fields.shift_remove("008");
+        rec.add_control_field_str("008", "200101s||||    xxu           000 0 eng d");
+        let ex = Extractor::new("008/7-10 | 008/11-14 | 264$c").unwrap();
+        assert_eq!(ex.first(&rec), Some("©1924".to_string()));
+    }
+
+    #[test]
+    fn test_all_returns_values_of_first_matching_selector() {
+        let ex = Extractor::new("260$c | 264$c").unwrap();
+        assert_eq!(ex.all(&record()), vec!["©1924", "1925."]);
+        let ex = Extractor::new("007/0").unwrap();
+        assert_eq!(ex.all(&record()), vec!["t", "c"]);
+    }
+
+    #[test]
+    fn test_multiple_codes_and_whole_field() {
+        let ex = Extractor::new("264[ 1]$ab").unwrap();
+        assert_eq!(
+            ex.first(&record()),
+            Some("New York : Scribner,".to_string())
+        );
+        let ex = Extractor::new("264[ 1]").unwrap();
+        assert_eq!(
+            ex.first(&record()),
+            Some("New York : Scribner, 1925.".to_string())
+        );
+        let ex = Extractor::new("264[ 1]$c/0-3").unwrap();
+        assert_eq!(ex.first(&record()), Some("1925".to_string()));
+    }
+
+    #[test]
+    fn test_no_match_returns_none() {
+        let ex = Extractor::new("260$c | 020$a").unwrap();
+        assert_eq!(ex.first(&record()), None);
+        assert!(ex.all(&record()).is_empty());
+    }
+
+    #[test]
+    fn test_display_round_trips() {
+        let expr = "260$c | 264[*1]$c | 008/7-10 | LDR/6";
+        let ex: Extractor = expr.parse().unwrap();
+        assert_eq!(ex.to_string(), expr);
+        assert_eq!(Extractor::new(&ex.to_string()).unwrap(), ex);
+    }
+
+    #[test]
+    fn test_invalid_expressions() {
+        for bad in [
+            "", "24$a", "245$", "008$a", "008/10-7", "008/x", "245[1]$a", "245[10$a", "LDR$a",
+            "260$c ||",
+        ] {
+            assert!(Extractor::new(bad).is_err(), "expected error for {bad:?}");
+        }
+    }
+}
@@ -0,0 +1,365 @@
+//! Lossless round trips of ISO 2709 records.
+//!
+//! A record read with [`MarcReader::read_record`](crate::MarcReader::read_record)
+//! and written with [`MarcWriter::write_record`](crate::MarcWriter::write_record)
+//! is normalized on the way out: control fields are written before data
+//! fields, fields of a tag are grouped at the position of the tag's first
+//! occurrence, invalid UTF-8 is written as it was decoded, and the leader's
+//! lengths are recomputed. The content survives; the bytes may not.
+//!
+//! [`MarcReader::read_lossless`](crate::MarcReader::read_lossless) keeps the
+//! bytes. It returns each [`Record`] together with a [`SourceRecord`]
+//! holding the bytes it was read from, and
+//! [`MarcWriter::write_lossless`](crate::MarcWriter::write_lossless) uses
+//! them:
+//!
+//! - A record that has not been changed since it was read is written back
+//!   byte for byte, whatever its directory order, duplicate control fields,
+//!   field contents or leader values.
+//! - A changed record is written in its original directory order, with
+//!   every field that still has its original content copied from the
+//!   original bytes. Fields that are new or changed are encoded as usual;
+//!   new fields follow the original ones in the record's own order.
+//!
+//! Whether a record or field has changed is decided by comparing its
+//! content with a hash taken when the record was read, so edits through any
+//! API — including direct changes to [`Record::fields`] — are noticed.
+//!
+//! Only [`MarcReader`](crate::MarcReader) and
+//! [`MarcWriter`](crate::MarcWriter) round-trip losslessly; other readers
+//! and writers, and the MARCXML and JSON formats, always normalize.
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::{MarcReader, MarcWriter};
+//! use std::io::Cursor;
+//!
+//! let original = std::fs::read("tests/data/multi_records.mrc").unwrap();
+//! let mut reader = MarcReader::new(Cursor::new(&original));
+//! let mut output = Vec::new();
+//! let mut writer = MarcWriter::new(&mut output);
+//! while let Some((record, source)) = reader.read_lossless().unwrap() {
+//!     writer.write_lossless(&record, &source).unwrap();
+//! }
+//! assert_eq!(output, original);
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::Arc;
+
+use crate::fingerprint::FingerprintConfig;
+use crate::iso2709::{
+    DIRECTORY_ENTRY_LEN, LEADER_LEN, is_control_field_tag, parse_directory_entry,
+};
+use crate::leader::Leader;
+use crate::record::{Field, Record};
+
+/// The bytes a record was read from, returned by
+/// [`MarcReader::read_lossless`](crate::MarcReader::read_lossless).
+#[derive(Clone)]
+pub struct SourceRecord {
+    bytes: Arc<Vec<u8>>,
+    leader: Leader,
+    fingerprint: u64,
+    entries: Vec<SourceEntry>,
+}
+
+/// One directory entry of the original record, tied to the record field it
+/// was parsed into.
+#[derive(Debug, Clone)]
+struct SourceEntry {
+    tag: String,
+    control: bool,
+    /// Occurrence of the tag in `Record::control_fields` or `Record::fields`.
+    index: usize,
+    /// Range of the field (with its terminator) in the original bytes.
+    start: usize,
+    end: usize,
+    hash: u64,
+}
+
+/// A field to write, in the order a lossless writer wants it.
+pub(crate) enum SourceSlot<'a> {
+    /// Original bytes of an unchanged field, terminator included.
+    Raw(&'a str, &'a [u8]),
+    /// A control field to encode.
+    Control(&'a str, &'a str),
+    /// A data field to encode.
+    Data(&'a str, &'a Field),
+}
+
+impl SourceRecord {
+    /// Remember `bytes` as the source of `record`, which was just parsed
+    /// from them.
+    ///
+    /// Directory entries that cannot be matched to a field of `record` (as
+    /// after lenient recovery) are dropped; those fields are then encoded
+    /// rather than copied when the record is written after a change.
+    pub(crate) fn capture(bytes: Arc<Vec<u8>>, record: &Record) -> Self {
+        let entries = directory_entries(&bytes, record).unwrap_or_default();
+        SourceRecord {
+            leader: record.leader.clone(),
+            fingerprint: fingerprint(record),
+            bytes,
+            entries,
+        }
+    }
+
+    /// The original record bytes: leader, directory, data and record
+    /// terminator.
+    #[must_use]
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Whether `record` still has the content it was read with.
+    #[must_use]
+    pub fn is_unchanged(&self, record: &Record) -> bool {
+        record.leader == self.leader && fingerprint(record) == self.fingerprint
+    }
+
+    /// Call `f` with every field of `record`: first the original directory
+    /// entries that still exist (as raw bytes when unchanged), then fields
+    /// added since the record was read.
+    pub(crate) fn for_each_slot<E>(
+        &self,
+        record: &Record,
+        mut f: impl FnMut(SourceSlot<'_>) -> Result<(), E>,
+    ) -> Result<(), E> {
+        let mut written: HashSet<(bool, &str, usize)> = HashSet::new();
+        for entry in &self.entries {
+            let slot = if entry.control {
+                let Some(value) = record
+                    .control_fields
+                    .get(entry.tag.as_str())
+                    .and_then(|values| values.get(entry.index))
+                else {
+                    continue;
+                };
+                if control_hash(&entry.tag, value) == entry.hash {
+                    SourceSlot::Raw(&entry.tag, &self.bytes[entry.start..entry.end])
+                } else {
+                    SourceSlot::Control(&entry.tag, value)
+                }
+            } else {
+                let Some(field) = record
+                    .fields
+                    .get(entry.tag.as_str())
+                    .and_then(|fields| fields.get(entry.index))
+                else {
+                    continue;
+                };
+                if field_hash(field) == entry.hash {
+                    SourceSlot::Raw(&entry.tag, &self.bytes[entry.start..entry.end])
+                } else {
+                    SourceSlot::Data(&entry.tag, field)
+                }
+            };
+            written.insert((entry.control, &entry.tag, entry.index));
+            f(slot)?;
+        }
+
+        for (tag, values) in &record.control_fields {
+            if tag.as_str() < "010" {
+                for (index, value) in values.iter().enumerate() {
+                    if !written.contains(&(true, tag.as_str(), index)) {
+                        f(SourceSlot::Control(tag, value))?;
+                    }
+                }
+            }
+        }
+        for (tag, fields) in &record.fields {
+            for (index, field) in fields.iter().enumerate() {
+                if !written.contains(&(false, tag.as_str(), index)) {
+                    f(SourceSlot::Data(tag, field))?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for SourceRecord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SourceRecord")
+            .field("len", &self.bytes.len())
+            .field("entries", &self.entries.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Tie each directory entry of `bytes` to the field of `record` parsed from
+/// it. `None` if the directory cannot be walked.
+fn directory_entries(bytes: &[u8], record: &Record) -> Option<Vec<SourceEntry>> {
+    let base = usize::try_from(record.leader.data_base_address).ok()?;
+    let directory = bytes.get(LEADER_LEN..base.checked_sub(1)?)?;
+    let mut occurrences: HashMap<String, usize> = HashMap::new();
+    let mut entries = Vec::with_capacity(directory.len() / DIRECTORY_ENTRY_LEN);
+
+    for chunk in directory.chunks_exact(DIRECTORY_ENTRY_LEN) {
+        let entry = parse_directory_entry(chunk).ok()?;
+        let start = base + entry.start;
+        let end = start + entry.length;
+        if end > bytes.len() {
+            continue;
+        }
+        let control = is_control_field_tag(&entry.tag);
+        let count = occurrences.entry(entry.tag.clone()).or_default();
+        let index = *count;
+        *count += 1;
+        let hash = if control {
+            let values = record.control_fields.get(entry.tag.as_str());
+            match values.and_then(|values| values.get(index)) {
+                Some(value) => control_hash(&entry.tag, value),
+                None => continue,
+            }
+        } else {
+            let fields = record.fields.get(entry.tag.as_str());
+            match fields.and_then(|fields| fields.get(index)) {
+                Some(field) => field_hash(field),
+                None => continue,
+            }
+        };
+        entries.push(SourceEntry {
+            tag: entry.tag,
+            control,
+            index,
+            start,
+            end,
+            hash,
+        });
+    }
+    Some(entries)
+}
+
+fn control_hash(tag: &str, value: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    tag.hash(&mut hasher);
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn field_hash(field: &Field) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    hash_field(field, &mut hasher);
+    hasher.finish()
+}
+
+fn hash_field(field: &Field, hasher: &mut DefaultHasher) {
+    field.tag.hash(hasher);
+    field.indicator1.hash(hasher);
+    field.indicator2.hash(hasher);
+    for subfield in &field.subfields {
+        subfield.code.hash(hasher);
+        subfield.value.hash(hasher);
+    }
+}
+
+/// The fingerprint of the leader and every field of `record`.
+fn fingerprint(record: &Record) -> u64 {
+    crate::fingerprint::fingerprint(record, &FingerprintConfig::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MarcReader, MarcWriter};
+    use std::io::Cursor;
+
+    /// Assemble an ISO 2709 record with fields in exactly the given order.
+    fn assemble(fields: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut directory = Vec::new();
+        let mut data = Vec::new();
+        for (tag, body) in fields {
+            directory.extend_from_slice(
+                format!("{tag}{:04}{:05}", body.len() + 1, data.len()).as_bytes(),
+            );
+            data.extend_from_slice(body);
+            data.push(0x1E);
+        }
+        directory.push(0x1E);
+        let base = LEADER_LEN + directory.len();
+        let length = base + data.len() + 1;
+        let mut bytes = format!("{length:05}nam a22{base:05}   4500").into_bytes();
+        bytes.extend(directory);
+        bytes.extend(data);
+        bytes.push(0x1D);
+        bytes
+    }
+
+    fn read(bytes: &[u8]) -> (Record, SourceRecord) {
+        MarcReader::new(Cursor::new(bytes))
+            .read_lossless()
+            .unwrap()
+            .unwrap()
+    }
+
+    fn write(record: &Record, source: Option<&SourceRecord>) -> Vec<u8> {
+        let mut out = Vec::new();
+        let mut writer = MarcWriter::new(&mut out);
+        match source {
+            Some(source) => writer.write_lossless(record, source).unwrap(),
+            None => writer.write_record(record).unwrap(),
+        }
+        out
+    }
+
+    fn tags(bytes: &[u8]) -> Vec<String> {
+        let (_, source) = read(bytes);
+        source.entries.iter().map(|e| e.tag.clone()).collect()
+    }
+
+    #[test]
+    fn test_unchanged_record_is_byte_exact() {
+        let original = assemble(&[
+            ("245", b"10\x1faTitle."),
+            ("001", b"ocm1"),
+            ("007", b"ta"),
+            ("650", b" 0\x1faCats."),
+            ("007", b"cr"),
+            ("005", b"20240101000000.0"),
+        ]);
+        let (record, source) = read(&original);
+        assert_eq!(record.control_fields.get("007").unwrap(), &["ta", "cr"]);
+        assert_eq!(write(&record, Some(&source)), original);
+
+        // Without the source the order is normalized.
+        let normalized = write(&record, None);
+        assert_ne!(normalized, original);
+        assert_eq!(
+            tags(&normalized),
+            ["001", "007", "007", "005", "245", "650"]
+        );
+    }
+
+    #[test]
+    fn test_changed_record_keeps_order_and_untouched_bytes() {
+        // Invalid UTF-8 in the 500 decodes lossily; it must survive a
+        // change elsewhere in the record.
+        let original = assemble(&[
+            ("245", b"10\x1faTitle."),
+            ("001", b"ocm1"),
+            ("500", b"  \x1faCaf\xe9."),
+            ("650", b" 0\x1faCats."),
+        ]);
+        let (mut record, source) = read(&original);
+        record.get_fields_mut("650").unwrap()[0].subfields[0].value = "Dogs.".to_string();
+        record.add_field(
+            Field::builder("700".to_string(), '1', ' ')
+                .subfield_str('a', "Smith, J.")
+                .build(),
+        );
+        assert!(!source.is_unchanged(&record));
+
+        let written = write(&record, Some(&source));
+        assert_eq!(tags(&written), ["245", "001", "500", "650", "700"]);
+        assert!(written.windows(b"Caf\xe9.".len()).any(|w| w == b"Caf\xe9."));
+        let (reread, _) = read(&written);
+        assert_eq!(
+            reread.get_fields("650").unwrap()[0].get_subfield('a'),
+            Some("Dogs.")
+        );
+    }
+}
@@ -39,6 +39,17 @@ use std::sync::LazyLock;
 static LINKAGE_RE: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"^(\d{3})-(\d{2,3})(?:/([\(\$][A-Za-z0-9]))?(?:/r)?$").unwrap());
 
+/// MARC 21 script identification codes used in subfield 6, with the script
+/// each names.
+pub const SCRIPT_IDENTIFICATION_CODES: &[(&str, &str)] = &[
+    ("(3", "Arabic"),
+    ("(B", "Latin"),
+    ("$1", "Chinese, Japanese, Korean"),
+    ("(N", "Cyrillic"),
+    ("(S", "Greek"),
+    ("(2", "Hebrew"),
+];
+
 /// Information extracted from MARC subfield 6 (Linkage).
 ///
 /// This structure represents the parsed linkage information that connects
@@ -137,6 +148,16 @@ impl LinkageInfo {
         &self.script_id
     }
 
+    /// Whether the script identification code is absent or one of
+    /// [`SCRIPT_IDENTIFICATION_CODES`].
+    #[must_use]
+    pub fn has_known_script(&self) -> bool {
+        self.script_id.is_empty()
+            || SCRIPT_IDENTIFICATION_CODES
+                .iter()
+                .any(|(code, _)| *code == self.script_id)
+    }
+
     /// Check if reverse script flag is set.
     #[must_use]
     pub fn is_reverse(&self) -> bool {
@@ -0,0 +1,159 @@
+//! Field protection lists for overlays and exports.
+//!
+//! Local fields (9XX, 59X, holdings and processing data) must survive when a
+//! record is overlaid with a fresh copy from a vendor or utility, and are
+//! often stripped before records are shared. [`FieldProtection`] names
+//! those fields once, as tag patterns where `X` matches any digit, so that
+//! [`Record::overlay`](crate::Record::overlay) and
+//! [`Record::strip_fields`](crate::Record::strip_fields) agree on them.
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::field_protection::FieldProtection;
+//!
+//! let protection: FieldProtection = "9XX, 59X".parse().unwrap();
+//! assert!(protection.is_protected("949"));
+//! assert!(protection.is_protected("590"));
+//! assert!(!protection.is_protected("500"));
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{MarcError, Result};
+
+/// A list of tag patterns whose fields are protected from overlay.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldProtection {
+    patterns: Vec<String>,
+}
+
+impl FieldProtection {
+    /// An empty protection list (nothing is protected).
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The conventional MARC 21 local-use blocks: `09X`, `59X`, `69X` and
+    /// `9XX`.
+    #[must_use]
+    pub fn local_fields() -> Self {
+        FieldProtection {
+            patterns: vec![
+                "09X".to_string(),
+                "59X".to_string(),
+                "69X".to_string(),
+                "9XX".to_string(),
+            ],
+        }
+    }
+
+    /// A list of known-valid, uppercase tag patterns.
+    pub(crate) fn from_patterns(patterns: &[&str]) -> Self {
+        FieldProtection {
+            patterns: patterns.iter().map(|p| (*p).to_string()).collect(),
+        }
+    }
+
+    /// Add a tag pattern (`"949"`, `"9XX"`, `"59X"`).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MarcError::InvalidField`] if `pattern` is not three
+    /// characters of digits and `X`.
+    pub fn protect(mut self, pattern: &str) -> Result<Self> {
+        let pattern = pattern.trim();
+        if pattern.len() != 3
+            || !pattern
+                .chars()
+                .all(|c| c.is_ascii_digit() || matches!(c, 'X' | 'x'))
+        {
+            return Err(MarcError::invalid_field_msg(format!(
+                "Invalid tag pattern '{pattern}': expected three digits or X"
+            )));
+        }
+        self.patterns.push(pattern.to_ascii_uppercase());
+        Ok(self)
+    }
+
+    /// The protected tag patterns.
+    #[must_use]
+    pub fn patterns(&self) -> &[String] {
+        &self.patterns
+    }
+
+    /// Whether no tags are protected.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// Whether fields with `tag` are protected.
+    #[must_use]
+    pub fn is_protected(&self, tag: &str) -> bool {
+        self.patterns.iter().any(|p| tag_matches(p, tag))
+    }
+}
+
+impl FromStr for FieldProtection {
+    type Err = MarcError;
+
+    /// Parse a comma- or whitespace-separated list of tag patterns.
+    fn from_str(s: &str) -> Result<Self> {
+        s.split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|p| !p.is_empty())
+            .try_fold(FieldProtection::new(), FieldProtection::protect)
+    }
+}
+
+impl fmt::Display for FieldProtection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.patterns.join(","))
+    }
+}
+
+/// Whether `tag` matches a tag pattern in which `X` (or `x`) matches any
+/// character.
+pub(crate) fn tag_matches(pattern: &str, tag: &str) -> bool {
+    pattern.len() == tag.len()
+        && pattern
+            .chars()
+            .zip(tag.chars())
+            .all(|(p, t)| p == t || matches!(p, 'X' | 'x'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_display() {
+        let protection: FieldProtection = "9xx,59X 001".parse().unwrap();
+        assert_eq!(protection.patterns(), ["9XX", "59X", "001"]);
+        assert_eq!(protection.to_string(), "9XX,59X,001");
+        assert!(protection.is_protected("001"));
+        assert!(!protection.is_protected("003"));
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        assert!("9XXX".parse::<FieldProtection>().is_err());
+        assert!("9A1".parse::<FieldProtection>().is_err());
+        assert!("".parse::<FieldProtection>().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_local_fields() {
+        let protection = FieldProtection::local_fields();
+        for tag in ["090", "590", "690", "949", "999"] {
+            assert!(protection.is_protected(tag), "{tag}");
+        }
+        for tag in ["245", "650", "856"] {
+            assert!(!protection.is_protected(tag), "{tag}");
+        }
+    }
+}
@@ -0,0 +1,512 @@
+//! Renumbering data fields by tag, for local-field migrations.
+//!
+//! Every ILS migration moves local fields somewhere new: 590 notes become
+//! 990s, a vendor's 9XX block shifts to 94X, a retired 69X scheme is
+//! folded into 690. A [`FieldRemapper`] holds an ordered list of
+//! [`FieldMapping`]s; each matches source tags, names the target tag, and
+//! can replace the indicators and prefix a subfield's text. The first
+//! mapping that matches a field wins, and fields no mapping matches are
+//! left alone.
+//!
+//! Source tags are written as an exact tag (`590`), a pattern with `X` for
+//! any digit (`59X`), or an inclusive range (`900-949`). A target is an
+//! exact tag or a pattern whose `X` positions copy the source tag's digit
+//! in the same position, so `59X` → `99X` sends 591 to 991. Control fields
+//! (`00X`) cannot be remapped.
+//!
+//! # Definition format
+//!
+//! Definitions are JSON, or TOML with the `toml` cargo feature:
+//!
+//! ```toml
+//! name = "Legacy ILS local fields"
+//!
+//! [[mapping]]
+//! source = "590"
+//! target = "990"
+//! prefix = "LOCAL: "
+//!
+//! [[mapping]]
+//! source = "69X"
+//! target = "690"
+//! ind2 = "4"
+//! ```
+//!
+//! `ind1` and `ind2` replace the indicators (omitted, they are kept);
+//! `prefix` is prepended to every `prefix_subfield` (default `a`). The JSON
+//! form has the same shape, with the mappings in a `"mapping"` array.
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::field_remap::{FieldMapping, FieldRemapper};
+//! use mrrc::{Field, Leader, Record};
+//!
+//! let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+//! record.add_field(
+//!     Field::builder("590".to_string(), ' ', ' ')
+//!         .subfield_str('a', "Signed by the author.")
+//!         .build(),
+//! );
+//!
+//! let remapper = FieldRemapper::new("local")
+//!     .mapping(FieldMapping::new("590", "990")?.with_prefix('a', "LOCAL: "));
+//! assert_eq!(remapper.remap_record(&mut record), 1);
+//!
+//! let field = record.get_field("990").unwrap();
+//! assert_eq!(field.get_subfield('a'), Some("LOCAL: Signed by the author."));
+//! assert!(record.get_field("590").is_none());
+//! # Ok::<(), mrrc::MarcError>(())
+//! ```
+
+use std::fmt::{self, Write};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{MarcError, Result};
+use crate::record::{Field, Record};
+
+/// Source tags a [`FieldMapping`] applies to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TagMatch {
+    /// A tag, with `None` for `X` positions.
+    Pattern([Option<u8>; 3]),
+    /// An inclusive range of numeric tags.
+    Range(u16, u16),
+}
+
+impl TagMatch {
+    fn parse(source: &str) -> Result<Self> {
+        let matcher = if let Some((low, high)) = source.split_once('-') {
+            let (low, high) = (parse_tag_number(low)?, parse_tag_number(high)?);
+            if low > high {
+                return Err(invalid_tag(source, "range is reversed"));
+            }
+            TagMatch::Range(low, high)
+        } else {
+            TagMatch::Pattern(parse_pattern(source)?)
+        };
+        let lowest = match &matcher {
+            TagMatch::Range(low, _) => *low,
+            TagMatch::Pattern(digits) => digits
+                .iter()
+                .fold(0, |n, d| n * 10 + u16::from(d.unwrap_or(b'0') - b'0')),
+        };
+        if lowest < 10 {
+            return Err(invalid_tag(source, "control fields cannot be remapped"));
+        }
+        Ok(matcher)
+    }
+
+    fn matches(&self, tag: &str) -> bool {
+        match self {
+            TagMatch::Pattern(digits) => {
+                tag.len() == 3
+                    && tag
+                        .bytes()
+                        .zip(digits)
+                        .all(|(b, d)| d.is_none_or(|d| d == b))
+            },
+            TagMatch::Range(low, high) => {
+                tag.len() == 3
+                    && tag
+                        .parse::<u16>()
+                        .is_ok_and(|n| (*low..=*high).contains(&n))
+            },
+        }
+    }
+}
+
+impl fmt::Display for TagMatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TagMatch::Pattern(digits) => digits
+                .iter()
+                .try_for_each(|d| f.write_char(d.map_or('X', char::from))),
+            TagMatch::Range(low, high) => write!(f, "{low:03}-{high:03}"),
+        }
+    }
+}
+
+fn parse_tag_number(tag: &str) -> Result<u16> {
+    match parse_pattern(tag)? {
+        [Some(a), Some(b), Some(c)] => Ok([a, b, c]
+            .iter()
+            .fold(0, |n, d| n * 10 + u16::from(d - b'0'))),
+        _ => Err(invalid_tag(tag, "range ends must be exact tags")),
+    }
+}
+
+fn parse_pattern(tag: &str) -> Result<[Option<u8>; 3]> {
+    let bytes = tag.trim().as_bytes();
+    if bytes.len() != 3 {
+        return Err(invalid_tag(tag, "expected three digits or X"));
+    }
+    let mut digits = [None; 3];
+    for (slot, &b) in digits.iter_mut().zip(bytes) {
+        *slot = match b {
+            b'0'..=b'9' => Some(b),
+            b'X' | b'x' => None,
+            _ => return Err(invalid_tag(tag, "expected three digits or X")),
+        };
+    }
+    Ok(digits)
+}
+
+fn invalid_tag(tag: &str, reason: &str) -> MarcError {
+    MarcError::invalid_field_msg(format!("Invalid remap tag '{tag}': {reason}"))
+}
+
+/// One source-to-target tag mapping.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "MappingDef", into = "MappingDef")]
+pub struct FieldMapping {
+    source: TagMatch,
+    target: [Option<u8>; 3],
+    /// Replacement first indicator; `None` keeps the field's own.
+    pub indicator1: Option<char>,
+    /// Replacement second indicator; `None` keeps the field's own.
+    pub indicator2: Option<char>,
+    /// Subfield code and text prepended to each occurrence of it.
+    pub prefix: Option<(char, String)>,
+}
+
+/// Serialized form of a [`FieldMapping`].
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MappingDef {
+    source: String,
+    target: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ind1: Option<char>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ind2: Option<char>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    prefix: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    prefix_subfield: Option<char>,
+}
+
+impl TryFrom<MappingDef> for FieldMapping {
+    type Error = MarcError;
+
+    fn try_from(def: MappingDef) -> Result<Self> {
+        let mut mapping = FieldMapping::new(&def.source, &def.target)?;
+        mapping.indicator1 = def.ind1;
+        mapping.indicator2 = def.ind2;
+        mapping.prefix = def
+            .prefix
+            .map(|text| (def.prefix_subfield.unwrap_or('a'), text));
+        Ok(mapping)
+    }
+}
+
+impl From<FieldMapping> for MappingDef {
+    fn from(mapping: FieldMapping) -> Self {
+        let target = mapping.target_pattern();
+        let (prefix_subfield, prefix) = match mapping.prefix {
+            Some((code, text)) => (Some(code).filter(|&c| c != 'a'), Some(text)),
+            None => (None, None),
+        };
+        MappingDef {
+            source: mapping.source.to_string(),
+            target,
+            ind1: mapping.indicator1,
+            ind2: mapping.indicator2,
+            prefix,
+            prefix_subfield,
+        }
+    }
+}
+
+impl FieldMapping {
+    /// Map fields matching `source` to `target`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MarcError::InvalidField`] if either tag is malformed or
+    /// could name a control field.
+    pub fn new(source: &str, target: &str) -> Result<Self> {
+        let source = TagMatch::parse(source)?;
+        TagMatch::parse(target)?;
+        Ok(FieldMapping {
+            source,
+            target: parse_pattern(target)?,
+            indicator1: None,
+            indicator2: None,
+            prefix: None,
+        })
+    }
+
+    /// Replace the indicators of remapped fields.
+    #[must_use]
+    pub fn with_indicators(mut self, indicator1: char, indicator2: char) -> Self {
+        self.indicator1 = Some(indicator1);
+        self.indicator2 = Some(indicator2);
+        self
+    }
+
+    /// Prepend `text` to every subfield `code` of remapped fields.
+    #[must_use]
+    pub fn with_prefix(mut self, code: char, text: &str) -> Self {
+        self.prefix = Some((code, text.to_string()));
+        self
+    }
+
+    /// Whether this mapping applies to fields tagged `tag`.
+    #[must_use]
+    pub fn matches(&self, tag: &str) -> bool {
+        self.source.matches(tag)
+    }
+
+    /// The tag a field tagged `tag` is moved to.
+    #[must_use]
+    pub fn target_tag(&self, tag: &str) -> String {
+        self.target
+            .iter()
+            .zip(tag.bytes())
+            .map(|(d, b)| char::from(d.unwrap_or(b)))
+            .collect()
+    }
+
+    /// Apply the mapping to `field`, which must match.
+    pub fn apply(&self, field: &mut Field) {
+        field.tag = self.target_tag(&field.tag);
+        if let Some(indicator) = self.indicator1 {
+            field.indicator1 = indicator;
+        }
+        if let Some(indicator) = self.indicator2 {
+            field.indicator2 = indicator;
+        }
+        if let Some((code, text)) = &self.prefix {
+            for subfield in field.subfields_by_code_mut(*code) {
+                subfield.value.insert_str(0, text);
+            }
+        }
+    }
+
+    fn target_pattern(&self) -> String {
+        TagMatch::Pattern(self.target).to_string()
+    }
+}
+
+/// A named, ordered set of field mappings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldRemapper {
+    /// Remapper name, for messages.
+    pub name: String,
+    /// Mappings, tried in order.
+    #[serde(default, rename = "mapping")]
+    pub mappings: Vec<FieldMapping>,
+}
+
+impl FieldRemapper {
+    /// Create a remapper with no mappings.
+    #[must_use]
+    pub fn new(name: &str) -> Self {
+        FieldRemapper {
+            name: name.to_string(),
+            mappings: Vec::new(),
+        }
+    }
+
+    /// Add a mapping after the existing ones.
+    #[must_use]
+    pub fn mapping(mut self, mapping: FieldMapping) -> Self {
+        self.mappings.push(mapping);
+        self
+    }
+
+    /// Parse a remapper from TOML (see the [module documentation](self)).
+    /// Requires the `toml` cargo feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MarcError::InvalidField`] if the TOML is malformed or
+    /// contains an invalid tag.
+    #[cfg(feature = "toml")]
+    pub fn from_toml(source: &str) -> Result<Self> {
+        toml::from_str(source).map_err(|e| {
+            MarcError::invalid_field_msg(format!("Invalid field remapping definition: {e}"))
+        })
+    }
+
+    /// Parse a remapper from JSON, in the same shape as the TOML
+    /// definition (see the [module documentation](self)).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MarcError::InvalidField`] if the JSON is malformed or
+    /// contains an invalid tag.
+    pub fn from_json(source: &str) -> Result<Self> {
+        serde_json::from_str(source).map_err(|e| {
+            MarcError::invalid_field_msg(format!("Invalid field remapping definition: {e}"))
+        })
+    }
+
+    /// Serialize the remapper as TOML. Requires the `toml` cargo feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MarcError::InvalidField`] if serialization fails.
+    #[cfg(feature = "toml")]
+    pub fn to_toml(&self) -> Result<String> {
+        toml::to_string(self).map_err(|e| {
+            MarcError::invalid_field_msg(format!("Cannot serialize field remapping: {e}"))
+        })
+    }
+
+    /// The first mapping that applies to `tag`.
+    #[must_use]
+    pub fn mapping_for(&self, tag: &str) -> Option<&FieldMapping> {
+        self.mappings.iter().find(|m| m.matches(tag))
+    }
+
+    /// Remap the fields of `record` and return how many moved.
+    ///
+    /// A remapped field goes after any fields already carrying its new
+    /// tag, and tags are re-sorted when anything moved.
+    pub fn remap_record(&self, record: &mut Record) -> usize {
+        if !record
+            .fields
+            .keys()
+            .any(|tag| self.mapping_for(tag).is_some())
+        {
+            return 0;
+        }
+        let mut moved = 0;
+        let mut kept = Vec::new();
+        for (tag, fields) in std::mem::take(&mut record.fields) {
+            match self.mapping_for(&tag) {
+                Some(mapping) => {
+                    for mut field in fields {
+                        mapping.apply(&mut field);
+                        kept.push(field);
+                        moved += 1;
+                    }
+                },
+                None => {
+                    record.fields.insert(tag, fields);
+                },
+            }
+        }
+        for field in kept {
+            record.add_field(field);
+        }
+        record.fields.sort_keys();
+        moved
+    }
+
+    /// Remap every record of a stream as it is read, passing errors
+    /// through.
+    pub fn remap_all<'a, I>(&'a self, records: I) -> impl Iterator<Item = Result<Record>> + 'a
+    where
+        I: IntoIterator<Item = Result<Record>>,
+        I::IntoIter: 'a,
+    {
+        records.into_iter().map(move |record| {
+            record.map(|mut record| {
+                self.remap_record(&mut record);
+                record
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::leader::Leader;
+
+    fn record(tags: &[&str]) -> Record {
+        let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+        for tag in tags {
+            record.add_field(
+                Field::builder((*tag).to_string(), '1', ' ')
+                    .subfield_str('a', tag)
+                    .build(),
+            );
+        }
+        record
+    }
+
+    #[test]
+    fn tag_patterns_and_ranges() {
+        let shift = FieldMapping::new("59X", "99X").unwrap();
+        assert!(shift.matches("591") && !shift.matches("600"));
+        assert_eq!(shift.target_tag("591"), "991");
+
+        let range = FieldMapping::new("900-949", "9X0").unwrap();
+        assert!(range.matches("949") && !range.matches("950"));
+        assert_eq!(range.target_tag("937"), "930");
+
+        assert!(FieldMapping::new("00X", "900").is_err());
+        assert!(FieldMapping::new("590", "005").is_err());
+        assert!(FieldMapping::new("950-900", "990").is_err());
+        assert!(FieldMapping::new("5A0", "990").is_err());
+    }
+
+    #[test]
+    fn remap_record_moves_fields_in_order() {
+        let remapper = FieldRemapper::new("t")
+            .mapping(
+                FieldMapping::new("590", "990")
+                    .unwrap()
+                    .with_prefix('a', "LOCAL: "),
+            )
+            .mapping(
+                FieldMapping::new("5XX", "500")
+                    .unwrap()
+                    .with_indicators(' ', ' '),
+            );
+        let mut record = record(&["245", "590", "990", "546", "590"]);
+        assert_eq!(remapper.remap_record(&mut record), 3);
+
+        let tags: Vec<&str> = record.fields().map(|f| f.tag.as_str()).collect();
+        assert_eq!(tags, ["245", "500", "990", "990", "990"]);
+        let values: Vec<&str> = record
+            .fields()
+            .filter_map(|f| f.get_subfield('a'))
+            .collect();
+        assert_eq!(values, ["245", "546", "990", "LOCAL: 590", "LOCAL: 590"]);
+        assert_eq!(record.get_field("500").unwrap().indicator1, ' ');
+        assert_eq!(record.get_field("990").unwrap().indicator1, '1');
+        assert_eq!(remapper.remap_record(&mut self::record(&["245"])), 0);
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn toml_round_trip_and_errors() {
+        let remapper = FieldRemapper::from_toml(
+            r#"
+            name = "legacy"
+            [[mapping]]
+            source = "590"
+            target = "990"
+            prefix = "LOCAL: "
+            [[mapping]]
+            source = "69x"
+            target = "690"
+            ind2 = "4"
+            prefix = "old "
+            prefix_subfield = "x"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            remapper.mappings[0].prefix,
+            Some(('a', "LOCAL: ".to_string()))
+        );
+        assert_eq!(remapper.mappings[1].indicator2, Some('4'));
+        assert_eq!(remapper.mappings[1].prefix.as_ref().unwrap().0, 'x');
+
+        let reparsed = FieldRemapper::from_toml(&remapper.to_toml().unwrap()).unwrap();
+        assert_eq!(reparsed, remapper);
+
+        let err = FieldRemapper::from_json(
+            r#"{"name": "bad", "mapping": [{"source": "0XX", "target": "900"}]}"#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("control fields"), "{err}");
+    }
+}
@@ -0,0 +1,584 @@
+//! Consistency between the leader and the 008 fixed-length data elements.
+//!
+//! Positions 18-34 of a bibliographic 008 mean different things for
+//! different kinds of material: in a book they code illustrations, target
+//! audience and nature of contents; in a serial, frequency, regularity and
+//! type of continuing resource. Which layout applies is decided by leader/06
+//! (type of record) and leader/07 (bibliographic level), as
+//! [`MaterialConfiguration::from_leader`]. Records migrated between systems
+//! often keep an 008 built for another layout, or one of the wrong length,
+//! and typed readers of 008 then return nonsense.
+//!
+//! [`check_record`] reports such problems and [`fix_record`] repairs them:
+//! an 008 of the wrong length is rebuilt from a [`skeleton`], keeping the
+//! positions every layout shares (0-17 and 35-39); coded values not valid for the configuration are
+//! replaced with the fill character `|` ("no attempt to code").
+//!
+//! | Configuration          | Leader/06        | Leader/07       |
+//! |------------------------|------------------|-----------------|
+//! | Books                  | `a`, `t`         | `a` `c` `d` `m` |
+//! | Continuing resources   | `a`              | `b` `i` `s`     |
+//! | Computer files         | `m`              | any             |
+//! | Maps                   | `e`, `f`         | any             |
+//! | Music                  | `c` `d` `i` `j`  | any             |
+//! | Visual materials       | `g` `k` `o` `r`  | any             |
+//! | Mixed materials        | `p`              | any             |
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::fixed_field::{check_record, fix_record, FixedFieldIssueKind};
+//! use mrrc::{Leader, Record};
+//!
+//! // A serial leader with a book 008 (illustrations "af" in 18-19).
+//! let mut record = Record::new(Leader::from_bytes(b"00000cas a2200000 a 4500").unwrap());
+//! record.add_control_field_str("008", "850101c19859999nyuaf         000 0 eng d");
+//! let issues = check_record(&record);
+//! assert!(issues.iter().any(|issue| issue.kind
+//!     == FixedFieldIssueKind::InvalidCode { position: 19, value: 'f' }));
+//!
+//! fix_record(&mut record);
+//! assert!(check_record(&record).is_empty());
+//! assert_eq!(&record.get_control_field("008").unwrap()[18..20], "a|");
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::leader::Leader;
+use crate::record::Record;
+
+/// Length of a bibliographic 008.
+pub const FIXED_FIELD_LEN: usize = 40;
+
+/// Government publication codes (008/28), shared by several layouts.
+const GOVERNMENT: &str = " acfilmosuz|";
+/// Form of item codes, shared by several layouts.
+const FORM_OF_ITEM: &str = " abcdfoqrs|";
+/// Target audience codes, shared by several layouts.
+const AUDIENCE: &str = " abcdefgj|";
+/// Nature of contents codes.
+const NATURE_OF_CONTENTS: &str = " abcdefgijklmnopqrstuvwyz256|";
+/// An undefined position: blank or fill.
+const UNDEFINED: &str = " |";
+/// No or yes, as `0` and `1`.
+const YES_NO: &str = "01|";
+
+/// Coded positions every configuration shares. Date entered (0-5) is
+/// checked separately; dates, place and language are left to other checks.
+const COMMON_CODES: &[(usize, usize, &str)] = &[
+    (6, 6, "bcdeikmnpqrstu|"),
+    (38, 38, " dorsx|"),
+    (39, 39, " cdu|"),
+];
+
+const BOOK_CODES: &[(usize, usize, &str)] = &[
+    (18, 21, " abcdefghijklmop|"),
+    (22, 22, AUDIENCE),
+    (23, 23, FORM_OF_ITEM),
+    (24, 27, NATURE_OF_CONTENTS),
+    (28, 28, GOVERNMENT),
+    (29, 31, YES_NO),
+    (32, 32, UNDEFINED),
+    (33, 33, "01defhijmpsu|"),
+    (34, 34, " abcd|"),
+];
+
+const CONTINUING_RESOURCE_CODES: &[(usize, usize, &str)] = &[
+    (18, 18, " abcdefghijkmqstuwz|"),
+    (19, 19, "nrux|"),
+    (21, 21, " dglmnpw|"),
+    (22, 22, " abcdefoqs|"),
+    (23, 23, FORM_OF_ITEM),
+    (24, 27, NATURE_OF_CONTENTS),
+    (28, 28, GOVERNMENT),
+    (29, 29, YES_NO),
+    (30, 32, UNDEFINED),
+    (33, 33, " abcdefghijkluz|"),
+    (34, 34, "012|"),
+];
+
+const COMPUTER_FILE_CODES: &[(usize, usize, &str)] = &[
+    (18, 21, UNDEFINED),
+    (22, 22, AUDIENCE),
+    (23, 23, " oq|"),
+    (24, 25, UNDEFINED),
+    (26, 26, "abcdefghijmuz|"),
+    (27, 27, UNDEFINED),
+    (28, 28, GOVERNMENT),
+    (29, 34, UNDEFINED),
+];
+
+const MAP_CODES: &[(usize, usize, &str)] = &[
+    (18, 21, " abcdefgijkmz|"),
+    (24, 24, UNDEFINED),
+    (25, 25, "abcdefguz|"),
+    (26, 27, UNDEFINED),
+    (28, 28, GOVERNMENT),
+    (29, 29, FORM_OF_ITEM),
+    (30, 30, UNDEFINED),
+    (31, 31, YES_NO),
+    (32, 32, UNDEFINED),
+    (33, 34, " ejklnoprz|"),
+];
+
+const MUSIC_CODES: &[(usize, usize, &str)] = &[
+    (20, 20, "abcdeghijklmnpuz|"),
+    (21, 21, " defnu|"),
+    (22, 22, AUDIENCE),
+    (23, 23, FORM_OF_ITEM),
+    (24, 29, " abcdefghikrsz|"),
+    (30, 31, " abcdefghijklmnoprstz|"),
+    (32, 32, UNDEFINED),
+    (33, 33, " abcnu|"),
+    (34, 34, UNDEFINED),
+];
+
+const VISUAL_CODES: &[(usize, usize, &str)] = &[
+    (21, 21, UNDEFINED),
+    (22, 22, AUDIENCE),
+    (23, 27, UNDEFINED),
+    (28, 28, GOVERNMENT),
+    (29, 29, FORM_OF_ITEM),
+    (30, 32, UNDEFINED),
+    (33, 33, "abcdfgiklmnopqrstvwz|"),
+    (34, 34, "aclnuz|"),
+];
+
+const MIXED_CODES: &[(usize, usize, &str)] = &[
+    (18, 22, UNDEFINED),
+    (23, 23, FORM_OF_ITEM),
+    (24, 34, UNDEFINED),
+];
+
+/// The 008/18-34 layout that applies to a record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MaterialConfiguration {
+    /// BK - Language material and manuscript language material
+    Books,
+    /// CR - Serials and integrating resources
+    ContinuingResources,
+    /// CF - Computer files
+    ComputerFiles,
+    /// MP - Cartographic material
+    Maps,
+    /// MU - Music and sound recordings
+    Music,
+    /// VM - Visual materials
+    VisualMaterials,
+    /// MX - Mixed materials
+    MixedMaterials,
+}
+
+impl MaterialConfiguration {
+    /// The configuration for leader/06 and leader/07; `None` if the type of
+    /// record is not a bibliographic one.
+    #[must_use]
+    pub fn from_leader(leader: &Leader) -> Option<Self> {
+        Some(match leader.record_type {
+            'a' if matches!(leader.bibliographic_level, 'b' | 'i' | 's') => {
+                MaterialConfiguration::ContinuingResources
+            },
+            'a' | 't' => MaterialConfiguration::Books,
+            'm' => MaterialConfiguration::ComputerFiles,
+            'e' | 'f' => MaterialConfiguration::Maps,
+            'c' | 'd' | 'i' | 'j' => MaterialConfiguration::Music,
+            'g' | 'k' | 'o' | 'r' => MaterialConfiguration::VisualMaterials,
+            'p' => MaterialConfiguration::MixedMaterials,
+            _ => return None,
+        })
+    }
+
+    /// Default 008/18-34 for a new record: blanks, "no" and "unknown"
+    /// codes valid for the configuration.
+    #[must_use]
+    pub fn default_positions(self) -> &'static str {
+        match self {
+            MaterialConfiguration::Books => "           000 0 ",
+            MaterialConfiguration::ContinuingResources => " u         0    0",
+            MaterialConfiguration::ComputerFiles => "        u        ",
+            MaterialConfiguration::Maps => "       u     0   ",
+            MaterialConfiguration::Music => "uuu              ",
+            MaterialConfiguration::VisualMaterials => "---            |n",
+            MaterialConfiguration::MixedMaterials => "                 ",
+        }
+    }
+
+    /// Inclusive position ranges of 008/18-34 and the codes allowed there.
+    fn codes(self) -> &'static [(usize, usize, &'static str)] {
+        match self {
+            MaterialConfiguration::Books => BOOK_CODES,
+            MaterialConfiguration::ContinuingResources => CONTINUING_RESOURCE_CODES,
+            MaterialConfiguration::ComputerFiles => COMPUTER_FILE_CODES,
+            MaterialConfiguration::Maps => MAP_CODES,
+            MaterialConfiguration::Music => MUSIC_CODES,
+            MaterialConfiguration::VisualMaterials => VISUAL_CODES,
+            MaterialConfiguration::MixedMaterials => MIXED_CODES,
+        }
+    }
+}
+
+/// What is wrong with a record's 008.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FixedFieldIssueKind {
+    /// Leader/06 does not name a bibliographic type of record, so no
+    /// configuration applies.
+    UnknownMaterial,
+    /// The record has no 008.
+    Missing,
+    /// The record has this many 008s; only the first is checked.
+    Repeated(usize),
+    /// The 008 has this many characters instead of 40.
+    WrongLength(usize),
+    /// A position holds a value not defined for the configuration.
+    InvalidCode {
+        /// Position in the 008
+        position: usize,
+        /// The character found there
+        value: char,
+    },
+}
+
+/// A problem found by [`check_record`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FixedFieldIssue {
+    /// The configuration selected by the leader, if any
+    pub configuration: Option<MaterialConfiguration>,
+    /// What is wrong
+    pub kind: FixedFieldIssueKind,
+}
+
+/// Check the record's 008 against the configuration its leader selects.
+///
+/// Coded values are only checked in an 008 of the right length, since
+/// positions in one of the wrong length cannot be trusted.
+#[must_use]
+pub fn check_record(record: &Record) -> Vec<FixedFieldIssue> {
+    let configuration = MaterialConfiguration::from_leader(&record.leader);
+    let issue = |kind| FixedFieldIssue {
+        configuration,
+        kind,
+    };
+    let mut issues = Vec::new();
+    if configuration.is_none() {
+        issues.push(issue(FixedFieldIssueKind::UnknownMaterial));
+    }
+    let values = record
+        .control_fields
+        .get("008")
+        .map_or(&[][..], Vec::as_slice);
+    let Some(fixed) = values.first() else {
+        issues.push(issue(FixedFieldIssueKind::Missing));
+        return issues;
+    };
+    if values.len() > 1 {
+        issues.push(issue(FixedFieldIssueKind::Repeated(values.len())));
+    }
+
+    let chars: Vec<char> = fixed.chars().collect();
+    if chars.len() != FIXED_FIELD_LEN {
+        issues.push(issue(FixedFieldIssueKind::WrongLength(chars.len())));
+        return issues;
+    }
+    issues.extend(
+        invalid_positions(&chars, configuration)
+            .map(|(position, value)| issue(FixedFieldIssueKind::InvalidCode { position, value })),
+    );
+    issues
+}
+
+/// Repair the record's 008 and return the issues that were found.
+///
+/// A missing 008 is added from the [`skeleton`] and repeated ones are
+/// dropped. An 008 of the wrong length is rebuilt from the skeleton,
+/// keeping its first 18 characters and, if it is too long, its last five
+/// as positions 35-39. Invalid coded values are then replaced with `|`,
+/// and an invalid date entered with today's date. Positions 18-34 are
+/// left alone when no configuration applies.
+pub fn fix_record(record: &mut Record) -> Vec<FixedFieldIssue> {
+    let issues = check_record(record);
+    if issues.is_empty() {
+        return issues;
+    }
+    let configuration = MaterialConfiguration::from_leader(&record.leader);
+    let skeleton: Vec<char> = skeleton().chars().collect();
+
+    let values = record.control_fields.entry("008".to_string()).or_default();
+    values.truncate(1);
+    let mut chars: Vec<char> = match values.first() {
+        Some(fixed) => fixed.chars().collect(),
+        None => skeleton.clone(),
+    };
+    if chars.len() != FIXED_FIELD_LEN {
+        let mut rebuilt = skeleton.clone();
+        let head = chars.len().min(18);
+        rebuilt[..head].copy_from_slice(&chars[..head]);
+        if chars.len() > FIXED_FIELD_LEN {
+            rebuilt[35..].copy_from_slice(&chars[chars.len() - 5..]);
+        }
+        chars = rebuilt;
+    }
+    let invalid: Vec<usize> = invalid_positions(&chars, configuration)
+        .map(|(position, _)| position)
+        .collect();
+    for position in invalid {
+        if position < 6 {
+            chars[..6].copy_from_slice(&skeleton[..6]);
+        } else {
+            chars[position] = '|';
+        }
+    }
+
+    let fixed: String = chars.into_iter().collect();
+    match values.first_mut() {
+        Some(value) => *value = fixed,
+        None => values.push(fixed),
+    }
+    issues
+}
+
+/// A 40-character 008 valid for every configuration: today as date
+/// entered, dates unknown, place `xx`, 18-34 filled with `|`, language
+/// `und` and cataloging source `d` (other).
+#[must_use]
+pub fn skeleton() -> String {
+    format!("{}nuuuuuuuuxx {}und d", date_entered(), "|".repeat(17))
+}
+
+/// Today's date (UTC) as `yymmdd`, the form of 008/00-05.
+pub(crate) fn date_entered() -> String {
+    let days = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs() / 86_400);
+    let (year, month, day) = civil_date(days);
+    format!("{:02}{month:02}{day:02}", year % 100)
+}
+
+/// The Gregorian date `days` days after 1970-01-01.
+fn civil_date(days: u64) -> (u64, u64, u64) {
+    // Count from 0000-03-01 so the leap day ends each 400-year era.
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = era * 400 + year_of_era + u64::from(month <= 2);
+    (year, month, day)
+}
+
+/// A 40-character 008 laid out for the configuration `leader` selects:
+/// the [`skeleton`] with 18-34 taken from
+/// [`MaterialConfiguration::default_positions`]. `None` if the leader is not
+/// a bibliographic one.
+#[must_use]
+pub fn default_008(leader: &Leader) -> Option<String> {
+    let configuration = MaterialConfiguration::from_leader(leader)?;
+    let mut fixed = skeleton();
+    fixed.replace_range(18..35, configuration.default_positions());
+    Some(fixed)
+}
+
+/// Make sure the record has exactly one 008 of the right length, returning
+/// whether it was changed.
+///
+/// A missing 008 is generated with [`default_008`]. An 008 of the wrong
+/// length is rebuilt from it, keeping the existing first 18 characters and,
+/// if it is too long, the last five as positions 35-39; repeated 008s are
+/// dropped. Coded values in an 008 of the right length are left alone (see
+/// [`fix_record`]). Records whose leader is not bibliographic are not
+/// changed.
+pub fn ensure_008(record: &mut Record) -> bool {
+    let Some(default) = default_008(&record.leader) else {
+        return false;
+    };
+    let values = record.control_fields.entry("008".to_string()).or_default();
+    let mut changed = values.len() > 1;
+    values.truncate(1);
+    match values.first_mut() {
+        None => {
+            values.push(default);
+            changed = true;
+        },
+        Some(fixed) if fixed.chars().count() != FIXED_FIELD_LEN => {
+            let chars: Vec<char> = fixed.chars().collect();
+            let mut rebuilt: Vec<char> = default.chars().collect();
+            let head = chars.len().min(18);
+            rebuilt[..head].copy_from_slice(&chars[..head]);
+            if chars.len() > FIXED_FIELD_LEN {
+                rebuilt[35..].copy_from_slice(&chars[chars.len() - 5..]);
+            }
+            *fixed = rebuilt.into_iter().collect();
+            changed = true;
+        },
+        Some(_) => {},
+    }
+    changed
+}
+
+/// Positions of `chars` (a 40-character 008) holding values not allowed
+/// there.
+fn invalid_positions(
+    chars: &[char],
+    configuration: Option<MaterialConfiguration>,
+) -> impl Iterator<Item = (usize, char)> + '_ {
+    let entered = chars[..6]
+        .iter()
+        .position(|c| !c.is_ascii_digit())
+        .map(|position| (position, chars[position]));
+    let tables = COMMON_CODES
+        .iter()
+        .chain(configuration.map_or(&[][..], MaterialConfiguration::codes));
+    let coded = tables.flat_map(move |&(first, last, allowed)| {
+        (first..=last)
+            .filter(move |&position| !allowed.contains(chars[position]))
+            .map(move |position| (position, chars[position]))
+    });
+    entered.into_iter().chain(coded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(leader: &[u8; 24], fixed: &str) -> Record {
+        let mut record = Record::new(Leader::from_bytes(leader).unwrap());
+        record.add_control_field_str("008", fixed);
+        record
+    }
+
+    #[test]
+    fn test_configuration_and_codes() {
+        let book = record(
+            b"00000cam a2200000 a 4500",
+            "850101s1985    nyua          000 0 eng d",
+        );
+        assert_eq!(
+            MaterialConfiguration::from_leader(&book.leader),
+            Some(MaterialConfiguration::Books)
+        );
+        assert!(check_record(&book).is_empty());
+
+        // The same 008 on a map: 25 must code the type of cartographic
+        // material, and it is blank.
+        let map = record(
+            b"00000cem a2200000 a 4500",
+            "850101s1985    nyua          000 0 eng d",
+        );
+        let kinds: Vec<FixedFieldIssueKind> = check_record(&map)
+            .into_iter()
+            .map(|issue| issue.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                FixedFieldIssueKind::InvalidCode {
+                    position: 25,
+                    value: ' '
+                },
+                FixedFieldIssueKind::InvalidCode {
+                    position: 29,
+                    value: '0'
+                },
+                FixedFieldIssueKind::InvalidCode {
+                    position: 30,
+                    value: '0'
+                },
+                FixedFieldIssueKind::InvalidCode {
+                    position: 33,
+                    value: '0'
+                },
+            ]
+        );
+
+        let mut unknown = Record::new(Leader::from_bytes(b"00000czm a2200000 a 4500").unwrap());
+        let kinds: Vec<FixedFieldIssueKind> = check_record(&unknown)
+            .into_iter()
+            .map(|issue| issue.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                FixedFieldIssueKind::UnknownMaterial,
+                FixedFieldIssueKind::Missing
+            ]
+        );
+        fix_record(&mut unknown);
+        assert_eq!(check_record(&unknown).len(), 1);
+    }
+
+    #[test]
+    fn test_default_008_is_valid_for_every_configuration() {
+        for leader in [
+            b"00000nam a2200000 a 4500",
+            b"00000nas a2200000 a 4500",
+            b"00000nmm a2200000 a 4500",
+            b"00000nem a2200000 a 4500",
+            b"00000njm a2200000 a 4500",
+            b"00000ngm a2200000 a 4500",
+            b"00000npc a2200000 a 4500",
+        ] {
+            let mut record = Record::new(Leader::from_bytes(leader).unwrap());
+            assert!(ensure_008(&mut record));
+            assert!(!ensure_008(&mut record));
+            let fixed = record.get_control_field("008").unwrap();
+            assert_eq!(fixed.len(), FIXED_FIELD_LEN);
+            assert!(check_record(&record).is_empty(), "{fixed:?}");
+        }
+
+        let authority = Record::new(Leader::from_bytes(b"00000nz  a2200000n  4500").unwrap());
+        assert_eq!(default_008(&authority.leader), None);
+    }
+
+    #[test]
+    fn test_civil_date() {
+        assert_eq!(civil_date(0), (1970, 1, 1));
+        assert_eq!(civil_date(11_016), (2000, 2, 29));
+        assert_eq!(civil_date(19_782), (2024, 2, 29));
+        assert_eq!(civil_date(20_818), (2026, 12, 31));
+        assert_eq!(date_entered().len(), 6);
+    }
+
+    #[test]
+    fn test_ensure_008_rebuilds_truncated() {
+        let mut book = record(b"00000nam a2200000 a 4500", "850101s1985    nyu");
+        book.add_control_field_str("008", "second");
+        assert!(ensure_008(&mut book));
+        let fixed = book.get_control_field("008").unwrap();
+        assert_eq!(book.control_fields["008"].len(), 1);
+        assert!(fixed.starts_with("850101s1985    nyu           000 0 und d"));
+    }
+
+    #[test]
+    fn test_fix_wrong_length_and_repeats() {
+        let mut short = record(b"00000cam a2200000 a 4500", "850101s1985    nyu");
+        short.add_control_field_str("008", "second");
+        let issues = fix_record(&mut short);
+        assert_eq!(issues[0].kind, FixedFieldIssueKind::Repeated(2));
+        assert_eq!(issues[1].kind, FixedFieldIssueKind::WrongLength(18));
+        let fixed = short.control_fields.get("008").unwrap();
+        assert_eq!(fixed.len(), 1);
+        assert_eq!(
+            fixed[0],
+            format!("850101s1985    nyu{}und d", "|".repeat(17))
+        );
+
+        let mut long = record(
+            b"00000cam a2200000 a 4500",
+            "85o101s1985    nyua    XX    000 0 Xfre c",
+        );
+        fix_record(&mut long);
+        let fixed = long.get_control_field("008").unwrap();
+        assert_eq!(fixed.len(), FIXED_FIELD_LEN);
+        assert!(fixed[..6].chars().all(|c| c.is_ascii_digit()));
+        assert_eq!(&fixed[6..18], "s1985    nyu");
+        assert_eq!(&fixed[35..], "fre c");
+        assert!(check_record(&long).is_empty());
+    }
+}
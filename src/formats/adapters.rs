@@ -0,0 +1,263 @@
+//! Slicing and sampling adapters for [`FormatReader`]s.
+//!
+//! Created by [`FormatReaderExt::skip_records`],
+//! [`FormatReaderExt::take_records`] and [`FormatReaderExt::sample`]. Each
+//! adapter is itself a [`FormatReader`], so they chain:
+//!
+//! ```
+//! use mrrc::formats::{FormatReader, FormatReaderExt};
+//! use mrrc::MarcReader;
+//!
+//! let file = std::fs::File::open("tests/data/multi_records.mrc").unwrap();
+//! let mut reader = MarcReader::new(file).skip_records(1).take_records(1);
+//! assert!(reader.read_record().unwrap().is_some());
+//! assert!(reader.read_record().unwrap().is_none());
+//! ```
+//!
+//! Records that are passed over go through [`FormatReader::skip_record`],
+//! which the ISO 2709 reader implements by seeking past the record length
+//! given in the leader instead of parsing the record.
+//!
+//! [`FormatReaderExt::skip_records`]: super::FormatReaderExt::skip_records
+//! [`FormatReaderExt::take_records`]: super::FormatReaderExt::take_records
+//! [`FormatReaderExt::sample`]: super::FormatReaderExt::sample
+
+use super::traits::FormatReader;
+use crate::error::Result;
+use crate::record::Record;
+
+/// Reader adapter that skips a number of leading records.
+#[derive(Debug)]
+pub struct SkipRecords<R> {
+    reader: R,
+    pending: usize,
+}
+
+impl<R: FormatReader> SkipRecords<R> {
+    pub(crate) fn new(reader: R, n: usize) -> Self {
+        SkipRecords { reader, pending: n }
+    }
+
+    /// Skip the leading records if that has not happened yet. Returns
+    /// `false` if the source ran out first.
+    fn skip_pending(&mut self) -> Result<bool> {
+        while self.pending > 0 {
+            self.pending -= 1;
+            if !self.reader.skip_record()? {
+                self.pending = 0;
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Unwrap the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: FormatReader> FormatReader for SkipRecords<R> {
+    fn read_record(&mut self) -> Result<Option<Record>> {
+        if !self.skip_pending()? {
+            return Ok(None);
+        }
+        self.reader.read_record()
+    }
+
+    fn records_read(&self) -> Option<usize> {
+        self.reader.records_read()
+    }
+
+    fn skip_record(&mut self) -> Result<bool> {
+        Ok(self.skip_pending()? && self.reader.skip_record()?)
+    }
+}
+
+/// Reader adapter that yields at most a number of records.
+#[derive(Debug)]
+pub struct TakeRecords<R> {
+    reader: R,
+    remaining: usize,
+}
+
+impl<R: FormatReader> TakeRecords<R> {
+    pub(crate) fn new(reader: R, n: usize) -> Self {
+        TakeRecords {
+            reader,
+            remaining: n,
+        }
+    }
+
+    /// Unwrap the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: FormatReader> FormatReader for TakeRecords<R> {
+    fn read_record(&mut self) -> Result<Option<Record>> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        self.reader.read_record()
+    }
+
+    fn records_read(&self) -> Option<usize> {
+        self.reader.records_read()
+    }
+
+    fn skip_record(&mut self) -> Result<bool> {
+        if self.remaining == 0 {
+            return Ok(false);
+        }
+        self.remaining -= 1;
+        self.reader.skip_record()
+    }
+}
+
+/// Reader adapter that yields a seeded pseudo-random sample of records.
+///
+/// Each record is kept independently with probability `rate`, decided by a
+/// `SplitMix64` generator, so a given seed selects the same positions on
+/// every platform and release.
+#[derive(Debug)]
+pub struct SampleRecords<R> {
+    reader: R,
+    threshold: u64,
+    state: u64,
+}
+
+impl<R: FormatReader> SampleRecords<R> {
+    /// `rate` is clamped to 0.0–1.0; NaN is treated as 0.0.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    pub(crate) fn new(reader: R, rate: f64, seed: u64) -> Self {
+        let rate = if rate.is_nan() {
+            0.0
+        } else {
+            rate.clamp(0.0, 1.0)
+        };
+        // Compare the top 53 bits of each draw against rate * 2^53, so that
+        // rate 1.0 keeps every record and 0.0 none.
+        let threshold = (rate * (1u64 << 53) as f64) as u64;
+        SampleRecords {
+            reader,
+            threshold,
+            state: seed,
+        }
+    }
+
+    fn keep_next(&mut self) -> bool {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        (z >> 11) < self.threshold
+    }
+
+    /// Pass over records until one is selected. Returns `false` if the
+    /// source ran out first.
+    fn advance_to_selected(&mut self) -> Result<bool> {
+        loop {
+            if self.keep_next() {
+                return Ok(true);
+            }
+            if !self.reader.skip_record()? {
+                return Ok(false);
+            }
+        }
+    }
+
+    /// Unwrap the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: FormatReader> FormatReader for SampleRecords<R> {
+    fn read_record(&mut self) -> Result<Option<Record>> {
+        if self.threshold == 0 || !self.advance_to_selected()? {
+            return Ok(None);
+        }
+        self.reader.read_record()
+    }
+
+    fn records_read(&self) -> Option<usize> {
+        self.reader.records_read()
+    }
+
+    fn skip_record(&mut self) -> Result<bool> {
+        if self.threshold == 0 || !self.advance_to_selected()? {
+            return Ok(false);
+        }
+        self.reader.skip_record()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::FormatReaderExt;
+    use super::*;
+    use crate::leader::Leader;
+    use crate::reader::MarcReader;
+    use crate::writer::MarcWriter;
+    use std::io::Cursor;
+
+    fn stream(count: usize) -> Cursor<Vec<u8>> {
+        let mut buffer = Vec::new();
+        let mut writer = MarcWriter::new(&mut buffer);
+        for i in 0..count {
+            let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+            record.add_control_field_str("001", &i.to_string());
+            writer.write_record(&record).unwrap();
+        }
+        writer.finish().unwrap();
+        Cursor::new(buffer)
+    }
+
+    fn ids<R: FormatReader>(mut reader: R) -> Vec<usize> {
+        let mut ids = Vec::new();
+        while let Some(record) = reader.read_record().unwrap() {
+            ids.push(record.get_control_field("001").unwrap().parse().unwrap());
+        }
+        ids
+    }
+
+    #[test]
+    fn test_skip_and_take() {
+        let reader = MarcReader::new(stream(10)).skip_records(3).take_records(4);
+        assert_eq!(ids(reader), [3, 4, 5, 6]);
+        assert_eq!(
+            ids(MarcReader::new(stream(2)).skip_records(5)),
+            Vec::<usize>::new()
+        );
+    }
+
+    #[test]
+    fn test_nth_record_skips_without_parsing() {
+        let mut reader = MarcReader::new(stream(10));
+        let record = reader.nth_record(7).unwrap().unwrap();
+        assert_eq!(record.get_control_field("001"), Some("7"));
+        assert_eq!(reader.records_read(), Some(1));
+        assert!(reader.nth_record(5).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_sample_is_deterministic() {
+        let first = ids(MarcReader::new(stream(200)).sample(0.25, 42));
+        let again = ids(MarcReader::new(stream(200)).sample(0.25, 42));
+        let other = ids(MarcReader::new(stream(200)).sample(0.25, 7));
+        assert_eq!(first, again);
+        assert_ne!(first, other);
+        assert!((25..=75).contains(&first.len()), "{}", first.len());
+
+        assert_eq!(ids(MarcReader::new(stream(20)).sample(1.0, 1)).len(), 20);
+        assert!(ids(MarcReader::new(stream(20)).sample(0.0, 1)).is_empty());
+    }
+}
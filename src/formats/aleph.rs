@@ -0,0 +1,464 @@
+//! Aleph sequential format, the line-based export of Ex Libris Aleph.
+//!
+//! Each line holds one field of one record, in fixed columns:
+//!
+//! ```text
+//! 000000001 LDR   L 00000nam^a2200000^a^4500
+//! 000000001 001   L ocm01234567
+//! 000000001 008   L 920219s1993^^^^caua^^^^^^^^^^000^0^eng^d
+//! 000000001 24510 L $$aA title /$$cby Someone.
+//! 000000001 FMT   L BK
+//! ```
+//!
+//! Columns 1–9 are the Aleph system number, which groups lines into
+//! records; 11–13 the tag; 14–15 the indicators; 17 the literal `L`; and
+//! the content starts at column 19. Data-field content is a run of
+//! `$$`-delimited subfields. In the leader and control fields `^` stands
+//! for a blank.
+//!
+//! Aleph's own alphanumeric fields (`FMT`, `CAT`, `OWN`, ...) are kept:
+//! with subfields they become data fields, without them control fields.
+
+use std::fmt::Write as _;
+use std::io::{BufRead, Write};
+
+use crate::encoding::ControlCharacters;
+use crate::error::{MarcError, Result};
+use crate::formats::{FormatReader, FormatWriter};
+use crate::leader::Leader;
+use crate::record::{Field, Record};
+
+/// Column where the content starts (0-based).
+const CONTENT_START: usize = 18;
+
+/// Subfield delimiter.
+const DELIMITER: &str = "$$";
+
+/// Whether `tag` holds fixed-length content written with `^` for blanks.
+fn is_fixed(tag: &str) -> bool {
+    tag == "LDR" || (tag.starts_with("00") && tag.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// One parsed line.
+#[derive(Debug)]
+struct Line {
+    system_number: String,
+    tag: String,
+    indicator1: char,
+    indicator2: char,
+    content: String,
+}
+
+fn parse_line(line: &str, line_number: usize) -> Result<Line> {
+    let malformed = |why: &str| {
+        MarcError::invalid_field_msg(format!(
+            "Aleph sequential line {line_number}: {why}: {line:?}"
+        ))
+    };
+    let system_number = line
+        .get(0..9)
+        .filter(|s| s.bytes().all(|b| b.is_ascii_digit()))
+        .ok_or_else(|| malformed("expected a 9-digit system number"))?;
+    let tag = line
+        .get(10..13)
+        .filter(|t| t.bytes().all(|b| b.is_ascii_alphanumeric()))
+        .ok_or_else(|| malformed("expected a 3-character tag"))?;
+    let indicators = line
+        .get(13..15)
+        .ok_or_else(|| malformed("line is too short"))?;
+    if line.get(9..10) != Some(" ") || line.get(15..17) != Some(" L") {
+        return Err(malformed("columns are not aligned"));
+    }
+    let mut indicators = indicators.chars();
+    let content = line.get(CONTENT_START..).unwrap_or_default();
+    Ok(Line {
+        system_number: system_number.to_string(),
+        tag: tag.to_string(),
+        indicator1: indicators.next().unwrap_or(' '),
+        indicator2: indicators.next().unwrap_or(' '),
+        content: content.to_string(),
+    })
+}
+
+/// Reads records from Aleph sequential text.
+#[derive(Debug)]
+pub struct AlephSequentialReader<R: BufRead> {
+    reader: R,
+    /// First line of the next record, read while finding the end of the
+    /// previous one.
+    pending: Option<Line>,
+    line_number: usize,
+    bytes_read: usize,
+    records_read: usize,
+    last_system_number: Option<String>,
+}
+
+impl<R: BufRead> AlephSequentialReader<R> {
+    /// Create a reader over `reader`.
+    pub fn new(reader: R) -> Self {
+        AlephSequentialReader {
+            reader,
+            pending: None,
+            line_number: 0,
+            bytes_read: 0,
+            records_read: 0,
+            last_system_number: None,
+        }
+    }
+
+    /// System number of the record most recently returned.
+    #[must_use]
+    pub fn last_system_number(&self) -> Option<&str> {
+        self.last_system_number.as_deref()
+    }
+
+    fn next_line(&mut self) -> Result<Option<Line>> {
+        let mut buffer = String::new();
+        loop {
+            buffer.clear();
+            let start = self.bytes_read;
+            let read = self.reader.read_line(&mut buffer)?;
+            if read == 0 {
+                return Ok(None);
+            }
+            self.line_number += 1;
+            self.bytes_read += read;
+            let line = buffer.trim_end_matches(['\n', '\r']);
+            if !line.trim().is_empty() {
+                return parse_line(line, self.line_number)
+                    .map(Some)
+                    .map_err(|e| e.with_byte_offset(Some(start)));
+            }
+        }
+    }
+
+    fn read_next(&mut self) -> Result<Option<Record>> {
+        let first = match self.pending.take() {
+            Some(line) => line,
+            None => match self.next_line()? {
+                Some(line) => line,
+                None => return Ok(None),
+            },
+        };
+        let system_number = first.system_number.clone();
+        let mut lines = vec![first];
+        while let Some(line) = self.next_line()? {
+            if line.system_number != system_number {
+                self.pending = Some(line);
+                break;
+            }
+            lines.push(line);
+        }
+
+        let leader = lines
+            .iter()
+            .find(|line| line.tag == "LDR")
+            .ok_or_else(|| {
+                MarcError::invalid_field_msg(format!(
+                    "Aleph sequential record {system_number} has no LDR line"
+                ))
+            })?
+            .content
+            .replace('^', " ");
+        let mut record = Record::new(Leader::from_bytes(leader.as_bytes())?);
+        for line in lines.into_iter().filter(|line| line.tag != "LDR") {
+            if is_fixed(&line.tag) {
+                record.add_control_field(line.tag, line.content.replace('^', " "));
+            } else if let Some(subfields) = line.content.strip_prefix(DELIMITER) {
+                let mut field = Field::new(line.tag, line.indicator1, line.indicator2);
+                for subfield in subfields.split(DELIMITER) {
+                    let mut chars = subfield.chars();
+                    if let Some(code) = chars.next() {
+                        field.add_subfield_str(code, chars.as_str());
+                    }
+                }
+                record.add_field(field);
+            } else {
+                record.add_control_field(line.tag, line.content);
+            }
+        }
+        self.records_read += 1;
+        self.last_system_number = Some(system_number);
+        Ok(Some(record))
+    }
+}
+
+impl<R: BufRead + std::fmt::Debug> FormatReader for AlephSequentialReader<R> {
+    /// Errors carry the 1-based index of the record being read and, for
+    /// malformed lines, the byte offset of the line.
+    fn read_record(&mut self) -> Result<Option<Record>> {
+        let index = self.records_read + 1;
+        self.read_next()
+            .map_err(|e| e.with_record_index(Some(index)))
+    }
+
+    fn records_read(&self) -> Option<usize> {
+        Some(self.records_read)
+    }
+}
+
+/// Writes records as Aleph sequential text.
+///
+/// Records are numbered 000000001, 000000002, ... unless
+/// [`system_numbers_from_001`](Self::system_numbers_from_001) is set.
+/// Values that the format cannot represent — a line break anywhere, or
+/// `$$` inside a subfield — are rejected rather than written corrupted,
+/// as are other control characters unless
+/// [`control_characters`](Self::control_characters) says otherwise.
+#[derive(Debug)]
+pub struct AlephSequentialWriter<W: Write> {
+    writer: W,
+    use_001: bool,
+    control_characters: ControlCharacters,
+    records_written: usize,
+    finished: bool,
+}
+
+impl<W: Write> AlephSequentialWriter<W> {
+    /// Create a writer over `writer`.
+    pub fn new(writer: W) -> Self {
+        AlephSequentialWriter {
+            writer,
+            use_001: false,
+            control_characters: ControlCharacters::default(),
+            records_written: 0,
+            finished: false,
+        }
+    }
+
+    /// Use the record's 001 as its system number when it is 1 to 9 digits,
+    /// falling back to the sequence number otherwise.
+    #[must_use]
+    pub fn system_numbers_from_001(mut self, enabled: bool) -> Self {
+        self.use_001 = enabled;
+        self
+    }
+
+    /// How to write control characters, such as MARC-8 escape sequences,
+    /// left in field data (default: reject).
+    #[must_use]
+    pub fn control_characters(mut self, mode: ControlCharacters) -> Self {
+        self.control_characters = mode;
+        self
+    }
+
+    /// Unwrap the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn system_number(&self, record: &Record) -> String {
+        let from_001 = record.get_control_field("001").filter(|id| {
+            self.use_001 && (1..=9).contains(&id.len()) && id.bytes().all(|b| b.is_ascii_digit())
+        });
+        match from_001 {
+            Some(id) => format!("{id:0>9}"),
+            None => format!("{:09}", self.records_written + 1),
+        }
+    }
+}
+
+fn unrepresentable(tag: &str, why: &str) -> MarcError {
+    MarcError::WriterError {
+        record_index: None,
+        record_control_number: None,
+        field_tag: Some(tag.to_string()),
+        message: format!("Field {tag} cannot be written as Aleph sequential: {why}"),
+    }
+}
+
+fn check_text(tag: &str, value: &str) -> Result<()> {
+    if value.contains(['\n', '\r']) {
+        return Err(unrepresentable(tag, "value contains a line break"));
+    }
+    Ok(())
+}
+
+impl<W: Write + std::fmt::Debug> FormatWriter for AlephSequentialWriter<W> {
+    fn write_record(&mut self, record: &Record) -> Result<()> {
+        if self.finished {
+            return Err(MarcError::WriterError {
+                record_index: None,
+                record_control_number: None,
+                field_tag: None,
+                message: "Cannot write to a finished writer".to_string(),
+            });
+        }
+        let index = self.records_written + 1;
+        let out = self
+            .format_record(record)
+            .map_err(|e| e.with_record_index(Some(index)))?;
+        self.writer.write_all(out.as_bytes())?;
+        self.records_written += 1;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        self.finished = true;
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    fn records_written(&self) -> Option<usize> {
+        Some(self.records_written)
+    }
+}
+
+impl<W: Write> AlephSequentialWriter<W> {
+    /// Build the whole record first so a rejected value leaves no partial
+    /// record in the output.
+    fn format_record(&self, record: &Record) -> Result<String> {
+        let sysno = self.system_number(record);
+        let leader = String::from_utf8_lossy(&record.leader.as_bytes()?).replace(' ', "^");
+        let mut out = format!("{sysno} LDR   L {leader}\n");
+        for (tag, value) in record.control_fields_iter() {
+            let value = self.control_characters.apply(tag, value)?;
+            check_text(tag, &value)?;
+            let value = if is_fixed(tag) {
+                value.replace(' ', "^")
+            } else {
+                value.to_string()
+            };
+            let _ = writeln!(out, "{sysno} {tag:<3}   L {value}");
+        }
+        for field in record.fields() {
+            let _ = write!(
+                out,
+                "{sysno} {:<3}{}{} L ",
+                field.tag, field.indicator1, field.indicator2
+            );
+            for subfield in &field.subfields {
+                let value = self.control_characters.apply(&field.tag, &subfield.value)?;
+                check_text(&field.tag, &value)?;
+                if value.contains(DELIMITER) || value.ends_with('$') {
+                    return Err(unrepresentable(&field.tag, "value contains '$$'"));
+                }
+                out.push_str(DELIMITER);
+                out.push(subfield.code);
+                out.push_str(&value);
+            }
+            out.push('\n');
+        }
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+000000042 FMT   L BK
+000000042 LDR   L 00000nam^a2200000^a^4500
+000000042 001   L 000000042
+000000042 008   L 920219s1993^^^^caua^^^^^^^^^^000^0^eng^d
+000000042 24510 L $$aA title /$$cby Someone.
+000000042 650 0 L $$aCats$$vFiction.
+000000042 CAT   L $$aLOAD$$b00$$c20240101
+
+000000043 LDR   L 00000nam^a2200000^a^4500
+000000043 245 0 L $$aSecond
+";
+
+    #[test]
+    fn test_reads_records_grouped_by_system_number() {
+        let mut reader = AlephSequentialReader::new(SAMPLE.as_bytes());
+        let first = reader.read_record().unwrap().unwrap();
+        assert_eq!(reader.last_system_number(), Some("000000042"));
+        assert_eq!(first.leader.record_type, 'a');
+        assert_eq!(first.get_control_field("FMT"), Some("BK"));
+        assert_eq!(
+            first.get_control_field("008"),
+            Some("920219s1993    caua          000 0 eng d")
+        );
+        let title = first.get_field("245").unwrap();
+        assert_eq!((title.indicator1, title.indicator2), ('1', '0'));
+        assert_eq!(title.get_subfield('c'), Some("by Someone."));
+        assert_eq!(first.get_field("650").unwrap().indicator1, ' ');
+        assert_eq!(
+            first.get_field("CAT").unwrap().get_subfield('b'),
+            Some("00")
+        );
+
+        let second = reader.read_record().unwrap().unwrap();
+        let title = second.get_field("245").unwrap();
+        assert_eq!((title.indicator1, title.indicator2), (' ', '0'));
+        assert!(reader.read_record().unwrap().is_none());
+        assert_eq!(reader.records_read(), Some(2));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let records = AlephSequentialReader::new(SAMPLE.as_bytes())
+            .read_all()
+            .unwrap();
+        let mut writer = AlephSequentialWriter::new(Vec::new()).system_numbers_from_001(true);
+        writer.write_batch(&records).unwrap();
+        writer.finish().unwrap();
+        let text = String::from_utf8(writer.into_inner()).unwrap();
+        assert!(text.starts_with("000000042 LDR   L 00000nam^a2200000^a^4500\n"));
+        assert!(text.contains("000000042 24510 L $$aA title /$$cby Someone.\n"));
+        // No 001 on the second record: numbered by position
+        assert!(text.contains("000000002 245 0 L $$aSecond\n"));
+
+        let again = AlephSequentialReader::new(text.as_bytes())
+            .read_all()
+            .unwrap();
+        assert_eq!(again.len(), 2);
+        for (a, b) in records.iter().zip(&again) {
+            assert_eq!(a.leader, b.leader);
+            assert_eq!(
+                a.control_fields_iter().collect::<Vec<_>>(),
+                b.control_fields_iter().collect::<Vec<_>>()
+            );
+            assert_eq!(
+                a.fields().collect::<Vec<_>>(),
+                b.fields().collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut reader = AlephSequentialReader::new("00000001 245 0 L $$ax\n".as_bytes());
+        assert!(reader.read_record().is_err());
+        let mut reader = AlephSequentialReader::new("000000001 245 0 L $$ax\n".as_bytes());
+        assert!(reader.read_record().is_err()); // no LDR
+
+        // Malformed lines report the record and the line's byte offset
+        let input = format!("{SAMPLE}000000043 LDR   L 00000nam^a2200000^a^4500\nbad line\n");
+        let mut reader = AlephSequentialReader::new(input.as_bytes());
+        let err = reader.read_all().unwrap_err();
+        let meta = err.metadata();
+        assert_eq!(meta.record_index, Some(2));
+        assert_eq!(meta.byte_offset, Some(input.len() - "bad line\n".len()));
+
+        let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+        record.add_field(
+            Field::builder("500".to_string(), ' ', ' ')
+                .subfield_str('a', "Costs $$5")
+                .build(),
+        );
+        let mut writer = AlephSequentialWriter::new(Vec::new());
+        let err = writer.write_record(&record).unwrap_err();
+        let meta = err.metadata();
+        assert_eq!(meta.record_index, Some(1));
+        assert_eq!(meta.field_tag, Some("500"));
+        assert!(writer.into_inner().is_empty());
+
+        // Control characters are rejected unless escaped
+        let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+        record.add_field(
+            Field::builder("245".to_string(), '0', '0')
+                .subfield_str('a', "H\x1Bb2\x1BsO")
+                .build(),
+        );
+        let mut writer = AlephSequentialWriter::new(Vec::new());
+        assert!(writer.write_record(&record).is_err());
+        let mut writer =
+            AlephSequentialWriter::new(Vec::new()).control_characters(ControlCharacters::Hex);
+        writer.write_record(&record).unwrap();
+        let out = String::from_utf8(writer.into_inner()).unwrap();
+        assert!(out.contains("$$aH{1B}b2{1B}sO\n"), "{out}");
+    }
+}
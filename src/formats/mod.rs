@@ -9,6 +9,7 @@
 //! | Format | Module | Description |
 //! |--------|--------|-------------|
 //! | ISO 2709 | `iso2709` | Standard MARC interchange format (baseline) |
+//! | Aleph sequential | [`aleph`](crate::formats::aleph) | Line-based export of Ex Libris Aleph |
 //!
 //! BIBFRAME linked data support is available via the [`bibframe`](crate::bibframe) module.
 //!
@@ -63,8 +64,10 @@
 //! ```
 
 // Core traits - always available
+mod adapters;
 mod traits;
 
+pub use adapters::{SampleRecords, SkipRecords, TakeRecords};
 pub use traits::{FormatReader, FormatReaderExt, FormatWriter, RecordIterator};
 
 /// ISO 2709 binary format support (MARC standard interchange format).
@@ -82,6 +85,8 @@ pub mod iso2709 {
     pub use crate::writer::MarcWriter as Iso2709Writer;
 }
 
+pub mod aleph;
+
 // ============================================================================
 // Format Detection and Convenience Functions
 // ============================================================================
@@ -92,6 +97,8 @@ pub mod iso2709 {
 pub enum Format {
     /// ISO 2709 binary MARC format (`.mrc`, `.marc`)
     Iso2709,
+    /// Aleph sequential text format (`.seq`)
+    AlephSequential,
 }
 
 impl Format {
@@ -111,6 +118,7 @@ impl Format {
     pub fn from_extension(ext: &str) -> Option<Self> {
         match ext.to_lowercase().as_str() {
             "mrc" | "marc" => Some(Self::Iso2709),
+            "seq" => Some(Self::AlephSequential),
             _ => None,
         }
     }
@@ -128,6 +136,7 @@ impl Format {
     pub const fn extension(&self) -> &'static str {
         match self {
             Self::Iso2709 => "mrc",
+            Self::AlephSequential => "seq",
         }
     }
 
@@ -136,6 +145,7 @@ impl Format {
     pub const fn name(&self) -> &'static str {
         match self {
             Self::Iso2709 => "ISO 2709",
+            Self::AlephSequential => "Aleph sequential",
         }
     }
 }
@@ -155,12 +165,14 @@ mod tests {
         assert_eq!(Format::from_extension("mrc"), Some(Format::Iso2709));
         assert_eq!(Format::from_extension("marc"), Some(Format::Iso2709));
         assert_eq!(Format::from_extension("MRC"), Some(Format::Iso2709));
+        assert_eq!(Format::from_extension("seq"), Some(Format::AlephSequential));
         assert_eq!(Format::from_extension("unknown"), None);
     }
 
     #[test]
     fn test_format_extension() {
         assert_eq!(Format::Iso2709.extension(), "mrc");
+        assert_eq!(Format::AlephSequential.extension(), "seq");
     }
 
     #[test]
@@ -32,6 +32,7 @@
 //! }
 //! ```
 
+use super::adapters::{SampleRecords, SkipRecords, TakeRecords};
 use crate::error::Result;
 use crate::record::Record;
 
@@ -99,6 +100,34 @@ pub trait FormatReader: std::fmt::Debug {
     fn records_read(&self) -> Option<usize> {
         None
     }
+
+    /// Advance past the next record without returning it.
+    ///
+    /// Returns `Ok(false)` if the source was already exhausted. The default
+    /// implementation reads and discards the record; readers that can find
+    /// record boundaries without parsing (such as the ISO 2709 reader,
+    /// which uses the leader's record length) override it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the record cannot be read or skipped.
+    fn skip_record(&mut self) -> Result<bool> {
+        Ok(self.read_record()?.is_some())
+    }
+}
+
+impl<R: FormatReader + ?Sized> FormatReader for &mut R {
+    fn read_record(&mut self) -> Result<Option<Record>> {
+        (**self).read_record()
+    }
+
+    fn records_read(&self) -> Option<usize> {
+        (**self).records_read()
+    }
+
+    fn skip_record(&mut self) -> Result<bool> {
+        (**self).skip_record()
+    }
 }
 
 /// Trait for writers that can serialize MARC records to a format.
@@ -215,6 +244,51 @@ pub trait FormatReaderExt: FormatReader {
     {
         RecordIterator { reader: self }
     }
+
+    /// Skip the first `n` records, without parsing them where the reader
+    /// supports it (see [`FormatReader::skip_record`]).
+    ///
+    /// The records are skipped on the first read from the returned adapter.
+    fn skip_records(self, n: usize) -> SkipRecords<Self>
+    where
+        Self: Sized,
+    {
+        SkipRecords::new(self, n)
+    }
+
+    /// Yield at most `n` records.
+    fn take_records(self, n: usize) -> TakeRecords<Self>
+    where
+        Self: Sized,
+    {
+        TakeRecords::new(self, n)
+    }
+
+    /// Yield a pseudo-random sample of about `rate` (0.0–1.0) of the
+    /// records, skipping the rest without parsing them where the reader
+    /// supports it. The same `seed` selects the same records on every run
+    /// and platform.
+    fn sample(self, rate: f64, seed: u64) -> SampleRecords<Self>
+    where
+        Self: Sized,
+    {
+        SampleRecords::new(self, rate, seed)
+    }
+
+    /// Skip `n` records and read the next one (zero-based, counted from the
+    /// reader's current position).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a record cannot be skipped or read.
+    fn nth_record(&mut self, n: usize) -> Result<Option<Record>> {
+        for _ in 0..n {
+            if !self.skip_record()? {
+                return Ok(None);
+            }
+        }
+        self.read_record()
+    }
 }
 
 impl<T: FormatReader> FormatReaderExt for T {}
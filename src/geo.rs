@@ -0,0 +1,543 @@
+//! Geographic area codes (043) and coordinates (034, 255).
+//!
+//! Map and geospatial records describe where they are about in two ways:
+//! Geographic Area Codes in field 043 (`n-us-ny`), and bounding coordinates
+//! in 034 `$d $e $f $g` (`W0950000`) or, for older records, in the
+//! transcribed statement of 255 `$c` (`(W 95°--W 90°/N 45°--N 40°)`).
+//! This module parses both into typed values — [`GeographicArea`] and
+//! [`BoundingBox`] in decimal degrees — ready for a geo-search index.
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::geo::{parse_coordinate, BoundingBox, GeographicArea};
+//!
+//! assert_eq!(parse_coordinate("W0950000").unwrap(), -95.0);
+//! assert_eq!(parse_coordinate("N0403030").unwrap(), 40.508_333_333_333_33);
+//!
+//! let bbox = BoundingBox::parse_255("(W 95°--W 90°/N 45°--N 40°).").unwrap();
+//! assert_eq!((bbox.west, bbox.east, bbox.north, bbox.south), (-95.0, -90.0, 45.0, 40.0));
+//! assert!(bbox.contains(42.0, -93.5));
+//! assert_eq!(bbox.envelope(), "ENVELOPE(-95, -90, 45, 40)");
+//!
+//! let area: GeographicArea = "n-us-ny".parse().unwrap();
+//! assert_eq!(area.segments().collect::<Vec<_>>(), ["n", "us", "ny"]);
+//! assert_eq!(area.region_name(), Some("North America"));
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{MarcError, Result};
+use crate::record::{Field, Record};
+
+/// A MARC Geographic Area Code, as found in 043 `$a`.
+///
+/// Codes are seven characters: a region letter followed by country and
+/// subdivision segments, padded with hyphens (`n-us-ny`, `e-fr---`,
+/// `nwbf---`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct GeographicArea {
+    code: String,
+}
+
+impl GeographicArea {
+    /// Parse a code. A trailing hyphen run may be omitted (`"e-fr"`).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MarcError::InvalidField`] if the code is longer than seven
+    /// characters, does not start with a letter, or contains anything other
+    /// than lowercase letters and hyphens.
+    pub fn parse(code: &str) -> Result<Self> {
+        let trimmed = code.trim();
+        let valid = (1..=7).contains(&trimmed.len())
+            && trimmed.starts_with(|c: char| c.is_ascii_lowercase())
+            && trimmed.chars().all(|c| c.is_ascii_lowercase() || c == '-');
+        if !valid {
+            return Err(MarcError::invalid_field_msg(format!(
+                "Invalid geographic area code '{code}'"
+            )));
+        }
+        Ok(GeographicArea {
+            code: format!("{trimmed:-<7}"),
+        })
+    }
+
+    /// The full seven-character code.
+    #[must_use]
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    /// The non-empty hyphen-separated segments, broadest first
+    /// (`n-us-ny` → `n`, `us`, `ny`).
+    pub fn segments(&self) -> impl Iterator<Item = &str> {
+        self.code.split('-').filter(|s| !s.is_empty())
+    }
+
+    /// The region letter (`n` for North America, `e` for Europe, ...).
+    #[must_use]
+    pub fn region(&self) -> char {
+        self.code.chars().next().unwrap_or('-')
+    }
+
+    /// The name of the region the code falls in.
+    #[must_use]
+    pub fn region_name(&self) -> Option<&'static str> {
+        Some(match self.region() {
+            'a' => "Asia",
+            'b' => "Commonwealth countries",
+            'c' => "Intercontinental areas (Western Hemisphere)",
+            'd' => "Developing countries",
+            'e' => "Europe",
+            'f' => "Africa",
+            'h' => "French Community",
+            'i' => "Indian Ocean",
+            'l' => "Atlantic Ocean",
+            'm' => "Intercontinental areas (Eastern Hemisphere)",
+            'n' => "North America",
+            'p' => "Pacific Ocean",
+            'q' => "Cold regions",
+            'r' => "Arctic Ocean",
+            's' => "South America",
+            't' => "Antarctica",
+            'u' => "Australasia",
+            'v' => "Communist countries",
+            'w' => "Tropics",
+            'x' => "Earth",
+            'z' => "Outer space",
+            _ => return None,
+        })
+    }
+
+    /// The enclosing areas, broadest first, ending with this one
+    /// (`n-us-ny` → `n------`, `n-us---`, `n-us-ny`). Useful for
+    /// hierarchical facets.
+    #[must_use]
+    pub fn hierarchy(&self) -> Vec<GeographicArea> {
+        let mut areas = Vec::new();
+        let mut prefix = String::new();
+        for segment in self.segments() {
+            if !prefix.is_empty() {
+                prefix.push('-');
+            }
+            prefix.push_str(segment);
+            areas.push(GeographicArea {
+                code: format!("{prefix:-<7}"),
+            });
+        }
+        areas
+    }
+}
+
+impl FromStr for GeographicArea {
+    type Err = MarcError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for GeographicArea {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.code)
+    }
+}
+
+/// A geographic extent in decimal degrees.
+///
+/// Longitudes are negative west of Greenwich and latitudes negative south
+/// of the equator. `west` may be greater than `east` when the extent
+/// crosses the antimeridian.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BoundingBox {
+    /// Westernmost longitude
+    pub west: f64,
+    /// Easternmost longitude
+    pub east: f64,
+    /// Northernmost latitude
+    pub north: f64,
+    /// Southernmost latitude
+    pub south: f64,
+}
+
+impl BoundingBox {
+    /// Create a bounding box, checking that the values are in range.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MarcError::InvalidField`] if a longitude is outside
+    /// ±180, a latitude is outside ±90, or `north` is south of `south`.
+    pub fn new(west: f64, east: f64, north: f64, south: f64) -> Result<Self> {
+        let lon_ok = |v: f64| (-180.0..=180.0).contains(&v);
+        let lat_ok = |v: f64| (-90.0..=90.0).contains(&v);
+        if !(lon_ok(west) && lon_ok(east) && lat_ok(north) && lat_ok(south)) || north < south {
+            return Err(MarcError::invalid_field_msg(format!(
+                "Invalid bounding box: W {west}, E {east}, N {north}, S {south}"
+            )));
+        }
+        Ok(BoundingBox {
+            west,
+            east,
+            north,
+            south,
+        })
+    }
+
+    /// Read the bounding coordinates of a 034 field (`$d` west, `$e` east,
+    /// `$f` north, `$g` south).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MarcError::InvalidField`] if a subfield is missing or a
+    /// coordinate cannot be parsed.
+    pub fn from_034(field: &Field) -> Result<Self> {
+        let get = |code: char, latitude: bool| -> Result<f64> {
+            let value = field.get_subfield(code).ok_or_else(|| {
+                MarcError::invalid_field_msg(format!("034 is missing coordinate subfield ${code}"))
+            })?;
+            parse_axis(value, latitude)
+        };
+        Self::new(
+            get('d', false)?,
+            get('e', false)?,
+            get('f', true)?,
+            get('g', true)?,
+        )
+    }
+
+    /// Parse the coordinate statement of 255 `$c`, e.g.
+    /// `(W 95°--W 90°/N 45°--N 40°)` or
+    /// `(E 15°30ʹ00ʺ--E 17°00ʹ00ʺ/N 50°00ʹ00ʺ--N 48°15ʹ00ʺ)`.
+    ///
+    /// A statement with a single longitude and latitude describes a point.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MarcError::InvalidField`] if the statement is not in the
+    /// longitude/latitude form.
+    pub fn parse_255(statement: &str) -> Result<Self> {
+        let invalid = || {
+            MarcError::invalid_field_msg(format!("Unrecognized coordinate statement '{statement}'"))
+        };
+        let inner = statement
+            .trim()
+            .trim_end_matches('.')
+            .trim_start_matches('(')
+            .trim_end_matches(')');
+        let (longitudes, latitudes) = inner.split_once('/').ok_or_else(invalid)?;
+        let range = |part: &str, latitude: bool| -> Result<(f64, f64)> {
+            let mut values = part
+                .split("--")
+                .map(|v| parse_statement_coordinate(v, latitude));
+            let first = values.next().ok_or_else(invalid)?.ok_or_else(invalid)?;
+            let second = match values.next() {
+                Some(value) => value.ok_or_else(invalid)?,
+                None => first,
+            };
+            if values.next().is_some() {
+                return Err(invalid());
+            }
+            Ok((first, second))
+        };
+        let (west, east) = range(longitudes, false)?;
+        let (north, south) = range(latitudes, true)?;
+        Self::new(west, east, north, south)
+    }
+
+    /// Whether the box is a single point.
+    #[must_use]
+    #[allow(clippy::float_cmp)]
+    pub fn is_point(&self) -> bool {
+        self.west == self.east && self.north == self.south
+    }
+
+    /// Whether the point at `latitude`, `longitude` lies within the box.
+    #[must_use]
+    pub fn contains(&self, latitude: f64, longitude: f64) -> bool {
+        let in_lon = if self.west <= self.east {
+            (self.west..=self.east).contains(&longitude)
+        } else {
+            longitude >= self.west || longitude <= self.east
+        };
+        in_lon && (self.south..=self.north).contains(&latitude)
+    }
+
+    /// The centre of the box as `(latitude, longitude)`.
+    #[must_use]
+    pub fn center(&self) -> (f64, f64) {
+        let mut longitude = f64::midpoint(self.west, self.east);
+        if self.west > self.east {
+            longitude += if longitude > 0.0 { -180.0 } else { 180.0 };
+        }
+        (f64::midpoint(self.north, self.south), longitude)
+    }
+
+    /// The box in the `ENVELOPE(west, east, north, south)` form accepted by
+    /// Solr and Elasticsearch spatial fields.
+    #[must_use]
+    pub fn envelope(&self) -> String {
+        format!(
+            "ENVELOPE({}, {}, {}, {})",
+            self.west, self.east, self.north, self.south
+        )
+    }
+}
+
+/// Parse a 034 coordinate into decimal degrees.
+///
+/// Accepts the forms allowed in 034 `$d`–`$g`: a hemisphere letter
+/// followed by degrees-minutes-seconds (`W0950000`, `N0403030`), degrees
+/// and decimal minutes (`E01030.50`) or seconds (`N0403030.5`), or
+/// decimal degrees (`E010.5`); and signed decimal degrees (`-95.5`). West
+/// and south are negative.
+///
+/// # Errors
+///
+/// Returns [`MarcError::InvalidField`] if the value is not in one of these
+/// forms or minutes or seconds are 60 or more.
+pub fn parse_coordinate(value: &str) -> Result<f64> {
+    let trimmed = value.trim();
+    let invalid = || MarcError::invalid_field_msg(format!("Invalid coordinate '{value}'"));
+    let mut chars = trimmed.chars();
+    let (sign, digits) = match chars.next() {
+        Some('N' | 'n' | 'E' | 'e') => (1.0, chars.as_str()),
+        Some('S' | 's' | 'W' | 'w') => (-1.0, chars.as_str()),
+        Some('+') => return signed_decimal(chars.as_str()).ok_or_else(invalid),
+        Some('-') => {
+            return signed_decimal(chars.as_str())
+                .map(|v| -v)
+                .ok_or_else(invalid);
+        },
+        Some(c) if c.is_ascii_digit() => return signed_decimal(trimmed).ok_or_else(invalid),
+        _ => return Err(invalid()),
+    };
+
+    let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    if whole.is_empty()
+        || !whole.bytes().all(|b| b.is_ascii_digit())
+        || !fraction.bytes().all(|b| b.is_ascii_digit())
+    {
+        return Err(invalid());
+    }
+    // The length of the whole part tells which unit the last digits are:
+    // dddmmss / ddmmss, dddmm / ddmm, or ddd.
+    let degree_digits = match whole.len() {
+        6 | 7 => whole.len() - 4,
+        4 | 5 => whole.len() - 2,
+        1..=3 => whole.len(),
+        _ => return Err(invalid()),
+    };
+    let number = |s: &str| s.parse::<f64>().map_err(|_| invalid());
+    let fractional = |s: &str| {
+        if fraction.is_empty() {
+            number(s)
+        } else {
+            number(&format!("{s}.{fraction}"))
+        }
+    };
+    let (deg, rest) = whole.split_at(degree_digits);
+    let degrees = match rest.len() {
+        0 => fractional(deg)?,
+        2 => dms(number(deg)?, fractional(rest)?, 0.0).ok_or_else(invalid)?,
+        _ => {
+            let (min, sec) = rest.split_at(2);
+            dms(number(deg)?, number(min)?, fractional(sec)?).ok_or_else(invalid)?
+        },
+    };
+    Ok(sign * degrees)
+}
+
+/// Parse a coordinate, rejecting a hemisphere letter for the wrong axis.
+fn parse_axis(value: &str, latitude: bool) -> Result<f64> {
+    let hemisphere = value
+        .trim_start()
+        .chars()
+        .next()
+        .map(|c| c.to_ascii_uppercase());
+    let wrong_axis = if latitude {
+        matches!(hemisphere, Some('E' | 'W'))
+    } else {
+        matches!(hemisphere, Some('N' | 'S'))
+    };
+    if wrong_axis {
+        let axis = if latitude { "latitude" } else { "longitude" };
+        return Err(MarcError::invalid_field_msg(format!(
+            "Coordinate '{value}' is not a {axis}"
+        )));
+    }
+    parse_coordinate(value)
+}
+
+fn signed_decimal(s: &str) -> Option<f64> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit() || b == b'.') {
+        return None;
+    }
+    s.parse().ok()
+}
+
+fn dms(degrees: f64, minutes: f64, seconds: f64) -> Option<f64> {
+    (minutes < 60.0 && seconds < 60.0).then(|| degrees + minutes / 60.0 + seconds / 3600.0)
+}
+
+/// Parse one coordinate of a 255 statement: a hemisphere letter followed
+/// by degrees and optional minutes and seconds separated by any marks
+/// (`°`, `ʹ`, `'`, `ʺ`, `"`, blanks).
+fn parse_statement_coordinate(value: &str, latitude: bool) -> Option<f64> {
+    let value = value.trim();
+    let mut chars = value.chars();
+    let sign = match (chars.next()?.to_ascii_uppercase(), latitude) {
+        ('N', true) | ('E', false) => 1.0,
+        ('S', true) | ('W', false) => -1.0,
+        _ => return None,
+    };
+    let mut numbers = chars
+        .as_str()
+        .split(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .filter(|s| !s.is_empty())
+        .map(str::parse::<f64>);
+    let degrees = numbers.next()?.ok()?;
+    let minutes = numbers.next().transpose().ok()?.unwrap_or(0.0);
+    let seconds = numbers.next().transpose().ok()?.unwrap_or(0.0);
+    if numbers.next().is_some() {
+        return None;
+    }
+    dms(degrees, minutes, seconds).map(|v| sign * v)
+}
+
+/// The Geographic Area Codes in a record's 043 `$a` subfields.
+///
+/// Malformed codes are skipped.
+#[must_use]
+pub fn geographic_areas(record: &Record) -> Vec<GeographicArea> {
+    record
+        .fields_by_tag("043")
+        .flat_map(|field| field.subfields_by_code('a'))
+        .filter_map(|code| GeographicArea::parse(code).ok())
+        .collect()
+}
+
+/// The bounding boxes of a record.
+///
+/// Boxes come from the 034 fields; when no 034 yields one, the 255 `$c`
+/// statements are used instead. Fields whose coordinates are missing or
+/// malformed are skipped — call [`BoundingBox::from_034`] or
+/// [`BoundingBox::parse_255`] directly to see why.
+#[must_use]
+pub fn bounding_boxes(record: &Record) -> Vec<BoundingBox> {
+    let boxes: Vec<BoundingBox> = record
+        .fields_by_tag("034")
+        .filter_map(|field| BoundingBox::from_034(field).ok())
+        .collect();
+    if !boxes.is_empty() {
+        return boxes;
+    }
+    record
+        .fields_by_tag("255")
+        .flat_map(|field| field.subfields_by_code('c'))
+        .filter_map(|statement| BoundingBox::parse_255(statement).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::leader::Leader;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn test_parse_coordinate_forms() {
+        assert_close(parse_coordinate("W1800000").unwrap(), -180.0);
+        assert_close(parse_coordinate("S0703000").unwrap(), -70.5);
+        assert_close(parse_coordinate("N451530").unwrap(), 45.258_333_333_333);
+        assert_close(parse_coordinate("E01030.5").unwrap(), 10.508_333_333_333);
+        assert_close(parse_coordinate("N0403030.6").unwrap(), 40.508_5);
+        assert_close(parse_coordinate("E010.25").unwrap(), 10.25);
+        assert_close(parse_coordinate("-95.5").unwrap(), -95.5);
+        assert_close(parse_coordinate("+12").unwrap(), 12.0);
+
+        for bad in ["", "X0950000", "W09500000", "W0956000", "W09a0000", "W.5"] {
+            assert!(parse_coordinate(bad).is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn test_bounding_box_from_034_and_255() {
+        let field = Field::builder("034".to_string(), '1', ' ')
+            .subfield_str('a', "a")
+            .subfield_str('b', "250000")
+            .subfield_str('d', "W0950000")
+            .subfield_str('e', "W0900000")
+            .subfield_str('f', "N0450000")
+            .subfield_str('g', "N0400000")
+            .build();
+        let from_034 = BoundingBox::from_034(&field).unwrap();
+        let from_255 =
+            BoundingBox::parse_255("(W 95°00ʹ00ʺ--W 90°00ʹ00ʺ/N 45°00ʹ00ʺ--N 40°00ʹ00ʺ)").unwrap();
+        assert_eq!(from_034, from_255);
+        assert_eq!(from_034.center(), (42.5, -92.5));
+
+        let point = BoundingBox::parse_255("(E 15°30'/N 50°)").unwrap();
+        assert!(point.is_point());
+        assert_close(point.west, 15.5);
+
+        let mut swapped = field.clone();
+        swapped.subfields[2].value = "N0950000".to_string();
+        assert!(BoundingBox::from_034(&swapped).is_err());
+        assert!(BoundingBox::parse_255("Scale 1:250,000").is_err());
+        assert!(BoundingBox::new(0.0, 1.0, 10.0, 20.0).is_err());
+    }
+
+    #[test]
+    fn test_antimeridian_box() {
+        let bbox = BoundingBox::new(170.0, -170.0, 10.0, -10.0).unwrap();
+        assert!(bbox.contains(0.0, 175.0));
+        assert!(bbox.contains(0.0, -175.0));
+        assert!(!bbox.contains(0.0, 0.0));
+        assert_eq!(bbox.center(), (0.0, 180.0));
+    }
+
+    #[test]
+    fn test_geographic_area_codes() {
+        let area = GeographicArea::parse("e-fr").unwrap();
+        assert_eq!(area.code(), "e-fr---");
+        assert_eq!(area.region(), 'e');
+        let codes: Vec<String> = GeographicArea::parse("n-us-ny")
+            .unwrap()
+            .hierarchy()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(codes, ["n------", "n-us---", "n-us-ny"]);
+        for bad in ["", "-us----", "N-US-NY", "n-us-nyc-"] {
+            assert!(GeographicArea::parse(bad).is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn test_record_extraction() {
+        let mut record = Record::new(Leader::from_bytes(b"00000cem a2200000 a 4500").unwrap());
+        record.add_field(
+            Field::builder("043".to_string(), ' ', ' ')
+                .subfield_str('a', "n-us-mn")
+                .subfield_str('a', "bogus!!")
+                .build(),
+        );
+        record.add_field(
+            Field::builder("255".to_string(), ' ', ' ')
+                .subfield_str('a', "Scale 1:250,000")
+                .subfield_str('c', "(W 97°--W 89°/N 49°--N 43°).")
+                .build(),
+        );
+        assert_eq!(geographic_areas(&record).len(), 1);
+        let boxes = bounding_boxes(&record);
+        assert_eq!(boxes.len(), 1);
+        assert_close(boxes[0].west, -97.0);
+    }
+}
@@ -0,0 +1,328 @@
+//! Moving holdings between separate MFHD records and embedded bib fields.
+//!
+//! Systems differ on where holdings live. Some keep them in separate MARC
+//! holdings records (MFHD) that point at the bibliographic record through
+//! 004; others embed the 852 location and 853–878 caption, enumeration and
+//! textual holdings fields directly in the bibliographic record.
+//! [`HoldingsEmbedding`] converts in both directions:
+//!
+//! - [`HoldingsEmbedding::embed`] copies the selected fields of a
+//!   [`HoldingsRecord`] onto a bibliographic [`Record`].
+//! - [`HoldingsEmbedding::extract`] removes embedded holdings fields from a
+//!   bibliographic record and returns them as standalone holdings records
+//!   linked to it by 004.
+//!
+//! Embedded fields from one holdings record are tied together by a link
+//! subfield (`$8` unless configured otherwise) inserted ahead of the
+//! field's other subfields and holding the holdings record's 001, or a
+//! sequence number when it has none. Any `$8` the holdings field already
+//! carried, such as the `1.1` pairing an 863 with its 853, follows it and
+//! is kept on extraction. Embedded fields without the link subfield are
+//! extracted together as one holdings record.
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::holdings_embedding::HoldingsEmbedding;
+//! use mrrc::{Field, HoldingsRecord, Leader, Record};
+//!
+//! let mut bib = Record::new(Leader::from_bytes(b"00000nas a2200000 a 4500").unwrap());
+//! bib.add_control_field_str("001", "b1");
+//!
+//! let mut mfhd = HoldingsRecord::new(Leader::from_bytes(b"00000ny  a22000003n 4500").unwrap());
+//! mfhd.add_control_field("001".to_string(), "h7".to_string());
+//! mfhd.add_location(
+//!     Field::builder("852".to_string(), '0', '0')
+//!         .subfield_str('b', "MAIN")
+//!         .subfield_str('h', "QA76")
+//!         .build(),
+//! );
+//!
+//! let embedding = HoldingsEmbedding::new();
+//! assert_eq!(embedding.embed(&mut bib, &mfhd), 1);
+//! assert_eq!(bib.get_field("852").unwrap().get_subfield('8'), Some("h7"));
+//!
+//! let extracted = embedding.extract(&mut bib);
+//! assert_eq!(extracted.len(), 1);
+//! assert_eq!(extracted[0].get_control_field("004"), Some("b1"));
+//! assert_eq!(extracted[0].locations()[0].get_subfield('8'), None);
+//! assert!(bib.get_field("852").is_none());
+//! ```
+
+use indexmap::IndexMap;
+
+use crate::holdings_record::HoldingsRecord;
+use crate::leader::Leader;
+use crate::record::{Field, Record, Subfield};
+
+/// Tags copied by default: location, captions and patterns, enumeration
+/// and chronology, textual holdings and item information.
+pub const DEFAULT_HOLDINGS_TAGS: &[&str] = &[
+    "852", "853", "854", "855", "863", "864", "865", "866", "867", "868", "876", "877", "878",
+];
+
+/// Converts between holdings records and holdings fields embedded in
+/// bibliographic records.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HoldingsEmbedding {
+    tags: Vec<String>,
+    link_code: char,
+}
+
+impl Default for HoldingsEmbedding {
+    fn default() -> Self {
+        HoldingsEmbedding {
+            tags: DEFAULT_HOLDINGS_TAGS
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            link_code: '8',
+        }
+    }
+}
+
+impl HoldingsEmbedding {
+    /// Embed and extract [`DEFAULT_HOLDINGS_TAGS`], linked by `$8`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Embed and extract only these tags.
+    #[must_use]
+    pub fn with_tags(mut self, tags: &[&str]) -> Self {
+        self.tags = tags.iter().map(ToString::to_string).collect();
+        self
+    }
+
+    /// Link embedded fields with this subfield code instead of `$8`.
+    #[must_use]
+    pub fn with_link_code(mut self, code: char) -> Self {
+        self.link_code = code;
+        self
+    }
+
+    /// Copy the selected fields of `holdings` onto `bib`, returning the
+    /// number of fields copied.
+    pub fn embed(&self, bib: &mut Record, holdings: &HoldingsRecord) -> usize {
+        let link = holdings.get_control_field("001").map_or_else(
+            || (self.embedded_links(bib) + 1).to_string(),
+            str::to_string,
+        );
+        let mut copied = 0;
+        for tag in &self.tags {
+            for field in holdings.get_fields(tag).unwrap_or_default() {
+                let mut field = field.clone();
+                field.subfields.insert(
+                    0,
+                    Subfield {
+                        code: self.link_code,
+                        value: link.clone(),
+                    },
+                );
+                bib.add_field(field);
+                copied += 1;
+            }
+        }
+        copied
+    }
+
+    /// Number of distinct link values on embedded 852s already in `bib`.
+    fn embedded_links(&self, bib: &Record) -> usize {
+        let mut links: Vec<&str> = bib
+            .fields_by_tag("852")
+            .filter_map(|field| field.subfields.first())
+            .filter(|sf| sf.code == self.link_code)
+            .map(|sf| sf.value.as_str())
+            .collect();
+        links.sort_unstable();
+        links.dedup();
+        links.len()
+    }
+
+    /// Remove the embedded holdings fields from `bib` and return them as
+    /// holdings records, one per link value in order of first appearance.
+    ///
+    /// Each holdings record gets the link value as its 001 (when there is
+    /// one), the bibliographic 001 as its 004, a generic 008, and a leader
+    /// typed as serial (`y`) when it has caption or enumeration fields and
+    /// single-part (`x`) otherwise.
+    pub fn extract(&self, bib: &mut Record) -> Vec<HoldingsRecord> {
+        let bib_control_number = bib.get_control_field("001").map(str::to_string);
+        let date_entered = bib
+            .get_control_field("008")
+            .and_then(|f| f.get(0..6))
+            .unwrap_or("000000")
+            .to_string();
+
+        let mut groups: IndexMap<String, Vec<Field>> = IndexMap::new();
+        for tag in &self.tags {
+            for mut field in bib.remove_fields_by_tag(tag) {
+                let link = if field.subfields.first().map(|sf| sf.code) == Some(self.link_code) {
+                    field.subfields.remove(0).value
+                } else {
+                    String::new()
+                };
+                groups.entry(link).or_default().push(field);
+            }
+        }
+
+        groups
+            .into_iter()
+            .map(|(link, fields)| {
+                let serial = fields.iter().any(|f| {
+                    matches!(
+                        f.tag.as_str(),
+                        "853" | "854" | "855" | "863" | "864" | "865"
+                    )
+                });
+                let mut holdings =
+                    HoldingsRecord::new(holdings_leader(if serial { 'y' } else { 'x' }));
+                if !link.is_empty() {
+                    holdings.add_control_field("001".to_string(), link);
+                }
+                if let Some(control_number) = &bib_control_number {
+                    holdings.add_control_field("004".to_string(), control_number.clone());
+                }
+                holdings.add_control_field(
+                    "008".to_string(),
+                    format!("{date_entered}0u    8   4001uu   0{date_entered}"),
+                );
+                for field in fields {
+                    holdings.add_field(field);
+                }
+                holdings
+            })
+            .collect()
+    }
+}
+
+/// Leader for an extracted holdings record: encoding level 3, no item
+/// information.
+fn holdings_leader(record_type: char) -> Leader {
+    Leader {
+        record_length: 0,
+        record_status: 'n',
+        record_type,
+        bibliographic_level: ' ',
+        control_record_type: ' ',
+        character_coding: 'a',
+        indicator_count: 2,
+        subfield_code_count: 2,
+        data_base_address: 0,
+        encoding_level: '3',
+        cataloging_form: 'n',
+        multipart_level: ' ',
+        reserved: "4500".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mfhd(id: Option<&str>, location: &str) -> HoldingsRecord {
+        let mut holdings = HoldingsRecord::new(holdings_leader('y'));
+        if let Some(id) = id {
+            holdings.add_control_field("001".to_string(), id.to_string());
+        }
+        holdings.add_location(
+            Field::builder("852".to_string(), '0', '1')
+                .subfield_str('b', location)
+                .build(),
+        );
+        holdings.add_field(
+            Field::builder("853".to_string(), '2', '0')
+                .subfield_str('8', "1")
+                .subfield_str('a', "v.")
+                .subfield_str('i', "(year)")
+                .build(),
+        );
+        holdings.add_field(
+            Field::builder("863".to_string(), '4', '1')
+                .subfield_str('8', "1.1")
+                .subfield_str('a', "1-10")
+                .subfield_str('i', "1990-1999")
+                .build(),
+        );
+        holdings.add_field(
+            Field::builder("583".to_string(), ' ', ' ')
+                .subfield_str('a', "retained")
+                .build(),
+        );
+        holdings
+    }
+
+    fn bib_record() -> Record {
+        let mut bib = Record::new(Leader::from_bytes(b"00000nas a2200000 a 4500").unwrap());
+        bib.add_control_field_str("001", "b1");
+        bib.add_control_field_str("008", "950101c19909999xx qr p       0   a0eng d");
+        bib
+    }
+
+    #[test]
+    fn test_round_trip_multiple_holdings() {
+        let embedding = HoldingsEmbedding::new();
+        let mut bib = bib_record();
+        assert_eq!(embedding.embed(&mut bib, &mfhd(Some("h1"), "MAIN")), 3);
+        assert_eq!(embedding.embed(&mut bib, &mfhd(None, "ANNEX")), 3);
+        assert!(bib.get_field("583").is_none());
+
+        let locations: Vec<&str> = bib
+            .fields_by_tag("852")
+            .filter_map(|f| f.get_subfield('8'))
+            .collect();
+        assert_eq!(locations, vec!["h1", "2"]);
+
+        let extracted = embedding.extract(&mut bib);
+        assert_eq!(extracted.len(), 2);
+        assert!(bib.get_fields("863").is_none());
+
+        let first = &extracted[0];
+        assert_eq!(first.get_control_field("001"), Some("h1"));
+        assert_eq!(first.get_control_field("004"), Some("b1"));
+        assert_eq!(first.leader.record_type, 'y');
+        assert_eq!(first.get_control_field("008").unwrap().len(), 32);
+        assert!(
+            first
+                .get_control_field("008")
+                .unwrap()
+                .starts_with("950101")
+        );
+        let enumeration = &first.enumeration_basic()[0];
+        assert_eq!(enumeration.get_subfield('8'), Some("1.1"));
+        assert_eq!(enumeration.get_subfield('a'), Some("1-10"));
+
+        assert_eq!(extracted[1].get_control_field("001"), Some("2"));
+        assert_eq!(extracted[1].locations()[0].get_subfield('b'), Some("ANNEX"));
+    }
+
+    #[test]
+    fn test_unlinked_fields_and_tag_selection() {
+        let mut bib = bib_record();
+        bib.add_field(
+            Field::builder("852".to_string(), '0', ' ')
+                .subfield_str('b', "MAIN")
+                .build(),
+        );
+        bib.add_field(
+            Field::builder("866".to_string(), ' ', '0')
+                .subfield_str('a', "v.1-5")
+                .build(),
+        );
+
+        let embedding = HoldingsEmbedding::new().with_tags(&["852"]);
+        let extracted = embedding.extract(&mut bib);
+        assert_eq!(extracted.len(), 1);
+        assert_eq!(extracted[0].get_control_field("001"), None);
+        assert_eq!(extracted[0].leader.record_type, 'x');
+        assert!(bib.get_field("866").is_some());
+
+        let mut bib = bib_record();
+        let embedding = HoldingsEmbedding::new()
+            .with_tags(&["852"])
+            .with_link_code('3');
+        assert_eq!(embedding.embed(&mut bib, &mfhd(Some("h9"), "MAIN")), 1);
+        assert_eq!(bib.get_field("852").unwrap().subfields[0].code, '3');
+    }
+}
@@ -0,0 +1,248 @@
+//! Physical items from item information fields (876–878).
+//!
+//! Holdings records (and bibliographic records with embedded holdings)
+//! describe individual pieces in 876 (basic unit), 877 (supplements) and
+//! 878 (indexes): barcode, copy number, cost, acquisition, status and
+//! temporary location. [`Item`] reads one of those fields into named
+//! values and writes it back, so item data can be migrated or edited
+//! without tracking subfield codes.
+//!
+//! | Field                                   | Subfield |
+//! |-----------------------------------------|----------|
+//! | [`internal_number`](Item::internal_number) | `$a`  |
+//! | [`cost`](Item::cost)                    | `$c`     |
+//! | [`acquisition_date`](Item::acquisition_date) | `$d` |
+//! | [`acquisition_source`](Item::acquisition_source) | `$e` |
+//! | [`use_restrictions`](Item::use_restrictions) | `$h` |
+//! | [`status`](Item::status)                | `$j`     |
+//! | [`temporary_location`](Item::temporary_location) | `$l` |
+//! | [`barcode`](Item::barcode)              | `$p`     |
+//! | [`copy_number`](Item::copy_number)      | `$t`     |
+//! | [`nonpublic_notes`](Item::nonpublic_notes) | `$x`  |
+//! | [`public_notes`](Item::public_notes)    | `$z`     |
+//! | [`materials`](Item::materials)          | `$3`     |
+//!
+//! Other subfields, and repeats of the single-valued ones, are kept in
+//! [`other`](Item::other) so that reading and writing an item loses
+//! nothing.
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::holdings_item::Item;
+//! use mrrc::Field;
+//!
+//! let field = Field::builder("876".to_string(), ' ', ' ')
+//!     .subfield_str('a', "i1234")
+//!     .subfield_str('d', "20240115")
+//!     .subfield_str('j', "Missing")
+//!     .subfield_str('p', "39015012345678")
+//!     .build();
+//! let mut item = Item::from_field(&field);
+//! assert_eq!(item.barcode.as_deref(), Some("39015012345678"));
+//! assert_eq!(item.acquisition_date.as_deref(), Some("20240115"));
+//!
+//! item.status = Some("Available".to_string());
+//! assert_eq!(item.to_field().get_subfield('j'), Some("Available"));
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::holdings_statement::HoldingsUnit;
+use crate::record::{Field, Subfield};
+
+/// One physical piece described by an 876, 877 or 878 field.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Item {
+    /// Which unit the item belongs to (876 basic, 877 supplement, 878 index).
+    pub unit: HoldingsUnit,
+    /// Internal item number (`$a`).
+    pub internal_number: Option<String>,
+    /// Cost (`$c`).
+    pub cost: Option<String>,
+    /// Date acquired (`$d`), normally `yyyymmdd`.
+    pub acquisition_date: Option<String>,
+    /// Source of acquisition (`$e`).
+    pub acquisition_source: Option<String>,
+    /// Use restrictions (`$h`).
+    pub use_restrictions: Vec<String>,
+    /// Item status (`$j`), such as `Missing` or `On order`.
+    pub status: Option<String>,
+    /// Temporary location (`$l`).
+    pub temporary_location: Option<String>,
+    /// Piece designation (`$p`), usually the barcode.
+    pub barcode: Option<String>,
+    /// Copy number (`$t`).
+    pub copy_number: Option<String>,
+    /// Nonpublic notes (`$x`).
+    pub nonpublic_notes: Vec<String>,
+    /// Public notes (`$z`).
+    pub public_notes: Vec<String>,
+    /// Materials specified (`$3`).
+    pub materials: Option<String>,
+    /// Subfields without a named value, in field order.
+    pub other: Vec<Subfield>,
+}
+
+impl Item {
+    /// An empty item for `unit`.
+    #[must_use]
+    pub fn new(unit: HoldingsUnit) -> Self {
+        Item {
+            unit,
+            internal_number: None,
+            cost: None,
+            acquisition_date: None,
+            acquisition_source: None,
+            use_restrictions: Vec::new(),
+            status: None,
+            temporary_location: None,
+            barcode: None,
+            copy_number: None,
+            nonpublic_notes: Vec::new(),
+            public_notes: Vec::new(),
+            materials: None,
+            other: Vec::new(),
+        }
+    }
+
+    /// Read an item information field. The unit comes from the tag; tags
+    /// other than 877 and 878 read as the basic unit.
+    #[must_use]
+    pub fn from_field(field: &Field) -> Self {
+        let mut item =
+            Item::new(HoldingsUnit::from_item_tag(&field.tag).unwrap_or(HoldingsUnit::Basic));
+        for subfield in &field.subfields {
+            let value = subfield.value.clone();
+            let single = match subfield.code {
+                'a' => &mut item.internal_number,
+                'c' => &mut item.cost,
+                'd' => &mut item.acquisition_date,
+                'e' => &mut item.acquisition_source,
+                'j' => &mut item.status,
+                'l' => &mut item.temporary_location,
+                'p' => &mut item.barcode,
+                't' => &mut item.copy_number,
+                '3' => &mut item.materials,
+                'h' => {
+                    item.use_restrictions.push(value);
+                    continue;
+                },
+                'x' => {
+                    item.nonpublic_notes.push(value);
+                    continue;
+                },
+                'z' => {
+                    item.public_notes.push(value);
+                    continue;
+                },
+                _ => {
+                    item.other.push(subfield.clone());
+                    continue;
+                },
+            };
+            if single.is_none() {
+                *single = Some(value);
+            } else {
+                item.other.push(subfield.clone());
+            }
+        }
+        item
+    }
+
+    /// Write the item as an 876, 877 or 878 field.
+    ///
+    /// Numeric subfields from [`other`](Item::other) (`$6`, `$8`) come
+    /// first, then the named values in MARC order, then the remaining
+    /// `other` subfields.
+    #[must_use]
+    pub fn to_field(&self) -> Field {
+        let mut field = Field::new(self.unit.item_tag().to_string(), ' ', ' ');
+        let (control, rest): (Vec<&Subfield>, Vec<&Subfield>) =
+            self.other.iter().partition(|sf| sf.code.is_ascii_digit());
+        field.subfields.extend(control.into_iter().cloned());
+
+        push(&mut field, '3', self.materials.as_ref());
+        push(&mut field, 'a', self.internal_number.as_ref());
+        push(&mut field, 'c', self.cost.as_ref());
+        push(&mut field, 'd', self.acquisition_date.as_ref());
+        push(&mut field, 'e', self.acquisition_source.as_ref());
+        for restriction in &self.use_restrictions {
+            field.add_subfield('h', restriction.clone());
+        }
+        push(&mut field, 'j', self.status.as_ref());
+        push(&mut field, 'l', self.temporary_location.as_ref());
+        push(&mut field, 'p', self.barcode.as_ref());
+        push(&mut field, 't', self.copy_number.as_ref());
+        for note in &self.nonpublic_notes {
+            field.add_subfield('x', note.clone());
+        }
+        for note in &self.public_notes {
+            field.add_subfield('z', note.clone());
+        }
+        field.subfields.extend(rest.into_iter().cloned());
+        field
+    }
+
+    /// The acquisition date, from `yyyymmdd` or `yyyy-mm-dd`.
+    #[cfg(feature = "chrono")]
+    #[must_use]
+    pub fn acquired_on(&self) -> Option<chrono::NaiveDate> {
+        let date = self.acquisition_date.as_deref()?.trim();
+        chrono::NaiveDate::parse_from_str(date, "%Y%m%d")
+            .or_else(|_| chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d"))
+            .ok()
+    }
+}
+
+fn push(field: &mut Field, code: char, value: Option<&String>) {
+    if let Some(value) = value {
+        field.add_subfield(code, value.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_keeps_unmodeled_subfields() {
+        let field = Field::builder("877".to_string(), ' ', ' ')
+            .subfield_str('8', "2")
+            .subfield_str('a', "i1")
+            .subfield_str('c', "USD 12.00")
+            .subfield_str('c', "USD 3.00")
+            .subfield_str('h', "Library use only")
+            .subfield_str('p', "3901")
+            .subfield_str('r', "3900")
+            .subfield_str('z', "Loose plates.")
+            .build();
+        let item = Item::from_field(&field);
+        assert_eq!(item.unit, HoldingsUnit::Supplement);
+        assert_eq!(item.cost.as_deref(), Some("USD 12.00"));
+        assert_eq!(item.use_restrictions, ["Library use only"]);
+        let codes: Vec<char> = item.other.iter().map(|sf| sf.code).collect();
+        assert_eq!(codes, ['8', 'c', 'r']);
+
+        let written = item.to_field();
+        assert_eq!(written.tag, "877");
+        let codes: Vec<char> = written.subfields.iter().map(|sf| sf.code).collect();
+        assert_eq!(codes, ['8', 'a', 'c', 'h', 'p', 'z', 'c', 'r']);
+        assert_eq!(Item::from_field(&written), item);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_acquired_on() {
+        let mut item = Item::new(HoldingsUnit::Index);
+        assert_eq!(item.acquired_on(), None);
+        item.acquisition_date = Some("2023-02-28".to_string());
+        assert_eq!(
+            item.acquired_on(),
+            chrono::NaiveDate::from_ymd_opt(2023, 2, 28)
+        );
+        item.acquisition_date = Some("2023".to_string());
+        assert_eq!(item.acquired_on(), None);
+        assert_eq!(item.to_field().tag, "878");
+    }
+}
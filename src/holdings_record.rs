@@ -4,12 +4,17 @@
 //! including location, call numbers, and enumeration/chronology information for serials.
 //! They are linked to bibliographic records but maintain separate MARC records.
 
+use crate::holdings_item::Item;
+use crate::holdings_statement::{HoldingsStatement, HoldingsUnit};
 use crate::leader::Leader;
 use crate::marc_record::MarcRecord;
 use crate::record::{Field, TagIndexMap};
 use crate::record_helpers::control_field_char_at;
 use serde::{Deserialize, Serialize};
 
+/// Item information tags, basic unit first.
+const ITEM_TAGS: [&str; 3] = ["876", "877", "878"];
+
 /// A MARC Holdings record (Type x/y/v/u, Leader/06)
 ///
 /// Fields are stored in insertion order using `IndexMap`, preserving the order
@@ -73,11 +78,127 @@ pub enum MethodOfAcquisition {
     Purchase,
     /// e - Exchange
     Exchange,
+    /// c - Cooperative or consortial purchase
+    CooperativePurchase,
+    /// d - Deposit
+    Deposit,
+    /// n - Non-library purchase
+    NonLibraryPurchase,
+    /// q - Lease
+    Lease,
+    /// z - Other method of acquisition
+    Other,
+}
+
+/// Receipt or acquisition status (008/06)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReceiptStatus {
+    /// 0 - Unknown
+    Unknown,
+    /// 1 - Other receipt or acquisition status
+    Other,
+    /// 2 - Received and complete or ceased
+    ReceivedAndComplete,
+    /// 3 - On order
+    OnOrder,
+    /// 4 - Currently received
+    CurrentlyReceived,
+    /// 5 - Not currently received
+    NotCurrentlyReceived,
+}
+
+/// General retention policy (008/12)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GeneralRetentionPolicy {
+    /// 0 - Unknown
+    Unknown,
+    /// 1 - Other general retention policy
+    Other,
+    /// 2 - Retained except as replaced by updates
+    ReplacedByUpdates,
+    /// 3 - Sample issue retained
+    SampleIssue,
+    /// 4 - Retained until replaced by microform
+    UntilMicroform,
+    /// 5 - Retained until replaced by cumulation, replacement volume, or
+    /// revision
+    UntilCumulation,
+    /// 6 - Retained for a limited period
+    LimitedPeriod,
+    /// 7 - Not retained
+    NotRetained,
+    /// 8 - Permanently retained
+    Permanent,
+}
+
+/// Which issues a specific retention policy keeps (008/13)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RetentionPolicyType {
+    /// l - Latest
+    Latest,
+    /// p - Previous
+    Previous,
+}
+
+/// Unit of a specific retention policy (008/15)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RetentionUnit {
+    /// m - Month(s)
+    Months,
+    /// w - Week(s)
+    Weeks,
+    /// y - Year(s)
+    Years,
+    /// e - Edition(s)
+    Editions,
+    /// i - Issue(s)
+    Issues,
+    /// s - Supplement(s)
+    Supplements,
+}
+
+/// Specific retention policy (008/13-15), e.g. "latest 3 years"
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpecificRetentionPolicy {
+    /// 008/13 - Policy type
+    pub policy: RetentionPolicyType,
+    /// 008/14 - Number of units (1-9), if given
+    pub units: Option<u8>,
+    /// 008/15 - Unit type, if given
+    pub unit: Option<RetentionUnit>,
+}
+
+/// Lending policy (008/20)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LendingPolicy {
+    /// a - Will lend
+    WillLend,
+    /// b - Will not lend
+    WillNotLend,
+    /// c - Will lend hard copy only
+    HardCopyOnly,
+    /// l - Limited lending policy
+    Limited,
+    /// u - Unknown
+    Unknown,
+}
+
+/// Reproduction policy (008/21)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReproductionPolicy {
+    /// a - Will reproduce
+    WillReproduce,
+    /// b - Will not reproduce
+    WillNotReproduce,
+    /// u - Unknown
+    Unknown,
 }
 
 /// Completeness of holdings (008/16)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Completeness {
+    /// 0 - Other
+    Other,
     /// 1 - Complete
     Complete,
     /// 2 - Incomplete
@@ -276,6 +397,39 @@ impl HoldingsRecord {
         self.fields.get(tag).map(Vec::as_slice)
     }
 
+    /// Items from the item information fields, 876 then 877 then 878.
+    #[must_use]
+    pub fn items(&self) -> Vec<Item> {
+        ITEM_TAGS
+            .iter()
+            .filter_map(|tag| self.fields.get(*tag))
+            .flatten()
+            .map(Item::from_field)
+            .collect()
+    }
+
+    /// Add an item as an 876, 877 or 878 field, according to its unit.
+    pub fn add_item(&mut self, item: &Item) {
+        self.add_item_information(item.to_field());
+    }
+
+    /// Apply `f` to every item and write the results back in place.
+    pub fn update_items<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut Item),
+    {
+        for tag in ITEM_TAGS {
+            for field in self.fields.get_mut(tag).into_iter().flatten() {
+                let mut item = Item::from_field(field);
+                f(&mut item);
+                let mut updated = item.to_field();
+                updated.indicator1 = field.indicator1;
+                updated.indicator2 = field.indicator2;
+                *field = updated;
+            }
+        }
+    }
+
     /// Add a field
     pub fn add_field(&mut self, field: Field) {
         self.fields
@@ -329,6 +483,10 @@ impl HoldingsRecord {
     }
 
     /// Get acquisition status from 008/06
+    ///
+    /// The codes here do not follow MARC 21 (where `0` is unknown and `1`
+    /// other status); [`receipt_status`](Self::receipt_status) decodes
+    /// the standard values.
     #[must_use]
     pub fn acquisition_status(&self) -> Option<AcquisitionStatus> {
         match control_field_char_at(self, "008", 6)? {
@@ -351,6 +509,95 @@ impl HoldingsRecord {
             'm' => Some(MethodOfAcquisition::Membership),
             'p' => Some(MethodOfAcquisition::Purchase),
             'e' => Some(MethodOfAcquisition::Exchange),
+            'c' => Some(MethodOfAcquisition::CooperativePurchase),
+            'd' => Some(MethodOfAcquisition::Deposit),
+            'n' => Some(MethodOfAcquisition::NonLibraryPurchase),
+            'q' => Some(MethodOfAcquisition::Lease),
+            'z' => Some(MethodOfAcquisition::Other),
+            _ => None,
+        }
+    }
+
+    /// Get receipt or acquisition status from 008/06
+    #[must_use]
+    pub fn receipt_status(&self) -> Option<ReceiptStatus> {
+        match control_field_char_at(self, "008", 6)? {
+            '0' => Some(ReceiptStatus::Unknown),
+            '1' => Some(ReceiptStatus::Other),
+            '2' => Some(ReceiptStatus::ReceivedAndComplete),
+            '3' => Some(ReceiptStatus::OnOrder),
+            '4' => Some(ReceiptStatus::CurrentlyReceived),
+            '5' => Some(ReceiptStatus::NotCurrentlyReceived),
+            _ => None,
+        }
+    }
+
+    /// Get general retention policy from 008/12
+    #[must_use]
+    pub fn general_retention_policy(&self) -> Option<GeneralRetentionPolicy> {
+        match control_field_char_at(self, "008", 12)? {
+            '0' => Some(GeneralRetentionPolicy::Unknown),
+            '1' => Some(GeneralRetentionPolicy::Other),
+            '2' => Some(GeneralRetentionPolicy::ReplacedByUpdates),
+            '3' => Some(GeneralRetentionPolicy::SampleIssue),
+            '4' => Some(GeneralRetentionPolicy::UntilMicroform),
+            '5' => Some(GeneralRetentionPolicy::UntilCumulation),
+            '6' => Some(GeneralRetentionPolicy::LimitedPeriod),
+            '7' => Some(GeneralRetentionPolicy::NotRetained),
+            '8' => Some(GeneralRetentionPolicy::Permanent),
+            _ => None,
+        }
+    }
+
+    /// Get specific retention policy from 008/13-15; `None` when 008/13
+    /// is blank (no specific policy) or not coded
+    #[must_use]
+    pub fn specific_retention_policy(&self) -> Option<SpecificRetentionPolicy> {
+        let policy = match control_field_char_at(self, "008", 13)? {
+            'l' => RetentionPolicyType::Latest,
+            'p' => RetentionPolicyType::Previous,
+            _ => return None,
+        };
+        let units = control_field_char_at(self, "008", 14)
+            .and_then(|c| c.to_digit(10))
+            .filter(|&n| n > 0)
+            .and_then(|n| u8::try_from(n).ok());
+        let unit = match control_field_char_at(self, "008", 15) {
+            Some('m') => Some(RetentionUnit::Months),
+            Some('w') => Some(RetentionUnit::Weeks),
+            Some('y') => Some(RetentionUnit::Years),
+            Some('e') => Some(RetentionUnit::Editions),
+            Some('i') => Some(RetentionUnit::Issues),
+            Some('s') => Some(RetentionUnit::Supplements),
+            _ => None,
+        };
+        Some(SpecificRetentionPolicy {
+            policy,
+            units,
+            unit,
+        })
+    }
+
+    /// Get lending policy from 008/20
+    #[must_use]
+    pub fn lending_policy(&self) -> Option<LendingPolicy> {
+        match control_field_char_at(self, "008", 20)? {
+            'a' => Some(LendingPolicy::WillLend),
+            'b' => Some(LendingPolicy::WillNotLend),
+            'c' => Some(LendingPolicy::HardCopyOnly),
+            'l' => Some(LendingPolicy::Limited),
+            'u' => Some(LendingPolicy::Unknown),
+            _ => None,
+        }
+    }
+
+    /// Get reproduction policy from 008/21
+    #[must_use]
+    pub fn reproduction_policy(&self) -> Option<ReproductionPolicy> {
+        match control_field_char_at(self, "008", 21)? {
+            'a' => Some(ReproductionPolicy::WillReproduce),
+            'b' => Some(ReproductionPolicy::WillNotReproduce),
+            'u' => Some(ReproductionPolicy::Unknown),
             _ => None,
         }
     }
@@ -359,6 +606,7 @@ impl HoldingsRecord {
     #[must_use]
     pub fn completeness(&self) -> Option<Completeness> {
         match control_field_char_at(self, "008", 16)? {
+            '0' => Some(Completeness::Other),
             '1' => Some(Completeness::Complete),
             '2' => Some(Completeness::Incomplete),
             '3' => Some(Completeness::Scattered),
@@ -367,6 +615,25 @@ impl HoldingsRecord {
         }
     }
 
+    /// Parse the paired captions (853/854/855) and enumeration (863/864/865)
+    /// fields for `unit` into a structured [`HoldingsStatement`].
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use mrrc::holdings_statement::HoldingsUnit;
+    ///
+    /// let statement = record.holdings_statement(HoldingsUnit::Basic);
+    /// println!("{}", statement.summary()); // e.g. "v.1(1990)-v.30(2019)"
+    /// for gap in statement.gaps() {
+    ///     println!("missing between {} and {}", gap.after, gap.before);
+    /// }
+    /// ```
+    #[must_use]
+    pub fn holdings_statement(&self, unit: HoldingsUnit) -> HoldingsStatement {
+        HoldingsStatement::from_record(self, unit)
+    }
+
     /// Check if this is a serial holdings record
     #[must_use]
     pub fn is_serial(&self) -> bool {
@@ -675,6 +942,57 @@ mod tests {
         assert_eq!(record.completeness(), Some(Completeness::Complete));
     }
 
+    #[test]
+    fn test_fixed_field_policies() {
+        // 008/06 '4', 07 'c', 12 '8', 13-15 'l3y', 16 '1', 20 'c', 21 'b'
+        let field_008 = "9803034c    8l3y1001cbeng0       ".to_string();
+        let record = HoldingsRecord::builder(create_test_leader())
+            .control_field("008".to_string(), field_008)
+            .build();
+        assert_eq!(
+            record.receipt_status(),
+            Some(ReceiptStatus::CurrentlyReceived)
+        );
+        assert_eq!(
+            record.method_of_acquisition(),
+            Some(MethodOfAcquisition::CooperativePurchase)
+        );
+        assert_eq!(
+            record.general_retention_policy(),
+            Some(GeneralRetentionPolicy::Permanent)
+        );
+        assert_eq!(
+            record.specific_retention_policy(),
+            Some(SpecificRetentionPolicy {
+                policy: RetentionPolicyType::Latest,
+                units: Some(3),
+                unit: Some(RetentionUnit::Years),
+            })
+        );
+        assert_eq!(record.completeness(), Some(Completeness::Complete));
+        assert_eq!(record.lending_policy(), Some(LendingPolicy::HardCopyOnly));
+        assert_eq!(
+            record.reproduction_policy(),
+            Some(ReproductionPolicy::WillNotReproduce)
+        );
+
+        // Blank 008/13-15 means no specific policy; fill characters decode to None.
+        let record = HoldingsRecord::builder(create_test_leader())
+            .control_field(
+                "008".to_string(),
+                "9803032u    |   4001||eng0       ".to_string(),
+            )
+            .build();
+        assert_eq!(
+            record.receipt_status(),
+            Some(ReceiptStatus::ReceivedAndComplete)
+        );
+        assert_eq!(record.specific_retention_policy(), None);
+        assert_eq!(record.general_retention_policy(), None);
+        assert_eq!(record.lending_policy(), None);
+        assert_eq!(record.reproduction_policy(), None);
+    }
+
     #[test]
     fn test_add_location() {
         let leader = create_test_leader();
@@ -911,6 +1229,34 @@ mod tests {
         assert_eq!(record.get_item_information("876").unwrap().len(), 1);
     }
 
+    #[test]
+    fn test_items_and_update() {
+        let mut record = HoldingsRecord::new(create_test_leader());
+        let mut index = Item::new(HoldingsUnit::Index);
+        index.barcode = Some("2".to_string());
+        record.add_item(&index);
+        let mut basic = Item::new(HoldingsUnit::Basic);
+        basic.barcode = Some("1".to_string());
+        basic.status = Some("On order".to_string());
+        record.add_item(&basic);
+
+        let barcodes: Vec<_> = record
+            .items()
+            .into_iter()
+            .filter_map(|item| item.barcode)
+            .collect();
+        assert_eq!(barcodes, ["1", "2"]);
+
+        record.update_items(|item| {
+            item.status = None;
+            item.temporary_location = Some("Reserve".to_string());
+        });
+        let field = &record.get_item_information("876").unwrap()[0];
+        assert_eq!(field.get_subfield('j'), None);
+        assert_eq!(field.get_subfield('l'), Some("Reserve"));
+        assert_eq!(record.items()[1].unit, HoldingsUnit::Index);
+    }
+
     #[test]
     fn test_other_fields() {
         let leader = create_test_leader();
@@ -1026,4 +1372,27 @@ mod tests {
             other => panic!("expected FieldNotFound, got {other:?}"),
         }
     }
+
+    #[test]
+    fn test_holdings_statement_from_record() {
+        let mut record = HoldingsRecord::new(create_test_leader());
+        record.add_captions_basic(
+            Field::builder("853".to_string(), '2', '0')
+                .subfield_str('8', "1")
+                .subfield_str('a', "v.")
+                .subfield_str('i', "(year)")
+                .build(),
+        );
+        record.add_enumeration_basic(
+            Field::builder("863".to_string(), '4', '1')
+                .subfield_str('8', "1.1")
+                .subfield_str('a', "1-30")
+                .subfield_str('i', "1990-2019")
+                .build(),
+        );
+
+        let statement = record.holdings_statement(HoldingsUnit::Basic);
+        assert_eq!(statement.summary(), "v.1(1990)-v.30(2019)");
+        assert!(record.holdings_statement(HoldingsUnit::Index).is_empty());
+    }
 }
@@ -0,0 +1,1340 @@
+//! Structured enumeration/chronology holdings statements (853–855 / 863–865).
+//!
+//! MARC 21 holdings records describe serial runs with paired fields: a
+//! *captions and pattern* field (853 basic, 854 supplements, 855 indexes)
+//! declares the labels for each enumeration and chronology level, and one
+//! or more *enumeration and chronology* fields (863/864/865) carry the
+//! values. The pairs are linked by the link number in subfield `$8`
+//! (`853 $8 1` captions `863 $8 1.1`, `863 $8 1.2`, ...).
+//!
+//! [`HoldingsStatement`] joins the two halves so that callers can render
+//! human-readable summaries ("v.1(1990)-v.30(2019)") and detect gaps
+//! without walking raw subfields.
+//!
+//! [`HoldingsStatement::expand`] and [`HoldingsStatement::compress`]
+//! implement the holdings expansion and compression algorithms: a range
+//! such as `v.1:no.1-v.2:no.12` is itemized issue by issue using the
+//! pattern's units per level (`$u`), numbering continuity (`$v`) and
+//! frequency (`$w`), and runs of consecutive issues are combined back into
+//! ranges. [`HoldingsStatement::to_fields`] turns the result back into
+//! 863/864/865 fields.
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::holdings_statement::{HoldingsStatement, HoldingsUnit};
+//! use mrrc::Field;
+//!
+//! let captions = vec![Field::builder("853".to_string(), '2', '0')
+//!     .subfield_str('8', "1")
+//!     .subfield_str('a', "v.")
+//!     .subfield_str('i', "(year)")
+//!     .build()];
+//! let values = vec![
+//!     Field::builder("863".to_string(), '4', '1')
+//!         .subfield_str('8', "1.1")
+//!         .subfield_str('a', "1-10")
+//!         .subfield_str('i', "1990-1999")
+//!         .build(),
+//!     Field::builder("863".to_string(), '4', '1')
+//!         .subfield_str('8', "1.2")
+//!         .subfield_str('a', "13-30")
+//!         .subfield_str('i', "2002-2019")
+//!         .build(),
+//! ];
+//!
+//! let statement = HoldingsStatement::from_fields(HoldingsUnit::Basic, &captions, &values);
+//! assert_eq!(statement.summary(), "v.1(1990)-v.10(1999), v.13(2002)-v.30(2019)");
+//! let gaps = statement.gaps();
+//! assert_eq!(gaps.len(), 1);
+//! assert_eq!(gaps[0].missing, Some((11, 12)));
+//! ```
+
+use crate::marc_record::MarcRecord;
+use crate::record::Field;
+use serde::{Deserialize, Serialize};
+
+/// Which bibliographic unit a holdings statement describes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum HoldingsUnit {
+    /// Basic bibliographic unit (853/863)
+    Basic,
+    /// Supplementary material (854/864)
+    Supplement,
+    /// Indexes (855/865)
+    Index,
+}
+
+impl HoldingsUnit {
+    /// Tag of the captions and pattern field for this unit.
+    #[must_use]
+    pub fn caption_tag(self) -> &'static str {
+        match self {
+            HoldingsUnit::Basic => "853",
+            HoldingsUnit::Supplement => "854",
+            HoldingsUnit::Index => "855",
+        }
+    }
+
+    /// Tag of the enumeration and chronology field for this unit.
+    #[must_use]
+    pub fn enumeration_tag(self) -> &'static str {
+        match self {
+            HoldingsUnit::Basic => "863",
+            HoldingsUnit::Supplement => "864",
+            HoldingsUnit::Index => "865",
+        }
+    }
+
+    /// Tag of the item information field for this unit.
+    #[must_use]
+    pub fn item_tag(self) -> &'static str {
+        match self {
+            HoldingsUnit::Basic => "876",
+            HoldingsUnit::Supplement => "877",
+            HoldingsUnit::Index => "878",
+        }
+    }
+
+    /// The unit of an item information tag (876, 877 or 878).
+    #[must_use]
+    pub fn from_item_tag(tag: &str) -> Option<Self> {
+        match tag {
+            "876" => Some(HoldingsUnit::Basic),
+            "877" => Some(HoldingsUnit::Supplement),
+            "878" => Some(HoldingsUnit::Index),
+            _ => None,
+        }
+    }
+}
+
+/// The caption declared for one enumeration or chronology level.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LevelCaption {
+    /// Subfield code of the level (`a`–`f` enumeration, `i`–`m` chronology).
+    pub code: char,
+    /// Caption text, e.g. `v.`, `no.`, `(year)`.
+    pub caption: String,
+    /// Units of this level per unit of the next higher level (`$u`), e.g.
+    /// `12` issues per volume, or `var`/`und`.
+    pub units: Option<String>,
+    /// Numbering continuity (`$v`): `r` restarts at each higher-level
+    /// unit, `c` continues across them.
+    pub continuity: Option<char>,
+}
+
+impl LevelCaption {
+    /// Whether the caption is shown in displays.
+    ///
+    /// Captions enclosed in parentheses (`(year)`, `(month)`) name the level
+    /// but are not displayed with the value.
+    #[must_use]
+    pub fn is_displayed(&self) -> bool {
+        !(self.caption.starts_with('(') && self.caption.ends_with(')'))
+    }
+}
+
+/// A parsed captions and pattern field (853/854/855).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CaptionPattern {
+    /// Link number from `$8`.
+    pub link: u32,
+    /// Enumeration level captions (`$a`–`$f`), highest level first.
+    pub enumeration: Vec<LevelCaption>,
+    /// Chronology level captions (`$i`–`$m`), highest level first.
+    pub chronology: Vec<LevelCaption>,
+    /// Frequency code or number of issues per year (`$w`).
+    pub frequency: Option<String>,
+}
+
+impl CaptionPattern {
+    /// Parse a captions and pattern field. Returns `None` without a
+    /// numeric `$8` link number.
+    #[must_use]
+    pub fn from_field(field: &Field) -> Option<Self> {
+        let (link, _) = parse_link(field.get_subfield('8')?)?;
+        let mut pattern = CaptionPattern {
+            link,
+            enumeration: Vec::new(),
+            chronology: Vec::new(),
+            frequency: field.get_subfield('w').map(str::to_string),
+        };
+        for subfield in &field.subfields {
+            let caption = LevelCaption {
+                code: subfield.code,
+                caption: subfield.value.clone(),
+                units: None,
+                continuity: None,
+            };
+            match subfield.code {
+                'a'..='f' => pattern.enumeration.push(caption),
+                'i'..='m' => pattern.chronology.push(caption),
+                // $u and $v describe the enumeration level they follow.
+                'u' => {
+                    if let Some(level) = pattern.enumeration.last_mut() {
+                        level.units = Some(subfield.value.trim().to_string());
+                    }
+                },
+                'v' => {
+                    if let Some(level) = pattern.enumeration.last_mut() {
+                        level.continuity = subfield.value.trim().chars().next();
+                    }
+                },
+                _ => {},
+            }
+        }
+        Some(pattern)
+    }
+
+    fn caption_for(&self, code: char) -> Option<&LevelCaption> {
+        self.enumeration
+            .iter()
+            .chain(&self.chronology)
+            .find(|c| c.code == code)
+    }
+}
+
+/// The value recorded for one level, possibly a range.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LevelValue {
+    /// Subfield code of the level.
+    pub code: char,
+    /// First value held.
+    pub start: String,
+    /// Last value held when the subfield records a range (`1-30`). An empty
+    /// string means an open range (`1-`, currently received).
+    pub end: Option<String>,
+}
+
+impl LevelValue {
+    fn parse(code: char, raw: &str) -> Self {
+        match raw.split_once('-') {
+            Some((start, end)) => LevelValue {
+                code,
+                start: start.trim().to_string(),
+                end: Some(end.trim().to_string()),
+            },
+            None => LevelValue {
+                code,
+                start: raw.trim().to_string(),
+                end: None,
+            },
+        }
+    }
+
+    /// The last value held: the range end when closed, else the start.
+    #[must_use]
+    pub fn last(&self) -> &str {
+        match &self.end {
+            Some(end) if !end.is_empty() => end,
+            _ => &self.start,
+        }
+    }
+}
+
+/// One parsed enumeration and chronology field (863/864/865).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HoldingsEntry {
+    /// Link number from `$8`, matching a [`CaptionPattern::link`].
+    pub link: u32,
+    /// Sequence number from `$8`.
+    pub sequence: u32,
+    /// Enumeration values (`$a`–`$f`), highest level first.
+    pub enumeration: Vec<LevelValue>,
+    /// Chronology values (`$i`–`$m`), highest level first.
+    pub chronology: Vec<LevelValue>,
+    /// Break indicator (`$w`): `g` gap, `n` non-gap break.
+    pub break_indicator: Option<char>,
+    /// Public note (`$z`).
+    pub public_note: Option<String>,
+}
+
+impl HoldingsEntry {
+    /// Parse an enumeration and chronology field. Returns `None` when `$8`
+    /// is missing or has no numeric link number.
+    #[must_use]
+    pub fn from_field(field: &Field) -> Option<Self> {
+        let (link, sequence) = parse_link(field.get_subfield('8')?)?;
+        let mut entry = HoldingsEntry {
+            link,
+            sequence: sequence.unwrap_or(0),
+            enumeration: Vec::new(),
+            chronology: Vec::new(),
+            break_indicator: field.get_subfield('w').and_then(|w| w.chars().next()),
+            public_note: field.get_subfield('z').map(str::to_string),
+        };
+        for subfield in &field.subfields {
+            match subfield.code {
+                'a'..='f' => entry
+                    .enumeration
+                    .push(LevelValue::parse(subfield.code, &subfield.value)),
+                'i'..='m' => entry
+                    .chronology
+                    .push(LevelValue::parse(subfield.code, &subfield.value)),
+                _ => {},
+            }
+        }
+        Some(entry)
+    }
+
+    /// Build the enumeration and chronology field (863/864/865) for this
+    /// entry. The first indicator is `4` (holdings level 4); the second is
+    /// `0` (compressed) for a range and `1` (uncompressed) for one item.
+    #[must_use]
+    pub fn to_field(&self, unit: HoldingsUnit) -> Field {
+        let form = if self.is_range() { '0' } else { '1' };
+        let mut field = Field::new(unit.enumeration_tag().to_string(), '4', form);
+        field.add_subfield_str('8', &format!("{}.{}", self.link, self.sequence));
+        for level in self.enumeration.iter().chain(&self.chronology) {
+            match &level.end {
+                Some(end) => field.add_subfield_str(level.code, &format!("{}-{end}", level.start)),
+                None => field.add_subfield_str(level.code, &level.start),
+            }
+        }
+        if let Some(indicator) = self.break_indicator {
+            field.add_subfield_str('w', &indicator.to_string());
+        }
+        if let Some(note) = &self.public_note {
+            field.add_subfield_str('z', note);
+        }
+        field
+    }
+
+    fn is_open(&self) -> bool {
+        self.enumeration
+            .iter()
+            .chain(&self.chronology)
+            .any(|v| v.end.as_deref() == Some(""))
+    }
+
+    fn is_range(&self) -> bool {
+        self.enumeration
+            .iter()
+            .chain(&self.chronology)
+            .any(|v| v.end.is_some())
+    }
+
+    /// The comparison levels: enumeration when present, else chronology.
+    fn primary_levels(&self) -> &[LevelValue] {
+        if self.enumeration.is_empty() {
+            &self.chronology
+        } else {
+            &self.enumeration
+        }
+    }
+}
+
+/// A gap detected between two consecutive holdings ranges.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HoldingsGap {
+    /// Rendered last issue held before the gap.
+    pub after: String,
+    /// Rendered first issue held after the gap.
+    pub before: String,
+    /// Subfield code of the level at which the gap occurs, when computable.
+    pub level: Option<char>,
+    /// Inclusive range of missing values at that level, when numeric.
+    pub missing: Option<(u64, u64)>,
+}
+
+/// A complete holdings statement for one bibliographic unit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HoldingsStatement {
+    /// Unit described (basic, supplements, indexes).
+    pub unit: HoldingsUnit,
+    /// Parsed captions and pattern fields.
+    pub captions: Vec<CaptionPattern>,
+    /// Parsed enumeration and chronology fields, ordered by link and sequence.
+    pub entries: Vec<HoldingsEntry>,
+}
+
+impl HoldingsStatement {
+    /// Build a statement from caption fields (853/854/855) and value fields
+    /// (863/864/865). Fields without a usable `$8` are skipped.
+    #[must_use]
+    pub fn from_fields(unit: HoldingsUnit, captions: &[Field], values: &[Field]) -> Self {
+        let mut entries: Vec<HoldingsEntry> = values
+            .iter()
+            .filter_map(HoldingsEntry::from_field)
+            .collect();
+        entries.sort_by_key(|e| (e.link, e.sequence));
+        HoldingsStatement {
+            unit,
+            captions: captions
+                .iter()
+                .filter_map(CaptionPattern::from_field)
+                .collect(),
+            entries,
+        }
+    }
+
+    /// Build a statement from the paired fields of any record type, so that
+    /// holdings embedded in bibliographic records are handled too.
+    #[must_use]
+    pub fn from_record<R: MarcRecord + ?Sized>(record: &R, unit: HoldingsUnit) -> Self {
+        Self::from_fields(
+            unit,
+            record.get_fields(unit.caption_tag()).unwrap_or_default(),
+            record
+                .get_fields(unit.enumeration_tag())
+                .unwrap_or_default(),
+        )
+    }
+
+    /// Whether the statement has no enumeration/chronology entries.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The caption pattern linked to `entry`, if any.
+    #[must_use]
+    pub fn pattern_for(&self, entry: &HoldingsEntry) -> Option<&CaptionPattern> {
+        self.captions.iter().find(|c| c.link == entry.link)
+    }
+
+    /// Render one entry, e.g. `v.1:no.1(1990:Jan.)-v.1:no.12(1990:Dec.)`.
+    ///
+    /// Enumeration levels are joined with `:` and prefixed by their
+    /// displayed captions; chronology follows in parentheses (or stands
+    /// alone when there is no enumeration).
+    #[must_use]
+    pub fn render_entry(&self, entry: &HoldingsEntry) -> String {
+        let pattern = self.pattern_for(entry);
+        let start = render_point(pattern, entry, false);
+        if !entry.is_range() {
+            return start;
+        }
+        if entry.is_open() {
+            return format!("{start}-");
+        }
+        format!("{start}-{}", render_point(pattern, entry, true))
+    }
+
+    /// Render the whole statement as a compressed summary such as
+    /// `v.1(1990)-v.30(2019)`.
+    ///
+    /// Entries are separated by `; ` after a recorded gap (`$w g`) and by
+    /// `, ` otherwise.
+    #[must_use]
+    pub fn summary(&self) -> String {
+        let mut out = String::new();
+        let mut previous: Option<&HoldingsEntry> = None;
+        for entry in &self.entries {
+            if let Some(prev) = previous {
+                out.push_str(if prev.break_indicator == Some('g') {
+                    "; "
+                } else {
+                    ", "
+                });
+            }
+            out.push_str(&self.render_entry(entry));
+            previous = Some(entry);
+        }
+        out
+    }
+
+    /// Detect gaps between consecutive entries that share a caption pattern.
+    ///
+    /// A gap is reported when an entry carries the `$w g` break indicator,
+    /// or when comparing the last issue of one entry with the first issue of
+    /// the next shows skipped numbers at the first level where they differ
+    /// (so `v.1:no.12` → `v.2:no.1` is continuous, `v.1:no.3` → `v.1:no.5`
+    /// is missing `no.4`). Entries without enumeration are compared by
+    /// chronology.
+    #[must_use]
+    pub fn gaps(&self) -> Vec<HoldingsGap> {
+        let mut gaps = Vec::new();
+        for pair in self.entries.windows(2) {
+            let (prev, next) = (&pair[0], &pair[1]);
+            if prev.link != next.link || prev.is_open() {
+                continue;
+            }
+            let computed = numeric_gap(prev.primary_levels(), next.primary_levels());
+            if computed.is_none() && prev.break_indicator != Some('g') {
+                continue;
+            }
+            let pattern = self.pattern_for(prev);
+            gaps.push(HoldingsGap {
+                after: render_point(pattern, prev, true),
+                before: render_point(pattern, next, false),
+                level: computed.map(|(code, _)| code),
+                missing: computed.map(|(_, range)| range),
+            });
+        }
+        gaps
+    }
+
+    /// The enumeration and chronology fields (863/864/865) for the
+    /// entries, e.g. to replace a record's fields after
+    /// [`compress`](Self::compress).
+    #[must_use]
+    pub fn to_fields(&self) -> Vec<Field> {
+        self.entries.iter().map(|e| e.to_field(self.unit)).collect()
+    }
+
+    /// Itemize `entry`: one single-issue entry per issue its range covers.
+    ///
+    /// Enumeration is stepped from the lowest level up, using the caption
+    /// pattern's units per higher-level unit (`$u`) and numbering
+    /// continuity (`$v`) to carry into the next level. Chronology is
+    /// stepped by the frequency (`$w`) for year, year/month and
+    /// year/season patterns, or copied to every issue when it is the same
+    /// at both ends of the range. Items carry the entry's public note but
+    /// no break indicator, since itemized holdings show their gaps.
+    ///
+    /// Returns `None` when the range is open, a value is not numeric, or
+    /// the pattern does not say enough to step exactly from the start of
+    /// the range to its end (a missing or `var` `$u`, say, or a year range
+    /// under a monthly pattern with no month level).
+    #[must_use]
+    pub fn expand_entry(&self, entry: &HoldingsEntry) -> Option<Vec<HoldingsEntry>> {
+        if entry.is_open() {
+            return None;
+        }
+        let pattern = self.pattern_for(entry);
+        let mut enumeration = point(&entry.enumeration, false);
+        let last_enumeration = point(&entry.enumeration, true);
+        let mut chronology = point(&entry.chronology, false);
+        let last_chronology = point(&entry.chronology, true);
+        let constant_chronology =
+            !entry.enumeration.is_empty() && same_point(&chronology, &last_chronology);
+
+        let mut items = Vec::new();
+        loop {
+            items.push(HoldingsEntry {
+                link: entry.link,
+                sequence: 0,
+                enumeration: enumeration.clone(),
+                chronology: chronology.clone(),
+                break_indicator: None,
+                public_note: entry.public_note.clone(),
+            });
+            let done = if entry.enumeration.is_empty() {
+                same_point(&chronology, &last_chronology)
+            } else {
+                same_point(&enumeration, &last_enumeration)
+            };
+            if done {
+                break;
+            }
+            if items.len() >= MAX_EXPANDED_ITEMS {
+                return None;
+            }
+            if !entry.enumeration.is_empty() {
+                enumeration = next_enumeration(pattern, &enumeration)?;
+                if is_past(&enumeration, &last_enumeration)? {
+                    return None;
+                }
+            }
+            if !constant_chronology {
+                chronology = next_chronology(pattern, &chronology)?;
+                if is_past(&chronology, &last_chronology)? {
+                    return None;
+                }
+            }
+        }
+        // Stepping must land on the end of the chronology range as well.
+        same_point(&chronology, &last_chronology).then_some(items)
+    }
+
+    /// Expand every entry with [`expand_entry`](Self::expand_entry) and
+    /// renumber the sequences. Entries that cannot be itemized are kept
+    /// as they are.
+    #[must_use]
+    pub fn expand(&self) -> HoldingsStatement {
+        let entries = self
+            .entries
+            .iter()
+            .flat_map(|entry| {
+                self.expand_entry(entry)
+                    .unwrap_or_else(|| vec![entry.clone()])
+            })
+            .collect();
+        self.with_entries(entries)
+    }
+
+    /// Combine consecutive issues into ranges, the inverse of
+    /// [`expand`](Self::expand), and renumber the sequences.
+    ///
+    /// Entries are itemized first, so partly compressed holdings are
+    /// compressed fully. An issue joins the range before it when it has
+    /// the same link and public note and is the next issue of the pattern
+    /// (by enumeration, or by chronology when there is none), with its
+    /// chronology either the same as the range's or stepped the way
+    /// `expand` would step it. Expanding the result therefore gives back
+    /// the itemized holdings. A break indicator recorded on an entry ends
+    /// the range there; other ranges are marked `$w g` when issues are
+    /// missing before the next range and `$w n` when it follows on.
+    /// Entries that cannot be itemized are kept as they are.
+    ///
+    /// ```
+    /// use mrrc::holdings_statement::{HoldingsStatement, HoldingsUnit};
+    /// use mrrc::Field;
+    ///
+    /// let captions = vec![Field::builder("853".to_string(), '2', '0')
+    ///     .subfield_str('8', "1")
+    ///     .subfield_str('a', "v.")
+    ///     .subfield_str('b', "no.")
+    ///     .subfield_str('u', "4")
+    ///     .subfield_str('v', "r")
+    ///     .build()];
+    /// let issues: Vec<Field> = [("1", "3"), ("1", "4"), ("2", "1"), ("2", "3")]
+    ///     .iter()
+    ///     .enumerate()
+    ///     .map(|(i, (v, no))| {
+    ///         Field::builder("863".to_string(), '4', '1')
+    ///             .subfield_str('8', &format!("1.{}", i + 1))
+    ///             .subfield_str('a', v)
+    ///             .subfield_str('b', no)
+    ///             .build()
+    ///     })
+    ///     .collect();
+    ///
+    /// let statement = HoldingsStatement::from_fields(HoldingsUnit::Basic, &captions, &issues);
+    /// let compressed = statement.compress();
+    /// assert_eq!(compressed.summary(), "v.1:no.3-v.2:no.1; v.2:no.3");
+    /// assert_eq!(compressed.expand(), statement);
+    /// ```
+    #[must_use]
+    pub fn compress(&self) -> HoldingsStatement {
+        let mut entries = Vec::new();
+        let mut run: Option<Run> = None;
+        for entry in &self.entries {
+            let Some(mut items) = self.expand_entry(entry) else {
+                if let Some(run) = run.take() {
+                    entries.push(self.close(run, Some(entry)));
+                }
+                entries.push(entry.clone());
+                continue;
+            };
+            if let Some(last) = items.last_mut() {
+                last.break_indicator = entry.break_indicator;
+            }
+            for item in items {
+                run = match run.take() {
+                    Some(mut run) => match self.follows(&run.last, &item) {
+                        Some(stepped) if run.stepped.is_none_or(|s| s == stepped) => {
+                            run.stepped = Some(stepped);
+                            run.last = item;
+                            Some(run)
+                        },
+                        _ => {
+                            entries.push(self.close(run, Some(&item)));
+                            Some(Run::new(item))
+                        },
+                    },
+                    None => Some(Run::new(item)),
+                };
+            }
+        }
+        if let Some(run) = run {
+            entries.push(self.close(run, None));
+        }
+        self.with_entries(entries)
+    }
+
+    /// Whether issue `next` continues a range ending at issue `last`.
+    /// Returns whether the chronology stepped (as opposed to staying the
+    /// same), or `None` if `next` does not follow.
+    fn follows(&self, last: &HoldingsEntry, next: &HoldingsEntry) -> Option<bool> {
+        if last.link != next.link
+            || last.break_indicator.is_some()
+            || last.public_note != next.public_note
+            || last.enumeration.is_empty() != next.enumeration.is_empty()
+        {
+            return None;
+        }
+        let pattern = self.pattern_for(last);
+        let steps_to = |next_chronology: Option<Vec<LevelValue>>| {
+            next_chronology.is_some_and(|c| same_point(&c, &next.chronology))
+        };
+        if last.enumeration.is_empty() {
+            return steps_to(next_chronology(pattern, &last.chronology)).then_some(true);
+        }
+        if !next_enumeration(pattern, &last.enumeration)
+            .is_some_and(|e| same_point(&e, &next.enumeration))
+        {
+            return None;
+        }
+        if same_point(&last.chronology, &next.chronology) {
+            Some(false)
+        } else {
+            steps_to(next_chronology(pattern, &last.chronology)).then_some(true)
+        }
+    }
+
+    /// Turn a run of issues into one entry, marking the break before
+    /// `next` when none is recorded.
+    fn close(&self, run: Run, next: Option<&HoldingsEntry>) -> HoldingsEntry {
+        let Run { first, last, .. } = run;
+        let join = |start: Vec<LevelValue>, end: &[LevelValue]| -> Vec<LevelValue> {
+            start
+                .into_iter()
+                .zip(end)
+                .map(|(mut level, end)| {
+                    if !same_value(&level.start, &end.start) {
+                        level.end = Some(end.start.clone());
+                    }
+                    level
+                })
+                .collect()
+        };
+        let break_indicator = last
+            .break_indicator
+            .or_else(|| next.and_then(|next| self.break_between(&last, next)));
+        HoldingsEntry {
+            enumeration: join(first.enumeration, &last.enumeration),
+            chronology: join(first.chronology, &last.chronology),
+            break_indicator,
+            ..first
+        }
+    }
+
+    /// `g` when issues are missing between issue `last` and entry `next`,
+    /// `n` when `next` starts with the following issue, `None` when the
+    /// pattern cannot tell.
+    fn break_between(&self, last: &HoldingsEntry, next: &HoldingsEntry) -> Option<char> {
+        if last.link != next.link {
+            return None;
+        }
+        let pattern = self.pattern_for(last);
+        let (expected, actual) = if last.enumeration.is_empty() {
+            (
+                next_chronology(pattern, &last.chronology)?,
+                point(&next.chronology, false),
+            )
+        } else {
+            (
+                next_enumeration(pattern, &last.enumeration)?,
+                point(&next.enumeration, false),
+            )
+        };
+        Some(if same_point(&expected, &actual) {
+            'n'
+        } else {
+            'g'
+        })
+    }
+
+    /// A statement with the same captions and `entries`, sequences
+    /// renumbered from 1 within each link.
+    fn with_entries(&self, mut entries: Vec<HoldingsEntry>) -> HoldingsStatement {
+        let mut link = None;
+        let mut sequence = 0;
+        for entry in &mut entries {
+            if link != Some(entry.link) {
+                link = Some(entry.link);
+                sequence = 0;
+            }
+            sequence += 1;
+            entry.sequence = sequence;
+        }
+        HoldingsStatement {
+            unit: self.unit,
+            captions: self.captions.clone(),
+            entries,
+        }
+    }
+}
+
+/// Upper bound on the issues one entry expands to, against runaway
+/// patterns.
+const MAX_EXPANDED_ITEMS: usize = 100_000;
+
+/// Consecutive issues being compressed into one entry.
+struct Run {
+    first: HoldingsEntry,
+    last: HoldingsEntry,
+    /// Whether chronology steps between issues; `None` for a single issue.
+    stepped: Option<bool>,
+}
+
+impl Run {
+    fn new(item: HoldingsEntry) -> Self {
+        Run {
+            first: item.clone(),
+            last: item,
+            stepped: None,
+        }
+    }
+}
+
+/// The start (or end) of each level's range, as single values.
+fn point(levels: &[LevelValue], end: bool) -> Vec<LevelValue> {
+    levels
+        .iter()
+        .map(|level| LevelValue {
+            code: level.code,
+            start: if end { level.last() } else { &level.start }.to_string(),
+            end: None,
+        })
+        .collect()
+}
+
+/// Whether two values are equal, numerically when both are numbers (so
+/// `01` equals `1`).
+fn same_value(a: &str, b: &str) -> bool {
+    match (a.parse::<u64>(), b.parse::<u64>()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+fn same_point(a: &[LevelValue], b: &[LevelValue]) -> bool {
+    a.len() == b.len()
+        && a.iter()
+            .zip(b)
+            .all(|(a, b)| a.code == b.code && same_value(&a.start, &b.start))
+}
+
+/// Whether point `a` comes after point `b`, comparing levels in order.
+/// `None` when a value is not numeric.
+fn is_past(a: &[LevelValue], b: &[LevelValue]) -> Option<bool> {
+    let numbers = |levels: &[LevelValue]| -> Option<Vec<u64>> {
+        levels.iter().map(|l| l.start.parse().ok()).collect()
+    };
+    Some(numbers(a)? > numbers(b)?)
+}
+
+/// `n` with the zero padding of `like` (`09` is followed by `10`, `01` by
+/// `02`).
+fn format_like(n: u64, like: &str) -> String {
+    if like.len() > 1 && like.starts_with('0') {
+        format!("{n:0width$}", width = like.len())
+    } else {
+        n.to_string()
+    }
+}
+
+fn with_values(levels: &[LevelValue], values: &[u64]) -> Vec<LevelValue> {
+    levels
+        .iter()
+        .zip(values)
+        .map(|(level, &n)| LevelValue {
+            code: level.code,
+            start: format_like(n, &level.start),
+            end: None,
+        })
+        .collect()
+}
+
+/// The enumeration of the issue after `levels`: the lowest level goes up
+/// by one, carrying into the level above after `$u` units (restarting at
+/// 1 under `$v r`, continuing under `$v c`). The highest level never
+/// carries.
+fn next_enumeration(
+    pattern: Option<&CaptionPattern>,
+    levels: &[LevelValue],
+) -> Option<Vec<LevelValue>> {
+    let mut values: Vec<u64> = levels
+        .iter()
+        .map(|l| l.start.parse().ok())
+        .collect::<Option<_>>()?;
+    let mut level = values.len().checked_sub(1)?;
+    while level > 0 {
+        let caption = pattern?.caption_for(levels[level].code)?;
+        let units: u64 = caption.units.as_deref()?.parse().ok().filter(|&u| u > 0)?;
+        let current = values[level];
+        if caption.continuity == Some('c') {
+            values[level] = current.checked_add(1)?;
+            if !current.is_multiple_of(units) {
+                return Some(with_values(levels, &values));
+            }
+        } else {
+            if current == 0 || current > units {
+                return None;
+            }
+            if current < units {
+                values[level] = current.checked_add(1)?;
+                return Some(with_values(levels, &values));
+            }
+            values[level] = 1;
+        }
+        level -= 1;
+    }
+    values[0] = values[0].checked_add(1)?;
+    Some(with_values(levels, &values))
+}
+
+/// Issues per year for a `$w` frequency code or number, when regular.
+fn issues_per_year(frequency: &str) -> Option<u64> {
+    match frequency {
+        "a" => Some(1),
+        "f" => Some(2),
+        "t" => Some(3),
+        "q" => Some(4),
+        "b" => Some(6),
+        "m" => Some(12),
+        "s" => Some(24),
+        "e" => Some(26),
+        "w" => Some(52),
+        other => other.parse().ok().filter(|&n| n > 0),
+    }
+}
+
+/// The chronology of the issue after `levels`, stepped by the pattern's
+/// frequency. Handles `(year)` alone (annual, biennial, triennial),
+/// `(year)(month)` when the issues per year divide twelve, and
+/// `(year)(season)` for quarterlies (season codes 21–24).
+fn next_chronology(
+    pattern: Option<&CaptionPattern>,
+    levels: &[LevelValue],
+) -> Option<Vec<LevelValue>> {
+    let pattern = pattern?;
+    let frequency = pattern.frequency.as_deref()?.trim();
+    let kind = |level: &LevelValue| {
+        pattern
+            .caption_for(level.code)
+            .map(|c| c.caption.to_ascii_lowercase())
+            .unwrap_or_default()
+    };
+    let year: u64 = levels.first()?.start.parse().ok()?;
+    if !kind(&levels[0]).contains("year") {
+        return None;
+    }
+    let values = match levels {
+        [_] => {
+            let step = match frequency {
+                "g" => 2,
+                "h" => 3,
+                _ if issues_per_year(frequency)? == 1 => 1,
+                _ => return None,
+            };
+            vec![year.checked_add(step)?]
+        },
+        [_, sub] => {
+            let n: u64 = sub.start.parse().ok()?;
+            let per_year = issues_per_year(frequency)?;
+            let kind = kind(sub);
+            if kind.contains("month") && 12_u64.is_multiple_of(per_year) && (1..=12).contains(&n) {
+                let next = n + 12 / per_year;
+                if next > 12 {
+                    vec![year.checked_add(1)?, next - 12]
+                } else {
+                    vec![year, next]
+                }
+            } else if kind.contains("season") && per_year == 4 && (21..=24).contains(&n) {
+                if n == 24 {
+                    vec![year.checked_add(1)?, 21]
+                } else {
+                    vec![year, n + 1]
+                }
+            } else {
+                return None;
+            }
+        },
+        _ => return None,
+    };
+    let mut next = with_values(levels, &values);
+    // Months and seasons keep a two-digit form across year boundaries.
+    if let (Some(sub), Some(level)) = (next.get_mut(1), levels.get(1)) {
+        sub.start = format!("{:0width$}", values[1], width = level.start.len());
+    }
+    Some(next)
+}
+
+/// Compare the last issue of `prev` with the first of `next`; returns the
+/// level code and missing inclusive range when numbers were skipped.
+fn numeric_gap(prev: &[LevelValue], next: &[LevelValue]) -> Option<(char, (u64, u64))> {
+    for (p, n) in prev.iter().zip(next) {
+        let (Ok(last), Ok(first)) = (p.last().parse::<u64>(), n.start.parse::<u64>()) else {
+            return None;
+        };
+        if last == first {
+            continue;
+        }
+        let after = last.checked_add(1)?;
+        if first > after {
+            return Some((n.code, (after, first - 1)));
+        }
+        return None;
+    }
+    None
+}
+
+fn render_point(pattern: Option<&CaptionPattern>, entry: &HoldingsEntry, end: bool) -> String {
+    let value_of = |v: &LevelValue| -> String {
+        if end {
+            v.last().to_string()
+        } else {
+            v.start.clone()
+        }
+    };
+    let caption_of = |code: char| pattern.and_then(|p| p.caption_for(code));
+
+    let enumeration: Vec<String> = entry
+        .enumeration
+        .iter()
+        .map(|v| {
+            let value = value_of(v);
+            match caption_of(v.code) {
+                Some(c) if c.is_displayed() => format!("{}{value}", c.caption),
+                _ => value,
+            }
+        })
+        .collect();
+    let chronology: Vec<String> = entry
+        .chronology
+        .iter()
+        .map(|v| {
+            let value = value_of(v);
+            match caption_of(v.code) {
+                Some(c) if c.is_displayed() => format!("{}{value}", c.caption),
+                Some(c) => display_chronology(&c.caption, &value),
+                None => value,
+            }
+        })
+        .collect();
+
+    match (enumeration.is_empty(), chronology.is_empty()) {
+        (_, true) => enumeration.join(":"),
+        (true, false) => chronology.join(":"),
+        (false, false) => format!("{}({})", enumeration.join(":"), chronology.join(":")),
+    }
+}
+
+/// Render month and season codes under `(month)`/`(season)` captions as
+/// their usual abbreviations.
+fn display_chronology(caption: &str, value: &str) -> String {
+    const MONTHS: [&str; 12] = [
+        "Jan.", "Feb.", "Mar.", "Apr.", "May", "June", "July", "Aug.", "Sept.", "Oct.", "Nov.",
+        "Dec.",
+    ];
+    const SEASONS: [&str; 4] = ["Spring", "Summer", "Autumn", "Winter"];
+    let caption = caption.to_ascii_lowercase();
+    if !(caption.contains("month") || caption.contains("season")) {
+        return value.to_string();
+    }
+    match value.parse::<usize>() {
+        Ok(m @ 1..=12) => MONTHS[m - 1].to_string(),
+        Ok(s @ 21..=24) => SEASONS[s - 21].to_string(),
+        _ => value.to_string(),
+    }
+}
+
+/// Parse `$8` as `link[.sequence][\type]`.
+fn parse_link(value: &str) -> Option<(u32, Option<u32>)> {
+    let value = value.split('\\').next().unwrap_or(value);
+    match value.split_once('.') {
+        Some((link, seq)) => Some((link.trim().parse().ok()?, seq.trim().parse().ok())),
+        None => Some((value.trim().parse().ok()?, None)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn caption(link: &str, subfields: &[(char, &str)]) -> Field {
+        let mut field = Field::new("853".to_string(), '2', '0');
+        field.add_subfield_str('8', link);
+        for (code, value) in subfields {
+            field.add_subfield_str(*code, value);
+        }
+        field
+    }
+
+    fn value(link: &str, subfields: &[(char, &str)]) -> Field {
+        let mut field = Field::new("863".to_string(), '4', '1');
+        field.add_subfield_str('8', link);
+        for (code, value) in subfields {
+            field.add_subfield_str(*code, value);
+        }
+        field
+    }
+
+    #[test]
+    fn test_simple_range_summary() {
+        let statement = HoldingsStatement::from_fields(
+            HoldingsUnit::Basic,
+            &[caption("1", &[('a', "v."), ('i', "(year)")])],
+            &[value("1.1", &[('a', "1-30"), ('i', "1990-2019")])],
+        );
+        assert_eq!(statement.summary(), "v.1(1990)-v.30(2019)");
+        assert!(statement.gaps().is_empty());
+    }
+
+    #[test]
+    fn test_two_level_with_months() {
+        let statement = HoldingsStatement::from_fields(
+            HoldingsUnit::Basic,
+            &[caption(
+                "1",
+                &[('a', "v."), ('b', "no."), ('i', "(year)"), ('j', "(month)")],
+            )],
+            &[value(
+                "1.1",
+                &[('a', "1"), ('b', "1-12"), ('i', "1990"), ('j', "01-12")],
+            )],
+        );
+        assert_eq!(
+            statement.summary(),
+            "v.1:no.1(1990:Jan.)-v.1:no.12(1990:Dec.)"
+        );
+    }
+
+    #[test]
+    fn test_entries_sorted_by_sequence_and_open_range() {
+        let statement = HoldingsStatement::from_fields(
+            HoldingsUnit::Basic,
+            &[caption("1", &[('a', "v."), ('i', "(year)")])],
+            &[
+                value("1.2", &[('a', "5-"), ('i', "1994-")]),
+                value("1.1", &[('a', "1-3"), ('i', "1990-1992"), ('w', "g")]),
+            ],
+        );
+        assert_eq!(statement.summary(), "v.1(1990)-v.3(1992); v.5(1994)-");
+        let gaps = statement.gaps();
+        assert_eq!(gaps.len(), 1);
+        assert_eq!(gaps[0].after, "v.3(1992)");
+        assert_eq!(gaps[0].before, "v.5(1994)");
+        assert_eq!(gaps[0].level, Some('a'));
+        assert_eq!(gaps[0].missing, Some((4, 4)));
+    }
+
+    #[test]
+    fn test_gap_at_lower_level_and_rollover() {
+        let statement = HoldingsStatement::from_fields(
+            HoldingsUnit::Basic,
+            &[caption("1", &[('a', "v."), ('b', "no.")])],
+            &[
+                value("1.1", &[('a', "1"), ('b', "1-12")]),
+                value("1.2", &[('a', "2"), ('b', "1-3")]),
+                value("1.3", &[('a', "2"), ('b', "6-12")]),
+            ],
+        );
+        let gaps = statement.gaps();
+        assert_eq!(gaps.len(), 1);
+        assert_eq!(gaps[0].level, Some('b'));
+        assert_eq!(gaps[0].missing, Some((4, 5)));
+        assert_eq!(gaps[0].after, "v.2:no.3");
+    }
+
+    #[test]
+    fn test_chronology_only_statement() {
+        let statement = HoldingsStatement::from_fields(
+            HoldingsUnit::Index,
+            &[caption("2", &[('i', "(year)")])],
+            &[
+                value("2.1", &[('i', "1990-1995")]),
+                value("2.2", &[('i', "1998")]),
+            ],
+        );
+        assert_eq!(statement.summary(), "1990-1995, 1998");
+        assert_eq!(statement.gaps()[0].missing, Some((1996, 1997)));
+    }
+
+    #[test]
+    fn test_gap_check_at_numeric_limit() {
+        let statement = HoldingsStatement::from_fields(
+            HoldingsUnit::Basic,
+            &[caption("1", &[('a', "v.")])],
+            &[
+                value("1.1", &[('a', "18446744073709551615")]),
+                value("1.2", &[('a', "1")]),
+            ],
+        );
+        assert!(statement.gaps().is_empty());
+    }
+
+    #[test]
+    fn test_fields_without_link_are_skipped() {
+        let mut unlinked = Field::new("863".to_string(), '4', '1');
+        unlinked.add_subfield_str('a', "1");
+        let statement = HoldingsStatement::from_fields(HoldingsUnit::Basic, &[], &[unlinked]);
+        assert!(statement.is_empty());
+        assert_eq!(statement.summary(), "");
+    }
+
+    #[test]
+    fn test_missing_caption_renders_bare_values() {
+        let statement = HoldingsStatement::from_fields(
+            HoldingsUnit::Basic,
+            &[],
+            &[value("1.1", &[('a', "1-2"), ('i', "1990-1991")])],
+        );
+        assert_eq!(statement.summary(), "1(1990)-2(1991)");
+    }
+    fn monthly_captions() -> Field {
+        caption(
+            "1",
+            &[
+                ('a', "v."),
+                ('b', "no."),
+                ('u', "12"),
+                ('v', "r"),
+                ('i', "(year)"),
+                ('j', "(month)"),
+                ('w', "m"),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_caption_units_and_continuity() {
+        let pattern = CaptionPattern::from_field(&monthly_captions()).unwrap();
+        assert_eq!(pattern.enumeration[0].units, None);
+        assert_eq!(pattern.enumeration[1].units.as_deref(), Some("12"));
+        assert_eq!(pattern.enumeration[1].continuity, Some('r'));
+    }
+
+    #[test]
+    fn test_expand_restarting_numbering_with_months() {
+        let statement = HoldingsStatement::from_fields(
+            HoldingsUnit::Basic,
+            &[monthly_captions()],
+            &[value(
+                "1.1",
+                &[
+                    ('a', "1-2"),
+                    ('b', "11-2"),
+                    ('i', "1990-1991"),
+                    ('j', "11-02"),
+                ],
+            )],
+        );
+        let expanded = statement.expand();
+        let rendered: Vec<String> = expanded
+            .entries
+            .iter()
+            .map(|e| expanded.render_entry(e))
+            .collect();
+        assert_eq!(
+            rendered,
+            [
+                "v.1:no.11(1990:Nov.)",
+                "v.1:no.12(1990:Dec.)",
+                "v.2:no.1(1991:Jan.)",
+                "v.2:no.2(1991:Feb.)",
+            ]
+        );
+        assert_eq!(expanded.entries[3].sequence, 4);
+        assert_eq!(expanded.compress(), statement);
+    }
+
+    #[test]
+    fn test_expand_continuous_numbering() {
+        let statement = HoldingsStatement::from_fields(
+            HoldingsUnit::Basic,
+            &[caption(
+                "1",
+                &[('a', "v."), ('b', "no."), ('u', "4"), ('v', "c")],
+            )],
+            &[value("1.1", &[('a', "1-2"), ('b', "3-6")])],
+        );
+        let expanded = statement.expand();
+        assert_eq!(expanded.summary(), "v.1:no.3, v.1:no.4, v.2:no.5, v.2:no.6");
+    }
+
+    #[test]
+    fn test_expand_refuses_what_the_pattern_cannot_step() {
+        let statement = HoldingsStatement::from_fields(
+            HoldingsUnit::Basic,
+            &[caption(
+                "1",
+                &[('a', "v."), ('b', "no."), ('u', "var"), ('i', "(year)")],
+            )],
+            &[
+                value("1.1", &[('a', "1-2"), ('b', "1-6")]),
+                value("1.2", &[('a', "3"), ('b', "1-2"), ('i', "1992-1993")]),
+                value("1.3", &[('a', "4-"), ('b', "1-")]),
+            ],
+        );
+        assert!(
+            statement
+                .entries
+                .iter()
+                .all(|e| statement.expand_entry(e).is_none())
+        );
+        assert_eq!(statement.expand(), statement);
+    }
+
+    #[test]
+    fn test_compress_stops_at_numeric_limit() {
+        const MAX: &str = "18446744073709551615";
+        for (code, caption_value) in [('a', "v."), ('i', "(year)")] {
+            let statement = HoldingsStatement::from_fields(
+                HoldingsUnit::Basic,
+                &[caption("1", &[(code, caption_value), ('w', "a")])],
+                &[value("1.1", &[(code, MAX)]), value("1.2", &[(code, "1")])],
+            );
+            assert_eq!(statement.compress().entries.len(), 2);
+        }
+    }
+
+    #[test]
+    fn test_compress_marks_breaks() {
+        let issues = [
+            ("1990", Some("n")),
+            ("1991", None),
+            ("1992", None),
+            ("1994", None),
+            ("1995", None),
+        ];
+        let values: Vec<Field> = issues
+            .iter()
+            .enumerate()
+            .map(|(i, (year, note))| {
+                let mut field = value(&format!("1.{}", i + 1), &[('i', year)]);
+                if let Some(note) = note {
+                    field.add_subfield_str('z', note);
+                }
+                field
+            })
+            .collect();
+        let statement = HoldingsStatement::from_fields(
+            HoldingsUnit::Basic,
+            &[caption("1", &[('i', "(year)"), ('w', "a")])],
+            &values,
+        );
+        let compressed = statement.compress();
+        assert_eq!(compressed.summary(), "1990, 1991-1992; 1994-1995");
+        let breaks: Vec<Option<char>> = compressed
+            .entries
+            .iter()
+            .map(|e| e.break_indicator)
+            .collect();
+        assert_eq!(breaks, [Some('n'), Some('g'), None]);
+        assert_eq!(compressed.expand(), statement);
+
+        let fields = compressed.to_fields();
+        assert_eq!(fields[1].tag, "863");
+        assert_eq!(fields[1].indicator2, '0');
+        assert_eq!(fields[1].get_subfield('8'), Some("1.2"));
+        assert_eq!(fields[1].get_subfield('i'), Some("1991-1992"));
+        assert_eq!(fields[1].get_subfield('w'), Some("g"));
+        assert_eq!(fields[0].indicator2, '1');
+        assert_eq!(fields[0].get_subfield('z'), Some("n"));
+    }
+
+    #[test]
+    fn test_compress_keeps_constant_and_stepped_chronology_apart() {
+        let statement = HoldingsStatement::from_fields(
+            HoldingsUnit::Basic,
+            &[caption(
+                "1",
+                &[
+                    ('a', "v."),
+                    ('b', "no."),
+                    ('u', "2"),
+                    ('v', "r"),
+                    ('i', "(year)"),
+                    ('w', "2"),
+                ],
+            )],
+            &[
+                value("1.1", &[('a', "1"), ('b', "1"), ('i', "1990")]),
+                value("1.2", &[('a', "1"), ('b', "2"), ('i', "1990")]),
+                value("1.3", &[('a', "2"), ('b', "1"), ('i', "1991")]),
+                value("1.4", &[('a', "2"), ('b', "2"), ('i', "1991")]),
+            ],
+        );
+        let compressed = statement.compress();
+        assert_eq!(
+            compressed.summary(),
+            "v.1:no.1(1990)-v.1:no.2(1990), v.2:no.1(1991)-v.2:no.2(1991)"
+        );
+        assert_eq!(compressed.expand(), statement);
+    }
+}
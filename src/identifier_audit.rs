@@ -0,0 +1,350 @@
+//! Batch auditing of standard identifiers across a record stream.
+//!
+//! [`IdentifierAuditor`] checks the ISBNs in 020 `$a`, ISSNs in 022 `$a`
+//! and LCCNs in 010 `$a` of every record it is fed, and reports two kinds
+//! of problem:
+//!
+//! - **invalid** — the value fails structural or check-digit validation
+//!   ([`IsbnValidator`](crate::IsbnValidator), [`IssnValidator`],
+//!   [`LccnValidator`]);
+//! - **duplicate** — the value is valid but was already seen on an earlier
+//!   record. ISBNs are compared in ISBN-13 form ([`normalize_isbn`]), so an
+//!   ISBN-10 on one record conflicts with the equivalent ISBN-13 on
+//!   another; LCCNs are compared in [`normalize_lccn`] form.
+//!
+//! Cancelled and incorrect identifiers (`$z`, `$y`) are skipped, since they
+//! are expected to be invalid or shared. Detecting duplicates keeps every
+//! valid identifier seen so far in memory.
+//!
+//! The findings serialize to JSON with [`IdentifierAuditor::to_json`] or to
+//! CSV with [`IdentifierAuditor::to_csv`].
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::identifier_audit::IdentifierAuditor;
+//! use mrrc::{Field, Leader, Record};
+//!
+//! let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+//! record.add_control_field_str("001", "ocm0001");
+//! record.add_field(
+//!     Field::builder("020".to_string(), ' ', ' ')
+//!         .subfield_str('a', "0306406153")
+//!         .build(),
+//! );
+//!
+//! let mut auditor = IdentifierAuditor::new();
+//! auditor.add(&record);
+//! assert_eq!(auditor.findings.len(), 1);
+//! assert!(auditor.to_csv().contains("1,ocm0001,020,isbn,0306406153,invalid,\n"));
+//! ```
+
+use std::collections::HashMap;
+use std::fmt::{self, Write};
+
+use serde::{Deserialize, Serialize};
+
+use crate::bibliographic_helpers::{IssnValidator, LccnValidator};
+use crate::error::{MarcError, Result};
+use crate::formats::FormatReader;
+use crate::normalize::{normalize_isbn, normalize_lccn};
+use crate::record::Record;
+
+/// The kind of standard identifier audited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IdentifierKind {
+    /// International Standard Book Number (020 `$a`)
+    Isbn,
+    /// International Standard Serial Number (022 `$a`)
+    Issn,
+    /// Library of Congress Control Number (010 `$a`)
+    Lccn,
+}
+
+impl IdentifierKind {
+    /// The tag this identifier is audited in.
+    #[must_use]
+    pub fn tag(self) -> &'static str {
+        match self {
+            IdentifierKind::Isbn => "020",
+            IdentifierKind::Issn => "022",
+            IdentifierKind::Lccn => "010",
+        }
+    }
+
+    /// Lowercase name, as used in reports.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            IdentifierKind::Isbn => "isbn",
+            IdentifierKind::Issn => "issn",
+            IdentifierKind::Lccn => "lccn",
+        }
+    }
+
+    /// Validate `value`, returning the comparison key if it is valid.
+    fn key(self, value: &str) -> Option<String> {
+        match self {
+            IdentifierKind::Isbn => normalize_isbn(value),
+            IdentifierKind::Issn => {
+                IssnValidator::validate(value).then(|| IssnValidator::normalize(value))
+            },
+            IdentifierKind::Lccn => LccnValidator::validate(value).then(|| normalize_lccn(value)),
+        }
+    }
+}
+
+impl fmt::Display for IdentifierKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// What is wrong with an identifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase", tag = "type")]
+pub enum IdentifierProblem {
+    /// Fails structural or check-digit validation
+    Invalid,
+    /// Valid, but already present on an earlier record
+    Duplicate {
+        /// 1-based position of the record the identifier was first seen on
+        first_record: u64,
+    },
+}
+
+/// One problem identifier found by the auditor.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IdentifierFinding {
+    /// 1-based position of the record in the audited stream
+    pub record: u64,
+    /// The record's 001, if present
+    pub control_number: Option<String>,
+    /// Kind of identifier
+    pub kind: IdentifierKind,
+    /// The identifier as it appears in the record
+    pub value: String,
+    /// The problem found
+    pub problem: IdentifierProblem,
+}
+
+/// Accumulates invalid and duplicate ISBN, ISSN and LCCN findings over a
+/// stream of records.
+#[derive(Debug, Clone, Default)]
+pub struct IdentifierAuditor {
+    /// Number of records audited
+    pub records: u64,
+    /// Number of identifiers checked
+    pub identifiers: u64,
+    /// Problems found, in stream order
+    pub findings: Vec<IdentifierFinding>,
+    seen: HashMap<(IdentifierKind, String), u64>,
+}
+
+impl IdentifierAuditor {
+    /// Create an empty auditor.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Audit one record.
+    pub fn add(&mut self, record: &Record) {
+        self.records += 1;
+        let position = self.records;
+        let control_number = record.get_control_field("001").map(str::to_string);
+
+        for kind in [
+            IdentifierKind::Lccn,
+            IdentifierKind::Isbn,
+            IdentifierKind::Issn,
+        ] {
+            for field in record.fields_by_tag(kind.tag()) {
+                for value in field.get_subfield_values('a') {
+                    let value = value.trim();
+                    if value.is_empty() {
+                        continue;
+                    }
+                    self.identifiers += 1;
+                    // Qualifiers follow the ISBN itself ("0306406152 (pbk.)").
+                    let candidate = match kind {
+                        IdentifierKind::Isbn => value.split_whitespace().next().unwrap_or(value),
+                        _ => value,
+                    };
+                    let problem = match kind.key(candidate) {
+                        None => Some(IdentifierProblem::Invalid),
+                        Some(key) => {
+                            let first = *self.seen.entry((kind, key)).or_insert(position);
+                            (first != position).then_some(IdentifierProblem::Duplicate {
+                                first_record: first,
+                            })
+                        },
+                    };
+                    if let Some(problem) = problem {
+                        self.findings.push(IdentifierFinding {
+                            record: position,
+                            control_number: control_number.clone(),
+                            kind,
+                            value: value.to_string(),
+                            problem,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    /// Audit every remaining record from `reader`, returning how many were
+    /// read.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from the reader; records read before it
+    /// remain audited.
+    pub fn consume<R: FormatReader + ?Sized>(&mut self, reader: &mut R) -> Result<u64> {
+        let mut read = 0;
+        while let Some(record) = reader.read_record()? {
+            self.add(&record);
+            read += 1;
+        }
+        Ok(read)
+    }
+
+    /// Whether no problems have been found.
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    /// The report as pretty-printed JSON: record and identifier counts plus
+    /// the list of findings.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MarcError::JsonError`] if serialization fails.
+    pub fn to_json(&self) -> Result<String> {
+        let value = serde_json::json!({
+            "records": self.records,
+            "identifiers": self.identifiers,
+            "findings": self.findings,
+        });
+        serde_json::to_string_pretty(&value).map_err(|e| MarcError::JsonError {
+            cause: e,
+            record_index: None,
+            byte_offset: None,
+            source_name: None,
+        })
+    }
+
+    /// The findings as a CSV table with columns
+    /// `record,control_number,tag,kind,value,problem,first_record`.
+    ///
+    /// `first_record` is only filled for duplicates.
+    #[must_use]
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("record,control_number,tag,kind,value,problem,first_record\n");
+        let escape = |s: &str| {
+            if s.contains([',', '"', '\n']) {
+                format!("\"{}\"", s.replace('"', "\"\""))
+            } else {
+                s.to_string()
+            }
+        };
+        for finding in &self.findings {
+            let (problem, first) = match finding.problem {
+                IdentifierProblem::Invalid => ("invalid", String::new()),
+                IdentifierProblem::Duplicate { first_record } => {
+                    ("duplicate", first_record.to_string())
+                },
+            };
+            writeln!(
+                out,
+                "{},{},{},{},{},{problem},{first}",
+                finding.record,
+                escape(finding.control_number.as_deref().unwrap_or("")),
+                finding.kind.tag(),
+                finding.kind,
+                escape(&finding.value),
+            )
+            .ok();
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::leader::Leader;
+    use crate::record::Field;
+
+    fn record(id: &str, tag: &str, values: &[&str]) -> Record {
+        let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+        record.add_control_field_str("001", id);
+        for value in values {
+            record.add_field(
+                Field::builder(tag.to_string(), ' ', ' ')
+                    .subfield_str('a', value)
+                    .build(),
+            );
+        }
+        record
+    }
+
+    #[test]
+    fn test_invalid_identifiers() {
+        let mut auditor = IdentifierAuditor::new();
+        auditor.add(&record("a", "020", &["0306406152 (pbk.)", "9780306406158"]));
+        auditor.add(&record("b", "022", &["0378-5955", "0378-5954"]));
+        auditor.add(&record("c", "010", &["  85000002 ", "85-00000x"]));
+
+        assert_eq!(auditor.records, 3);
+        assert_eq!(auditor.identifiers, 6);
+        let invalid: Vec<_> = auditor
+            .findings
+            .iter()
+            .map(|f| (f.record, f.kind, f.value.as_str()))
+            .collect();
+        assert_eq!(
+            invalid,
+            [
+                (1, IdentifierKind::Isbn, "9780306406158"),
+                (2, IdentifierKind::Issn, "0378-5954"),
+                (3, IdentifierKind::Lccn, "85-00000x"),
+            ]
+        );
+        assert!(
+            auditor
+                .findings
+                .iter()
+                .all(|f| f.problem == IdentifierProblem::Invalid)
+        );
+    }
+
+    #[test]
+    fn test_duplicates_across_records() {
+        let mut auditor = IdentifierAuditor::new();
+        auditor.add(&record("a", "020", &["0306406152", "978-0-306-40615-7"]));
+        auditor.add(&record("b", "020", &["9780306406157"]));
+        auditor.add(&record("c", "010", &["n 78-890351"]));
+        auditor.add(&record("d", "010", &["n78890351"]));
+
+        assert_eq!(auditor.findings.len(), 2);
+        assert_eq!(
+            auditor.findings[0].problem,
+            IdentifierProblem::Duplicate { first_record: 1 }
+        );
+        assert_eq!(auditor.findings[1].control_number.as_deref(), Some("d"));
+        assert_eq!(
+            auditor.findings[1].problem,
+            IdentifierProblem::Duplicate { first_record: 3 }
+        );
+
+        let csv = auditor.to_csv();
+        assert!(csv.contains("2,b,020,isbn,9780306406157,duplicate,1\n"));
+        let json: serde_json::Value = serde_json::from_str(&auditor.to_json().unwrap()).unwrap();
+        assert_eq!(json["findings"][1]["problem"]["type"], "duplicate");
+        assert_eq!(json["findings"][1]["problem"]["first_record"], 3);
+    }
+}
@@ -0,0 +1,265 @@
+//! Keyword indexing text grouped into named buckets.
+//!
+//! Search-engine feeds usually want a handful of text fields per record —
+//! title words, author names, subject terms, notes, and a general keyword
+//! blob — rather than the full MARC structure. An [`IndexProfile`] names
+//! those buckets and lists the [`Selector`]s (the [`crate::extract`]
+//! syntax, e.g. `245$abnp` or `264[*1]$b`) that feed each one.
+//! [`index_text`] (or [`Record::index_text`](crate::Record::index_text))
+//! collects every selected value, strips ISBD punctuation from the word
+//! edges, drops stopwords, and joins what remains with single spaces.
+//!
+//! [`IndexProfile::standard`] is a preset covering common bibliographic
+//! tags with a short English stopword list.
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::index_text::IndexProfile;
+//! use mrrc::{Field, Leader, Record};
+//!
+//! let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+//! record.add_field(
+//!     Field::builder("245".to_string(), '1', '4')
+//!         .subfield_str('a', "The history of the Iowa maps /")
+//!         .subfield_str('c', "by Jane Doe.")
+//!         .build(),
+//! );
+//!
+//! let text = record.index_text(&IndexProfile::standard());
+//! assert_eq!(text["title"], "history Iowa maps");
+//!
+//! let profile = IndexProfile::new().with_bucket("statement", "245$c").unwrap();
+//! assert_eq!(record.index_text(&profile)["statement"], "by Jane Doe");
+//! ```
+
+use std::collections::BTreeSet;
+
+use indexmap::IndexMap;
+
+use crate::error::{MarcError, Result};
+use crate::extract::Selector;
+use crate::marc_record::MarcRecord;
+
+/// Stopwords used by [`IndexProfile::standard`].
+pub const ENGLISH_STOPWORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "for", "from", "in", "into", "is", "it",
+    "its", "of", "on", "or", "the", "to", "with",
+];
+
+/// One named bucket of index text and the selectors that feed it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexBucket {
+    /// Bucket name, used as the key in the output
+    pub name: String,
+    /// Every value of every selector is added to the bucket, in order
+    pub selectors: Vec<Selector>,
+}
+
+/// A set of named index buckets and the stopwords removed from them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IndexProfile {
+    /// Buckets in output order
+    pub buckets: Vec<IndexBucket>,
+    /// Lowercased words dropped from every bucket
+    pub stopwords: BTreeSet<String>,
+}
+
+impl IndexProfile {
+    /// An empty profile with no buckets and no stopwords.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The preset `title`, `author`, `subject`, `notes` and `keyword`
+    /// buckets, with [`ENGLISH_STOPWORDS`].
+    #[must_use]
+    pub fn standard() -> Self {
+        const BUCKETS: [(&str, &str); 5] = [
+            (
+                "title",
+                "130$adfklmnoprs, 210$ab, 222$ab, 240$adfklmnoprs, 245$abfgknps, \
+                 246$abfgnp, 730$adfklmnoprst, 740$anp, 830$adfklmnoprstv",
+            ),
+            (
+                "author",
+                "100$abcdq, 110$abcdn, 111$acdenq, 700$abcdq, 710$abcdn, 711$acdenq, \
+                 245$c",
+            ),
+            (
+                "subject",
+                "600$abcdqtvxyz, 610$abtvxyz, 611$acdentvxyz, 630$adtvxyz, \
+                 648$avxyz, 650$abcvxyz, 651$avxyz, 653$a, 655$avxyz",
+            ),
+            (
+                "notes",
+                "500$a, 501$a, 502$abcdgo, 504$a, 505$agrt, 508$a, 511$a, \
+                 518$a, 520$ab, 521$a, 530$a, 545$ab, 546$ab, 550$a, 586$a",
+            ),
+            (
+                "keyword",
+                "100, 110, 111, 130, 240, 245, 246, 250, 260$abc, 264$abc, 490$av, \
+                 500, 505, 520, 600, 610, 611, 630, 650, 651, 653, 655, 700, 710, \
+                 711, 730, 740, 800, 810, 811, 830",
+            ),
+        ];
+        let buckets = BUCKETS
+            .iter()
+            .map(|(name, selectors)| IndexBucket {
+                name: (*name).to_string(),
+                selectors: selectors
+                    .split(',')
+                    .filter_map(|s| Selector::parse(s).ok())
+                    .collect(),
+            })
+            .collect();
+        IndexProfile {
+            buckets,
+            ..IndexProfile::new()
+        }
+        .with_stopwords(ENGLISH_STOPWORDS.iter().copied())
+    }
+
+    /// Add a bucket fed by a comma-separated list of selectors, such as
+    /// `"245$abnp, 246$abnp"`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MarcError::InvalidField`] if the list is empty or any
+    /// selector is malformed.
+    pub fn with_bucket(mut self, name: &str, selectors: &str) -> Result<Self> {
+        let selectors = selectors
+            .split(',')
+            .filter(|s| !s.trim().is_empty())
+            .map(Selector::parse)
+            .collect::<Result<Vec<_>>>()?;
+        if selectors.is_empty() {
+            return Err(MarcError::invalid_field_msg(format!(
+                "index bucket '{name}' has no selectors"
+            )));
+        }
+        self.buckets.push(IndexBucket {
+            name: name.to_string(),
+            selectors,
+        });
+        Ok(self)
+    }
+
+    /// Add stopwords; matching ignores case.
+    #[must_use]
+    pub fn with_stopwords<'a>(mut self, words: impl IntoIterator<Item = &'a str>) -> Self {
+        self.stopwords
+            .extend(words.into_iter().map(str::to_lowercase));
+        self
+    }
+
+    /// Remove all stopwords, keeping every word.
+    #[must_use]
+    pub fn without_stopwords(mut self) -> Self {
+        self.stopwords.clear();
+        self
+    }
+}
+
+/// Build the index text for each bucket of `profile`.
+///
+/// Every bucket appears in the result, in profile order; a bucket with no
+/// matching values maps to an empty string.
+#[must_use]
+pub fn index_text<R: MarcRecord + ?Sized>(
+    record: &R,
+    profile: &IndexProfile,
+) -> IndexMap<String, String> {
+    profile
+        .buckets
+        .iter()
+        .map(|bucket| {
+            let mut words: Vec<String> = Vec::new();
+            for selector in &bucket.selectors {
+                for value in selector.values(record) {
+                    words.extend(
+                        value
+                            .split_whitespace()
+                            .map(|word| word.trim_matches(|c: char| !c.is_alphanumeric()))
+                            .filter(|word| {
+                                !word.is_empty()
+                                    && !profile.stopwords.contains(&word.to_lowercase())
+                            })
+                            .map(str::to_string),
+                    );
+                }
+            }
+            (bucket.name.clone(), words.join(" "))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::leader::Leader;
+    use crate::record::{Field, Record};
+
+    fn record() -> Record {
+        let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+        record.add_control_field_str("001", "ocm123");
+        for (tag, ind2, subfields) in [
+            ("100", ' ', vec![('a', "Doe, Jane,"), ('d', "1950-")]),
+            (
+                "245",
+                '0',
+                vec![('a', "Maps of Iowa :"), ('b', "a survey /")],
+            ),
+            ("500", ' ', vec![('a', "Includes index.")]),
+            ("650", '0', vec![('a', "Maps"), ('z', "Iowa.")]),
+            ("650", '0', vec![('a', "Atlases")]),
+        ] {
+            let mut field = Field::new(tag.to_string(), ' ', ind2);
+            for (code, value) in subfields {
+                field.add_subfield_str(code, value);
+            }
+            record.add_field(field);
+        }
+        record
+    }
+
+    #[test]
+    fn test_standard_buckets() {
+        let text = record().index_text(&IndexProfile::standard());
+        let keys: Vec<_> = text.keys().map(String::as_str).collect();
+        assert_eq!(keys, ["title", "author", "subject", "notes", "keyword"]);
+        let selectors: usize = IndexProfile::standard()
+            .buckets
+            .iter()
+            .map(|b| b.selectors.len())
+            .sum();
+        assert_eq!(selectors, 9 + 7 + 9 + 15 + 31);
+        assert_eq!(text["title"], "Maps Iowa survey");
+        assert_eq!(text["author"], "Doe Jane 1950");
+        assert_eq!(text["subject"], "Maps Iowa Atlases");
+        assert_eq!(text["notes"], "Includes index");
+        assert_eq!(
+            text["keyword"],
+            "Doe Jane 1950 Maps Iowa survey Includes index Maps Iowa Atlases"
+        );
+    }
+
+    #[test]
+    fn test_custom_profile() {
+        let profile = IndexProfile::new()
+            .with_bucket("title", "245$a")
+            .unwrap()
+            .with_bucket("empty", "246$a")
+            .unwrap();
+        let text = index_text(&record(), &profile);
+        assert_eq!(text["title"], "Maps of Iowa");
+        assert_eq!(text["empty"], "");
+
+        let profile = profile.with_stopwords(["MAPS", "of"]);
+        assert_eq!(index_text(&record(), &profile)["title"], "Iowa");
+
+        assert!(IndexProfile::new().with_bucket("x", " , ").is_err());
+        assert!(IndexProfile::new().with_bucket("x", "24$a").is_err());
+    }
+}
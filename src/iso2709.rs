@@ -101,6 +101,8 @@ pub struct ParseContext {
     current_buffer: Option<std::sync::Arc<Vec<u8>>>,
     /// Absolute stream offset of `current_buffer[0]`.
     current_buffer_base_offset: Option<usize>,
+    /// Size limits enforced by the parse skeleton.
+    pub limits: crate::recovery::ReaderLimits,
 }
 
 impl ParseContext {
@@ -214,6 +216,24 @@ impl ParseContext {
         }
     }
 
+    /// Construct an [`MarcError::RecordLengthInvalid`] inheriting the
+    /// current stream/record positional state.
+    #[must_use]
+    pub fn err_record_length_invalid(
+        &self,
+        found: Option<&[u8]>,
+        expected: impl Into<String>,
+    ) -> MarcError {
+        MarcError::RecordLengthInvalid {
+            record_index: self.record_index_opt(),
+            byte_offset: Some(self.stream_byte_offset),
+            source_name: self.source_name.clone(),
+            found: found.map(crate::error::truncate_bytes),
+            expected: Some(expected.into()),
+            bytes_near: self.capture_bytes_near(),
+        }
+    }
+
     /// Construct an [`MarcError::TruncatedRecord`] inheriting the current
     /// stream/record positional state.
     #[must_use]
@@ -590,6 +610,7 @@ pub fn validate_directory_tag(
     Err(MarcError::WriterError {
         record_index,
         record_control_number: record_control_number.map(String::from),
+        field_tag: Some(tag.to_string()),
         message: format!(
             "Field tag {tag:?} is not 3 ASCII bytes (got {} bytes); cannot fit into the ISO 2709 directory entry's tag field",
             tag.len()
@@ -624,6 +645,7 @@ pub fn check_iso2709_size(
         return Err(MarcError::WriterError {
             record_index,
             record_control_number: record_control_number.map(String::from),
+            field_tag: None,
             message: format!(
                 "Record length exceeds ISO 2709 limit ({record_length} bytes; max {ISO2709_MAX_FIELD})"
             ),
@@ -633,6 +655,7 @@ pub fn check_iso2709_size(
         return Err(MarcError::WriterError {
             record_index,
             record_control_number: record_control_number.map(String::from),
+            field_tag: None,
             message: format!(
                 "Base address exceeds ISO 2709 limit ({base_address} bytes; max {ISO2709_MAX_FIELD})"
             ),
@@ -673,6 +696,7 @@ pub fn check_directory_field_length(
         return Err(MarcError::WriterError {
             record_index,
             record_control_number: record_control_number.map(String::from),
+            field_tag: Some(tag.to_string()),
             message: format!(
                 "Field {tag} length exceeds ISO 2709 directory entry limit ({field_length} bytes; max {ISO2709_MAX_DIRECTORY_FIELD_LEN}); cannot fit into the directory entry's 4-digit length field"
             ),
@@ -885,6 +909,61 @@ fn is_valid_indicator(b: u8) -> bool {
     b.is_ascii_digit() || b == b' '
 }
 
+/// Convert the data fields of a whole ISO 2709 record (leader, directory
+/// and data) that use an alternate subfield delimiter accepted by
+/// `delimiters` to [`SUBFIELD_DELIMITER`], in place; see
+/// [`SubfieldDelimiters`](crate::SubfieldDelimiters) for the rules.
+///
+/// Returns the alternate delimiters converted, in the order first seen.
+/// Nothing is converted when the leader or directory cannot be read; the
+/// parse reports those problems.
+pub(crate) fn standardize_subfield_delimiters(
+    bytes: &mut [u8],
+    delimiters: crate::SubfieldDelimiters,
+) -> Vec<u8> {
+    let mut found = Vec::new();
+    let Some(base) = bytes.get(12..17).and_then(|b| parse_5digits(b).ok()) else {
+        return found;
+    };
+    let directory_end = base.saturating_sub(1).min(bytes.len());
+    let mut entry = LEADER_LEN;
+    while entry + DIRECTORY_ENTRY_LEN <= directory_end {
+        let directory = &bytes[entry..entry + DIRECTORY_ENTRY_LEN];
+        entry += DIRECTORY_ENTRY_LEN;
+        if directory.starts_with(b"00") {
+            continue;
+        }
+        let (Ok(len), Ok(start)) = (
+            parse_4digits(&directory[3..7]),
+            parse_5digits(&directory[7..12]),
+        ) else {
+            continue;
+        };
+        let end = (base + start + len).min(bytes.len());
+        let Some(field) = bytes.get_mut(base + start + 2..end) else {
+            continue;
+        };
+        let is_code =
+            |b: Option<&u8>| b.is_some_and(|b| b.is_ascii_lowercase() || b.is_ascii_digit());
+        let delimiter = field[0];
+        if delimiter == SUBFIELD_DELIMITER
+            || !delimiters.accepts(delimiter)
+            || !is_code(field.get(1))
+        {
+            continue;
+        }
+        for i in 0..field.len() {
+            if field[i] == delimiter && is_code(field.get(i + 1)) {
+                field[i] = SUBFIELD_DELIMITER;
+            }
+        }
+        if !found.contains(&delimiter) {
+            found.push(delimiter);
+        }
+    }
+    found
+}
+
 /// Walk the subfield bytes of a data field (everything after the two
 /// indicator bytes) and produce a vector of [`Subfield`]s.
 ///
@@ -1148,9 +1227,11 @@ mod tests {
             MarcError::WriterError {
                 record_index,
                 record_control_number,
+                field_tag,
                 message,
             } => {
                 assert_eq!(record_index, Some(2));
+                assert_eq!(field_tag.as_deref(), Some("245"));
                 assert_eq!(record_control_number.as_deref(), Some("ctrl-1"));
                 assert!(message.contains("245"), "message names the tag: {message}");
                 assert!(
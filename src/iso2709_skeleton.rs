@@ -225,6 +225,16 @@ where
 
     let record_length = leader.record_length as usize;
 
+    if let Err(err) = check_record_length(&leader_bytes, record_length, ctx) {
+        // Read past the body without buffering it so the next call starts
+        // on the following record.
+        let body_len = record_length.saturating_sub(LEADER_LEN) as u64;
+        let skipped = std::io::copy(&mut (&mut *reader).take(body_len), &mut std::io::sink())
+            .map_err(|e| ctx.err_io(e))?;
+        ctx.advance(LEADER_LEN + usize::try_from(skipped).unwrap_or(0));
+        return Err(err);
+    }
+
     ctx.advance(LEADER_LEN);
 
     // Read the full record data. In non-Strict modes a short read returns
@@ -234,7 +244,29 @@ where
     // truncated-record dispatch in `parse_record_body` is the
     // lenient/permissive recovery point. Wrapping in Arc moves the Vec
     // (no byte copy).
-    let (record_data, bytes_read) = read_record_data(reader, record_length, recovery_mode, ctx)?;
+    let (mut record_data, mut bytes_read) =
+        read_record_data(reader, record_length, recovery_mode, ctx)?;
+    let mut leader = leader;
+    // A leader claiming fewer bytes than its own directory describes leaves
+    // the rest of the record in the stream. Outside strict mode, read on to
+    // the end the directory gives and correct the leader.
+    if recovery_mode != RecoveryMode::Strict
+        && bytes_read == record_length.saturating_sub(LEADER_LEN)
+        && record_data.last() != Some(&iso2709::RECORD_TERMINATOR)
+        && let Some(length) = directory_record_length(&leader, &record_data)
+        && length > record_length
+        && length <= ctx.limits.max_record_len
+    {
+        let extra = (&mut *reader)
+            .take((length - record_length) as u64)
+            .read_to_end(&mut record_data)
+            .map_err(|e| ctx.err_io(e))?;
+        bytes_read += extra;
+        let repaired = record_length + extra;
+        errors.push(record_length_warning(&leader_bytes, repaired, ctx));
+        cap.note(ctx)?;
+        leader.record_length = u32::try_from(repaired).unwrap_or(leader.record_length);
+    }
     let record_data = std::sync::Arc::new(record_data);
     let body_range = 0..record_data.len();
     let buffer_base_offset = ctx.stream_byte_offset;
@@ -253,6 +285,51 @@ where
     )
 }
 
+/// Reject a record whose leader claims more than
+/// [`ReaderLimits::max_record_len`](crate::ReaderLimits::max_record_len).
+fn check_record_length(
+    leader_bytes: &[u8; LEADER_LEN],
+    record_length: usize,
+    ctx: &ParseContext,
+) -> Result<()> {
+    let max = ctx.limits.max_record_len;
+    if record_length > max {
+        return Err(ctx.err_record_length_invalid(
+            Some(&leader_bytes[..5]),
+            format!("at most {max} bytes (reader limit), got {record_length}"),
+        ));
+    }
+    Ok(())
+}
+
+/// The record length implied by the directory in `body` (the bytes after
+/// the leader): the end of the furthest field plus the record terminator.
+/// `None` when the directory is not all in `body` or an entry is
+/// unreadable.
+fn directory_record_length(leader: &Leader, body: &[u8]) -> Option<usize> {
+    let base = leader.data_base_address as usize;
+    let directory = body.get(..base.checked_sub(LEADER_LEN + 1)?)?;
+    let mut end = 0;
+    for entry in directory.chunks(iso2709::DIRECTORY_ENTRY_LEN) {
+        let length = parse_4digits(entry.get(3..7)?).ok()?;
+        let start = parse_5digits(entry.get(7..12)?).ok()?;
+        end = end.max(start + length);
+    }
+    Some(base + end + 1)
+}
+
+/// Warning for a leader record length corrected to `length`.
+fn record_length_warning(
+    leader_bytes: &[u8; LEADER_LEN],
+    length: usize,
+    ctx: &ParseContext,
+) -> MarcError {
+    ctx.err_record_length_invalid(
+        Some(&leader_bytes[..5]),
+        format!("{length:05} (the record's actual length)"),
+    )
+}
+
 /// Parse and validate the 24 leader bytes: structural parse, read-readiness
 /// validation, optional `StrictMarc` semantic validation (dispatched through
 /// the builder), and the per-reader record-type guard.
@@ -353,7 +430,26 @@ pub fn parse_iso2709_record_from_bytes<B: Iso2709Builder>(
         errors,
     )?;
 
-    let record_length = leader.record_length as usize;
+    let mut leader = leader;
+    let mut record_length = leader.record_length as usize;
+    check_record_length(&leader_bytes, record_length, ctx)?;
+    // The buffer runs past the leader's length to a record terminator: the
+    // leader's length is wrong. Outside strict mode, take the buffer's.
+    if recovery_mode != RecoveryMode::Strict
+        && record_bytes.len() > record_length
+        && record_bytes.len() <= ctx.limits.max_record_len
+        && record_bytes.last() == Some(&iso2709::RECORD_TERMINATOR)
+        && record_bytes.get(record_length.wrapping_sub(1)) != Some(&iso2709::RECORD_TERMINATOR)
+    {
+        errors.push(record_length_warning(
+            &leader_bytes,
+            record_bytes.len(),
+            ctx,
+        ));
+        cap.note(ctx)?;
+        record_length = record_bytes.len();
+        leader.record_length = u32::try_from(record_length).unwrap_or(leader.record_length);
+    }
     let expected_data_len = record_length.saturating_sub(LEADER_LEN);
     let buffer_base_offset = ctx.stream_byte_offset;
 
@@ -455,14 +551,19 @@ fn parse_record_body<B: Iso2709Builder>(
     // RECORD_TERMINATOR (0x1D); a different byte means the leader's record
     // length disagrees with the data — the record either runs past or stops
     // short of where the leader said. Strict mode surfaces this as E006;
-    // lenient/permissive let directory parsing proceed and absorb the
-    // disagreement via the existing recovery cap.
-    if recovery_mode == RecoveryMode::Strict
-        && record_data.len() == record_length - LEADER_LEN
+    // lenient/permissive record it and let directory parsing proceed (the
+    // callers have already corrected a leader that claims too little where
+    // the data shows the true length).
+    if record_data.len() == record_length - LEADER_LEN
         && record_data.last() != Some(&iso2709::RECORD_TERMINATOR)
     {
         ctx.stream_byte_offset = record_data_offset + record_data.len() - 1;
-        return Err(ctx.err_end_of_record_not_found());
+        let err = ctx.err_end_of_record_not_found();
+        if recovery_mode == RecoveryMode::Strict {
+            return Err(err);
+        }
+        errors.push(err);
+        cap.note(ctx)?;
     }
 
     // Clamp directory + data slices at the actual buffer length so a short
@@ -480,6 +581,18 @@ fn parse_record_body<B: Iso2709Builder>(
         &[]
     };
 
+    let entry_count = directory.len() / 12;
+    if entry_count > ctx.limits.max_fields {
+        ctx.stream_byte_offset = record_data_offset;
+        return Err(ctx.err_directory_invalid(
+            None,
+            format!(
+                "at most {} directory entries (reader limit), found {entry_count}",
+                ctx.limits.max_fields
+            ),
+        ));
+    }
+
     let mut builder = B::new_for(leader);
 
     // Walk directory entries (12 bytes each: tag(3) + length(4) + start(5)),
@@ -562,6 +675,17 @@ fn parse_record_body<B: Iso2709Builder>(
                 continue;
             },
         };
+        if field_length > ctx.limits.max_field_len {
+            ctx.current_field_tag = tag.as_bytes().try_into().ok();
+            ctx.stream_byte_offset = record_data_offset + pos + 3;
+            return Err(ctx.err_directory_invalid(
+                Some(&entry_chunk[3..7]),
+                format!(
+                    "field length at most {} bytes (reader limit)",
+                    ctx.limits.max_field_len
+                ),
+            ));
+        }
         let start_position = match parse_5digits(&entry_chunk[7..12]) {
             Ok(n) => n,
             Err(parse_err) => {
@@ -633,12 +757,29 @@ fn parse_record_body<B: Iso2709Builder>(
             continue;
         }
 
-        let field_data = &data[start_position..end_position];
+        let mut field_data = &data[start_position..end_position];
 
         if tag == "LDR" {
             continue;
         }
 
+        // Every field should end with FIELD_TERMINATOR (0x1E). Outside
+        // strict mode a field without one is recorded and kept whole: the
+        // decoders below treat the last byte as the terminator, so it is
+        // supplied rather than losing a byte of data.
+        let terminated;
+        if recovery_mode != RecoveryMode::Strict && field_data.last() != Some(&FIELD_TERMINATOR) {
+            ctx.current_field_tag = tag.as_bytes().try_into().ok();
+            ctx.stream_byte_offset =
+                record_data_offset + data_start + end_position.saturating_sub(1);
+            let err = ctx.err_invalid_field(format!("Field {tag} has no field terminator"));
+            ctx.current_field_tag = None;
+            errors.push(err);
+            cap.note(ctx)?;
+            terminated = [field_data, &[FIELD_TERMINATOR]].concat();
+            field_data = &terminated;
+        }
+
         if is_control_field_tag(&tag) {
             let value = match B::decode_control_field_value(field_data, &tag, ctx, validation_level)
             {
@@ -691,8 +832,22 @@ fn parse_record_body<B: Iso2709Builder>(
                 if recovery_mode == RecoveryMode::Strict {
                     return Err(e);
                 }
+                // A bad indicator has an obvious repair: keep the field with
+                // the indicator blanked instead of dropping it.
+                let repaired = e.metadata().indicator_position.and_then(|position| {
+                    blank_indicators(
+                        field_data,
+                        *tag_bytes,
+                        position,
+                        B::parse_config(validation_level),
+                        ctx,
+                    )
+                });
                 errors.push(e);
                 cap.note(ctx)?;
+                if let Some(field) = repaired {
+                    builder.add_data_field(field);
+                }
             },
         }
     }
@@ -705,6 +860,29 @@ fn parse_record_body<B: Iso2709Builder>(
     Ok(Some(builder.finalize()))
 }
 
+/// Re-parse a data field rejected for an invalid indicator, with that
+/// indicator and any other non-digit, non-blank indicator set to a blank.
+fn blank_indicators(
+    field_data: &[u8],
+    tag: [u8; 3],
+    position: u8,
+    config: DataFieldParseConfig,
+    ctx: &ParseContext,
+) -> Option<Field> {
+    let mut bytes = field_data.to_vec();
+    for (i, byte) in bytes.iter_mut().take(2).enumerate() {
+        if i == usize::from(position) || !(byte.is_ascii_digit() || *byte == b' ') {
+            *byte = b' ';
+        }
+    }
+    let config = DataFieldParseConfig {
+        indicator: iso2709::IndicatorMode::Lossy,
+        ..config
+    };
+    let tag = std::str::from_utf8(&tag).ok()?.to_string();
+    parse_data_field(&bytes, tag, config, ctx).ok()
+}
+
 // Re-export a couple of names callers commonly want alongside the trait
 // without forcing them to also `use crate::iso2709::...`.
 pub use iso2709::{DataFieldParseConfig as ParseConfig, FIELD_TERMINATOR as DIRECTORY_TERMINATOR};
@@ -23,6 +23,7 @@
 
 use crate::error::Result;
 use crate::record::{Field, Record};
+use crate::recovery::{self, ParseOptions};
 use serde_json::{Value, json};
 
 /// Convert a MARC record to JSON.
@@ -50,6 +51,7 @@ use serde_json::{Value, json};
 ///
 /// Returns an error if the record cannot be converted to JSON.
 pub fn record_to_json(record: &Record) -> Result<Value> {
+    crate::telemetry::stage_span!("mrrc.convert", from = "marc", to = "json");
     let mut fields = Vec::new();
 
     // Add leader as first item
@@ -115,9 +117,23 @@ pub fn record_to_json(record: &Record) -> Result<Value> {
 ///
 /// Returns an error if the JSON is invalid or missing required fields.
 pub fn json_to_record(json: &Value) -> Result<Record> {
+    json_to_record_with(json, ParseOptions::default())
+}
+
+/// Convert JSON back to a MARC record with the given strictness.
+///
+/// In lenient modes a malformed leader is repaired and missing or invalid
+/// (when checked) indicators become blanks; each repair is recorded in
+/// [`Record::warnings`].
+///
+/// # Errors
+///
+/// Returns an error if the JSON does not have the expected shape, or if
+/// the record is malformed and `options` is strict.
+pub fn json_to_record_with(json: &Value, options: ParseOptions) -> Result<Record> {
     use crate::error::MarcError;
     use crate::iso2709::ParseContext;
-    use crate::leader::Leader;
+    crate::telemetry::stage_span!("mrrc.convert", from = "json", to = "marc");
 
     let mut ctx = ParseContext::new();
     ctx.begin_record();
@@ -140,7 +156,9 @@ pub fn json_to_record(json: &Value) -> Result<Record> {
         .and_then(|v| v.as_str())
         .ok_or_else(|| MarcError::invalid_field_msg("Missing leader field".to_string()))?;
 
-    let leader = Leader::from_bytes(leader_str.as_bytes()).map_err(|e| e.with_position(&ctx))?;
+    let mut warnings = Vec::new();
+    let leader = recovery::leader_from_text(leader_str, options, &mut warnings)
+        .map_err(|e| e.with_position(&ctx))?;
     let mut record = Record::new(leader);
 
     // Process remaining fields
@@ -165,17 +183,30 @@ pub fn json_to_record(json: &Value) -> Result<Record> {
                     MarcError::invalid_field_msg(format!("Field {tag} must be object"))
                 })?;
 
-                let ind1 = field_obj
-                    .get("ind1")
-                    .and_then(|v| v.as_str())
-                    .and_then(|s| s.chars().next())
-                    .ok_or_else(|| MarcError::invalid_field_msg("Missing ind1".to_string()))?;
-
-                let ind2 = field_obj
-                    .get("ind2")
-                    .and_then(|v| v.as_str())
-                    .and_then(|s| s.chars().next())
-                    .ok_or_else(|| MarcError::invalid_field_msg("Missing ind2".to_string()))?;
+                let mut indicator = |key: &str, position: u8| -> Result<char> {
+                    let value = field_obj
+                        .get(key)
+                        .and_then(|v| v.as_str())
+                        .and_then(|s| s.chars().next());
+                    if let Some(c) = value {
+                        return recovery::indicator_from_text(
+                            c,
+                            tag,
+                            position,
+                            options,
+                            &mut warnings,
+                        );
+                    }
+                    let err = MarcError::invalid_field_msg(format!("Missing {key}"))
+                        .with_field_tag(Some(tag.clone()));
+                    if options.is_strict() {
+                        return Err(err);
+                    }
+                    warnings.push(err);
+                    Ok(' ')
+                };
+                let ind1 = indicator("ind1", 0)?;
+                let ind2 = indicator("ind2", 1)?;
 
                 let mut field = Field::new(tag.clone(), ind1, ind2);
 
@@ -195,6 +226,9 @@ pub fn json_to_record(json: &Value) -> Result<Record> {
         }
     }
 
+    if options.collect_warnings && !warnings.is_empty() {
+        record.errors = std::sync::Arc::new(warnings);
+    }
     Ok(record)
 }
 
@@ -83,17 +83,74 @@
 //! - [`reader`] — Reading MARC records from binary data streams
 //! - [`writer`] — Writing MARC records to binary format
 //! - [`formats`] — Format traits and ISO 2709 support
+//! - [`fidelity`] — Byte-exact ISO 2709 round trips
+//! - `arrow` — Arrow record batches of extracted columns (cargo feature `arrow`)
+//! - `parquet` — Parquet datasets of whole records (cargo feature `parquet`)
 //! - [`bibframe`] — BIBFRAME linked data conversion (cargo feature `bibframe`, on by default)
+//! - `bgzf` — Parallel reading and indexed writing of block-gzipped files (cargo feature `gzip`)
 //! - [`boundary_scanner`] — Record boundary detection for parallel processing
+//! - [`stats`] — Shared record, byte, error and rate counters for readers, writers and pipelines
+//! - [`batch`] — One-call parallel conversion (`Batch::new(input).to(writer).run()`) with progress callbacks
+//! - [`checkpoint`] — Resumable stream processing from saved byte offsets
+//! - [`change_log`] — Field- and subfield-level change tracking for automated processing
 //! - [`leader`] — MARC record leader (24-byte header)
+//! - [`record_serde`] — Canonical, versioned serde representation of `Record`
 //! - [`json`] — JSON serialization/deserialization
 //! - [`marcjson`] — MARCJSON format (standard JSON-LD format for MARC)
+//! - [`marc_in_json`] — MARC-in-JSON, the pymarc `as_json()` dialect
 //! - [`marcxml`] — MARCXML serialization/deserialization
+//! - [`xml_security`] — Entity, depth and attribute-size hardening for the XML readers
+//! - [`xml_style`] — Indentation and namespace-prefix layout for the MARCXML and MODS writers
 //! - [`csv`] — CSV (Comma-Separated Values) export format
 //! - [`dublin_core`] — Dublin Core metadata serialization
 //! - [`mods`] — MODS (Metadata Object Description Schema) bidirectional conversion
+//! - [`scripts`] — Scripts used in a record (880 `$6` codes, Unicode ranges) and its language of cataloging
+//! - [`schema_org`] — schema.org JSON-LD (`Book`, `Periodical`, `CreativeWork`) for web pages
+//! - [`openurl`] — `OpenURL` 1.0 KEV context objects and `COinS` spans for link resolvers
+//! - [`authority_reference`] — Typed see-from/see-also references decoded from authority 4XX/5XX
+//! - [`authority_ids`] — Parsing and setting `$0`/`$1` authority identifiers
+//! - [`authority_flip`] — Rewriting bibliographic headings from authority see-from references
+//! - [`holdings_embedding`] — Embedding holdings in bibliographic records and extracting them to MFHDs
+//! - [`holdings_item`] — Typed physical items (barcode, cost, status, location) from 876–878
+//! - [`locations`] — Location code tables (TOML/CSV) for validating and remapping 852 locations
+//! - [`enrichment`] — Filling in `$0` from a pluggable heading resolver
+//! - [`codes`] — MARC language, country and relator code lists
+//! - [`dictionary`] — Embedded MARC 21 field, subfield, leader and 008 names for `Record::explain` and validation
+//! - [`contents`] — Formatted contents notes (505) parsed into entries and rewritten as basic or enhanced
+//! - [`dates`] — 008 date types and 260/264 date statements as EDTF and year ranges
+//! - [`geo`] — Geographic area codes (043) and bounding coordinates (034, 255)
+//! - [`names`] — Personal and corporate name headings parsed into parts, with display and sort forms
+//! - [`normalize`] — NACO, title, ISBN and LCCN normalization for matching
 //! - [`encoding`] — Character encoding support (MARC-8 and UTF-8)
+//! - [`mojibake`] — Detecting and repairing double-encoded text
 //! - [`error`] — Error types and result type
+//! - [`field_protection`] — Protected-field lists honored by overlay and strip operations
+//! - [`field_remap`] — Tag-range field renumbering for local-field migrations
+//! - [`fingerprint`] — Stable record fingerprints for change detection
+//! - [`fixed_field`] — Leader/008 consistency checks and 008 repair
+//! - [`punctuation`] — Adding and removing ISBD punctuation
+//! - [`subfield_order`] — Canonical MARC 21 subfield order per tag
+//! - [`subjects`] — 6XX headings to and from composite `A--B--C` strings, and typed heading components for facets
+//! - [`profile`] — Cataloging profile validation (BIBCO, CONSER, local TOML profiles)
+//! - `provenance` — 005 transaction timestamps and processing provenance stamps (cargo feature `chrono`)
+//! - [`record_set`] — In-memory record collections indexed by control number, ISBN and title
+//! - [`merge_reader`] — Merging already-sorted record files into one stream, with duplicate policies
+//! - [`record_sort`] — External merge sort of record files larger than memory, and sorted writers
+//! - [`record_index`] — Random access to records in a file by position or control number
+//! - [`profiler`] — Field, subfield, indicator and leader statistics over a record stream
+//! - [`identifier_audit`] — Invalid and duplicate ISBN/ISSN/LCCN report over a record stream
+//! - [`index_text`] — Named keyword-index text buckets (title, author, subject, notes, keyword)
+//! - [`linkage_audit`] — Orphaned holdings 004 and authority 014 links to bibliographic records
+//! - [`replacer`] — Regex find and replace over tag/subfield scopes, with capture groups and dry-run reports
+//! - [`search`] — Case- and diacritic-insensitive term search in a record with hit positions and snippets
+//! - [`shared_record`] — `ArcRecord`, a cheaply cloneable thread-safe record handle for fan-out pipelines
+//! - [`extract`] — Extraction expressions with fallback chains (`260$c | 008/7-10`)
+//! - [`crosswalk`] — MARC-to-MODS/Dublin Core/CSV element mappings as data, with TOML/JSON overrides
+//! - [`split_writer`] — Splitting output across files by record count, size or partition key
+//! - [`telemetry`] — Spans and record counters through `tracing` (cargo feature `tracing`)
+//! - [`templates`] — Brief records generated from templates (built in code or TOML) and key/value metadata
+//! - [`unimarc`] — UNIMARC leader, mandatory fields, detection and MARC 21 conversion
+//! - [`urls`] — 856 electronic locations, URL normalization, proxy rewriting and link checking
 //!
 //! ## Format Support
 //!
@@ -107,83 +164,181 @@
 //! - **MODS** — Detailed metadata description schema for libraries
 //! - **Character Encodings** — MARC-8 and UTF-8 with automatic detection
 
+#[cfg(feature = "arrow")]
+pub mod arrow;
+pub mod authority_flip;
+pub mod authority_ids;
 pub mod authority_queries;
 pub mod authority_reader;
 pub mod authority_record;
+pub mod authority_reference;
 pub mod authority_writer;
+pub mod batch;
+#[cfg(feature = "gzip")]
+pub mod bgzf;
 #[cfg(feature = "bibframe")]
 pub mod bibframe;
 pub mod bibliographic_helpers;
 pub mod boundary_scanner;
+pub mod change_log;
+pub mod checkpoint;
+pub mod codes;
+pub mod contents;
+pub mod crosswalk;
 pub mod csv;
+pub mod dates;
+pub mod dictionary;
 pub mod dublin_core;
 pub mod encoding;
 pub mod encoding_validation;
+pub mod enrichment;
 pub mod error;
+pub mod extract;
+pub mod fidelity;
 pub mod field_collection;
 pub mod field_linkage;
+pub mod field_protection;
 pub mod field_query;
 pub mod field_query_helpers;
+pub mod field_remap;
+pub mod fingerprint;
+pub mod fixed_field;
 pub mod format_queries;
 /// Multi-format support with unified Reader/Writer traits.
 ///
 /// See the [`formats`] module documentation for details on supported formats
 /// and how to use format-agnostic code.
 pub mod formats;
+pub mod geo;
+pub mod holdings_embedding;
+pub mod holdings_item;
 pub mod holdings_reader;
 pub mod holdings_record;
+pub mod holdings_statement;
 pub mod holdings_writer;
+pub mod identifier_audit;
+pub mod index_text;
 #[doc(hidden)]
 pub mod iso2709;
 #[doc(hidden)]
 pub mod iso2709_skeleton;
 pub mod json;
 pub mod leader;
+pub mod linkage_audit;
+pub mod lint;
+pub mod locations;
 pub mod macros;
 pub mod marc8_tables;
+pub mod marc_in_json;
 pub mod marc_record;
 pub mod marcjson;
 pub mod marcxml;
+pub mod merge_reader;
 pub mod mods;
+pub mod mojibake;
+pub mod names;
+pub mod normalize;
+pub mod openurl;
+#[cfg(feature = "parquet")]
+pub mod parquet;
 pub mod producer_consumer_pipeline;
+pub mod profile;
+pub mod profiler;
+#[cfg(feature = "chrono")]
+pub mod provenance;
+pub mod punctuation;
 pub mod rayon_parser_pool;
 pub mod reader;
 /// Core MARC record structures (`Record`, `Field`, `Subfield`)
 pub mod record;
 pub mod record_builder_generic;
 pub mod record_helpers;
+pub mod record_index;
+pub mod record_serde;
+pub mod record_set;
+pub mod record_sort;
 pub mod record_validation;
 pub mod recovery;
+pub mod replacer;
+pub mod schema_org;
+pub mod scripts;
+pub mod search;
+pub mod shared_record;
+pub mod split_writer;
+pub mod stats;
+pub mod subfield_order;
+pub mod subjects;
+pub mod telemetry;
+pub mod templates;
+pub mod unimarc;
+pub mod urls;
 pub mod validation;
 pub mod writer;
+pub mod xml_security;
+pub mod xml_style;
 
+pub use authority_flip::{AuthorityFlipper, HeadingFlip};
+pub use authority_ids::{AuthorityId, AuthoritySource};
 pub use authority_queries::AuthorityQueries;
 pub use authority_reader::AuthorityMarcReader;
 pub use authority_record::{
-    AuthorityRecord, AuthorityRecordBuilder, HeadingType, KindOfRecord, LevelOfEstablishment,
+    AuthorityIssue, AuthorityRecord, AuthorityRecordBuilder, HeadingType, KindOfRecord,
+    LevelOfEstablishment, SourceCitation, SubdivisionType, SubdivisionUsage,
 };
+pub use authority_reference::{Reference, ReferenceBuilder, ReferenceKind, SpecialRelationship};
 pub use authority_writer::AuthorityMarcWriter;
-pub use bibliographic_helpers::{IsbnValidator, PublicationInfo};
-pub use encoding_validation::{EncodingAnalysis, EncodingValidator};
+pub use bibliographic_helpers::{IsbnValidator, IssnValidator, LccnValidator, PublicationInfo};
+pub use crosswalk::Crosswalk;
+pub use encoding_validation::{
+    CharsetAudit, CharsetUsage, EncodingAnalysis, EncodingIssue, EncodingIssueKind, EncodingRepair,
+    EncodingValidator, OutsideCoding, RecordCharsetUsage, RepairMode,
+};
+pub use enrichment::{AsyncHeadingResolver, HeadingResolver, RecordEnricher};
 pub use error::{BytesNear, ErrorMetadata, MarcError, Result};
+pub use extract::Extractor;
+pub use fidelity::SourceRecord;
 pub use field_linkage::LinkageInfo;
+pub use field_protection::FieldProtection;
 pub use field_query::{FieldQuery, SubfieldPatternQuery, SubfieldValueQuery, TagRangeQuery};
 pub use field_query_helpers::FieldQueryHelpers;
+pub use fingerprint::FingerprintConfig;
 pub use format_queries::{AuthoritySpecificQueries, BibliographicQueries, HoldingsSpecificQueries};
+pub use holdings_item::Item;
 pub use holdings_reader::HoldingsMarcReader;
 pub use holdings_record::{
-    AcquisitionStatus, Completeness, HoldingsRecord, HoldingsRecordBuilder, HoldingsType,
-    MethodOfAcquisition,
+    AcquisitionStatus, Completeness, GeneralRetentionPolicy, HoldingsRecord, HoldingsRecordBuilder,
+    HoldingsType, LendingPolicy, MethodOfAcquisition, ReceiptStatus, ReproductionPolicy,
+    RetentionPolicyType, RetentionUnit, SpecificRetentionPolicy,
 };
+pub use holdings_statement::{HoldingsStatement, HoldingsUnit};
 pub use holdings_writer::HoldingsMarcWriter;
+pub use identifier_audit::IdentifierAuditor;
+pub use index_text::IndexProfile;
 pub use leader::Leader;
+pub use linkage_audit::LinkageAuditor;
+pub use lint::LintReport;
 pub use marc_record::MarcRecord;
+pub use merge_reader::{Duplicates, MergeReader};
+pub use mojibake::{MojibakeReport, detect_mojibake, repair_mojibake};
 pub use producer_consumer_pipeline::{PipelineConfig, PipelineError, ProducerConsumerPipeline};
+pub use profile::{Profile, ProfileReport};
+pub use profiler::RecordProfiler;
+#[cfg(feature = "chrono")]
+pub use provenance::ProvenanceStamper;
 pub use reader::{MarcReader, parse_record_from_bytes, parse_record_from_shared_bytes};
 pub use record::{Field, FieldBuilder, Record, RecordBuilder, Subfield};
 pub use record_builder_generic::GenericRecordBuilder;
 pub use record_helpers::RecordHelpers;
-pub use record_validation::RecordStructureValidator;
-pub use recovery::{RecoveryMode, ValidationLevel};
+pub use record_set::{IndexKey, RecordSet};
+pub use record_validation::{DictionaryIssue, LinkageIssue, RecordStructureValidator};
+pub use recovery::{ParseOptions, ReaderLimits, RecoveryMode, SubfieldDelimiters, ValidationLevel};
+pub use replacer::Replacer;
+pub use search::SearchOptions;
+pub use shared_record::ArcRecord;
+pub use split_writer::SplitWriter;
+pub use stats::{Stats, StatsSnapshot};
+pub use subfield_order::SubfieldOrder;
 pub use validation::IndicatorValidator;
-pub use writer::MarcWriter;
+pub use writer::{Conformance, MarcWriter, Oversize};
+pub use xml_security::XmlOptions;
+pub use xml_style::{XmlNamespace, XmlStyle};
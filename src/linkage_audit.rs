@@ -0,0 +1,444 @@
+//! Cross-file validation of holdings and authority links to bibliographic
+//! records.
+//!
+//! Holdings records point at the bibliographic record they describe with a
+//! 004 (Control Number for Related Bibliographic Record), and authority
+//! records for serials and series may point at one with 014 `$a` (Link to
+//! Bibliographic Record for Serial or Series Title). Before a migration it
+//! is worth checking that every such link lands on a record that is
+//! actually being migrated.
+//!
+//! [`LinkageAuditor`] collects the 001s of a bibliographic file and the
+//! links of holdings and authority files, in any order, then reports:
+//!
+//! - **orphan** — the link names a control number not in the bibliographic
+//!   file;
+//! - **missing** — a holdings record has no 004 at all.
+//!
+//! Control numbers are compared after trimming, and an organization prefix
+//! in parentheses (`(OCoLC)12345`) on a link is ignored.
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::linkage_audit::{LinkProblem, LinkageAuditor};
+//! use mrrc::{HoldingsRecord, Leader, Record};
+//!
+//! let mut bib = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+//! bib.add_control_field_str("001", "bib1");
+//!
+//! let mut holdings = HoldingsRecord::new(Leader::from_bytes(b"00000nx  a2200000 a 4500").unwrap());
+//! holdings.add_control_field("001".to_string(), "hold1".to_string());
+//! holdings.add_control_field("004".to_string(), "bib2".to_string());
+//!
+//! let mut auditor = LinkageAuditor::new();
+//! auditor.add_bib(&bib);
+//! auditor.add_holdings(&holdings);
+//! let findings = auditor.findings();
+//! assert_eq!(findings[0].target.as_deref(), Some("bib2"));
+//! assert_eq!(findings[0].problem, LinkProblem::Orphan);
+//! ```
+
+use std::collections::HashSet;
+use std::fmt::{self, Write};
+use std::io::Read;
+
+use serde::{Deserialize, Serialize};
+
+use crate::authority_reader::AuthorityMarcReader;
+use crate::error::{MarcError, Result};
+use crate::formats::FormatReader;
+use crate::holdings_reader::HoldingsMarcReader;
+use crate::marc_record::MarcRecord;
+
+/// Where a link to a bibliographic record comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkSource {
+    /// Holdings record 004
+    Holdings,
+    /// Authority record 014 `$a`
+    Authority,
+}
+
+impl LinkSource {
+    /// The tag the link is read from.
+    #[must_use]
+    pub fn tag(self) -> &'static str {
+        match self {
+            LinkSource::Holdings => "004",
+            LinkSource::Authority => "014",
+        }
+    }
+
+    /// Lowercase name, as used in reports.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            LinkSource::Holdings => "holdings",
+            LinkSource::Authority => "authority",
+        }
+    }
+}
+
+impl fmt::Display for LinkSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// What is wrong with a link.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkProblem {
+    /// The linked control number is not in the bibliographic file
+    Orphan,
+    /// A holdings record has no 004
+    Missing,
+}
+
+impl fmt::Display for LinkProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            LinkProblem::Orphan => "orphan",
+            LinkProblem::Missing => "missing",
+        })
+    }
+}
+
+/// One unresolved link.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LinkageFinding {
+    /// Kind of record the link comes from
+    pub source: LinkSource,
+    /// 1-based position of the record among those of its kind
+    pub record: u64,
+    /// The linking record's 001, if present
+    pub control_number: Option<String>,
+    /// The linked control number as it appears in the record; `None` for
+    /// [`LinkProblem::Missing`]
+    pub target: Option<String>,
+    /// The problem found
+    pub problem: LinkProblem,
+}
+
+/// A link recorded for resolution once every file has been read.
+#[derive(Debug, Clone)]
+struct Link {
+    source: LinkSource,
+    record: u64,
+    control_number: Option<String>,
+    target: Option<String>,
+}
+
+/// Collects bibliographic control numbers and holdings/authority links,
+/// and reports the links that do not resolve.
+#[derive(Debug, Clone, Default)]
+pub struct LinkageAuditor {
+    /// Number of bibliographic records read
+    pub bib_records: u64,
+    /// Number of holdings records read
+    pub holdings_records: u64,
+    /// Number of authority records read
+    pub authority_records: u64,
+    bib_ids: HashSet<String>,
+    links: Vec<Link>,
+}
+
+impl LinkageAuditor {
+    /// Create an empty auditor.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a bibliographic record's 001 as a link target.
+    pub fn add_bib<R: MarcRecord + ?Sized>(&mut self, record: &R) {
+        self.bib_records += 1;
+        if let Some(id) = record.get_control_field("001") {
+            self.bib_ids.insert(id.trim().to_string());
+        }
+    }
+
+    /// Record a holdings record's 004 link.
+    pub fn add_holdings<R: MarcRecord + ?Sized>(&mut self, record: &R) {
+        self.holdings_records += 1;
+        self.links.push(Link {
+            source: LinkSource::Holdings,
+            record: self.holdings_records,
+            control_number: record.get_control_field("001").map(str::to_string),
+            target: record
+                .get_control_field("004")
+                .filter(|target| !target.trim().is_empty())
+                .map(str::to_string),
+        });
+    }
+
+    /// Record an authority record's 014 `$a` links; records without a 014
+    /// link to nothing and are only counted.
+    pub fn add_authority<R: MarcRecord + ?Sized>(&mut self, record: &R) {
+        self.authority_records += 1;
+        let control_number = record.get_control_field("001");
+        for field in record.get_fields("014").unwrap_or_default() {
+            for target in field.get_subfield_values('a') {
+                if target.trim().is_empty() {
+                    continue;
+                }
+                self.links.push(Link {
+                    source: LinkSource::Authority,
+                    record: self.authority_records,
+                    control_number: control_number.map(str::to_string),
+                    target: Some(target.to_string()),
+                });
+            }
+        }
+    }
+
+    /// Register every remaining record from a bibliographic reader,
+    /// returning how many were read.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from the reader; records read before it
+    /// remain registered.
+    pub fn consume_bibs<R: FormatReader + ?Sized>(&mut self, reader: &mut R) -> Result<u64> {
+        let mut read = 0;
+        while let Some(record) = reader.read_record()? {
+            self.add_bib(&record);
+            read += 1;
+        }
+        Ok(read)
+    }
+
+    /// Record the links of every remaining holdings record, returning how
+    /// many were read.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from the reader.
+    pub fn consume_holdings<R: Read>(&mut self, reader: &mut HoldingsMarcReader<R>) -> Result<u64> {
+        let mut read = 0;
+        while let Some(record) = reader.read_record()? {
+            self.add_holdings(&record);
+            read += 1;
+        }
+        Ok(read)
+    }
+
+    /// Record the links of every remaining authority record, returning how
+    /// many were read.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from the reader.
+    pub fn consume_authorities<R: Read>(
+        &mut self,
+        reader: &mut AuthorityMarcReader<R>,
+    ) -> Result<u64> {
+        let mut read = 0;
+        while let Some(record) = reader.read_record()? {
+            self.add_authority(&record);
+            read += 1;
+        }
+        Ok(read)
+    }
+
+    /// Links that do not resolve against the bibliographic records seen so
+    /// far, holdings first, each in stream order.
+    #[must_use]
+    pub fn findings(&self) -> Vec<LinkageFinding> {
+        let mut findings: Vec<LinkageFinding> = self
+            .links
+            .iter()
+            .filter_map(|link| {
+                let problem = match &link.target {
+                    None => LinkProblem::Missing,
+                    Some(target) if self.bib_ids.contains(link_key(target)) => return None,
+                    Some(_) => LinkProblem::Orphan,
+                };
+                Some(LinkageFinding {
+                    source: link.source,
+                    record: link.record,
+                    control_number: link.control_number.clone(),
+                    target: link.target.clone(),
+                    problem,
+                })
+            })
+            .collect();
+        findings.sort_by_key(|finding| finding.source == LinkSource::Authority);
+        findings
+    }
+
+    /// Whether every link resolves.
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.findings().is_empty()
+    }
+
+    /// The report as pretty-printed JSON: record counts plus the list of
+    /// findings.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MarcError::JsonError`] if serialization fails.
+    pub fn to_json(&self) -> Result<String> {
+        let value = serde_json::json!({
+            "bib_records": self.bib_records,
+            "holdings_records": self.holdings_records,
+            "authority_records": self.authority_records,
+            "findings": self.findings(),
+        });
+        serde_json::to_string_pretty(&value).map_err(|e| MarcError::JsonError {
+            cause: e,
+            record_index: None,
+            byte_offset: None,
+            source_name: None,
+        })
+    }
+
+    /// The findings as a CSV table with columns
+    /// `source,record,control_number,tag,target,problem`.
+    #[must_use]
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("source,record,control_number,tag,target,problem\n");
+        let escape = |s: &str| {
+            if s.contains([',', '"', '\n']) {
+                format!("\"{}\"", s.replace('"', "\"\""))
+            } else {
+                s.to_string()
+            }
+        };
+        for finding in self.findings() {
+            writeln!(
+                out,
+                "{},{},{},{},{},{}",
+                finding.source,
+                finding.record,
+                escape(finding.control_number.as_deref().unwrap_or("")),
+                finding.source.tag(),
+                escape(finding.target.as_deref().unwrap_or("")),
+                finding.problem,
+            )
+            .ok();
+        }
+        out
+    }
+}
+
+/// The control number a link refers to, without any `(org)` prefix.
+fn link_key(target: &str) -> &str {
+    let target = target.trim();
+    match target
+        .strip_prefix('(')
+        .and_then(|rest| rest.split_once(')'))
+    {
+        Some((_, id)) => id.trim(),
+        None => target,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authority_record::AuthorityRecord;
+    use crate::holdings_record::HoldingsRecord;
+    use crate::leader::Leader;
+    use crate::record::{Field, Record};
+
+    fn bib(id: &str) -> Record {
+        let mut record = Record::new(Leader::from_bytes(b"00000nas a2200000 a 4500").unwrap());
+        record.add_control_field_str("001", id);
+        record
+    }
+
+    fn holdings(id: &str, bib: Option<&str>) -> HoldingsRecord {
+        let mut record =
+            HoldingsRecord::new(Leader::from_bytes(b"00000nx  a2200000 a 4500").unwrap());
+        record.add_control_field("001".to_string(), id.to_string());
+        if let Some(bib) = bib {
+            record.add_control_field("004".to_string(), bib.to_string());
+        }
+        record
+    }
+
+    fn authority(id: &str, links: &[&str]) -> AuthorityRecord {
+        let mut record =
+            AuthorityRecord::new(Leader::from_bytes(b"00000nz  a2200000n  4500").unwrap());
+        record.add_control_field("001".to_string(), id.to_string());
+        for link in links {
+            record.add_field(
+                Field::builder("014".to_string(), ' ', ' ')
+                    .subfield_str('a', link)
+                    .build(),
+            );
+        }
+        record
+    }
+
+    #[test]
+    fn test_orphans_and_missing_links() {
+        let mut auditor = LinkageAuditor::new();
+        auditor.add_holdings(&holdings("h1", Some("b1")));
+        auditor.add_holdings(&holdings("h2", Some("b9")));
+        auditor.add_holdings(&holdings("h3", None));
+        auditor.add_authority(&authority("a1", &["(DLC) b2", "b8"]));
+        auditor.add_authority(&authority("a2", &[]));
+        // Bibs may be registered after the records that link to them.
+        auditor.add_bib(&bib("b1"));
+        auditor.add_bib(&bib(" b2 "));
+
+        let found: Vec<_> = auditor
+            .findings()
+            .into_iter()
+            .map(|f| (f.source, f.record, f.target, f.problem))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (
+                    LinkSource::Holdings,
+                    2,
+                    Some("b9".to_string()),
+                    LinkProblem::Orphan
+                ),
+                (LinkSource::Holdings, 3, None, LinkProblem::Missing),
+                (
+                    LinkSource::Authority,
+                    1,
+                    Some("b8".to_string()),
+                    LinkProblem::Orphan
+                ),
+            ]
+        );
+        assert_eq!(auditor.authority_records, 2);
+        assert!(!auditor.is_clean());
+
+        let csv = auditor.to_csv();
+        assert!(csv.contains("holdings,3,h3,004,,missing\n"));
+        assert!(csv.contains("authority,1,a1,014,b8,orphan\n"));
+        let json: serde_json::Value = serde_json::from_str(&auditor.to_json().unwrap()).unwrap();
+        assert_eq!(json["bib_records"], 2);
+        assert_eq!(json["findings"][0]["problem"], "orphan");
+    }
+
+    #[test]
+    fn test_consume_files() {
+        let mut bytes = Vec::new();
+        {
+            let mut writer = crate::MarcWriter::new(&mut bytes);
+            writer.write_record(&bib("b1")).unwrap();
+        }
+        let mut holdings_bytes = Vec::new();
+        {
+            let mut writer = crate::holdings_writer::HoldingsMarcWriter::new(&mut holdings_bytes);
+            writer.write_record(&holdings("h1", Some("b1"))).unwrap();
+        }
+
+        let mut auditor = LinkageAuditor::new();
+        let mut bibs = crate::MarcReader::new(std::io::Cursor::new(bytes));
+        assert_eq!(auditor.consume_bibs(&mut bibs).unwrap(), 1);
+        let mut holdings = HoldingsMarcReader::new(std::io::Cursor::new(holdings_bytes));
+        assert_eq!(auditor.consume_holdings(&mut holdings).unwrap(), 1);
+        assert!(auditor.is_clean());
+    }
+}
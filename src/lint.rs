@@ -0,0 +1,540 @@
+//! Cataloging lint: everyday record defects, with fixes where they are safe.
+//!
+//! Structural validation ([`crate::RecordStructureValidator`]) answers "is
+//! this a well-formed MARC record?" and a [`crate::profile::Profile`]
+//! answers "does it carry the required elements?". [`lint`] looks for the
+//! slips that get past both:
+//!
+//! - 245 first indicator disagreeing with the presence of a 1XX main entry
+//! - 245 without terminal punctuation in a record that uses ISBD
+//!   punctuation (leader/18 `a` or `i`)
+//! - a nonfiling indicator that does not match a leading article in `$a`
+//! - 260 and 264 in the same record
+//! - the same ISBN in more than one 020
+//! - empty subfields, and subfields holding only whitespace
+//!
+//! Each [`LintIssue`] carries a [`LintFix`] when the correction cannot lose
+//! information: setting an indicator the record itself determines,
+//! appending a period, dropping an empty subfield or an identical
+//! duplicate 020. [`apply_fixes`] applies them; [`fix`] lints and fixes in
+//! one step.
+//!
+//! ```
+//! use mrrc::lint::{self, LintRule};
+//! use mrrc::{Field, Leader, Record};
+//!
+//! let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 i 4500").unwrap());
+//! record.add_field(
+//!     Field::builder("100".to_string(), '1', ' ')
+//!         .subfield_str('a', "Twain, Mark,")
+//!         .build(),
+//! );
+//! record.add_field(
+//!     Field::builder("245".to_string(), '0', '4')
+//!         .subfield_str('a', "The adventures of Tom Sawyer")
+//!         .subfield_str('c', " ")
+//!         .build(),
+//! );
+//!
+//! let report = lint::lint(&record);
+//! let rules: Vec<LintRule> = report.issues.iter().map(|i| i.rule).collect();
+//! assert_eq!(
+//!     rules,
+//!     vec![LintRule::WhitespaceValue, LintRule::TitleAddedEntry, LintRule::TitleEndPunctuation]
+//! );
+//!
+//! assert_eq!(lint::fix(&mut record), 3);
+//! assert!(lint::lint(&record).is_clean());
+//! let title = record.get_field("245").unwrap();
+//! assert_eq!(title.indicator1, '1');
+//! assert_eq!(title.get_subfield('a'), Some("The adventures of Tom Sawyer."));
+//! ```
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::bibliographic_helpers::IsbnValidator;
+use crate::record::{Field, Record};
+
+/// Initial articles recognized by the nonfiling indicator check, lowercase.
+const ARTICLES: &[&str] = &[
+    "a", "an", "the", // English
+    "l", "la", "le", "les", "un", "une", // French
+    "der", "die", "das", "den", "dem", "ein", "eine", "einen", "einem", "einer",
+    "eines", // German
+    "el", "las", "lo", "los", "una", // Spanish
+    "gli", "il", "uno", // Italian
+    "as", "o", "os", "um", "uma", // Portuguese
+    "een", "het", // Dutch
+];
+
+/// Tags whose second indicator counts nonfiling characters.
+const NONFILING_IND2: &[&str] = &[
+    "222", "240", "242", "243", "245", "440", "730", "740", "830",
+];
+
+/// Tags whose first indicator counts nonfiling characters.
+const NONFILING_IND1: &[&str] = &["130", "630"];
+
+/// A kind of defect [`lint`] looks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum LintRule {
+    /// 245 first indicator is 0 with a 1XX present, or 1 without one.
+    TitleAddedEntry,
+    /// 245 does not end with terminal punctuation although the record
+    /// uses ISBD punctuation.
+    TitleEndPunctuation,
+    /// A nonfiling indicator does not match the title's leading article.
+    NonfilingIndicator,
+    /// The record has both 260 and 264.
+    PublicationFields,
+    /// An ISBN appears in more than one 020.
+    DuplicateIsbn,
+    /// A subfield has no value.
+    EmptySubfield,
+    /// A subfield value is only whitespace.
+    WhitespaceValue,
+}
+
+impl LintRule {
+    /// Short identifier for reports (`"title-added-entry"`).
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            LintRule::TitleAddedEntry => "title-added-entry",
+            LintRule::TitleEndPunctuation => "title-end-punctuation",
+            LintRule::NonfilingIndicator => "nonfiling-indicator",
+            LintRule::PublicationFields => "publication-fields",
+            LintRule::DuplicateIsbn => "duplicate-isbn",
+            LintRule::EmptySubfield => "empty-subfield",
+            LintRule::WhitespaceValue => "whitespace-value",
+        }
+    }
+}
+
+impl fmt::Display for LintRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A correction [`apply_fixes`] can make to the field an issue names.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum LintFix {
+    /// Set the first indicator.
+    SetIndicator1(char),
+    /// Set the second indicator.
+    SetIndicator2(char),
+    /// Append a period to the last subfield that is not a control subfield.
+    AppendPeriod,
+    /// Remove the subfield at this position in the field.
+    RemoveSubfield(usize),
+    /// Remove the whole field.
+    RemoveField,
+}
+
+/// One defect found by [`lint`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LintIssue {
+    /// The rule that found it.
+    pub rule: LintRule,
+    /// Tag of the field concerned.
+    pub tag: String,
+    /// Zero-based occurrence among fields with this tag.
+    pub occurrence: usize,
+    /// What is wrong, for people.
+    pub message: String,
+    /// The correction, when one is safe.
+    pub fix: Option<LintFix>,
+}
+
+impl fmt::Display for LintIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "[{}] field {} (occurrence {}): {}",
+            self.rule, self.tag, self.occurrence, self.message
+        )
+    }
+}
+
+/// Result of [`lint`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LintReport {
+    /// Every defect, in field order.
+    pub issues: Vec<LintIssue>,
+}
+
+impl LintReport {
+    /// Whether no defects were found.
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Issues that [`apply_fixes`] can correct.
+    pub fn fixable(&self) -> impl Iterator<Item = &LintIssue> {
+        self.issues.iter().filter(|i| i.fix.is_some())
+    }
+
+    /// Issues found by one rule.
+    pub fn by_rule(&self, rule: LintRule) -> impl Iterator<Item = &LintIssue> {
+        self.issues.iter().filter(move |i| i.rule == rule)
+    }
+}
+
+/// Check a record for common cataloging defects.
+#[must_use]
+pub fn lint(record: &Record) -> LintReport {
+    let mut issues = Vec::new();
+    let has_main_entry = record.fields.keys().any(|tag| tag.starts_with('1'));
+    let isbd = matches!(record.leader.cataloging_form, 'a' | 'i');
+    let mut isbns: HashMap<String, &Field> = HashMap::new();
+
+    for (tag, fields) in &record.fields {
+        for (occurrence, field) in fields.iter().enumerate() {
+            let mut issue = |rule: LintRule, message: String, fix: Option<LintFix>| {
+                issues.push(LintIssue {
+                    rule,
+                    tag: tag.clone(),
+                    occurrence,
+                    message,
+                    fix,
+                });
+            };
+
+            for (i, subfield) in field.subfields.iter().enumerate() {
+                if subfield.value.is_empty() {
+                    issue(
+                        LintRule::EmptySubfield,
+                        format!("${} is empty", subfield.code),
+                        Some(LintFix::RemoveSubfield(i)),
+                    );
+                } else if subfield.value.trim().is_empty() {
+                    issue(
+                        LintRule::WhitespaceValue,
+                        format!("${} holds only whitespace", subfield.code),
+                        Some(LintFix::RemoveSubfield(i)),
+                    );
+                }
+            }
+
+            if tag == "245" {
+                match (field.indicator1, has_main_entry) {
+                    ('0', true) => issue(
+                        LintRule::TitleAddedEntry,
+                        "first indicator 0 but the record has a 1XX main entry".to_string(),
+                        Some(LintFix::SetIndicator1('1')),
+                    ),
+                    ('1', false) => issue(
+                        LintRule::TitleAddedEntry,
+                        "first indicator 1 but the record has no 1XX main entry".to_string(),
+                        Some(LintFix::SetIndicator1('0')),
+                    ),
+                    _ => {},
+                }
+                if isbd && let Some((message, fix)) = check_title_ending(field) {
+                    issue(LintRule::TitleEndPunctuation, message, fix);
+                }
+            }
+
+            if let Some((message, fix)) = check_nonfiling(tag, field) {
+                issue(LintRule::NonfilingIndicator, message, fix);
+            }
+
+            if tag == "260" && occurrence == 0 && record.fields.contains_key("264") {
+                issue(
+                    LintRule::PublicationFields,
+                    "record has both 260 and 264".to_string(),
+                    None,
+                );
+            }
+
+            if tag == "020"
+                && let Some(isbn) = field.get_subfield('a')
+                && let Some(token) = isbn.split_whitespace().next()
+            {
+                let key = IsbnValidator::normalize(token).to_ascii_uppercase();
+                if let Some(first) = isbns.get(&key) {
+                    let identical = first.indicator1 == field.indicator1
+                        && first.indicator2 == field.indicator2
+                        && first.subfields == field.subfields;
+                    issue(
+                        LintRule::DuplicateIsbn,
+                        format!("ISBN {token} already appears in an earlier 020"),
+                        identical.then_some(LintFix::RemoveField),
+                    );
+                } else {
+                    isbns.insert(key, field);
+                }
+            }
+        }
+    }
+    LintReport { issues }
+}
+
+/// Check a title's nonfiling indicator against its leading article. A
+/// wrong count for an article is fixable; a count with no article we
+/// recognize is reported without a fix, since the article may be in a
+/// language [`ARTICLES`] does not cover.
+fn check_nonfiling(tag: &str, field: &Field) -> Option<(String, Option<LintFix>)> {
+    let (indicator, set): (char, fn(char) -> LintFix) = if NONFILING_IND2.contains(&tag) {
+        (field.indicator2, LintFix::SetIndicator2)
+    } else if NONFILING_IND1.contains(&tag) {
+        (field.indicator1, LintFix::SetIndicator1)
+    } else {
+        return None;
+    };
+    let count = indicator.to_digit(10).filter(|&n| n > 0)?;
+    let title = field.get_subfield('a').unwrap_or_default();
+    match leading_article_len(title).and_then(|len| u32::try_from(len).ok()) {
+        Some(len) if len == count => None,
+        Some(len) if len <= 9 => Some((
+            format!("nonfiling count {count} but the leading article is {len} characters"),
+            char::from_digit(len, 10).map(set),
+        )),
+        _ => Some((
+            format!("nonfiling count {count} but $a does not begin with an article"),
+            None,
+        )),
+    }
+}
+
+/// Check that a 245 ends with `.`, `?` or `!` (before any closing quote or
+/// bracket). A missing period is fixable; a trailing ISBD separator means
+/// something else is wrong, so it is reported without a fix.
+fn check_title_ending(field: &Field) -> Option<(String, Option<LintFix>)> {
+    let last = field
+        .subfields
+        .iter()
+        .rev()
+        .find(|s| !s.code.is_ascii_digit() && !s.value.trim().is_empty())?;
+    let text = last
+        .value
+        .trim_end()
+        .trim_end_matches([']', ')', '"', '\'', '\u{201D}', '\u{2019}']);
+    match text.chars().last() {
+        Some('.' | '?' | '!') | None => None,
+        Some(mark @ (':' | ';' | '/' | '=' | ',' | '+')) => Some((
+            format!("ends with \"{mark}\" instead of terminal punctuation"),
+            None,
+        )),
+        Some(_) => Some((
+            "does not end with a period".to_string(),
+            Some(LintFix::AppendPeriod),
+        )),
+    }
+}
+
+/// Characters an initial article occupies, including any punctuation
+/// before it and the space or apostrophe after it (`"The "` is 4,
+/// `"L'"` is 2). `None` if the title does not begin with an article.
+fn leading_article_len(title: &str) -> Option<usize> {
+    let lead = title.chars().take_while(|c| !c.is_alphanumeric()).count();
+    let rest: String = title.chars().skip(lead).collect();
+    let word_len = rest.chars().take_while(|c| c.is_alphabetic()).count();
+    let next = rest.chars().nth(word_len);
+    if !matches!(next, Some(' ' | '\'' | '\u{2019}')) {
+        return None;
+    }
+    let word: String = rest
+        .chars()
+        .take(word_len)
+        .collect::<String>()
+        .to_lowercase();
+    // "L'" elides; a bare "l" is not a word
+    if word == "l" && next == Some(' ') {
+        return None;
+    }
+    ARTICLES
+        .contains(&word.as_str())
+        .then_some(lead + word_len + 1)
+}
+
+/// Apply the fixes in `report`, which must come from [`lint`] on this
+/// record unchanged since. Returns the number of fixes applied.
+pub fn apply_fixes(record: &mut Record, report: &LintReport) -> usize {
+    // Later fields and subfields first, so positions stay valid; a field's
+    // removal comes after any other fix to it
+    let mut fixes: Vec<(&str, usize, &LintFix)> = report
+        .issues
+        .iter()
+        .filter_map(|i| Some((i.tag.as_str(), i.occurrence, i.fix.as_ref()?)))
+        .collect();
+    fixes.sort_by_key(|(tag, occurrence, fix)| {
+        let (rank, position) = match fix {
+            LintFix::RemoveSubfield(i) => (1, Reverse(*i)),
+            LintFix::RemoveField => (2, Reverse(0)),
+            _ => (0, Reverse(0)),
+        };
+        (*tag, Reverse(*occurrence), rank, position)
+    });
+    fixes.dedup();
+
+    let mut applied = 0;
+    for (tag, occurrence, fix) in fixes {
+        let Some(fields) = record.fields.get_mut(tag) else {
+            continue;
+        };
+        if occurrence >= fields.len() {
+            continue;
+        }
+        let field = &mut fields[occurrence];
+        match fix {
+            LintFix::SetIndicator1(value) => field.indicator1 = *value,
+            LintFix::SetIndicator2(value) => field.indicator2 = *value,
+            LintFix::AppendPeriod => {
+                let Some(last) = field
+                    .subfields
+                    .iter_mut()
+                    .rev()
+                    .find(|s| !s.code.is_ascii_digit() && !s.value.trim().is_empty())
+                else {
+                    continue;
+                };
+                let trimmed = last.value.trim_end().len();
+                last.value.truncate(trimmed);
+                last.value.push('.');
+            },
+            LintFix::RemoveSubfield(i) => {
+                if *i >= field.subfields.len() {
+                    continue;
+                }
+                field.subfields.remove(*i);
+            },
+            LintFix::RemoveField => {
+                fields.remove(occurrence);
+                if fields.is_empty() {
+                    record.fields.shift_remove(tag);
+                }
+            },
+        }
+        applied += 1;
+    }
+    applied
+}
+
+/// Lint `record` and apply every safe fix. Returns the number applied;
+/// lint again to see what is left for a cataloger.
+pub fn fix(record: &mut Record) -> usize {
+    let report = lint(record);
+    apply_fixes(record, &report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::leader::Leader;
+
+    fn field(tag: &str, ind1: char, ind2: char, subfields: &[(char, &str)]) -> Field {
+        let mut field = Field::new(tag.to_string(), ind1, ind2);
+        for (code, value) in subfields {
+            field.add_subfield_str(*code, value);
+        }
+        field
+    }
+
+    fn record(fields: Vec<Field>) -> Record {
+        let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 i 4500").unwrap());
+        for f in fields {
+            record.add_field(f);
+        }
+        record
+    }
+
+    #[test]
+    fn test_nonfiling_indicator_and_articles() {
+        assert_eq!(leading_article_len("The cat"), Some(4));
+        assert_eq!(leading_article_len("\"A\" is for alibi"), None);
+        assert_eq!(leading_article_len("L'étranger"), Some(2));
+        assert_eq!(leading_article_len("[The] cat"), None);
+        assert_eq!(leading_article_len("Theory"), None);
+
+        let mut rec = record(vec![
+            field("245", '1', '2', &[('a', "The cat.")]),
+            field("830", ' ', '4', &[('a', "Studies in history.")]),
+            field("130", '4', ' ', &[('a', "An album.")]),
+        ]);
+        let report = lint(&rec);
+        let found: Vec<_> = report
+            .by_rule(LintRule::NonfilingIndicator)
+            .map(|i| (i.tag.as_str(), i.fix.clone()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("245", Some(LintFix::SetIndicator2('4'))),
+                ("830", None),
+                ("130", Some(LintFix::SetIndicator1('3'))),
+            ]
+        );
+        assert_eq!(fix(&mut rec), 2);
+        assert_eq!(rec.get_field("245").unwrap().indicator2, '4');
+        assert_eq!(rec.get_field("130").unwrap().indicator1, '3');
+    }
+
+    #[test]
+    fn test_duplicate_isbns_and_publication_fields() {
+        let isbn = [('a', "978-0-306-40615-7 (pbk.)")];
+        let mut rec = record(vec![
+            field("020", ' ', ' ', &isbn),
+            field("020", ' ', ' ', &[('a', "9780306406157"), ('q', "ebook")]),
+            field("020", ' ', ' ', &isbn),
+            field("260", ' ', ' ', &[('a', "New York")]),
+            field("264", ' ', '1', &[('a', "New York")]),
+        ]);
+        let report = lint(&rec);
+        let found: Vec<_> = report
+            .issues
+            .iter()
+            .map(|i| (i.rule, i.occurrence, i.fix.clone()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (LintRule::DuplicateIsbn, 1, None),
+                (LintRule::DuplicateIsbn, 2, Some(LintFix::RemoveField)),
+                (LintRule::PublicationFields, 0, None),
+            ]
+        );
+        assert_eq!(
+            report.issues[2].to_string(),
+            "[publication-fields] field 260 (occurrence 0): record has both 260 and 264"
+        );
+
+        assert_eq!(apply_fixes(&mut rec, &report), 1);
+        assert_eq!(rec.get_fields("020").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_title_ending_depends_on_isbd_and_separators() {
+        let mut rec = record(vec![field(
+            "245",
+            '0',
+            '0',
+            &[('a', "Roughing it :"), ('b', "a memoir /"), ('6', "880-01")],
+        )]);
+        let report = lint(&rec);
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(report.issues[0].fix, None);
+
+        rec.get_field_mut("245").unwrap().subfields[1].value = "a memoir ".to_string();
+        assert_eq!(fix(&mut rec), 1);
+        assert_eq!(
+            rec.get_field("245").unwrap().get_subfield('b'),
+            Some("a memoir.")
+        );
+
+        // Punctuation omitted (leader/18 c): nothing to report
+        rec.leader.cataloging_form = 'c';
+        rec.get_field_mut("245").unwrap().subfields[1].value = "a memoir".to_string();
+        assert!(lint(&rec).is_clean());
+    }
+}
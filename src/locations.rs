@@ -0,0 +1,509 @@
+//! Location code tables for validating and remapping holdings locations.
+//!
+//! Every migration maps the old system's location codes to the new one's.
+//! A [`LocationTable`] lists the codes allowed in 852 `$a` (institution),
+//! `$b` (sublocation or collection) and `$c` (shelving location), each with
+//! an optional label and, for retired codes, the code that replaces it.
+//! [`LocationTable::validate`] reports codes that are unknown or retired,
+//! and [`LocationTable::remap_record`] / [`LocationTable::remap_holdings`]
+//! rewrite retired codes in place.
+//!
+//! Tables load from CSV, or from TOML with the `toml` cargo feature. In
+//! TOML, each `[[location]]` has a `code`, an optional `subfield` (default
+//! `b`), `label` and `replaced_by`:
+//!
+//! ```toml
+//! name = "Main campus"
+//!
+//! [[location]]
+//! code = "MAIN"
+//! label = "Main Library"
+//!
+//! [[location]]
+//! code = "STACKS"
+//! replaced_by = "MAIN"
+//! ```
+//!
+//! CSV has a header row naming the same columns
+//! (`subfield,code,label,replaced_by`); only `code` is required, and
+//! values may be double-quoted.
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::locations::{LocationIssueKind, LocationTable};
+//! use mrrc::Field;
+//!
+//! let table = LocationTable::from_csv(
+//!     "subfield,code,label,replaced_by\n\
+//!      b,MAIN,Main Library,\n\
+//!      b,STACKS,,MAIN\n",
+//! )?;
+//!
+//! let mut field = Field::builder("852".to_string(), '0', ' ')
+//!     .subfield_str('b', "STACKS")
+//!     .build();
+//! let issues = table.validate_field(&field);
+//! assert_eq!(issues[0].kind, LocationIssueKind::Replaced("MAIN".to_string()));
+//!
+//! assert_eq!(table.remap_field(&mut field), 1);
+//! assert_eq!(field.get_subfield('b'), Some("MAIN"));
+//! assert!(table.validate_field(&field).is_empty());
+//! # Ok::<(), mrrc::MarcError>(())
+//! ```
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{MarcError, Result};
+use crate::holdings_record::HoldingsRecord;
+use crate::marc_record::MarcRecord;
+use crate::record::{Field, Record};
+
+/// One code in a [`LocationTable`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocationCode {
+    /// The 852 subfield the code belongs in: `a`, `b` or `c`.
+    #[serde(default = "default_subfield")]
+    pub subfield: char,
+    /// The code.
+    pub code: String,
+    /// Display label.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// The code that replaces this one, if it is retired.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replaced_by: Option<String>,
+}
+
+fn default_subfield() -> char {
+    'b'
+}
+
+impl LocationCode {
+    /// A current code for `subfield`.
+    #[must_use]
+    pub fn new(subfield: char, code: &str) -> Self {
+        LocationCode {
+            subfield,
+            code: code.to_string(),
+            label: None,
+            replaced_by: None,
+        }
+    }
+
+    /// Set the display label.
+    #[must_use]
+    pub fn with_label(mut self, label: &str) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
+
+    /// Mark the code as retired in favour of `code`.
+    #[must_use]
+    pub fn replaced_by(mut self, code: &str) -> Self {
+        self.replaced_by = Some(code.to_string());
+        self
+    }
+}
+
+/// What is wrong with a location code.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LocationIssueKind {
+    /// The code is not in the table.
+    Unknown,
+    /// The code is retired; the value is its replacement.
+    Replaced(String),
+}
+
+/// A location code in a record that is not current in the table.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocationIssue {
+    /// Tag of the field (`"852"`).
+    pub tag: String,
+    /// Which occurrence of the tag, from 0.
+    pub occurrence: usize,
+    /// Subfield code (`a`, `b` or `c`).
+    pub subfield: char,
+    /// The code as it appears in the record.
+    pub code: String,
+    /// Whether the code is unknown or retired.
+    pub kind: LocationIssueKind,
+}
+
+/// A table of location codes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "TableDef", into = "TableDef")]
+pub struct LocationTable {
+    /// Table name, for messages.
+    pub name: String,
+    codes: Vec<LocationCode>,
+    index: HashMap<(char, String), usize>,
+}
+
+/// Serialized form of [`LocationTable`], without the index.
+#[derive(Serialize, Deserialize)]
+struct TableDef {
+    #[serde(default)]
+    name: String,
+    #[serde(default, rename = "location")]
+    locations: Vec<LocationCode>,
+}
+
+impl From<TableDef> for LocationTable {
+    fn from(def: TableDef) -> Self {
+        def.locations
+            .into_iter()
+            .fold(LocationTable::new(&def.name), LocationTable::location)
+    }
+}
+
+impl From<LocationTable> for TableDef {
+    fn from(table: LocationTable) -> Self {
+        TableDef {
+            name: table.name,
+            locations: table.codes,
+        }
+    }
+}
+
+impl LocationTable {
+    /// An empty table.
+    #[must_use]
+    pub fn new(name: &str) -> Self {
+        LocationTable {
+            name: name.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Add a code; a later entry for the same subfield and code replaces an
+    /// earlier one.
+    #[must_use]
+    pub fn location(mut self, code: LocationCode) -> Self {
+        let key = (code.subfield, code.code.clone());
+        if let Some(&i) = self.index.get(&key) {
+            self.codes[i] = code;
+        } else {
+            self.index.insert(key, self.codes.len());
+            self.codes.push(code);
+        }
+        self
+    }
+
+    /// Parse a table from TOML (see the [module documentation](self)).
+    /// Requires the `toml` cargo feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MarcError::InvalidField`] if the TOML is malformed or does
+    /// not describe a location table.
+    #[cfg(feature = "toml")]
+    pub fn from_toml(source: &str) -> Result<Self> {
+        toml::from_str(source).map_err(|e| {
+            MarcError::invalid_field_msg(format!("Invalid location table definition: {e}"))
+        })
+    }
+
+    /// Parse a table from CSV with a header row (see the
+    /// [module documentation](self)). Blank lines are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MarcError::InvalidField`] if there is no `code` column, a
+    /// row has an empty code, or a `subfield` value is not `a`, `b` or `c`.
+    pub fn from_csv(source: &str) -> Result<Self> {
+        let mut lines = source.lines().filter(|line| !line.trim().is_empty());
+        let header = split_csv_line(lines.next().unwrap_or_default());
+        let column = |name: &str| {
+            header
+                .iter()
+                .position(|h| h.trim().eq_ignore_ascii_case(name))
+        };
+        let code_column = column("code").ok_or_else(|| {
+            MarcError::invalid_field_msg("Location table CSV has no 'code' column")
+        })?;
+        let (subfield_column, label_column, replaced_column) =
+            (column("subfield"), column("label"), column("replaced_by"));
+
+        let mut table = LocationTable::default();
+        for (row, line) in lines.enumerate() {
+            let values = split_csv_line(line);
+            let get = |i: Option<usize>| {
+                i.and_then(|i| values.get(i))
+                    .map(|v| v.trim())
+                    .filter(|v| !v.is_empty())
+            };
+            let invalid = |what: &str| {
+                MarcError::invalid_field_msg(format!("Location table CSV row {}: {what}", row + 2))
+            };
+            let code = get(Some(code_column)).ok_or_else(|| invalid("empty code"))?;
+            let subfield = match get(subfield_column) {
+                None => 'b',
+                Some(s @ ("a" | "b" | "c")) => s.chars().next().unwrap_or('b'),
+                Some(other) => return Err(invalid(&format!("invalid subfield '{other}'"))),
+            };
+            let mut entry = LocationCode::new(subfield, code);
+            entry.label = get(label_column).map(str::to_string);
+            entry.replaced_by = get(replaced_column).map(str::to_string);
+            table = table.location(entry);
+        }
+        Ok(table)
+    }
+
+    /// Look up `code` for 852 `subfield`.
+    #[must_use]
+    pub fn get(&self, subfield: char, code: &str) -> Option<&LocationCode> {
+        self.index
+            .get(&(subfield, code.trim().to_string()))
+            .map(|&i| &self.codes[i])
+    }
+
+    /// All codes, in the order they were added.
+    #[must_use]
+    pub fn codes(&self) -> &[LocationCode] {
+        &self.codes
+    }
+
+    /// Which subfields the table has codes for; subfields with no codes
+    /// are not checked.
+    fn covers(&self, subfield: char) -> bool {
+        self.codes.iter().any(|code| code.subfield == subfield)
+    }
+
+    /// Check the `$a`, `$b` and `$c` codes of one 852.
+    #[must_use]
+    pub fn validate_field(&self, field: &Field) -> Vec<LocationIssue> {
+        self.field_issues(field, 0)
+    }
+
+    /// Check every 852 of a bibliographic or holdings record.
+    #[must_use]
+    pub fn validate<R: MarcRecord>(&self, record: &R) -> Vec<LocationIssue> {
+        record
+            .get_fields("852")
+            .unwrap_or_default()
+            .iter()
+            .enumerate()
+            .flat_map(|(occurrence, field)| self.field_issues(field, occurrence))
+            .collect()
+    }
+
+    fn field_issues(&self, field: &Field, occurrence: usize) -> Vec<LocationIssue> {
+        let mut issues = Vec::new();
+        for subfield in field
+            .subfields
+            .iter()
+            .filter(|sf| matches!(sf.code, 'a' | 'b' | 'c') && self.covers(sf.code))
+        {
+            let kind = match self.get(subfield.code, &subfield.value) {
+                None => LocationIssueKind::Unknown,
+                Some(LocationCode {
+                    replaced_by: Some(new),
+                    ..
+                }) => LocationIssueKind::Replaced(new.clone()),
+                Some(_) => continue,
+            };
+            issues.push(LocationIssue {
+                tag: field.tag.clone(),
+                occurrence,
+                subfield: subfield.code,
+                code: subfield.value.clone(),
+                kind,
+            });
+        }
+        issues
+    }
+
+    /// Rewrite retired codes in one field to their replacements, following
+    /// chains of replacements. Returns the number of subfields changed.
+    pub fn remap_field(&self, field: &mut Field) -> usize {
+        let mut changed = 0;
+        for subfield in field
+            .subfields
+            .iter_mut()
+            .filter(|sf| matches!(sf.code, 'a' | 'b' | 'c'))
+        {
+            if let Some(new) = self.current_code(subfield.code, &subfield.value) {
+                subfield.value = new;
+                changed += 1;
+            }
+        }
+        changed
+    }
+
+    /// Rewrite retired codes in every 852 of a bibliographic record.
+    pub fn remap_record(&self, record: &mut Record) -> usize {
+        record
+            .fields_by_tag_mut("852")
+            .map(|field| self.remap_field(field))
+            .sum()
+    }
+
+    /// Rewrite retired codes in every 852 of a holdings record.
+    pub fn remap_holdings(&self, record: &mut HoldingsRecord) -> usize {
+        record
+            .fields
+            .get_mut("852")
+            .into_iter()
+            .flatten()
+            .map(|field| self.remap_field(field))
+            .sum()
+    }
+
+    /// The replacement for a retired code, after following any chain; `None`
+    /// if the code is current or unknown. Cycles stop at the last code not
+    /// yet seen.
+    fn current_code(&self, subfield: char, code: &str) -> Option<String> {
+        let mut current = self.get(subfield, code)?.replaced_by.clone()?;
+        let mut seen = vec![code.trim().to_string()];
+        while let Some(next) = self
+            .get(subfield, &current)
+            .and_then(|entry| entry.replaced_by.clone())
+        {
+            if seen.contains(&next) {
+                break;
+            }
+            seen.push(std::mem::replace(&mut current, next));
+        }
+        Some(current)
+    }
+}
+
+/// Split one CSV line into values, honoring double quotes and `""` escapes.
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut values = Vec::new();
+    let mut value = String::new();
+    let mut quoted = false;
+    let mut chars = line.trim_end_matches('\r').chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                value.push('"');
+                chars.next();
+            },
+            '"' => quoted = !quoted,
+            ',' if !quoted => values.push(std::mem::take(&mut value)),
+            _ => value.push(c),
+        }
+    }
+    values.push(value);
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::leader::Leader;
+
+    fn table() -> LocationTable {
+        LocationTable::from_csv(
+            "subfield,code,label,replaced_by\n\
+             a,DLC,,\n\
+             b,MAIN,Main Library,\n\
+             b,OLD,,OLDER\n\
+             b,OLDER,,MAIN\n",
+        )
+        .unwrap()
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_toml() {
+        let table = LocationTable::from_toml(
+            r#"
+            name = "Test"
+
+            [[location]]
+            subfield = "a"
+            code = "DLC"
+
+            [[location]]
+            code = "MAIN"
+            label = "Main Library"
+
+            [[location]]
+            code = "OLD"
+            replaced_by = "OLDER"
+
+            [[location]]
+            code = "OLDER"
+            replaced_by = "MAIN"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(table.name, "Test");
+        assert_eq!(table.codes(), self::table().codes());
+    }
+
+    #[test]
+    fn test_validate_and_remap() {
+        let table = table();
+        assert_eq!(
+            table.get('b', "MAIN").unwrap().label.as_deref(),
+            Some("Main Library")
+        );
+
+        let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+        for (a, b) in [("DLC", "MAIN"), ("XYZ", "OLD")] {
+            record.add_field(
+                Field::builder("852".to_string(), ' ', ' ')
+                    .subfield_str('a', a)
+                    .subfield_str('b', b)
+                    .subfield_str('c', "anything")
+                    .build(),
+            );
+        }
+        let issues = table.validate(&record);
+        assert_eq!(issues.len(), 2);
+        assert_eq!((issues[0].occurrence, issues[0].subfield), (1, 'a'));
+        assert_eq!(issues[0].kind, LocationIssueKind::Unknown);
+        assert_eq!(
+            issues[1].kind,
+            LocationIssueKind::Replaced("OLDER".to_string())
+        );
+
+        assert_eq!(table.remap_record(&mut record), 1);
+        assert_eq!(
+            record.get_fields("852").unwrap()[1].get_subfield('b'),
+            Some("MAIN")
+        );
+
+        let mut holdings =
+            HoldingsRecord::new(Leader::from_bytes(b"00000nx  a2200000 a 4500").unwrap());
+        holdings.add_location(
+            Field::builder("852".to_string(), ' ', ' ')
+                .subfield_str('b', "OLDER")
+                .build(),
+        );
+        assert_eq!(table.remap_holdings(&mut holdings), 1);
+        assert!(table.validate(&holdings).is_empty());
+    }
+
+    #[test]
+    fn test_csv() {
+        let table = LocationTable::from_csv(
+            "code,label,subfield\n\"REF\",\"Reference, 2nd floor\",c\n\nMAIN,\"Main \"\"Old\"\" Library\",\n",
+        )
+        .unwrap();
+        assert_eq!(table.codes().len(), 2);
+        assert_eq!(
+            table.get('c', "REF").unwrap().label.as_deref(),
+            Some("Reference, 2nd floor")
+        );
+        assert_eq!(
+            table.get('b', "MAIN").unwrap().label.as_deref(),
+            Some("Main \"Old\" Library")
+        );
+
+        assert!(LocationTable::from_csv("label\nx\n").is_err());
+        let err = LocationTable::from_csv("code,subfield\nX,q\n").unwrap_err();
+        assert!(err.to_string().contains("row 2"));
+        #[cfg(feature = "toml")]
+        {
+            let toml = toml::to_string(&table).unwrap();
+            assert_eq!(LocationTable::from_toml(&toml).unwrap(), table);
+        }
+    }
+}
@@ -0,0 +1,272 @@
+//! MARC-in-JSON, the dialect read and written by pymarc.
+//!
+//! MARC-in-JSON (the code4lib proposal implemented by pymarc's
+//! `Record.as_json()`, `JSONReader` and `JSONWriter`) wraps each record in an
+//! object with the leader and an ordered list of fields:
+//!
+//! ```json
+//! {
+//!   "leader": "00000nam a2200000 a 4500",
+//!   "fields": [
+//!     {"001": "ocm01234567"},
+//!     {"245": {"ind1": "1", "ind2": "0",
+//!              "subfields": [{"a": "A title /"}, {"c": "by Someone."}]}}
+//!   ]
+//! }
+//! ```
+//!
+//! This differs from [`marcjson`](crate::marcjson), where a record is a bare
+//! array with the leader as its first element. A field whose value is a
+//! string is a control field; an object is a data field. A collection is a
+//! JSON array of record objects.
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::marc_in_json::{marc_in_json_to_record, record_to_marc_in_json};
+//!
+//! let json: serde_json::Value = serde_json::from_str(
+//!     r#"{"leader": "00000nam a2200000 a 4500",
+//!         "fields": [{"001": "12345"},
+//!                    {"245": {"subfields": [{"a": "Title"}], "ind1": "1", "ind2": "0"}}]}"#,
+//! )?;
+//! let record = marc_in_json_to_record(&json)?;
+//! assert_eq!(record.get_control_field("001"), Some("12345"));
+//! assert_eq!(record_to_marc_in_json(&record)?, json);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use serde_json::{Map, Value};
+
+use crate::error::{MarcError, Result};
+use crate::leader::Leader;
+use crate::record::{Field, Record};
+
+/// Convert a record to a MARC-in-JSON object.
+///
+/// Control fields come first, then data fields, each in record order.
+///
+/// # Errors
+///
+/// Returns an error if the leader cannot be serialized.
+pub fn record_to_marc_in_json(record: &Record) -> Result<Value> {
+    crate::telemetry::stage_span!("mrrc.convert", from = "marc", to = "marc_in_json");
+    let leader = String::from_utf8_lossy(&record.leader.as_bytes()?).to_string();
+    let mut fields = Vec::new();
+    for (tag, value) in record.control_fields_iter() {
+        fields.push(single_entry(tag, Value::String(value.to_string())));
+    }
+    for field in record.fields() {
+        let subfields = field
+            .subfields
+            .iter()
+            .map(|sf| single_entry(&sf.code.to_string(), Value::String(sf.value.clone())))
+            .collect();
+        let mut data = Map::new();
+        data.insert(
+            "ind1".to_string(),
+            Value::String(field.indicator1.to_string()),
+        );
+        data.insert(
+            "ind2".to_string(),
+            Value::String(field.indicator2.to_string()),
+        );
+        data.insert("subfields".to_string(), Value::Array(subfields));
+        fields.push(single_entry(&field.tag, Value::Object(data)));
+    }
+    let mut object = Map::new();
+    object.insert("leader".to_string(), Value::String(leader));
+    object.insert("fields".to_string(), Value::Array(fields));
+    Ok(Value::Object(object))
+}
+
+/// Convert records to a MARC-in-JSON collection (a JSON array), as written
+/// by pymarc's `JSONWriter`.
+///
+/// # Errors
+///
+/// Returns an error if a leader cannot be serialized.
+pub fn records_to_marc_in_json(records: &[Record]) -> Result<Value> {
+    records
+        .iter()
+        .map(record_to_marc_in_json)
+        .collect::<Result<Vec<_>>>()
+        .map(Value::Array)
+}
+
+/// Convert a MARC-in-JSON object to a record.
+///
+/// Missing indicators default to blank.
+///
+/// # Errors
+///
+/// Returns [`MarcError::InvalidField`] if the value is not a MARC-in-JSON
+/// record, and a leader error if the leader does not parse.
+pub fn marc_in_json_to_record(json: &Value) -> Result<Record> {
+    crate::telemetry::stage_span!("mrrc.convert", from = "marc_in_json", to = "marc");
+    let object = json
+        .as_object()
+        .ok_or_else(|| invalid("record must be a JSON object"))?;
+    let leader = object
+        .get("leader")
+        .and_then(Value::as_str)
+        .ok_or_else(|| invalid("record is missing its leader"))?;
+    let mut record = Record::new(Leader::from_bytes(leader.as_bytes())?);
+
+    let fields = match object.get("fields") {
+        None => &Vec::new(),
+        Some(fields) => fields
+            .as_array()
+            .ok_or_else(|| invalid("\"fields\" must be an array"))?,
+    };
+    for entry in fields {
+        let (tag, value) = only_entry(entry, "field")?;
+        match value {
+            Value::String(data) => record.add_control_field(tag.to_string(), data.clone()),
+            Value::Object(data) => record.add_field(data_field(tag, data)?),
+            _ => {
+                return Err(invalid(&format!(
+                    "field {tag} must be a string or an object"
+                )));
+            },
+        }
+    }
+    Ok(record)
+}
+
+/// Convert a MARC-in-JSON collection to records.
+///
+/// Accepts an array of record objects, or a single record object, as
+/// pymarc's `JSONReader` does.
+///
+/// # Errors
+///
+/// Returns an error if any record does not convert; see
+/// [`marc_in_json_to_record`].
+pub fn marc_in_json_to_records(json: &Value) -> Result<Vec<Record>> {
+    match json {
+        Value::Array(records) => records.iter().map(marc_in_json_to_record).collect(),
+        _ => Ok(vec![marc_in_json_to_record(json)?]),
+    }
+}
+
+fn invalid(message: &str) -> MarcError {
+    MarcError::invalid_field_msg(format!("MARC-in-JSON: {message}"))
+}
+
+fn single_entry(key: &str, value: Value) -> Value {
+    let mut object = Map::new();
+    object.insert(key.to_string(), value);
+    Value::Object(object)
+}
+
+/// The key and value of a one-entry object such as `{"001": "..."}`.
+fn only_entry<'a>(value: &'a Value, what: &str) -> Result<(&'a str, &'a Value)> {
+    match value.as_object() {
+        Some(object) if object.len() == 1 => object
+            .iter()
+            .next()
+            .map(|(key, value)| (key.as_str(), value))
+            .ok_or_else(|| invalid(&format!("empty {what}"))),
+        _ => Err(invalid(&format!(
+            "each {what} must be an object with one key"
+        ))),
+    }
+}
+
+fn one_char(value: &str, what: &str) -> Result<char> {
+    let mut chars = value.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Ok(c),
+        _ => Err(invalid(&format!(
+            "{what} must be one character, got {value:?}"
+        ))),
+    }
+}
+
+fn data_field(tag: &str, data: &Map<String, Value>) -> Result<Field> {
+    let indicator = |key: &str| -> Result<char> {
+        match data.get(key) {
+            None | Some(Value::Null) => Ok(' '),
+            Some(Value::String(value)) => one_char(value, &format!("{tag} {key}")),
+            Some(_) => Err(invalid(&format!("{tag} {key} must be a string"))),
+        }
+    };
+    let mut field = Field::new(tag.to_string(), indicator("ind1")?, indicator("ind2")?);
+    let subfields = match data.get("subfields") {
+        None => &Vec::new(),
+        Some(subfields) => subfields
+            .as_array()
+            .ok_or_else(|| invalid(&format!("{tag} subfields must be an array")))?,
+    };
+    for subfield in subfields {
+        let (code, value) = only_entry(subfield, "subfield")?;
+        let value = value
+            .as_str()
+            .ok_or_else(|| invalid(&format!("{tag} ${code} must be a string")))?;
+        field.add_subfield_str(one_char(code, "subfield code")?, value);
+    }
+    Ok(field)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Output of pymarc's `JSONWriter` for two records (key order as pymarc
+    /// emits it: `subfields` before the indicators).
+    const PYMARC_COLLECTION: &str = r#"[{"leader": "00714cam a2200205 a 4500", "fields": [{"001": "12883376"}, {"005": "20030616111422.0"}, {"008": "020805s2002    nyu    j      000 1 eng  "}, {"020": {"subfields": [{"a": "0786808772"}], "ind1": " ", "ind2": " "}}, {"100": {"subfields": [{"a": "Chabon, Michael."}], "ind1": "1", "ind2": " "}}, {"245": {"subfields": [{"a": "Summerland /"}, {"c": "Michael Chabon."}], "ind1": "1", "ind2": "0"}}, {"650": {"subfields": [{"a": "Fantasy."}], "ind1": " ", "ind2": "1"}}, {"650": {"subfields": [{"a": "Baseball"}, {"v": "Fiction."}], "ind1": " ", "ind2": "1"}}]}, {"leader": "00000cam a2200000 a 4500", "fields": [{"001": "x"}, {"007": "cr"}, {"007": "ta"}, {"245": {"subfields": [{"a": "Café été"}], "ind1": "0", "ind2": "0"}}]}]"#;
+
+    #[test]
+    fn test_reads_pymarc_output() {
+        let json: Value = serde_json::from_str(PYMARC_COLLECTION).unwrap();
+        let records = marc_in_json_to_records(&json).unwrap();
+        assert_eq!(records.len(), 2);
+
+        let first = &records[0];
+        assert_eq!(first.leader.record_length, 714);
+        assert_eq!(first.get_control_field("001"), Some("12883376"));
+        let subjects: Vec<&Field> = first.fields_by_tag("650").collect();
+        assert_eq!(subjects.len(), 2);
+        assert_eq!(subjects[1].get_subfield('v'), Some("Fiction."));
+        assert_eq!(subjects[1].indicator2, '1');
+
+        let second = &records[1];
+        assert_eq!(
+            second.control_fields_iter().collect::<Vec<_>>(),
+            [("001", "x"), ("007", "cr"), ("007", "ta")]
+        );
+        assert_eq!(
+            second.get_field("245").unwrap().get_subfield('a'),
+            Some("Café été")
+        );
+    }
+
+    #[test]
+    fn test_round_trip_matches_pymarc() {
+        // Same JSON value (object key order is not significant)
+        let json: Value = serde_json::from_str(PYMARC_COLLECTION).unwrap();
+        let records = marc_in_json_to_records(&json).unwrap();
+        assert_eq!(records_to_marc_in_json(&records).unwrap(), json);
+
+        // A single object is a one-record collection
+        let single = marc_in_json_to_records(&json[1]).unwrap();
+        assert_eq!(record_to_marc_in_json(&single[0]).unwrap(), json[1]);
+    }
+
+    #[test]
+    fn test_rejects_malformed_input() {
+        for bad in [
+            "[]",
+            r#"{"fields": []}"#,
+            r#"{"leader": "00000nam a2200000 a 4500", "fields": {"001": "x"}}"#,
+            r#"{"leader": "00000nam a2200000 a 4500", "fields": [{"001": "x", "003": "y"}]}"#,
+            r#"{"leader": "00000nam a2200000 a 4500", "fields": [{"245": 5}]}"#,
+            r#"{"leader": "00000nam a2200000 a 4500", "fields": [{"245": {"ind1": "10", "subfields": []}}]}"#,
+            r#"{"leader": "00000nam a2200000 a 4500", "fields": [{"245": {"subfields": [{"ab": "x"}]}}]}"#,
+        ] {
+            let json: Value = serde_json::from_str(bad).unwrap();
+            assert!(marc_in_json_to_record(&json).is_err(), "{bad}");
+        }
+    }
+}
@@ -10,6 +10,7 @@
 //! - Data fields (010+): `{tag: {ind1, ind2, subfields: [{code: value}, ...]}}`
 
 use crate::error::{MarcError, Result};
+use crate::formats::FormatWriter;
 use crate::iso2709::ParseContext;
 use crate::leader::Leader;
 use crate::record::{Field, Record};
@@ -40,6 +41,7 @@ use serde_json::{Value, json};
 ///
 /// Returns an error if the record cannot be converted to MARCJSON.
 pub fn record_to_marcjson(record: &Record) -> Result<Value> {
+    crate::telemetry::stage_span!("mrrc.convert", from = "marc", to = "marcjson");
     let mut fields = Vec::new();
 
     // Add leader as a special field
@@ -97,6 +99,7 @@ pub fn record_to_marcjson(record: &Record) -> Result<Value> {
 ///
 /// Returns an error if the MARCJSON is invalid or missing required fields.
 pub fn marcjson_to_record(json: &Value) -> Result<Record> {
+    crate::telemetry::stage_span!("mrrc.convert", from = "marcjson", to = "marc");
     let mut ctx = ParseContext::new();
     ctx.begin_record();
 
@@ -179,6 +182,70 @@ pub fn marcjson_to_record(json: &Value) -> Result<Record> {
     Ok(record)
 }
 
+/// Writes records as newline-delimited MARCJSON, one record per line.
+///
+/// # Examples
+///
+/// ```
+/// use mrrc::formats::FormatWriter;
+/// use mrrc::marcjson::{self, MarcjsonWriter};
+/// use mrrc::{Leader, Record};
+///
+/// let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+/// record.add_control_field_str("001", "ocm1");
+///
+/// let mut writer = MarcjsonWriter::new(Vec::new());
+/// writer.write_record(&record)?;
+/// writer.write_record(&record)?;
+/// writer.finish()?;
+/// let output = String::from_utf8(writer.into_inner()).unwrap();
+/// for line in output.lines() {
+///     let json = serde_json::from_str(line).unwrap();
+///     assert_eq!(marcjson::marcjson_to_record(&json)?.get_control_field("001"), Some("ocm1"));
+/// }
+/// # Ok::<(), mrrc::MarcError>(())
+/// ```
+#[derive(Debug)]
+pub struct MarcjsonWriter<W: std::io::Write> {
+    writer: W,
+    records_written: usize,
+}
+
+impl<W: std::io::Write> MarcjsonWriter<W> {
+    /// Create a writer over `writer`.
+    pub fn new(writer: W) -> Self {
+        MarcjsonWriter {
+            writer,
+            records_written: 0,
+        }
+    }
+
+    /// Unwrap the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: std::io::Write + std::fmt::Debug> FormatWriter for MarcjsonWriter<W> {
+    fn write_record(&mut self, record: &Record) -> Result<()> {
+        let mut line = record_to_marcjson(record)?.to_string();
+        line.push('\n');
+        self.writer.write_all(line.as_bytes())?;
+        self.records_written += 1;
+        crate::telemetry::records_written(1);
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    fn records_written(&self) -> Option<usize> {
+        Some(self.records_written)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
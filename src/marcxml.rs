@@ -27,10 +27,14 @@
 //! # }
 //! ```
 
+use crate::encoding::ControlCharacters;
 use crate::error::{MarcError, Result};
+use crate::formats::FormatWriter;
 use crate::iso2709::ParseContext;
-use crate::leader::Leader;
 use crate::record::{Field, Record};
+use crate::recovery::{self, ParseOptions};
+use crate::xml_security::GuardedReader;
+use crate::xml_style::{XmlNamespace, XmlStyle};
 use quick_xml::events::Event;
 use quick_xml::se::to_string as xml_to_string;
 use quick_xml::{Decoder, XmlVersion};
@@ -144,7 +148,7 @@ fn strip_marcxml_ns(xml: &str) -> String {
 /// the `<record>` element (inclusive) through its end tag (exclusive of
 /// anything after).
 fn read_marcxml_record<B: std::io::BufRead>(
-    reader: &mut quick_xml::reader::Reader<B>,
+    reader: &mut GuardedReader<B>,
     ctx: &ParseContext,
 ) -> Result<MarcxmlRecord> {
     let mut buf = Vec::new();
@@ -244,7 +248,7 @@ fn read_marcxml_record<B: std::io::BufRead>(
 /// Read all Text/CData events until the end tag matching `name`. Returns
 /// the concatenated, unescaped payload verbatim (whitespace preserved).
 fn read_leaf_text<B: std::io::BufRead>(
-    reader: &mut quick_xml::reader::Reader<B>,
+    reader: &mut GuardedReader<B>,
     name: &[u8],
     ctx: &ParseContext,
 ) -> Result<String> {
@@ -376,8 +380,54 @@ fn attr_value(
 ///
 /// # Errors
 ///
-/// Returns an error if the record cannot be serialized to XML.
+/// Returns an error if the record cannot be serialized to XML, including
+/// [`MarcError::EncodingError`] for control characters in field data (see
+/// [`record_to_marcxml_with`]).
 pub fn record_to_marcxml(record: &Record) -> Result<String> {
+    record_to_marcxml_with(record, ControlCharacters::default())
+}
+
+/// Convert a MARC record to MARCXML, choosing how control characters in
+/// field data are written.
+///
+/// XML 1.0 cannot carry C0 control characters such as the ESC that starts
+/// a MARC-8 escape sequence, so [`record_to_marcxml`] rejects them.
+/// [`ControlCharacters::Hex`] writes them as `{1B}`-style notation instead
+/// (restore with [`restore_control_characters`](crate::encoding::restore_control_characters)
+/// after reading), and [`ControlCharacters::Decode`] decodes MARC-8 escape
+/// sequences to Unicode.
+///
+/// # Errors
+///
+/// Returns an error if the record cannot be serialized to XML, or a value
+/// cannot be written under `control_characters`.
+pub fn record_to_marcxml_with(
+    record: &Record,
+    control_characters: ControlCharacters,
+) -> Result<String> {
+    crate::telemetry::stage_span!("mrrc.convert", from = "marc", to = "marcxml");
+    let body = record_element(record, control_characters)?;
+
+    // Insert xmlns attribute into the root <record> element
+    let body = body.replacen("<record>", &format!("<record xmlns=\"{MARCXML_NS}\">"), 1);
+
+    Ok(format!("<?xml version=\"1.0\" encoding=\"UTF-8\"?>{body}"))
+}
+
+/// Convert a MARC record to MARCXML laid out according to `style`
+/// (indentation and namespace prefix; see [`crate::xml_style`]).
+///
+/// # Errors
+///
+/// Returns an error if the record cannot be serialized, as for
+/// [`record_to_marcxml`].
+pub fn record_to_marcxml_styled(record: &Record, style: &XmlStyle) -> Result<String> {
+    style.apply(&record_to_marcxml(record)?, MARCXML_NS, 0)
+}
+
+/// Serialize `record` as a bare `<record>` element, without the XML
+/// declaration or namespace.
+fn record_element(record: &Record, control_characters: ControlCharacters) -> Result<String> {
     let leader_bytes = record.leader.as_bytes()?;
     let leader_str = String::from_utf8_lossy(&leader_bytes).to_string();
 
@@ -386,7 +436,7 @@ pub fn record_to_marcxml(record: &Record) -> Result<String> {
         for value in values {
             controlfields.push(MarcxmlControlField {
                 tag: tag.clone(),
-                value: value.clone(),
+                value: control_characters.apply(tag, value)?.into_owned(),
             });
         }
     }
@@ -398,7 +448,7 @@ pub fn record_to_marcxml(record: &Record) -> Result<String> {
             for subfield in &field.subfields {
                 subfields.push(MarcxmlSubfield {
                     code: subfield.code.to_string(),
-                    value: subfield.value.clone(),
+                    value: control_characters.apply(tag, &subfield.value)?.into_owned(),
                 });
             }
 
@@ -417,14 +467,151 @@ pub fn record_to_marcxml(record: &Record) -> Result<String> {
         datafield: datafields,
     };
 
-    let body = xml_to_string(&xml_record).map_err(|e| {
-        MarcError::invalid_field_msg(format!("Failed to serialize to MARCXML: {e}"))
-    })?;
+    xml_to_string(&xml_record)
+        .map_err(|e| MarcError::invalid_field_msg(format!("Failed to serialize to MARCXML: {e}")))
+}
 
-    // Insert xmlns attribute into the root <record> element
-    let body = body.replacen("<record>", &format!("<record xmlns=\"{MARCXML_NS}\">"), 1);
+// ---------------------------------------------------------------------------
+// Streaming serialization: records → MARCXML collection
+// ---------------------------------------------------------------------------
 
-    Ok(format!("<?xml version=\"1.0\" encoding=\"UTF-8\"?>{body}"))
+/// Writes records as a MARCXML `<collection>`, one `<record>` at a time.
+///
+/// The XML declaration and opening `<collection>` tag are written with the
+/// first record (or by [`finish`](FormatWriter::finish) for an empty
+/// collection), so memory use does not grow with the number of records.
+///
+/// # Examples
+///
+/// ```
+/// use mrrc::formats::FormatWriter;
+/// use mrrc::marcxml::{self, MarcxmlWriter};
+/// use mrrc::{Field, Leader, Record};
+///
+/// let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+/// record.add_field(
+///     Field::builder("245".to_string(), '1', '0')
+///         .subfield_str('a', "A title")
+///         .build(),
+/// );
+///
+/// let mut writer = MarcxmlWriter::new(Vec::new());
+/// writer.write_record(&record)?;
+/// writer.write_record(&record)?;
+/// writer.finish()?;
+/// let xml = String::from_utf8(writer.into_inner()).unwrap();
+/// assert_eq!(marcxml::marcxml_to_records(&xml)?.len(), 2);
+/// # Ok::<(), mrrc::MarcError>(())
+/// ```
+#[derive(Debug)]
+pub struct MarcxmlWriter<W: std::io::Write> {
+    writer: W,
+    control_characters: ControlCharacters,
+    style: Option<XmlStyle>,
+    started: bool,
+    finished: bool,
+    records_written: usize,
+}
+
+impl<W: std::io::Write> MarcxmlWriter<W> {
+    /// Create a writer over `writer`.
+    pub fn new(writer: W) -> Self {
+        MarcxmlWriter {
+            writer,
+            control_characters: ControlCharacters::default(),
+            style: None,
+            started: false,
+            finished: false,
+            records_written: 0,
+        }
+    }
+
+    /// Write control characters in field data as
+    /// [`record_to_marcxml_with`] does.
+    #[must_use]
+    pub fn with_control_characters(mut self, control_characters: ControlCharacters) -> Self {
+        self.control_characters = control_characters;
+        self
+    }
+
+    /// Lay out records according to `style` instead of one record per
+    /// line. In pretty mode records are indented one level inside the
+    /// `<collection>`.
+    #[must_use]
+    pub fn with_style(mut self, style: XmlStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Unwrap the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn collection_name(&self) -> String {
+        self.style.as_ref().map_or_else(
+            || "collection".to_string(),
+            |s| s.element_name("collection"),
+        )
+    }
+
+    fn start(&mut self) -> Result<()> {
+        if !self.started {
+            self.started = true;
+            let declaration = match &self.style {
+                Some(style) if style.namespace != XmlNamespace::Native => {
+                    style.declaration(MARCXML_NS)
+                },
+                _ => format!(" xmlns=\"{MARCXML_NS}\""),
+            };
+            write!(
+                self.writer,
+                "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<{}{declaration}>\n",
+                self.collection_name()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl<W: std::io::Write + std::fmt::Debug> FormatWriter for MarcxmlWriter<W> {
+    fn write_record(&mut self, record: &Record) -> Result<()> {
+        if self.finished {
+            return Err(MarcError::WriterError {
+                record_index: None,
+                record_control_number: None,
+                field_tag: None,
+                message: "Cannot write to a finished writer".to_string(),
+            });
+        }
+        let mut element = record_element(record, self.control_characters)?;
+        if let Some(style) = &self.style {
+            let depth = usize::from(style.indent.is_some());
+            element = style.apply(&element, MARCXML_NS, depth)?;
+        }
+        if !element.ends_with('\n') {
+            element.push('\n');
+        }
+        self.start()?;
+        self.writer.write_all(element.as_bytes())?;
+        self.records_written += 1;
+        crate::telemetry::records_written(1);
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        if !self.finished {
+            self.start()?;
+            writeln!(self.writer, "</{}>", self.collection_name())?;
+            self.finished = true;
+        }
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    fn records_written(&self) -> Option<usize> {
+        Some(self.records_written)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -461,9 +648,24 @@ pub fn record_to_marcxml(record: &Record) -> Result<String> {
 ///
 /// Returns an error if the XML is invalid or missing required elements.
 pub fn marcxml_to_record(xml: &str) -> Result<Record> {
+    marcxml_to_record_with(xml, ParseOptions::default())
+}
+
+/// Convert a MARCXML string to a MARC record with the given strictness.
+///
+/// In lenient modes a malformed leader is repaired, invalid indicators
+/// (when checked) become blanks and subfields without a code are dropped;
+/// each repair is recorded in [`Record::warnings`].
+///
+/// # Errors
+///
+/// Returns an error if the XML is invalid, or if the record is malformed
+/// and `options` is strict.
+pub fn marcxml_to_record_with(xml: &str, options: ParseOptions) -> Result<Record> {
+    crate::telemetry::stage_span!("mrrc.convert", from = "marcxml", to = "marc");
     let mut ctx = ParseContext::new();
     let cleaned = strip_marcxml_ns(xml);
-    let mut reader = quick_xml::reader::Reader::from_str(&cleaned);
+    let mut reader = GuardedReader::from_str(&cleaned, options.xml);
 
     // Walk to the first <record> Start event.
     let mut buf = Vec::new();
@@ -485,7 +687,7 @@ pub fn marcxml_to_record(xml: &str) -> Result<Record> {
 
     ctx.begin_record();
     let xml_record = read_marcxml_record(&mut reader, &ctx)?;
-    marcxml_record_to_record(xml_record).map_err(|e| e.with_position(&ctx))
+    marcxml_record_to_record(xml_record, options).map_err(|e| e.with_position(&ctx))
 }
 
 /// Convert a MARCXML `<collection>` string to multiple MARC records.
@@ -505,9 +707,21 @@ pub fn marcxml_to_record(xml: &str) -> Result<Record> {
 ///
 /// Returns an error if the XML is invalid or cannot be parsed.
 pub fn marcxml_to_records(xml: &str) -> Result<Vec<Record>> {
+    marcxml_to_records_with(xml, ParseOptions::default())
+}
+
+/// Convert a MARCXML `<collection>` string to multiple MARC records with
+/// the given strictness. See [`marcxml_to_record_with`].
+///
+/// # Errors
+///
+/// Returns an error if the XML is invalid, or if a record is malformed and
+/// `options` is strict.
+pub fn marcxml_to_records_with(xml: &str, options: ParseOptions) -> Result<Vec<Record>> {
+    crate::telemetry::stage_span!("mrrc.convert", from = "marcxml", to = "marc");
     let mut ctx = ParseContext::new();
     let cleaned = strip_marcxml_ns(xml);
-    let mut reader = quick_xml::reader::Reader::from_str(&cleaned);
+    let mut reader = GuardedReader::from_str(&cleaned, options.xml);
 
     let mut records = Vec::new();
     let mut buf = Vec::new();
@@ -520,8 +734,10 @@ pub fn marcxml_to_records(xml: &str) -> Result<Vec<Record>> {
             Event::Start(ref e) if e.name().into_inner() == b"record" => {
                 ctx.begin_record();
                 let xml_record = read_marcxml_record(&mut reader, &ctx)?;
-                records
-                    .push(marcxml_record_to_record(xml_record).map_err(|e| e.with_position(&ctx))?);
+                records.push(
+                    marcxml_record_to_record(xml_record, options)
+                        .map_err(|e| e.with_position(&ctx))?,
+                );
             },
             Event::Eof => return Ok(records),
             _ => {},
@@ -530,8 +746,9 @@ pub fn marcxml_to_records(xml: &str) -> Result<Vec<Record>> {
 }
 
 /// Internal helper: convert a deserialized `MarcxmlRecord` into a `Record`.
-fn marcxml_record_to_record(xml_record: MarcxmlRecord) -> Result<Record> {
-    let leader = Leader::from_bytes(xml_record.leader.as_bytes())?;
+fn marcxml_record_to_record(xml_record: MarcxmlRecord, options: ParseOptions) -> Result<Record> {
+    let mut warnings = Vec::new();
+    let leader = recovery::leader_from_text(&xml_record.leader, options, &mut warnings)?;
     let mut record = Record::new(leader);
 
     for cf in xml_record.controlfield {
@@ -541,26 +758,37 @@ fn marcxml_record_to_record(xml_record: MarcxmlRecord) -> Result<Record> {
     for df in xml_record.datafield {
         let ind1 = df.ind1.chars().next().unwrap_or(' ');
         let ind2 = df.ind2.chars().next().unwrap_or(' ');
+        let ind1 = recovery::indicator_from_text(ind1, &df.tag, 0, options, &mut warnings)?;
+        let ind2 = recovery::indicator_from_text(ind2, &df.tag, 1, options, &mut warnings)?;
 
         let mut field = Field::new(df.tag, ind1, ind2);
 
         for sf in df.subfield {
-            let code =
-                sf.code.chars().next().ok_or_else(|| {
-                    MarcError::invalid_field_msg("Missing subfield code".to_string())
-                })?;
+            let Some(code) = sf.code.chars().next() else {
+                let err = MarcError::invalid_field_msg("Missing subfield code".to_string())
+                    .with_field_tag(Some(field.tag.clone()));
+                if options.is_strict() {
+                    return Err(err);
+                }
+                warnings.push(err);
+                continue;
+            };
             field.add_subfield(code, sf.value);
         }
 
         record.add_field(field);
     }
 
+    if options.collect_warnings && !warnings.is_empty() {
+        record.errors = std::sync::Arc::new(warnings);
+    }
     Ok(record)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::leader::Leader;
 
     fn make_test_leader() -> Leader {
         Leader {
@@ -817,6 +1045,35 @@ mod tests {
         assert_eq!(fields[0].get_subfield('a'), Some("first\nsecond"));
     }
 
+    /// A raw MARC-8 escape sequence cannot go into XML 1.0: it is rejected
+    /// by default, and written as hex notation or decoded on request.
+    #[test]
+    fn test_marcxml_control_characters() {
+        let mut record = Record::new(make_test_leader());
+        let mut field = Field::new("245".to_string(), '1', '0');
+        field.add_subfield('a', "H\x1Bb2\x1BsO".to_string());
+        record.add_field(field);
+
+        let err = record_to_marcxml(&record).unwrap_err();
+        assert!(matches!(err, MarcError::EncodingError { .. }), "{err}");
+
+        let xml = record_to_marcxml_with(&record, ControlCharacters::Hex).unwrap();
+        assert!(xml.contains("H{1B}b2{1B}sO"));
+        let mut restored = marcxml_to_record(&xml).unwrap();
+        crate::encoding::restore_control_characters(&mut restored);
+        assert_eq!(
+            restored.get_field("245").unwrap().get_subfield('a'),
+            Some("H\x1Bb2\x1BsO")
+        );
+
+        let xml = record_to_marcxml_with(&record, ControlCharacters::Decode).unwrap();
+        let decoded = marcxml_to_record(&xml).unwrap();
+        assert_eq!(
+            decoded.get_field("245").unwrap().get_subfield('a'),
+            Some("H\u{2082}O")
+        );
+    }
+
     #[test]
     fn test_parse_full_marcxml_record() {
         // A complete MARCXML record (standard namespace, control and data fields)
@@ -0,0 +1,321 @@
+//! Merging already-sorted record streams into one.
+//!
+//! [`MergeReader`] reads several sources that are each sorted by the same
+//! key (for example the output of [`record_sort`](crate::record_sort))
+//! and yields one stream in key order, holding only the next record of
+//! each source in memory. Records with equal keys come out in source
+//! order, so a nightly update file listed after the base file follows the
+//! base record it replaces.
+//!
+//! With a [`Duplicates`] policy other than [`Duplicates::Keep`], each run
+//! of records sharing a key — across sources and within one — collapses
+//! to a single record: the first, the last, or the one with the latest
+//! 005 transaction timestamp.
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::formats::FormatReader;
+//! use mrrc::merge_reader::{Duplicates, MergeReader};
+//! use mrrc::{Leader, MarcReader, MarcWriter, Record};
+//!
+//! fn file(records: &[(&str, &str)]) -> Vec<u8> {
+//!     let mut bytes = Vec::new();
+//!     let mut writer = MarcWriter::new(&mut bytes);
+//!     for (id, ts) in records {
+//!         let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+//!         record.add_control_field_str("001", id);
+//!         record.add_control_field_str("005", ts);
+//!         writer.write_record(&record).unwrap();
+//!     }
+//!     drop(writer);
+//!     bytes
+//! }
+//!
+//! let base = file(&[("a1", "20240101000000.0"), ("b2", "20240101000000.0")]);
+//! let update = file(&[("b2", "20240301000000.0"), ("c3", "20240301000000.0")]);
+//! let mut reader = MergeReader::new(
+//!     vec![MarcReader::new(base.as_slice()), MarcReader::new(update.as_slice())],
+//!     |record: &Record| record.get_control_field("001").unwrap_or_default().to_string(),
+//! )
+//! .duplicates(Duplicates::Latest005);
+//!
+//! let mut merged = Vec::new();
+//! while let Some(record) = reader.read_record()? {
+//!     merged.push(record.get_control_field("005").unwrap().to_string());
+//! }
+//! assert_eq!(merged, ["20240101000000.0", "20240301000000.0", "20240301000000.0"]);
+//! assert_eq!(reader.duplicates_dropped(), 1);
+//! # Ok::<(), mrrc::MarcError>(())
+//! ```
+
+use crate::error::{MarcError, Result};
+use crate::formats::FormatReader;
+use crate::record::Record;
+
+/// What to do with records that share a key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Duplicates {
+    /// Yield every record, equal keys in source order (the default)
+    #[default]
+    Keep,
+    /// Yield the first record of each key: the earliest source wins
+    First,
+    /// Yield the last record of each key: the latest source wins
+    Last,
+    /// Yield the record with the greatest 005; records without a 005
+    /// lose to those with one, and ties go to the later record
+    Latest005,
+}
+
+/// A [`FormatReader`] over several key-sorted sources, yielding their
+/// records merged in key order.
+///
+/// Each source must already be sorted by `key` (ascending); a record
+/// whose key is lower than the one before it in the same source is an
+/// error. The next record of every source is held in memory, so memory
+/// use does not grow with the size of the inputs.
+pub struct MergeReader<R, K, F> {
+    sources: Vec<R>,
+    /// Next record of each source with its key; `None` once exhausted.
+    heads: Vec<Option<(K, Record)>>,
+    primed: bool,
+    key: F,
+    duplicates: Duplicates,
+    records_read: usize,
+    dropped: usize,
+}
+
+impl<R: std::fmt::Debug, K, F> std::fmt::Debug for MergeReader<R, K, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MergeReader")
+            .field("sources", &self.sources)
+            .field("duplicates", &self.duplicates)
+            .field("records_read", &self.records_read)
+            .field("dropped", &self.dropped)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<R, K, F> MergeReader<R, K, F>
+where
+    R: FormatReader,
+    K: Ord,
+    F: Fn(&Record) -> K,
+{
+    /// Merge `sources`, each sorted by `key`. Records with equal keys are
+    /// all yielded, in source order, unless [`duplicates`](Self::duplicates)
+    /// says otherwise.
+    pub fn new(sources: Vec<R>, key: F) -> Self {
+        let heads = sources.iter().map(|_| None).collect();
+        MergeReader {
+            sources,
+            heads,
+            primed: false,
+            key,
+            duplicates: Duplicates::Keep,
+            records_read: 0,
+            dropped: 0,
+        }
+    }
+
+    /// Set the policy for records sharing a key.
+    #[must_use]
+    pub fn duplicates(mut self, duplicates: Duplicates) -> Self {
+        self.duplicates = duplicates;
+        self
+    }
+
+    /// Records discarded so far under the [`Duplicates`] policy.
+    #[must_use]
+    pub fn duplicates_dropped(&self) -> usize {
+        self.dropped
+    }
+
+    /// Unwrap the sources.
+    pub fn into_inner(self) -> Vec<R> {
+        self.sources
+    }
+
+    /// Read the next record of source `index` into its head, checking it
+    /// does not sort before `previous`.
+    fn advance(&mut self, index: usize, previous: Option<&K>) -> Result<()> {
+        let Some(record) = self.sources[index].read_record()? else {
+            self.heads[index] = None;
+            return Ok(());
+        };
+        let key = (self.key)(&record);
+        if previous.is_some_and(|previous| key < *previous) {
+            return Err(MarcError::invalid_field_msg(format!(
+                "Merge source {index} is not sorted by the merge key"
+            )));
+        }
+        self.heads[index] = Some((key, record));
+        Ok(())
+    }
+
+    /// Index of the head with the lowest key; ties go to the earlier
+    /// source.
+    fn lowest(&self) -> Option<usize> {
+        let mut lowest: Option<(usize, &K)> = None;
+        for (index, head) in self.heads.iter().enumerate() {
+            if let Some((key, _)) = head
+                && lowest.is_none_or(|(_, low)| key < low)
+            {
+                lowest = Some((index, key));
+            }
+        }
+        lowest.map(|(index, _)| index)
+    }
+
+    /// Take the lowest head and refill its source.
+    fn pop(&mut self) -> Result<Option<(K, Record)>> {
+        let Some(index) = self.lowest() else {
+            return Ok(None);
+        };
+        let (key, record) = self.heads[index].take().expect("lowest head is present");
+        self.advance(index, Some(&key))?;
+        Ok(Some((key, record)))
+    }
+
+    /// Whether the lowest remaining head has key `key`.
+    fn next_is(&self, key: &K) -> bool {
+        self.lowest()
+            .and_then(|index| self.heads[index].as_ref())
+            .is_some_and(|(next, _)| next == key)
+    }
+}
+
+/// Whether `candidate` should replace `kept` under [`Duplicates::Latest005`].
+fn is_later(candidate: &Record, kept: &Record) -> bool {
+    candidate.get_control_field("005") >= kept.get_control_field("005")
+}
+
+impl<R, K, F> FormatReader for MergeReader<R, K, F>
+where
+    R: FormatReader,
+    K: Ord,
+    F: Fn(&Record) -> K,
+{
+    fn read_record(&mut self) -> Result<Option<Record>> {
+        if !self.primed {
+            for index in 0..self.sources.len() {
+                self.advance(index, None)?;
+            }
+            self.primed = true;
+        }
+        let Some((key, mut record)) = self.pop()? else {
+            return Ok(None);
+        };
+        if self.duplicates != Duplicates::Keep {
+            while self.next_is(&key) {
+                let (_, candidate) = self.pop()?.expect("next head is present");
+                self.dropped += 1;
+                let replace = match self.duplicates {
+                    Duplicates::Keep | Duplicates::First => false,
+                    Duplicates::Last => true,
+                    Duplicates::Latest005 => is_later(&candidate, &record),
+                };
+                if replace {
+                    record = candidate;
+                }
+            }
+        }
+        self.records_read += 1;
+        Ok(Some(record))
+    }
+
+    fn records_read(&self) -> Option<usize> {
+        Some(self.records_read)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::leader::Leader;
+    use crate::reader::MarcReader;
+    use crate::writer::MarcWriter;
+
+    fn file(records: &[(&str, Option<&str>)]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let mut writer = MarcWriter::new(&mut bytes);
+        for (id, ts) in records {
+            let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+            record.add_control_field_str("001", id);
+            if let Some(ts) = ts {
+                record.add_control_field_str("005", ts);
+            }
+            writer.write_record(&record).unwrap();
+        }
+        drop(writer);
+        bytes
+    }
+
+    fn by_001(record: &Record) -> String {
+        record
+            .get_control_field("001")
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    fn merge(files: &[Vec<u8>], duplicates: Duplicates) -> Result<Vec<(String, String)>> {
+        let sources = files
+            .iter()
+            .map(|f| MarcReader::new(f.as_slice()))
+            .collect();
+        let mut reader = MergeReader::new(sources, by_001).duplicates(duplicates);
+        let mut out = Vec::new();
+        while let Some(record) = reader.read_record()? {
+            out.push((
+                by_001(&record),
+                record
+                    .get_control_field("005")
+                    .unwrap_or_default()
+                    .to_string(),
+            ));
+        }
+        Ok(out)
+    }
+
+    fn ids(merged: &[(String, String)]) -> Vec<&str> {
+        merged.iter().map(|(id, _)| id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_merge_keeps_all_in_source_order() {
+        let files = [
+            file(&[("a", Some("1")), ("c", Some("1")), ("e", Some("1"))]),
+            file(&[("b", Some("2")), ("c", Some("2")), ("f", Some("2"))]),
+            file(&[]),
+            file(&[("c", Some("3")), ("d", Some("3"))]),
+        ];
+        let merged = merge(&files, Duplicates::Keep).unwrap();
+        assert_eq!(ids(&merged), ["a", "b", "c", "c", "c", "d", "e", "f"]);
+        let cs: Vec<&str> = merged[2..5].iter().map(|(_, ts)| ts.as_str()).collect();
+        assert_eq!(cs, ["1", "2", "3"]);
+    }
+
+    #[test]
+    fn test_merge_duplicate_policies() {
+        let files = [
+            file(&[("a", Some("20240105")), ("b", None)]),
+            file(&[("a", Some("20240301")), ("b", Some("20240101"))]),
+            file(&[("a", Some("20240201")), ("a", Some("20240110"))]),
+        ];
+        let pick = |duplicates| {
+            let merged = merge(&files, duplicates).unwrap();
+            assert_eq!(ids(&merged), ["a", "b"]);
+            merged.into_iter().map(|(_, ts)| ts).collect::<Vec<_>>()
+        };
+        assert_eq!(pick(Duplicates::First), ["20240105", ""]);
+        assert_eq!(pick(Duplicates::Last), ["20240110", "20240101"]);
+        assert_eq!(pick(Duplicates::Latest005), ["20240301", "20240101"]);
+    }
+
+    #[test]
+    fn test_merge_rejects_unsorted_source() {
+        let files = [file(&[("a", None)]), file(&[("c", None), ("b", None)])];
+        assert!(merge(&files, Duplicates::Keep).is_err());
+    }
+}
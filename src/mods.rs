@@ -14,6 +14,9 @@
 //! - Locations and holdings information
 //! - Related resources
 //!
+//! [`ModsWriter`] and [`ModsReader`] stream `<modsCollection>` documents one
+//! record at a time, for collections too large to hold in memory.
+//!
 //! # Examples
 //!
 //! ```ignore
@@ -30,13 +33,29 @@
 //! ```
 
 use std::fmt::Write;
+use std::io::BufRead;
+use std::sync::LazyLock;
 
-use quick_xml::Reader;
 use quick_xml::events::Event;
 
+use crate::crosswalk::Crosswalk;
 use crate::error::{MarcError, Result};
+use crate::formats::{FormatReader, FormatWriter};
 use crate::leader::Leader;
 use crate::record::{Field, Record};
+use crate::xml_security::{GuardedReader, XmlOptions};
+use crate::xml_style::{XmlNamespace, XmlStyle};
+
+/// The built-in simple-element mappings.
+static DEFAULT_CROSSWALK: LazyLock<Crosswalk> = LazyLock::new(Crosswalk::mods);
+
+/// The MODS namespace URI.
+const MODS_NS: &str = "http://www.loc.gov/mods/v3";
+
+/// Namespace declarations on the root element of MODS output.
+const MODS_NAMESPACES: &str = "xmlns=\"http://www.loc.gov/mods/v3\" \
+    xmlns:mods=\"http://www.loc.gov/mods/v3\" \
+    xmlns:xlink=\"http://www.w3.org/1999/xlink\"";
 
 /// Convert a MARC record to MODS XML format.
 ///
@@ -44,14 +63,24 @@ use crate::record::{Field, Record};
 /// Common mappings:
 /// - 245 (Title Statement) → mods:titleInfo/mods:title
 /// - 1XX (Main Entry) → mods:name
-/// - 6XX (Subject) → mods:subject
 /// - 520 (Summary) → mods:abstract
+/// - 500 (General Note) → mods:note
+/// - 521 (Target Audience) → mods:targetAudience
+/// - 506 / 540 (Access and Use) → mods:accessCondition
 /// - 260 (Publication) → mods:originInfo
 /// - 300 (Physical Description) → mods:physicalDescription
-/// - 020 (ISBN) / 022 (ISSN) → mods:identifier
+/// - 020 (ISBN) / 022 (ISSN) / 001 (Control Number, type="local") → mods:identifier
 /// - 041 (Language) → mods:language
 /// - 650 (Topical Subject) → mods:subject/mods:topic
 /// - 651 (Geographic Subject) → mods:subject/mods:geographic
+/// - 655 (Genre/Form) → mods:genre
+/// - 505 (Contents) → mods:tableOfContents
+/// - 050 / 082 / 084 (Classification) → mods:classification
+/// - 76X-78X (Linking Entries) → mods:relatedItem (host, preceding, otherFormat, ...)
+/// - 440 / 490 / 800-830 (Series) → mods:relatedItem type="series"
+/// - 852 (Location) → mods:location/mods:physicalLocation, shelfLocator
+/// - 856 (Electronic Location) → mods:location/mods:url
+/// - 001 / 003 / 005 / 008 / 040 → mods:recordInfo
 ///
 /// # Examples
 ///
@@ -72,25 +101,202 @@ use crate::record::{Field, Record};
 ///
 /// Returns an error if the record cannot be converted.
 pub fn record_to_mods_xml(record: &Record) -> Result<String> {
-    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
-    xml.push_str("<mods xmlns=\"http://www.loc.gov/mods/v3\" ");
-    xml.push_str("xmlns:mods=\"http://www.loc.gov/mods/v3\" ");
-    xml.push_str("xmlns:xlink=\"http://www.w3.org/1999/xlink\">\n");
-
-    write_titles(&mut xml, record);
-    write_names(&mut xml, record);
-    write_type_of_resource(&mut xml, record);
-    write_origin_info(&mut xml, record);
-    write_physical_description(&mut xml, record);
-    write_abstract(&mut xml, record);
-    write_subjects(&mut xml, record);
-    write_identifiers(&mut xml, record);
-    write_language(&mut xml, record);
+    record_to_mods_xml_with(record, &DEFAULT_CROSSWALK)
+}
 
+/// Convert a MARC record to MODS XML, taking the simple elements (abstract,
+/// notes, subjects, identifiers, access conditions, ...) from `crosswalk`.
+///
+/// Element names in the crosswalk are MODS element paths such as
+/// `subject/topic` or `identifier[type=isbn]`; each value becomes one
+/// element. Titles, names, origin information and the other structured
+/// elements are written as by [`record_to_mods_xml`] whatever the crosswalk.
+///
+/// # Errors
+///
+/// Returns [`MarcError::InvalidField`] if a crosswalk element is not a
+/// valid element path.
+pub fn record_to_mods_xml_with(record: &Record, crosswalk: &Crosswalk) -> Result<String> {
+    crate::telemetry::stage_span!("mrrc.convert", from = "marc", to = "mods");
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    writeln!(xml, "<mods {MODS_NAMESPACES}>").ok();
+    write_mods_body(&mut xml, record, crosswalk)?;
     xml.push_str("</mods>\n");
     Ok(xml)
 }
 
+/// Convert a MARC record to MODS XML laid out according to `style`
+/// (indentation and namespace prefix; see [`crate::xml_style`]).
+///
+/// # Errors
+///
+/// Returns an error if the record cannot be converted, as for
+/// [`record_to_mods_xml`].
+pub fn record_to_mods_xml_styled(record: &Record, style: &XmlStyle) -> Result<String> {
+    style.apply(&record_to_mods_xml(record)?, MODS_NS, 0)
+}
+
+/// Write the elements of one `<mods>` element, without the element itself.
+fn write_mods_body(xml: &mut String, record: &Record, crosswalk: &Crosswalk) -> Result<()> {
+    write_titles(xml, record);
+    write_names(xml, record);
+    write_type_of_resource(xml, record);
+    write_genres(xml, record);
+    write_origin_info(xml, record);
+    write_physical_description(xml, record);
+    write_crosswalk_elements(xml, record, crosswalk)?;
+    write_table_of_contents(xml, record);
+    write_classifications(xml, record);
+    write_related_items(xml, record);
+    write_language(xml, record);
+    write_locations(xml, record);
+    write_record_info(xml, record);
+    Ok(())
+}
+
+/// Streams records as a `<modsCollection>` document.
+///
+/// Each record is converted and written as soon as it is passed in, so
+/// memory use does not grow with the size of the collection. The
+/// collection's start tag is written with the first record and its end
+/// tag by [`finish`](FormatWriter::finish), which must be called for the
+/// document to be complete.
+///
+/// # Examples
+///
+/// ```
+/// use mrrc::formats::FormatWriter;
+/// use mrrc::mods::ModsWriter;
+/// use mrrc::{Field, Leader, Record};
+///
+/// let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+/// record.add_field(
+///     Field::builder("245".to_string(), '1', '0')
+///         .subfield_str('a', "A title")
+///         .build(),
+/// );
+///
+/// let mut writer = ModsWriter::new(Vec::new());
+/// writer.write_record(&record)?;
+/// writer.finish()?;
+/// let xml = String::from_utf8(writer.into_inner()).unwrap();
+/// assert!(xml.contains("<modsCollection"));
+/// assert!(xml.contains("<mods:title>A title</mods:title>"));
+/// # Ok::<(), mrrc::MarcError>(())
+/// ```
+#[derive(Debug)]
+pub struct ModsWriter<W: std::io::Write> {
+    writer: W,
+    crosswalk: Crosswalk,
+    style: Option<XmlStyle>,
+    started: bool,
+    finished: bool,
+    records_written: usize,
+}
+
+impl<W: std::io::Write> ModsWriter<W> {
+    /// Create a writer over `writer`.
+    pub fn new(writer: W) -> Self {
+        ModsWriter {
+            writer,
+            crosswalk: Crosswalk::mods(),
+            style: None,
+            started: false,
+            finished: false,
+            records_written: 0,
+        }
+    }
+
+    /// Take the simple element mappings from `crosswalk` instead of
+    /// [`Crosswalk::mods`], as [`record_to_mods_xml_with`] does.
+    #[must_use]
+    pub fn with_crosswalk(mut self, crosswalk: Crosswalk) -> Self {
+        self.crosswalk = crosswalk;
+        self
+    }
+
+    /// Lay out records according to `style` instead of the default
+    /// two-space indentation. In pretty mode records are indented one level
+    /// inside the `<modsCollection>`.
+    #[must_use]
+    pub fn with_style(mut self, style: XmlStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Unwrap the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn collection_name(&self) -> String {
+        self.style.as_ref().map_or_else(
+            || "modsCollection".to_string(),
+            |s| s.element_name("modsCollection"),
+        )
+    }
+
+    fn start(&mut self) -> Result<()> {
+        if !self.started {
+            self.started = true;
+            let declarations = match &self.style {
+                Some(style) if style.namespace != XmlNamespace::Native => format!(
+                    "{} xmlns:xlink=\"http://www.w3.org/1999/xlink\"",
+                    style.declaration(MODS_NS)
+                ),
+                _ => format!(" {MODS_NAMESPACES}"),
+            };
+            write!(
+                self.writer,
+                "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<{}{declarations}>\n",
+                self.collection_name()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl<W: std::io::Write + std::fmt::Debug> FormatWriter for ModsWriter<W> {
+    fn write_record(&mut self, record: &Record) -> Result<()> {
+        if self.finished {
+            return Err(MarcError::WriterError {
+                record_index: None,
+                record_control_number: None,
+                field_tag: None,
+                message: "Cannot write to a finished writer".to_string(),
+            });
+        }
+        self.start()?;
+        let mut xml = String::from("<mods>\n");
+        write_mods_body(&mut xml, record, &self.crosswalk)?;
+        xml.push_str("</mods>\n");
+        if let Some(style) = &self.style {
+            let depth = usize::from(style.indent.is_some());
+            xml = style.apply(&xml, MODS_NS, depth)?;
+            if !xml.ends_with('\n') {
+                xml.push('\n');
+            }
+        }
+        self.writer.write_all(xml.as_bytes())?;
+        self.records_written += 1;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        if !self.finished {
+            self.start()?;
+            writeln!(self.writer, "</{}>", self.collection_name())?;
+            self.finished = true;
+        }
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    fn records_written(&self) -> Option<usize> {
+        Some(self.records_written)
+    }
+}
+
 fn write_titles(xml: &mut String, record: &Record) {
     if let Some(fields_245) = record.fields.get("245") {
         for field in fields_245 {
@@ -294,128 +500,426 @@ fn write_physical_description(xml: &mut String, record: &Record) {
     }
 }
 
-fn write_abstract(xml: &mut String, record: &Record) {
-    // Summary/Abstract (520)
-    if let Some(fields) = record.fields.get("520") {
-        for field in fields {
-            if let Some(subfield) = field.subfields.iter().find(|s| s.code == 'a') {
-                writeln!(
-                    xml,
-                    "  <mods:abstract>{}</mods:abstract>",
-                    escape_xml(&subfield.value)
-                )
-                .ok();
+/// Write the simple elements mapped by `crosswalk`, one element per value.
+fn write_crosswalk_elements(
+    xml: &mut String,
+    record: &Record,
+    crosswalk: &Crosswalk,
+) -> Result<()> {
+    for mapping in &crosswalk.mappings {
+        let path = parse_element_path(&mapping.element)?;
+        for value in mapping.values(record) {
+            write_element_path(xml, &path, &value);
+        }
+    }
+    Ok(())
+}
+
+/// One step of a crosswalk element path such as `identifier[type=isbn]`.
+struct PathStep<'a> {
+    name: &'a str,
+    attributes: Vec<(&'a str, &'a str)>,
+}
+
+/// Parse a `/`-separated element path with `[name=value]` attributes.
+fn parse_element_path(path: &str) -> Result<Vec<PathStep<'_>>> {
+    let invalid = || MarcError::invalid_field_msg(format!("Invalid MODS element path '{path}'"));
+    let is_name = |s: &str| {
+        !s.is_empty()
+            && s.chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    };
+    path.split('/')
+        .map(|step| {
+            let (name, mut rest) = step.split_once('[').map_or((step, ""), |(n, r)| (n, r));
+            if !is_name(name) {
+                return Err(invalid());
             }
+            let mut attributes = Vec::new();
+            while !rest.is_empty() {
+                let (attribute, next) = rest.split_once(']').ok_or_else(invalid)?;
+                let (key, value) = attribute.split_once('=').ok_or_else(invalid)?;
+                if !is_name(key) {
+                    return Err(invalid());
+                }
+                attributes.push((key, value));
+                rest = match next {
+                    "" => "",
+                    next => next.strip_prefix('[').ok_or_else(invalid)?,
+                };
+            }
+            Ok(PathStep { name, attributes })
+        })
+        .collect()
+}
+
+/// Write `value` as the innermost element of `path`.
+fn write_element_path(xml: &mut String, path: &[PathStep<'_>], value: &str) {
+    let last = path.len() - 1;
+    for (depth, step) in path.iter().enumerate() {
+        write!(xml, "{}<mods:{}", "  ".repeat(depth + 1), step.name).ok();
+        for (key, attribute) in &step.attributes {
+            write!(xml, " {key}=\"{}\"", escape_xml(attribute)).ok();
         }
+        if depth == last {
+            writeln!(xml, ">{}</mods:{}>", escape_xml(value), step.name).ok();
+        } else {
+            xml.push_str(">\n");
+        }
+    }
+    for (depth, step) in path[..last].iter().enumerate().rev() {
+        writeln!(xml, "{}</mods:{}>", "  ".repeat(depth + 1), step.name).ok();
     }
+}
 
-    // General notes (500)
-    if let Some(fields) = record.fields.get("500") {
+fn write_language(xml: &mut String, record: &Record) {
+    if let Some(fields) = record.fields.get("041") {
         for field in fields {
-            if let Some(subfield) = field.subfields.iter().find(|s| s.code == 'a') {
-                writeln!(
-                    xml,
-                    "  <mods:note>{}</mods:note>",
-                    escape_xml(&subfield.value)
-                )
-                .ok();
+            for subfield in field.subfields.iter().filter(|s| s.code == 'a') {
+                xml.push_str("  <mods:language>\n");
+                let langs: Vec<&str> = subfield.value.split_whitespace().collect();
+                for lang in langs {
+                    if !lang.is_empty() {
+                        writeln!(
+                            xml,
+                            "    <mods:languageTerm type=\"code\" authority=\"iso639-2b\">{lang}</mods:languageTerm>"
+                        )
+                        .ok();
+                    }
+                }
+                xml.push_str("  </mods:language>\n");
+            }
+        }
+    }
+}
+
+fn write_genres(xml: &mut String, record: &Record) {
+    // Genre/form terms (655)
+    for field in record.fields_by_tag("655") {
+        let Some(term) = field.get_subfield('a') else {
+            continue;
+        };
+        let authority = match field.indicator2 {
+            '0' => Some("lcsh"),
+            '7' => field.get_subfield('2'),
+            _ => None,
+        };
+        match authority {
+            Some(authority) => writeln!(
+                xml,
+                "  <mods:genre authority=\"{}\">{}</mods:genre>",
+                escape_xml(authority),
+                escape_xml(term)
+            ),
+            None => writeln!(xml, "  <mods:genre>{}</mods:genre>", escape_xml(term)),
+        }
+        .ok();
+    }
+}
+
+fn write_table_of_contents(xml: &mut String, record: &Record) {
+    // Formatted contents (505): $a as is, or enhanced $t/$r parts joined
+    for field in record.fields_by_tag("505") {
+        let contents = match field.get_subfield('a') {
+            Some(text) => text.to_string(),
+            None => field
+                .subfields
+                .iter()
+                .filter(|s| matches!(s.code, 't' | 'r' | 'g'))
+                .map(|s| s.value.trim())
+                .collect::<Vec<_>>()
+                .join(" "),
+        };
+        if !contents.is_empty() {
+            writeln!(
+                xml,
+                "  <mods:tableOfContents>{}</mods:tableOfContents>",
+                escape_xml(&contents)
+            )
+            .ok();
+        }
+    }
+}
+
+fn write_classifications(xml: &mut String, record: &Record) {
+    // LC (050), Dewey (082) and other schemes (084, scheme in $2)
+    for tag in ["050", "082", "084"] {
+        for field in record.fields_by_tag(tag) {
+            let Some(number) = field.get_subfield('a') else {
+                continue;
+            };
+            let number = match field.get_subfield('b') {
+                Some(item) if tag == "050" => format!("{number} {item}"),
+                _ => number.to_string(),
+            };
+            let mut attrs = String::new();
+            match tag {
+                "050" => attrs.push_str(" authority=\"lcc\""),
+                "082" => {
+                    attrs.push_str(" authority=\"ddc\"");
+                    if let Some(edition) = field.get_subfield('2') {
+                        write!(attrs, " edition=\"{}\"", escape_xml(edition)).ok();
+                    }
+                },
+                _ => {
+                    if let Some(scheme) = field.get_subfield('2') {
+                        write!(attrs, " authority=\"{}\"", escape_xml(scheme)).ok();
+                    }
+                },
             }
+            writeln!(
+                xml,
+                "  <mods:classification{attrs}>{}</mods:classification>",
+                escape_xml(&number)
+            )
+            .ok();
         }
     }
 }
 
-fn write_subjects(xml: &mut String, record: &Record) {
-    // Topical subjects (650)
-    if let Some(fields) = record.fields.get("650") {
+/// MODS `relatedItem` type for a linking entry or series tag.
+fn related_item_type(tag: &str) -> Option<&'static str> {
+    match tag {
+        "760" | "440" | "490" | "800" | "810" | "811" | "830" => Some("series"),
+        "762" | "770" | "774" => Some("constituent"),
+        "765" | "767" | "775" => Some("otherVersion"),
+        "772" | "773" => Some("host"),
+        "776" => Some("otherFormat"),
+        "780" => Some("preceding"),
+        "785" => Some("succeeding"),
+        "786" => Some("original"),
+        _ => None,
+    }
+}
+
+fn write_related_items(xml: &mut String, record: &Record) {
+    // Linking entries (760-787)
+    for (tag, fields) in &record.fields {
+        if !("760".."788").contains(&tag.as_str()) {
+            continue;
+        }
         for field in fields {
-            if let Some(subfield) = field.subfields.iter().find(|s| s.code == 'a') {
-                xml.push_str("  <mods:subject>\n");
+            let title = field.get_subfield('t');
+            let name = field.get_subfield('a');
+            if title.is_none() && name.is_none() {
+                continue;
+            }
+            open_related_item(xml, related_item_type(tag));
+            if let Some(name) = name {
                 writeln!(
                     xml,
-                    "    <mods:topic>{}</mods:topic>",
-                    escape_xml(&subfield.value)
+                    "    <mods:name><mods:namePart>{}</mods:namePart></mods:name>",
+                    escape_xml(name)
                 )
                 .ok();
-                xml.push_str("  </mods:subject>\n");
             }
-        }
-    }
-
-    // Geographic subjects (651)
-    if let Some(fields) = record.fields.get("651") {
-        for field in fields {
-            if let Some(subfield) = field.subfields.iter().find(|s| s.code == 'a') {
-                xml.push_str("  <mods:subject>\n");
+            if let Some(title) = title {
+                write_related_title(xml, title);
+            }
+            for (code, id_type) in [('x', "issn"), ('z', "isbn"), ('w', "local")] {
+                for value in field.get_subfield_values(code) {
+                    writeln!(
+                        xml,
+                        "    <mods:identifier type=\"{id_type}\">{}</mods:identifier>",
+                        escape_xml(value)
+                    )
+                    .ok();
+                }
+            }
+            if let Some(part) = field.get_subfield('g') {
                 writeln!(
                     xml,
-                    "    <mods:geographic>{}</mods:geographic>",
-                    escape_xml(&subfield.value)
+                    "    <mods:part><mods:text>{}</mods:text></mods:part>",
+                    escape_xml(part)
                 )
                 .ok();
-                xml.push_str("  </mods:subject>\n");
             }
+            xml.push_str("  </mods:relatedItem>\n");
         }
     }
-}
 
-fn write_identifiers(xml: &mut String, record: &Record) {
-    // ISBN (020)
-    if let Some(fields) = record.fields.get("020") {
-        for field in fields {
-            if let Some(subfield) = field.subfields.iter().find(|s| s.code == 'a') {
+    // Series statements (440, 490) and series added entries (800-830)
+    for tag in ["440", "490", "800", "810", "811", "830"] {
+        for field in record.fields_by_tag(tag) {
+            let (name, title) = match tag {
+                "800" | "810" | "811" => (field.get_subfield('a'), field.get_subfield('t')),
+                _ => (None, field.get_subfield('a')),
+            };
+            let Some(title) = title else {
+                continue;
+            };
+            open_related_item(xml, Some("series"));
+            if let Some(name) = name {
                 writeln!(
                     xml,
-                    "  <mods:identifier type=\"isbn\">{}</mods:identifier>",
-                    escape_xml(&subfield.value)
+                    "    <mods:name><mods:namePart>{}</mods:namePart></mods:name>",
+                    escape_xml(name)
+                )
+                .ok();
+            }
+            write_related_title(xml, title);
+            if let Some(issn) = field.get_subfield('x') {
+                writeln!(
+                    xml,
+                    "    <mods:identifier type=\"issn\">{}</mods:identifier>",
+                    escape_xml(issn)
                 )
                 .ok();
             }
+            if let Some(volume) = field.get_subfield('v') {
+                writeln!(
+                    xml,
+                    "    <mods:part><mods:detail type=\"volume\"><mods:number>{}</mods:number></mods:detail></mods:part>",
+                    escape_xml(volume)
+                )
+                .ok();
+            }
+            xml.push_str("  </mods:relatedItem>\n");
         }
     }
+}
 
-    // ISSN (022)
-    if let Some(fields) = record.fields.get("022") {
-        for field in fields {
-            if let Some(subfield) = field.subfields.iter().find(|s| s.code == 'a') {
+fn open_related_item(xml: &mut String, item_type: Option<&str>) {
+    match item_type {
+        Some(item_type) => writeln!(xml, "  <mods:relatedItem type=\"{item_type}\">"),
+        None => writeln!(xml, "  <mods:relatedItem>"),
+    }
+    .ok();
+}
+
+fn write_related_title(xml: &mut String, title: &str) {
+    writeln!(
+        xml,
+        "    <mods:titleInfo><mods:title>{}</mods:title></mods:titleInfo>",
+        escape_xml(title)
+    )
+    .ok();
+}
+
+fn write_locations(xml: &mut String, record: &Record) {
+    // Holdings location (852)
+    for field in record.fields_by_tag("852") {
+        let shelf_locator = field
+            .subfields
+            .iter()
+            .filter(|s| matches!(s.code, 'h' | 'i'))
+            .map(|s| s.value.as_str())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let sublocations: Vec<&str> = field.get_subfield_values('b');
+        if field.get_subfield('a').is_none() && sublocations.is_empty() && shelf_locator.is_empty()
+        {
+            continue;
+        }
+        xml.push_str("  <mods:location>\n");
+        for institution in field.get_subfield_values('a') {
+            writeln!(
+                xml,
+                "    <mods:physicalLocation>{}</mods:physicalLocation>",
+                escape_xml(institution)
+            )
+            .ok();
+        }
+        if !shelf_locator.is_empty() {
+            writeln!(
+                xml,
+                "    <mods:shelfLocator>{}</mods:shelfLocator>",
+                escape_xml(&shelf_locator)
+            )
+            .ok();
+        }
+        if !sublocations.is_empty() {
+            xml.push_str("    <mods:holdingSimple><mods:copyInformation>\n");
+            for sublocation in sublocations {
                 writeln!(
                     xml,
-                    "  <mods:identifier type=\"issn\">{}</mods:identifier>",
-                    escape_xml(&subfield.value)
+                    "      <mods:subLocation>{}</mods:subLocation>",
+                    escape_xml(sublocation)
                 )
                 .ok();
             }
+            xml.push_str("    </mods:copyInformation></mods:holdingSimple>\n");
+        }
+        xml.push_str("  </mods:location>\n");
+    }
+
+    // Electronic location (856): $3 → displayLabel, $z → note
+    for field in record.fields_by_tag("856") {
+        for url in field.get_subfield_values('u') {
+            let mut attrs = String::new();
+            if let Some(label) = field.get_subfield('3') {
+                write!(attrs, " displayLabel=\"{}\"", escape_xml(label)).ok();
+            }
+            if let Some(note) = field.get_subfield('z') {
+                write!(attrs, " note=\"{}\"", escape_xml(note)).ok();
+            }
+            writeln!(
+                xml,
+                "  <mods:location><mods:url{attrs}>{}</mods:url></mods:location>",
+                escape_xml(url)
+            )
+            .ok();
         }
     }
+}
+
+fn write_record_info(xml: &mut String, record: &Record) {
+    let mut info = String::new();
 
-    // Control number (001)
-    if let Some(control_001) = record.control_fields.get("001").and_then(|v| v.first()) {
+    let cataloging_source = record.get_field("040");
+    if let Some(source) = cataloging_source.and_then(|f| f.get_subfield('a')) {
         writeln!(
-            xml,
-            "  <mods:identifier type=\"local\">{}</mods:identifier>",
-            escape_xml(control_001)
+            info,
+            "    <mods:recordContentSource authority=\"marcorg\">{}</mods:recordContentSource>",
+            escape_xml(source)
         )
         .ok();
     }
-}
-
-fn write_language(xml: &mut String, record: &Record) {
-    if let Some(fields) = record.fields.get("041") {
-        for field in fields {
-            for subfield in field.subfields.iter().filter(|s| s.code == 'a') {
-                xml.push_str("  <mods:language>\n");
-                let langs: Vec<&str> = subfield.value.split_whitespace().collect();
-                for lang in langs {
-                    if !lang.is_empty() {
-                        writeln!(
-                            xml,
-                            "    <mods:languageTerm type=\"code\" authority=\"iso639-2b\">{lang}</mods:languageTerm>"
-                        )
-                        .ok();
-                    }
-                }
-                xml.push_str("  </mods:language>\n");
-            }
+    if let Some(entered) = record.get_control_field("008").and_then(|f| f.get(0..6)) {
+        writeln!(
+            info,
+            "    <mods:recordCreationDate encoding=\"marc\">{}</mods:recordCreationDate>",
+            escape_xml(entered)
+        )
+        .ok();
+    }
+    if let Some(changed) = record.get_control_field("005") {
+        writeln!(
+            info,
+            "    <mods:recordChangeDate encoding=\"iso8601\">{}</mods:recordChangeDate>",
+            escape_xml(changed)
+        )
+        .ok();
+    }
+    if let Some(id) = record.get_control_field("001") {
+        match record.get_control_field("003") {
+            Some(source) => writeln!(
+                info,
+                "    <mods:recordIdentifier source=\"{}\">{}</mods:recordIdentifier>",
+                escape_xml(source),
+                escape_xml(id)
+            ),
+            None => writeln!(
+                info,
+                "    <mods:recordIdentifier>{}</mods:recordIdentifier>",
+                escape_xml(id)
+            ),
         }
+        .ok();
+    }
+    if let Some(language) = cataloging_source.and_then(|f| f.get_subfield('b')) {
+        writeln!(
+            info,
+            "    <mods:languageOfCataloging><mods:languageTerm type=\"code\" authority=\"iso639-2b\">{}</mods:languageTerm></mods:languageOfCataloging>",
+            escape_xml(language)
+        )
+        .ok();
+    }
+
+    if !info.is_empty() {
+        xml.push_str("  <mods:recordInfo>\n");
+        xml.push_str(&info);
+        xml.push_str("  </mods:recordInfo>\n");
     }
 }
 
@@ -473,7 +977,7 @@ impl StartInfo {
 }
 
 /// Read the text content of the current element and consume the end tag.
-fn read_text(reader: &mut Reader<&[u8]>, buf: &mut Vec<u8>) -> Result<String> {
+fn read_text<R: BufRead>(reader: &mut GuardedReader<R>, buf: &mut Vec<u8>) -> Result<String> {
     let mut text = String::new();
     loop {
         match reader.read_event_into(buf) {
@@ -491,7 +995,7 @@ fn read_text(reader: &mut Reader<&[u8]>, buf: &mut Vec<u8>) -> Result<String> {
                 if let Ok(Some(c)) = e.resolve_char_ref() {
                     text.push(c);
                 } else {
-                    match e.as_ref() {
+                    match &*e {
                         b"amp" => text.push('&'),
                         b"lt" => text.push('<'),
                         b"gt" => text.push('>'),
@@ -516,7 +1020,7 @@ fn read_text(reader: &mut Reader<&[u8]>, buf: &mut Vec<u8>) -> Result<String> {
 }
 
 /// Skip over the current element and all its children until the matching end tag.
-fn skip_element(reader: &mut Reader<&[u8]>, buf: &mut Vec<u8>) -> Result<()> {
+fn skip_element<R: BufRead>(reader: &mut GuardedReader<R>, buf: &mut Vec<u8>) -> Result<()> {
     let mut depth: u32 = 1;
     loop {
         match reader.read_event_into(buf) {
@@ -572,7 +1076,10 @@ fn resource_type_to_leader_code(s: &str) -> char {
 }
 
 /// Read the next start-element event, returning owned `StartInfo`. Returns `None` at EOF or end-tag.
-fn next_start(reader: &mut Reader<&[u8]>, buf: &mut Vec<u8>) -> Result<Option<StartInfo>> {
+fn next_start<R: BufRead>(
+    reader: &mut GuardedReader<R>,
+    buf: &mut Vec<u8>,
+) -> Result<Option<StartInfo>> {
     loop {
         match reader.read_event_into(buf) {
             Ok(Event::Start(ref e)) => {
@@ -598,8 +1105,18 @@ fn next_start(reader: &mut Reader<&[u8]>, buf: &mut Vec<u8>) -> Result<Option<St
 ///
 /// Returns an error if the XML is malformed or cannot be parsed.
 pub fn mods_xml_to_record(xml: &str) -> Result<Record> {
-    let mut reader = Reader::from_str(xml);
-    reader.config_mut().trim_text(false);
+    mods_xml_to_record_with(xml, XmlOptions::default())
+}
+
+/// Parse a single MODS XML document with the given XML hardening options.
+///
+/// # Errors
+///
+/// Returns an error if the XML is malformed, cannot be parsed, or breaks
+/// one of the limits in `options`.
+pub fn mods_xml_to_record_with(xml: &str, options: XmlOptions) -> Result<Record> {
+    crate::telemetry::stage_span!("mrrc.convert", from = "mods", to = "marc");
+    let mut reader = GuardedReader::from_str(xml, options);
     let mut buf = Vec::new();
 
     // Advance to the <mods> start element
@@ -626,31 +1143,14 @@ pub fn mods_xml_to_record(xml: &str) -> Result<Record> {
 
 /// Parse a `<modsCollection>` containing multiple MODS records.
 ///
+/// Use [`ModsReader`] to read a collection from a file one record at a time.
+///
 /// # Errors
 ///
 /// Returns an error if the XML is malformed or cannot be parsed.
 pub fn mods_xml_to_records(xml: &str) -> Result<Vec<Record>> {
-    let mut reader = Reader::from_str(xml);
-    reader.config_mut().trim_text(false);
-    let mut buf = Vec::new();
-    let mut records = Vec::new();
-
-    loop {
-        match reader.read_event_into(&mut buf) {
-            Ok(Event::Start(ref e)) => {
-                let local = strip_ns_owned(e.name().as_ref());
-                buf.clear();
-                if local == b"mods" {
-                    records.push(parse_mods_element(&mut reader, &mut buf)?);
-                }
-                // else modsCollection — continue into children
-            },
-            Ok(Event::Eof) => break,
-            Err(e) => return Err(MarcError::invalid_field_msg(format!("XML read: {e}"))),
-            _ => {},
-        }
-        buf.clear();
-    }
+    crate::telemetry::stage_span!("mrrc.convert", from = "mods", to = "marc");
+    let records = ModsReader::new(xml.as_bytes()).read_all()?;
 
     // If called on a single <mods> document, still return it
     if records.is_empty() {
@@ -660,8 +1160,96 @@ pub fn mods_xml_to_records(xml: &str) -> Result<Vec<Record>> {
     Ok(records)
 }
 
+/// Streams records from a `<modsCollection>` document.
+///
+/// Only the `<mods>` element being parsed is held in memory, so a
+/// collection of any size can be read from a [`BufRead`] such as a
+/// buffered file. A document whose root is a single `<mods>` yields that
+/// one record.
+///
+/// # Examples
+///
+/// ```
+/// use mrrc::formats::FormatReader;
+/// use mrrc::mods::ModsReader;
+///
+/// let xml = r#"<modsCollection xmlns="http://www.loc.gov/mods/v3">
+///     <mods><titleInfo><title>First</title></titleInfo></mods>
+///     <mods><titleInfo><title>Second</title></titleInfo></mods>
+/// </modsCollection>"#;
+///
+/// let mut reader = ModsReader::new(xml.as_bytes());
+/// while let Some(record) = reader.read_record()? {
+///     assert!(record.get_field("245").is_some());
+/// }
+/// assert_eq!(reader.records_read(), Some(2));
+/// # Ok::<(), mrrc::MarcError>(())
+/// ```
+#[derive(Debug)]
+pub struct ModsReader<R: BufRead> {
+    reader: GuardedReader<R>,
+    buf: Vec<u8>,
+    records_read: usize,
+}
+
+impl<R: BufRead> ModsReader<R> {
+    /// Create a reader over `reader`.
+    pub fn new(reader: R) -> Self {
+        ModsReader {
+            reader: GuardedReader::from_reader(reader, XmlOptions::default()),
+            buf: Vec::new(),
+            records_read: 0,
+        }
+    }
+
+    /// Set the XML hardening options (default [`XmlOptions::default`]).
+    #[must_use]
+    pub fn with_xml_options(mut self, options: XmlOptions) -> Self {
+        self.reader.set_options(options);
+        self
+    }
+
+    fn read_next(&mut self) -> Result<Option<Record>> {
+        loop {
+            match self.reader.read_event_into(&mut self.buf) {
+                Ok(Event::Start(ref e)) => {
+                    let local = strip_ns_owned(e.name().as_ref());
+                    self.buf.clear();
+                    if local == b"mods" {
+                        let record = parse_mods_element(&mut self.reader, &mut self.buf)?;
+                        self.buf.clear();
+                        self.records_read += 1;
+                        return Ok(Some(record));
+                    }
+                    // else modsCollection — continue into children
+                },
+                Ok(Event::Eof) => return Ok(None),
+                Err(e) => return Err(MarcError::invalid_field_msg(format!("XML read: {e}"))),
+                _ => {},
+            }
+            self.buf.clear();
+        }
+    }
+}
+
+impl<R: BufRead + std::fmt::Debug> FormatReader for ModsReader<R> {
+    /// Errors carry the 1-based index of the record being read.
+    fn read_record(&mut self) -> Result<Option<Record>> {
+        let index = self.records_read + 1;
+        self.read_next()
+            .map_err(|e| e.with_record_index(Some(index)))
+    }
+
+    fn records_read(&self) -> Option<usize> {
+        Some(self.records_read)
+    }
+}
+
 /// Parse the children of a `<mods>` element into a MARC Record.
-fn parse_mods_element(reader: &mut Reader<&[u8]>, buf: &mut Vec<u8>) -> Result<Record> {
+fn parse_mods_element<R: BufRead>(
+    reader: &mut GuardedReader<R>,
+    buf: &mut Vec<u8>,
+) -> Result<Record> {
     let mut record = Record::new(make_default_leader());
     // Track whether we've assigned the primary 1XX entries
     let mut has_100 = false;
@@ -695,7 +1283,7 @@ fn parse_mods_element(reader: &mut Reader<&[u8]>, buf: &mut Vec<u8>) -> Result<R
                     b"subject" => parse_subject(reader, buf, &mut record)?,
                     b"identifier" => parse_identifier(reader, buf, &info, &mut record)?,
                     b"language" => parse_language(reader, buf, &mut record)?,
-                    b"genre" => parse_genre(reader, buf, &mut record)?,
+                    b"genre" => parse_genre(reader, buf, &info, &mut record)?,
                     b"classification" => {
                         parse_classification(reader, buf, &info, &mut record)?;
                     },
@@ -727,8 +1315,8 @@ fn parse_mods_element(reader: &mut Reader<&[u8]>, buf: &mut Vec<u8>) -> Result<R
 }
 
 /// Parse `<titleInfo>` → 245 or 246 depending on `@type`.
-fn parse_title_info(
-    reader: &mut Reader<&[u8]>,
+fn parse_title_info<R: BufRead>(
+    reader: &mut GuardedReader<R>,
     buf: &mut Vec<u8>,
     info: &StartInfo,
     record: &mut Record,
@@ -805,8 +1393,8 @@ fn parse_title_info(
 
 /// Parse `<name>` → 100/110/111/700/710/711 depending on `@type` and role.
 #[allow(clippy::too_many_arguments)]
-fn parse_name(
-    reader: &mut Reader<&[u8]>,
+fn parse_name<R: BufRead>(
+    reader: &mut GuardedReader<R>,
     buf: &mut Vec<u8>,
     info: &StartInfo,
     record: &mut Record,
@@ -913,8 +1501,8 @@ fn parse_name(
 }
 
 /// Parse `<typeOfResource>` → Leader record type.
-fn parse_type_of_resource(
-    reader: &mut Reader<&[u8]>,
+fn parse_type_of_resource<R: BufRead>(
+    reader: &mut GuardedReader<R>,
     buf: &mut Vec<u8>,
     record: &mut Record,
 ) -> Result<()> {
@@ -926,8 +1514,8 @@ fn parse_type_of_resource(
 }
 
 /// Parse `<originInfo>` → 260 (place/publisher/date) and 250 (edition).
-fn parse_origin_info(
-    reader: &mut Reader<&[u8]>,
+fn parse_origin_info<R: BufRead>(
+    reader: &mut GuardedReader<R>,
     buf: &mut Vec<u8>,
     record: &mut Record,
 ) -> Result<()> {
@@ -1019,8 +1607,8 @@ fn parse_origin_info(
 }
 
 /// Parse `<physicalDescription>` → 300.
-fn parse_physical_description(
-    reader: &mut Reader<&[u8]>,
+fn parse_physical_description<R: BufRead>(
+    reader: &mut GuardedReader<R>,
     buf: &mut Vec<u8>,
     record: &mut Record,
 ) -> Result<()> {
@@ -1086,8 +1674,8 @@ fn parse_physical_description(
 }
 
 /// Parse `<abstract>` → 520 $a.
-fn parse_abstract(
-    reader: &mut Reader<&[u8]>,
+fn parse_abstract<R: BufRead>(
+    reader: &mut GuardedReader<R>,
     buf: &mut Vec<u8>,
     record: &mut Record,
 ) -> Result<()> {
@@ -1101,7 +1689,11 @@ fn parse_abstract(
 }
 
 /// Parse `<note>` → 500 $a.
-fn parse_note(reader: &mut Reader<&[u8]>, buf: &mut Vec<u8>, record: &mut Record) -> Result<()> {
+fn parse_note<R: BufRead>(
+    reader: &mut GuardedReader<R>,
+    buf: &mut Vec<u8>,
+    record: &mut Record,
+) -> Result<()> {
     let text = read_text(reader, buf)?;
     if !text.is_empty() {
         let mut field = Field::new("500".to_string(), ' ', ' ');
@@ -1112,7 +1704,11 @@ fn parse_note(reader: &mut Reader<&[u8]>, buf: &mut Vec<u8>, record: &mut Record
 }
 
 /// Parse `<subject>` → 650/651 (topic/geographic).
-fn parse_subject(reader: &mut Reader<&[u8]>, buf: &mut Vec<u8>, record: &mut Record) -> Result<()> {
+fn parse_subject<R: BufRead>(
+    reader: &mut GuardedReader<R>,
+    buf: &mut Vec<u8>,
+    record: &mut Record,
+) -> Result<()> {
     loop {
         match reader.read_event_into(buf) {
             Ok(Event::Start(ref e)) => {
@@ -1162,8 +1758,8 @@ fn parse_subject(reader: &mut Reader<&[u8]>, buf: &mut Vec<u8>, record: &mut Rec
 }
 
 /// Parse `<identifier>` → 020/022/010/024/001 depending on `@type`.
-fn parse_identifier(
-    reader: &mut Reader<&[u8]>,
+fn parse_identifier<R: BufRead>(
+    reader: &mut GuardedReader<R>,
     buf: &mut Vec<u8>,
     info: &StartInfo,
     record: &mut Record,
@@ -1200,7 +1796,9 @@ fn parse_identifier(
             record.add_field(field);
         },
         Some("local") => {
-            record.add_control_field("001".to_string(), text);
+            if record.get_control_field("001").is_none() {
+                record.add_control_field("001".to_string(), text);
+            }
         },
         _ => {
             let mut field = Field::new("024".to_string(), '8', ' ');
@@ -1213,8 +1811,8 @@ fn parse_identifier(
 }
 
 /// Parse `<language>` → 041 $a.
-fn parse_language(
-    reader: &mut Reader<&[u8]>,
+fn parse_language<R: BufRead>(
+    reader: &mut GuardedReader<R>,
     buf: &mut Vec<u8>,
     record: &mut Record,
 ) -> Result<()> {
@@ -1256,25 +1854,42 @@ fn parse_language(
     Ok(())
 }
 
-/// Parse `<genre>` → 655 $a.
-fn parse_genre(reader: &mut Reader<&[u8]>, buf: &mut Vec<u8>, record: &mut Record) -> Result<()> {
+/// Parse `<genre>` → 655 $a, with `@authority` as the thesaurus (ind2 or $2).
+fn parse_genre<R: BufRead>(
+    reader: &mut GuardedReader<R>,
+    buf: &mut Vec<u8>,
+    info: &StartInfo,
+    record: &mut Record,
+) -> Result<()> {
+    let authority = info.attr(b"authority").map(String::from);
     let text = read_text(reader, buf)?;
     if !text.is_empty() {
-        let mut field = Field::new("655".to_string(), ' ', '7');
+        let ind2 = match authority.as_deref() {
+            Some("lcsh") => '0',
+            Some(_) => '7',
+            None => '4',
+        };
+        let mut field = Field::new("655".to_string(), ' ', ind2);
         field.add_subfield('a', text);
+        if ind2 == '7'
+            && let Some(authority) = authority
+        {
+            field.add_subfield('2', authority);
+        }
         record.add_field(field);
     }
     Ok(())
 }
 
 /// Parse `<classification>` → 050/082 depending on `@authority`.
-fn parse_classification(
-    reader: &mut Reader<&[u8]>,
+fn parse_classification<R: BufRead>(
+    reader: &mut GuardedReader<R>,
     buf: &mut Vec<u8>,
     info: &StartInfo,
     record: &mut Record,
 ) -> Result<()> {
     let authority = info.attr(b"authority").map(String::from);
+    let edition = info.attr(b"edition").map(String::from);
     let text = read_text(reader, buf)?;
 
     if text.is_empty() {
@@ -1290,6 +1905,9 @@ fn parse_classification(
         Some("ddc") => {
             let mut field = Field::new("082".to_string(), '0', '4');
             field.add_subfield('a', text);
+            if let Some(edition) = edition {
+                field.add_subfield('2', edition);
+            }
             record.add_field(field);
         },
         _ => {
@@ -1305,42 +1923,48 @@ fn parse_classification(
     Ok(())
 }
 
-/// Parse `<location>` → 856 $u or 852 $a.
-fn parse_location(
-    reader: &mut Reader<&[u8]>,
+/// Parse `<location>` → 856 per `<url>` ($3 from `@displayLabel`, $z from
+/// `@note`) and one 852 for the physical location ($a), sublocations ($b) and
+/// shelf locator ($h).
+fn parse_location<R: BufRead>(
+    reader: &mut GuardedReader<R>,
     buf: &mut Vec<u8>,
     record: &mut Record,
 ) -> Result<()> {
+    let mut holdings = Field::new("852".to_string(), ' ', ' ');
     loop {
         match reader.read_event_into(buf) {
             Ok(Event::Start(ref e)) => {
-                let local = strip_ns_owned(e.name().as_ref());
+                let child = StartInfo::from_event(e);
                 buf.clear();
-                match local.as_slice() {
+                match child.local_name.as_slice() {
                     b"url" => {
                         let text = read_text(reader, buf)?;
                         if !text.is_empty() {
                             let mut field = Field::new("856".to_string(), '4', '0');
+                            if let Some(label) = child.attr(b"displayLabel") {
+                                field.add_subfield('3', label.to_string());
+                            }
                             field.add_subfield('u', text);
+                            if let Some(note) = child.attr(b"note") {
+                                field.add_subfield('z', note.to_string());
+                            }
                             record.add_field(field);
                         }
                     },
                     b"physicalLocation" => {
                         let text = read_text(reader, buf)?;
                         if !text.is_empty() {
-                            let mut field = Field::new("852".to_string(), ' ', ' ');
-                            field.add_subfield('a', text);
-                            record.add_field(field);
+                            holdings.add_subfield('a', text);
                         }
                     },
                     b"shelfLocator" => {
                         let text = read_text(reader, buf)?;
                         if !text.is_empty() {
-                            let mut field = Field::new("852".to_string(), ' ', ' ');
-                            field.add_subfield('h', text);
-                            record.add_field(field);
+                            holdings.add_subfield('h', text);
                         }
                     },
+                    b"holdingSimple" => parse_holding_simple(reader, buf, &mut holdings)?,
                     _ => skip_element(reader, buf)?,
                 }
             },
@@ -1356,46 +1980,116 @@ fn parse_location(
         }
         buf.clear();
     }
+
+    if !holdings.subfields.is_empty() {
+        // Keep the 852 subfields in MARC order: $a, $b, then $h.
+        holdings.subfields.sort_by_key(|s| s.code);
+        record.add_field(holdings);
+    }
+    Ok(())
+}
+
+/// Parse `<holdingSimple>` → 852 $b from each `<subLocation>`.
+fn parse_holding_simple<R: BufRead>(
+    reader: &mut GuardedReader<R>,
+    buf: &mut Vec<u8>,
+    holdings: &mut Field,
+) -> Result<()> {
+    let mut depth: u32 = 1;
+    loop {
+        match reader.read_event_into(buf) {
+            Ok(Event::Start(ref e)) => {
+                let local = strip_ns_owned(e.name().as_ref());
+                buf.clear();
+                if local == b"subLocation" {
+                    let text = read_text(reader, buf)?;
+                    if !text.is_empty() {
+                        holdings.add_subfield('b', text);
+                    }
+                } else {
+                    depth += 1;
+                }
+            },
+            Ok(Event::End(_)) => {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(MarcError::invalid_field_msg(format!("XML read: {e}"))),
+            _ => {},
+        }
+        buf.clear();
+    }
     Ok(())
 }
 
-/// Parse `<relatedItem>` → 773/780/785/830 depending on `@type`.
-fn parse_related_item(
-    reader: &mut Reader<&[u8]>,
+/// Parse `<relatedItem>` → a 76X-78X linking entry, or 800/830 for a series,
+/// depending on `@type`.
+///
+/// The related item's name becomes $a, its title $t (or $a of an 830), ISSN
+/// and ISBN identifiers $x and $z, a local identifier $w, part text $g, and a
+/// series volume number $v.
+fn parse_related_item<R: BufRead>(
+    reader: &mut GuardedReader<R>,
     buf: &mut Vec<u8>,
     info: &StartInfo,
     record: &mut Record,
 ) -> Result<()> {
-    let rel_type = info.attr(b"type");
-    let tag = match rel_type {
-        Some("host") => "773",
-        Some("preceding") => "780",
-        Some("succeeding") => "785",
-        Some("series") => "830",
-        _ => "787",
-    };
+    let rel_type = info.attr(b"type").map(String::from);
 
-    let mut title: Option<String> = None;
+    let mut item = RelatedItem::default();
 
     loop {
         match reader.read_event_into(buf) {
             Ok(Event::Start(ref e)) => {
-                let local = strip_ns_owned(e.name().as_ref());
+                let child = StartInfo::from_event(e);
                 buf.clear();
-                if local == b"titleInfo" {
-                    // Read children to find <title>
-                    while let Some(child) = next_start(reader, buf)? {
-                        if child.local_name == b"title" {
-                            let text = read_text(reader, buf)?;
-                            if !text.is_empty() {
-                                title = Some(text);
+                match child.local_name.as_slice() {
+                    b"titleInfo" => {
+                        // Read children to find <title>
+                        while let Some(inner) = next_start(reader, buf)? {
+                            if inner.local_name == b"title" {
+                                let text = read_text(reader, buf)?;
+                                if !text.is_empty() {
+                                    item.title = Some(text);
+                                }
+                            } else {
+                                skip_element(reader, buf)?;
                             }
-                        } else {
-                            skip_element(reader, buf)?;
                         }
-                    }
-                } else {
-                    skip_element(reader, buf)?;
+                    },
+                    b"name" => {
+                        while let Some(inner) = next_start(reader, buf)? {
+                            if inner.local_name == b"namePart" && inner.attr(b"type").is_none() {
+                                let text = read_text(reader, buf)?;
+                                if !text.is_empty() && item.name.is_none() {
+                                    item.name = Some(text);
+                                }
+                            } else {
+                                skip_element(reader, buf)?;
+                            }
+                        }
+                    },
+                    b"identifier" => {
+                        let code = match child.attr(b"type") {
+                            Some("issn") => Some('x'),
+                            Some("isbn") => Some('z'),
+                            Some("local") => Some('w'),
+                            _ => None,
+                        };
+                        let text = read_text(reader, buf)?;
+                        if let Some(code) = code
+                            && !text.is_empty()
+                        {
+                            item.identifiers.push((code, text));
+                        }
+                    },
+                    b"part" => {
+                        parse_related_part(reader, buf, &mut item.part_text, &mut item.volume)?;
+                    },
+                    _ => skip_element(reader, buf)?,
                 }
             },
             Ok(Event::End(ref e)) => {
@@ -1411,20 +2105,114 @@ fn parse_related_item(
         buf.clear();
     }
 
-    if let Some(t) = title {
-        let ind1 = if tag == "830" { ' ' } else { '0' };
-        let mut field = Field::new(tag.to_string(), ind1, ' ');
-        let sub_code = if tag == "830" { 'a' } else { 't' };
-        field.add_subfield(sub_code, t);
+    if let Some(field) = item.into_field(rel_type.as_deref()) {
         record.add_field(field);
     }
+    Ok(())
+}
+
+/// What [`parse_related_item`] collects from a `<relatedItem>`.
+#[derive(Default)]
+struct RelatedItem {
+    title: Option<String>,
+    name: Option<String>,
+    identifiers: Vec<(char, String)>,
+    part_text: Option<String>,
+    volume: Option<String>,
+}
+
+impl RelatedItem {
+    /// The linking or series field for a related item of `rel_type`, or
+    /// `None` if it has no title.
+    fn into_field(self, rel_type: Option<&str>) -> Option<Field> {
+        let title = self.title?;
+
+        if rel_type == Some("series") {
+            let mut field = if let Some(name) = self.name {
+                let mut field = Field::new("800".to_string(), '1', ' ');
+                field.add_subfield('a', name);
+                field.add_subfield('t', title);
+                field
+            } else {
+                let mut field = Field::new("830".to_string(), ' ', '0');
+                field.add_subfield('a', title);
+                field
+            };
+            if let Some(volume) = self.volume {
+                field.add_subfield('v', volume);
+            }
+            for (code, value) in self
+                .identifiers
+                .into_iter()
+                .filter(|(code, _)| *code == 'x')
+            {
+                field.add_subfield(code, value);
+            }
+            return Some(field);
+        }
+
+        let tag = match rel_type {
+            Some("host") => "773",
+            Some("constituent") => "774",
+            Some("otherVersion") => "775",
+            Some("otherFormat") => "776",
+            Some("preceding") => "780",
+            Some("succeeding") => "785",
+            Some("original") => "786",
+            _ => "787",
+        };
+        let mut field = Field::new(tag.to_string(), '0', ' ');
+        if let Some(name) = self.name {
+            field.add_subfield('a', name);
+        }
+        field.add_subfield('t', title);
+        if let Some(part_text) = self.part_text {
+            field.add_subfield('g', part_text);
+        }
+        for (code, value) in self.identifiers {
+            field.add_subfield(code, value);
+        }
+        Some(field)
+    }
+}
 
+/// Parse the `<part>` of a related item: `<text>` and a volume `<detail>`'s
+/// `<number>`.
+fn parse_related_part<R: BufRead>(
+    reader: &mut GuardedReader<R>,
+    buf: &mut Vec<u8>,
+    text: &mut Option<String>,
+    volume: &mut Option<String>,
+) -> Result<()> {
+    while let Some(child) = next_start(reader, buf)? {
+        match child.local_name.as_slice() {
+            b"text" => {
+                let value = read_text(reader, buf)?;
+                if !value.is_empty() {
+                    *text = Some(value);
+                }
+            },
+            b"detail" => {
+                while let Some(inner) = next_start(reader, buf)? {
+                    if inner.local_name == b"number" {
+                        let value = read_text(reader, buf)?;
+                        if !value.is_empty() {
+                            *volume = Some(value);
+                        }
+                    } else {
+                        skip_element(reader, buf)?;
+                    }
+                }
+            },
+            _ => skip_element(reader, buf)?,
+        }
+    }
     Ok(())
 }
 
-/// Parse `<recordInfo>` → 001, 003, 040.
-fn parse_record_info(
-    reader: &mut Reader<&[u8]>,
+/// Parse `<recordInfo>` → 001, 003, 005, 040.
+fn parse_record_info<R: BufRead>(
+    reader: &mut GuardedReader<R>,
     buf: &mut Vec<u8>,
     record: &mut Record,
 ) -> Result<()> {
@@ -1437,7 +2225,7 @@ fn parse_record_info(
                     b"recordIdentifier" => {
                         let source = child.attr(b"source").map(String::from);
                         let text = read_text(reader, buf)?;
-                        if !text.is_empty() {
+                        if !text.is_empty() && record.get_control_field("001").is_none() {
                             record.add_control_field("001".to_string(), text);
                         }
                         if let Some(src) = source
@@ -1449,9 +2237,13 @@ fn parse_record_info(
                     b"recordContentSource" => {
                         let text = read_text(reader, buf)?;
                         if !text.is_empty() {
-                            let mut field = Field::new("040".to_string(), ' ', ' ');
-                            field.add_subfield('a', text);
-                            record.add_field(field);
+                            add_cataloging_source(record, 'a', text);
+                        }
+                    },
+                    b"recordChangeDate" => {
+                        let text = read_text(reader, buf)?;
+                        if !text.is_empty() && record.get_control_field("005").is_none() {
+                            record.add_control_field("005".to_string(), text);
                         }
                     },
                     b"languageOfCataloging" => {
@@ -1459,9 +2251,7 @@ fn parse_record_info(
                             if inner.local_name == b"languageTerm" {
                                 let text = read_text(reader, buf)?;
                                 if !text.is_empty() {
-                                    let mut field = Field::new("040".to_string(), ' ', ' ');
-                                    field.add_subfield('b', text);
-                                    record.add_field(field);
+                                    add_cataloging_source(record, 'b', text);
                                 }
                             } else {
                                 skip_element(reader, buf)?;
@@ -1486,9 +2276,24 @@ fn parse_record_info(
     Ok(())
 }
 
+/// Add a subfield to the record's 040, creating it if needed.
+fn add_cataloging_source(record: &mut Record, code: char, value: String) {
+    if let Some(field) = record
+        .get_fields_mut("040")
+        .and_then(|fields| fields.first_mut())
+    {
+        field.add_subfield(code, value);
+        field.subfields.sort_by_key(|s| s.code);
+    } else {
+        let mut field = Field::new("040".to_string(), ' ', ' ');
+        field.add_subfield(code, value);
+        record.add_field(field);
+    }
+}
+
 /// Parse `<accessCondition>` → 506 or 540.
-fn parse_access_condition(
-    reader: &mut Reader<&[u8]>,
+fn parse_access_condition<R: BufRead>(
+    reader: &mut GuardedReader<R>,
     buf: &mut Vec<u8>,
     info: &StartInfo,
     record: &mut Record,
@@ -1513,8 +2318,8 @@ fn parse_access_condition(
 }
 
 /// Parse `<tableOfContents>` → 505 $a.
-fn parse_table_of_contents(
-    reader: &mut Reader<&[u8]>,
+fn parse_table_of_contents<R: BufRead>(
+    reader: &mut GuardedReader<R>,
     buf: &mut Vec<u8>,
     record: &mut Record,
 ) -> Result<()> {
@@ -1528,8 +2333,8 @@ fn parse_table_of_contents(
 }
 
 /// Parse `<targetAudience>` → 521 $a.
-fn parse_target_audience(
-    reader: &mut Reader<&[u8]>,
+fn parse_target_audience<R: BufRead>(
+    reader: &mut GuardedReader<R>,
     buf: &mut Vec<u8>,
     record: &mut Record,
 ) -> Result<()> {
@@ -2157,7 +2962,271 @@ mod tests {
         let subj = restored.get_fields("650").unwrap();
         assert_eq!(subj[0].get_subfield('a'), Some("Rust programming"));
 
-        // Control number roundtrips via identifier type="local"
+        // Control number is written as both a local identifier and the
+        // recordInfo/recordIdentifier, and read back once
+        assert!(mods_xml.contains("<mods:identifier type=\"local\">test123</mods:identifier>"));
+        assert!(mods_xml.contains("<mods:recordIdentifier>test123</mods:recordIdentifier>"));
         assert_eq!(restored.get_control_field("001"), Some("test123"));
+        assert_eq!(restored.control_fields["001"].len(), 1);
+    }
+
+    fn full_record() -> Record {
+        let mut record = Record::new(make_test_leader());
+        record.add_control_field("001".to_string(), "ocm123".to_string());
+        record.add_control_field("003".to_string(), "OCoLC".to_string());
+        record.add_control_field("005".to_string(), "20240102030405.0".to_string());
+        record.add_control_field(
+            "008".to_string(),
+            "240102s2024    nyu           000 0 eng d".to_string(),
+        );
+        let fields = [
+            (
+                "040",
+                ' ',
+                ' ',
+                vec![('a', "DLC"), ('b', "eng"), ('c', "DLC")],
+            ),
+            (
+                "050",
+                ' ',
+                '0',
+                vec![('a', "QA76.73.R87"), ('b', "S65 2024")],
+            ),
+            ("082", '0', '4', vec![('a', "005.133"), ('2', "23")]),
+            ("245", '1', '0', vec![('a', "Rust in depth")]),
+            (
+                "490",
+                '1',
+                ' ',
+                vec![('a', "Systems series"), ('v', "v. 4")],
+            ),
+            ("505", '0', ' ', vec![('a', "Ownership -- Lifetimes")]),
+            ("655", ' ', '7', vec![('a', "Textbooks."), ('2', "lcgft")]),
+            (
+                "773",
+                '0',
+                ' ',
+                vec![
+                    ('t', "Journal of Systems"),
+                    ('g', "Vol. 2"),
+                    ('x', "1234-5678"),
+                ],
+            ),
+            (
+                "776",
+                '0',
+                '8',
+                vec![
+                    ('a', "Smith, Jane."),
+                    ('t', "Rust in depth"),
+                    ('z', "9780000000002"),
+                ],
+            ),
+            ("780", '0', '0', vec![('t', "Rust basics")]),
+            (
+                "830",
+                ' ',
+                '0',
+                vec![('a', "Systems series."), ('v', "v. 4")],
+            ),
+            (
+                "852",
+                ' ',
+                ' ',
+                vec![
+                    ('a', "MIT"),
+                    ('b', "Stacks"),
+                    ('h', "QA76.73"),
+                    ('i', ".R87"),
+                ],
+            ),
+            (
+                "856",
+                '4',
+                '1',
+                vec![
+                    ('3', "Table of contents"),
+                    ('u', "https://example.org/toc"),
+                    ('z', "Free"),
+                ],
+            ),
+        ];
+        for (tag, ind1, ind2, subfields) in fields {
+            let mut field = Field::new(tag.to_string(), ind1, ind2);
+            for (code, value) in subfields {
+                field.add_subfield(code, value.to_string());
+            }
+            record.add_field(field);
+        }
+
+        record
+    }
+
+    #[test]
+    fn test_mods_write_full_mapping() {
+        let xml = record_to_mods_xml(&full_record()).unwrap();
+        assert!(xml.contains(
+            "<mods:classification authority=\"lcc\">QA76.73.R87 S65 2024</mods:classification>"
+        ));
+        assert!(xml.contains(
+            "<mods:classification authority=\"ddc\" edition=\"23\">005.133</mods:classification>"
+        ));
+        assert!(xml.contains("<mods:genre authority=\"lcgft\">Textbooks.</mods:genre>"));
+        assert!(
+            xml.contains("<mods:tableOfContents>Ownership -- Lifetimes</mods:tableOfContents>")
+        );
+        assert!(xml.contains("<mods:relatedItem type=\"otherFormat\">"));
+        assert!(xml.contains("<mods:shelfLocator>QA76.73 .R87</mods:shelfLocator>"));
+        assert!(xml.contains("<mods:subLocation>Stacks</mods:subLocation>"));
+        assert!(xml.contains("<mods:url displayLabel=\"Table of contents\" note=\"Free\">"));
+        assert!(
+            xml.contains("<mods:recordIdentifier source=\"OCoLC\">ocm123</mods:recordIdentifier>")
+        );
+        assert!(xml.contains("<mods:identifier type=\"local\">ocm123</mods:identifier>"));
+        assert!(xml.contains(
+            "<mods:recordCreationDate encoding=\"marc\">240102</mods:recordCreationDate>"
+        ));
+        assert_eq!(xml.matches("<mods:relatedItem type=\"series\">").count(), 2);
+    }
+
+    #[test]
+    fn test_mods_full_mapping_roundtrip() {
+        let xml = record_to_mods_xml(&full_record()).unwrap();
+        let restored = mods_xml_to_record(&xml).unwrap();
+        assert_eq!(restored.get_control_field("001"), Some("ocm123"));
+        assert_eq!(restored.get_control_field("003"), Some("OCoLC"));
+        assert_eq!(restored.get_control_field("005"), Some("20240102030405.0"));
+
+        let f040 = restored.get_fields("040").unwrap();
+        assert_eq!(f040.len(), 1);
+        assert_eq!(f040[0].get_subfield('a'), Some("DLC"));
+        assert_eq!(f040[0].get_subfield('b'), Some("eng"));
+
+        assert_eq!(
+            restored.get_field("082").unwrap().get_subfield('2'),
+            Some("23")
+        );
+        let genre = restored.get_field("655").unwrap();
+        assert_eq!(genre.indicator2, '7');
+        assert_eq!(genre.get_subfield('2'), Some("lcgft"));
+        assert_eq!(
+            restored.get_field("505").unwrap().get_subfield('a'),
+            Some("Ownership -- Lifetimes")
+        );
+    }
+
+    #[test]
+    fn test_mods_related_item_and_location_roundtrip() {
+        let xml = record_to_mods_xml(&full_record()).unwrap();
+        let restored = mods_xml_to_record(&xml).unwrap();
+
+        let host = restored.get_field("773").unwrap();
+        assert_eq!(host.get_subfield('t'), Some("Journal of Systems"));
+        assert_eq!(host.get_subfield('g'), Some("Vol. 2"));
+        assert_eq!(host.get_subfield('x'), Some("1234-5678"));
+        let other_format = restored.get_field("776").unwrap();
+        assert_eq!(other_format.get_subfield('a'), Some("Smith, Jane."));
+        assert_eq!(other_format.get_subfield('z'), Some("9780000000002"));
+        assert!(restored.get_field("780").is_some());
+
+        let series = restored.get_fields("830").unwrap();
+        assert_eq!(series.len(), 2);
+        assert_eq!(series[0].get_subfield('v'), Some("v. 4"));
+
+        let holdings = restored.get_fields("852").unwrap();
+        assert_eq!(holdings.len(), 1);
+        assert_eq!(holdings[0].get_subfield('a'), Some("MIT"));
+        assert_eq!(holdings[0].get_subfield('b'), Some("Stacks"));
+        assert_eq!(holdings[0].get_subfield('h'), Some("QA76.73 .R87"));
+
+        let url = restored.get_field("856").unwrap();
+        assert_eq!(url.get_subfield('3'), Some("Table of contents"));
+        assert_eq!(url.get_subfield('u'), Some("https://example.org/toc"));
+        assert_eq!(url.get_subfield('z'), Some("Free"));
+    }
+
+    #[test]
+    fn test_mods_writer_reader_stream() {
+        let mut writer = ModsWriter::new(Vec::new());
+        for i in 0..3 {
+            let mut record = full_record();
+            record.control_fields.shift_remove("001");
+            record.add_control_field("001".to_string(), format!("rec{i}"));
+            writer.write_record(&record).unwrap();
+        }
+        writer.finish().unwrap();
+        assert_eq!(writer.records_written(), Some(3));
+        assert!(writer.write_record(&full_record()).is_err());
+        let xml = writer.into_inner();
+
+        let mut reader = ModsReader::new(std::io::BufReader::with_capacity(64, xml.as_slice()));
+        let mut ids = Vec::new();
+        while let Some(record) = reader.read_record().unwrap() {
+            assert_eq!(
+                record.get_field("245").unwrap().get_subfield('a'),
+                Some("Rust in depth")
+            );
+            ids.push(record.get_control_field("001").unwrap().to_string());
+        }
+        assert_eq!(ids, ["rec0", "rec1", "rec2"]);
+        assert_eq!(reader.records_read(), Some(3));
+    }
+
+    #[test]
+    fn test_mods_writer_empty_collection() {
+        let mut writer = ModsWriter::new(Vec::new());
+        writer.finish().unwrap();
+        let xml = String::from_utf8(writer.into_inner()).unwrap();
+        assert!(xml.contains("<modsCollection"));
+        assert!(xml.ends_with("</modsCollection>\n"));
+        assert!(
+            ModsReader::new(xml.as_bytes())
+                .read_record()
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_mods_custom_crosswalk() {
+        let mut record = full_record();
+        record.add_field(
+            Field::builder("506".to_string(), ' ', ' ')
+                .subfield_str('a', "Staff only")
+                .build(),
+        );
+        record.add_field(
+            Field::builder("650".to_string(), ' ', '0')
+                .subfield_str('a', "Cataloging")
+                .build(),
+        );
+        record.add_field(
+            Field::builder("500".to_string(), ' ', ' ')
+                .subfield_str('a', "A note")
+                .build(),
+        );
+        let overrides = Crosswalk::from_json(
+            r#"{"name": "local", "omit": ["note"], "mapping": [
+                {"element": "subject[authority=local]/topic", "source": "650$a"}
+            ]}"#,
+        )
+        .unwrap();
+        let crosswalk = Crosswalk::mods().with_overrides(overrides);
+        let xml = record_to_mods_xml_with(&record, &crosswalk).unwrap();
+        assert!(!xml.contains("<mods:note>"));
+        assert!(xml.contains("  <mods:subject authority=\"local\">\n    <mods:topic>"));
+        assert!(xml.contains(
+            "<mods:accessCondition type=\"restriction on access\">Staff only</mods:accessCondition>"
+        ));
+
+        let parsed = mods_xml_to_record(&xml).unwrap();
+        assert_eq!(
+            parsed.get_field("506").unwrap().get_subfield('a'),
+            Some("Staff only")
+        );
+
+        let bad = Crosswalk::new("bad")
+            .mapping(crate::crosswalk::Mapping::new("note[type", "500$a").unwrap());
+        assert!(record_to_mods_xml_with(&record, &bad).is_err());
     }
 }
@@ -0,0 +1,398 @@
+//! Detecting and repairing double-encoded text.
+//!
+//! Records that passed through a legacy ILS often carry text that was
+//! decoded with the wrong character set somewhere along the way. Three
+//! patterns account for most of it:
+//!
+//! - [`MojibakeKind::Latin1DoubleEncoded`]: UTF-8 read as Latin-1 (or
+//!   Windows-1252) and encoded to UTF-8 again, so `café` arrives as
+//!   `cafÃ©`. Repaired wherever a run of Latin-1 characters spells out a
+//!   valid UTF-8 sequence; text that went through this twice is repaired
+//!   twice.
+//! - [`MojibakeKind::Marc8DeclaredUtf8`]: UTF-8 bytes in a record whose
+//!   leader/09 declares MARC-8, decoded through the MARC-8 tables.
+//!   Repaired when the value maps back to the exact bytes it was decoded
+//!   from and those bytes are UTF-8 with at least one non-ASCII character.
+//! - [`MojibakeKind::NumericCharacterReference`]: `&#x4E00;` or `&#19968;`
+//!   left in the data by a lossless MARC-8 conversion or an XML export,
+//!   replaced with the character it names.
+//!
+//! [`detect_mojibake`] reports what [`repair_mojibake`] would change
+//! without changing anything.
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::mojibake::{repair_mojibake, MojibakeKind};
+//! use mrrc::{Field, Leader, Record};
+//!
+//! let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+//! record.add_control_field_str("001", "ocm42");
+//! record.add_field(
+//!     Field::builder("245".to_string(), '1', '0')
+//!         .subfield_str('a', "Caf\u{c3}\u{a9} &#x4E00;")
+//!         .build(),
+//! );
+//!
+//! let report = repair_mojibake(&mut record);
+//! assert_eq!(report.control_number.as_deref(), Some("ocm42"));
+//! assert_eq!(report.fixes.len(), 2);
+//! assert_eq!(report.fixes[0].kind, MojibakeKind::Latin1DoubleEncoded);
+//! assert_eq!(
+//!     record.get_field("245").unwrap().get_subfield('a'),
+//!     Some("Café 一")
+//! );
+//! ```
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use unicode_normalization::UnicodeNormalization;
+
+use crate::encoding::{MarcEncoding, decode_bytes};
+use crate::encoding_validation::windows_1252;
+use crate::marc8_tables::{CharacterSetId, get_charset_table};
+use crate::record::Record;
+
+/// Times a value is run through the Latin-1 repair, enough for text that
+/// was double-encoded more than once.
+const LATIN1_PASSES: usize = 3;
+
+/// Kind of double encoding found.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MojibakeKind {
+    /// UTF-8 read as Latin-1 or Windows-1252 and re-encoded
+    Latin1DoubleEncoded,
+    /// UTF-8 content in a record declared as MARC-8
+    Marc8DeclaredUtf8,
+    /// Numeric character reference such as `&#x4E00;`
+    NumericCharacterReference,
+}
+
+/// One repaired value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MojibakeFix {
+    /// Field tag
+    pub tag: String,
+    /// Subfield code, or `None` for a control field
+    pub subfield_code: Option<char>,
+    /// Pattern that was repaired
+    pub kind: MojibakeKind,
+    /// Value before this repair
+    pub original: String,
+    /// Value after this repair
+    pub repaired: String,
+}
+
+/// What [`detect_mojibake`] or [`repair_mojibake`] found in one record.
+///
+/// A value with more than one pattern gets one fix per pattern, in the
+/// order they were repaired (MARC-8, then Latin-1, then character
+/// references), each starting from the previous one's result.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MojibakeReport {
+    /// The record's 001, to tie the report back to it
+    pub control_number: Option<String>,
+    /// Repairs in field order
+    pub fixes: Vec<MojibakeFix>,
+}
+
+impl MojibakeReport {
+    /// Whether no double encoding was found.
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.fixes.is_empty()
+    }
+
+    /// Number of fixes of one kind.
+    #[must_use]
+    pub fn count(&self, kind: MojibakeKind) -> usize {
+        self.fixes.iter().filter(|fix| fix.kind == kind).count()
+    }
+}
+
+/// Report the double encoding in `record` without changing it.
+#[must_use]
+pub fn detect_mojibake(record: &Record) -> MojibakeReport {
+    let mut copy = record.clone();
+    repair_mojibake(&mut copy)
+}
+
+/// Repair the double encoding in `record`, returning what was changed.
+///
+/// The MARC-8 check only runs when leader/09 declares MARC-8; the leader
+/// itself is left alone, since values are Unicode either way once read.
+pub fn repair_mojibake(record: &mut Record) -> MojibakeReport {
+    let marc8 = record.leader.character_coding != 'a';
+    let mut report = MojibakeReport {
+        control_number: record.get_control_field("001").map(str::to_string),
+        fixes: Vec::new(),
+    };
+    for (tag, values) in &mut record.control_fields {
+        for value in values {
+            repair_value(value, marc8, |kind, original, repaired| {
+                report.fixes.push(MojibakeFix {
+                    tag: tag.clone(),
+                    subfield_code: None,
+                    kind,
+                    original,
+                    repaired,
+                });
+            });
+        }
+    }
+    for (tag, fields) in &mut record.fields {
+        for subfield in fields.iter_mut().flat_map(|field| &mut field.subfields) {
+            let code = subfield.code;
+            repair_value(&mut subfield.value, marc8, |kind, original, repaired| {
+                report.fixes.push(MojibakeFix {
+                    tag: tag.clone(),
+                    subfield_code: Some(code),
+                    kind,
+                    original,
+                    repaired,
+                });
+            });
+        }
+    }
+    report
+}
+
+/// Run each repair over one value, reporting the ones that changed it.
+fn repair_value(
+    value: &mut String,
+    marc8: bool,
+    mut report: impl FnMut(MojibakeKind, String, String),
+) {
+    let mut apply = |kind, repaired: Option<String>, value: &mut String| {
+        if let Some(repaired) = repaired {
+            report(kind, std::mem::replace(value, repaired.clone()), repaired);
+        }
+    };
+    if marc8 {
+        let repaired = repair_marc8(value);
+        apply(MojibakeKind::Marc8DeclaredUtf8, repaired, value);
+    }
+    let repaired = repair_latin1(value);
+    apply(MojibakeKind::Latin1DoubleEncoded, repaired, value);
+    let repaired = repair_references(value);
+    apply(MojibakeKind::NumericCharacterReference, repaired, value);
+}
+
+/// Undo Latin-1 double encoding, or `None` if there is none.
+fn repair_latin1(value: &str) -> Option<String> {
+    let mut current = value.to_string();
+    let mut changed = false;
+    for _ in 0..LATIN1_PASSES {
+        match repair_latin1_once(&current) {
+            Some(repaired) => {
+                current = repaired;
+                changed = true;
+            },
+            None => break,
+        }
+    }
+    changed.then_some(current)
+}
+
+fn repair_latin1_once(value: &str) -> Option<String> {
+    let chars: Vec<char> = value.chars().collect();
+    let mut repaired = String::with_capacity(value.len());
+    let mut changed = false;
+    let mut i = 0;
+    while i < chars.len() {
+        if let Some((c, len)) = utf8_sequence_at(&chars[i..]) {
+            repaired.push(c);
+            changed = true;
+            i += len;
+        } else {
+            repaired.push(chars[i]);
+            i += 1;
+        }
+    }
+    changed.then_some(repaired)
+}
+
+/// A non-ASCII character spelled out as Latin-1 at the start of `chars`,
+/// with the number of characters it spans.
+fn utf8_sequence_at(chars: &[char]) -> Option<(char, usize)> {
+    let lead = latin1_byte(*chars.first()?)?;
+    let len = match lead {
+        0xC2..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        0xF0..=0xF4 => 4,
+        _ => return None,
+    };
+    let bytes = chars
+        .get(..len)?
+        .iter()
+        .map(|&c| latin1_byte(c))
+        .collect::<Option<Vec<u8>>>()?;
+    let decoded = std::str::from_utf8(&bytes).ok()?;
+    decoded.chars().next().map(|c| (c, len))
+}
+
+/// The byte a character had before a Latin-1 or Windows-1252 decode.
+fn latin1_byte(c: char) -> Option<u8> {
+    match u32::from(c) {
+        0x80..=0xFF => u8::try_from(u32::from(c)).ok(),
+        _ => (0x80..=0x9F).find(|&b| windows_1252(char::from(b)) == Some(c)),
+    }
+}
+
+/// Bytes for the characters of the default MARC-8 sets (ASCII in G0 and
+/// ANSEL in G1), which is all a stray UTF-8 byte can decode to.
+static MARC8_DEFAULT_BYTES: LazyLock<HashMap<char, u8>> = LazyLock::new(|| {
+    let mut bytes = HashMap::new();
+    for set in [
+        CharacterSetId::BasicLatin,
+        CharacterSetId::AnselExtendedLatin,
+    ] {
+        for (&byte, &(unicode, _)) in get_charset_table(set) {
+            if let Some(c) = char::from_u32(unicode) {
+                bytes.entry(c).or_insert(byte);
+            }
+        }
+    }
+    bytes
+});
+
+/// Recover UTF-8 text that was decoded as MARC-8, or `None` if the value
+/// does not map back to UTF-8 exactly.
+fn repair_marc8(value: &str) -> Option<String> {
+    if value.is_ascii() {
+        return None;
+    }
+    // MARC-8 decoding composes combining marks; decompose to get the
+    // characters back one per byte.
+    let bytes = value
+        .nfd()
+        .map(|c| match c {
+            '\n' | '\r' => Some(c as u8),
+            _ => MARC8_DEFAULT_BYTES.get(&c).copied(),
+        })
+        .collect::<Option<Vec<u8>>>()?;
+    let utf8 = String::from_utf8(bytes.clone()).ok()?;
+    if utf8.is_ascii() || decode_bytes(&bytes, MarcEncoding::Marc8).ok()? != value {
+        return None;
+    }
+    Some(utf8)
+}
+
+/// Replace numeric character references, or `None` if there are none.
+fn repair_references(value: &str) -> Option<String> {
+    let mut repaired = String::with_capacity(value.len());
+    let mut rest = value;
+    let mut changed = false;
+    while let Some(start) = rest.find("&#") {
+        repaired.push_str(&rest[..start]);
+        rest = &rest[start..];
+        if let Some((c, len)) = parse_reference(rest) {
+            repaired.push(c);
+            rest = &rest[len..];
+            changed = true;
+        } else {
+            repaired.push_str("&#");
+            rest = &rest[2..];
+        }
+    }
+    repaired.push_str(rest);
+    changed.then_some(repaired)
+}
+
+/// The character named by the reference at the start of `s`, with the
+/// reference's length. Control characters are not decoded.
+fn parse_reference(s: &str) -> Option<(char, usize)> {
+    let body = &s[2..];
+    let end = body.find(';')?;
+    let digits = &body[..end];
+    let code = match digits.strip_prefix(['x', 'X']) {
+        Some(hex) if !hex.is_empty() && hex.len() <= 6 => u32::from_str_radix(hex, 16).ok()?,
+        None if !digits.is_empty() && digits.len() <= 7 => digits.parse().ok()?,
+        _ => return None,
+    };
+    let c = char::from_u32(code).filter(|c| !c.is_control())?;
+    Some((c, 2 + end + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::leader::Leader;
+    use crate::record::Field;
+
+    fn record(leader: &[u8], value: &str) -> Record {
+        let mut record = Record::new(Leader::from_bytes(leader).unwrap());
+        record.add_control_field_str("001", "r1");
+        record.add_field(
+            Field::builder("245".to_string(), '1', '0')
+                .subfield_str('a', value)
+                .build(),
+        );
+        record
+    }
+
+    fn title(record: &Record) -> &str {
+        record.get_field("245").unwrap().get_subfield('a').unwrap()
+    }
+
+    #[test]
+    fn test_latin1_double_encoding() {
+        let mut rec = record(
+            b"00000nam a2200000 a 4500",
+            "Fran\u{c3}\u{a7}ais \u{e2}\u{20ac}\u{2122}",
+        );
+        let report = repair_mojibake(&mut rec);
+        assert_eq!(title(&rec), "Français \u{2019}");
+        assert_eq!(report.count(MojibakeKind::Latin1DoubleEncoded), 1);
+        assert_eq!(report.fixes[0].subfield_code, Some('a'));
+
+        // Encoded twice over.
+        let mut rec = record(b"00000nam a2200000 a 4500", "caf\u{c3}\u{83}\u{c2}\u{a9}");
+        repair_mojibake(&mut rec);
+        assert_eq!(title(&rec), "café");
+
+        // Genuine Latin-1 text is left alone.
+        let mut rec = record(b"00000nam a2200000 a 4500", "Ã la carte, São Paulo");
+        assert!(repair_mojibake(&mut rec).is_clean());
+    }
+
+    #[test]
+    fn test_marc8_declared_utf8() {
+        let utf8 = "café";
+        let decoded = decode_bytes(utf8.as_bytes(), MarcEncoding::Marc8).unwrap();
+        assert_ne!(decoded, utf8);
+
+        let mut rec = record(b"00000nam  2200000 a 4500", &decoded);
+        let report = detect_mojibake(&rec);
+        assert_eq!(report.count(MojibakeKind::Marc8DeclaredUtf8), 1);
+        assert_eq!(title(&rec), decoded);
+
+        repair_mojibake(&mut rec);
+        assert_eq!(title(&rec), utf8);
+
+        // The same text under a UTF-8 leader is not second-guessed.
+        let rec = record(b"00000nam a2200000 a 4500", &decoded);
+        assert_eq!(
+            detect_mojibake(&rec).count(MojibakeKind::Marc8DeclaredUtf8),
+            0
+        );
+    }
+
+    #[test]
+    fn test_numeric_character_references() {
+        let mut rec = record(
+            b"00000nam a2200000 a 4500",
+            "&#x4E00;&#20108; &amp; &#x1B; &#xZZ; &#",
+        );
+        let report = repair_mojibake(&mut rec);
+        assert_eq!(title(&rec), "一二 &amp; &#x1B; &#xZZ; &#");
+        assert_eq!(report.fixes.len(), 1);
+        assert_eq!(
+            report.fixes[0].original,
+            "&#x4E00;&#20108; &amp; &#x1B; &#xZZ; &#"
+        );
+        assert_eq!(report.control_number.as_deref(), Some("r1"));
+    }
+}
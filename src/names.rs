@@ -0,0 +1,527 @@
+//! Personal and corporate name headings as structured names.
+//!
+//! [`PersonalName::parse_from_field`] splits an X00 heading (100, 600, 700,
+//! 800) into surname, forename, numeration, titles, fuller form, dates and
+//! relators; [`PersonalName::parse`] does the same for a heading string such
+//! as `Fitzgerald, F. Scott (Francis Scott), 1896-1940.`
+//! [`CorporateName::parse_from_field`] reads an X10 heading (110, 610, 710,
+//! 810) as a name followed by its subordinate units.
+//!
+//! Both produce three forms:
+//!
+//! - `heading()` — the inverted catalog form, `Fitzgerald, F. Scott, 1896-1940`
+//! - `display_form()` — natural order for display, `F. Scott Fitzgerald`
+//! - `sort_form()` — a browse key (see [`sort_key`])
+//!
+//! ISBD punctuation is removed from every part.
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::names::PersonalName;
+//! use mrrc::Field;
+//!
+//! let field = Field::builder("100".to_string(), '1', ' ')
+//!     .subfield_str('a', "Fitzgerald, F. Scott")
+//!     .subfield_str('q', "(Francis Scott),")
+//!     .subfield_str('d', "1896-1940,")
+//!     .subfield_str('e', "author.")
+//!     .build();
+//! let name = PersonalName::parse_from_field(&field).unwrap();
+//!
+//! assert_eq!(name.surname.as_deref(), Some("Fitzgerald"));
+//! assert_eq!(name.forename.as_deref(), Some("F. Scott"));
+//! assert_eq!(name.fuller_form.as_deref(), Some("Francis Scott"));
+//! assert_eq!(name.birth_year(), Some(1896));
+//! assert_eq!(name.display_form(), "F. Scott Fitzgerald");
+//! assert_eq!(name.heading(), "Fitzgerald, F. Scott (Francis Scott), 1896-1940");
+//! assert_eq!(PersonalName::parse(&name.heading()), PersonalName { relators: vec![], ..name });
+//! ```
+
+use crate::normalize::sort_key;
+use crate::punctuation::strip_value;
+use crate::record::Field;
+
+/// The form of a personal name's entry element (X00 first indicator).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum PersonalNameKind {
+    /// Entered under forename (0): `John II, King of France`.
+    Forename,
+    /// Entered under surname (1): `Fitzgerald, F. Scott`.
+    #[default]
+    Surname,
+    /// A family name (3): `Kennedy family`.
+    Family,
+}
+
+impl PersonalNameKind {
+    /// Decode an X00 first indicator; unknown values read as surname.
+    #[must_use]
+    pub fn from_indicator(indicator: char) -> Self {
+        match indicator {
+            '0' => PersonalNameKind::Forename,
+            '3' => PersonalNameKind::Family,
+            _ => PersonalNameKind::Surname,
+        }
+    }
+
+    /// The X00 first indicator for this kind.
+    #[must_use]
+    pub fn indicator(self) -> char {
+        match self {
+            PersonalNameKind::Forename => '0',
+            PersonalNameKind::Surname => '1',
+            PersonalNameKind::Family => '3',
+        }
+    }
+}
+
+/// A personal name heading.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PersonalName {
+    /// Form of the entry element.
+    pub kind: PersonalNameKind,
+    /// Surname, or the family name; `None` for names entered under forename.
+    pub surname: Option<String>,
+    /// Forenames or initials.
+    pub forename: Option<String>,
+    /// Roman numeral (`$b`), such as `II`.
+    pub numeration: Option<String>,
+    /// Titles and other words associated with the name (`$c`).
+    pub titles: Vec<String>,
+    /// Fuller form of the name (`$q`), without parentheses.
+    pub fuller_form: Option<String>,
+    /// Dates associated with the name (`$d`), such as `1896-1940`.
+    pub dates: Option<String>,
+    /// Relator terms (`$e`).
+    pub relators: Vec<String>,
+}
+
+impl PersonalName {
+    /// Parse an X00 field; `None` if it has no `$a`.
+    #[must_use]
+    pub fn parse_from_field(field: &Field) -> Option<Self> {
+        let kind = PersonalNameKind::from_indicator(field.indicator1);
+        let mut name = PersonalName {
+            kind,
+            ..Self::default()
+        };
+        name.set_entry_element(&clean(field.get_subfield('a')?));
+        for subfield in &field.subfields {
+            let value = clean(&subfield.value);
+            if value.is_empty() {
+                continue;
+            }
+            match subfield.code {
+                'b' => name.numeration = Some(value),
+                'c' => name.titles.push(value),
+                'q' => name.fuller_form = Some(unparenthesize(&value).to_string()),
+                'd' => name.dates = Some(value),
+                'e' => name.relators.push(value),
+                _ => {},
+            }
+        }
+        Some(name)
+    }
+
+    /// Parse a heading string in catalog form.
+    ///
+    /// The first comma separates surname from forename. A name ending in a
+    /// roman numeral (`John II`), or of several words with nothing after
+    /// it, is taken as entered under forename. A parenthesized part is the fuller form, a
+    /// later part with digits is the dates and any other later part is a
+    /// title.
+    #[must_use]
+    pub fn parse(heading: &str) -> Self {
+        let heading = clean(heading);
+        let mut name = PersonalName::default();
+        let mut parts = heading.split(", ").map(str::trim).filter(|p| !p.is_empty());
+        let Some(first) = parts.next() else {
+            return name;
+        };
+
+        let (first, fuller) = split_fuller_form(first);
+        name.fuller_form = fuller;
+        let numbered = first
+            .rsplit_once(' ')
+            .filter(|(_, numeral)| is_roman_numeral(numeral));
+        if let Some((forename, numeral)) = numbered {
+            name.kind = PersonalNameKind::Forename;
+            name.forename = Some(forename.to_string());
+            name.numeration = Some(numeral.to_string());
+        } else if first.contains(' ') && !heading.contains(", ") {
+            name.kind = PersonalNameKind::Forename;
+            name.forename = Some(first.to_string());
+        } else {
+            name.surname = Some(first.to_string());
+            if let Some(second) = parts.next() {
+                let (forename, fuller) = split_fuller_form(second);
+                name.forename = Some(forename.to_string());
+                if fuller.is_some() {
+                    name.fuller_form = fuller;
+                }
+            }
+        }
+        for part in parts {
+            if part.contains(|c: char| c.is_ascii_digit()) {
+                name.dates = Some(part.to_string());
+            } else {
+                name.titles.push(part.to_string());
+            }
+        }
+        name
+    }
+
+    /// The catalog form: `Surname, Forename Numeration (Fuller form),
+    /// Titles, Dates`, without relators.
+    #[must_use]
+    pub fn heading(&self) -> String {
+        let mut heading = match self.kind {
+            PersonalNameKind::Forename => self.forename.clone().unwrap_or_default(),
+            _ => join(", ", [self.surname.as_deref(), self.forename.as_deref()]),
+        };
+        if let Some(numeration) = &self.numeration {
+            heading.push(' ');
+            heading.push_str(numeration);
+        }
+        if let Some(fuller) = &self.fuller_form {
+            heading.push_str(" (");
+            heading.push_str(fuller);
+            heading.push(')');
+        }
+        for part in self.titles.iter().chain(&self.dates) {
+            heading.push_str(", ");
+            heading.push_str(part);
+        }
+        heading
+    }
+
+    /// Natural order for display: `F. Scott Fitzgerald`, `John II, King of
+    /// France`. Dates and relators are left out.
+    #[must_use]
+    pub fn display_form(&self) -> String {
+        let mut display = match self.kind {
+            PersonalNameKind::Surname => {
+                join(" ", [self.forename.as_deref(), self.surname.as_deref()])
+            },
+            PersonalNameKind::Forename => self.forename.clone().unwrap_or_default(),
+            PersonalNameKind::Family => self.surname.clone().unwrap_or_default(),
+        };
+        if let Some(numeration) = &self.numeration {
+            display.push(' ');
+            display.push_str(numeration);
+        }
+        for title in &self.titles {
+            display.push_str(", ");
+            display.push_str(title);
+        }
+        display
+    }
+
+    /// A browse key for the heading, so `Smith, John, 1900-` files after
+    /// `Smith, John` and before `Smith, Jonathan`.
+    #[must_use]
+    pub fn sort_form(&self) -> String {
+        sort_key(&self.heading())
+    }
+
+    /// Year of birth from the dates: `1896` in `1896-1940` or `b. 1896`.
+    #[must_use]
+    pub fn birth_year(&self) -> Option<i32> {
+        life_years(self.dates.as_deref()?).0
+    }
+
+    /// Year of death from the dates: `1940` in `1896-1940` or `d. 1940`.
+    #[must_use]
+    pub fn death_year(&self) -> Option<i32> {
+        life_years(self.dates.as_deref()?).1
+    }
+
+    fn set_entry_element(&mut self, value: &str) {
+        match (self.kind, value.split_once(',')) {
+            (PersonalNameKind::Forename, _) => self.forename = Some(value.to_string()),
+            (_, Some((surname, forename))) if !forename.trim().is_empty() => {
+                self.surname = Some(surname.trim().to_string());
+                self.forename = Some(forename.trim().to_string());
+            },
+            _ => self.surname = Some(value.to_string()),
+        }
+    }
+}
+
+/// The first-indicator form of a corporate name (X10).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum CorporateNameKind {
+    /// An inverted name (0).
+    Inverted,
+    /// A jurisdiction name (1): `United States. Congress`.
+    Jurisdiction,
+    /// A name in direct order (2): `Harvard University`.
+    #[default]
+    Direct,
+}
+
+impl CorporateNameKind {
+    /// Decode an X10 first indicator; unknown values read as direct order.
+    #[must_use]
+    pub fn from_indicator(indicator: char) -> Self {
+        match indicator {
+            '0' => CorporateNameKind::Inverted,
+            '1' => CorporateNameKind::Jurisdiction,
+            _ => CorporateNameKind::Direct,
+        }
+    }
+
+    /// The X10 first indicator for this kind.
+    #[must_use]
+    pub fn indicator(self) -> char {
+        match self {
+            CorporateNameKind::Inverted => '0',
+            CorporateNameKind::Jurisdiction => '1',
+            CorporateNameKind::Direct => '2',
+        }
+    }
+}
+
+/// A corporate name heading and its hierarchy of subordinate units.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CorporateName {
+    /// Form of the entry element.
+    pub kind: CorporateNameKind,
+    /// The corporate body or jurisdiction (`$a`).
+    pub name: String,
+    /// Subordinate units from broadest to narrowest (`$b`).
+    pub units: Vec<String>,
+    /// Relator terms (`$e`).
+    pub relators: Vec<String>,
+}
+
+impl CorporateName {
+    /// Parse an X10 field; `None` if it has no `$a`.
+    #[must_use]
+    pub fn parse_from_field(field: &Field) -> Option<Self> {
+        let mut name = CorporateName {
+            kind: CorporateNameKind::from_indicator(field.indicator1),
+            name: clean(field.get_subfield('a')?),
+            ..Self::default()
+        };
+        for subfield in &field.subfields {
+            let value = clean(&subfield.value);
+            match subfield.code {
+                'b' if !value.is_empty() => name.units.push(value),
+                'e' if !value.is_empty() => name.relators.push(value),
+                _ => {},
+            }
+        }
+        Some(name)
+    }
+
+    /// Parse a heading string such as `United States. Congress. House`.
+    ///
+    /// The heading is split at each `. ` followed by a capital letter,
+    /// except after an initial or abbreviation (`U.S. Army`, `Dept. of`).
+    #[must_use]
+    pub fn parse(heading: &str) -> Self {
+        let heading = clean(heading);
+        let mut levels = Vec::new();
+        let mut start = 0;
+        for (i, _) in heading.match_indices(". ") {
+            let next_upper = heading[i + 2..].starts_with(char::is_uppercase);
+            if next_upper && strip_value(&heading[start..=i]).len() == i - start {
+                levels.push(heading[start..i].to_string());
+                start = i + 2;
+            }
+        }
+        levels.push(heading[start..].to_string());
+        let name = levels.remove(0);
+        CorporateName {
+            name,
+            units: levels,
+            ..Self::default()
+        }
+    }
+
+    /// The name followed by each subordinate unit.
+    #[must_use]
+    pub fn hierarchy(&self) -> Vec<&str> {
+        std::iter::once(self.name.as_str())
+            .chain(self.units.iter().map(String::as_str))
+            .collect()
+    }
+
+    /// The next body up the hierarchy, or `None` for a top-level body.
+    #[must_use]
+    pub fn parent(&self) -> Option<CorporateName> {
+        let mut parent = self.clone();
+        parent.units.pop()?;
+        parent.relators.clear();
+        Some(parent)
+    }
+
+    /// The catalog form, levels joined with `. `.
+    #[must_use]
+    pub fn heading(&self) -> String {
+        self.hierarchy().join(". ")
+    }
+
+    /// The display form; the same as the [`heading`](Self::heading), since
+    /// corporate names are already in direct order.
+    #[must_use]
+    pub fn display_form(&self) -> String {
+        self.heading()
+    }
+
+    /// A browse key for the heading.
+    #[must_use]
+    pub fn sort_form(&self) -> String {
+        sort_key(&self.heading())
+    }
+}
+
+/// A value with ISBD punctuation removed.
+fn clean(value: &str) -> String {
+    strip_value(value.trim()).to_string()
+}
+
+fn unparenthesize(value: &str) -> &str {
+    value
+        .trim()
+        .trim_start_matches('(')
+        .trim_end_matches(')')
+        .trim()
+}
+
+/// Split `Scott (Francis Scott)` into `Scott` and `Francis Scott`.
+fn split_fuller_form(part: &str) -> (&str, Option<String>) {
+    match part.split_once(" (") {
+        Some((before, fuller)) if fuller.ends_with(')') => {
+            (before.trim(), Some(unparenthesize(fuller).to_string()))
+        },
+        _ => (part, None),
+    }
+}
+
+fn is_roman_numeral(word: &str) -> bool {
+    !word.is_empty()
+        && word
+            .chars()
+            .all(|c| matches!(c, 'I' | 'V' | 'X' | 'L' | 'C' | 'M'))
+}
+
+fn join<'a>(separator: &str, parts: impl IntoIterator<Item = Option<&'a str>>) -> String {
+    parts
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// Birth and death years from a `$d` value. Flourished dates (`fl.`,
+/// `active`) and centuries give neither.
+fn life_years(dates: &str) -> (Option<i32>, Option<i32>) {
+    let lower = dates.to_lowercase();
+    if lower.starts_with("fl") || lower.starts_with("active") || lower.contains("cent") {
+        return (None, None);
+    }
+    if lower.starts_with("d.") || lower.starts_with("died") {
+        return (None, first_year(dates));
+    }
+    match dates.split_once('-') {
+        Some((birth, death)) => (first_year(birth), first_year(death)),
+        None => (first_year(dates), None),
+    }
+}
+
+/// The first run of three or four digits in `text`.
+fn first_year(text: &str) -> Option<i32> {
+    text.split(|c: char| !c.is_ascii_digit())
+        .find(|digits| (3..=4).contains(&digits.len()))
+        .and_then(|digits| digits.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(tag: &str, ind1: char, subfields: &[(char, &str)]) -> Field {
+        let mut builder = Field::builder(tag.to_string(), ind1, ' ');
+        for (code, value) in subfields {
+            builder = builder.subfield_str(*code, value);
+        }
+        builder.build()
+    }
+
+    #[test]
+    fn test_personal_names() {
+        let king = PersonalName::parse_from_field(&field(
+            "600",
+            '0',
+            &[
+                ('a', "John"),
+                ('b', "II,"),
+                ('c', "King of France,"),
+                ('d', "1319-1364."),
+            ],
+        ))
+        .unwrap();
+        assert_eq!(king.kind, PersonalNameKind::Forename);
+        assert_eq!(king.surname, None);
+        assert_eq!(king.display_form(), "John II, King of France");
+        assert_eq!(king.heading(), "John II, King of France, 1319-1364");
+        assert_eq!(
+            PersonalName::parse("John II, King of France, 1319-1364."),
+            king
+        );
+
+        let family =
+            PersonalName::parse_from_field(&field("100", '3', &[('a', "Kennedy family.")]));
+        assert_eq!(family.unwrap().display_form(), "Kennedy family");
+
+        let died = PersonalName::parse("Smith, John, d. 1850");
+        assert_eq!((died.birth_year(), died.death_year()), (None, Some(1850)));
+        let living = PersonalName::parse("Smith, John, 1950-");
+        assert_eq!(
+            (living.birth_year(), living.death_year()),
+            (Some(1950), None)
+        );
+        assert_eq!(
+            PersonalName::parse("Smith, John, active 1600").birth_year(),
+            None
+        );
+        assert!(PersonalName::parse("Smith, John").sort_form() < living.sort_form());
+        assert!(PersonalName::parse_from_field(&field("100", '1', &[('d', "1900-")])).is_none());
+    }
+
+    #[test]
+    fn test_corporate_names() {
+        let house = CorporateName::parse_from_field(&field(
+            "110",
+            '1',
+            &[
+                ('a', "United States."),
+                ('b', "Congress."),
+                ('b', "House."),
+                ('e', "issuing body."),
+            ],
+        ))
+        .unwrap();
+        assert_eq!(house.kind, CorporateNameKind::Jurisdiction);
+        assert_eq!(house.hierarchy(), ["United States", "Congress", "House"]);
+        assert_eq!(house.relators, ["issuing body"]);
+        let congress = house.parent().unwrap();
+        assert_eq!(congress.display_form(), "United States. Congress");
+        assert!(congress.parent().unwrap().parent().is_none());
+
+        let parsed = CorporateName::parse("U.S. Army. Corps of Engineers. Dept. of Research.");
+        assert_eq!(
+            parsed.hierarchy(),
+            ["U.S. Army", "Corps of Engineers", "Dept. of Research"]
+        );
+        assert_eq!(
+            parsed.sort_form(),
+            "u s army corps of engineers dept of research"
+        );
+    }
+}
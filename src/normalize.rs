@@ -0,0 +1,676 @@
+//! Normalized forms of headings, titles, and standard numbers for matching.
+//!
+//! Validation (see [`IsbnValidator`]) answers "is this well formed?";
+//! matching and deduplication need a second question answered: "are these
+//! two strings the same thing?". This module produces the comparison forms:
+//!
+//! - [`naco_normalize`] / [`naco_normalize_heading`] — the NACO authority
+//!   comparison rules (case folding, diacritic and punctuation removal)
+//! - [`normalize_title`] — a title key with non-filing characters dropped
+//!   according to the field's non-filing indicator
+//! - [`sort_key`] / [`title_sort_key`] — browse keys whose byte order is a
+//!   sensible alphabetical order
+//! - [`isbn10_to_isbn13`], [`isbn13_to_isbn10`], [`hyphenate_isbn`] — ISBN
+//!   form conversion
+//! - [`normalize_lccn`] — the Library of Congress LCCN normalization rules
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::normalize::{isbn10_to_isbn13, naco_normalize, normalize_lccn};
+//!
+//! assert_eq!(naco_normalize("Brontë, Charlotte, 1816-1855."), "BRONTE CHARLOTTE 1816 1855");
+//! assert_eq!(isbn10_to_isbn13("0-306-40615-2").as_deref(), Some("9780306406157"));
+//! assert_eq!(normalize_lccn("n 78-89035"), "n78089035");
+//! ```
+
+use crate::bibliographic_helpers::IsbnValidator;
+use crate::record::Field;
+use std::fmt::Write;
+use unicode_normalization::UnicodeNormalization;
+
+/// Normalize a string according to the NACO comparison rules.
+///
+/// Letters are upper-cased, diacritics are removed, special letters are
+/// expanded (`Æ` → `AE`, `Þ` → `TH`, `Ł` → `L`, ...), apostrophes and
+/// brackets are deleted, all other punctuation (including every comma)
+/// becomes a blank, `&`, `@`, `#`, `+`, `♭` and `♯` are kept, and runs of
+/// blanks collapse to one.
+///
+/// Use [`naco_normalize_heading`] for a whole heading field, which keeps the
+/// first comma of subfield `$a` as the rules require for personal names.
+#[must_use]
+pub fn naco_normalize(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    push_naco(&mut out, value, false);
+    collapse_blanks(&out)
+}
+
+/// NACO-normalize a heading field (1XX, 4XX, 5XX, 6XX, 7XX, 8XX).
+///
+/// Subfields are normalized individually and joined with a single blank.
+/// Numeric control subfields (`$0`–`$9`, e.g. `$0` identifiers and `$6`
+/// linkage) are skipped. The first comma in the first `$a` is retained, per
+/// the NACO rules for distinguishing surname from forename.
+#[must_use]
+pub fn naco_normalize_heading(field: &Field) -> String {
+    let mut out = String::new();
+    let mut seen_a = false;
+    for subfield in &field.subfields {
+        if subfield.code.is_ascii_digit() {
+            continue;
+        }
+        out.push(' ');
+        let first_a = subfield.code == 'a' && !seen_a;
+        push_naco(&mut out, &subfield.value, first_a);
+        if first_a {
+            // A retained comma that ends the subfield ("Mark Twain,") is
+            // punctuation, not a surname separator.
+            let kept = out.trim_end_matches(' ').len();
+            if out[..kept].ends_with(',') {
+                out.truncate(kept - 1);
+            }
+        }
+        seen_a |= subfield.code == 'a';
+    }
+    collapse_blanks(&out)
+}
+
+/// Append the NACO form of `value` to `out`.
+fn push_naco(out: &mut String, value: &str, mut keep_first_comma: bool) {
+    for c in value.nfd() {
+        match c {
+            // Combining diacritics, apostrophes, brackets, alif and ayn are
+            // deleted outright rather than turned into blanks.
+            '\u{0300}'..='\u{036F}'
+            | '\u{FE20}'..='\u{FE2F}'
+            | '\''
+            | '['
+            | ']'
+            | 'ʻ'
+            | 'ʼ'
+            | '‘'
+            | '’' => {},
+            ',' if keep_first_comma => {
+                out.push(',');
+                keep_first_comma = false;
+            },
+            '&' | '@' | '#' | '+' | '♭' | '♯' => out.push(c),
+            'Æ' | 'æ' => out.push_str("AE"),
+            'Œ' | 'œ' => out.push_str("OE"),
+            'Þ' | 'þ' => out.push_str("TH"),
+            'ß' => out.push_str("SS"),
+            'Đ' | 'đ' | 'Ð' | 'ð' => out.push('D'),
+            'Ł' | 'ł' => out.push('L'),
+            'Ø' | 'ø' => out.push('O'),
+            'ı' => out.push('I'),
+            '⁰' | '₀' => out.push('0'),
+            '¹' | '₁' => out.push('1'),
+            '²' | '₂' => out.push('2'),
+            '³' | '₃' => out.push('3'),
+            c if c.is_alphanumeric() => out.extend(c.to_uppercase()),
+            _ => out.push(' '),
+        }
+    }
+}
+
+fn collapse_blanks(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Number of non-filing characters declared by a title field's indicator.
+///
+/// Fields 130, 630, 730 and 740 carry the count in indicator 1; 222, 240,
+/// 242, 243, 245, 440 and 830 carry it in indicator 2. Other fields, and
+/// non-digit indicators, yield 0.
+#[must_use]
+pub fn non_filing_count(field: &Field) -> usize {
+    let indicator = match field.tag.as_str() {
+        "130" | "630" | "730" | "740" => field.indicator1,
+        "222" | "240" | "242" | "243" | "245" | "440" | "830" => field.indicator2,
+        _ => return 0,
+    };
+    indicator.to_digit(10).map_or(0, |d| d as usize)
+}
+
+/// Drop the first `count` characters (an initial article such as "The ")
+/// from `value`.
+///
+/// Counts characters rather than bytes, so a diacritic-bearing article
+/// ("L'é...") is handled as catalogers count it. Returns the whole string
+/// when `count` is 0 or exceeds the value length.
+#[must_use]
+pub fn strip_non_filing(value: &str, count: usize) -> &str {
+    if count == 0 {
+        return value;
+    }
+    match value.char_indices().nth(count) {
+        Some((idx, _)) => &value[idx..],
+        None => value,
+    }
+}
+
+/// A normalized title key for matching, built from a title field.
+///
+/// The non-filing characters declared by the field's indicator (see
+/// [`non_filing_count`]) are removed from the first `$a`, the title
+/// subfields are joined (statement of responsibility `$c`, medium `$h`
+/// and numeric control subfields are excluded), and the result is
+/// NACO-normalized.
+///
+/// # Examples
+///
+/// ```
+/// use mrrc::normalize::normalize_title;
+/// use mrrc::Field;
+///
+/// let field = Field::builder("245".to_string(), '1', '4')
+///     .subfield_str('a', "The Great Gatsby /")
+///     .subfield_str('c', "F. Scott Fitzgerald.")
+///     .build();
+/// assert_eq!(normalize_title(&field), "GREAT GATSBY");
+/// ```
+#[must_use]
+pub fn normalize_title(field: &Field) -> String {
+    naco_normalize(&filing_title(field))
+}
+
+/// Fold a string into a key whose byte order is a sensible browse order.
+///
+/// The string is NACO-normalized (diacritics, case and punctuation folded
+/// away), lower-cased, and every run of digits is left-padded with zeros to
+/// eight places so that "Part 2" sorts before "Part 10".
+///
+/// # Examples
+///
+/// ```
+/// use mrrc::normalize::sort_key;
+///
+/// assert_eq!(sort_key("Émile, ou De l'éducation"), "emile ou de leducation");
+/// assert!(sort_key("Part 2") < sort_key("Part 10"));
+/// ```
+#[must_use]
+pub fn sort_key(value: &str) -> String {
+    let folded = naco_normalize(value).to_lowercase();
+    let mut out = String::with_capacity(folded.len());
+    let mut digits = String::new();
+    for c in folded.chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        if !digits.is_empty() {
+            let _ = write!(out, "{digits:0>8}");
+            digits.clear();
+        }
+        out.push(c);
+    }
+    if !digits.is_empty() {
+        let _ = write!(out, "{digits:0>8}");
+    }
+    out
+}
+
+/// A browse/sort key for a title field (245, 240, 130, 730, ...).
+///
+/// Non-filing characters are skipped according to the field's indicator
+/// (see [`non_filing_count`]), the title subfields are joined as in
+/// [`normalize_title`], and the result is folded with [`sort_key`].
+#[must_use]
+pub fn title_sort_key(field: &Field) -> String {
+    sort_key(&filing_title(field))
+}
+
+/// The title subfields of `field` joined with blanks, with the non-filing
+/// characters removed from the first `$a`.
+pub(crate) fn filing_title(field: &Field) -> String {
+    let skip = non_filing_count(field);
+    let mut first_a = true;
+    let mut parts = Vec::new();
+    for subfield in &field.subfields {
+        if subfield.code.is_ascii_digit() || matches!(subfield.code, 'c' | 'h') {
+            continue;
+        }
+        if subfield.code == 'a' && first_a {
+            first_a = false;
+            parts.push(strip_non_filing(&subfield.value, skip));
+        } else {
+            parts.push(subfield.value.as_str());
+        }
+    }
+    parts.join(" ")
+}
+
+/// Convert an ISBN-10 to its ISBN-13 (978-prefixed) form.
+///
+/// Hyphens and spaces in the input are ignored. Returns `None` if the input
+/// is not a valid ISBN-10.
+#[must_use]
+pub fn isbn10_to_isbn13(isbn: &str) -> Option<String> {
+    let clean = IsbnValidator::normalize(isbn).to_ascii_uppercase();
+    if !IsbnValidator::validate_isbn10(&clean) {
+        return None;
+    }
+    let mut out = format!("978{}", &clean[..9]);
+    let check = isbn13_check_digit(&out)?;
+    out.push(check);
+    Some(out)
+}
+
+/// Convert a 978-prefixed ISBN-13 to its ISBN-10 form.
+///
+/// Hyphens and spaces in the input are ignored. Returns `None` if the input
+/// is not a valid ISBN-13 or uses the 979 prefix (which has no ISBN-10
+/// equivalent).
+#[must_use]
+pub fn isbn13_to_isbn10(isbn: &str) -> Option<String> {
+    let clean = IsbnValidator::normalize(isbn);
+    if !clean.starts_with("978") || !IsbnValidator::validate_isbn13(&clean) {
+        return None;
+    }
+    let body = &clean[3..12];
+    let sum: u32 = body
+        .chars()
+        .zip((2..=10).rev())
+        .map(|(c, w)| c.to_digit(10).unwrap_or(0) * w)
+        .sum();
+    let check = (11 - sum % 11) % 11;
+    let check = if check == 10 {
+        'X'
+    } else {
+        char::from_digit(check, 10)?
+    };
+    Some(format!("{body}{check}"))
+}
+
+/// Normalize any valid ISBN to its unhyphenated ISBN-13 form.
+///
+/// This is the usual match key for deduplication: ISBN-10 and ISBN-13
+/// forms of the same book compare equal. Returns `None` for invalid input.
+#[must_use]
+pub fn normalize_isbn(isbn: &str) -> Option<String> {
+    let clean = IsbnValidator::normalize(isbn).to_ascii_uppercase();
+    match clean.len() {
+        10 => isbn10_to_isbn13(&clean),
+        13 if IsbnValidator::validate_isbn13(&clean) => Some(clean),
+        _ => None,
+    }
+}
+
+fn isbn13_check_digit(first12: &str) -> Option<char> {
+    let sum: u32 = first12
+        .chars()
+        .enumerate()
+        .map(|(i, c)| c.to_digit(10).map(|d| if i % 2 == 0 { d } else { d * 3 }))
+        .sum::<Option<u32>>()?;
+    char::from_digit((10 - sum % 10) % 10, 10)
+}
+
+/// Registrant-element ranges, from the ISBN agency's range table, for the
+/// 978 registration groups 0, 1, 2, 3, 4 and 7; other groups are not
+/// covered. Each entry is `(upper bound of the next seven digits,
+/// registrant length)`, in ascending order. A length of 0 marks a block
+/// whose ranges are not covered.
+const REGISTRANT_RANGES: &[(char, &[(u32, usize)])] = &[
+    (
+        '0',
+        &[
+            (1_999_999, 2),
+            (2_279_999, 3),
+            (2_289_999, 4),
+            (3_689_999, 3),
+            (3_699_999, 4),
+            (6_389_999, 3),
+            (6_397_999, 4),
+            (6_399_999, 7),
+            (6_449_999, 3),
+            (6_459_999, 7),
+            (6_479_999, 3),
+            (6_489_999, 7),
+            (6_549_999, 3),
+            (6_559_999, 4),
+            (6_999_999, 3),
+            (8_499_999, 4),
+            (8_999_999, 5),
+            (9_499_999, 6),
+            (9_999_999, 7),
+        ],
+    ),
+    (
+        '1',
+        &[
+            (999_999, 2),
+            (3_999_999, 3),
+            (5_499_999, 4),
+            (8_697_999, 5),
+            (9_989_999, 6),
+            (9_999_999, 7),
+        ],
+    ),
+    (
+        '2',
+        &[
+            (1_999_999, 2),
+            (3_499_999, 3),
+            (3_999_999, 5),
+            (4_799_999, 3),
+            (4_999_999, 0),
+            (6_999_999, 3),
+            (8_399_999, 4),
+            (8_999_999, 5),
+            (9_499_999, 0),
+            (9_999_999, 7),
+        ],
+    ),
+    (
+        '3',
+        &[
+            (299_999, 2),
+            (339_999, 3),
+            (369_999, 4),
+            (399_999, 5),
+            (1_999_999, 2),
+            (6_999_999, 3),
+            (8_499_999, 4),
+            (8_999_999, 5),
+            (9_499_999, 6),
+            (9_539_999, 7),
+            (9_699_999, 5),
+            (9_849_999, 7),
+            (9_999_999, 5),
+        ],
+    ),
+    (
+        '4',
+        &[
+            (1_999_999, 2),
+            (6_999_999, 3),
+            (8_499_999, 4),
+            (8_999_999, 5),
+            (9_499_999, 6),
+            (9_999_999, 7),
+        ],
+    ),
+    (
+        '7',
+        &[
+            (999_999, 2),
+            (4_999_999, 3),
+            (7_999_999, 4),
+            (8_999_999, 5),
+            (9_999_999, 6),
+        ],
+    ),
+];
+
+/// Insert hyphens between the elements of an ISBN.
+///
+/// Accepts ISBN-10 or ISBN-13 input (hyphens and spaces ignored) and
+/// returns it in the same length, hyphenated as prefix-group-registrant-
+/// publication-check (e.g. `978-0-306-40615-7`, `0-306-40615-2`).
+///
+/// Hyphenation depends on the ISBN agency's range tables. Only the 978
+/// registration groups 0 and 1 (English), 2 (French), 3 (German),
+/// 4 (Japan) and 7 (China) are covered, and not every block of group 2;
+/// `None` is returned for other groups and blocks, for 979 ISBNs and for
+/// invalid ISBNs, rather than guessing where the hyphens go.
+#[must_use]
+pub fn hyphenate_isbn(isbn: &str) -> Option<String> {
+    let clean = IsbnValidator::normalize(isbn).to_ascii_uppercase();
+    let (isbn13, is_10) = match clean.len() {
+        10 => (isbn10_to_isbn13(&clean)?, true),
+        13 if IsbnValidator::validate_isbn13(&clean) => (clean.clone(), false),
+        _ => return None,
+    };
+    if !isbn13.starts_with("978") {
+        return None;
+    }
+    let group = isbn13[3..].chars().next()?;
+    let ranges = REGISTRANT_RANGES
+        .iter()
+        .find(|(g, _)| *g == group)
+        .map(|(_, r)| *r)?;
+    let next_seven: u32 = isbn13[4..11].parse().ok()?;
+    let registrant_len = ranges
+        .iter()
+        .find(|(upper, _)| next_seven <= *upper)
+        .map(|(_, len)| *len)
+        .filter(|&len| len > 0)?;
+    let registrant = &isbn13[4..4 + registrant_len];
+    let publication = &isbn13[4 + registrant_len..12];
+
+    if is_10 {
+        let check = &clean[9..];
+        Some(format!("{group}-{registrant}-{publication}-{check}"))
+    } else {
+        let check = &isbn13[12..];
+        Some(format!("978-{group}-{registrant}-{publication}-{check}"))
+    }
+}
+
+/// Normalize an LCCN according to the Library of Congress rules.
+///
+/// All blanks are removed; a forward slash and everything after it is
+/// removed; if a hyphen is present it is removed and the digits to its
+/// right are left-padded with zeros to six digits.
+///
+/// # Examples
+///
+/// ```
+/// use mrrc::normalize::normalize_lccn;
+///
+/// assert_eq!(normalize_lccn("n78-890351"), "n78890351");
+/// assert_eq!(normalize_lccn("   85000002 "), "85000002");
+/// assert_eq!(normalize_lccn("85-2 "), "85000002");
+/// assert_eq!(normalize_lccn("75-425165//r75"), "75425165");
+/// ```
+#[must_use]
+pub fn normalize_lccn(lccn: &str) -> String {
+    let mut clean: String = lccn.chars().filter(|c| !c.is_whitespace()).collect();
+    if let Some(slash) = clean.find('/') {
+        clean.truncate(slash);
+    }
+    if let Some((prefix, serial)) = clean.split_once('-') {
+        let serial: String = serial.chars().filter(char::is_ascii_digit).collect();
+        if serial.len() <= 6 {
+            return format!("{prefix}{serial:0>6}");
+        }
+        return format!("{prefix}{serial}");
+    }
+    clean
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_naco_normalize_basic() {
+        assert_eq!(
+            naco_normalize("Brontë, Charlotte, 1816-1855."),
+            "BRONTE CHARLOTTE 1816 1855"
+        );
+        assert_eq!(naco_normalize("  [Sound   recording]  "), "SOUND RECORDING");
+        assert_eq!(naco_normalize("O'Brien"), "OBRIEN");
+        assert_eq!(naco_normalize("Æsop's fables"), "AESOPS FABLES");
+        assert_eq!(naco_normalize("Þórr & Łódź"), "THORR & LODZ");
+        assert_eq!(naco_normalize("C++ / C#"), "C++ C#");
+    }
+
+    #[test]
+    fn test_naco_normalize_heading_keeps_first_comma() {
+        let field = Field::builder("100".to_string(), '1', ' ')
+            .subfield_str('a', "Smith, John, Jr.,")
+            .subfield_str('d', "1900-1990.")
+            .subfield_str('0', "http://id.loc.gov/authorities/names/n123")
+            .build();
+        assert_eq!(naco_normalize_heading(&field), "SMITH, JOHN JR 1900 1990");
+
+        let field = Field::builder("100".to_string(), '0', ' ')
+            .subfield_str('a', "Mark Twain,")
+            .subfield_str('d', "1835-1910")
+            .build();
+        assert_eq!(naco_normalize_heading(&field), "MARK TWAIN 1835 1910");
+    }
+
+    #[test]
+    fn test_non_filing_count_by_tag() {
+        let f245 = Field::new("245".to_string(), '1', '4');
+        let f130 = Field::new("130".to_string(), '2', ' ');
+        let f650 = Field::new("650".to_string(), ' ', '0');
+        let bad = Field::new("245".to_string(), '1', ' ');
+        assert_eq!(non_filing_count(&f245), 4);
+        assert_eq!(non_filing_count(&f130), 2);
+        assert_eq!(non_filing_count(&f650), 0);
+        assert_eq!(non_filing_count(&bad), 0);
+    }
+
+    #[test]
+    fn test_strip_non_filing() {
+        assert_eq!(strip_non_filing("The Hobbit", 4), "Hobbit");
+        assert_eq!(strip_non_filing("L'été", 2), "été");
+        assert_eq!(strip_non_filing("A", 5), "A");
+        assert_eq!(strip_non_filing("Hobbit", 0), "Hobbit");
+    }
+
+    #[test]
+    fn test_normalize_title() {
+        let field = Field::builder("245".to_string(), '1', '4')
+            .subfield_str('a', "The Lord of the Rings.")
+            .subfield_str('n', "Part 1,")
+            .subfield_str('p', "The fellowship of the ring /")
+            .subfield_str('c', "J.R.R. Tolkien.")
+            .build();
+        assert_eq!(
+            normalize_title(&field),
+            "LORD OF THE RINGS PART 1 THE FELLOWSHIP OF THE RING"
+        );
+    }
+
+    #[test]
+    fn test_sort_key_folding() {
+        assert_eq!(sort_key("  Ça va?  "), "ca va");
+        assert_eq!(sort_key("Vol. 3"), "vol 00000003");
+        assert!(sort_key("Part 2") < sort_key("Part 10"));
+        assert!(sort_key("émile") < sort_key("Zola"));
+    }
+
+    #[test]
+    fn test_title_sort_key_uses_indicator() {
+        let f245 = Field::builder("245".to_string(), '0', '4')
+            .subfield_str('a', "The Émigrés /")
+            .subfield_str('c', "by someone.")
+            .build();
+        assert_eq!(title_sort_key(&f245), "emigres");
+        let f130 = Field::builder("130".to_string(), '2', ' ')
+            .subfield_str('a', "A Hobbit tale.")
+            .subfield_str('l', "English.")
+            .build();
+        assert_eq!(title_sort_key(&f130), "hobbit tale english");
+    }
+
+    #[test]
+    fn test_isbn_conversion() {
+        assert_eq!(
+            isbn10_to_isbn13("0-306-40615-2").as_deref(),
+            Some("9780306406157")
+        );
+        assert_eq!(
+            isbn10_to_isbn13("043942089x").as_deref(),
+            Some("9780439420891")
+        );
+        assert_eq!(
+            isbn13_to_isbn10("978-0-439-42089-1").as_deref(),
+            Some("043942089X")
+        );
+        assert_eq!(
+            isbn13_to_isbn10("9780306406157").as_deref(),
+            Some("0306406152")
+        );
+        assert_eq!(isbn13_to_isbn10("9791034304540"), None);
+        assert_eq!(isbn10_to_isbn13("0306406153"), None);
+    }
+
+    #[test]
+    fn test_normalize_isbn() {
+        assert_eq!(
+            normalize_isbn("0-306-40615-2"),
+            normalize_isbn("978-0-306-40615-7")
+        );
+        assert_eq!(normalize_isbn("garbage"), None);
+    }
+
+    #[test]
+    fn test_hyphenate_isbn() {
+        assert_eq!(
+            hyphenate_isbn("9780306406157").as_deref(),
+            Some("978-0-306-40615-7")
+        );
+        assert_eq!(
+            hyphenate_isbn("0306406152").as_deref(),
+            Some("0-306-40615-2")
+        );
+        assert_eq!(
+            hyphenate_isbn("9781565924796").as_deref(),
+            Some("978-1-56592-479-6")
+        );
+        assert_eq!(
+            hyphenate_isbn("043942089X").as_deref(),
+            Some("0-439-42089-X")
+        );
+        assert_eq!(hyphenate_isbn("123"), None);
+    }
+
+    #[test]
+    fn test_hyphenate_isbn_group_zero_blocks() {
+        assert_eq!(
+            hyphenate_isbn("9780228012344").as_deref(),
+            Some("978-0-2280-1234-4")
+        );
+        assert_eq!(
+            hyphenate_isbn("0228012341").as_deref(),
+            Some("0-2280-1234-1")
+        );
+        assert_eq!(
+            hyphenate_isbn("9780369012340").as_deref(),
+            Some("978-0-3690-1234-0")
+        );
+        assert_eq!(
+            hyphenate_isbn("9780639801230").as_deref(),
+            Some("978-0-6398012-3-0")
+        );
+        assert_eq!(
+            hyphenate_isbn("9780645123456").as_deref(),
+            Some("978-0-6451234-5-6")
+        );
+        assert_eq!(
+            hyphenate_isbn("9780655012344").as_deref(),
+            Some("978-0-6550-1234-4")
+        );
+    }
+
+    #[test]
+    fn test_hyphenate_isbn_uncovered_groups() {
+        // Group 600 (Iran), 979 and uncovered blocks of group 2
+        assert_eq!(hyphenate_isbn("9786001234569"), None);
+        assert_eq!(hyphenate_isbn("9791069123458"), None);
+        assert_eq!(hyphenate_isbn("9782490123452"), None);
+        assert_eq!(hyphenate_isbn("9782912345677"), None);
+        assert_eq!(
+            hyphenate_isbn("9782700012347").as_deref(),
+            Some("978-2-7000-1234-7")
+        );
+    }
+
+    #[test]
+    fn test_normalize_lccn_rules() {
+        assert_eq!(normalize_lccn("n78-890351"), "n78890351");
+        assert_eq!(normalize_lccn("n78-89035"), "n78089035");
+        assert_eq!(normalize_lccn("n 78890351 "), "n78890351");
+        assert_eq!(normalize_lccn(" 85000002 "), "85000002");
+        assert_eq!(normalize_lccn("85-2 "), "85000002");
+        assert_eq!(normalize_lccn("2001-000002"), "2001000002");
+        assert_eq!(normalize_lccn("75-425165//r75"), "75425165");
+        assert_eq!(normalize_lccn(" 79139101 /AC/r932"), "79139101");
+    }
+}
@@ -0,0 +1,334 @@
+//! `OpenURL` 1.0 context objects and `COinS` for link resolvers.
+//!
+//! [`record_to_openurl_kev`] describes a record as an `OpenURL` 1.0
+//! (Z39.88-2004) key/encoded-value context object, the query string a link
+//! resolver takes to find full text or holdings. [`record_to_coins`] wraps
+//! the same context object in a `COinS` `<span class="Z3988">` for embedding
+//! in HTML, where browser extensions and reference managers pick it up.
+//!
+//! The metadata format and genre follow the leader and 008:
+//!
+//! | Leader/07          | Format    | `rft.genre`                                   |
+//! |--------------------|-----------|-----------------------------------------------|
+//! | `b` (serial part)  | `journal` | `article`, host from 773                      |
+//! | `s`, `i`           | `journal` | `journal`                                     |
+//! | `a` (monograph part) | `book`  | `bookitem`, host from 773                     |
+//! | other, text        | `book`    | `proceeding` (008/29 = 1), `report` (008/24-27 has `t`), else `book` |
+//! | other, non-text    | `dc`      | (none; Dublin Core keys `rft.title`, ...)     |
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::openurl::record_to_openurl_kev;
+//! use mrrc::{Field, Leader, Record};
+//!
+//! let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+//! record.add_field(
+//!     Field::builder("245".to_string(), '1', '0')
+//!         .subfield_str('a', "Roughing it /")
+//!         .build(),
+//! );
+//!
+//! let kev = record_to_openurl_kev(&record);
+//! assert!(kev.contains("rft.genre=book"));
+//! assert!(kev.contains("rft.btitle=Roughing%20it"));
+//! ```
+
+use std::fmt::Write;
+
+use crate::punctuation::display_value;
+use crate::record::Record;
+use crate::record_helpers::RecordHelpers;
+
+/// `OpenURL` metadata formats.
+const BOOK_FORMAT: &str = "info:ofi/fmt:kev:mtx:book";
+const JOURNAL_FORMAT: &str = "info:ofi/fmt:kev:mtx:journal";
+const DC_FORMAT: &str = "info:ofi/fmt:kev:mtx:dc";
+
+/// Convert a record to an `OpenURL` 1.0 KEV context object.
+///
+/// The result is a query string (without a leading `?`) to append to a
+/// link resolver's base URL. See the [module documentation](self) for how
+/// the format and genre are chosen.
+#[must_use]
+pub fn record_to_openurl_kev(record: &Record) -> String {
+    crate::telemetry::stage_span!("mrrc.convert", from = "marc", to = "openurl");
+    let mut kev = Kev::default();
+    kev.push("ctx_ver", "Z39.88-2004");
+    kev.push("ctx_enc", "info:ofi/enc:UTF-8");
+
+    let title = display(record, "245", &['a', 'b', 'n', 'p']);
+    let host = display(record, "773", &['t']);
+    let leader = &record.leader;
+    let text = matches!(leader.record_type, 'a' | 't');
+    let book = match leader.bibliographic_level {
+        'b' => {
+            kev.push("rft_val_fmt", JOURNAL_FORMAT);
+            kev.push("rft.genre", "article");
+            kev.push_opt("rft.atitle", title);
+            kev.push_opt("rft.jtitle", host);
+            kev.push_all("rft.issn", host_identifiers(record, 'x'));
+            false
+        },
+        's' | 'i' => {
+            kev.push("rft_val_fmt", JOURNAL_FORMAT);
+            kev.push("rft.genre", "journal");
+            kev.push_opt("rft.jtitle", title);
+            kev.push_all("rft.issn", identifiers(record, "022"));
+            false
+        },
+        'a' if text => {
+            kev.push("rft_val_fmt", BOOK_FORMAT);
+            kev.push("rft.genre", "bookitem");
+            kev.push_opt("rft.atitle", title);
+            kev.push_opt("rft.btitle", host);
+            kev.push_all("rft.isbn", host_identifiers(record, 'z'));
+            true
+        },
+        _ if text => {
+            kev.push("rft_val_fmt", BOOK_FORMAT);
+            kev.push("rft.genre", book_genre(record));
+            kev.push_opt("rft.btitle", title);
+            kev.push_all("rft.isbn", identifiers(record, "020"));
+            kev.push_opt("rft.edition", display(record, "250", &['a']));
+            kev.push_opt("rft.series", display(record, "490", &['a']));
+            true
+        },
+        _ => {
+            kev.push("rft_val_fmt", DC_FORMAT);
+            kev.push_opt("rft.title", title);
+            for tag in ["100", "110", "111", "700", "710", "711"] {
+                kev.push_all("rft.creator", names(record, tag));
+            }
+            kev.push_opt("rft.publisher", publication(record, 'b'));
+            kev.push_opt("rft.date", record.publication_year().map(|y| y.to_string()));
+            kev.push_opt("rft.language", record.language().map(str::to_string));
+            return kev.finish();
+        },
+    };
+
+    kev.push_all("rft.au", names(record, "100"));
+    kev.push_all("rft.aucorp", names(record, "110"));
+    kev.push_all("rft.au", names(record, "700"));
+    if book {
+        kev.push_opt("rft.pub", publication(record, 'b'));
+        kev.push_opt("rft.place", publication(record, 'a'));
+    }
+    kev.push_opt("rft.date", record.publication_year().map(|y| y.to_string()));
+    kev.finish()
+}
+
+/// Convert a record to a `COinS` span carrying its `OpenURL` context object.
+///
+/// The span is empty; pages usually leave it that way, since tools read
+/// the `title` attribute.
+#[must_use]
+pub fn record_to_coins(record: &Record) -> String {
+    let kev = record_to_openurl_kev(record).replace('&', "&amp;");
+    format!("<span class=\"Z3988\" title=\"{kev}\"></span>")
+}
+
+/// Accumulates encoded key/value pairs.
+#[derive(Default)]
+struct Kev {
+    query: String,
+}
+
+impl Kev {
+    fn push(&mut self, key: &str, value: &str) {
+        if !self.query.is_empty() {
+            self.query.push('&');
+        }
+        self.query.push_str(key);
+        self.query.push('=');
+        percent_encode(&mut self.query, value);
+    }
+
+    fn push_opt(&mut self, key: &str, value: Option<String>) {
+        if let Some(value) = value {
+            self.push(key, &value);
+        }
+    }
+
+    fn push_all(&mut self, key: &str, values: Vec<String>) {
+        for value in values {
+            self.push(key, &value);
+        }
+    }
+
+    fn finish(self) -> String {
+        self.query
+    }
+}
+
+/// Percent-encode everything but RFC 3986 unreserved characters.
+fn percent_encode(out: &mut String, value: &str) {
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            out.push(char::from(byte));
+        } else {
+            write!(out, "%{byte:02X}").ok();
+        }
+    }
+}
+
+/// The first `tag` field's `codes` subfields as display text.
+fn display(record: &Record, tag: &str, codes: &[char]) -> Option<String> {
+    record
+        .fields_by_tag(tag)
+        .find_map(|field| display_value(field, codes, " "))
+}
+
+/// `$a` of every `tag` field.
+fn names(record: &Record, tag: &str) -> Vec<String> {
+    record
+        .fields_by_tag(tag)
+        .filter_map(|field| display_value(field, &['a'], " "))
+        .collect()
+}
+
+/// ISBNs or ISSNs from `tag` $a, without qualifiers such as `(pbk.)`.
+fn identifiers(record: &Record, tag: &str) -> Vec<String> {
+    record
+        .fields_by_tag(tag)
+        .filter_map(|field| field.get_subfield('a'))
+        .filter_map(|value| value.split_whitespace().next())
+        .map(str::to_string)
+        .collect()
+}
+
+/// The host item's ISSN (773 $x) or ISBN (773 $z).
+fn host_identifiers(record: &Record, code: char) -> Vec<String> {
+    record
+        .fields_by_tag("773")
+        .flat_map(|field| field.get_subfield_values(code))
+        .filter_map(|value| value.split_whitespace().next())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Place (`a`) or publisher (`b`) from 260, else 264 second indicator 1.
+fn publication(record: &Record, code: char) -> Option<String> {
+    let field = record.get_field("260").or_else(|| {
+        record
+            .fields_by_tag("264")
+            .find(|field| field.indicator2 == '1')
+    })?;
+    display_value(field, &[code], " ")
+}
+
+/// Book genre from the 008 conference and nature-of-contents positions.
+fn book_genre(record: &Record) -> &'static str {
+    let Some(fixed) = record.get_control_field("008") else {
+        return "book";
+    };
+    if fixed.get(29..30) == Some("1") {
+        "proceeding"
+    } else if fixed
+        .get(24..28)
+        .is_some_and(|contents| contents.contains('t'))
+    {
+        "report"
+    } else {
+        "book"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::leader::Leader;
+    use crate::record::Field;
+
+    fn record(leader: &[u8], fields: &[(&str, &[(char, &str)])]) -> Record {
+        let mut record = Record::new(Leader::from_bytes(leader).unwrap());
+        record.add_control_field_str("008", "850101s1872    ctu           000 0 eng d");
+        for (tag, subfields) in fields {
+            let mut builder = Field::builder((*tag).to_string(), ' ', '1');
+            for (code, value) in *subfields {
+                builder = builder.subfield_str(*code, value);
+            }
+            record.add_field(builder.build());
+        }
+        record
+    }
+
+    #[test]
+    fn test_book_kev() {
+        let book = record(
+            b"00000nam a2200000 a 4500",
+            &[
+                ("020", &[('a', "9780306406157 (pbk.)")]),
+                ("100", &[('a', "Twain, Mark,"), ('d', "1835-1910.")]),
+                ("245", &[('a', "Roughing it :"), ('b', "a memoir & more /")]),
+                (
+                    "264",
+                    &[
+                        ('a', "Hartford :"),
+                        ('b', "American Pub. Co.,"),
+                        ('c', "1872."),
+                    ],
+                ),
+            ],
+        );
+        assert_eq!(
+            record_to_openurl_kev(&book),
+            "ctx_ver=Z39.88-2004&ctx_enc=info%3Aofi%2Fenc%3AUTF-8\
+             &rft_val_fmt=info%3Aofi%2Ffmt%3Akev%3Amtx%3Abook&rft.genre=book\
+             &rft.btitle=Roughing%20it%20a%20memoir%20%26%20more&rft.isbn=9780306406157\
+             &rft.au=Twain%2C%20Mark&rft.pub=American%20Pub.%20Co.&rft.place=Hartford\
+             &rft.date=1872"
+        );
+        let coins = record_to_coins(&book);
+        assert!(
+            coins.starts_with("<span class=\"Z3988\" title=\"ctx_ver=Z39.88-2004&amp;ctx_enc=")
+        );
+    }
+
+    #[test]
+    fn test_non_ascii_008_does_not_panic() {
+        let mut book = record(
+            b"00000nam a2200000 a 4500",
+            &[("245", &[('a', "Untitled.")])],
+        );
+        book.control_fields.insert(
+            "008".to_string(),
+            vec!["850101é1872    ctu           000 0 eé d".to_string()],
+        );
+        let kev = record_to_openurl_kev(&book);
+        assert!(!kev.contains("rft.date"));
+        assert!(kev.contains("rft.btitle=Untitled"));
+    }
+
+    #[test]
+    fn test_genre_switching() {
+        let article = record(
+            b"00000nab a2200000 a 4500",
+            &[
+                ("245", &[('a', "On rafts.")]),
+                ("773", &[('t', "Boating quarterly."), ('x', "1234-5678")]),
+            ],
+        );
+        let kev = record_to_openurl_kev(&article);
+        assert!(kev.contains("mtx%3Ajournal&rft.genre=article&rft.atitle=On%20rafts"));
+        assert!(kev.contains("&rft.jtitle=Boating%20quarterly&rft.issn=1234-5678"));
+
+        let serial = record(
+            b"00000nas a2200000 a 4500",
+            &[("022", &[('a', "1234-5678")])],
+        );
+        assert!(record_to_openurl_kev(&serial).contains("rft.genre=journal&rft.issn=1234-5678"));
+
+        let mut proceedings = record(b"00000nam a2200000 a 4500", &[]);
+        proceedings.control_fields["008"][0].replace_range(29..30, "1");
+        assert!(record_to_openurl_kev(&proceedings).contains("rft.genre=proceeding"));
+
+        let score = record(
+            b"00000ncm a2200000 a 4500",
+            &[("245", &[('a', "Sonatas.")])],
+        );
+        let kev = record_to_openurl_kev(&score);
+        assert!(kev.contains("mtx%3Adc&rft.title=Sonatas&rft.date=1872&rft.language=eng"));
+        assert!(!kev.contains("rft.genre"));
+    }
+}
@@ -12,10 +12,12 @@
 use crate::boundary_scanner::RecordBoundaryScanner;
 use crate::rayon_parser_pool::parse_batch_parallel;
 use crate::record::Record;
+use crate::stats::Stats;
 use crossbeam_channel::{Receiver, Sender, bounded};
 use std::collections::VecDeque;
 use std::fs::File;
-use std::io::Read;
+use std::io::{Read, Seek, SeekFrom};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Mutex, MutexGuard, PoisonError};
 use std::thread;
 
@@ -75,16 +77,23 @@ impl std::fmt::Display for PipelineError {
 
 impl std::error::Error for PipelineError {}
 
+/// A parsed batch: each record with the file offset just past its end.
+type Batch = Vec<(Record, u64)>;
+
 /// Producer task: reads file, scans boundaries, parses in parallel, sends to channel
 fn producer_task(
     file: File,
-    sender: &Sender<Vec<Record>>,
+    start_offset: u64,
+    sender: &Sender<Batch>,
     config: &PipelineConfig,
 ) -> PipelineResult<()> {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::info_span!("mrrc.pipeline.producer").entered();
     let mut file = file;
     let mut buffer = vec![0u8; config.buffer_size];
     let mut scanner = RecordBoundaryScanner::new();
     let mut leftover = Vec::new(); // Buffer for partial records from previous chunk
+    let mut leftover_offset = start_offset; // File offset of `leftover[0]`
 
     loop {
         // Read next chunk
@@ -96,6 +105,7 @@ fn producer_task(
             // EOF reached - if there's leftover data, it's an incomplete record
             break;
         }
+        crate::telemetry::stage_span!("mrrc.pipeline.batch", bytes = n);
 
         // Concatenate leftover from previous chunk with current chunk
         let mut current_buffer = leftover.clone();
@@ -119,15 +129,24 @@ fn producer_task(
                 // full = backpressure). One send per chunk instead of one per
                 // record; the consumer drains a local buffer. Skip empty batches.
                 if !records.is_empty() {
+                    let batch = records
+                        .into_iter()
+                        .zip(&boundaries)
+                        .map(|(record, &(offset, len))| {
+                            (record, leftover_offset + (offset + len) as u64)
+                        })
+                        .collect();
                     sender
-                        .send(records)
+                        .send(batch)
                         .map_err(|_| PipelineError::ChannelSendError)?;
                 }
 
                 // If the last boundary doesn't reach the end, save the tail as leftover
                 if all_complete {
+                    leftover_offset += current_buffer.len() as u64;
                     leftover.clear();
                 } else if let Some(&(offset, len)) = boundaries.last() {
+                    leftover_offset += (offset + len) as u64;
                     leftover = current_buffer[offset + len..].to_vec();
                 }
             },
@@ -144,13 +163,17 @@ fn producer_task(
 /// Consumer-facing pipeline handle
 #[derive(Debug)]
 pub struct ProducerConsumerPipeline {
-    receiver: Receiver<Vec<Record>>,
+    receiver: Receiver<Batch>,
     /// Records drained from the most recent batch but not yet handed out. The
     /// channel delivers a `Vec<Record>` per chunk; the consumer hands records
     /// out one at a time from here. A `Mutex` provides the interior mutability
     /// the `&self` accessors need; the consumer is single-threaded, so the lock
     /// is uncontended and is never held across the blocking channel `recv`.
-    buffer: Mutex<VecDeque<Record>>,
+    buffer: Mutex<VecDeque<(Record, u64)>>,
+    /// File offset just past the last record handed out.
+    position: AtomicU64,
+    /// Records handed out and the bytes they span.
+    stats: Stats,
     /// Optional handle to producer thread for join semantics
     _producer_handle: Option<thread::JoinHandle<PipelineResult<()>>>,
 }
@@ -165,23 +188,69 @@ impl ProducerConsumerPipeline {
     ///
     /// Returns `PipelineError::IoError` if file cannot be opened.
     pub fn from_file(path: &str, config: &PipelineConfig) -> PipelineResult<Self> {
-        let file = File::open(path).map_err(|e| PipelineError::IoError(e.to_string()))?;
+        Self::from_file_at(path, 0, config)
+    }
+
+    /// Create a pipeline that starts reading `path` at byte `offset`, which
+    /// must be the start of a record — typically a [`position`] saved by an
+    /// earlier run (see [`crate::checkpoint`]).
+    ///
+    /// [`position`]: ProducerConsumerPipeline::position
+    ///
+    /// # Errors
+    ///
+    /// Returns `PipelineError::IoError` if the file cannot be opened or
+    /// positioned.
+    pub fn from_file_at(path: &str, offset: u64, config: &PipelineConfig) -> PipelineResult<Self> {
+        let mut file = File::open(path).map_err(|e| PipelineError::IoError(e.to_string()))?;
+        file.seek(SeekFrom::Start(offset))
+            .map_err(|e| PipelineError::IoError(e.to_string()))?;
 
         let (sender, receiver) = bounded(config.channel_capacity);
 
         let producer_config = config.clone();
-        let producer_handle = thread::spawn(move || producer_task(file, &sender, &producer_config));
+        let producer_handle =
+            thread::spawn(move || producer_task(file, offset, &sender, &producer_config));
 
         Ok(ProducerConsumerPipeline {
             receiver,
             buffer: Mutex::new(VecDeque::new()),
+            position: AtomicU64::new(offset),
+            stats: Stats::new(),
             _producer_handle: Some(producer_handle),
         })
     }
 
+    /// File offset just past the last record returned by [`next`] or
+    /// [`try_next`]: where a resumed run should start once that record has
+    /// been processed.
+    ///
+    /// [`next`]: ProducerConsumerPipeline::next
+    /// [`try_next`]: ProducerConsumerPipeline::try_next
+    #[must_use]
+    pub fn position(&self) -> u64 {
+        self.position.load(Ordering::Relaxed)
+    }
+
+    /// Throughput of the records handed out so far; the clock starts when
+    /// the pipeline is created. Clone the handle to watch it from another
+    /// thread.
+    #[must_use]
+    pub fn stats(&self) -> &Stats {
+        &self.stats
+    }
+
+    /// Hand out the next buffered record, advancing [`position`](Self::position).
+    fn pop_buffered(&self) -> Option<Record> {
+        let (record, end) = self.lock_buffer().pop_front()?;
+        let start = self.position.swap(end, Ordering::Relaxed);
+        self.stats.add_record(end.saturating_sub(start));
+        Some(record)
+    }
+
     /// Lock the local record buffer, recovering from a poisoned lock (a
     /// poisoned buffer still holds valid records — no need to abort).
-    fn lock_buffer(&self) -> MutexGuard<'_, VecDeque<Record>> {
+    fn lock_buffer(&self) -> MutexGuard<'_, VecDeque<(Record, u64)>> {
         self.buffer.lock().unwrap_or_else(PoisonError::into_inner)
     }
 
@@ -199,7 +268,7 @@ impl ProducerConsumerPipeline {
         use crossbeam_channel::TryRecvError;
 
         loop {
-            if let Some(record) = self.lock_buffer().pop_front() {
+            if let Some(record) = self.pop_buffered() {
                 return Ok(Some(record));
             }
             // Buffer empty: pull the next batch without blocking and refill.
@@ -222,7 +291,7 @@ impl ProducerConsumerPipeline {
     /// Currently returns Ok(None) on channel disconnection.
     pub fn next(&self) -> PipelineResult<Option<Record>> {
         loop {
-            if let Some(record) = self.lock_buffer().pop_front() {
+            if let Some(record) = self.pop_buffered() {
                 return Ok(Some(record));
             }
             // Buffer empty: block for the next batch, then refill. The lock is
@@ -245,10 +314,16 @@ impl ProducerConsumerPipeline {
             .buffer
             .into_inner()
             .unwrap_or_else(PoisonError::into_inner);
+        let stats = self.stats;
+        let mut position = self.position.into_inner();
         buffered
             .into_iter()
             .chain(self.receiver.into_iter().flatten())
-            .map(Ok)
+            .map(move |(record, end)| {
+                stats.add_record(end.saturating_sub(position));
+                position = end;
+                Ok(record)
+            })
     }
 }
 
@@ -0,0 +1,531 @@
+//! Cataloging profile validation (BIBCO, CONSER and local profiles).
+//!
+//! Structural validation ([`crate::RecordStructureValidator`]) answers "is
+//! this a well-formed MARC record?". A [`Profile`] answers a different
+//! question: "does this record carry the elements a given cataloging
+//! standard requires?" — for example the PCC BIBCO Standard Record (BSR) or
+//! a CONSER minimal-level serial record.
+//!
+//! A profile is a list of [`ProfileElement`]s, each naming a tag pattern
+//! (`245`, `1XX`, `260|264`), an [`Obligation`], the subfields that must be
+//! present when the field is, and whether the field may repeat. Closed
+//! profiles additionally report fields the profile does not mention.
+//!
+//! Profiles can be built in code, taken from the built-in
+//! [`Profile::bibco_standard_record`] and [`Profile::conser_minimal`], or
+//! loaded from TOML with the `toml` cargo feature:
+//!
+//! ```
+//! # #[cfg(feature = "toml")]
+//! # {
+//! use mrrc::profile::Profile;
+//! use mrrc::{Field, Leader, Record};
+//!
+//! let profile = Profile::from_toml(
+//!     r#"
+//!     name = "Local e-book"
+//!
+//!     [[element]]
+//!     tag = "245"
+//!     subfields = ["a"]
+//!     repeatable = false
+//!
+//!     [[element]]
+//!     tag = "856"
+//!     subfields = ["u"]
+//!     "#,
+//! )
+//! .unwrap();
+//!
+//! let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+//! record.add_field(
+//!     Field::builder("245".to_string(), '0', '0')
+//!         .subfield_str('a', "Title")
+//!         .build(),
+//! );
+//!
+//! let report = profile.validate(&record);
+//! assert!(!report.is_valid());
+//! assert_eq!(report.missing().count(), 1);
+//! assert_eq!(report.issues[0].to_string(), "missing mandatory field 856");
+//! # }
+//! ```
+//!
+//! The built-in profiles summarize the published PCC standards for quick
+//! QA checks; they do not check fixed-field values or content rules.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "toml")]
+use crate::error::{MarcError, Result};
+use crate::field_protection::tag_matches;
+use crate::record::Record;
+
+/// How strongly a profile requires an element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Obligation {
+    /// The field must be present.
+    #[default]
+    Mandatory,
+    /// The field must be present when applicable to the resource. Absence
+    /// is not reported, but subfield and repeatability rules still apply.
+    IfApplicable,
+    /// The field may be present.
+    Optional,
+}
+
+/// One element (field) of a cataloging profile.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProfileElement {
+    /// Tag pattern: a three-character tag where `X` matches any digit,
+    /// optionally with `|`-separated alternatives (`"260|264"`, `"1XX"`).
+    pub tag: String,
+    /// Whether the element is required.
+    #[serde(default)]
+    pub obligation: Obligation,
+    /// Subfields that must be present in every occurrence of the field.
+    #[serde(default)]
+    pub subfields: Vec<char>,
+    /// Whether the element may occur more than once.
+    #[serde(default = "default_repeatable")]
+    pub repeatable: bool,
+}
+
+fn default_repeatable() -> bool {
+    true
+}
+
+impl ProfileElement {
+    /// A mandatory element with no subfield requirements.
+    #[must_use]
+    pub fn mandatory(tag: &str) -> Self {
+        ProfileElement {
+            tag: tag.to_string(),
+            obligation: Obligation::Mandatory,
+            subfields: Vec::new(),
+            repeatable: true,
+        }
+    }
+
+    /// A mandatory-if-applicable element with no subfield requirements.
+    #[must_use]
+    pub fn if_applicable(tag: &str) -> Self {
+        ProfileElement {
+            obligation: Obligation::IfApplicable,
+            ..Self::mandatory(tag)
+        }
+    }
+
+    /// Require `codes` in every occurrence of the field.
+    #[must_use]
+    pub fn with_subfields(mut self, codes: &[char]) -> Self {
+        self.subfields = codes.to_vec();
+        self
+    }
+
+    /// Mark the element as non-repeatable.
+    #[must_use]
+    pub fn non_repeatable(mut self) -> Self {
+        self.repeatable = false;
+        self
+    }
+
+    /// Whether `tag` matches this element's tag pattern.
+    #[must_use]
+    pub fn matches_tag(&self, tag: &str) -> bool {
+        self.tag
+            .split('|')
+            .any(|pattern| tag_matches(pattern.trim(), tag))
+    }
+}
+
+/// A cataloging profile: a named set of element requirements.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Profile {
+    /// Profile name, used in reports.
+    pub name: String,
+    /// When `true`, fields not matched by any element are reported as
+    /// [`ProfileIssue::ExtraElement`].
+    #[serde(default)]
+    pub closed: bool,
+    /// Element requirements, in the order they are checked.
+    #[serde(default, rename = "element")]
+    pub elements: Vec<ProfileElement>,
+}
+
+impl Profile {
+    /// Create an empty, open profile.
+    #[must_use]
+    pub fn new(name: &str) -> Self {
+        Profile {
+            name: name.to_string(),
+            closed: false,
+            elements: Vec::new(),
+        }
+    }
+
+    /// Add an element requirement.
+    #[must_use]
+    pub fn element(mut self, element: ProfileElement) -> Self {
+        self.elements.push(element);
+        self
+    }
+
+    /// Parse a profile from TOML.
+    ///
+    /// The document has a top-level `name`, an optional `closed` flag, and
+    /// an `[[element]]` table per element with `tag`, `obligation`
+    /// (`"mandatory"`, `"if-applicable"` or `"optional"`; default
+    /// mandatory), `subfields` and `repeatable` (default `true`).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MarcError::InvalidField`] if the TOML is malformed or does
+    /// not describe a profile.
+    #[cfg(feature = "toml")]
+    pub fn from_toml(source: &str) -> Result<Self> {
+        toml::from_str(source)
+            .map_err(|e| MarcError::invalid_field_msg(format!("Invalid profile definition: {e}")))
+    }
+
+    /// PCC BIBCO Standard Record (BSR) for monographs.
+    #[must_use]
+    pub fn bibco_standard_record() -> Self {
+        Profile::new("BIBCO Standard Record")
+            .element(ProfileElement::mandatory("008").non_repeatable())
+            .element(ProfileElement::if_applicable("010").non_repeatable())
+            .element(ProfileElement::if_applicable("020"))
+            .element(
+                ProfileElement::mandatory("040")
+                    .with_subfields(&['a', 'c'])
+                    .non_repeatable(),
+            )
+            .element(
+                ProfileElement::mandatory("042")
+                    .with_subfields(&['a'])
+                    .non_repeatable(),
+            )
+            .element(
+                ProfileElement::if_applicable("1XX")
+                    .with_subfields(&['a'])
+                    .non_repeatable(),
+            )
+            .element(ProfileElement::if_applicable("240").non_repeatable())
+            .element(
+                ProfileElement::mandatory("245")
+                    .with_subfields(&['a'])
+                    .non_repeatable(),
+            )
+            .element(ProfileElement::if_applicable("246"))
+            .element(ProfileElement::if_applicable("250"))
+            .element(ProfileElement::mandatory("260|264"))
+            .element(ProfileElement::mandatory("300").with_subfields(&['a']))
+            .element(ProfileElement::mandatory("336"))
+            .element(ProfileElement::mandatory("337"))
+            .element(ProfileElement::mandatory("338"))
+            .element(ProfileElement::if_applicable("490").with_subfields(&['a']))
+            .element(ProfileElement::if_applicable("6XX"))
+            .element(ProfileElement::if_applicable("7XX"))
+            .element(ProfileElement::if_applicable("8XX"))
+    }
+
+    /// CONSER minimal-level record for serials.
+    #[must_use]
+    pub fn conser_minimal() -> Self {
+        Profile::new("CONSER minimal")
+            .element(ProfileElement::mandatory("008").non_repeatable())
+            .element(ProfileElement::if_applicable("022"))
+            .element(
+                ProfileElement::mandatory("040")
+                    .with_subfields(&['a', 'c'])
+                    .non_repeatable(),
+            )
+            .element(
+                ProfileElement::mandatory("042")
+                    .with_subfields(&['a'])
+                    .non_repeatable(),
+            )
+            .element(ProfileElement::if_applicable("1XX").non_repeatable())
+            .element(
+                ProfileElement::mandatory("245")
+                    .with_subfields(&['a'])
+                    .non_repeatable(),
+            )
+            .element(ProfileElement::if_applicable("246"))
+            .element(ProfileElement::if_applicable("250"))
+            .element(ProfileElement::mandatory("260|264"))
+            .element(ProfileElement::if_applicable("310").non_repeatable())
+            .element(ProfileElement::if_applicable("362"))
+            .element(ProfileElement::mandatory("336"))
+            .element(ProfileElement::mandatory("337"))
+            .element(ProfileElement::mandatory("338"))
+            .element(ProfileElement::if_applicable("588"))
+    }
+
+    /// Check `record` against the profile.
+    #[must_use]
+    pub fn validate(&self, record: &Record) -> ProfileReport {
+        let mut issues = Vec::new();
+
+        for element in &self.elements {
+            let mut count = 0;
+            let mut matched_tag = None;
+            for (tag, _) in record.control_fields_iter() {
+                if element.matches_tag(tag) {
+                    count += 1;
+                    matched_tag.get_or_insert(tag);
+                }
+            }
+            for (tag, fields) in &record.fields {
+                if !element.matches_tag(tag) {
+                    continue;
+                }
+                matched_tag.get_or_insert(tag.as_str());
+                for (occurrence, field) in fields.iter().enumerate() {
+                    count += 1;
+                    for &code in &element.subfields {
+                        if field.get_subfield(code).is_none() {
+                            issues.push(ProfileIssue::MissingSubfield {
+                                tag: tag.clone(),
+                                occurrence,
+                                code,
+                            });
+                        }
+                    }
+                }
+            }
+
+            if count == 0 && element.obligation == Obligation::Mandatory {
+                issues.push(ProfileIssue::MissingElement {
+                    tag: element.tag.clone(),
+                });
+            }
+            if count > 1 && !element.repeatable {
+                issues.push(ProfileIssue::RepeatedElement {
+                    tag: matched_tag.unwrap_or(&element.tag).to_string(),
+                    count,
+                });
+            }
+        }
+
+        if self.closed {
+            let tags = record
+                .control_fields_iter()
+                .map(|(tag, _)| tag)
+                .chain(record.fields.keys().map(String::as_str));
+            for tag in tags {
+                if !self.elements.iter().any(|e| e.matches_tag(tag))
+                    && !issues
+                        .iter()
+                        .any(|i| matches!(i, ProfileIssue::ExtraElement { tag: t } if t == tag))
+                {
+                    issues.push(ProfileIssue::ExtraElement {
+                        tag: tag.to_string(),
+                    });
+                }
+            }
+        }
+
+        ProfileReport {
+            profile: self.name.clone(),
+            issues,
+        }
+    }
+}
+
+/// A single way in which a record departs from a profile.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum ProfileIssue {
+    /// A mandatory element is absent.
+    MissingElement {
+        /// The element's tag pattern
+        tag: String,
+    },
+    /// A field is present but lacks a required subfield.
+    MissingSubfield {
+        /// Field tag
+        tag: String,
+        /// Zero-based occurrence among fields with this tag
+        occurrence: usize,
+        /// The missing subfield code
+        code: char,
+    },
+    /// A non-repeatable element occurs more than once.
+    RepeatedElement {
+        /// Field tag
+        tag: String,
+        /// Number of occurrences
+        count: usize,
+    },
+    /// A closed profile does not mention this field.
+    ExtraElement {
+        /// Field tag
+        tag: String,
+    },
+}
+
+impl fmt::Display for ProfileIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProfileIssue::MissingElement { tag } => write!(f, "missing mandatory field {tag}"),
+            ProfileIssue::MissingSubfield {
+                tag,
+                occurrence,
+                code,
+            } => write!(
+                f,
+                "field {tag} (occurrence {occurrence}) is missing ${code}"
+            ),
+            ProfileIssue::RepeatedElement { tag, count } => {
+                write!(f, "non-repeatable field {tag} occurs {count} times")
+            },
+            ProfileIssue::ExtraElement { tag } => write!(f, "field {tag} is not in the profile"),
+        }
+    }
+}
+
+/// Result of [`Profile::validate`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProfileReport {
+    /// Name of the profile the record was checked against.
+    pub profile: String,
+    /// Every departure from the profile, in the order found.
+    pub issues: Vec<ProfileIssue>,
+}
+
+impl ProfileReport {
+    /// Whether the record satisfies the profile.
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Missing fields and subfields.
+    pub fn missing(&self) -> impl Iterator<Item = &ProfileIssue> {
+        self.issues.iter().filter(|i| {
+            matches!(
+                i,
+                ProfileIssue::MissingElement { .. } | ProfileIssue::MissingSubfield { .. }
+            )
+        })
+    }
+
+    /// Fields present in the record but not allowed by the profile, either
+    /// because a closed profile does not list them or because they repeat.
+    pub fn extra(&self) -> impl Iterator<Item = &ProfileIssue> {
+        self.issues.iter().filter(|i| {
+            matches!(
+                i,
+                ProfileIssue::ExtraElement { .. } | ProfileIssue::RepeatedElement { .. }
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::leader::Leader;
+    use crate::record::Field;
+
+    fn field(tag: &str, subfields: &[(char, &str)]) -> Field {
+        let mut field = Field::new(tag.to_string(), ' ', ' ');
+        for (code, value) in subfields {
+            field.add_subfield_str(*code, value);
+        }
+        field
+    }
+
+    fn bsr_record() -> Record {
+        let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 i 4500").unwrap());
+        record.add_control_field_str("008", "240101s2024    nyu           000 0 eng d");
+        record.add_field(field(
+            "040",
+            &[('a', "DLC"), ('b', "eng"), ('e', "rda"), ('c', "DLC")],
+        ));
+        record.add_field(field("042", &[('a', "pcc")]));
+        record.add_field(field("100", &[('a', "Author, A.")]));
+        record.add_field(field("245", &[('a', "Title")]));
+        record.add_field(field(
+            "264",
+            &[('a', "New York"), ('b', "Pub"), ('c', "2024")],
+        ));
+        record.add_field(field("300", &[('a', "200 pages")]));
+        record.add_field(field("336", &[('a', "text")]));
+        record.add_field(field("337", &[('a', "unmediated")]));
+        record.add_field(field("338", &[('a', "volume")]));
+        record
+    }
+
+    #[test]
+    fn test_bibco_standard_record_passes() {
+        let report = Profile::bibco_standard_record().validate(&bsr_record());
+        assert!(report.is_valid(), "{:?}", report.issues);
+        assert_eq!(report.profile, "BIBCO Standard Record");
+    }
+
+    #[test]
+    fn test_missing_and_repeated_elements() {
+        let mut record = bsr_record();
+        record.remove_fields_by_tag("264");
+        record.remove_fields_by_tag("040");
+        record.add_field(field("040", &[('a', "DLC")]));
+        record.add_field(field("110", &[('a', "Body")]));
+
+        let report = Profile::bibco_standard_record().validate(&record);
+        assert!(!report.is_valid());
+        assert!(report.issues.contains(&ProfileIssue::MissingSubfield {
+            tag: "040".to_string(),
+            occurrence: 0,
+            code: 'c',
+        }));
+        assert!(report.issues.contains(&ProfileIssue::MissingElement {
+            tag: "260|264".to_string(),
+        }));
+        assert!(report.issues.contains(&ProfileIssue::RepeatedElement {
+            tag: "100".to_string(),
+            count: 2,
+        }));
+        assert_eq!(report.missing().count(), 2);
+        assert_eq!(report.extra().count(), 1);
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_closed_profile_reports_extra_fields() {
+        let profile = Profile::from_toml(
+            r#"
+            name = "Tiny"
+            closed = true
+
+            [[element]]
+            tag = "245"
+
+            [[element]]
+            tag = "5XX"
+            obligation = "optional"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(profile.elements[1].obligation, Obligation::Optional);
+
+        let mut record = bsr_record();
+        record.add_field(field("500", &[('a', "Note")]));
+        let report = profile.validate(&record);
+        let extra: Vec<String> = report.extra().map(ToString::to_string).collect();
+        assert!(extra.contains(&"field 008 is not in the profile".to_string()));
+        assert!(extra.contains(&"field 100 is not in the profile".to_string()));
+        assert!(!extra.iter().any(|e| e.contains("500") || e.contains("245")));
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_invalid_toml_is_an_error() {
+        assert!(Profile::from_toml("name = ").is_err());
+        assert!(Profile::from_toml("[[element]]\ntag = \"245\"").is_err());
+    }
+}
@@ -0,0 +1,368 @@
+//! Statistical profiling of a record stream.
+//!
+//! Collection analysis and pre-migration assessment start from the same
+//! questions: which fields and subfields are used and how often, what
+//! indicator values appear, how records are encoded, what kinds of records
+//! the file holds, and how large they are. [`RecordProfiler`] accumulates
+//! those counts one record at a time, so it works on streams of any size
+//! and can be fed from any [`FormatReader`]. Profiles of separate chunks
+//! (for example from parallel readers) can be combined with
+//! [`RecordProfiler::merge`].
+//!
+//! The finished profile serializes to JSON with [`RecordProfiler::to_json`]
+//! or to a long-format CSV table with [`RecordProfiler::to_csv`].
+//!
+//! (Not to be confused with [`crate::profile`], which checks records
+//! against cataloging standards.)
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::profiler::RecordProfiler;
+//! use mrrc::MarcReader;
+//!
+//! let file = std::fs::File::open("tests/data/multi_records.mrc").unwrap();
+//! let mut reader = MarcReader::new(file);
+//!
+//! let mut profiler = RecordProfiler::new();
+//! let read = profiler.consume(&mut reader).unwrap();
+//! assert_eq!(read, profiler.records);
+//! assert!(profiler.tags["245"].subfields[&'a'] >= read);
+//!
+//! let csv = profiler.to_csv();
+//! assert!(csv.starts_with("section,tag,code,count\n"));
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{MarcError, Result};
+use crate::formats::FormatReader;
+use crate::record::Record;
+
+/// Usage counts for one tag.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TagStats {
+    /// Total occurrences of the tag
+    pub occurrences: u64,
+    /// Number of records containing the tag at least once
+    pub records: u64,
+    /// Occurrences of each subfield code (empty for control fields)
+    pub subfields: BTreeMap<char, u64>,
+    /// Distribution of first-indicator values (empty for control fields)
+    pub indicator1: BTreeMap<char, u64>,
+    /// Distribution of second-indicator values (empty for control fields)
+    pub indicator2: BTreeMap<char, u64>,
+}
+
+impl TagStats {
+    fn merge(&mut self, other: &TagStats) {
+        self.occurrences += other.occurrences;
+        self.records += other.records;
+        merge_counts(&mut self.subfields, &other.subfields);
+        merge_counts(&mut self.indicator1, &other.indicator1);
+        merge_counts(&mut self.indicator2, &other.indicator2);
+    }
+}
+
+/// Record length statistics, taken from leader/00-04.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SizeStats {
+    /// Number of records with a non-zero record length
+    pub count: u64,
+    /// Smallest record length in bytes
+    pub min: u32,
+    /// Largest record length in bytes
+    pub max: u32,
+    /// Sum of record lengths in bytes
+    pub total: u64,
+}
+
+impl SizeStats {
+    fn add(&mut self, length: u32) {
+        if self.count == 0 || length < self.min {
+            self.min = length;
+        }
+        self.max = self.max.max(length);
+        self.total += u64::from(length);
+        self.count += 1;
+    }
+
+    fn merge(&mut self, other: &SizeStats) {
+        if other.count == 0 {
+            return;
+        }
+        if self.count == 0 || other.min < self.min {
+            self.min = other.min;
+        }
+        self.max = self.max.max(other.max);
+        self.total += other.total;
+        self.count += other.count;
+    }
+
+    /// Mean record length in bytes, or `None` if no lengths were seen.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn average(&self) -> Option<f64> {
+        (self.count > 0).then(|| self.total as f64 / self.count as f64)
+    }
+}
+
+/// Accumulates field, subfield, indicator, leader and size statistics over
+/// a stream of records.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordProfiler {
+    /// Number of records profiled
+    pub records: u64,
+    /// Statistics per tag, control fields included
+    pub tags: BTreeMap<String, TagStats>,
+    /// Distribution of leader/09 (character coding scheme: blank for
+    /// MARC-8, `a` for UCS/Unicode)
+    pub character_coding: BTreeMap<char, u64>,
+    /// Distribution of leader/06 (type of record)
+    pub record_types: BTreeMap<char, u64>,
+    /// Distribution of leader/07 (bibliographic level)
+    pub bibliographic_levels: BTreeMap<char, u64>,
+    /// Record length statistics
+    pub sizes: SizeStats,
+}
+
+impl RecordProfiler {
+    /// Create an empty profiler.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add one record to the profile.
+    ///
+    /// Sizes come from the leader's record length, so records built in
+    /// memory (length 0) are counted everywhere except [`Self::sizes`].
+    pub fn add(&mut self, record: &Record) {
+        self.records += 1;
+        let leader = &record.leader;
+        *self
+            .character_coding
+            .entry(leader.character_coding)
+            .or_default() += 1;
+        *self.record_types.entry(leader.record_type).or_default() += 1;
+        *self
+            .bibliographic_levels
+            .entry(leader.bibliographic_level)
+            .or_default() += 1;
+        if leader.record_length > 0 {
+            self.sizes.add(leader.record_length);
+        }
+
+        for (tag, values) in &record.control_fields {
+            let stats = self.tags.entry(tag.clone()).or_default();
+            stats.occurrences += values.len() as u64;
+            stats.records += 1;
+        }
+        for (tag, fields) in &record.fields {
+            let stats = self.tags.entry(tag.clone()).or_default();
+            stats.occurrences += fields.len() as u64;
+            stats.records += 1;
+            for field in fields {
+                *stats.indicator1.entry(field.indicator1).or_default() += 1;
+                *stats.indicator2.entry(field.indicator2).or_default() += 1;
+                for subfield in &field.subfields {
+                    *stats.subfields.entry(subfield.code).or_default() += 1;
+                }
+            }
+        }
+    }
+
+    /// Profile every remaining record from `reader`. Returns the number of
+    /// records read.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error the reader reports; records read before it
+    /// remain in the profile.
+    pub fn consume<R: FormatReader + ?Sized>(&mut self, reader: &mut R) -> Result<u64> {
+        let mut read = 0;
+        while let Some(record) = reader.read_record()? {
+            self.add(&record);
+            read += 1;
+        }
+        Ok(read)
+    }
+
+    /// Fold another profile into this one.
+    pub fn merge(&mut self, other: &RecordProfiler) {
+        self.records += other.records;
+        for (tag, stats) in &other.tags {
+            self.tags.entry(tag.clone()).or_default().merge(stats);
+        }
+        merge_counts(&mut self.character_coding, &other.character_coding);
+        merge_counts(&mut self.record_types, &other.record_types);
+        merge_counts(&mut self.bibliographic_levels, &other.bibliographic_levels);
+        self.sizes.merge(&other.sizes);
+    }
+
+    /// The profile as pretty-printed JSON, with the average record size
+    /// included under `sizes.average`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MarcError::JsonError`] if serialization fails.
+    pub fn to_json(&self) -> Result<String> {
+        let mut value = serde_json::to_value(self).map_err(json_error)?;
+        if let Some(sizes) = value.get_mut("sizes").and_then(|s| s.as_object_mut()) {
+            sizes.insert(
+                "average".to_string(),
+                serde_json::json!(self.sizes.average()),
+            );
+        }
+        serde_json::to_string_pretty(&value).map_err(json_error)
+    }
+
+    /// The profile as a CSV table with columns `section,tag,code,count`.
+    ///
+    /// Sections are `records`, `record_type`, `bibliographic_level`,
+    /// `character_coding` and `size` (codes `min`, `max`, `average`) for the
+    /// whole stream, and `field`, `field_records`, `subfield`, `indicator1`
+    /// and `indicator2` per tag. Blank codes are written as `#`, the MARC
+    /// documentation convention.
+    #[must_use]
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("section,tag,code,count\n");
+        let code = |c: char| if c == ' ' { '#' } else { c };
+        let mut row = |section: &str, tag: &str, c: &str, count: &dyn std::fmt::Display| {
+            let c = if c.contains([',', '"']) {
+                format!("\"{}\"", c.replace('"', "\"\""))
+            } else {
+                c.to_string()
+            };
+            writeln!(out, "{section},{tag},{c},{count}").ok();
+        };
+
+        row("records", "", "", &self.records);
+        for (section, counts) in [
+            ("record_type", &self.record_types),
+            ("bibliographic_level", &self.bibliographic_levels),
+            ("character_coding", &self.character_coding),
+        ] {
+            for (c, count) in counts {
+                row(section, "", &code(*c).to_string(), count);
+            }
+        }
+        if let Some(average) = self.sizes.average() {
+            row("size", "", "min", &self.sizes.min);
+            row("size", "", "max", &self.sizes.max);
+            row("size", "", "average", &format!("{average:.1}"));
+        }
+        for (tag, stats) in &self.tags {
+            row("field", tag, "", &stats.occurrences);
+            row("field_records", tag, "", &stats.records);
+            for (section, counts) in [
+                ("subfield", &stats.subfields),
+                ("indicator1", &stats.indicator1),
+                ("indicator2", &stats.indicator2),
+            ] {
+                for (c, count) in counts {
+                    row(section, tag, &code(*c).to_string(), count);
+                }
+            }
+        }
+        out
+    }
+}
+
+fn merge_counts(into: &mut BTreeMap<char, u64>, from: &BTreeMap<char, u64>) {
+    for (key, count) in from {
+        *into.entry(*key).or_default() += count;
+    }
+}
+
+fn json_error(e: serde_json::Error) -> MarcError {
+    MarcError::JsonError {
+        cause: e,
+        record_index: None,
+        byte_offset: None,
+        source_name: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::leader::Leader;
+    use crate::reader::MarcReader;
+    use crate::record::Field;
+    use crate::writer::MarcWriter;
+
+    fn record(title: &str, subjects: usize) -> Record {
+        let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+        record.add_control_field_str("001", title);
+        record.add_field(
+            Field::builder("245".to_string(), '1', '0')
+                .subfield_str('a', title)
+                .build(),
+        );
+        for _ in 0..subjects {
+            record.add_field(
+                Field::builder("650".to_string(), ' ', '0')
+                    .subfield_str('a', "Maps")
+                    .subfield_str('z', "Iowa")
+                    .build(),
+            );
+        }
+        record
+    }
+
+    #[test]
+    fn test_counts_from_stream() {
+        let mut buffer = Vec::new();
+        {
+            let mut writer = MarcWriter::new(&mut buffer);
+            writer.write_record(&record("One", 2)).unwrap();
+            writer
+                .write_record(&record("Two, longer title", 0))
+                .unwrap();
+            writer.finish().unwrap();
+        }
+        let mut profiler = RecordProfiler::new();
+        let read = profiler
+            .consume(&mut MarcReader::new(std::io::Cursor::new(buffer)))
+            .unwrap();
+
+        assert_eq!(read, 2);
+        assert_eq!(profiler.record_types[&'a'], 2);
+        assert_eq!(profiler.character_coding[&'a'], 2);
+        let subjects = &profiler.tags["650"];
+        assert_eq!((subjects.occurrences, subjects.records), (2, 1));
+        assert_eq!(subjects.indicator2[&'0'], 2);
+        assert_eq!(subjects.subfields[&'z'], 2);
+        assert_eq!(profiler.tags["001"].records, 2);
+        assert!(profiler.tags["001"].subfields.is_empty());
+        assert_eq!(profiler.sizes.count, 2);
+        assert!(profiler.sizes.min < profiler.sizes.max);
+    }
+
+    #[test]
+    fn test_merge_and_reports() {
+        let mut left = RecordProfiler::new();
+        left.add(&record("A", 1));
+        let mut right = RecordProfiler::new();
+        right.add(&record("B", 3));
+        left.merge(&right);
+
+        assert_eq!(left.records, 2);
+        assert_eq!(left.tags["650"].occurrences, 4);
+        assert_eq!(left.sizes.average(), None);
+
+        let csv = left.to_csv();
+        assert!(csv.contains("records,,,2\n"));
+        assert!(csv.contains("indicator1,650,#,4\n"));
+        assert!(csv.contains("subfield,245,a,2\n"));
+        assert!(!csv.contains("size,"));
+
+        let json: serde_json::Value = serde_json::from_str(&left.to_json().unwrap()).unwrap();
+        assert_eq!(json["tags"]["650"]["subfields"]["z"], 4);
+        assert!(json["sizes"]["average"].is_null());
+    }
+}
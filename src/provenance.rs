@@ -0,0 +1,316 @@
+//! 005 transaction timestamps and processing provenance.
+//!
+//! The 005 control field records when a record was last changed, as
+//! `yyyymmddhhmmss.f` in local time with no zone. [`parse_005`] and
+//! [`format_005`] convert between that form and [`NaiveDateTime`];
+//! [`Record::transaction_timestamp`] and
+//! [`Record::set_transaction_timestamp`] apply them to a record.
+//!
+//! [`ProvenanceStamper`] records which batch process last touched a record
+//! in a local 9XX field — tool, version, date and rule set — updating its
+//! own earlier stamp rather than piling up new ones, and can wrap any
+//! [`FormatWriter`] so every written record is stamped.
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::provenance::ProvenanceStamper;
+//! use mrrc::{Leader, MarcWriter, Record};
+//! use mrrc::formats::FormatWriter;
+//!
+//! let stamper = ProvenanceStamper::new("998", "authority-cleanup")
+//!     .version("2.3.0")
+//!     .rule_set("lcnaf-2024");
+//!
+//! let mut buffer = Vec::new();
+//! let mut writer = stamper.wrap(MarcWriter::new(&mut buffer));
+//! let record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+//! writer.write_record(&record).unwrap();
+//! writer.finish().unwrap();
+//! ```
+//!
+//! A stamped record carries a field like
+//! `998 __ $a authority-cleanup $b 2.3.0 $c 2024-06-01T09:30:00 $d lcnaf-2024`
+//! and an 005 of `20240601093000.0`.
+
+use chrono::{Local, NaiveDateTime, Timelike};
+
+use crate::error::Result;
+use crate::formats::FormatWriter;
+use crate::record::{Field, Record};
+
+/// Parse a 005 value (`yyyymmddhhmmss.f`).
+///
+/// The tenths of a second are optional, and a bare `yyyymmdd` date (seen
+/// in older records) is read as midnight. Returns `None` for anything else,
+/// including impossible dates.
+#[must_use]
+pub fn parse_005(value: &str) -> Option<NaiveDateTime> {
+    let value = value.trim();
+    let (digits, tenths) = match value.split_once('.') {
+        Some((digits, fraction)) => (digits, Some(fraction)),
+        None => (value, None),
+    };
+    if !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let date_time = match digits.len() {
+        14 => NaiveDateTime::parse_from_str(digits, "%Y%m%d%H%M%S").ok()?,
+        8 if tenths.is_none() => chrono::NaiveDate::parse_from_str(digits, "%Y%m%d")
+            .ok()?
+            .and_hms_opt(0, 0, 0)?,
+        _ => return None,
+    };
+    match tenths {
+        None => Some(date_time),
+        Some(fraction) => {
+            let tenth = fraction
+                .parse::<u32>()
+                .ok()
+                .filter(|_| fraction.len() == 1)?;
+            date_time.with_nanosecond(tenth * 100_000_000)
+        },
+    }
+}
+
+/// Format a date and time as a 005 value (`yyyymmddhhmmss.f`).
+#[must_use]
+pub fn format_005(date_time: &NaiveDateTime) -> String {
+    format!(
+        "{}.{}",
+        date_time.format("%Y%m%d%H%M%S"),
+        (date_time.nanosecond() % 1_000_000_000) / 100_000_000
+    )
+}
+
+/// Stamps records with a local field describing the process that touched
+/// them.
+///
+/// The field has `$a` tool, `$b` version, `$c` date and time (ISO 8601,
+/// local time) and `$d` rule set; unset parts are left out. A field with
+/// the configured tag whose `$a` already names this tool is replaced, so
+/// re-running a process updates its stamp. Stamps from other tools are
+/// kept.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProvenanceStamper {
+    tag: String,
+    tool: String,
+    version: Option<String>,
+    rule_set: Option<String>,
+    at: Option<NaiveDateTime>,
+    update_005: bool,
+}
+
+impl ProvenanceStamper {
+    /// Stamp fields with `tag` (normally a 9XX local field) naming `tool`.
+    #[must_use]
+    pub fn new(tag: &str, tool: &str) -> Self {
+        ProvenanceStamper {
+            tag: tag.to_string(),
+            tool: tool.to_string(),
+            version: None,
+            rule_set: None,
+            at: None,
+            update_005: true,
+        }
+    }
+
+    /// Tool version for `$b`.
+    #[must_use]
+    pub fn version(mut self, version: &str) -> Self {
+        self.version = Some(version.to_string());
+        self
+    }
+
+    /// Rule set or configuration name for `$d`.
+    #[must_use]
+    pub fn rule_set(mut self, rule_set: &str) -> Self {
+        self.rule_set = Some(rule_set.to_string());
+        self
+    }
+
+    /// Use a fixed processing time instead of the clock, so every record
+    /// in a batch carries the same time.
+    #[must_use]
+    pub fn at(mut self, date_time: NaiveDateTime) -> Self {
+        self.at = Some(date_time);
+        self
+    }
+
+    /// Whether stamping also sets 005 to the processing time (default
+    /// `true`).
+    #[must_use]
+    pub fn update_005(mut self, update: bool) -> Self {
+        self.update_005 = update;
+        self
+    }
+
+    /// Add or update this tool's stamp on `record`.
+    pub fn stamp(&self, record: &mut Record) {
+        let now = self.at.unwrap_or_else(|| Local::now().naive_local());
+        let mut builder = Field::builder(self.tag.clone(), ' ', ' ').subfield_str('a', &self.tool);
+        if let Some(version) = &self.version {
+            builder = builder.subfield_str('b', version);
+        }
+        builder = builder.subfield_str('c', &now.format("%Y-%m-%dT%H:%M:%S").to_string());
+        if let Some(rule_set) = &self.rule_set {
+            builder = builder.subfield_str('d', rule_set);
+        }
+        let stamp = builder.build();
+
+        let existing = record
+            .fields_by_tag_mut(&self.tag)
+            .find(|field| field.get_subfield('a') == Some(self.tool.as_str()));
+        match existing {
+            Some(field) => *field = stamp,
+            None => record.add_field(stamp),
+        }
+        if self.update_005 {
+            record.set_transaction_timestamp(&now);
+        }
+    }
+
+    /// Wrap `writer` so every record written through it is stamped.
+    #[must_use]
+    pub fn wrap<W: FormatWriter>(self, writer: W) -> StampingWriter<W> {
+        StampingWriter {
+            stamper: self,
+            writer,
+        }
+    }
+}
+
+/// A [`FormatWriter`] that stamps each record before writing it.
+///
+/// Created by [`ProvenanceStamper::wrap`]. The caller's record is left
+/// unchanged; a stamped copy is written.
+#[derive(Debug)]
+pub struct StampingWriter<W> {
+    stamper: ProvenanceStamper,
+    writer: W,
+}
+
+impl<W> StampingWriter<W> {
+    /// Unwrap the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: FormatWriter> FormatWriter for StampingWriter<W> {
+    fn write_record(&mut self, record: &Record) -> Result<()> {
+        let mut record = record.clone();
+        self.stamper.stamp(&mut record);
+        self.writer.write_record(&record)
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        self.writer.finish()
+    }
+
+    fn records_written(&self) -> Option<usize> {
+        self.writer.records_written()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::leader::Leader;
+
+    fn at(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_format_005() {
+        let parsed = parse_005("19940223151047.0").unwrap();
+        assert_eq!(parsed, at("1994-02-23 15:10:47"));
+        assert_eq!(format_005(&parsed), "19940223151047.0");
+        assert_eq!(
+            format_005(&parse_005("20240601093000.7").unwrap()),
+            "20240601093000.7"
+        );
+        assert_eq!(parse_005("19850104"), Some(at("1985-01-04 00:00:00")));
+        for bad in [
+            "",
+            "2024",
+            "20241301000000.0",
+            "20240601093000.75",
+            "2024060109300x",
+        ] {
+            assert_eq!(parse_005(bad), None, "{bad}");
+        }
+    }
+
+    #[test]
+    fn test_record_timestamp() {
+        let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+        assert_eq!(record.transaction_timestamp(), None);
+        record.add_control_field_str("005", "20200101000000.0");
+        record.set_transaction_timestamp(&at("2024-06-01 09:30:00"));
+        assert_eq!(record.control_fields["005"], ["20240601093000.0"]);
+        assert_eq!(
+            record.transaction_timestamp(),
+            Some(at("2024-06-01 09:30:00"))
+        );
+    }
+
+    #[test]
+    fn test_stamp_updates_own_field() {
+        let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+        record.add_field(
+            Field::builder("998".to_string(), ' ', ' ')
+                .subfield_str('a', "other-tool")
+                .build(),
+        );
+        let stamper = ProvenanceStamper::new("998", "cleanup")
+            .version("1.0")
+            .rule_set("rules-a")
+            .at(at("2024-06-01 09:30:00"));
+        stamper.stamp(&mut record);
+        stamper
+            .clone()
+            .version("1.1")
+            .at(at("2024-07-01 10:00:00"))
+            .stamp(&mut record);
+
+        let stamps: Vec<Vec<&str>> = record
+            .fields_by_tag("998")
+            .map(|f| f.subfields.iter().map(|s| s.value.as_str()).collect())
+            .collect();
+        assert_eq!(
+            stamps,
+            [
+                vec!["other-tool"],
+                vec!["cleanup", "1.1", "2024-07-01T10:00:00", "rules-a"]
+            ]
+        );
+        assert_eq!(record.get_control_field("005"), Some("20240701100000.0"));
+    }
+
+    #[test]
+    fn test_stamping_writer() {
+        let stamper = ProvenanceStamper::new("998", "cleanup")
+            .update_005(false)
+            .at(at("2024-06-01 09:30:00"));
+        let record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+        let mut buffer = Vec::new();
+        let mut writer = stamper.wrap(crate::writer::MarcWriter::new(&mut buffer));
+        writer.write_record(&record).unwrap();
+        writer.finish().unwrap();
+        assert_eq!(writer.records_written(), Some(1));
+        drop(writer);
+
+        assert!(record.get_field("998").is_none());
+        let written = crate::reader::MarcReader::new(buffer.as_slice())
+            .read_record()
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            written.get_field("998").unwrap().get_subfield('c'),
+            Some("2024-06-01T09:30:00")
+        );
+        assert_eq!(written.get_control_field("005"), None);
+    }
+}
@@ -0,0 +1,327 @@
+//! ISBD punctuation removal and insertion.
+//!
+//! MARC 21 records traditionally carry ISBD punctuation at the end of each
+//! subfield (`245 $a Title : $b subtitle / $c by A. Author.`). PCC records
+//! increasingly omit it, leaving punctuation to display software, so
+//! records exchanged between the two conventions need converting in both
+//! directions.
+//!
+//! [`strip_field_punctuation`] removes trailing separators (` :`, ` ;`,
+//! ` /`, ` =`, ` +`, `,`) and terminal periods while keeping periods that
+//! end an initial or a common abbreviation (`J.`, `U.S.`, `Jr.`, `v.`).
+//! [`add_field_punctuation`] puts the separator that precedes each
+//! subfield back, following per-tag rules for the fields where ISBD
+//! punctuation is conventional, and adds a terminal period where one
+//! belongs. [`strip_punctuation`] and [`add_punctuation`] apply these to
+//! a whole record.
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::punctuation::{add_field_punctuation, strip_field_punctuation};
+//! use mrrc::Field;
+//!
+//! let mut field = Field::builder("245".to_string(), '1', '0')
+//!     .subfield_str('a', "Roughing it :")
+//!     .subfield_str('b', "a memoir /")
+//!     .subfield_str('c', "by Mark Twain.")
+//!     .build();
+//!
+//! assert!(strip_field_punctuation(&mut field));
+//! assert_eq!(field.get_subfield('a'), Some("Roughing it"));
+//! assert_eq!(field.get_subfield('c'), Some("by Mark Twain"));
+//!
+//! assert!(add_field_punctuation(&mut field));
+//! assert_eq!(field.get_subfield('a'), Some("Roughing it :"));
+//! assert_eq!(field.get_subfield('b'), Some("a memoir /"));
+//! assert_eq!(field.get_subfield('c'), Some("by Mark Twain."));
+//! ```
+
+use crate::record::{Field, Record};
+
+/// ISBD separators that may end a subfield.
+const SEPARATORS: &[&str] = &[" :", " ;", " /", " =", " +"];
+
+/// Words whose trailing period is part of an abbreviation.
+const ABBREVIATIONS: &[&str] = &[
+    "approx", "bros", "ca", "co", "corp", "dept", "dr", "ed", "eds", "etc", "ill", "inc", "jr",
+    "ltd", "mr", "mrs", "ms", "no", "nos", "pt", "pts", "rev", "sr", "st", "univ", "vol", "vols",
+];
+
+/// Characters that already end a subfield with punctuation.
+const ENDING_MARKS: &[char] = &['.', ',', ':', ';', '/', '=', '+', '?', '!', '-'];
+
+/// Punctuation rules for one tag.
+struct Rules {
+    /// Punctuation appended to the previous subfield before each code.
+    before: &'static [(char, &'static str)],
+    /// Whether the field ends with a period.
+    terminal: bool,
+}
+
+fn rules(tag: &str) -> Option<Rules> {
+    let (before, terminal): (&'static [(char, &'static str)], bool) = match tag {
+        "245" => (&[('b', " :"), ('c', " /"), ('n', "."), ('p', ".")], true),
+        "246" => (&[('b', " :")], false),
+        "250" => (&[('b', " /")], true),
+        "260" | "264" => (&[('a', " ;"), ('b', " :"), ('c', ",")], true),
+        "300" => (&[('b', " :"), ('c', " ;"), ('e', " +")], false),
+        "490" => (&[('v', " ;"), ('x', ",")], false),
+        "100" | "600" | "700" | "800" => (
+            &[
+                ('c', ","),
+                ('d', ","),
+                ('e', ","),
+                ('j', ","),
+                ('t', "."),
+                ('v', " ;"),
+            ],
+            true,
+        ),
+        "110" | "610" | "710" | "810" => (&[('b', "."), ('e', ","), ('t', "."), ('v', " ;")], true),
+        "111" | "611" | "711" | "811" => (&[('j', ","), ('t', "."), ('v', " ;")], true),
+        "130" | "630" | "730" | "830" => (&[('p', "."), ('v', " ;")], true),
+        "650" | "651" | "655" => (&[], true),
+        t if t.starts_with('5') => (&[], true),
+        _ => return None,
+    };
+    Some(Rules { before, terminal })
+}
+
+/// Remove trailing ISBD punctuation from every subfield of `field`.
+///
+/// Numeric control subfields are left alone. Returns `true` if anything
+/// changed.
+pub fn strip_field_punctuation(field: &mut Field) -> bool {
+    let mut changed = false;
+    for subfield in &mut field.subfields {
+        if subfield.code.is_ascii_digit() {
+            continue;
+        }
+        let stripped = strip_value(&subfield.value);
+        if stripped.len() != subfield.value.len() {
+            subfield.value.truncate(stripped.len());
+            changed = true;
+        }
+    }
+    changed
+}
+
+/// Add ISBD punctuation to `field` according to its tag's rules.
+///
+/// Subfields that already end in punctuation are not touched, so the
+/// function is safe to run on partially punctuated fields. Fields whose
+/// tags have no rules are left unchanged. Returns `true` if anything
+/// changed.
+pub fn add_field_punctuation(field: &mut Field) -> bool {
+    let Some(rules) = rules(&field.tag) else {
+        return false;
+    };
+    let is_245 = field.tag == "245";
+    let mut changed = false;
+
+    let content: Vec<usize> = field
+        .subfields
+        .iter()
+        .enumerate()
+        .filter(|(_, sf)| !sf.code.is_ascii_digit())
+        .map(|(i, _)| i)
+        .collect();
+
+    for pair in content.windows(2) {
+        let (prev, next) = (pair[0], pair[1]);
+        let prev_code = field.subfields[prev].code;
+        let next_code = field.subfields[next].code;
+        let mark = if is_245 && next_code == 'p' && prev_code == 'n' {
+            Some(",")
+        } else {
+            rules
+                .before
+                .iter()
+                .find(|(code, _)| *code == next_code)
+                .map(|(_, mark)| *mark)
+        };
+        if let Some(mark) = mark
+            && append_mark(&mut field.subfields[prev].value, mark)
+        {
+            changed = true;
+        }
+    }
+
+    if rules.terminal
+        && let Some(&last) = content.last()
+    {
+        let value = &mut field.subfields[last].value;
+        if !value.ends_with([')', ']', '"']) && append_mark(value, ".") {
+            changed = true;
+        }
+    }
+    changed
+}
+
+/// Strip ISBD punctuation from every data field in `record`.
+///
+/// Returns the number of fields changed.
+pub fn strip_punctuation(record: &mut Record) -> usize {
+    record
+        .fields_mut()
+        .map(strip_field_punctuation)
+        .filter(|changed| *changed)
+        .count()
+}
+
+/// Add ISBD punctuation to every data field in `record` that has rules.
+///
+/// Returns the number of fields changed.
+pub fn add_punctuation(record: &mut Record) -> usize {
+    record
+        .fields_mut()
+        .map(add_field_punctuation)
+        .filter(|changed| *changed)
+        .count()
+}
+
+/// The `codes` subfields of `field`, in field order, with trailing ISBD
+/// punctuation removed and joined with `separator`; `None` if they are all
+/// missing or empty. Used by the display-oriented exports.
+pub(crate) fn display_value(field: &Field, codes: &[char], separator: &str) -> Option<String> {
+    let value = field
+        .subfields
+        .iter()
+        .filter(|sf| codes.contains(&sf.code))
+        .map(|sf| strip_value(sf.value.trim_start()))
+        .filter(|v| !v.is_empty())
+        .collect::<Vec<_>>()
+        .join(separator);
+    (!value.is_empty()).then_some(value)
+}
+
+/// Append `mark` unless `value` is empty or already ends in punctuation.
+fn append_mark(value: &mut String, mark: &str) -> bool {
+    let trimmed_len = value.trim_end().len();
+    if trimmed_len == 0 || value[..trimmed_len].ends_with(ENDING_MARKS) {
+        return false;
+    }
+    value.truncate(trimmed_len);
+    value.push_str(mark);
+    true
+}
+
+pub(crate) fn strip_value(value: &str) -> &str {
+    let mut s = value.trim_end();
+    if let Some(sep) = SEPARATORS.iter().find(|sep| s.ends_with(*sep)) {
+        s = s[..s.len() - sep.len()].trim_end();
+    } else if let Some(rest) = s.strip_suffix(',') {
+        s = rest.trim_end();
+    }
+    if let Some(rest) = s.strip_suffix('.')
+        && !rest.ends_with('.')
+        && !ends_with_abbreviation(rest)
+    {
+        s = rest.trim_end();
+    }
+    s
+}
+
+/// Whether the word before a trailing period is an initial (`J`, `U.S`)
+/// or a common abbreviation.
+fn ends_with_abbreviation(before_period: &str) -> bool {
+    let word = before_period
+        .rsplit(|c: char| c.is_whitespace() || c == '(' || c == '[')
+        .next()
+        .unwrap_or_default();
+    let last_segment = word.rsplit('.').next().unwrap_or_default();
+    let mut chars = last_segment.chars();
+    if let (Some(c), None) = (chars.next(), chars.next())
+        && c.is_alphabetic()
+    {
+        return true;
+    }
+    ABBREVIATIONS
+        .iter()
+        .any(|abbr| word.eq_ignore_ascii_case(abbr))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(tag: &str, subfields: &[(char, &str)]) -> Field {
+        let mut field = Field::new(tag.to_string(), ' ', ' ');
+        for (code, value) in subfields {
+            field.add_subfield_str(*code, value);
+        }
+        field
+    }
+
+    fn values(field: &Field) -> Vec<&str> {
+        field.subfields.iter().map(|sf| sf.value.as_str()).collect()
+    }
+
+    #[test]
+    fn test_strip_keeps_abbreviations_and_initials() {
+        let mut f = field(
+            "100",
+            &[
+                ('a', "Smith, J. R.,"),
+                ('c', "Jr.,"),
+                ('d', "1900-1980."),
+                ('e', "ed."),
+            ],
+        );
+        assert!(strip_field_punctuation(&mut f));
+        assert_eq!(values(&f), ["Smith, J. R.", "Jr.", "1900-1980", "ed."]);
+    }
+
+    #[test]
+    fn test_publication_round_trip() {
+        let punctuated = [
+            ('a', "New York ;"),
+            ('a', "London :"),
+            ('b', "Harper,"),
+            ('c', "2001."),
+        ];
+        let mut f = field("264", &punctuated);
+        assert!(strip_field_punctuation(&mut f));
+        assert_eq!(values(&f), ["New York", "London", "Harper", "2001"]);
+        assert!(add_field_punctuation(&mut f));
+        assert_eq!(values(&f), punctuated.map(|(_, v)| v));
+    }
+
+    #[test]
+    fn test_title_parts_and_existing_punctuation() {
+        let mut f = field(
+            "245",
+            &[
+                ('a', "Works"),
+                ('n', "Part 2"),
+                ('p', "Poems?"),
+                ('c', "[by X]"),
+            ],
+        );
+        assert!(add_field_punctuation(&mut f));
+        assert_eq!(values(&f), ["Works.", "Part 2,", "Poems?", "[by X]"]);
+        assert!(!add_field_punctuation(&mut f));
+    }
+
+    #[test]
+    fn test_record_level_and_control_subfields() {
+        let mut record =
+            Record::new(crate::Leader::from_bytes(b"00000nam a2200000 i 4500").unwrap());
+        record.add_field(field(
+            "650",
+            &[('a', "Art"), ('x', "History."), ('0', "sh1.")],
+        ));
+        record.add_field(field("020", &[('a', "9780000000000")]));
+        record.add_field(field("500", &[('a', "Includes index")]));
+
+        assert_eq!(add_punctuation(&mut record), 1);
+        assert_eq!(
+            record.get_field("500").unwrap().get_subfield('a'),
+            Some("Includes index.")
+        );
+        assert_eq!(strip_punctuation(&mut record), 2);
+        let subject = record.get_field("650").unwrap();
+        assert_eq!(values(subject), ["Art", "History", "sh1."]);
+    }
+}
@@ -19,6 +19,11 @@
 //! println!("Parsed {} records in parallel", records.len());
 //! # Ok::<(), Box<dyn std::error::Error>>(())
 //! ```
+//!
+//! [`parse_batch_parallel`] fails the whole batch on the first bad record.
+//! [`parse_batch_parallel_results`] instead returns one result per record,
+//! and [`parse_batch_parallel_map`] also runs a conversion on each parsed
+//! record inside the pool.
 
 use crate::error::{MarcError, Result};
 use crate::reader::MarcReader;
@@ -68,6 +73,10 @@ pub fn parse_batch_parallel(
 ) -> Result<Vec<Record>> {
     use rayon::prelude::*;
 
+    crate::telemetry::stage_span!(
+        "mrrc.pipeline.parse_batch",
+        records = record_boundaries.len()
+    );
     // Validate all boundaries are within buffer. Use checked_add so an
     // offset near usize::MAX cannot wrap past the bounds check and panic on
     // the slice below; an overflowing sum is treated as out of bounds.
@@ -88,23 +97,118 @@ pub fn parse_batch_parallel(
     record_boundaries
         .par_iter()
         .enumerate()
-        .map(|(idx, (offset, length))| {
-            // Extract the record's bytes
-            let record_bytes = &buffer[*offset..offset + length];
-
-            // Create a cursor over the record bytes and parse it
-            let cursor = Cursor::new(record_bytes);
-            let mut reader = MarcReader::new(cursor);
-
-            reader.read_record().and_then(|opt| {
-                opt.ok_or_else(|| {
-                    MarcError::invalid_field_msg(format!(
-                        "Record {idx} at offset {offset} parsed as empty"
-                    ))
-                })
+        .map(|(idx, &(offset, length))| parse_one(idx, offset, length, buffer))
+        .collect::<Result<Vec<Record>>>()
+}
+
+/// Parse a batch of records in parallel, keeping going past bad records.
+///
+/// Unlike [`parse_batch_parallel`], a malformed record (or a boundary
+/// outside the buffer) fails only its own slot: the result holds one
+/// `(index, result)` pair per boundary, in boundary order, where `index` is
+/// the 0-based position in `record_boundaries`. Each error carries the
+/// 1-based record index and, where the parser reported one, its absolute
+/// byte offset in `buffer`.
+///
+/// # Example
+///
+/// ```no_run
+/// use mrrc::rayon_parser_pool::parse_batch_parallel_results;
+///
+/// let buffer = vec![/* MARC data */];
+/// let boundaries = vec![(0, 100), (100, 95), (195, 105)];
+/// for (index, result) in parse_batch_parallel_results(&boundaries, &buffer) {
+///     match result {
+///         Ok(record) => println!("{index}: {:?}", record.get_control_field("001")),
+///         Err(e) => eprintln!("{index}: skipped: {e}"),
+///     }
+/// }
+/// ```
+#[must_use]
+pub fn parse_batch_parallel_results(
+    record_boundaries: &[(usize, usize)],
+    buffer: &[u8],
+) -> Vec<(usize, Result<Record>)> {
+    parse_batch_parallel_map(record_boundaries, buffer, Ok)
+}
+
+/// Parse a batch of records in parallel and run `map` on each one in the
+/// same pool.
+///
+/// Lets a downstream conversion (to JSON, BIBFRAME, a database row) run in
+/// parallel with the parse instead of serially afterwards. Errors are
+/// isolated per record as in [`parse_batch_parallel_results`]; `map` is
+/// only called for records that parsed, and an error it returns lands in
+/// that record's slot.
+///
+/// # Example
+///
+/// ```no_run
+/// use mrrc::rayon_parser_pool::parse_batch_parallel_map;
+///
+/// let buffer = vec![/* MARC data */];
+/// let boundaries = vec![(0, 100), (100, 95)];
+/// let ids = parse_batch_parallel_map(&boundaries, &buffer, |record| {
+///     Ok(record.get_control_field("001").map(str::to_string))
+/// });
+/// assert_eq!(ids.len(), 2);
+/// ```
+pub fn parse_batch_parallel_map<T, F>(
+    record_boundaries: &[(usize, usize)],
+    buffer: &[u8],
+    map: F,
+) -> Vec<(usize, Result<T>)>
+where
+    T: Send,
+    F: Fn(Record) -> Result<T> + Sync,
+{
+    use rayon::prelude::*;
+
+    crate::telemetry::stage_span!(
+        "mrrc.pipeline.parse_batch",
+        records = record_boundaries.len()
+    );
+    record_boundaries
+        .par_iter()
+        .enumerate()
+        .map(|(idx, &(offset, length))| {
+            (idx, parse_one(idx, offset, length, buffer).and_then(&map))
+        })
+        .collect()
+}
+
+/// Parse the record at `buffer[offset..offset + length]`, the `idx`th in
+/// its batch.
+fn parse_one(idx: usize, offset: usize, length: usize, buffer: &[u8]) -> Result<Record> {
+    let Some(record_bytes) = offset
+        .checked_add(length)
+        .and_then(|end| buffer.get(offset..end))
+    else {
+        return Err(MarcError::invalid_field_msg(format!(
+            "Record boundary ({offset}, {length}) exceeds buffer size {}",
+            buffer.len()
+        ))
+        .with_record_index(Some(idx + 1)));
+    };
+
+    // Create a cursor over the record bytes and parse it
+    let mut reader = MarcReader::new(Cursor::new(record_bytes));
+    reader
+        .read_record()
+        .and_then(|opt| {
+            opt.ok_or_else(|| {
+                MarcError::invalid_field_msg(format!(
+                    "Record {idx} at offset {offset} parsed as empty"
+                ))
             })
         })
-        .collect::<Result<Vec<Record>>>()
+        .map_err(|e| {
+            // The reader only saw this record's bytes; shift its offset to
+            // the batch buffer.
+            let byte_offset = e.metadata().byte_offset.map(|b| b + offset);
+            e.with_record_index(Some(idx + 1))
+                .with_byte_offset(byte_offset)
+        })
 }
 
 /// Parse a limited batch of MARC records in parallel.
@@ -321,4 +425,62 @@ mod tests {
         assert_eq!(records[0].get_control_field("001"), Some("rec0000"));
         assert_eq!(records[1].get_control_field("001"), Some("rec0001"));
     }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_parse_batch_parallel_results_isolates_bad_records() {
+        let originals: Vec<Record> = (0..3)
+            .map(|i| build_test_record(&format!("rec{i:04}")))
+            .collect();
+        let (mut buffer, mut boundaries) = build_stream(&originals);
+        let second_start = boundaries[1].0;
+        for byte in &mut buffer[second_start + 27..second_start + 31] {
+            *byte = b'X';
+        }
+        boundaries.push((buffer.len(), 10));
+
+        let results = parse_batch_parallel_results(&boundaries, &buffer);
+
+        assert_eq!(results.len(), 4);
+        let indexes: Vec<usize> = results.iter().map(|(i, _)| *i).collect();
+        assert_eq!(indexes, vec![0, 1, 2, 3]);
+        assert_eq!(
+            results[0].1.as_ref().unwrap().get_control_field("001"),
+            Some("rec0000")
+        );
+        assert_eq!(
+            results[2].1.as_ref().unwrap().get_control_field("001"),
+            Some("rec0002")
+        );
+        let bad = results[1].1.as_ref().unwrap_err();
+        assert_eq!(bad.metadata().record_index, Some(2));
+        let out_of_bounds = results[3].1.as_ref().unwrap_err();
+        assert!(out_of_bounds.to_string().contains("exceeds buffer"));
+        assert_eq!(out_of_bounds.metadata().record_index, Some(4));
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_parse_batch_parallel_map_runs_function_per_record() {
+        let originals: Vec<Record> = (0..4)
+            .map(|i| build_test_record(&format!("rec{i:04}")))
+            .collect();
+        let (buffer, boundaries) = build_stream(&originals);
+
+        let results = parse_batch_parallel_map(&boundaries, &buffer, |record| {
+            let id = record.get_control_field("001").unwrap_or_default();
+            if id == "rec0002" {
+                return Err(MarcError::invalid_field_msg("rejected"));
+            }
+            Ok(id.to_string())
+        });
+
+        let mapped: Vec<_> = results
+            .into_iter()
+            .map(|(i, r)| (i, r.map_err(|e| e.to_string())))
+            .collect();
+        assert_eq!(mapped[0], (0, Ok("rec0000".to_string())));
+        assert_eq!(mapped[3], (3, Ok("rec0003".to_string())));
+        assert!(mapped[2].1.as_ref().unwrap_err().contains("rejected"));
+    }
 }
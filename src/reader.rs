@@ -34,12 +34,20 @@
 //! ```
 
 use crate::error::Result;
+use crate::fidelity::SourceRecord;
 use crate::formats::FormatReader;
-use crate::iso2709::{DataFieldParseConfig, ParseContext};
-use crate::iso2709_skeleton::{Iso2709Builder, parse_iso2709_record};
+use crate::iso2709::{
+    DataFieldParseConfig, LEADER_LEN, ParseContext, read_leader_bytes, read_record_data,
+    standardize_subfield_delimiters,
+};
+use crate::iso2709_skeleton::{
+    Iso2709Builder, parse_iso2709_record, parse_iso2709_record_from_bytes,
+};
 use crate::leader::Leader;
 use crate::record::{Field, Record};
-use crate::recovery::{RecoveryCap, RecoveryMode, ValidationLevel};
+use crate::recovery::{
+    ParseOptions, RecoveryCap, RecoveryMode, SubfieldDelimiters, ValidationLevel,
+};
 use std::io::Read;
 
 /// Buffer capacity for readers opened from a filesystem path.
@@ -76,9 +84,12 @@ pub struct MarcReader<R: Read> {
     reader: R,
     recovery_mode: RecoveryMode,
     validation_level: ValidationLevel,
+    collect_warnings: bool,
     records_read: usize,
     ctx: ParseContext,
     cap: RecoveryCap,
+    stats: Option<crate::stats::Stats>,
+    subfield_delimiters: SubfieldDelimiters,
 }
 
 impl<R: Read> MarcReader<R> {
@@ -103,9 +114,12 @@ impl<R: Read> MarcReader<R> {
             reader,
             recovery_mode: RecoveryMode::Strict,
             validation_level: ValidationLevel::default(),
+            collect_warnings: true,
             records_read: 0,
             ctx: ParseContext::new(),
             cap: RecoveryCap::new(),
+            stats: None,
+            subfield_delimiters: SubfieldDelimiters::Standard,
         }
     }
 
@@ -163,6 +177,38 @@ impl<R: Read> MarcReader<R> {
         self
     }
 
+    /// Set recovery mode, validation level and warning collection together.
+    ///
+    /// With [`ParseOptions::lenient`] the reader salvages damaged records —
+    /// keeping fields with invalid indicators as blanks or missing their
+    /// field terminators, and reading on past a leader record length that
+    /// stops short of the record's end — and leaves what it repaired in
+    /// [`Record::warnings`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mrrc::{MarcReader, ParseOptions};
+    /// use std::io::Cursor;
+    ///
+    /// let mut reader = MarcReader::new(Cursor::new(vec![]))
+    ///     .with_parse_options(ParseOptions::lenient());
+    /// ```
+    #[must_use]
+    pub fn with_parse_options(mut self, options: ParseOptions) -> Self {
+        self.recovery_mode = options.recovery_mode;
+        self.validation_level = options.validation_level;
+        self.collect_warnings = options.collect_warnings;
+        self
+    }
+
+    /// Count records read, bytes consumed and read errors in `stats`.
+    #[must_use]
+    pub fn with_stats(mut self, stats: crate::stats::Stats) -> Self {
+        self.stats = Some(stats);
+        self
+    }
+
     /// Attach a source identifier (filename or stream id) to errors raised by
     /// this reader. Populates `source_name` on every emitted error where
     /// applicable. Use [`MarcReader::from_path`] when constructing from a
@@ -191,6 +237,41 @@ impl<R: Read> MarcReader<R> {
         self.cap.set_max(n);
         self
     }
+
+    /// Reject records larger than `limits` allow.
+    ///
+    /// Records over a limit fail with a descriptive error in every
+    /// recovery mode; the reader stays positioned on the next record. See
+    /// [`ReaderLimits`](crate::ReaderLimits) for the defaults.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mrrc::{MarcReader, ReaderLimits};
+    /// use std::io::Cursor;
+    ///
+    /// let mut reader = MarcReader::new(Cursor::new(vec![]))
+    ///     .with_limits(ReaderLimits::default().with_max_record_len(20_000));
+    /// ```
+    #[must_use]
+    pub fn with_limits(mut self, limits: crate::recovery::ReaderLimits) -> Self {
+        self.ctx.limits = limits;
+        self
+    }
+
+    /// Accept `|`, `$` or another byte as a subfield delimiter in records
+    /// from systems that write one in place of `0x1F`.
+    ///
+    /// Converted fields are parsed as if written with `0x1F`, and each
+    /// converted record carries one warning in [`Record::errors`] — in
+    /// every recovery mode, since the conversion was asked for. See
+    /// [`SubfieldDelimiters`] for how fields are recognized. The source kept
+    /// by [`Self::read_lossless`] is the converted bytes.
+    #[must_use]
+    pub fn with_subfield_delimiters(mut self, delimiters: SubfieldDelimiters) -> Self {
+        self.subfield_delimiters = delimiters;
+        self
+    }
 }
 
 impl MarcReader<std::io::BufReader<std::fs::File>> {
@@ -240,27 +321,185 @@ impl<R: Read> MarcReader<R> {
     /// - The record structure is invalid
     /// - An I/O error occurs
     pub fn read_record(&mut self) -> Result<Option<Record>> {
+        Ok(self.read_counted(false)?.map(|(record, _)| record))
+    }
+
+    /// Read the next record together with the bytes it was read from.
+    ///
+    /// Pass both to [`MarcWriter::write_lossless`](crate::MarcWriter::write_lossless)
+    /// to write an unchanged record back byte for byte and a changed one in
+    /// its original field order; see [`crate::fidelity`]. This costs one
+    /// copy of each record's bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mrrc::MarcReader;
+    /// use std::io::Cursor;
+    ///
+    /// let original = std::fs::read("tests/data/simple_book.mrc").unwrap();
+    /// let mut reader = MarcReader::new(Cursor::new(&original));
+    /// let (record, source) = reader.read_lossless().unwrap().unwrap();
+    /// assert!(source.is_unchanged(&record));
+    /// assert_eq!(source.bytes(), &original[..]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// As for [`Self::read_record`].
+    pub fn read_lossless(&mut self) -> Result<Option<(Record, SourceRecord)>> {
+        Ok(self
+            .read_counted(true)?
+            .and_then(|(record, source)| Some((record, source?))))
+    }
+
+    /// [`Self::read_next`] with the stats accounting.
+    fn read_counted(&mut self, lossless: bool) -> Result<Option<(Record, Option<SourceRecord>)>> {
+        let Some(stats) = self.stats.clone() else {
+            return self.read_next(lossless);
+        };
+        let start = self.ctx.stream_byte_offset;
+        let result = self.read_next(lossless);
+        match &result {
+            Ok(Some(_)) => {
+                stats.add_record(self.ctx.stream_byte_offset.saturating_sub(start) as u64);
+            },
+            Ok(None) => {},
+            Err(_) => stats.add_error(),
+        }
+        result
+    }
+
+    /// Parse the next record, keeping its source when `lossless`.
+    fn read_next(&mut self, lossless: bool) -> Result<Option<(Record, Option<SourceRecord>)>> {
+        crate::telemetry::stage_span!("mrrc.parse", record_index = self.records_read + 1);
         let mut errors = Vec::new();
-        let result = parse_iso2709_record::<R, BibBuilder>(
-            &mut self.reader,
-            &mut self.ctx,
-            &mut self.cap,
-            self.recovery_mode,
-            self.validation_level,
-            &mut errors,
-        )?;
-        let result = result.map(|mut record| {
-            if !errors.is_empty() {
+        let result = if lossless || self.subfield_delimiters != SubfieldDelimiters::Standard {
+            self.read_whole(&mut errors)?
+                .map(|(record, bytes)| (record, lossless.then_some(bytes)))
+        } else {
+            parse_iso2709_record::<R, BibBuilder>(
+                &mut self.reader,
+                &mut self.ctx,
+                &mut self.cap,
+                self.recovery_mode,
+                self.validation_level,
+                &mut errors,
+            )?
+            .map(|record| (record, None))
+        };
+        let result = result.map(|(mut record, bytes)| {
+            if self.collect_warnings && !errors.is_empty() {
                 record.errors = std::sync::Arc::new(errors);
             }
-            record
+            let source = bytes.map(|bytes| SourceRecord::capture(bytes, &record));
+            (record, source)
         });
         if result.is_some() {
             self.records_read += 1;
+            crate::telemetry::records_read(1);
         }
         Ok(result)
     }
 
+    /// Read the next record's bytes whole, convert alternate subfield
+    /// delimiters, and parse them. The bytes are returned with the record.
+    fn read_whole(
+        &mut self,
+        errors: &mut Vec<crate::MarcError>,
+    ) -> Result<Option<(Record, std::sync::Arc<Vec<u8>>)>> {
+        if self.cap.is_exhausted() {
+            return Ok(None);
+        }
+        let Some(leader_bytes) = read_leader_bytes(&mut self.reader)? else {
+            return Ok(None);
+        };
+        // An unreadable length reads no body; the parse below then reports
+        // the bad leader with full context. Short reads are left to the
+        // parse too, which applies the recovery mode.
+        let record_length = std::str::from_utf8(&leader_bytes[..5])
+            .ok()
+            .and_then(|digits| digits.parse::<usize>().ok())
+            .unwrap_or(LEADER_LEN);
+        let (body, _) = read_record_data(
+            &mut self.reader,
+            record_length,
+            RecoveryMode::Lenient,
+            &self.ctx,
+        )?;
+        let mut bytes = Vec::with_capacity(LEADER_LEN + body.len());
+        bytes.extend_from_slice(&leader_bytes);
+        bytes.extend_from_slice(&body);
+        let converted = if self.subfield_delimiters == SubfieldDelimiters::Standard {
+            Vec::new()
+        } else {
+            standardize_subfield_delimiters(&mut bytes, self.subfield_delimiters)
+        };
+        let bytes = std::sync::Arc::new(bytes);
+
+        let record = parse_iso2709_record_from_bytes::<BibBuilder>(
+            &bytes,
+            &mut self.ctx,
+            &mut self.cap,
+            self.recovery_mode,
+            self.validation_level,
+            errors,
+        )?;
+        if record.is_some() && !converted.is_empty() {
+            let delimiters: Vec<String> = converted
+                .iter()
+                .map(|&b| format!("'{}'", char::from(b)))
+                .collect();
+            self.ctx.current_field_tag = None;
+            errors.push(self.ctx.err_invalid_field(format!(
+                "Subfield delimiter {} converted to 0x1F",
+                delimiters.join(", ")
+            )));
+        }
+        Ok(record.map(|record| (record, bytes)))
+    }
+
+    /// Skip the next record without parsing it.
+    ///
+    /// Only the leader is decoded; the rest of the record is read past
+    /// using the leader's record length. Returns `Ok(false)` at end of
+    /// input. Skipped records are not counted by
+    /// [`FormatReader::records_read`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the leader is malformed, an I/O error occurs,
+    /// or (in `RecoveryMode::Strict`) the input ends inside the record.
+    pub fn skip_record(&mut self) -> Result<bool> {
+        if self.cap.is_exhausted() {
+            return Ok(false);
+        }
+        let Some(leader_bytes) = read_leader_bytes(&mut self.reader)? else {
+            return Ok(false);
+        };
+        self.ctx.begin_record();
+        let leader_offset = self.ctx.stream_byte_offset;
+        let leader = Leader::from_bytes(&leader_bytes).map_err(|e| {
+            e.with_position(&self.ctx)
+                .with_bytes_near(&leader_bytes, leader_offset)
+        })?;
+        self.ctx.advance(LEADER_LEN);
+
+        let expected = (leader.record_length as usize).saturating_sub(LEADER_LEN);
+        let skipped = std::io::copy(
+            &mut (&mut self.reader).take(expected as u64),
+            &mut std::io::sink(),
+        )
+        .map_err(|e| self.ctx.err_io(e))?;
+        let skipped = usize::try_from(skipped).unwrap_or(expected);
+        self.ctx.advance(skipped);
+        if skipped < expected && self.recovery_mode == RecoveryMode::Strict {
+            return Err(self.ctx.err_truncated_record(Some(expected), Some(skipped)));
+        }
+        crate::telemetry::records_skipped(1);
+        Ok(true)
+    }
+
     /// Iterate over records, yielding each paired with its accumulated
     /// non-fatal errors. Equivalent to iterating with [`Self::read_record`]
     /// and reading [`Record::errors`] from each yielded record — same data,
@@ -454,6 +693,10 @@ impl<R: Read + std::fmt::Debug> FormatReader for MarcReader<R> {
     fn records_read(&self) -> Option<usize> {
         Some(self.records_read)
     }
+
+    fn skip_record(&mut self) -> Result<bool> {
+        MarcReader::skip_record(self)
+    }
 }
 
 #[cfg(test)]
@@ -922,4 +1165,94 @@ mod tests {
             record.errors
         );
     }
+
+    fn limits_test_stream() -> (Vec<u8>, usize) {
+        let mut small = Record::new(Leader::from_bytes(b"00000nam a2200000 i 4500").unwrap());
+        small.add_control_field("001".to_string(), "small".to_string());
+        let mut large = Record::new(Leader::from_bytes(b"00000nam a2200000 i 4500").unwrap());
+        large.add_control_field("001".to_string(), "large".to_string());
+        for i in 0..20 {
+            large.add_field(
+                Field::builder("500".to_string(), ' ', ' ')
+                    .subfield_str('a', &format!("Note {i} {}", "x".repeat(200)))
+                    .build(),
+            );
+        }
+        let large_bytes = large.to_iso2709_bytes().unwrap();
+        let mut stream = large_bytes.clone();
+        stream.extend_from_slice(&small.to_iso2709_bytes().unwrap());
+        (stream, large_bytes.len())
+    }
+
+    #[test]
+    fn test_limits_reject_oversized_record_and_stay_aligned() {
+        let (stream, large_len) = limits_test_stream();
+        let limits = crate::ReaderLimits::default().with_max_record_len(large_len - 1);
+        let mut reader = MarcReader::new(Cursor::new(stream))
+            .with_recovery_mode(RecoveryMode::Lenient)
+            .with_limits(limits);
+        let err = reader.read_record().expect_err("record over the limit");
+        assert!(
+            matches!(err, crate::MarcError::RecordLengthInvalid { .. }),
+            "got {err:?}"
+        );
+        assert!(err.to_string().contains("reader limit"), "{err}");
+
+        let next = reader.read_record().unwrap().expect("following record");
+        assert_eq!(next.get_control_field("001"), Some("small"));
+    }
+
+    #[test]
+    fn test_limits_reject_long_fields_and_many_fields() {
+        let (stream, _) = limits_test_stream();
+        let mut reader = MarcReader::new(Cursor::new(stream.clone()))
+            .with_limits(crate::ReaderLimits::default().with_max_field_len(100));
+        let err = reader.read_record().expect_err("field over the limit");
+        assert!(
+            matches!(&err, crate::MarcError::DirectoryInvalid { field_tag, .. } if field_tag.as_deref() == Some("500")),
+            "got {err:?}"
+        );
+
+        let mut reader = MarcReader::new(Cursor::new(stream))
+            .with_limits(crate::ReaderLimits::default().with_max_fields(10));
+        let err = reader.read_record().expect_err("too many fields");
+        assert!(
+            matches!(&err, crate::MarcError::DirectoryInvalid { expected: Some(e), .. } if e.contains("directory entries")),
+            "got {err:?}"
+        );
+    }
+
+    #[test]
+    fn test_alternate_subfield_delimiters_converted_with_warning() {
+        let stream = build_record(&[
+            ("001", b"ocm1|a"),
+            ("245", b"10|aCats|cby A. Cat & Co."),
+            ("650", b" 0$aCats$vJuvenile"),
+            ("500", b"  \x1fa$10.00 |b"),
+        ]);
+        let mut reader = MarcReader::new(Cursor::new(stream.clone()))
+            .with_subfield_delimiters(SubfieldDelimiters::Detect);
+        let record = reader.read_record().unwrap().unwrap();
+        assert_eq!(record.get_control_field("001"), Some("ocm1|a"));
+        let title = record.get_field("245").unwrap();
+        assert_eq!(title.get_subfield('a'), Some("Cats"));
+        assert_eq!(title.get_subfield('c'), Some("by A. Cat & Co."));
+        let subject = record.get_field("650").unwrap();
+        assert_eq!(subject.get_subfield('v'), Some("Juvenile"));
+        assert_eq!(
+            record.get_field("500").unwrap().get_subfield('a'),
+            Some("$10.00 |b")
+        );
+        assert_eq!(record.errors.len(), 1);
+        assert!(record.errors[0].to_string().contains("'|', '$'"));
+
+        // Only the named byte is accepted: the '$' field stays malformed.
+        let mut reader = MarcReader::new(Cursor::new(stream))
+            .with_subfield_delimiters(SubfieldDelimiters::Alternate(b'|'));
+        let err = reader.read_record().expect_err("650 still uses '$'");
+        assert!(
+            matches!(&err, crate::MarcError::InvalidField { field_tag, .. } if field_tag.as_deref() == Some("650")),
+            "got {err:?}"
+        );
+    }
 }
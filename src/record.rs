@@ -48,8 +48,11 @@
 //! }
 //! ```
 
+use crate::field_protection::FieldProtection;
+use crate::fingerprint::FingerprintConfig;
 use crate::leader::Leader;
 use crate::marc_record::MarcRecord;
+use crate::subfield_order::SubfieldOrder;
 use foldhash::fast::FixedState;
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
@@ -71,7 +74,10 @@ pub type TagIndexMap<V> = IndexMap<String, V, FixedState>;
 /// Fields are stored in insertion order using `IndexMap`, preserving the order
 /// in which fields were added to the record. This ensures round-trip fidelity
 /// when serializing and deserializing records.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// `Serialize` and `Deserialize` use the versioned canonical representation
+/// documented in [`record_serde`](crate::record_serde).
+#[derive(Debug, Clone)]
 pub struct Record {
     /// Record leader (24 bytes)
     pub leader: Leader,
@@ -80,15 +86,15 @@ pub struct Record {
     pub control_fields: TagIndexMap<Vec<String>>,
     /// Data fields (010+) - tag -> fields, preserves insertion order
     pub fields: TagIndexMap<Vec<Field>>,
-    /// Non-fatal errors accumulated while parsing this record. Always
-    /// empty in `RecoveryMode::Strict` (the parser returns `Err` on the
-    /// first error before the record is yielded). Populated in
+    /// Non-fatal errors accumulated while parsing this record. Empty in
+    /// `RecoveryMode::Strict` (the parser returns `Err` on the first
+    /// error before the record is yielded), apart from the warning for
+    /// converted [`SubfieldDelimiters`](crate::SubfieldDelimiters). Populated in
     /// `Lenient` and `Permissive` with the diagnostics that would have
     /// been raised — same positional context, same code/slug. Wrapped in
     /// `Arc` so cloning a record is cheap and `MarcError` doesn't need
     /// to implement `Clone` (some variants carry non-Clone causes).
     /// Skipped during serialization (parse-time diagnostics, not record data).
-    #[serde(skip)]
     pub errors: std::sync::Arc<Vec<crate::error::MarcError>>,
 }
 
@@ -126,6 +132,15 @@ impl Record {
         }
     }
 
+    /// Problems a lenient reader recovered from while parsing this record.
+    ///
+    /// The same diagnostics as [`Record::errors`]; empty for records read
+    /// strictly or built in code.
+    #[must_use]
+    pub fn warnings(&self) -> &[crate::error::MarcError] {
+        &self.errors
+    }
+
     /// Create a builder for fluently constructing MARC records
     ///
     /// # Examples
@@ -275,6 +290,50 @@ impl Record {
         })
     }
 
+    /// Iterate over every subfield of every data field, with its tag and
+    /// field
+    ///
+    /// Fields are visited in tag order and subfields in field order, which
+    /// makes this a single loop for full-text indexing or encoding scans.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mrrc::{Field, Leader, Record};
+    ///
+    /// let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+    /// record.add_field(
+    ///     Field::builder("245".to_string(), '1', '0')
+    ///         .subfield_str('a', "Title")
+    ///         .subfield_str('c', "Author")
+    ///         .build(),
+    /// );
+    ///
+    /// let codes: Vec<_> = record
+    ///     .all_subfields()
+    ///     .map(|(tag, _field, subfield)| format!("{tag}${}", subfield.code))
+    ///     .collect();
+    /// assert_eq!(codes, ["245$a", "245$c"]);
+    /// ```
+    pub fn all_subfields(&self) -> impl Iterator<Item = (&str, &Field, &Subfield)> {
+        self.fields().flat_map(|field| {
+            field
+                .subfields
+                .iter()
+                .map(move |subfield| (field.tag.as_str(), field, subfield))
+        })
+    }
+
+    /// Iterate over the values of every subfield of every data field
+    ///
+    /// The flattened counterpart of [`all_subfields`](Self::all_subfields);
+    /// control field values are available from
+    /// [`control_fields_iter`](Self::control_fields_iter).
+    pub fn all_values(&self) -> impl Iterator<Item = &str> {
+        self.all_subfields()
+            .map(|(_, _, subfield)| subfield.value.as_str())
+    }
+
     // ============================================================================
     // Advanced field queries
     // ============================================================================
@@ -857,6 +916,274 @@ impl Record {
         }
     }
 
+    /// Remove every control and data field whose tag matches `spec`
+    ///
+    /// Typically used to drop local fields before sharing a record.
+    /// Returns the removed data fields.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let removed = record.strip_fields(&FieldProtection::local_fields());
+    /// ```
+    pub fn strip_fields(&mut self, spec: &FieldProtection) -> Vec<Field> {
+        self.control_fields.retain(|tag, _| !spec.is_protected(tag));
+        let mut removed = Vec::new();
+        self.fields.retain(|tag, fields| {
+            if spec.is_protected(tag) {
+                removed.append(fields);
+                false
+            } else {
+                true
+            }
+        });
+        removed
+    }
+
+    /// Compute a stable 64-bit fingerprint of the record's content
+    ///
+    /// Identical across platforms and releases for the same `config`; see
+    /// [`crate::fingerprint`] for what is hashed.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let config = FingerprintConfig::ignoring_volatile();
+    /// if record.fingerprint(&config) == stored {
+    ///     continue; // unchanged since the last run
+    /// }
+    /// ```
+    #[must_use]
+    pub fn fingerprint(&self, config: &FingerprintConfig) -> u64 {
+        crate::fingerprint::fingerprint(self, config)
+    }
+
+    /// The [`Record::fingerprint`] as 16 lowercase hex digits
+    #[must_use]
+    pub fn fingerprint_hex(&self, config: &FingerprintConfig) -> String {
+        format!("{:016x}", self.fingerprint(config))
+    }
+
+    /// The 005 date and time of latest transaction, if present and valid
+    ///
+    /// See [`crate::provenance::parse_005`] for the accepted forms.
+    #[cfg(feature = "chrono")]
+    #[must_use]
+    pub fn transaction_timestamp(&self) -> Option<chrono::NaiveDateTime> {
+        self.get_control_field("005")
+            .and_then(crate::provenance::parse_005)
+    }
+
+    /// Set 005 to `date_time`, replacing any existing value
+    #[cfg(feature = "chrono")]
+    pub fn set_transaction_timestamp(&mut self, date_time: &chrono::NaiveDateTime) {
+        self.control_fields.insert(
+            "005".to_string(),
+            vec![crate::provenance::format_005(date_time)],
+        );
+    }
+
+    /// Serialize this record to ISO 2709 bytes.
+    ///
+    /// The directory, base address of data and record length are computed
+    /// here, as [`MarcWriter`](crate::MarcWriter) does for a stream; the
+    /// leader stored in the record is not changed. Useful where each record
+    /// travels as its own payload, such as a message queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mrrc::{Field, Leader, Record};
+    ///
+    /// let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500")?);
+    /// record.add_control_field_str("001", "ocm123");
+    /// record.add_field(Field::builder("245".to_string(), '0', '0').subfield_str('a', "Title.").build());
+    ///
+    /// let bytes = record.to_iso2709_bytes()?;
+    /// assert_eq!(&bytes[..5], format!("{:05}", bytes.len()).as_bytes());
+    /// assert_eq!(Record::from_iso2709_bytes(&bytes)?.get_control_field("001"), Some("ocm123"));
+    /// # Ok::<(), mrrc::MarcError>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MarcError::WriterError`](crate::MarcError::WriterError) or
+    /// a size error if a tag is malformed, a field is longer than the
+    /// directory allows, or the record exceeds 99,999 bytes.
+    pub fn to_iso2709_bytes(&self) -> crate::error::Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        crate::writer::MarcWriter::new(&mut bytes).write_record(self)?;
+        Ok(bytes)
+    }
+
+    /// Parse exactly one ISO 2709 record from `bytes`, strictly.
+    ///
+    /// Use [`parse_record_from_bytes`](crate::parse_record_from_bytes) to
+    /// choose a recovery mode or validation level.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bytes are empty, malformed, or continue past
+    /// the record length given in the leader.
+    pub fn from_iso2709_bytes(bytes: &[u8]) -> crate::error::Result<Record> {
+        let declared = bytes
+            .get(..5)
+            .and_then(|len| std::str::from_utf8(len).ok())
+            .and_then(|len| len.parse::<usize>().ok());
+        if let Some(declared) = declared
+            && bytes.len() > declared
+        {
+            return Err(crate::MarcError::invalid_field_msg(format!(
+                "{} bytes follow the end of the record",
+                bytes.len() - declared
+            )));
+        }
+        crate::reader::parse_record_from_bytes(
+            bytes.to_vec(),
+            crate::RecoveryMode::Strict,
+            crate::ValidationLevel::Structural,
+        )?
+        .ok_or_else(|| crate::MarcError::invalid_field_msg("No record in input"))
+    }
+
+    /// Overlay this record with `incoming`, keeping protected fields
+    ///
+    /// The leader and every unprotected control and data field are taken
+    /// from `incoming`; fields whose tags match `protection` are kept from
+    /// this record and any protected fields in `incoming` are discarded.
+    /// Data fields end up in tag order.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// // Replace a brief record with the full vendor copy, keeping local 9XX
+    /// record.overlay(vendor_record, &FieldProtection::local_fields());
+    /// ```
+    pub fn overlay(&mut self, incoming: Record, protection: &FieldProtection) {
+        let Record {
+            leader,
+            control_fields,
+            fields,
+            ..
+        } = incoming;
+        self.leader = leader;
+
+        self.control_fields
+            .retain(|tag, _| protection.is_protected(tag));
+        for (tag, values) in control_fields {
+            if !protection.is_protected(&tag) {
+                self.control_fields.insert(tag, values);
+            }
+        }
+        self.control_fields.sort_keys();
+
+        self.fields.retain(|tag, _| protection.is_protected(tag));
+        for (tag, values) in fields {
+            if !protection.is_protected(&tag) {
+                self.fields.insert(tag, values);
+            }
+        }
+        self.fields.sort_keys();
+    }
+
+    /// Scripts used in the record's data fields, in [`Script`] order
+    ///
+    /// Combines 880 `$6` script codes with the Unicode ranges of the field
+    /// text. See [`crate::scripts`].
+    ///
+    /// [`Script`]: crate::scripts::Script
+    #[must_use]
+    pub fn scripts_used(&self) -> Vec<crate::scripts::Script> {
+        crate::scripts::scripts_used(self)
+    }
+
+    /// Language of cataloging (040 `$b`), e.g. `"eng"`
+    #[must_use]
+    pub fn cataloging_language(&self) -> Option<&str> {
+        crate::scripts::cataloging_language(self)
+    }
+
+    /// Wrap the record in an [`ArcRecord`](crate::ArcRecord) for cheap
+    /// sharing across consumers and threads
+    #[must_use]
+    pub fn into_shared(self) -> crate::shared_record::ArcRecord {
+        crate::shared_record::ArcRecord::new(self)
+    }
+
+    /// Add or repair the 008 so it has the layout the leader's type of
+    /// record and bibliographic level call for, returning whether it changed
+    ///
+    /// See [`crate::fixed_field::ensure_008`].
+    pub fn ensure_008(&mut self) -> bool {
+        crate::fixed_field::ensure_008(self)
+    }
+
+    /// Keyword index text for each bucket of `profile`
+    ///
+    /// See [`crate::index_text`]; [`IndexProfile::standard`] gives title,
+    /// author, subject, notes and keyword buckets.
+    ///
+    /// [`IndexProfile::standard`]: crate::index_text::IndexProfile::standard
+    #[must_use]
+    pub fn index_text(
+        &self,
+        profile: &crate::index_text::IndexProfile,
+    ) -> indexmap::IndexMap<String, String> {
+        crate::index_text::index_text(self, profile)
+    }
+
+    /// The record's 6XX subject headings, each split into its kind, main
+    /// heading, typed subdivisions and vocabulary
+    ///
+    /// See [`crate::subjects::subject_headings`].
+    #[must_use]
+    pub fn subject_headings(&self) -> Vec<crate::subjects::SubjectHeading> {
+        crate::subjects::subject_headings(self)
+    }
+
+    /// Render the record one element per line, labeled from the embedded
+    /// MARC 21 dictionary (`245 $a Title proper: ...`, `008/35-37
+    /// Language: eng (English)`).
+    ///
+    /// See [`crate::dictionary::explain`].
+    #[must_use]
+    pub fn explain(&self) -> String {
+        crate::dictionary::explain(self)
+    }
+
+    /// Occurrences of `term` in the record's fields and subfields, with
+    /// their positions and snippets
+    ///
+    /// See [`crate::search`]; by default matching ignores case and
+    /// diacritics.
+    #[must_use]
+    pub fn search(
+        &self,
+        term: &str,
+        options: &crate::search::SearchOptions,
+    ) -> Vec<crate::search::Hit> {
+        crate::search::search(self, term, options)
+    }
+
+    /// Common cataloging defects in the record, with safe fixes
+    ///
+    /// See [`crate::lint`]; [`crate::lint::fix`] applies the fixes.
+    #[must_use]
+    pub fn lint(&self) -> crate::lint::LintReport {
+        crate::lint::lint(self)
+    }
+
+    /// Reorder the subfields of every field into conventional MARC 21 order
+    ///
+    /// Applies [`Field::canonicalize_subfield_order`] to each data field and
+    /// returns the number of fields whose order changed.
+    pub fn canonicalize(&mut self) -> usize {
+        self.fields_mut()
+            .map(Field::canonicalize_subfield_order)
+            .filter(|changed| *changed)
+            .count()
+    }
+
     /// Remove all fields from the record
     pub fn clear_fields(&mut self) {
         self.fields.clear();
@@ -993,6 +1320,103 @@ pub struct RecordBuilder {
 }
 
 impl RecordBuilder {
+    /// Start a MARC 21 bibliographic record for a book.
+    ///
+    /// The leader is `00000nam a2200000 i 4500`: a new record for printed
+    /// language material, monograph, Unicode, full level, ISBD punctuation
+    /// included. An 008 is added with today's date entered, dates unknown,
+    /// language `und`, and the material-specific positions 18-34 coded
+    /// `|` (no attempt to code). Add a control number (001) and a title
+    /// for [`build_validated`](Self::build_validated) to pass.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mrrc::RecordBuilder;
+    ///
+    /// let record = RecordBuilder::bibliographic()
+    ///     .control_field_str("001", "12345")
+    ///     .author("Twain, Mark")
+    ///     .title("Roughing it")
+    ///     .isbn("9780520268173")
+    ///     .build_validated()?;
+    /// let title = record.get_field("245").unwrap();
+    /// assert_eq!(title.get_subfield('a'), Some("Roughing it"));
+    /// assert_eq!(title.indicator1, '1');
+    /// # Ok::<(), mrrc::MarcError>(())
+    /// ```
+    #[must_use]
+    pub fn bibliographic() -> Self {
+        let leader = Leader {
+            record_length: 0,
+            record_status: 'n',
+            record_type: 'a',
+            bibliographic_level: 'm',
+            control_record_type: ' ',
+            character_coding: 'a',
+            indicator_count: 2,
+            subfield_code_count: 2,
+            data_base_address: 0,
+            encoding_level: ' ',
+            cataloging_form: 'i',
+            multipart_level: ' ',
+            reserved: "4500".to_string(),
+        };
+        Record::builder(leader).control_field("008".to_string(), crate::fixed_field::skeleton())
+    }
+
+    /// Add a title statement (245 `$a`).
+    ///
+    /// The first indicator is `1` (title added entry) when the record has a
+    /// 1XX main entry and `0` otherwise; [`author`](Self::author) keeps it
+    /// in step when called later.
+    #[must_use]
+    pub fn title(mut self, title: &str) -> Self {
+        let ind1 = if self.has_main_entry() { '1' } else { '0' };
+        self.record.add_field(
+            Field::builder("245".to_string(), ind1, '0')
+                .subfield_str('a', title)
+                .build(),
+        );
+        self
+    }
+
+    /// Add a personal-name main entry (100 `$a`).
+    ///
+    /// A name with a comma is entered under surname (first indicator `1`),
+    /// otherwise under forename (`0`). Any 245 already added gets first
+    /// indicator `1`.
+    #[must_use]
+    pub fn author(mut self, name: &str) -> Self {
+        let ind1 = if name.contains(',') { '1' } else { '0' };
+        self.record.add_field(
+            Field::builder("100".to_string(), ind1, ' ')
+                .subfield_str('a', name)
+                .build(),
+        );
+        for field in self.record.fields_by_tag_mut("245") {
+            field.indicator1 = '1';
+        }
+        self
+    }
+
+    /// Add an ISBN (020 `$a`).
+    #[must_use]
+    pub fn isbn(mut self, isbn: &str) -> Self {
+        self.record.add_field(
+            Field::builder("020".to_string(), ' ', ' ')
+                .subfield_str('a', isbn)
+                .build(),
+        );
+        self
+    }
+
+    fn has_main_entry(&self) -> bool {
+        ["100", "110", "111", "130"]
+            .iter()
+            .any(|tag| self.record.get_field(tag).is_some())
+    }
+
     /// Add a control field to the record being built
     #[must_use]
     pub fn control_field(mut self, tag: String, value: String) -> Self {
@@ -1019,6 +1443,21 @@ impl RecordBuilder {
     pub fn build(self) -> Record {
         self.record
     }
+
+    /// Build the record after checking its structure with
+    /// [`RecordStructureValidator::validate_record`](crate::RecordStructureValidator::validate_record)
+    /// and [`validate_directory_structure`](crate::RecordStructureValidator::validate_directory_structure).
+    ///
+    /// # Errors
+    ///
+    /// Returns the first structural problem found: an invalid leader, a
+    /// missing 001 or 008, a malformed tag, indicator or subfield code, or a
+    /// record too large for ISO 2709.
+    pub fn build_validated(self) -> crate::error::Result<Record> {
+        crate::RecordStructureValidator::validate_record(&self.record)?;
+        crate::RecordStructureValidator::validate_directory_structure(&self.record)?;
+        Ok(self.record)
+    }
 }
 
 impl Field {
@@ -1309,6 +1748,50 @@ impl Field {
         }
     }
 
+    /// Reorder subfields into the conventional MARC 21 order for this tag
+    ///
+    /// Uses the built-in [`SubfieldOrder`] for the field's tag; fields
+    /// without one are left unchanged. Returns `true` if the order changed.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// // 245 $c ... $a ... $b ...  becomes  245 $a ... $b ... $c ...
+    /// field.canonicalize_subfield_order();
+    /// ```
+    pub fn canonicalize_subfield_order(&mut self) -> bool {
+        SubfieldOrder::for_tag(&self.tag).is_some_and(|order| order.apply(self))
+    }
+
+    /// Parse every `$0` and `$1` value, classified by source.
+    ///
+    /// See [`crate::authority_ids`] for the recognized forms.
+    #[must_use]
+    pub fn authority_ids(&self) -> Vec<crate::authority_ids::AuthorityId> {
+        self.subfields
+            .iter()
+            .filter(|sf| matches!(sf.code, '0' | '1'))
+            .map(|sf| crate::authority_ids::AuthorityId::parse(sf.code, &sf.value))
+            .collect()
+    }
+
+    /// Set an authority identifier subfield (normally `$0` or `$1`).
+    ///
+    /// Replaces the first subfield with the same code whose value is from
+    /// the same source (id.loc.gov, VIAF, FAST, ...) and returns its old
+    /// value. Otherwise inserts a new subfield where MARC 21 places it:
+    /// after the data subfields and any `$4` or `$2`, with `$0` ahead of
+    /// `$1` and both ahead of `$5`.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// field.set_authority_id('0', "http://id.loc.gov/authorities/names/n79021164");
+    /// ```
+    pub fn set_authority_id(&mut self, code: char, value: &str) -> Option<String> {
+        crate::authority_ids::set_authority_id(self, code, value)
+    }
+
     /// Clear all subfields from the field
     pub fn clear_subfields(&mut self) {
         self.subfields.clear();
@@ -1405,6 +1888,31 @@ mod tests {
         assert!(record.fields.is_empty());
     }
 
+    #[test]
+    fn test_all_subfields_and_values() {
+        let mut record = Record::new(make_leader());
+        record.add_control_field_str("001", "12345");
+        for (tag, value) in [("650", "Maps"), ("245", "Title"), ("650", "Atlases")] {
+            record.add_field(
+                Field::builder(tag.to_string(), ' ', '0')
+                    .subfield_str('a', value)
+                    .subfield_str('z', "Iowa")
+                    .build(),
+            );
+        }
+
+        let tags: Vec<_> = record.all_subfields().map(|(tag, _, _)| tag).collect();
+        assert_eq!(tags, ["650", "650", "650", "650", "245", "245"]);
+        assert!(
+            record
+                .all_subfields()
+                .all(|(tag, field, _)| tag == field.tag)
+        );
+        let values: Vec<_> = record.all_values().collect();
+        assert_eq!(values, ["Maps", "Iowa", "Atlases", "Iowa", "Title", "Iowa"]);
+        assert_eq!(Record::new(make_leader()).all_values().count(), 0);
+    }
+
     #[test]
     fn test_add_control_field() {
         let leader = make_leader();
@@ -1440,6 +1948,91 @@ mod tests {
         assert_eq!(fields.unwrap().len(), 1);
     }
 
+    #[test]
+    fn test_iso2709_bytes_round_trip() {
+        let mut record = Record::new(make_leader());
+        record.add_control_field_str("001", "12345");
+        let mut field = Field::new("245".to_string(), '1', '0');
+        field.add_subfield('a', "Tïtle".to_string());
+        record.add_field(field);
+
+        let bytes = record.to_iso2709_bytes().unwrap();
+        assert_eq!(record.leader.record_length, 1000);
+        assert_eq!(bytes.last(), Some(&0x1D));
+        let parsed = Record::from_iso2709_bytes(&bytes).unwrap();
+        assert_eq!(parsed.leader.record_length as usize, bytes.len());
+        assert_eq!(
+            parsed.get_field("245").unwrap().get_subfield('a'),
+            Some("Tïtle")
+        );
+        assert_eq!(parsed.to_iso2709_bytes().unwrap(), bytes);
+
+        let mut two = bytes.clone();
+        two.extend_from_slice(&bytes);
+        assert!(Record::from_iso2709_bytes(&two).is_err());
+        assert!(Record::from_iso2709_bytes(&[]).is_err());
+    }
+
+    #[test]
+    fn test_overlay_keeps_protected_fields() {
+        let mut record = Record::new(make_leader());
+        record.add_control_field_str("001", "local-1");
+        record.add_control_field_str("005", "20200101000000.0");
+        record.add_field(
+            Field::builder("245".to_string(), '0', '0')
+                .subfield_str('a', "Brief")
+                .build(),
+        );
+        record.add_field(
+            Field::builder("949".to_string(), ' ', ' ')
+                .subfield_str('a', "Item")
+                .build(),
+        );
+
+        let mut incoming = Record::new(make_leader());
+        incoming.leader.encoding_level = '1';
+        incoming.add_control_field_str("001", "vendor-9");
+        incoming.add_control_field_str("005", "20240101000000.0");
+        incoming.add_field(
+            Field::builder("245".to_string(), '1', '0')
+                .subfield_str('a', "Full")
+                .build(),
+        );
+        incoming.add_field(
+            Field::builder("100".to_string(), '1', ' ')
+                .subfield_str('a', "Author")
+                .build(),
+        );
+        incoming.add_field(
+            Field::builder("949".to_string(), ' ', ' ')
+                .subfield_str('a', "Vendor")
+                .build(),
+        );
+
+        let protection: FieldProtection = "001 9XX".parse().unwrap();
+        record.overlay(incoming, &protection);
+
+        assert_eq!(record.leader.encoding_level, '1');
+        assert_eq!(record.get_control_field("001"), Some("local-1"));
+        assert_eq!(record.get_control_field("005"), Some("20240101000000.0"));
+        assert_eq!(
+            record.get_field("245").unwrap().get_subfield('a'),
+            Some("Full")
+        );
+        assert_eq!(record.get_fields("949").unwrap().len(), 1);
+        assert_eq!(
+            record.get_field("949").unwrap().get_subfield('a'),
+            Some("Item")
+        );
+        let tags: Vec<&str> = record.fields.keys().map(String::as_str).collect();
+        assert_eq!(tags, ["100", "245", "949"]);
+
+        let removed = record.strip_fields(&protection);
+        assert_eq!(removed.len(), 1);
+        assert!(record.get_control_field("001").is_none());
+        assert!(record.get_fields("949").is_none());
+    }
+
     #[test]
     fn test_multiple_fields_same_tag() {
         let leader = make_leader();
@@ -134,21 +134,13 @@ pub trait RecordHelpers: MarcRecord {
                 })
             })
             .or_else(|| {
-                self.get_control_field("008").and_then(|field_008| {
-                    if field_008.len() >= 11 {
-                        let year = &field_008[7..11];
-                        if year != "    "
-                            && year != "0000"
+                self.get_control_field("008")
+                    .and_then(|field_008| field_008.get(7..11))
+                    .filter(|year| {
+                        *year != "    "
+                            && *year != "0000"
                             && year.chars().all(|c| c.is_ascii_digit())
-                        {
-                            Some(year)
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }
-                })
+                    })
             })
     }
 
@@ -198,14 +190,9 @@ pub trait RecordHelpers: MarcRecord {
     /// Returns a 3-character language code (e.g., "eng" for English).
     #[must_use]
     fn language(&self) -> Option<&str> {
-        self.get_control_field("008").and_then(|field_008| {
-            if field_008.len() >= 38 {
-                let lang = &field_008[35..38];
-                if lang == "   " { None } else { Some(lang) }
-            } else {
-                None
-            }
-        })
+        self.get_control_field("008")
+            .and_then(|field_008| field_008.get(35..38))
+            .filter(|&lang| lang != "   ")
     }
 
     /// Get the control number (system number) from field 001
@@ -305,21 +292,13 @@ pub trait RecordHelpers: MarcRecord {
         }
 
         // Fall back to field 008
-        self.get_control_field("008").and_then(|field_008| {
-            if field_008.len() >= 11 {
-                let year_str = &field_008[7..11];
-                if year_str != "    "
-                    && year_str != "0000"
-                    && year_str.chars().all(|c| c.is_ascii_digit())
-                {
-                    year_str.parse().ok()
-                } else {
-                    None
-                }
-            } else {
-                None
-            }
-        })
+        let year_str = self.get_control_field("008")?.get(7..11)?;
+        if year_str != "    " && year_str != "0000" && year_str.chars().all(|c| c.is_ascii_digit())
+        {
+            year_str.parse().ok()
+        } else {
+            None
+        }
     }
 
     /// Get the place of publication from field 260 or 264 (RDA), subfield 'a'
@@ -379,6 +358,35 @@ pub trait RecordHelpers: MarcRecord {
         self.get_field("130").and_then(|f| f.get_subfield('a'))
     }
 
+    /// Get a browse/sort key for the title proper (field 245)
+    ///
+    /// Skips the non-filing characters declared in indicator 2 and folds
+    /// case, diacritics and punctuation; see
+    /// [`normalize::title_sort_key`](crate::normalize::title_sort_key).
+    ///
+    /// # Examples
+    /// ```ignore
+    /// // 245 14 $a The Great Gatsby /$c F. Scott Fitzgerald.
+    /// assert_eq!(record.title_sort_key().as_deref(), Some("great gatsby"));
+    /// ```
+    #[must_use]
+    fn title_sort_key(&self) -> Option<String> {
+        self.get_field("245").map(crate::normalize::title_sort_key)
+    }
+
+    /// Get a browse/sort key for the uniform title (field 240, else 130)
+    ///
+    /// A record carries a 240 when the main entry is a name and a 130 when
+    /// the uniform title is itself the main entry, so at most one applies.
+    /// Field 240 counts non-filing characters in indicator 2 and field 130
+    /// in indicator 1.
+    #[must_use]
+    fn uniform_title_sort_key(&self) -> Option<String> {
+        self.get_field("240")
+            .or_else(|| self.get_field("130"))
+            .map(crate::normalize::title_sort_key)
+    }
+
     /// Get the government document classification from field 086, subfield 'a'
     ///
     /// Also known as `SuDoc` (Superintendent of Documents) number.
@@ -739,4 +747,38 @@ mod tests {
         assert!(subjects.contains(&"Commentaries."));
         assert!(subjects.contains(&"Local topic"));
     }
+
+    #[test]
+    fn test_title_sort_keys_apply_non_filing_indicators() {
+        let mut record = create_test_record();
+        for tag in ["245", "240", "130"] {
+            record.remove_fields_by_tag(tag);
+        }
+        record.add_field(
+            Field::builder("245".to_string(), '1', '4')
+                .subfield_str('a', "The Great Gatsby /")
+                .subfield_str('c', "F. Scott Fitzgerald.")
+                .build(),
+        );
+        assert_eq!(record.title_sort_key().as_deref(), Some("great gatsby"));
+        assert_eq!(record.uniform_title_sort_key(), None);
+
+        record.add_field(
+            Field::builder("130".to_string(), '4', ' ')
+                .subfield_str('a', "Les Misérables.")
+                .subfield_str('l', "English")
+                .build(),
+        );
+        assert_eq!(
+            record.uniform_title_sort_key().as_deref(),
+            Some("miserables english")
+        );
+
+        record.add_field(
+            Field::builder("240".to_string(), '1', '2')
+                .subfield_str('a', "L'Étranger.")
+                .build(),
+        );
+        assert_eq!(record.uniform_title_sort_key().as_deref(), Some("etranger"));
+    }
 }
@@ -0,0 +1,460 @@
+//! Random access to records in an ISO 2709 file.
+//!
+//! A [`RecordIndex`] is built by scanning a file once, noting where each
+//! record starts and how long it is, and which control numbers it carries
+//! (001, and optionally 035 `$a`). Afterwards any record can be fetched by
+//! position or control number with one seek and one record parse, and the
+//! index can be saved to a sidecar file so the scan is not repeated.
+//!
+//! 035 values are indexed both as written (`(OCoLC)ocm12345`) and without
+//! the parenthesized source prefix (`ocm12345`).
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::record_index::RecordIndex;
+//! use std::fs::File;
+//!
+//! let index = RecordIndex::build_from_path("tests/data/multi_records.mrc").unwrap();
+//! assert!(!index.is_empty());
+//!
+//! let mut file = File::open("tests/data/multi_records.mrc").unwrap();
+//! let last = index.get(&mut file, index.len() - 1).unwrap().unwrap();
+//!
+//! if let Some(id) = last.get_control_field("001") {
+//!     let again = index.get_by_control_number(&mut file, id).unwrap().unwrap();
+//!     assert_eq!(again.get_control_field("001"), Some(id));
+//! }
+//!
+//! // Persist next to the data file and load later
+//! let mut sidecar = Vec::new();
+//! index.save(&mut sidecar).unwrap();
+//! assert_eq!(RecordIndex::load(sidecar.as_slice()).unwrap(), index);
+//! ```
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+use crate::error::{MarcError, Result};
+use crate::iso2709::{LEADER_LEN, read_leader_bytes};
+use crate::leader::Leader;
+use crate::reader::parse_record_from_bytes;
+use crate::record::Record;
+use crate::recovery::{RecoveryMode, ValidationLevel};
+
+/// First line of a saved index.
+const SIDECAR_HEADER: &str = "mrrc-record-index\t1";
+
+/// Location and keys of one record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexEntry {
+    /// Byte offset of the record's leader in the file
+    pub offset: u64,
+    /// Record length in bytes
+    pub length: u32,
+    /// Control numbers the record can be looked up by
+    pub keys: Vec<String>,
+}
+
+/// Configures and builds a [`RecordIndex`].
+#[derive(Debug, Clone)]
+pub struct RecordIndexBuilder {
+    index_035: bool,
+    recovery_mode: RecoveryMode,
+}
+
+impl Default for RecordIndexBuilder {
+    fn default() -> Self {
+        RecordIndexBuilder {
+            index_035: true,
+            recovery_mode: RecoveryMode::Lenient,
+        }
+    }
+}
+
+impl RecordIndexBuilder {
+    /// Whether to index 035 `$a` system control numbers as well as 001
+    /// (default `true`).
+    #[must_use]
+    pub fn index_035(mut self, enabled: bool) -> Self {
+        self.index_035 = enabled;
+        self
+    }
+
+    /// Recovery mode used to read control numbers while scanning (default
+    /// [`RecoveryMode::Lenient`], so one damaged record does not stop the
+    /// scan). Records are always indexed by position.
+    #[must_use]
+    pub fn recovery_mode(mut self, mode: RecoveryMode) -> Self {
+        self.recovery_mode = mode;
+        self
+    }
+
+    /// Scan `source` from its current position to the end.
+    ///
+    /// Offsets are relative to where the scan started.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error on I/O failure, on a malformed leader, if the input
+    /// ends inside a record, or if a record cannot be parsed under the
+    /// configured recovery mode.
+    pub fn build<R: Read>(&self, mut source: R) -> Result<RecordIndex> {
+        let mut index = RecordIndex::default();
+        let mut offset = 0u64;
+        while let Some(bytes) = read_raw_record(&mut source, offset)? {
+            let actual_len = bytes.len();
+
+            let keys = match parse_record_from_bytes(
+                bytes,
+                self.recovery_mode,
+                ValidationLevel::Structural,
+            )? {
+                Some(record) => self.keys(&record),
+                None => Vec::new(),
+            };
+            index.push(IndexEntry {
+                offset,
+                length: u32::try_from(actual_len).unwrap_or(u32::MAX),
+                keys,
+            });
+            offset += actual_len as u64;
+        }
+        Ok(index)
+    }
+
+    /// Open and scan the file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened or scanned.
+    pub fn build_from_path(&self, path: impl AsRef<Path>) -> Result<RecordIndex> {
+        let file = std::fs::File::open(path)?;
+        self.build(BufReader::new(file))
+    }
+
+    fn keys(&self, record: &Record) -> Vec<String> {
+        let mut keys = Vec::new();
+        if let Some(id) = record.get_control_field("001") {
+            let id = id.trim();
+            if !id.is_empty() {
+                keys.push(id.to_string());
+            }
+        }
+        if self.index_035 {
+            for value in record
+                .fields_by_tag("035")
+                .flat_map(|field| field.subfields_by_code('a'))
+            {
+                let value = value.trim();
+                if value.is_empty() {
+                    continue;
+                }
+                keys.push(value.to_string());
+                if let Some((_, number)) = value.split_once(')') {
+                    let number = number.trim();
+                    if !number.is_empty() {
+                        keys.push(number.to_string());
+                    }
+                }
+            }
+        }
+        keys.dedup();
+        keys
+    }
+}
+
+/// Read the raw bytes of the next record, leader included, or `None` at
+/// end of input. `offset` is only used in the error message.
+pub(crate) fn read_raw_record<R: Read>(source: &mut R, offset: u64) -> Result<Option<Vec<u8>>> {
+    let Some(leader_bytes) = read_leader_bytes(source)? else {
+        return Ok(None);
+    };
+    let leader = Leader::from_bytes(&leader_bytes)?;
+    let length = leader.record_length as usize;
+    let mut bytes = Vec::with_capacity(length.max(LEADER_LEN));
+    bytes.extend_from_slice(&leader_bytes);
+    source
+        .take(length.saturating_sub(LEADER_LEN) as u64)
+        .read_to_end(&mut bytes)?;
+    if bytes.len() < length {
+        return Err(MarcError::invalid_field_msg(format!(
+            "Record at byte {offset} is truncated: expected {length} bytes, found {}",
+            bytes.len()
+        )));
+    }
+    Ok(Some(bytes))
+}
+
+/// Byte offsets of the records in a file, keyed by position and control
+/// number.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RecordIndex {
+    entries: Vec<IndexEntry>,
+    by_key: HashMap<String, Vec<usize>>,
+}
+
+impl RecordIndex {
+    /// A builder with default settings (001 and 035 indexed).
+    #[must_use]
+    pub fn builder() -> RecordIndexBuilder {
+        RecordIndexBuilder::default()
+    }
+
+    /// Scan `source` with default settings.
+    ///
+    /// # Errors
+    ///
+    /// See [`RecordIndexBuilder::build`].
+    pub fn build<R: Read>(source: R) -> Result<Self> {
+        Self::builder().build(source)
+    }
+
+    /// Scan the file at `path` with default settings.
+    ///
+    /// # Errors
+    ///
+    /// See [`RecordIndexBuilder::build_from_path`].
+    pub fn build_from_path(path: impl AsRef<Path>) -> Result<Self> {
+        Self::builder().build_from_path(path)
+    }
+
+    fn push(&mut self, entry: IndexEntry) {
+        let position = self.entries.len();
+        for key in &entry.keys {
+            self.by_key.entry(key.clone()).or_default().push(position);
+        }
+        self.entries.push(entry);
+    }
+
+    /// Number of records indexed.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the index is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// All entries, in file order.
+    #[must_use]
+    pub fn entries(&self) -> &[IndexEntry] {
+        &self.entries
+    }
+
+    /// The entry for the record at `position` (zero-based).
+    #[must_use]
+    pub fn entry(&self, position: usize) -> Option<&IndexEntry> {
+        self.entries.get(position)
+    }
+
+    /// Positions of all records carrying `control_number`, in file order.
+    #[must_use]
+    pub fn positions_of(&self, control_number: &str) -> &[usize] {
+        self.by_key
+            .get(control_number.trim())
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Read the record at `position` from `source`, which must be the file
+    /// the index was built from.
+    ///
+    /// Returns `Ok(None)` if `position` is out of range.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if seeking or reading fails or the bytes at the
+    /// recorded offset do not parse as a record.
+    pub fn get<R: Read + Seek>(&self, source: &mut R, position: usize) -> Result<Option<Record>> {
+        let Some(entry) = self.entries.get(position) else {
+            return Ok(None);
+        };
+        source.seek(SeekFrom::Start(entry.offset))?;
+        let mut bytes = vec![0u8; entry.length as usize];
+        source.read_exact(&mut bytes)?;
+        parse_record_from_bytes(bytes, RecoveryMode::Strict, ValidationLevel::Structural)
+    }
+
+    /// Read the first record carrying `control_number` in 001 or 035.
+    ///
+    /// # Errors
+    ///
+    /// See [`RecordIndex::get`].
+    pub fn get_by_control_number<R: Read + Seek>(
+        &self,
+        source: &mut R,
+        control_number: &str,
+    ) -> Result<Option<Record>> {
+        match self.positions_of(control_number).first() {
+            Some(&position) => self.get(source, position),
+            None => Ok(None),
+        }
+    }
+
+    /// Write the index in its sidecar format: a header line, then one
+    /// tab-separated line per record of offset, length and keys.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    pub fn save<W: Write>(&self, mut writer: W) -> Result<()> {
+        writeln!(writer, "{SIDECAR_HEADER}")?;
+        for entry in &self.entries {
+            write!(writer, "{}\t{}", entry.offset, entry.length)?;
+            for key in &entry.keys {
+                write!(writer, "\t{}", key.replace(['\t', '\n', '\r'], " "))?;
+            }
+            writeln!(writer)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Write the index to a sidecar file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be created or written.
+    pub fn save_to_path(&self, path: impl AsRef<Path>) -> Result<()> {
+        let file = std::fs::File::create(path)?;
+        self.save(std::io::BufWriter::new(file))
+    }
+
+    /// Read an index written by [`RecordIndex::save`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MarcError::InvalidField`] if the data is not a saved
+    /// index, or an I/O error if reading fails.
+    pub fn load<R: Read>(reader: R) -> Result<Self> {
+        let invalid = |line: usize| {
+            MarcError::invalid_field_msg(format!("Invalid record index at line {line}"))
+        };
+        let mut lines = BufReader::new(reader).lines();
+        match lines.next().transpose()? {
+            Some(header) if header == SIDECAR_HEADER => {},
+            _ => return Err(invalid(1)),
+        }
+        let mut index = RecordIndex::default();
+        for (number, line) in lines.enumerate() {
+            let line = line?;
+            let mut parts = line.split('\t');
+            let offset = parts.next().and_then(|p| p.parse().ok());
+            let length = parts.next().and_then(|p| p.parse().ok());
+            let (Some(offset), Some(length)) = (offset, length) else {
+                return Err(invalid(number + 2));
+            };
+            index.push(IndexEntry {
+                offset,
+                length,
+                keys: parts.map(str::to_string).collect(),
+            });
+        }
+        Ok(index)
+    }
+
+    /// Read a sidecar file written by [`RecordIndex::save_to_path`].
+    ///
+    /// # Errors
+    ///
+    /// See [`RecordIndex::load`].
+    pub fn load_from_path(path: impl AsRef<Path>) -> Result<Self> {
+        Self::load(std::fs::File::open(path)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::record::Field;
+    use crate::writer::MarcWriter;
+    use std::io::Cursor;
+
+    fn file() -> Cursor<Vec<u8>> {
+        let mut buffer = Vec::new();
+        let mut writer = MarcWriter::new(&mut buffer);
+        for i in 0..5 {
+            let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+            record.add_control_field_str("001", &format!("rec{i}"));
+            record.add_field(
+                Field::builder("035".to_string(), ' ', ' ')
+                    .subfield_str('a', &format!("(OCoLC)ocm1234{i}"))
+                    .build(),
+            );
+            record.add_field(
+                Field::builder("245".to_string(), '0', '0')
+                    .subfield_str('a', &"x".repeat(i * 10))
+                    .build(),
+            );
+            writer.write_record(&record).unwrap();
+        }
+        writer.finish().unwrap();
+        Cursor::new(buffer)
+    }
+
+    #[test]
+    fn test_lookup_by_position_and_key() {
+        let mut data = file();
+        let index = RecordIndex::build(&mut data).unwrap();
+        assert_eq!(index.len(), 5);
+        assert_eq!(index.entry(0).unwrap().offset, 0);
+        assert_eq!(
+            index.entry(1).unwrap().offset,
+            u64::from(index.entry(0).unwrap().length)
+        );
+
+        let third = index.get(&mut data, 2).unwrap().unwrap();
+        assert_eq!(third.get_control_field("001"), Some("rec2"));
+        for key in ["rec3", "(OCoLC)ocm12343", "ocm12343"] {
+            let record = index
+                .get_by_control_number(&mut data, key)
+                .unwrap()
+                .unwrap();
+            assert_eq!(record.get_control_field("001"), Some("rec3"), "{key}");
+        }
+        assert!(index.get(&mut data, 5).unwrap().is_none());
+        assert!(
+            index
+                .get_by_control_number(&mut data, "nope")
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_without_035() {
+        let index = RecordIndex::builder()
+            .index_035(false)
+            .build(file())
+            .unwrap();
+        assert_eq!(index.entry(0).unwrap().keys, ["rec0"]);
+        assert!(index.positions_of("ocm12340").is_empty());
+    }
+
+    #[test]
+    fn test_sidecar_round_trip() {
+        let index = RecordIndex::build(file()).unwrap();
+        let mut sidecar = Vec::new();
+        index.save(&mut sidecar).unwrap();
+        let text = String::from_utf8(sidecar.clone()).unwrap();
+        assert!(text.starts_with("mrrc-record-index\t1\n0\t"));
+
+        let loaded = RecordIndex::load(sidecar.as_slice()).unwrap();
+        assert_eq!(loaded, index);
+        assert_eq!(loaded.positions_of("ocm12344"), [4]);
+
+        assert!(RecordIndex::load("not an index\n".as_bytes()).is_err());
+        assert!(RecordIndex::load("mrrc-record-index\t1\nx\t1\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_truncated_file_is_an_error() {
+        let mut bytes = file().into_inner();
+        bytes.truncate(bytes.len() - 10);
+        assert!(RecordIndex::build(bytes.as_slice()).is_err());
+    }
+}
@@ -0,0 +1,324 @@
+//! Canonical serde representation of [`Record`].
+//!
+//! [`Record`] implements `Serialize` and `Deserialize` with a documented,
+//! versioned shape, so applications can embed records in their own
+//! payloads (JSON, YAML, `MessagePack`, ...) and read them back with later
+//! releases. It is independent of the in-memory layout, and distinct from
+//! the MARC-in-JSON dialects in [`json`](crate::json) and
+//! [`marcjson`](crate::marcjson), which exist for interchange with other
+//! tools.
+//!
+//! # Version 1
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "leader": "00000nam a2200000 a 4500",
+//!   "control_fields": [
+//!     {"tag": "001", "value": "ocm01234567"}
+//!   ],
+//!   "fields": [
+//!     {"tag": "245", "ind1": "1", "ind2": "0",
+//!      "subfields": [{"code": "a", "value": "A title"}]}
+//!   ]
+//! }
+//! ```
+//!
+//! - `version` is [`CANONICAL_VERSION`]. A missing version is read as 1; a
+//!   newer version than this release understands is rejected.
+//! - `leader` is the 24-character leader string.
+//! - `control_fields` and `fields` are lists in record order, so repeated
+//!   tags and their order survive.
+//! - Indicators and subfield codes are one-character strings.
+//! - Parse diagnostics ([`Record::errors`]) are not serialized.
+//!
+//! Serialization fails only for a leader that cannot be written (see
+//! [`Leader::as_bytes`]).
+//!
+//! For compatibility, deserialization also accepts the shape produced by
+//! releases before the canonical form: a leader object, and maps from tag to
+//! lists of control-field values and fields (with `indicator1`/`indicator2`).
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::{Leader, Record};
+//!
+//! let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+//! record.add_control_field_str("001", "12345");
+//!
+//! let json = serde_json::to_string(&record).unwrap();
+//! assert!(json.starts_with(r#"{"version":1,"leader":"00000nam a2200000 a 4500""#));
+//! let back: Record = serde_json::from_str(&json).unwrap();
+//! assert_eq!(back.get_control_field("001"), Some("12345"));
+//! ```
+
+use indexmap::IndexMap;
+use serde::de::Error as _;
+use serde::ser::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::leader::Leader;
+use crate::record::{Field, Record, Subfield};
+
+/// Version of the canonical representation written by this release.
+pub const CANONICAL_VERSION: u32 = 1;
+
+#[derive(Serialize)]
+struct RecordOut<'a> {
+    version: u32,
+    leader: String,
+    control_fields: Vec<ControlField<&'a str>>,
+    fields: Vec<FieldOut<'a>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ControlField<S> {
+    tag: S,
+    value: S,
+}
+
+#[derive(Serialize)]
+struct FieldOut<'a> {
+    tag: &'a str,
+    ind1: char,
+    ind2: char,
+    subfields: &'a [Subfield],
+}
+
+impl<'a> From<&'a Field> for FieldOut<'a> {
+    fn from(field: &'a Field) -> Self {
+        FieldOut {
+            tag: &field.tag,
+            ind1: field.indicator1,
+            ind2: field.indicator2,
+            subfields: &field.subfields,
+        }
+    }
+}
+
+/// A data field; the legacy shape spells the indicators out.
+#[derive(Deserialize)]
+struct FieldIn {
+    tag: String,
+    #[serde(alias = "indicator1")]
+    ind1: char,
+    #[serde(alias = "indicator2")]
+    ind2: char,
+    subfields: Vec<Subfield>,
+}
+
+impl From<FieldIn> for Field {
+    fn from(field: FieldIn) -> Self {
+        let mut out = Field::new(field.tag, field.ind1, field.ind2);
+        out.subfields = field.subfields.into();
+        out
+    }
+}
+
+impl Serialize for Record {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let leader = self.leader.as_bytes().map_err(S::Error::custom)?;
+        RecordOut {
+            version: CANONICAL_VERSION,
+            // Leader bytes are read as Latin-1 (see `Leader::from_bytes`)
+            leader: leader.iter().map(|&b| char::from(b)).collect(),
+            control_fields: self
+                .control_fields_iter()
+                .map(|(tag, value)| ControlField { tag, value })
+                .collect(),
+            fields: self.fields().map(FieldOut::from).collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+#[derive(Deserialize)]
+struct RecordIn {
+    #[serde(default)]
+    version: Option<u32>,
+    leader: LeaderIn,
+    #[serde(default)]
+    control_fields: ControlFieldsIn,
+    #[serde(default)]
+    fields: FieldsIn,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum LeaderIn {
+    Text(String),
+    Legacy(Leader),
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ControlFieldsIn {
+    List(Vec<ControlField<String>>),
+    Legacy(IndexMap<String, Vec<String>>),
+}
+
+impl Default for ControlFieldsIn {
+    fn default() -> Self {
+        ControlFieldsIn::List(Vec::new())
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum FieldsIn {
+    List(Vec<FieldIn>),
+    Legacy(IndexMap<String, Vec<FieldIn>>),
+}
+
+impl Default for FieldsIn {
+    fn default() -> Self {
+        FieldsIn::List(Vec::new())
+    }
+}
+
+fn leader_from_text(text: &str) -> Result<Leader, String> {
+    let bytes = text
+        .chars()
+        .map(|c| u8::try_from(c).map_err(|_| format!("leader contains '{c}'")))
+        .collect::<Result<Vec<u8>, String>>()?;
+    if bytes.len() != 24 {
+        return Err(format!("leader must be 24 characters, got {}", bytes.len()));
+    }
+    Leader::from_bytes(&bytes).map_err(|e| e.to_string())
+}
+
+impl<'de> Deserialize<'de> for Record {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let input = RecordIn::deserialize(deserializer)?;
+        if let Some(version) = input.version
+            && version > CANONICAL_VERSION
+        {
+            return Err(D::Error::custom(format!(
+                "unsupported record version {version} (this release reads up to {CANONICAL_VERSION})"
+            )));
+        }
+        let leader = match input.leader {
+            LeaderIn::Text(text) => leader_from_text(&text).map_err(D::Error::custom)?,
+            LeaderIn::Legacy(leader) => leader,
+        };
+        let mut record = Record::new(leader);
+        match input.control_fields {
+            ControlFieldsIn::List(list) => {
+                for ControlField { tag, value } in list {
+                    record.add_control_field(tag, value);
+                }
+            },
+            ControlFieldsIn::Legacy(map) => {
+                for (tag, values) in map {
+                    for value in values {
+                        record.add_control_field(tag.clone(), value);
+                    }
+                }
+            },
+        }
+        match input.fields {
+            FieldsIn::List(list) => list.into_iter().for_each(|f| record.add_field(f.into())),
+            FieldsIn::Legacy(map) => map
+                .into_values()
+                .flatten()
+                .for_each(|f| record.add_field(f.into())),
+        }
+        Ok(record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const V1: &str = r#"{"version":1,"leader":"00000cam a2200000 i 4500","control_fields":[{"tag":"001","value":"ocm01234567"},{"tag":"007","value":"cr"},{"tag":"007","value":"ta"}],"fields":[{"tag":"245","ind1":"1","ind2":"0","subfields":[{"code":"a","value":"A title /"},{"code":"c","value":"by Someone."}]},{"tag":"650","ind1":" ","ind2":"0","subfields":[{"code":"a","value":"Cats."}]},{"tag":"650","ind1":" ","ind2":"0","subfields":[{"code":"a","value":"Dogs."}]}]}"#;
+
+    fn record() -> Record {
+        let mut record = Record::new(Leader::from_bytes(b"00000cam a2200000 i 4500").unwrap());
+        record.add_control_field_str("001", "ocm01234567");
+        record.add_control_field_str("007", "cr");
+        record.add_control_field_str("007", "ta");
+        record.add_field(
+            Field::builder("245".to_string(), '1', '0')
+                .subfield_str('a', "A title /")
+                .subfield_str('c', "by Someone.")
+                .build(),
+        );
+        for subject in ["Cats.", "Dogs."] {
+            record.add_field(
+                Field::builder("650".to_string(), ' ', '0')
+                    .subfield_str('a', subject)
+                    .build(),
+            );
+        }
+        record
+    }
+
+    fn assert_same(a: &Record, b: &Record) {
+        assert_eq!(a.leader, b.leader);
+        assert_eq!(
+            a.control_fields_iter().collect::<Vec<_>>(),
+            b.control_fields_iter().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            a.fields().collect::<Vec<_>>(),
+            b.fields().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_version_1_shape_is_pinned() {
+        // Changing this output breaks stored payloads: bump
+        // CANONICAL_VERSION and keep reading version 1 instead.
+        assert_eq!(serde_json::to_string(&record()).unwrap(), V1);
+        let read: Record = serde_json::from_str(V1).unwrap();
+        assert_same(&read, &record());
+    }
+
+    #[test]
+    fn test_reads_pre_canonical_shape() {
+        let legacy = r#"{
+            "leader": {"record_length": 0, "record_status": "c", "record_type": "a",
+                "bibliographic_level": "m", "control_record_type": " ",
+                "character_coding": "a", "indicator_count": 2, "subfield_code_count": 2,
+                "data_base_address": 0, "encoding_level": " ", "cataloging_form": "i",
+                "multipart_level": " ", "reserved": "4500"},
+            "control_fields": {"001": ["ocm01234567"], "007": ["cr", "ta"]},
+            "fields": {
+                "245": [{"tag": "245", "indicator1": "1", "indicator2": "0",
+                    "subfields": [{"code": "a", "value": "A title /"},
+                                  {"code": "c", "value": "by Someone."}]}],
+                "650": [{"tag": "650", "indicator1": " ", "indicator2": "0",
+                         "subfields": [{"code": "a", "value": "Cats."}]},
+                        {"tag": "650", "indicator1": " ", "indicator2": "0",
+                         "subfields": [{"code": "a", "value": "Dogs."}]}]
+            }
+        }"#;
+        let read: Record = serde_json::from_str(legacy).unwrap();
+        assert_same(&read, &record());
+
+        // `Field` on its own (as in authority and holdings records) keeps
+        // the spelled-out indicator names.
+        let field = serde_json::to_value(record().fields().next().unwrap()).unwrap();
+        assert_eq!(field["indicator1"], "1");
+        assert!(field.get("ind1").is_none());
+    }
+
+    #[test]
+    fn test_rejects_bad_input() {
+        let newer = V1.replace(r#""version":1"#, r#""version":2"#);
+        let err = serde_json::from_str::<Record>(&newer).unwrap_err();
+        assert!(err.to_string().contains("unsupported record version 2"));
+
+        let unversioned = V1.replace(r#""version":1,"#, "");
+        assert!(serde_json::from_str::<Record>(&unversioned).is_ok());
+
+        let short_leader = V1.replace("i 4500", "i 450");
+        assert!(serde_json::from_str::<Record>(&short_leader).is_err());
+
+        let mut bad = record();
+        bad.leader.reserved = "45".to_string();
+        assert!(serde_json::to_string(&bad).is_err());
+    }
+}
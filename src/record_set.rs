@@ -0,0 +1,413 @@
+//! An in-memory collection of records with lookup indexes.
+//!
+//! Cross-record work such as deduplication, overlay and authority
+//! maintenance keeps needing the same lookups: which records carry this
+//! control number, this ISBN, this title. A [`RecordSet`] holds the records
+//! and keeps an inverted index for each [`IndexKey`] it was created with,
+//! updated as records are inserted and removed.
+//!
+//! Keys are normalized so that equivalent forms match:
+//!
+//! | Key | Indexed values |
+//! |-----|----------------|
+//! | [`IndexKey::ControlNumber`] | 001, trimmed |
+//! | [`IndexKey::SystemControlNumber`] | 035 `$a` as written and without its `(ORG)` prefix |
+//! | [`IndexKey::Isbn`] | 020 `$a` as unhyphenated ISBN-13 (see [`normalize_isbn`]) |
+//! | [`IndexKey::TitleKey`] | 245 title without non-filing characters (see [`normalize_title`]) |
+//!
+//! Lookups on a key the set does not index fall back to a scan.
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::record_set::RecordSet;
+//! use mrrc::{Field, Leader, Record};
+//!
+//! let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+//! record.add_control_field_str("001", "b1");
+//! record.add_field(
+//!     Field::builder("020".to_string(), ' ', ' ')
+//!         .subfield_str('a', "0-306-40615-2 (pbk.)")
+//!         .build(),
+//! );
+//!
+//! let mut set = RecordSet::new();
+//! set.insert(record);
+//! assert_eq!(set.find_by_isbn("9780306406157").len(), 1);
+//! assert_eq!(set.find_by_control_number("b1").len(), 1);
+//!
+//! let removed = set.remove_where(|r| r.get_control_field("001") == Some("b1"));
+//! assert_eq!(removed.len(), 1);
+//! assert!(set.is_empty());
+//! ```
+
+use std::collections::HashMap;
+
+use crate::normalize::{naco_normalize, normalize_isbn, normalize_title};
+use crate::record::Record;
+
+/// A lookup key a [`RecordSet`] can index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IndexKey {
+    /// 001 control number
+    ControlNumber,
+    /// 035 `$a` system control numbers
+    SystemControlNumber,
+    /// 020 `$a` ISBNs
+    Isbn,
+    /// Normalized 245 title
+    TitleKey,
+}
+
+impl IndexKey {
+    /// Every key, the default for [`RecordSet::new`].
+    pub const ALL: [IndexKey; 4] = [
+        IndexKey::ControlNumber,
+        IndexKey::SystemControlNumber,
+        IndexKey::Isbn,
+        IndexKey::TitleKey,
+    ];
+
+    /// The normalized values of this key in `record`, without duplicates.
+    #[must_use]
+    pub fn values(self, record: &Record) -> Vec<String> {
+        let mut values = Vec::new();
+        match self {
+            IndexKey::ControlNumber => {
+                values.extend(
+                    record
+                        .get_control_field("001")
+                        .map(|id| id.trim().to_string()),
+                );
+            },
+            IndexKey::SystemControlNumber => {
+                for value in record
+                    .fields_by_tag("035")
+                    .flat_map(|field| field.subfields_by_code('a'))
+                {
+                    let value = value.trim();
+                    values.push(value.to_string());
+                    if let Some((_, number)) = value.split_once(')') {
+                        values.push(number.trim().to_string());
+                    }
+                }
+            },
+            IndexKey::Isbn => {
+                values.extend(
+                    record
+                        .fields_by_tag("020")
+                        .flat_map(|field| field.subfields_by_code('a'))
+                        .filter_map(|value| normalize_isbn(value.split_whitespace().next()?)),
+                );
+            },
+            IndexKey::TitleKey => {
+                values.extend(record.get_field("245").map(normalize_title));
+            },
+        }
+        values.retain(|v| !v.is_empty());
+        values.sort_unstable();
+        values.dedup();
+        values
+    }
+}
+
+/// Records held in memory with inverted indexes on chosen keys.
+///
+/// Records keep their insertion order; lookups return matches in that
+/// order. Records are only reachable by shared reference, so the indexes
+/// cannot go stale: to change a record, remove it, edit it and insert it
+/// again.
+#[derive(Debug, Clone)]
+pub struct RecordSet {
+    /// Insertion-ordered slots; removed records leave `None`.
+    slots: Vec<Option<Record>>,
+    len: usize,
+    indexes: HashMap<IndexKey, HashMap<String, Vec<usize>>>,
+}
+
+impl Default for RecordSet {
+    fn default() -> Self {
+        Self::with_keys(&IndexKey::ALL)
+    }
+}
+
+impl RecordSet {
+    /// Create an empty set indexing every [`IndexKey`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an empty set indexing only `keys`.
+    #[must_use]
+    pub fn with_keys(keys: &[IndexKey]) -> Self {
+        RecordSet {
+            slots: Vec::new(),
+            len: 0,
+            indexes: keys.iter().map(|&key| (key, HashMap::new())).collect(),
+        }
+    }
+
+    /// Number of records in the set.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the set holds no records.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Whether `key` is indexed.
+    #[must_use]
+    pub fn is_indexed(&self, key: IndexKey) -> bool {
+        self.indexes.contains_key(&key)
+    }
+
+    /// Add a record and index it.
+    pub fn insert(&mut self, record: Record) {
+        let slot = self.slots.len();
+        for (key, index) in &mut self.indexes {
+            for value in key.values(&record) {
+                index.entry(value).or_default().push(slot);
+            }
+        }
+        self.slots.push(Some(record));
+        self.len += 1;
+    }
+
+    /// Iterate over the records in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = &Record> {
+        self.slots.iter().flatten()
+    }
+
+    /// Records whose normalized `key` equals `value`. `value` must already
+    /// be in the key's normalized form (see [`IndexKey::values`]).
+    #[must_use]
+    pub fn find(&self, key: IndexKey, value: &str) -> Vec<&Record> {
+        self.records_at(self.slots_for(key, value))
+    }
+
+    /// Records with this 001 or 035 `$a`, with or without the `(ORG)`
+    /// prefix.
+    #[must_use]
+    pub fn find_by_control_number(&self, control_number: &str) -> Vec<&Record> {
+        let control_number = control_number.trim();
+        let mut slots = self.slots_for(IndexKey::ControlNumber, control_number);
+        slots.extend(self.slots_for(IndexKey::SystemControlNumber, control_number));
+        slots.sort_unstable();
+        slots.dedup();
+        self.records_at(slots)
+    }
+
+    /// Records with this ISBN in 020 `$a`, in either ISBN-10 or ISBN-13
+    /// form. Returns nothing for an invalid ISBN.
+    #[must_use]
+    pub fn find_by_isbn(&self, isbn: &str) -> Vec<&Record> {
+        normalize_isbn(isbn.trim()).map_or_else(Vec::new, |isbn| self.find(IndexKey::Isbn, &isbn))
+    }
+
+    /// Records whose 245 title, without non-filing characters, matches
+    /// `title` after NACO normalization.
+    #[must_use]
+    pub fn find_by_title(&self, title: &str) -> Vec<&Record> {
+        self.find(IndexKey::TitleKey, &naco_normalize(title))
+    }
+
+    /// Remove and return every record matching `predicate`, in insertion
+    /// order.
+    pub fn remove_where<F>(&mut self, mut predicate: F) -> Vec<Record>
+    where
+        F: FnMut(&Record) -> bool,
+    {
+        let matching: Vec<usize> = self
+            .slots
+            .iter()
+            .enumerate()
+            .filter(|(_, slot)| slot.as_ref().is_some_and(&mut predicate))
+            .map(|(i, _)| i)
+            .collect();
+        matching
+            .into_iter()
+            .filter_map(|slot| self.take(slot))
+            .collect()
+    }
+
+    /// Keep only the records matching `predicate`.
+    pub fn retain<F>(&mut self, mut predicate: F)
+    where
+        F: FnMut(&Record) -> bool,
+    {
+        self.remove_where(|record| !predicate(record));
+    }
+
+    /// Slots of the records whose `key` has `value`, in insertion order.
+    fn slots_for(&self, key: IndexKey, value: &str) -> Vec<usize> {
+        match self.indexes.get(&key) {
+            Some(index) => index.get(value).cloned().unwrap_or_default(),
+            None => self
+                .slots
+                .iter()
+                .enumerate()
+                .filter(|(_, slot)| {
+                    slot.as_ref()
+                        .is_some_and(|record| key.values(record).iter().any(|v| v == value))
+                })
+                .map(|(i, _)| i)
+                .collect(),
+        }
+    }
+
+    fn records_at(&self, slots: Vec<usize>) -> Vec<&Record> {
+        slots
+            .into_iter()
+            .filter_map(|slot| self.slots[slot].as_ref())
+            .collect()
+    }
+
+    /// Take the record out of `slot` and drop it from the indexes.
+    fn take(&mut self, slot: usize) -> Option<Record> {
+        let record = self.slots[slot].take()?;
+        for (key, index) in &mut self.indexes {
+            for value in key.values(&record) {
+                if let Some(slots) = index.get_mut(&value) {
+                    slots.retain(|&s| s != slot);
+                    if slots.is_empty() {
+                        index.remove(&value);
+                    }
+                }
+            }
+        }
+        self.len -= 1;
+        Some(record)
+    }
+
+    /// Consume the set, returning its records in insertion order.
+    #[must_use]
+    pub fn into_records(self) -> Vec<Record> {
+        self.slots.into_iter().flatten().collect()
+    }
+}
+
+impl FromIterator<Record> for RecordSet {
+    fn from_iter<I: IntoIterator<Item = Record>>(iter: I) -> Self {
+        let mut set = RecordSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<Record> for RecordSet {
+    fn extend<I: IntoIterator<Item = Record>>(&mut self, iter: I) {
+        for record in iter {
+            self.insert(record);
+        }
+    }
+}
+
+impl IntoIterator for RecordSet {
+    type Item = Record;
+    type IntoIter = std::iter::Flatten<std::vec::IntoIter<Option<Record>>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.slots.into_iter().flatten()
+    }
+}
+
+impl<'a> IntoIterator for &'a RecordSet {
+    type Item = &'a Record;
+    type IntoIter = std::iter::Flatten<std::slice::Iter<'a, Option<Record>>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.slots.iter().flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::leader::Leader;
+    use crate::record::Field;
+
+    fn record(id: &str, oclc: &str, isbn: &str, title: &str) -> Record {
+        let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+        record.add_control_field_str("001", id);
+        record.add_field(
+            Field::builder("035".to_string(), ' ', ' ')
+                .subfield_str('a', oclc)
+                .build(),
+        );
+        record.add_field(
+            Field::builder("020".to_string(), ' ', ' ')
+                .subfield_str('a', isbn)
+                .build(),
+        );
+        record.add_field(
+            Field::builder(
+                "245".to_string(),
+                '1',
+                if title.starts_with("The ") { '4' } else { '0' },
+            )
+            .subfield_str('a', title)
+            .build(),
+        );
+        record
+    }
+
+    fn ids(records: &[&Record]) -> Vec<String> {
+        records
+            .iter()
+            .filter_map(|r| r.get_control_field("001"))
+            .map(str::to_string)
+            .collect()
+    }
+
+    fn sample() -> Vec<Record> {
+        vec![
+            record("b1", "(OCoLC)ocm00001", "0306406152", "The Hobbit /"),
+            record("b2", "(OCoLC)b1", "978-0-306-40615-7", "Hobbit."),
+            record("b3", "(OCoLC)ocm00003", "1-55860-832-X", "The Silmarillion"),
+        ]
+    }
+
+    #[test]
+    fn test_lookups() {
+        let set: RecordSet = sample().into_iter().collect();
+        assert_eq!(set.len(), 3);
+        assert_eq!(ids(&set.find_by_isbn("978-0306406157")), ["b1", "b2"]);
+        assert_eq!(ids(&set.find_by_isbn("155860832x")), ["b3"]);
+        assert!(set.find_by_isbn("not an isbn").is_empty());
+        assert_eq!(ids(&set.find_by_control_number("b1")), ["b1", "b2"]);
+        assert_eq!(ids(&set.find_by_control_number("(OCoLC)ocm00003")), ["b3"]);
+        assert_eq!(ids(&set.find_by_title("hobbit")), ["b1", "b2"]);
+    }
+
+    #[test]
+    fn test_unindexed_key_scans() {
+        let mut set = RecordSet::with_keys(&[IndexKey::ControlNumber]);
+        set.extend(sample());
+        assert!(!set.is_indexed(IndexKey::Isbn));
+        assert_eq!(ids(&set.find_by_isbn("0306406152")), ["b1", "b2"]);
+        assert_eq!(ids(&set.find_by_control_number("ocm00001")), ["b1"]);
+    }
+
+    #[test]
+    fn test_removal_updates_indexes() {
+        let mut set: RecordSet = sample().into_iter().collect();
+        let removed = set.remove_where(|r| r.get_control_field("001") == Some("b1"));
+        assert_eq!(removed.len(), 1);
+        assert_eq!(ids(&set.find_by_isbn("0306406152")), ["b2"]);
+        assert_eq!(ids(&set.find_by_control_number("b1")), ["b2"]);
+
+        set.retain(|r| r.get_control_field("001") != Some("b3"));
+        assert_eq!(set.len(), 1);
+        assert!(set.find_by_title("silmarillion").is_empty());
+
+        set.insert(record("b4", "(OCoLC)x", "0306406152", "Hobbit"));
+        let all: Vec<&Record> = set.iter().collect();
+        assert_eq!(ids(&all), ["b2", "b4"]);
+        assert_eq!(set.into_records().len(), 2);
+    }
+}
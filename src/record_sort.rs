@@ -0,0 +1,588 @@
+//! Sorting record files larger than memory.
+//!
+//! [`sort_records`] is an external merge sort over ISO 2709 streams. Input
+//! is read in runs of up to the memory limit, each run is keyed and sorted
+//! on the rayon thread pool and, if the input does not fit in one run,
+//! spilled to a temporary file; the runs are then merged into the output.
+//! Records are copied byte for byte, so sorting never changes a record.
+//!
+//! [`SortedWriter`] does the same for records produced in code: it wraps
+//! any [`FormatWriter`] and writes what it is given in key order when
+//! finished.
+//!
+//! The sort is stable: records with equal keys keep their input order,
+//! which lets a following pass deduplicate by keeping the first (or last)
+//! record of each key.
+//!
+//! The memory limit counts record bytes held in a run. Parsed records and
+//! keys are only held while a run is being sorted, so actual peak usage
+//! is a small multiple of the limit. Runs are merged in a single pass
+//! with every run file open at once, so choose a limit that keeps the
+//! number of runs well below the open-file limit.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use std::fs::File;
+//! use std::io::{BufReader, BufWriter};
+//!
+//! use mrrc::normalize::title_sort_key;
+//! use mrrc::record_sort::sort_records;
+//!
+//! # fn main() -> mrrc::Result<()> {
+//! let input = BufReader::new(File::open("union.mrc")?);
+//! let output = BufWriter::new(File::create("union-sorted.mrc")?);
+//! let stats = sort_records(
+//!     input,
+//!     output,
+//!     |record| record.get_field("245").map(title_sort_key),
+//!     512 * 1024 * 1024,
+//! )?;
+//! println!("{} records in {} runs", stats.records, stats.runs);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rayon::prelude::*;
+
+use crate::error::{MarcError, Result};
+use crate::formats::FormatWriter;
+use crate::reader::parse_record_from_bytes;
+use crate::record::Record;
+use crate::record_index::read_raw_record;
+use crate::recovery::{RecoveryMode, ValidationLevel};
+use crate::writer::MarcWriter;
+
+/// Counter that keeps run file names unique within the process.
+static RUN_FILES: AtomicUsize = AtomicUsize::new(0);
+
+/// Outcome of a sort.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SortStats {
+    /// Records written
+    pub records: usize,
+    /// Sorted runs the input was split into (1 when it fit in memory)
+    pub runs: usize,
+}
+
+/// Configures an external sort. [`sort_records`] covers the common case.
+#[derive(Debug, Clone)]
+pub struct RecordSorter {
+    memory_limit: usize,
+    temp_dir: PathBuf,
+    recovery_mode: RecoveryMode,
+}
+
+impl Default for RecordSorter {
+    fn default() -> Self {
+        RecordSorter {
+            memory_limit: 256 * 1024 * 1024,
+            temp_dir: std::env::temp_dir(),
+            recovery_mode: RecoveryMode::Lenient,
+        }
+    }
+}
+
+impl RecordSorter {
+    /// A sorter with a 256 MiB run size, spilling to the system temporary
+    /// directory.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bytes of record data held per run before it is sorted and spilled.
+    #[must_use]
+    pub fn memory_limit(mut self, bytes: usize) -> Self {
+        self.memory_limit = bytes;
+        self
+    }
+
+    /// Directory for run files (default [`std::env::temp_dir`]).
+    #[must_use]
+    pub fn temp_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.temp_dir = dir.into();
+        self
+    }
+
+    /// Recovery mode used when parsing records to compute their keys
+    /// (default [`RecoveryMode::Lenient`]). The bytes written are the
+    /// original ones either way.
+    #[must_use]
+    pub fn recovery_mode(mut self, mode: RecoveryMode) -> Self {
+        self.recovery_mode = mode;
+        self
+    }
+
+    /// Sort the records of `input` by `key` into `output`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error on I/O failure, on a truncated or malformed record,
+    /// or if a record cannot be parsed under the configured recovery mode.
+    /// Run files are removed whether or not the sort succeeds.
+    pub fn sort<R, W, K, F>(&self, mut input: R, mut output: W, key: F) -> Result<SortStats>
+    where
+        R: Read,
+        W: Write,
+        K: Ord + Send,
+        F: Fn(&Record) -> K + Sync,
+    {
+        let mut runs = RunFiles::default();
+        let mut stats = SortStats::default();
+        let mut offset = 0u64;
+        let mut buffer = Vec::new();
+        let mut buffered = 0;
+        loop {
+            let next = read_raw_record(&mut input, offset)?;
+            let at_end = next.is_none();
+            if let Some(bytes) = next {
+                offset += bytes.len() as u64;
+                buffered += bytes.len();
+                buffer.push(bytes);
+            }
+            if !at_end && buffered < self.memory_limit {
+                continue;
+            }
+
+            if !buffer.is_empty() {
+                let run = self.sort_run(std::mem::take(&mut buffer), &key)?;
+                buffered = 0;
+                stats.records += run.len();
+                stats.runs += 1;
+                if at_end && runs.paths.is_empty() {
+                    // Everything fit in one run: no need to spill.
+                    for bytes in run {
+                        output.write_all(&bytes)?;
+                    }
+                    break;
+                }
+                runs.write(&self.temp_dir, &run)?;
+            }
+            if at_end {
+                self.merge(&runs, &key, |bytes| Ok(output.write_all(&bytes)?))?;
+                break;
+            }
+        }
+        output.flush()?;
+        Ok(stats)
+    }
+
+    /// Key and stably sort one run of raw records.
+    fn sort_run<K, F>(&self, records: Vec<Vec<u8>>, key: &F) -> Result<Vec<Vec<u8>>>
+    where
+        K: Ord + Send,
+        F: Fn(&Record) -> K + Sync,
+    {
+        let mut keyed = records
+            .into_par_iter()
+            .map(|bytes| Ok((self.key_of(&bytes, key)?, bytes)))
+            .collect::<Result<Vec<_>>>()?;
+        keyed.par_sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(keyed.into_iter().map(|(_, bytes)| bytes).collect())
+    }
+
+    fn key_of<K, F>(&self, bytes: &[u8], key: &F) -> Result<K>
+    where
+        F: Fn(&Record) -> K,
+    {
+        Ok(key(&self.parse(bytes.to_vec())?))
+    }
+
+    fn parse(&self, bytes: Vec<u8>) -> Result<Record> {
+        parse_record_from_bytes(bytes, self.recovery_mode, ValidationLevel::Structural)?
+            .ok_or_else(|| MarcError::invalid_field_msg("Empty record in sort input"))
+    }
+
+    /// Merge sorted run files into `output`. Ties go to the earlier run,
+    /// which keeps the sort stable.
+    fn merge<K, F>(
+        &self,
+        runs: &RunFiles,
+        key: &F,
+        mut emit: impl FnMut(Vec<u8>) -> Result<()>,
+    ) -> Result<()>
+    where
+        K: Ord,
+        F: Fn(&Record) -> K,
+    {
+        let mut readers = runs
+            .paths
+            .iter()
+            .map(|path| Ok(BufReader::new(File::open(path)?)))
+            .collect::<Result<Vec<_>>>()?;
+        let mut heads = Vec::with_capacity(readers.len());
+        let mut heap = BinaryHeap::new();
+        for (run, reader) in readers.iter_mut().enumerate() {
+            let head = read_raw_record(reader, 0)?;
+            if let Some(bytes) = &head {
+                heap.push(Reverse((self.key_of(bytes, key)?, run)));
+            }
+            heads.push(head);
+        }
+        while let Some(Reverse((_, run))) = heap.pop() {
+            if let Some(bytes) = heads[run].take() {
+                emit(bytes)?;
+            }
+            heads[run] = read_raw_record(&mut readers[run], 0)?;
+            if let Some(bytes) = &heads[run] {
+                heap.push(Reverse((self.key_of(bytes, key)?, run)));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Spilled runs, deleted on drop.
+#[derive(Debug, Default)]
+struct RunFiles {
+    paths: Vec<PathBuf>,
+}
+
+impl RunFiles {
+    /// Create the next run file.
+    fn create(&mut self, dir: &std::path::Path) -> Result<BufWriter<File>> {
+        let path = dir.join(format!(
+            "mrrc-sort-{}-{}.mrc",
+            std::process::id(),
+            RUN_FILES.fetch_add(1, Ordering::Relaxed)
+        ));
+        let file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)?;
+        self.paths.push(path);
+        Ok(BufWriter::new(file))
+    }
+
+    fn write(&mut self, dir: &std::path::Path, run: &[Vec<u8>]) -> Result<()> {
+        let mut writer = self.create(dir)?;
+        for bytes in run {
+            writer.write_all(bytes)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+impl Drop for RunFiles {
+    fn drop(&mut self) {
+        for path in &self.paths {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+/// Sort the records of `input` by `key` into `output`, holding at most
+/// about `memory_limit` bytes of records in memory at a time.
+///
+/// See [`RecordSorter`] to choose the temporary directory or recovery mode.
+///
+/// # Errors
+///
+/// See [`RecordSorter::sort`].
+pub fn sort_records<R, W, K, F>(
+    input: R,
+    output: W,
+    key: F,
+    memory_limit: usize,
+) -> Result<SortStats>
+where
+    R: Read,
+    W: Write,
+    K: Ord + Send,
+    F: Fn(&Record) -> K + Sync,
+{
+    RecordSorter::new()
+        .memory_limit(memory_limit)
+        .sort(input, output, key)
+}
+
+/// A [`FormatWriter`] that writes records to an inner writer ordered by a
+/// key, for exports that must be sorted (by 001, title key, call number).
+///
+/// Records are held until [`finish`](FormatWriter::finish), then sorted
+/// stably and written. Once the held records pass the sorter's memory
+/// limit they are sorted and spilled to a run file as ISO 2709, and the
+/// runs are merged at the end, so every record must fit in ISO 2709 when
+/// the output is larger than the limit. Nothing reaches the inner writer
+/// before `finish`.
+///
+/// # Examples
+///
+/// ```
+/// use mrrc::record_sort::SortedWriter;
+/// use mrrc::formats::FormatWriter;
+/// use mrrc::{Leader, MarcReader, MarcWriter, Record};
+///
+/// let mut bytes = Vec::new();
+/// let mut writer = SortedWriter::new(MarcWriter::new(&mut bytes), |record: &Record| {
+///     record.get_control_field("001").unwrap_or_default().to_string()
+/// });
+/// for id in ["b2", "a1", "c3"] {
+///     let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+///     record.add_control_field_str("001", id);
+///     writer.write_record(&record)?;
+/// }
+/// writer.finish()?;
+/// drop(writer);
+///
+/// let mut reader = MarcReader::new(bytes.as_slice());
+/// let first = reader.read_record()?.unwrap();
+/// assert_eq!(first.get_control_field("001"), Some("a1"));
+/// # Ok::<(), mrrc::MarcError>(())
+/// ```
+pub struct SortedWriter<W, K, F> {
+    inner: W,
+    key: F,
+    sorter: RecordSorter,
+    buffer: Vec<(K, Record)>,
+    buffered: usize,
+    runs: RunFiles,
+}
+
+impl<W: std::fmt::Debug, K, F> std::fmt::Debug for SortedWriter<W, K, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SortedWriter")
+            .field("inner", &self.inner)
+            .field("sorter", &self.sorter)
+            .field("buffered", &self.buffer.len())
+            .field("runs", &self.runs.paths.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<W, K, F> SortedWriter<W, K, F>
+where
+    W: FormatWriter,
+    K: Ord + Send,
+    F: Fn(&Record) -> K + Sync,
+{
+    /// Write records to `inner` in `key` order, spilling with the default
+    /// [`RecordSorter`] settings.
+    pub fn new(inner: W, key: F) -> Self {
+        SortedWriter {
+            inner,
+            key,
+            sorter: RecordSorter::new(),
+            buffer: Vec::new(),
+            buffered: 0,
+            runs: RunFiles::default(),
+        }
+    }
+
+    /// Use `sorter`'s memory limit, temporary directory and recovery mode
+    /// (used to read spilled runs back).
+    #[must_use]
+    pub fn with_sorter(mut self, sorter: RecordSorter) -> Self {
+        self.sorter = sorter;
+        self
+    }
+
+    /// Unwrap the inner writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Sort the held records and write them to a new run file.
+    fn spill(&mut self) -> Result<()> {
+        let mut run = std::mem::take(&mut self.buffer);
+        self.buffered = 0;
+        run.par_sort_by(|a, b| a.0.cmp(&b.0));
+        let mut file = self.runs.create(&self.sorter.temp_dir)?;
+        let mut writer = MarcWriter::new(&mut file);
+        for (_, record) in &run {
+            writer.write_record(record)?;
+        }
+        drop(writer);
+        file.flush()?;
+        Ok(())
+    }
+}
+
+/// Rough in-memory size of a record, for the memory limit: its ISO 2709
+/// length without the directory's fixed overhead per field.
+fn approximate_len(record: &Record) -> usize {
+    let control: usize = record
+        .control_fields_iter()
+        .map(|(_, value)| value.len() + 13)
+        .sum();
+    let data: usize = record
+        .fields()
+        .map(|field| {
+            15 + field
+                .subfields
+                .iter()
+                .map(|subfield| subfield.value.len() + 2)
+                .sum::<usize>()
+        })
+        .sum();
+    24 + control + data
+}
+
+impl<W, K, F> FormatWriter for SortedWriter<W, K, F>
+where
+    W: FormatWriter,
+    K: Ord + Send,
+    F: Fn(&Record) -> K + Sync,
+{
+    fn write_record(&mut self, record: &Record) -> Result<()> {
+        self.buffered += approximate_len(record);
+        self.buffer.push(((self.key)(record), record.clone()));
+        if self.buffered >= self.sorter.memory_limit {
+            self.spill()?;
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        if self.runs.paths.is_empty() {
+            let mut run = std::mem::take(&mut self.buffer);
+            self.buffered = 0;
+            run.par_sort_by(|a, b| a.0.cmp(&b.0));
+            for (_, record) in &run {
+                self.inner.write_record(record)?;
+            }
+        } else {
+            if !self.buffer.is_empty() {
+                self.spill()?;
+            }
+            let runs = std::mem::take(&mut self.runs);
+            let (sorter, inner) = (&self.sorter, &mut self.inner);
+            sorter.merge(&runs, &self.key, |bytes| {
+                inner.write_record(&sorter.parse(bytes)?)
+            })?;
+        }
+        self.inner.finish()
+    }
+
+    fn records_written(&self) -> Option<usize> {
+        self.inner.records_written()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::leader::Leader;
+    use crate::reader::MarcReader;
+
+    fn input(ids: &[&str]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let mut writer = MarcWriter::new(&mut bytes);
+        for (i, id) in ids.iter().enumerate() {
+            let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+            record.add_control_field_str("001", id);
+            record.add_control_field_str("005", &format!("2020010100000{i}.0"));
+            writer.write_record(&record).unwrap();
+        }
+        drop(writer);
+        bytes
+    }
+
+    fn read(bytes: &[u8]) -> Vec<(String, String)> {
+        let mut reader = MarcReader::new(bytes);
+        let mut out = Vec::new();
+        while let Some(record) = reader.read_record().unwrap() {
+            out.push((
+                record.get_control_field("001").unwrap().to_string(),
+                record.get_control_field("005").unwrap().to_string(),
+            ));
+        }
+        out
+    }
+
+    fn by_001(record: &Record) -> String {
+        record
+            .get_control_field("001")
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    #[test]
+    fn test_in_memory_sort() {
+        let data = input(&["c", "a", "b"]);
+        let mut out = Vec::new();
+        let stats = sort_records(data.as_slice(), &mut out, by_001, usize::MAX).unwrap();
+        assert_eq!(
+            stats,
+            SortStats {
+                records: 3,
+                runs: 1
+            }
+        );
+        let ids: Vec<String> = read(&out).into_iter().map(|(id, _)| id).collect();
+        assert_eq!(ids, ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_spilled_sort_is_stable() {
+        let ids = ["m", "b", "z", "b", "a", "m", "q", "b", "c"];
+        let data = input(&ids);
+        let dir = std::env::temp_dir().join(format!("mrrc-sort-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let mut out = Vec::new();
+        let stats = RecordSorter::new()
+            .memory_limit(1)
+            .temp_dir(&dir)
+            .sort(data.as_slice(), &mut out, by_001)
+            .unwrap();
+        assert_eq!(
+            stats,
+            SortStats {
+                records: 9,
+                runs: 9
+            }
+        );
+        assert!(fs::read_dir(&dir).unwrap().next().is_none());
+        fs::remove_dir(&dir).unwrap();
+
+        let sorted = read(&out);
+        let ids: Vec<&str> = sorted.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(ids, ["a", "b", "b", "b", "c", "m", "m", "q", "z"]);
+        // Equal keys keep input order (005 carries the input position).
+        let bs: Vec<&str> = sorted[1..4].iter().map(|(_, ts)| ts.as_str()).collect();
+        assert_eq!(
+            bs,
+            ["20200101000001.0", "20200101000003.0", "20200101000007.0"]
+        );
+    }
+
+    #[test]
+    fn test_sorted_writer_spills_and_merges() {
+        let ids = ["m", "b", "z", "b", "a", "q"];
+        let data = input(&ids);
+        let dir = std::env::temp_dir().join(format!("mrrc-sorted-writer-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        for (limit, runs) in [(usize::MAX, 0), (1, ids.len())] {
+            let mut out = Vec::new();
+            let mut writer = SortedWriter::new(MarcWriter::new(&mut out), by_001)
+                .with_sorter(RecordSorter::new().memory_limit(limit).temp_dir(&dir));
+            let mut reader = MarcReader::new(data.as_slice());
+            while let Some(record) = reader.read_record().unwrap() {
+                writer.write_record(&record).unwrap();
+            }
+            assert_eq!(fs::read_dir(&dir).unwrap().count(), runs);
+            assert_eq!(writer.records_written(), Some(0));
+            writer.finish().unwrap();
+            assert_eq!(writer.records_written(), Some(ids.len()));
+            drop(writer);
+
+            let sorted = read(&out);
+            let ids: Vec<&str> = sorted.iter().map(|(id, _)| id.as_str()).collect();
+            assert_eq!(ids, ["a", "b", "b", "m", "q", "z"]);
+            assert_eq!(sorted[1].1, "20200101000001.0");
+        }
+        assert!(fs::read_dir(&dir).unwrap().next().is_none());
+        fs::remove_dir(&dir).unwrap();
+    }
+}
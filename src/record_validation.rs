@@ -1,9 +1,15 @@
 //! Validation of MARC record structure and integrity.
 //!
 //! This module provides validation of the MARC record structure itself,
-//! including the leader format, directory consistency, and field lengths.
+//! including the leader format, directory consistency, field lengths, the
+//! integrity of 880 linkage (subfield 6), and field and subfield
+//! repeatability against the embedded [`dictionary`](crate::dictionary).
+
+use std::collections::HashMap;
+use std::fmt;
 
 use crate::error::{MarcError, Result};
+use crate::field_linkage::LinkageInfo;
 use crate::leader::Leader;
 use crate::record::Record;
 
@@ -427,6 +433,309 @@ impl RecordStructureValidator {
     pub fn is_valid(record: &Record) -> bool {
         Self::validate_record(record).is_ok()
     }
+
+    /// Check every subfield 6 linkage in the record.
+    ///
+    /// Reports `$6` values that do not parse, unknown script identification
+    /// codes, occurrence numbers used twice on either side of a link, 880s
+    /// whose linked field does not exist (orphans), and fields that point
+    /// at an 880 that does not point back. An 880 with occurrence `00` is
+    /// deliberately unlinked and is not an orphan.
+    #[must_use]
+    pub fn linkage_issues(record: &Record) -> Vec<LinkageIssue> {
+        let mut issues = Vec::new();
+        // (linked tag, occurrence) -> field index, for each side of the link
+        let mut originals: HashMap<(String, String), usize> = HashMap::new();
+        let mut alternates: HashMap<(String, String), usize> = HashMap::new();
+        let mut links = Vec::new();
+
+        for (tag, fields) in &record.fields {
+            for (index, field) in fields.iter().enumerate() {
+                for value in field.subfields_by_code('6') {
+                    let info = LinkageInfo::parse(value)
+                        .filter(|info| (tag == "880") != (info.tag == "880"));
+                    let Some(info) = info else {
+                        issues.push(LinkageIssue::Malformed {
+                            tag: tag.clone(),
+                            index,
+                            value: value.to_string(),
+                        });
+                        continue;
+                    };
+                    if !info.has_known_script() {
+                        issues.push(LinkageIssue::UnknownScript {
+                            tag: tag.clone(),
+                            index,
+                            script: info.script_id.clone(),
+                        });
+                    }
+                    if tag == "880" && info.occurrence.bytes().all(|b| b == b'0') {
+                        continue;
+                    }
+                    let (seen, key) = if tag == "880" {
+                        (&mut alternates, (info.tag.clone(), info.occurrence.clone()))
+                    } else {
+                        (&mut originals, (tag.clone(), info.occurrence.clone()))
+                    };
+                    if seen.insert(key, index).is_some() {
+                        issues.push(LinkageIssue::DuplicateOccurrence {
+                            tag: tag.clone(),
+                            occurrence: info.occurrence.clone(),
+                        });
+                    } else {
+                        links.push((tag.clone(), index, info));
+                    }
+                }
+            }
+        }
+
+        for (tag, index, info) in links {
+            if tag == "880" {
+                if !originals.contains_key(&(info.tag.clone(), info.occurrence.clone())) {
+                    issues.push(LinkageIssue::Orphan880 {
+                        index,
+                        linked_tag: info.tag,
+                        occurrence: info.occurrence,
+                    });
+                }
+            } else if !alternates.contains_key(&(tag.clone(), info.occurrence.clone())) {
+                issues.push(LinkageIssue::MissingAlternate {
+                    tag,
+                    index,
+                    occurrence: info.occurrence,
+                });
+            }
+        }
+        issues
+    }
+
+    /// Check fields and subfields against the embedded MARC 21
+    /// [`dictionary`](crate::dictionary).
+    ///
+    /// Reports non-repeatable fields that occur more than once, subfield
+    /// codes a field does not define, and non-repeatable subfields that
+    /// occur more than once in a field. Only fields the dictionary lists
+    /// are checked, so local fields and fields outside its coverage pass.
+    #[must_use]
+    pub fn dictionary_issues(record: &Record) -> Vec<DictionaryIssue> {
+        let mut issues = Vec::new();
+        let mut check_repeat = |tag: &str, count: usize| {
+            if count > 1
+                && crate::dictionary::field(tag).is_some_and(|definition| !definition.repeatable)
+            {
+                issues.push(DictionaryIssue::NonRepeatableField {
+                    tag: tag.to_string(),
+                    count,
+                });
+            }
+        };
+        for (tag, values) in &record.control_fields {
+            check_repeat(tag, values.len());
+        }
+        for (tag, fields) in &record.fields {
+            check_repeat(tag, fields.len());
+        }
+
+        for (tag, fields) in &record.fields {
+            let Some(definition) = crate::dictionary::field(tag) else {
+                continue;
+            };
+            for (index, field) in fields.iter().enumerate() {
+                let mut seen: Vec<char> = Vec::new();
+                let mut reported: Vec<char> = Vec::new();
+                for subfield in &field.subfields {
+                    let code = subfield.code;
+                    let issue = match definition.subfield(code) {
+                        None => DictionaryIssue::UndefinedSubfield {
+                            tag: tag.clone(),
+                            index,
+                            code,
+                        },
+                        Some(sub) if !sub.repeatable && seen.contains(&code) => {
+                            DictionaryIssue::NonRepeatableSubfield {
+                                tag: tag.clone(),
+                                index,
+                                code,
+                            }
+                        },
+                        Some(_) => {
+                            seen.push(code);
+                            continue;
+                        },
+                    };
+                    seen.push(code);
+                    // One issue per code and field, however often it occurs.
+                    if !reported.contains(&code) {
+                        reported.push(code);
+                        issues.push(issue);
+                    }
+                }
+            }
+        }
+        issues
+    }
+
+    /// Validate subfield 6 linkage, failing on the first issue found by
+    /// [`linkage_issues`](Self::linkage_issues).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MarcError::InvalidField`] describing the first broken link.
+    pub fn validate_linkage(record: &Record) -> Result<()> {
+        match Self::linkage_issues(record).first() {
+            Some(issue) => Err(MarcError::invalid_field_msg(issue.to_string())),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A broken subfield 6 linkage found by
+/// [`RecordStructureValidator::linkage_issues`].
+///
+/// `index` is the zero-based position of the field among fields with the
+/// same tag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LinkageIssue {
+    /// A `$6` that does not parse as `TAG-NN[/script][/r]`, or that links an
+    /// 880 to an 880 or a regular field to anything but an 880.
+    Malformed {
+        /// Field tag
+        tag: String,
+        /// Field position among fields with this tag
+        index: usize,
+        /// The `$6` value
+        value: String,
+    },
+    /// A script identification code MARC 21 does not define.
+    UnknownScript {
+        /// Field tag
+        tag: String,
+        /// Field position among fields with this tag
+        index: usize,
+        /// The script code
+        script: String,
+    },
+    /// An occurrence number used by more than one field on the same side of
+    /// a link: two 880s for the same field, or two fields of one tag
+    /// claiming the same 880.
+    DuplicateOccurrence {
+        /// Tag of the repeated fields (`880` for the alternate side)
+        tag: String,
+        /// The repeated occurrence number
+        occurrence: String,
+    },
+    /// An 880 whose linked field does not exist or does not link back.
+    Orphan880 {
+        /// Position among the record's 880s
+        index: usize,
+        /// Tag the 880 claims to represent
+        linked_tag: String,
+        /// Occurrence number
+        occurrence: String,
+    },
+    /// A field that links to an 880 that does not exist or does not link
+    /// back.
+    MissingAlternate {
+        /// Field tag
+        tag: String,
+        /// Field position among fields with this tag
+        index: usize,
+        /// Occurrence number
+        occurrence: String,
+    },
+}
+
+impl fmt::Display for LinkageIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LinkageIssue::Malformed { tag, index, value } => {
+                write!(
+                    f,
+                    "field {tag} (occurrence {index}) has malformed $6 {value:?}"
+                )
+            },
+            LinkageIssue::UnknownScript { tag, index, script } => write!(
+                f,
+                "field {tag} (occurrence {index}) has unknown script code {script:?}"
+            ),
+            LinkageIssue::DuplicateOccurrence { tag, occurrence } => {
+                write!(
+                    f,
+                    "linkage occurrence {occurrence} is repeated in field {tag}"
+                )
+            },
+            LinkageIssue::Orphan880 {
+                index,
+                linked_tag,
+                occurrence,
+            } => write!(
+                f,
+                "880 (occurrence {index}) links to {linked_tag}-{occurrence}, which does not link back"
+            ),
+            LinkageIssue::MissingAlternate {
+                tag,
+                index,
+                occurrence,
+            } => write!(
+                f,
+                "field {tag} (occurrence {index}) links to 880-{occurrence}, which does not link back"
+            ),
+        }
+    }
+}
+
+/// A departure from the embedded MARC 21 dictionary found by
+/// [`RecordStructureValidator::dictionary_issues`].
+///
+/// `index` is the zero-based position of the field among fields with the
+/// same tag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DictionaryIssue {
+    /// A non-repeatable field that occurs more than once.
+    NonRepeatableField {
+        /// Field tag
+        tag: String,
+        /// Number of occurrences
+        count: usize,
+    },
+    /// A subfield code the field does not define.
+    UndefinedSubfield {
+        /// Field tag
+        tag: String,
+        /// Field position among fields with this tag
+        index: usize,
+        /// Subfield code
+        code: char,
+    },
+    /// A non-repeatable subfield that occurs more than once in a field.
+    NonRepeatableSubfield {
+        /// Field tag
+        tag: String,
+        /// Field position among fields with this tag
+        index: usize,
+        /// Subfield code
+        code: char,
+    },
+}
+
+impl fmt::Display for DictionaryIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DictionaryIssue::NonRepeatableField { tag, count } => {
+                write!(f, "field {tag} is not repeatable but occurs {count} times")
+            },
+            DictionaryIssue::UndefinedSubfield { tag, index, code } => write!(
+                f,
+                "field {tag} (occurrence {index}) has undefined subfield ${code}"
+            ),
+            DictionaryIssue::NonRepeatableSubfield { tag, index, code } => write!(
+                f,
+                "field {tag} (occurrence {index}) repeats non-repeatable subfield ${code}"
+            ),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -753,4 +1062,129 @@ mod tests {
         // But it demonstrates the validation is in place
         let _ = result;
     }
+    fn linked(tag: &str, link: &str) -> crate::record::Field {
+        crate::record::Field::builder(tag.to_string(), ' ', ' ')
+            .subfield_str('6', link)
+            .subfield_str('a', "x")
+            .build()
+    }
+
+    #[test]
+    fn test_linkage_issues_clean_record() {
+        let mut record = Record::new(create_test_leader());
+        record.add_field(linked("100", "880-01"));
+        record.add_field(linked("245", "880-02"));
+        record.add_field(linked("880", "100-01/(3/r"));
+        record.add_field(linked("880", "245-02/(3/r"));
+        record.add_field(linked("880", "500-00/$1"));
+        assert!(RecordStructureValidator::linkage_issues(&record).is_empty());
+        assert!(RecordStructureValidator::validate_linkage(&record).is_ok());
+    }
+
+    #[test]
+    fn test_linkage_issues_broken_record() {
+        let mut record = Record::new(create_test_leader());
+        record.add_field(linked("100", "880-01"));
+        record.add_field(linked("245", "880-02"));
+        record.add_field(linked("650", "880-03"));
+        record.add_field(linked("650", "880-03"));
+        record.add_field(linked("700", "100-01"));
+        record.add_field(linked("880", "100-01/(Q"));
+        record.add_field(linked("880", "245-05"));
+        record.add_field(linked("880", "650-03"));
+        record.add_field(linked("880", "garbage"));
+
+        let issues = RecordStructureValidator::linkage_issues(&record);
+        assert_eq!(
+            issues,
+            [
+                LinkageIssue::DuplicateOccurrence {
+                    tag: "650".to_string(),
+                    occurrence: "03".to_string(),
+                },
+                LinkageIssue::Malformed {
+                    tag: "700".to_string(),
+                    index: 0,
+                    value: "100-01".to_string(),
+                },
+                LinkageIssue::UnknownScript {
+                    tag: "880".to_string(),
+                    index: 0,
+                    script: "(Q".to_string(),
+                },
+                LinkageIssue::Malformed {
+                    tag: "880".to_string(),
+                    index: 3,
+                    value: "garbage".to_string(),
+                },
+                LinkageIssue::MissingAlternate {
+                    tag: "245".to_string(),
+                    index: 0,
+                    occurrence: "02".to_string(),
+                },
+                LinkageIssue::Orphan880 {
+                    index: 1,
+                    linked_tag: "245".to_string(),
+                    occurrence: "05".to_string(),
+                },
+            ]
+        );
+        let err = RecordStructureValidator::validate_linkage(&record).unwrap_err();
+        assert!(err.to_string().contains("repeated"), "{err}");
+    }
+
+    #[test]
+    fn test_dictionary_issues() {
+        let mut record = Record::new(create_test_leader());
+        record.add_control_field_str("001", "ocm1");
+        record.add_control_field_str("001", "ocm2");
+        for _ in 0..2 {
+            record.add_field(
+                crate::record::Field::builder("245".to_string(), '1', '0')
+                    .subfield_str('a', "Title")
+                    .subfield_str('a', "Again")
+                    .subfield_str('a', "And again")
+                    .subfield_str('j', "Undefined")
+                    .build(),
+            );
+        }
+        record.add_field(
+            crate::record::Field::builder("650".to_string(), ' ', '0')
+                .subfield_str('a', "Cats")
+                .subfield_str('x', "Behavior")
+                .subfield_str('x', "Juvenile")
+                .build(),
+        );
+        record.add_field(
+            crate::record::Field::builder("949".to_string(), ' ', ' ')
+                .subfield_str('a', "local")
+                .subfield_str('a', "local")
+                .build(),
+        );
+
+        let issues = RecordStructureValidator::dictionary_issues(&record);
+        assert_eq!(
+            issues[..3],
+            [
+                DictionaryIssue::NonRepeatableField {
+                    tag: "001".to_string(),
+                    count: 2,
+                },
+                DictionaryIssue::NonRepeatableField {
+                    tag: "245".to_string(),
+                    count: 2,
+                },
+                DictionaryIssue::NonRepeatableSubfield {
+                    tag: "245".to_string(),
+                    index: 0,
+                    code: 'a',
+                },
+            ]
+        );
+        assert_eq!(issues.len(), 6);
+        assert_eq!(
+            issues[3].to_string(),
+            "field 245 (occurrence 0) has undefined subfield $j"
+        );
+    }
 }
@@ -127,3 +127,245 @@ pub enum ValidationLevel {
     /// reader.
     StrictMarc,
 }
+
+/// Strictness settings shared by the ISO 2709, MARCXML and JSON readers.
+///
+/// Bundles [`RecoveryMode`] and [`ValidationLevel`] with whether recovered
+/// problems are kept on the record as warnings ([`crate::Record::warnings`]).
+/// Outside [`RecoveryMode::Strict`], readers repair what they can rather than
+/// dropping it: an invalid indicator becomes a blank (at
+/// [`ValidationLevel::StrictMarc`], where indicators are checked); an ISO
+/// 2709 leader whose record length is shorter than the record is corrected
+/// to the length the directory describes; an ISO 2709 field missing its
+/// field terminator is kept whole; and a malformed leader read from MARCXML
+/// or JSON is padded or truncated to 24 bytes with unparseable lengths
+/// zeroed.
+///
+/// The default is strict, structural validation with warnings collected —
+/// the readers' behavior without options.
+///
+/// ```
+/// use mrrc::{MarcReader, ParseOptions};
+///
+/// let reader = MarcReader::new(std::io::empty()).with_parse_options(ParseOptions::lenient());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// What to do when a problem is found.
+    pub recovery_mode: RecoveryMode,
+    /// Which problems are checked for.
+    pub validation_level: ValidationLevel,
+    /// Keep recovered problems on the record as warnings. When false they
+    /// are still recovered from, but discarded.
+    pub collect_warnings: bool,
+    /// Hardening limits applied by the XML readers; ignored elsewhere.
+    pub xml: crate::xml_security::XmlOptions,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions::strict()
+    }
+}
+
+impl ParseOptions {
+    /// Fail on the first problem.
+    #[must_use]
+    pub const fn strict() -> Self {
+        ParseOptions {
+            recovery_mode: RecoveryMode::Strict,
+            validation_level: ValidationLevel::Structural,
+            collect_warnings: true,
+            xml: crate::xml_security::XmlOptions::new(),
+        }
+    }
+
+    /// Repair or skip what is broken, checking indicators, subfield codes
+    /// and UTF-8 so that problems there are repaired and reported too.
+    #[must_use]
+    pub const fn lenient() -> Self {
+        ParseOptions {
+            recovery_mode: RecoveryMode::Lenient,
+            validation_level: ValidationLevel::StrictMarc,
+            collect_warnings: true,
+            xml: crate::xml_security::XmlOptions::new(),
+        }
+    }
+
+    pub(crate) fn is_strict(self) -> bool {
+        self.recovery_mode == RecoveryMode::Strict
+    }
+}
+
+/// Which bytes [`MarcReader`](crate::MarcReader) accepts as subfield
+/// delimiters besides the standard `0x1F`.
+///
+/// Some legacy systems write records that are otherwise valid ISO 2709
+/// but use `|` (0x7C) or `$` as the subfield delimiter. With an alternate
+/// delimiter allowed, a data field is converted when the byte after its
+/// indicators is that delimiter followed by a subfield code (an ASCII
+/// lower-case letter or digit); every such delimiter-and-code pair in the
+/// field then becomes `0x1F` plus the code, and the record carries one
+/// warning naming the bytes converted. A `$` or `|` in a value that is
+/// followed by a lower-case letter or digit (`$10.00`) is read as a
+/// delimiter too, so only enable this for sources known to need it.
+///
+/// ```
+/// use mrrc::{MarcReader, SubfieldDelimiters};
+///
+/// let reader = MarcReader::new(std::io::empty())
+///     .with_subfield_delimiters(SubfieldDelimiters::Detect);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SubfieldDelimiters {
+    /// Only `0x1F` (the default)
+    #[default]
+    Standard,
+    /// `0x1F`, or the given byte in fields that start with it
+    Alternate(u8),
+    /// `0x1F`, or `|` or `$` in fields that start with one of them
+    Detect,
+}
+
+impl SubfieldDelimiters {
+    /// Whether `byte` is accepted as an alternate delimiter.
+    #[must_use]
+    pub fn accepts(self, byte: u8) -> bool {
+        match self {
+            SubfieldDelimiters::Standard => false,
+            SubfieldDelimiters::Alternate(alternate) => byte == alternate,
+            SubfieldDelimiters::Detect => matches!(byte, b'|' | b'$'),
+        }
+    }
+}
+
+/// Upper bounds on the size of records accepted by the ISO 2709 readers.
+///
+/// The leader and directory already cap a record at 99,999 bytes and a
+/// field at 9,999, which [`ReaderLimits::default`] allows. Services that
+/// parse untrusted uploads can lower them with
+/// [`MarcReader::with_limits`](crate::MarcReader::with_limits). A record
+/// over a limit is an error in every [`RecoveryMode`]: it is not salvaged,
+/// and its body is read past without being buffered so the stream stays
+/// aligned on the next record.
+///
+/// ```
+/// use mrrc::{MarcReader, ReaderLimits};
+///
+/// let limits = ReaderLimits::default()
+///     .with_max_record_len(20_000)
+///     .with_max_fields(500);
+/// let reader = MarcReader::new(std::io::empty()).with_limits(limits);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReaderLimits {
+    /// Largest record length (leader bytes 0-4) accepted, in bytes.
+    pub max_record_len: usize,
+    /// Largest field length accepted from a directory entry, in bytes.
+    pub max_field_len: usize,
+    /// Most directory entries accepted in one record.
+    pub max_fields: usize,
+}
+
+impl Default for ReaderLimits {
+    fn default() -> Self {
+        ReaderLimits {
+            max_record_len: crate::iso2709::ISO2709_MAX_FIELD,
+            max_field_len: crate::iso2709::ISO2709_MAX_DIRECTORY_FIELD_LEN,
+            max_fields: 10_000,
+        }
+    }
+}
+
+impl ReaderLimits {
+    /// Set the largest record length accepted.
+    #[must_use]
+    pub const fn with_max_record_len(mut self, len: usize) -> Self {
+        self.max_record_len = len;
+        self
+    }
+
+    /// Set the largest field length accepted.
+    #[must_use]
+    pub const fn with_max_field_len(mut self, len: usize) -> Self {
+        self.max_field_len = len;
+        self
+    }
+
+    /// Set the most fields accepted in one record.
+    #[must_use]
+    pub const fn with_max_fields(mut self, count: usize) -> Self {
+        self.max_fields = count;
+        self
+    }
+}
+
+/// Parse a leader taken from a text format (MARCXML, JSON).
+///
+/// Outside strict mode a leader that fails to parse is repaired — padded
+/// or cut to 24 bytes, with non-digit lengths and counts replaced by their
+/// usual values — and the original error is pushed to `warnings`.
+pub(crate) fn leader_from_text(
+    raw: &str,
+    options: ParseOptions,
+    warnings: &mut Vec<MarcError>,
+) -> Result<crate::Leader> {
+    let err = match crate::Leader::from_bytes(raw.as_bytes()) {
+        Ok(leader) => return Ok(leader),
+        Err(err) if options.is_strict() => return Err(err),
+        Err(err) => err,
+    };
+    let mut bytes: Vec<u8> = raw.bytes().take(24).collect();
+    bytes.resize(24, b' ');
+    for (range, fallback) in [
+        (0..5, "00000"),
+        (10..11, "2"),
+        (11..12, "2"),
+        (12..17, "00000"),
+    ] {
+        if !bytes[range.clone()].iter().all(u8::is_ascii_digit) {
+            bytes[range].copy_from_slice(fallback.as_bytes());
+        }
+    }
+    let leader = crate::Leader::from_bytes(&bytes)?;
+    warnings.push(err);
+    Ok(leader)
+}
+
+/// Check an indicator taken from a text format (MARCXML, JSON).
+///
+/// Only checked at [`ValidationLevel::StrictMarc`], as in the ISO 2709
+/// reader. An indicator that is not a digit or blank is an error in strict
+/// mode; otherwise it is replaced by a blank and the error pushed to
+/// `warnings`.
+pub(crate) fn indicator_from_text(
+    indicator: char,
+    tag: &str,
+    position: u8,
+    options: ParseOptions,
+    warnings: &mut Vec<MarcError>,
+) -> Result<char> {
+    if options.validation_level != ValidationLevel::StrictMarc
+        || indicator.is_ascii_digit()
+        || indicator == ' '
+    {
+        return Ok(indicator);
+    }
+    let err = MarcError::InvalidIndicator {
+        record_index: None,
+        byte_offset: None,
+        record_byte_offset: None,
+        source_name: None,
+        record_control_number: None,
+        field_tag: Some(tag.to_string()),
+        indicator_position: Some(position),
+        found: Some(indicator.to_string().into_bytes()),
+        expected: Some("ASCII digit (0-9) or space".to_string()),
+        bytes_near: None,
+    };
+    if options.is_strict() {
+        return Err(err);
+    }
+    warnings.push(err);
+    Ok(' ')
+}
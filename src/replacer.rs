@@ -0,0 +1,335 @@
+//! Regular-expression find and replace across records.
+//!
+//! A [`Replacer`] pairs a regex with a replacement and a scope — the tags
+//! and subfield codes it may touch — and rewrites every matching value in
+//! scope. The replacement uses the [`regex`] crate's syntax, so `$1` and
+//! `${name}` insert capture groups and `$$` is a literal dollar sign.
+//!
+//! Tags are written as exact tags or with `X` for any character (`6XX`).
+//! With no tags every field is in scope; with no subfield codes every
+//! subfield is, and so are control fields (which have no subfields) whose
+//! tags are in scope.
+//!
+//! [`Replacer::preview`] and [`Replacer::dry_run`] report what would change
+//! as [`ChangeEntry`]s without touching the records, for review before a
+//! batch edit; [`Replacer::replace_record`] and [`Replacer::replace_all`]
+//! make the changes. To log them, run the replacer inside
+//! [`TrackedRecord::track`](crate::change_log::TrackedRecord::track) under
+//! its [`name`](Replacer::name).
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::replacer::Replacer;
+//! use mrrc::{Field, Leader, Record};
+//!
+//! let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+//! record.add_field(
+//!     Field::builder("650".to_string(), ' ', '0')
+//!         .subfield_str('a', "Computers")
+//!         .subfield_str('x', "History -- 20th century")
+//!         .build(),
+//! );
+//!
+//! // "20th century" -> "century 20"
+//! let replacer = Replacer::new(r"(\d+)th century", "century $1")?
+//!     .with_tags(["6XX"])
+//!     .with_subfields(['x', 'y']);
+//!
+//! let preview = replacer.preview(&record);
+//! assert_eq!(preview[0].new.as_deref(), Some("History -- century 20"));
+//!
+//! assert_eq!(replacer.replace_record(&mut record), 1);
+//! let field = record.get_field("650").unwrap();
+//! assert_eq!(field.get_subfield('x'), Some("History -- century 20"));
+//! # Ok::<(), mrrc::MarcError>(())
+//! ```
+
+use std::borrow::Cow;
+
+use regex::Regex;
+
+use crate::change_log::{ChangeEntry, ChangeKind, RecordChanges};
+use crate::error::{MarcError, Result};
+use crate::record::Record;
+
+/// Default rule id of a [`Replacer`] in change logs.
+pub const DEFAULT_RULE: &str = "replace";
+
+/// A regex find-and-replace limited to a set of tags and subfields.
+#[derive(Debug, Clone)]
+pub struct Replacer {
+    name: String,
+    pattern: Regex,
+    replacement: String,
+    tags: Vec<String>,
+    subfields: Vec<char>,
+}
+
+impl Replacer {
+    /// Replace every match of `pattern` with `replacement` in every field
+    /// and subfield.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MarcError::InvalidField`] if `pattern` is not a valid
+    /// regular expression.
+    pub fn new(pattern: &str, replacement: &str) -> Result<Self> {
+        let pattern = Regex::new(pattern)
+            .map_err(|e| MarcError::invalid_field_msg(format!("Invalid replace pattern: {e}")))?;
+        Ok(Replacer {
+            name: DEFAULT_RULE.to_string(),
+            pattern,
+            replacement: replacement.to_string(),
+            tags: Vec::new(),
+            subfields: Vec::new(),
+        })
+    }
+
+    /// Only touch fields whose tags match these patterns (`X` matches any
+    /// character).
+    #[must_use]
+    pub fn with_tags<I, S>(mut self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.tags = tags.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Only touch subfields with these codes; control fields are then out
+    /// of scope.
+    #[must_use]
+    pub fn with_subfields(mut self, codes: impl IntoIterator<Item = char>) -> Self {
+        self.subfields = codes.into_iter().collect();
+        self
+    }
+
+    /// Report changes under `name` instead of [`DEFAULT_RULE`].
+    #[must_use]
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// The rule id changes are reported under.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn in_scope(&self, tag: &str) -> bool {
+        self.tags.is_empty()
+            || self
+                .tags
+                .iter()
+                .any(|pattern| crate::field_protection::tag_matches(pattern, tag))
+    }
+
+    fn replace<'v>(&self, value: &'v str) -> Option<Cow<'v, str>> {
+        match self.pattern.replace_all(value, self.replacement.as_str()) {
+            Cow::Owned(new) if new != value => Some(Cow::Owned(new)),
+            _ => None,
+        }
+    }
+
+    /// The changes [`replace_record`](Replacer::replace_record) would make
+    /// to `record`, without making them.
+    #[must_use]
+    pub fn preview(&self, record: &Record) -> Vec<ChangeEntry> {
+        let mut entries = Vec::new();
+        let mut entry = |tag: &str, occurrence, subfield, position, old: &str, new: Cow<str>| {
+            entries.push(ChangeEntry {
+                rule: self.name.clone(),
+                tag: tag.to_string(),
+                occurrence,
+                subfield,
+                position,
+                indicators: false,
+                kind: ChangeKind::Modified,
+                old: Some(old.to_string()),
+                new: Some(new.into_owned()),
+            });
+        };
+        if self.subfields.is_empty() {
+            for (tag, values) in &record.control_fields {
+                if !self.in_scope(tag) {
+                    continue;
+                }
+                for (occurrence, value) in values.iter().enumerate() {
+                    if let Some(new) = self.replace(value) {
+                        entry(tag, occurrence, None, None, value, new);
+                    }
+                }
+            }
+        }
+        for (tag, fields) in &record.fields {
+            if !self.in_scope(tag) {
+                continue;
+            }
+            for (occurrence, field) in fields.iter().enumerate() {
+                for (position, subfield) in field.subfields.iter().enumerate() {
+                    if !self.subfields.is_empty() && !self.subfields.contains(&subfield.code) {
+                        continue;
+                    }
+                    if let Some(new) = self.replace(&subfield.value) {
+                        let code = Some(subfield.code);
+                        entry(tag, occurrence, code, Some(position), &subfield.value, new);
+                    }
+                }
+            }
+        }
+        entries
+    }
+
+    /// Make the replacements in `record` and return how many values
+    /// changed.
+    pub fn replace_record(&self, record: &mut Record) -> usize {
+        let changes = self.preview(record);
+        if changes.is_empty() {
+            return 0;
+        }
+        for change in &changes {
+            let new = change.new.clone().unwrap_or_default();
+            let value = match change.position {
+                Some(position) => record
+                    .fields
+                    .get_mut(&change.tag)
+                    .and_then(|fields| fields.get_mut(change.occurrence))
+                    .and_then(|field| field.subfields.get_mut(position))
+                    .map(|subfield| &mut subfield.value),
+                None => record
+                    .control_fields
+                    .get_mut(&change.tag)
+                    .and_then(|values| values.get_mut(change.occurrence)),
+            };
+            if let Some(value) = value {
+                *value = new;
+            }
+        }
+        changes.len()
+    }
+
+    /// Make the replacements in every record of a stream as it is read,
+    /// passing errors through.
+    pub fn replace_all<'a, I>(&'a self, records: I) -> impl Iterator<Item = Result<Record>> + 'a
+    where
+        I: IntoIterator<Item = Result<Record>>,
+        I::IntoIter: 'a,
+    {
+        records.into_iter().map(move |record| {
+            record.map(|mut record| {
+                self.replace_record(&mut record);
+                record
+            })
+        })
+    }
+
+    /// Report what the replacements would change in a stream, one entry
+    /// per record that would change (numbered from 1 in stream order),
+    /// without changing anything.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from `records`.
+    pub fn dry_run<I>(&self, records: I) -> Result<Vec<RecordChanges>>
+    where
+        I: IntoIterator<Item = Result<Record>>,
+    {
+        let mut report = Vec::new();
+        for (index, record) in records.into_iter().enumerate() {
+            let record = record?;
+            let changes = self.preview(&record);
+            if !changes.is_empty() {
+                report.push(RecordChanges {
+                    record: index + 1,
+                    control_number: record.get_control_field("001").map(str::to_string),
+                    changes,
+                });
+            }
+        }
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::change_log::TrackedRecord;
+    use crate::leader::Leader;
+    use crate::record::Field;
+
+    fn record(id: &str, note: &str) -> Record {
+        let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+        record.add_control_field_str("001", id);
+        record.add_field(
+            Field::builder("500".to_string(), ' ', ' ')
+                .subfield_str('a', note)
+                .subfield_str('5', note)
+                .build(),
+        );
+        record.add_field(
+            Field::builder("590".to_string(), ' ', ' ')
+                .subfield_str('a', note)
+                .build(),
+        );
+        record
+    }
+
+    #[test]
+    fn test_scope_limits_replacements() {
+        let replacer = Replacer::new("ocm", "on").unwrap();
+        let mut all = record("ocm123", "ocm note");
+        assert_eq!(replacer.replace_record(&mut all), 4);
+        assert_eq!(all.get_control_field("001"), Some("on123"));
+
+        let scoped = Replacer::new("ocm", "on")
+            .unwrap()
+            .with_tags(["50X"])
+            .with_subfields(['a']);
+        let mut some = record("ocm123", "ocm note");
+        assert_eq!(scoped.replace_record(&mut some), 1);
+        assert_eq!(some.get_control_field("001"), Some("ocm123"));
+        let note = some.get_field("500").unwrap();
+        assert_eq!(note.get_subfield('a'), Some("on note"));
+        assert_eq!(note.get_subfield('5'), Some("ocm note"));
+        assert_eq!(
+            some.get_field("590").unwrap().get_subfield('a'),
+            Some("ocm note")
+        );
+
+        assert!(Replacer::new("(", "").is_err());
+    }
+
+    #[test]
+    fn test_dry_run_reports_without_changing() {
+        let replacer = Replacer::new(r"(\w+), (\w+)", "$2 $1")
+            .unwrap()
+            .with_tags(["590"])
+            .with_name("swap-names");
+        let records = vec![
+            Ok(record("a1", "no comma")),
+            Ok(record("a2", "Smith, Jane")),
+        ];
+        let report = replacer.dry_run(records).unwrap();
+        assert_eq!(report.len(), 1);
+        assert_eq!(report[0].record, 2);
+        assert_eq!(report[0].control_number.as_deref(), Some("a2"));
+        let change = &report[0].changes[0];
+        assert_eq!(
+            (change.rule.as_str(), change.tag.as_str()),
+            ("swap-names", "590")
+        );
+        assert_eq!(change.new.as_deref(), Some("Jane Smith"));
+
+        let mut tracked = TrackedRecord::new(record("a3", "Doe, John"));
+        let replaced = tracked.track(replacer.name(), |r| replacer.replace_record(r));
+        assert_eq!(replaced, 1);
+        assert_eq!(tracked.change_log().by_rule("swap-names").count(), 1);
+        assert_eq!(
+            tracked.record().get_field("590").unwrap().get_subfield('a'),
+            Some("John Doe")
+        );
+    }
+}
@@ -0,0 +1,353 @@
+//! schema.org JSON-LD for embedding in web pages.
+//!
+//! [`record_to_schema_org`] describes a bibliographic record as a
+//! schema.org `Book`, `Periodical` or `CreativeWork`, the shape search
+//! engines read from a `<script type="application/ld+json">` block in a
+//! catalog page. Only the widely consumed properties are produced:
+//!
+//! | Property        | Source                                            |
+//! |-----------------|---------------------------------------------------|
+//! | `@type`         | Leader/06-07: `Book`, `Periodical`, else `CreativeWork` |
+//! | `name`          | 245 `$a $b $n $p`                                 |
+//! | `alternateName` | 246 `$a $b`                                       |
+//! | `author`        | 100 (`Person`), 110 and 111 (`Organization`)      |
+//! | `contributor`   | 700 (`Person`), 710 and 711 (`Organization`)      |
+//! | `isbn` / `issn` | 020 `$a` / 022 `$a`                               |
+//! | `bookEdition`   | 250 `$a` (books only)                             |
+//! | `publisher`     | 260 `$b`, else 264 second indicator 1 `$b`        |
+//! | `datePublished` | Publication year from 260/264 `$c`, else 008/07-10 |
+//! | `inLanguage`    | 008/35-37                                         |
+//! | `about`         | 600, 610, 611, 630, 650, 651 with subdivisions    |
+//! | `description`   | 520 `$a`                                          |
+//! | `url`           | 856 `$u`                                          |
+//!
+//! ISBD punctuation is stripped from values. Properties with one value are
+//! written as a single value and properties with several as an array.
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::schema_org::record_to_schema_org;
+//! use mrrc::{Field, Leader, Record};
+//!
+//! let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+//! record.add_field(
+//!     Field::builder("100".to_string(), '1', ' ')
+//!         .subfield_str('a', "Twain, Mark,")
+//!         .build(),
+//! );
+//! record.add_field(
+//!     Field::builder("245".to_string(), '1', '0')
+//!         .subfield_str('a', "Roughing it /")
+//!         .subfield_str('c', "Mark Twain.")
+//!         .build(),
+//! );
+//!
+//! let json = record_to_schema_org(&record);
+//! assert_eq!(json["@type"], "Book");
+//! assert_eq!(json["name"], "Roughing it");
+//! assert_eq!(json["author"]["name"], "Twain, Mark");
+//! ```
+
+use serde_json::{Map, Value, json};
+
+use crate::punctuation::{display_value, strip_value};
+use crate::record::{Field, Record};
+use crate::record_helpers::RecordHelpers;
+
+/// Subject tags mapped to `about`, with the schema.org type of each heading.
+const SUBJECTS: &[(&str, &str)] = &[
+    ("600", "Person"),
+    ("610", "Organization"),
+    ("611", "Event"),
+    ("630", "CreativeWork"),
+    ("650", "Thing"),
+    ("651", "Place"),
+];
+
+/// Convert a bibliographic record to a schema.org JSON-LD object.
+///
+/// See the [module documentation](self) for the properties produced.
+#[must_use]
+pub fn record_to_schema_org(record: &Record) -> Value {
+    crate::telemetry::stage_span!("mrrc.convert", from = "marc", to = "schema_org");
+    let kind = schema_type(record);
+    let mut object = Map::new();
+    object.insert("@context".to_string(), json!("https://schema.org"));
+    object.insert("@type".to_string(), json!(kind));
+
+    let mut set = |property: &str, values: Vec<Value>| {
+        if let Some(value) = one_or_many(values) {
+            object.insert(property.to_string(), value);
+        }
+    };
+
+    set(
+        "name",
+        field_values(record, "245", &['a', 'b', 'n', 'p'], " ")
+            .into_iter()
+            .take(1)
+            .map(Value::from)
+            .collect(),
+    );
+    set("alternateName", strings(record, "246", &['a', 'b']));
+    set("author", agents(record, &["100", "110", "111"]));
+    set("contributor", agents(record, &["700", "710", "711"]));
+    match kind {
+        "Periodical" => set("issn", identifiers(record, "022")),
+        _ => set("isbn", identifiers(record, "020")),
+    }
+    if kind == "Book" {
+        set("bookEdition", strings(record, "250", &['a']));
+    }
+    set("publisher", publisher(record).into_iter().collect());
+    set(
+        "datePublished",
+        record
+            .publication_year()
+            .map(|year| json!(year.to_string()))
+            .into_iter()
+            .collect(),
+    );
+    set(
+        "inLanguage",
+        record.language().map(Value::from).into_iter().collect(),
+    );
+    set("about", subjects(record));
+    set("description", strings(record, "520", &['a']));
+    set("url", strings(record, "856", &['u']));
+
+    Value::Object(object)
+}
+
+/// `Book` for language material monographs, `Periodical` for serials,
+/// `CreativeWork` otherwise.
+fn schema_type(record: &Record) -> &'static str {
+    let leader = &record.leader;
+    match (leader.record_type, leader.bibliographic_level) {
+        (_, 's') => "Periodical",
+        ('a' | 't', 'm') => "Book",
+        _ => "CreativeWork",
+    }
+}
+
+/// One value as itself, several as an array, none as `None`.
+fn one_or_many(mut values: Vec<Value>) -> Option<Value> {
+    match values.len() {
+        0 => None,
+        1 => values.pop(),
+        _ => Some(Value::Array(values)),
+    }
+}
+
+/// The `codes` subfields of each `tag` field with ISBD punctuation removed,
+/// joined with `separator`; fields with none of the subfields are skipped.
+fn field_values(record: &Record, tag: &str, codes: &[char], separator: &str) -> Vec<String> {
+    record
+        .fields_by_tag(tag)
+        .filter_map(|field| display_value(field, codes, separator))
+        .collect()
+}
+
+fn strings(record: &Record, tag: &str, codes: &[char]) -> Vec<Value> {
+    field_values(record, tag, codes, " ")
+        .into_iter()
+        .map(Value::from)
+        .collect()
+}
+
+/// ISBNs or ISSNs, without qualifiers such as `(pbk.)`.
+fn identifiers(record: &Record, tag: &str) -> Vec<Value> {
+    field_values(record, tag, &['a'], " ")
+        .iter()
+        .filter_map(|value| value.split_whitespace().next())
+        .map(Value::from)
+        .collect()
+}
+
+/// Persons (X00) and organizations (X10, X11) from name fields.
+fn agents(record: &Record, tags: &[&str]) -> Vec<Value> {
+    let mut agents = Vec::new();
+    for tag in tags {
+        let (kind, codes): (&str, &[char]) = if tag.ends_with("00") {
+            ("Person", &['a', 'b', 'c', 'q'])
+        } else {
+            ("Organization", &['a', 'b'])
+        };
+        for name in field_values(record, tag, codes, " ") {
+            agents.push(json!({ "@type": kind, "name": name }));
+        }
+    }
+    agents
+}
+
+fn publisher(record: &Record) -> Option<Value> {
+    let field = record.get_field("260").or_else(|| {
+        record
+            .fields_by_tag("264")
+            .find(|field| field.indicator2 == '1')
+    })?;
+    let name = display_value(field, &['b'], " ")?;
+    Some(json!({ "@type": "Organization", "name": name }))
+}
+
+/// Subject headings as typed things, subdivisions joined with ` -- `.
+fn subjects(record: &Record) -> Vec<Value> {
+    let mut about = Vec::new();
+    for (tag, kind) in SUBJECTS {
+        let codes: &[char] = match *tag {
+            "600" => &['a', 'b', 'c', 'd', 'q', 't', 'v', 'x', 'y', 'z'],
+            "610" | "611" => &['a', 'b', 'c', 'd', 'n', 't', 'v', 'x', 'y', 'z'],
+            _ => &['a', 'b', 'p', 'v', 'x', 'y', 'z'],
+        };
+        for field in record.fields_by_tag(tag) {
+            let Some(heading) = subject_heading(field, codes) else {
+                continue;
+            };
+            let mut thing = json!({ "@type": kind, "name": heading });
+            if let Some(uri) = field
+                .get_subfield_values('0')
+                .into_iter()
+                .find(|v| v.starts_with("http"))
+            {
+                thing["@id"] = json!(uri);
+            }
+            about.push(thing);
+        }
+    }
+    about
+}
+
+/// The heading, with `$v`, `$x`, `$y` and `$z` subdivisions after ` -- `.
+fn subject_heading(field: &Field, codes: &[char]) -> Option<String> {
+    let mut heading = String::new();
+    for subfield in field.subfields.iter().filter(|sf| codes.contains(&sf.code)) {
+        let value = strip_value(subfield.value.trim_start());
+        if value.is_empty() {
+            continue;
+        }
+        if !heading.is_empty() {
+            heading.push_str(if matches!(subfield.code, 'v' | 'x' | 'y' | 'z') {
+                " -- "
+            } else {
+                " "
+            });
+        }
+        heading.push_str(value);
+    }
+    (!heading.is_empty()).then_some(heading)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::leader::Leader;
+
+    fn record(leader: &[u8]) -> Record {
+        let mut record = Record::new(Leader::from_bytes(leader).unwrap());
+        record.add_control_field_str("008", "850101s1872    nyu           000 0 eng d");
+        record
+    }
+
+    fn add(record: &mut Record, tag: &str, ind2: char, subfields: &[(char, &str)]) {
+        let mut builder = Field::builder(tag.to_string(), ' ', ind2);
+        for (code, value) in subfields {
+            builder = builder.subfield_str(*code, value);
+        }
+        record.add_field(builder.build());
+    }
+
+    #[test]
+    fn test_book() {
+        let mut record = record(b"00000nam a2200000 a 4500");
+        add(&mut record, "020", ' ', &[('a', "9780306406157 (pbk.)")]);
+        add(
+            &mut record,
+            "100",
+            ' ',
+            &[('a', "Twain, Mark,"), ('d', "1835-1910.")],
+        );
+        add(
+            &mut record,
+            "245",
+            '0',
+            &[('a', "Roughing it :"), ('b', "a memoir /"), ('c', "Twain.")],
+        );
+        add(&mut record, "250", ' ', &[('a', "1st ed.")]);
+        add(
+            &mut record,
+            "264",
+            '1',
+            &[
+                ('a', "Hartford :"),
+                ('b', "American Pub. Co.,"),
+                ('c', "1872."),
+            ],
+        );
+        add(
+            &mut record,
+            "650",
+            '0',
+            &[
+                ('a', "Frontier and pioneer life"),
+                ('z', "Nevada."),
+                ('0', "http://id.loc.gov/x"),
+            ],
+        );
+        add(&mut record, "651", '0', &[('a', "West (U.S.)")]);
+        add(&mut record, "700", ' ', &[('a', "Smith, A.")]);
+        add(&mut record, "710", ' ', &[('a', "Mark Twain Project.")]);
+
+        let json = record_to_schema_org(&record);
+        assert_eq!(json["@context"], "https://schema.org");
+        assert_eq!(json["@type"], "Book");
+        assert_eq!(json["name"], "Roughing it a memoir");
+        assert_eq!(json["isbn"], "9780306406157");
+        assert_eq!(json["bookEdition"], "1st ed.");
+        assert_eq!(
+            json["author"],
+            json!({"@type": "Person", "name": "Twain, Mark"})
+        );
+        assert_eq!(json["contributor"][1]["@type"], "Organization");
+        assert_eq!(json["publisher"]["name"], "American Pub. Co.");
+        assert_eq!(json["datePublished"], "1872");
+        assert_eq!(json["inLanguage"], "eng");
+        assert_eq!(
+            json["about"][0]["name"],
+            "Frontier and pioneer life -- Nevada"
+        );
+        assert_eq!(json["about"][0]["@id"], "http://id.loc.gov/x");
+        assert_eq!(
+            json["about"][1],
+            json!({"@type": "Place", "name": "West (U.S.)"})
+        );
+    }
+
+    #[test]
+    fn test_non_ascii_008_does_not_panic() {
+        let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+        record.add_control_field_str("008", "850101é1872    nyu           000 0 eé d");
+        add(&mut record, "245", '0', &[('a', "Untitled.")]);
+        let json = record_to_schema_org(&record);
+        assert_eq!(json["name"], "Untitled");
+        assert!(json.get("datePublished").is_none());
+        assert!(json.get("inLanguage").is_none());
+    }
+
+    #[test]
+    fn test_periodical_and_creative_work() {
+        let mut serial = record(b"00000nas a2200000 a 4500");
+        add(&mut serial, "020", ' ', &[('a', "0306406152")]);
+        add(&mut serial, "022", ' ', &[('a', "1234-5678")]);
+        let json = record_to_schema_org(&serial);
+        assert_eq!(json["@type"], "Periodical");
+        assert_eq!(json["issn"], "1234-5678");
+        assert!(json.get("isbn").is_none());
+
+        let map = record(b"00000nem a2200000 a 4500");
+        let json = record_to_schema_org(&map);
+        assert_eq!(json["@type"], "CreativeWork");
+        assert!(json.get("name").is_none());
+        assert!(json.get("bookEdition").is_none());
+    }
+}
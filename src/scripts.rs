@@ -0,0 +1,256 @@
+//! Writing systems used in a record and its language of cataloging.
+//!
+//! A record's scripts are read from two places: the script identification
+//! code at the end of each 880's `$6` (`245-01/(N`, `245-01/cyrl`), and the
+//! Unicode ranges of the characters in its data fields. The same heuristics
+//! give the language tags the BIBFRAME converter puts on 880 literals.
+//!
+//! ```
+//! use mrrc::scripts::Script;
+//! use mrrc::{Field, Leader, Record};
+//!
+//! let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+//! record.add_field(
+//!     Field::builder("040".to_string(), ' ', ' ')
+//!         .subfield_str('a', "DLC")
+//!         .subfield_str('b', "eng")
+//!         .build(),
+//! );
+//! record.add_field(
+//!     Field::builder("880".to_string(), '1', '0')
+//!         .subfield_str('6', "245-01/(N")
+//!         .subfield_str('a', "Война и мир")
+//!         .build(),
+//! );
+//! record.add_field(
+//!     Field::builder("245".to_string(), '1', '0')
+//!         .subfield_str('6', "880-01")
+//!         .subfield_str('a', "Voĭna i mir")
+//!         .build(),
+//! );
+//!
+//! assert_eq!(record.scripts_used(), vec![Script::Latin, Script::Cyrillic]);
+//! assert_eq!(record.cataloging_language(), Some("eng"));
+//! ```
+
+use std::collections::BTreeSet;
+
+use crate::record::{Field, Record};
+
+/// A writing system recognized in MARC data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Script {
+    /// Basic and extended Latin.
+    Latin,
+    /// Greek.
+    Greek,
+    /// Cyrillic.
+    Cyrillic,
+    /// Hebrew.
+    Hebrew,
+    /// Arabic.
+    Arabic,
+    /// CJK ideographs (Chinese characters, kanji, hanja).
+    Han,
+    /// Japanese hiragana and katakana.
+    Kana,
+    /// Korean hangul.
+    Hangul,
+}
+
+impl Script {
+    /// Script named by an 880 `$6` script identification code.
+    ///
+    /// Accepts the MARC-8 escape codes (`(3`, `(N`, `(2`, `(S`, `(B`) and
+    /// ISO 15924 codes in any case. `$1` (EACC) covers Han, kana and hangul
+    /// alike, so it names no single script and returns `None`.
+    #[must_use]
+    pub fn from_code(code: &str) -> Option<Script> {
+        match code {
+            "(B" => Some(Script::Latin),
+            "(S" => Some(Script::Greek),
+            "(N" => Some(Script::Cyrillic),
+            "(2" => Some(Script::Hebrew),
+            "(3" => Some(Script::Arabic),
+            _ => match code.to_ascii_lowercase().as_str() {
+                "latn" => Some(Script::Latin),
+                "grek" => Some(Script::Greek),
+                "cyrl" => Some(Script::Cyrillic),
+                "hebr" => Some(Script::Hebrew),
+                "arab" => Some(Script::Arabic),
+                "hani" => Some(Script::Han),
+                "jpan" | "hira" | "kana" | "hrkt" => Some(Script::Kana),
+                "hang" | "kore" => Some(Script::Hangul),
+                _ => None,
+            },
+        }
+    }
+
+    /// Script a character belongs to, or `None` for digits, punctuation
+    /// and scripts not listed here.
+    #[must_use]
+    pub fn of_char(ch: char) -> Option<Script> {
+        match ch {
+            'A'..='Z'
+            | 'a'..='z'
+            | '\u{00C0}'..='\u{00D6}'
+            | '\u{00D8}'..='\u{00F6}'
+            | '\u{00F8}'..='\u{024F}'
+            | '\u{1E00}'..='\u{1EFF}' => Some(Script::Latin),
+            '\u{0370}'..='\u{03FF}' | '\u{1F00}'..='\u{1FFF}' => Some(Script::Greek),
+            '\u{0400}'..='\u{04FF}' | '\u{0500}'..='\u{052F}' => Some(Script::Cyrillic),
+            '\u{0590}'..='\u{05FF}' => Some(Script::Hebrew),
+            '\u{0600}'..='\u{06FF}' | '\u{0750}'..='\u{077F}' => Some(Script::Arabic),
+            '\u{3400}'..='\u{4DBF}' | '\u{4E00}'..='\u{9FFF}' | '\u{F900}'..='\u{FAFF}' => {
+                Some(Script::Han)
+            },
+            '\u{3040}'..='\u{309F}' | '\u{30A0}'..='\u{30FF}' => Some(Script::Kana),
+            '\u{1100}'..='\u{11FF}' | '\u{AC00}'..='\u{D7AF}' => Some(Script::Hangul),
+            _ => None,
+        }
+    }
+
+    /// ISO 15924 code for the script.
+    #[must_use]
+    pub fn iso15924(self) -> &'static str {
+        match self {
+            Script::Latin => "Latn",
+            Script::Greek => "Grek",
+            Script::Cyrillic => "Cyrl",
+            Script::Hebrew => "Hebr",
+            Script::Arabic => "Arab",
+            Script::Han => "Hani",
+            Script::Kana => "Hrkt",
+            Script::Hangul => "Hang",
+        }
+    }
+
+    /// Most likely BCP 47 language for text in this script.
+    ///
+    /// A guess, not an identification: Cyrillic is tagged Russian and Han
+    /// Chinese. Latin text gets no tag.
+    #[must_use]
+    pub fn language_hint(self) -> Option<&'static str> {
+        match self {
+            Script::Latin => None,
+            Script::Greek => Some("el"),
+            Script::Cyrillic => Some("ru"),
+            Script::Hebrew => Some("he"),
+            Script::Arabic => Some("ar"),
+            Script::Han => Some("zh"),
+            Script::Kana => Some("ja"),
+            Script::Hangul => Some("ko"),
+        }
+    }
+}
+
+/// Script named in a field's `$6`, if it carries a script identification
+/// code (the part after `/`).
+#[must_use]
+pub fn linked_script(field: &Field) -> Option<Script> {
+    let linkage = field.get_subfield('6')?;
+    let (_, rest) = linkage.split_once('/')?;
+    // A field orientation code ("/r") may follow the script code
+    Script::from_code(rest.split('/').next().unwrap_or(rest))
+}
+
+/// Language tag for an alternate graphic representation.
+///
+/// Uses the `$6` script code when it implies a language, otherwise the
+/// first character outside Latin in the field's other subfields.
+#[must_use]
+pub fn linked_language(field: &Field) -> Option<&'static str> {
+    field.get_subfield('6')?;
+    linked_script(field)
+        .and_then(Script::language_hint)
+        .or_else(|| {
+            field
+                .subfields()
+                .filter(|sf| sf.code != '6')
+                .flat_map(|sf| sf.value.chars())
+                .find_map(|ch| Script::of_char(ch).and_then(Script::language_hint))
+        })
+}
+
+/// Every script used in a record's data fields, in [`Script`] order.
+///
+/// Counts the 880 `$6` script codes and the characters of every subfield
+/// except `$6`. Control fields hold codes, not text, and are ignored.
+#[must_use]
+pub fn scripts_used(record: &Record) -> Vec<Script> {
+    let mut scripts = BTreeSet::new();
+    for field in record.fields() {
+        if field.tag == "880" {
+            scripts.extend(linked_script(field));
+        }
+        for subfield in field.subfields().filter(|sf| sf.code != '6') {
+            scripts.extend(subfield.value.chars().filter_map(Script::of_char));
+        }
+    }
+    scripts.into_iter().collect()
+}
+
+/// Language of cataloging from 040 `$b`, when present.
+#[must_use]
+pub fn cataloging_language(record: &Record) -> Option<&str> {
+    record
+        .get_field("040")?
+        .get_subfield('b')
+        .map(str::trim)
+        .filter(|code| !code.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::leader::Leader;
+
+    fn field(tag: &str, subfields: &[(char, &str)]) -> Field {
+        let mut field = Field::new(tag.to_string(), ' ', ' ');
+        for (code, value) in subfields {
+            field.add_subfield_str(*code, value);
+        }
+        field
+    }
+
+    #[test]
+    fn linked_language_prefers_script_code_then_content() {
+        let coded = field("880", &[('6', "245-01/(3/r"), ('a', "abc")]);
+        assert_eq!(linked_script(&coded), Some(Script::Arabic));
+        assert_eq!(linked_language(&coded), Some("ar"));
+
+        let eacc = field("880", &[('6', "245-01/$1"), ('a', "1990 東京")]);
+        assert_eq!(linked_script(&eacc), None);
+        assert_eq!(linked_language(&eacc), Some("zh"));
+
+        let latin = field("880", &[('6', "245-01/(B"), ('a', "Ελληνικά")]);
+        assert_eq!(linked_language(&latin), Some("el"));
+        assert_eq!(linked_language(&field("245", &[('a', "Ελληνικά")])), None);
+    }
+
+    #[test]
+    fn scripts_used_collects_codes_and_characters() {
+        let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+        assert!(scripts_used(&record).is_empty());
+        record.add_field(field("880", &[('6', "245-01/hang"), ('a', "")]));
+        record.add_field(field("245", &[('6', "880-01"), ('a', "1984")]));
+        assert_eq!(scripts_used(&record), vec![Script::Hangul]);
+
+        record.add_field(field("500", &[('a', "ひらがな 漢字 Note")]));
+        assert_eq!(
+            scripts_used(&record),
+            vec![Script::Latin, Script::Han, Script::Kana, Script::Hangul]
+        );
+    }
+
+    #[test]
+    fn cataloging_language_reads_040_b() {
+        let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+        assert_eq!(cataloging_language(&record), None);
+        record.add_field(field("040", &[('a', "DLC"), ('b', " ")]));
+        assert_eq!(cataloging_language(&record), None);
+        record.fields.clear();
+        record.add_field(field("040", &[('a', "DLC"), ('b', "fre")]));
+        assert_eq!(cataloging_language(&record), Some("fre"));
+    }
+}
@@ -0,0 +1,365 @@
+//! Text search within a record, with hit positions and snippets.
+//!
+//! [`search`] (or [`Record::search`](crate::Record::search)) finds every
+//! occurrence of a term in a record's control fields and subfields and
+//! reports where it is — tag, field occurrence, subfield code and byte
+//! range in the value — with the surrounding text for a highlighted
+//! snippet.
+//!
+//! By default matching ignores case and diacritics: the term and the text
+//! are both compared in a folded form where letters are decomposed, their
+//! combining marks dropped, special letters spelled out (`æ` → `ae`,
+//! `ø` → `o`, `ł` → `l`, `þ` → `th`, `ß` → `ss`) and everything
+//! lowercased. Hit offsets always refer to the original, unfolded value.
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::search::SearchOptions;
+//! use mrrc::{Field, Leader, Record};
+//!
+//! let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+//! record.add_field(
+//!     Field::builder("245".to_string(), '1', '4')
+//!         .subfield_str('a', "The great Gatsby /")
+//!         .subfield_str('c', "F. Scott Fitzgerald.")
+//!         .build(),
+//! );
+//! record.add_field(
+//!     Field::builder("600".to_string(), '1', '0')
+//!         .subfield_str('a', "GÄTSBY, Jay")
+//!         .build(),
+//! );
+//!
+//! let hits = record.search("gatsby", &SearchOptions::default());
+//! assert_eq!(hits.len(), 2);
+//! assert_eq!((hits[0].tag.as_str(), hits[0].subfield), ("245", Some('a')));
+//! assert_eq!(hits[0].highlight("<b>", "</b>"), "The great <b>Gatsby</b> /");
+//! assert_eq!(hits[1].matched, "GÄTSBY");
+//!
+//! let exact = SearchOptions::default().with_fold_diacritics(false);
+//! assert_eq!(record.search("gatsby", &exact).len(), 1);
+//! ```
+
+use unicode_normalization::UnicodeNormalization;
+
+use crate::record::Record;
+
+/// How [`search`] matches and reports.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchOptions {
+    /// Match case exactly (default `false`)
+    pub case_sensitive: bool,
+    /// Ignore diacritics and spell out special letters (default `true`)
+    pub fold_diacritics: bool,
+    /// Also search control fields 001–009 (default `true`)
+    pub control_fields: bool,
+    /// Only search these tags; `X` matches any character, so `"6XX"`
+    /// covers all subject fields. Empty means every tag.
+    pub tags: Vec<String>,
+    /// Characters of context kept on each side of a hit (default 30)
+    pub context: usize,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        SearchOptions {
+            case_sensitive: false,
+            fold_diacritics: true,
+            control_fields: true,
+            tags: Vec::new(),
+            context: 30,
+        }
+    }
+}
+
+impl SearchOptions {
+    /// Set whether case must match exactly.
+    #[must_use]
+    pub fn with_case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+
+    /// Set whether diacritics are ignored.
+    #[must_use]
+    pub fn with_fold_diacritics(mut self, fold: bool) -> Self {
+        self.fold_diacritics = fold;
+        self
+    }
+
+    /// Set whether control fields are searched.
+    #[must_use]
+    pub fn with_control_fields(mut self, control_fields: bool) -> Self {
+        self.control_fields = control_fields;
+        self
+    }
+
+    /// Restrict the search to tags matching these patterns.
+    #[must_use]
+    pub fn with_tags<I, S>(mut self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.tags = tags.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the characters of context kept on each side of a hit.
+    #[must_use]
+    pub fn with_context(mut self, chars: usize) -> Self {
+        self.context = chars;
+        self
+    }
+
+    fn searches_tag(&self, tag: &str) -> bool {
+        self.tags.is_empty()
+            || self
+                .tags
+                .iter()
+                .any(|pattern| crate::field_protection::tag_matches(pattern, tag))
+    }
+}
+
+/// One occurrence of the search term.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hit {
+    /// Tag of the field the hit is in
+    pub tag: String,
+    /// 0-based occurrence of the field among fields with the same tag
+    pub occurrence: usize,
+    /// Subfield code, or `None` for a control field
+    pub subfield: Option<char>,
+    /// 0-based position of the subfield within the field, or `None` for a
+    /// control field
+    pub subfield_index: Option<usize>,
+    /// Byte offset of the hit in the field or subfield value
+    pub start: usize,
+    /// Byte offset just past the hit
+    pub end: usize,
+    /// The text matched, as it appears in the value
+    pub matched: String,
+    /// Up to [`SearchOptions::context`] characters before the hit
+    pub before: String,
+    /// Up to [`SearchOptions::context`] characters after the hit
+    pub after: String,
+}
+
+impl Hit {
+    /// The snippet with the matched text wrapped in `open` and `close`
+    /// (for example `"<mark>"` and `"</mark>"`).
+    #[must_use]
+    pub fn highlight(&self, open: &str, close: &str) -> String {
+        format!("{}{open}{}{close}{}", self.before, self.matched, self.after)
+    }
+}
+
+/// Find every occurrence of `term` in `record`, in field order.
+///
+/// Hits within one value do not overlap. An empty term (or one that folds
+/// to nothing) finds nothing.
+#[must_use]
+pub fn search(record: &Record, term: &str, options: &SearchOptions) -> Vec<Hit> {
+    let needle: Vec<char> = fold(term, options).into_iter().map(|(c, _)| c).collect();
+    let mut hits = Vec::new();
+    if needle.is_empty() {
+        return hits;
+    }
+    if options.control_fields {
+        let mut occurrences: Vec<&str> = Vec::new();
+        for (tag, value) in record.control_fields_iter() {
+            let occurrence = occurrences.iter().filter(|seen| **seen == tag).count();
+            occurrences.push(tag);
+            if options.searches_tag(tag) {
+                for (start, end) in find_in(value, &needle, options) {
+                    hits.push(Hit {
+                        tag: tag.to_string(),
+                        occurrence,
+                        ..hit(value, start, end, options)
+                    });
+                }
+            }
+        }
+    }
+    for (tag, fields) in &record.fields {
+        if !options.searches_tag(tag) {
+            continue;
+        }
+        for (occurrence, field) in fields.iter().enumerate() {
+            for (index, subfield) in field.subfields.iter().enumerate() {
+                let value = subfield.value.as_str();
+                for (start, end) in find_in(value, &needle, options) {
+                    hits.push(Hit {
+                        tag: tag.clone(),
+                        occurrence,
+                        subfield: Some(subfield.code),
+                        subfield_index: Some(index),
+                        ..hit(value, start, end, options)
+                    });
+                }
+            }
+        }
+    }
+    hits
+}
+
+/// A hit at `value[start..end]` with its context; location fields are
+/// filled in by the caller.
+fn hit(value: &str, start: usize, end: usize, options: &SearchOptions) -> Hit {
+    let before = &value[..start];
+    let skip = before.chars().count().saturating_sub(options.context);
+    Hit {
+        tag: String::new(),
+        occurrence: 0,
+        subfield: None,
+        subfield_index: None,
+        start,
+        end,
+        matched: value[start..end].to_string(),
+        before: before.chars().skip(skip).collect(),
+        after: value[end..].chars().take(options.context).collect(),
+    }
+}
+
+/// Byte ranges of the non-overlapping matches of `needle` in `value`.
+fn find_in(value: &str, needle: &[char], options: &SearchOptions) -> Vec<(usize, usize)> {
+    let folded = fold(value, options);
+    let mut ranges = Vec::new();
+    let mut i = 0;
+    while i + needle.len() <= folded.len() {
+        if folded[i..i + needle.len()]
+            .iter()
+            .map(|(c, _)| c)
+            .eq(needle.iter())
+        {
+            let start = folded[i].1;
+            let last = folded[i + needle.len() - 1].1;
+            let end = last + value[last..].chars().next().map_or(0, char::len_utf8);
+            ranges.push((start, end));
+            i += needle.len();
+        } else {
+            i += 1;
+        }
+    }
+    ranges
+}
+
+/// The folded characters of `value`, each paired with the byte offset of
+/// the original character it came from.
+fn fold(value: &str, options: &SearchOptions) -> Vec<(char, usize)> {
+    let mut out = Vec::with_capacity(value.len());
+    let mut push = |c: char, at: usize| {
+        if options.case_sensitive {
+            out.push((c, at));
+        } else {
+            out.extend(c.to_lowercase().map(|l| (l, at)));
+        }
+    };
+    for (at, c) in value.char_indices() {
+        if !options.fold_diacritics {
+            push(c, at);
+            continue;
+        }
+        for d in c.nfd() {
+            match d {
+                '\u{0300}'..='\u{036F}'
+                | '\u{1AB0}'..='\u{1AFF}'
+                | '\u{1DC0}'..='\u{1DFF}'
+                | '\u{20D0}'..='\u{20FF}'
+                | '\u{FE20}'..='\u{FE2F}' => {},
+                'Æ' => "AE".chars().for_each(|s| push(s, at)),
+                'æ' => "ae".chars().for_each(|s| push(s, at)),
+                'Œ' => "OE".chars().for_each(|s| push(s, at)),
+                'œ' => "oe".chars().for_each(|s| push(s, at)),
+                'Þ' => "TH".chars().for_each(|s| push(s, at)),
+                'þ' => "th".chars().for_each(|s| push(s, at)),
+                'ß' => "ss".chars().for_each(|s| push(s, at)),
+                'Đ' | 'Ð' => push('D', at),
+                'đ' | 'ð' => push('d', at),
+                'Ł' => push('L', at),
+                'ł' => push('l', at),
+                'Ø' => push('O', at),
+                'ø' => push('o', at),
+                'ı' => push('i', at),
+                d => push(d, at),
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::leader::Leader;
+    use crate::record::Field;
+
+    fn record() -> Record {
+        let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+        record.add_control_field_str("001", "gats001");
+        for (tag, value) in [
+            ("100", "Brontë, Charlotte,"),
+            ("245", "Jane Eyre ; Bronte family letters / by BRONTË"),
+            ("500", "Æsop and Łódź; æsop again"),
+        ] {
+            record.add_field(
+                Field::builder(tag.to_string(), '1', ' ')
+                    .subfield_str('a', value)
+                    .build(),
+            );
+        }
+        record
+    }
+
+    #[test]
+    fn test_folded_offsets_point_at_original_text() {
+        let hits = record().search("bronte", &SearchOptions::default());
+        let found: Vec<_> = hits
+            .iter()
+            .map(|h| (h.tag.as_str(), h.matched.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            [("100", "Brontë"), ("245", "Bronte"), ("245", "BRONTË")]
+        );
+        let last = &hits[2];
+        assert_eq!(last.after, "");
+        assert_eq!(
+            &"Jane Eyre ; Bronte family letters / by BRONTË"[last.start..last.end],
+            "BRONTË"
+        );
+
+        let hits = record().search("AESOP", &SearchOptions::default());
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].matched, "Æsop");
+        let lodz = record().search("lodz", &SearchOptions::default());
+        assert_eq!(lodz[0].matched, "Łódź");
+    }
+
+    #[test]
+    fn test_options() {
+        let options = SearchOptions::default().with_case_sensitive(true);
+        assert_eq!(record().search("Bronte", &options).len(), 2);
+
+        let options = SearchOptions::default()
+            .with_fold_diacritics(false)
+            .with_case_sensitive(true);
+        assert_eq!(record().search("Bronte", &options).len(), 1);
+
+        let options = SearchOptions::default().with_tags(["1XX"]).with_context(3);
+        let hits = record().search("charlotte", &options);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(
+            (hits[0].before.as_str(), hits[0].after.as_str()),
+            ("ë, ", ",")
+        );
+        assert_eq!(hits[0].highlight("[", "]"), "ë, [Charlotte],");
+
+        let hits = record().search("GATS", &SearchOptions::default());
+        assert_eq!((hits[0].tag.as_str(), hits[0].subfield), ("001", None));
+        let options = SearchOptions::default().with_control_fields(false);
+        assert!(record().search("gats", &options).is_empty());
+        assert!(record().search("", &SearchOptions::default()).is_empty());
+    }
+}
@@ -0,0 +1,159 @@
+//! Cheaply cloneable, thread-safe handles to immutable records.
+//!
+//! Fan-out pipelines parse a record once and hand it to several consumers
+//! (a MARCXML writer, a JSON writer, an indexer), often on different
+//! threads. Cloning a [`Record`] for each consumer copies every field and
+//! subfield; [`ArcRecord`] wraps the record in an [`Arc`] so each clone is
+//! a reference-count increment instead.
+//!
+//! An `ArcRecord` dereferences to [`Record`], so every read-only method and
+//! every writer that takes `&Record` works unchanged. Code that needs to
+//! modify one consumer's copy calls [`ArcRecord::make_mut`], which copies
+//! the record only if other handles still share it.
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::{ArcRecord, Leader, Record};
+//!
+//! let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+//! record.add_control_field_str("001", "ocm123");
+//!
+//! let shared = ArcRecord::from(record);
+//! let for_thread = shared.clone();
+//! let id = std::thread::spawn(move || for_thread.get_control_field("001").map(str::to_string))
+//!     .join()
+//!     .unwrap();
+//! assert_eq!(id.as_deref(), Some("ocm123"));
+//! assert!(ArcRecord::ptr_eq(&shared, &shared.clone()));
+//! ```
+
+use std::ops::Deref;
+use std::sync::Arc;
+
+use serde::{Serialize, Serializer};
+
+use crate::record::Record;
+
+/// A shared, immutable [`Record`]; cloning does not copy the record.
+#[derive(Debug, Clone)]
+pub struct ArcRecord(Arc<Record>);
+
+impl ArcRecord {
+    /// Wrap `record` for sharing.
+    #[must_use]
+    pub fn new(record: Record) -> Self {
+        ArcRecord(Arc::new(record))
+    }
+
+    /// Mutable access to the record, copying it first if other handles
+    /// share it (copy-on-write).
+    pub fn make_mut(&mut self) -> &mut Record {
+        Arc::make_mut(&mut self.0)
+    }
+
+    /// The owned record: moved out if this is the only handle, cloned
+    /// otherwise.
+    #[must_use]
+    pub fn into_record(self) -> Record {
+        Arc::unwrap_or_clone(self.0)
+    }
+
+    /// Whether two handles share the same record allocation.
+    #[must_use]
+    pub fn ptr_eq(a: &ArcRecord, b: &ArcRecord) -> bool {
+        Arc::ptr_eq(&a.0, &b.0)
+    }
+
+    /// Number of handles sharing this record.
+    #[must_use]
+    pub fn share_count(&self) -> usize {
+        Arc::strong_count(&self.0)
+    }
+}
+
+impl Deref for ArcRecord {
+    type Target = Record;
+
+    fn deref(&self) -> &Record {
+        &self.0
+    }
+}
+
+impl AsRef<Record> for ArcRecord {
+    fn as_ref(&self) -> &Record {
+        &self.0
+    }
+}
+
+impl From<Record> for ArcRecord {
+    fn from(record: Record) -> Self {
+        ArcRecord::new(record)
+    }
+}
+
+impl From<Arc<Record>> for ArcRecord {
+    fn from(record: Arc<Record>) -> Self {
+        ArcRecord(record)
+    }
+}
+
+impl From<ArcRecord> for Arc<Record> {
+    fn from(record: ArcRecord) -> Self {
+        record.0
+    }
+}
+
+impl Serialize for ArcRecord {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::leader::Leader;
+    use crate::record::Field;
+
+    fn record() -> Record {
+        let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+        record.add_field(
+            Field::builder("245".to_string(), '0', '0')
+                .subfield_str('a', "Shared")
+                .build(),
+        );
+        record
+    }
+
+    #[test]
+    fn test_clone_shares_and_make_mut_copies() {
+        let mut first = ArcRecord::from(record());
+        let second = first.clone();
+        assert!(ArcRecord::ptr_eq(&first, &second));
+        assert_eq!(first.share_count(), 2);
+
+        first.make_mut().add_control_field_str("001", "changed");
+        assert!(!ArcRecord::ptr_eq(&first, &second));
+        assert_eq!(first.get_control_field("001"), Some("changed"));
+        assert_eq!(second.get_control_field("001"), None);
+        assert_eq!(
+            second
+                .into_record()
+                .get_field("245")
+                .unwrap()
+                .get_subfield('a'),
+            Some("Shared")
+        );
+    }
+
+    #[test]
+    fn test_serializes_like_record() {
+        let owned = record();
+        let shared = ArcRecord::new(owned.clone());
+        assert_eq!(
+            serde_json::to_value(&shared).unwrap(),
+            serde_json::to_value(&owned).unwrap()
+        );
+    }
+}
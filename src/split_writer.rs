@@ -0,0 +1,391 @@
+//! Writing one record stream to many ISO 2709 outputs.
+//!
+//! [`SplitWriter`] starts a new output whenever the current one would pass
+//! a record-count or byte-size limit, and can route records to separate
+//! outputs by a partition key (record type, holding location, ...). Each
+//! partition has its own sequence of outputs, so limits and partitioning
+//! combine.
+//!
+//! Outputs are usually files named from a template, where `{key}` is
+//! replaced by the partition key and `{n}` by the 1-based sequence number
+//! within the partition:
+//!
+//! ```no_run
+//! use mrrc::split_writer::SplitWriter;
+//! use mrrc::MarcReader;
+//!
+//! # fn main() -> mrrc::Result<()> {
+//! let mut reader = MarcReader::from_path("all.mrc")?;
+//! let mut writer = SplitWriter::to_files("out/{key}-{n}.mrc")
+//!     .max_records(10_000)
+//!     .max_bytes(50 * 1024 * 1024)
+//!     .partition_by(|record| {
+//!         record
+//!             .get_field("949")
+//!             .and_then(|f| f.get_subfield('l'))
+//!             .unwrap_or("none")
+//!             .to_string()
+//!     });
+//! while let Some(record) = reader.read_record()? {
+//!     writer.write_record(&record)?;
+//! }
+//! writer.finish()?;
+//! for output in writer.outputs() {
+//!     println!("{}: {} records", output.name, output.records);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! One output per partition is open at a time, so partitioning by a key
+//! with many distinct values opens many files at once.
+
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+use crate::error::{MarcError, Result};
+use crate::formats::FormatWriter;
+use crate::record::Record;
+use crate::writer::MarcWriter;
+
+type OpenFn<W> = Box<dyn FnMut(&str, usize) -> std::io::Result<(W, String)>>;
+type PartitionFn = Box<dyn FnMut(&Record) -> String>;
+
+/// A completed (or still open) output of a [`SplitWriter`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SplitOutput {
+    /// Name of the output (the file path for [`SplitWriter::to_files`])
+    pub name: String,
+    /// Partition key (empty when not partitioning)
+    pub key: String,
+    /// 1-based sequence number within the partition
+    pub sequence: usize,
+    /// Records written
+    pub records: usize,
+    /// Bytes written
+    pub bytes: u64,
+}
+
+struct Chunk<W> {
+    writer: W,
+    output: usize,
+}
+
+/// Writes records across multiple outputs by count, size or partition.
+pub struct SplitWriter<W: Write> {
+    open: OpenFn<W>,
+    partition: Option<PartitionFn>,
+    max_records: Option<usize>,
+    max_bytes: Option<u64>,
+    open_chunks: HashMap<String, Chunk<W>>,
+    outputs: Vec<SplitOutput>,
+    scratch: Vec<u8>,
+    finished: bool,
+}
+
+impl<W: Write> fmt::Debug for SplitWriter<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SplitWriter")
+            .field("max_records", &self.max_records)
+            .field("max_bytes", &self.max_bytes)
+            .field("partitioned", &self.partition.is_some())
+            .field("outputs", &self.outputs)
+            .field("finished", &self.finished)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SplitWriter<BufWriter<File>> {
+    /// Write to files named by `template`, replacing `{key}` with the
+    /// partition key and `{n}` with the sequence number.
+    ///
+    /// Characters in keys other than ASCII letters, digits, `-`, `_` and
+    /// `.` become `_`, and an empty key becomes `_`. Existing files are
+    /// overwritten.
+    #[must_use]
+    pub fn to_files(template: &str) -> Self {
+        let template = template.to_string();
+        Self::new(move |key, sequence| {
+            let path = file_name(&template, key, sequence);
+            let file = File::create(&path)?;
+            Ok((BufWriter::new(file), path))
+        })
+    }
+}
+
+impl<W: Write> SplitWriter<W> {
+    /// Write to outputs created by `open`, which receives the partition key
+    /// and sequence number and returns the writer and a name for it.
+    pub fn new<F>(open: F) -> Self
+    where
+        F: FnMut(&str, usize) -> std::io::Result<(W, String)> + 'static,
+    {
+        SplitWriter {
+            open: Box::new(open),
+            partition: None,
+            max_records: None,
+            max_bytes: None,
+            open_chunks: HashMap::new(),
+            outputs: Vec::new(),
+            scratch: Vec::new(),
+            finished: false,
+        }
+    }
+
+    /// Start a new output after `n` records.
+    #[must_use]
+    pub fn max_records(mut self, n: usize) -> Self {
+        self.max_records = Some(n.max(1));
+        self
+    }
+
+    /// Start a new output before a record would take the current one past
+    /// `bytes`. A record larger than the limit is written to an output of
+    /// its own.
+    #[must_use]
+    pub fn max_bytes(mut self, bytes: u64) -> Self {
+        self.max_bytes = Some(bytes);
+        self
+    }
+
+    /// Route each record to the outputs for the key `partition` returns.
+    #[must_use]
+    pub fn partition_by<F>(mut self, partition: F) -> Self
+    where
+        F: FnMut(&Record) -> String + 'static,
+    {
+        self.partition = Some(Box::new(partition));
+        self
+    }
+
+    /// Write one record.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the record cannot be serialized, an output
+    /// cannot be opened or written, two outputs get the same name, or the
+    /// writer is finished.
+    pub fn write_record(&mut self, record: &Record) -> Result<()> {
+        if self.finished {
+            return Err(MarcError::WriterError {
+                record_index: None,
+                record_control_number: None,
+                field_tag: None,
+                message: "Cannot write to a finished writer".to_string(),
+            });
+        }
+        self.scratch.clear();
+        MarcWriter::new(&mut self.scratch).write_record(record)?;
+        let length = self.scratch.len() as u64;
+
+        let key = self
+            .partition
+            .as_mut()
+            .map(|p| p(record))
+            .unwrap_or_default();
+        let rollover = self.open_chunks.get(&key).is_some_and(|chunk| {
+            let output = &self.outputs[chunk.output];
+            output.records > 0
+                && (self.max_records.is_some_and(|max| output.records >= max)
+                    || self
+                        .max_bytes
+                        .is_some_and(|max| output.bytes + length > max))
+        });
+        if rollover && let Some(mut chunk) = self.open_chunks.remove(&key) {
+            chunk.writer.flush()?;
+        }
+
+        let chunk = match self.open_chunks.entry(key) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let key = entry.key();
+                let sequence = self.outputs.iter().filter(|o| &o.key == key).count() + 1;
+                let (writer, name) = (self.open)(key, sequence)?;
+                if self.outputs.iter().any(|o| o.name == name) {
+                    return Err(MarcError::WriterError {
+                        record_index: None,
+                        record_control_number: None,
+                        field_tag: None,
+                        message: format!(
+                            "Split output '{name}' would be written twice; include {{key}} and {{n}} in the name"
+                        ),
+                    });
+                }
+                self.outputs.push(SplitOutput {
+                    name,
+                    key: key.clone(),
+                    sequence,
+                    records: 0,
+                    bytes: 0,
+                });
+                entry.insert(Chunk {
+                    writer,
+                    output: self.outputs.len() - 1,
+                })
+            },
+        };
+        chunk.writer.write_all(&self.scratch)?;
+        let output = &mut self.outputs[chunk.output];
+        output.records += 1;
+        output.bytes += length;
+        Ok(())
+    }
+
+    /// Flush and close every open output.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if flushing fails.
+    pub fn finish(&mut self) -> Result<()> {
+        for (_, mut chunk) in self.open_chunks.drain() {
+            chunk.writer.flush()?;
+        }
+        self.finished = true;
+        Ok(())
+    }
+
+    /// The outputs created so far, in the order they were opened.
+    #[must_use]
+    pub fn outputs(&self) -> &[SplitOutput] {
+        &self.outputs
+    }
+
+    /// Total records written across all outputs.
+    #[must_use]
+    pub fn records_written(&self) -> usize {
+        self.outputs.iter().map(|o| o.records).sum()
+    }
+}
+
+impl<W: Write> FormatWriter for SplitWriter<W> {
+    fn write_record(&mut self, record: &Record) -> Result<()> {
+        SplitWriter::write_record(self, record)
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        SplitWriter::finish(self)
+    }
+
+    fn records_written(&self) -> Option<usize> {
+        Some(SplitWriter::records_written(self))
+    }
+}
+
+/// Expand a file-name template for one output.
+fn file_name(template: &str, key: &str, sequence: usize) -> String {
+    let mut safe: String = key
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if safe.is_empty() {
+        safe.push('_');
+    }
+    template
+        .replace("{key}", &safe)
+        .replace("{n}", &sequence.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::leader::Leader;
+    use crate::reader::MarcReader;
+    use crate::record::Field;
+
+    fn record(id: usize, location: &str) -> Record {
+        let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+        record.add_control_field_str("001", &id.to_string());
+        record.add_field(
+            Field::builder("949".to_string(), ' ', ' ')
+                .subfield_str('l', location)
+                .build(),
+        );
+        record
+    }
+
+    fn read_ids(path: &str) -> Vec<String> {
+        let mut reader = MarcReader::from_path(path).unwrap();
+        let mut ids = Vec::new();
+        while let Some(record) = reader.read_record().unwrap() {
+            ids.push(record.get_control_field("001").unwrap().to_string());
+        }
+        ids
+    }
+
+    #[test]
+    fn test_split_by_count_and_partition() {
+        let dir = tempfile::tempdir().unwrap();
+        let template = format!("{}/{{key}}-{{n}}.mrc", dir.path().display());
+        let mut writer = SplitWriter::to_files(&template)
+            .max_records(2)
+            .partition_by(|r| {
+                r.get_field("949")
+                    .and_then(|f| f.get_subfield('l'))
+                    .unwrap_or_default()
+                    .to_string()
+            });
+        for (id, location) in [
+            (1, "main"),
+            (2, "law"),
+            (3, "main"),
+            (4, "main"),
+            (5, "a/b"),
+        ] {
+            writer.write_record(&record(id, location)).unwrap();
+        }
+        writer.finish().unwrap();
+
+        let summary: Vec<(&str, usize, usize)> = writer
+            .outputs()
+            .iter()
+            .map(|o| (o.key.as_str(), o.sequence, o.records))
+            .collect();
+        assert_eq!(
+            summary,
+            [("main", 1, 2), ("law", 1, 1), ("main", 2, 1), ("a/b", 1, 1)]
+        );
+        assert_eq!(read_ids(&writer.outputs()[0].name), ["1", "3"]);
+        assert_eq!(read_ids(&writer.outputs()[2].name), ["4"]);
+        assert!(writer.outputs()[3].name.ends_with("a_b-1.mrc"));
+        assert_eq!(writer.records_written(), 5);
+        assert!(writer.write_record(&record(6, "x")).is_err());
+    }
+
+    #[test]
+    fn test_split_by_bytes() {
+        let dir = tempfile::tempdir().unwrap();
+        let template = format!("{}/part-{{n}}.mrc", dir.path().display());
+        let size = {
+            let mut buffer = Vec::new();
+            MarcWriter::new(&mut buffer)
+                .write_record(&record(1, "main"))
+                .unwrap();
+            buffer.len() as u64
+        };
+        let mut writer = SplitWriter::to_files(&template).max_bytes(size * 3 - 1);
+        for id in 1..=5 {
+            writer.write_record(&record(id, "main")).unwrap();
+        }
+        writer.finish().unwrap();
+        let counts: Vec<usize> = writer.outputs().iter().map(|o| o.records).collect();
+        assert_eq!(counts, [2, 2, 1]);
+        assert!(writer.outputs().iter().all(|o| o.bytes < size * 3));
+    }
+
+    #[test]
+    fn test_duplicate_names_are_rejected() {
+        let mut writer =
+            SplitWriter::new(|_, _| Ok((Vec::new(), "same".to_string()))).max_records(1);
+        writer.write_record(&record(1, "main")).unwrap();
+        assert!(writer.write_record(&record(2, "main")).is_err());
+    }
+}
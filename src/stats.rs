@@ -0,0 +1,220 @@
+//! Throughput counters shared between readers, writers and pipelines.
+//!
+//! A [`Stats`] handle counts records, bytes and errors as a stage runs and
+//! produces [`StatsSnapshot`]s for dashboards and progress displays. It is
+//! cheap to clone and safe to update from several threads, so one handle
+//! can be passed to a reader, kept by the caller, and read from a
+//! monitoring thread while the stage runs.
+//!
+//! [`MarcReader::with_stats`](crate::MarcReader::with_stats),
+//! [`MarcWriter::with_stats`](crate::MarcWriter::with_stats),
+//! [`Batch::with_stats`](crate::batch::Batch::with_stats) and
+//! [`ProducerConsumerPipeline::stats`](crate::producer_consumer_pipeline::ProducerConsumerPipeline::stats)
+//! update a handle automatically; [`Stats::add_record`] and
+//! [`Stats::add_error`] instrument any other stage.
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::{Field, Leader, MarcReader, MarcWriter, Record, Stats};
+//!
+//! let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+//! record.add_field(Field::builder("245".to_string(), '0', '0').subfield_str('a', "T").build());
+//!
+//! let written = Stats::new();
+//! let mut bytes = Vec::new();
+//! MarcWriter::new(&mut bytes)
+//!     .with_stats(written.clone())
+//!     .write_record(&record)?;
+//!
+//! let read = Stats::new();
+//! let mut reader = MarcReader::new(bytes.as_slice()).with_stats(read.clone());
+//! while reader.read_record()?.is_some() {}
+//!
+//! let snapshot = read.snapshot();
+//! assert_eq!(snapshot.records, 1);
+//! assert_eq!(snapshot.bytes, written.snapshot().bytes);
+//! # Ok::<(), mrrc::MarcError>(())
+//! ```
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// A shared, thread-safe set of throughput counters; clones update the
+/// same counters.
+#[derive(Debug, Clone)]
+pub struct Stats(Arc<Counters>);
+
+#[derive(Debug)]
+struct Counters {
+    records: AtomicU64,
+    bytes: AtomicU64,
+    errors: AtomicU64,
+    started: Instant,
+    /// Time and record count of the previous snapshot, for the current rate.
+    last: Mutex<(Instant, u64)>,
+}
+
+/// The counters of a [`Stats`] at one moment.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StatsSnapshot {
+    /// Records processed
+    pub records: u64,
+    /// Bytes processed (serialized record lengths)
+    pub bytes: u64,
+    /// Records that failed
+    pub errors: u64,
+    /// Time since the handle was created
+    pub elapsed: Duration,
+    /// Records per second over the whole run
+    pub rate: f64,
+    /// Records per second since the previous snapshot (over the whole run
+    /// for the first one)
+    pub current_rate: f64,
+}
+
+impl StatsSnapshot {
+    /// Mean time per record, or `None` before the first record.
+    #[must_use]
+    pub fn per_record(&self) -> Option<Duration> {
+        let records = u32::try_from(self.records).unwrap_or(u32::MAX);
+        (records > 0).then(|| self.elapsed / records)
+    }
+}
+
+impl Default for Stats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Stats {
+    /// Create a handle with zeroed counters; the clock starts now.
+    #[must_use]
+    pub fn new() -> Self {
+        let started = Instant::now();
+        Stats(Arc::new(Counters {
+            records: AtomicU64::new(0),
+            bytes: AtomicU64::new(0),
+            errors: AtomicU64::new(0),
+            started,
+            last: Mutex::new((started, 0)),
+        }))
+    }
+
+    /// Count one record of `bytes` bytes.
+    pub fn add_record(&self, bytes: u64) {
+        self.0.records.fetch_add(1, Ordering::Relaxed);
+        self.0.bytes.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// Count one failed record.
+    pub fn add_error(&self) {
+        self.0.errors.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records counted so far.
+    #[must_use]
+    pub fn records(&self) -> u64 {
+        self.0.records.load(Ordering::Relaxed)
+    }
+
+    /// Bytes counted so far.
+    #[must_use]
+    pub fn bytes(&self) -> u64 {
+        self.0.bytes.load(Ordering::Relaxed)
+    }
+
+    /// Failed records counted so far.
+    #[must_use]
+    pub fn errors(&self) -> u64 {
+        self.0.errors.load(Ordering::Relaxed)
+    }
+
+    /// Time since the handle was created.
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        self.0.started.elapsed()
+    }
+
+    /// Read the counters and compute rates. Each call starts a new
+    /// interval for [`StatsSnapshot::current_rate`].
+    #[must_use]
+    pub fn snapshot(&self) -> StatsSnapshot {
+        let now = Instant::now();
+        let records = self.records();
+        let elapsed = now.duration_since(self.0.started);
+        let (since, before) = {
+            let mut last = self.0.last.lock().unwrap_or_else(PoisonError::into_inner);
+            std::mem::replace(&mut *last, (now, records))
+        };
+        StatsSnapshot {
+            records,
+            bytes: self.bytes(),
+            errors: self.errors(),
+            elapsed,
+            rate: per_second(records, elapsed),
+            current_rate: per_second(records.saturating_sub(before), now.duration_since(since)),
+        }
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn per_second(records: u64, elapsed: Duration) -> f64 {
+    let seconds = elapsed.as_secs_f64();
+    if seconds > 0.0 {
+        records as f64 / seconds
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts_across_clones_and_threads() {
+        let stats = Stats::new();
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let stats = stats.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..10 {
+                        stats.add_record(100);
+                    }
+                    stats.add_error();
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.records, 40);
+        assert_eq!(snapshot.bytes, 4000);
+        assert_eq!(snapshot.errors, 4);
+        assert!(snapshot.per_record().is_some());
+    }
+
+    #[test]
+    fn test_current_rate_covers_interval_since_last_snapshot() {
+        let stats = Stats::new();
+        stats.add_record(1);
+        let first = stats.snapshot();
+        assert_eq!(first.records, 1);
+        std::thread::sleep(Duration::from_millis(5));
+        let second = stats.snapshot();
+        assert!(second.current_rate.abs() < f64::EPSILON);
+        assert!(second.rate > 0.0);
+        assert_eq!(
+            StatsSnapshot {
+                records: 0,
+                ..second
+            }
+            .per_record(),
+            None
+        );
+    }
+}
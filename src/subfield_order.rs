@@ -0,0 +1,280 @@
+//! Subfield-order canonicalization following MARC 21 conventions.
+//!
+//! Records from some vendors arrive with subfields in arbitrary order
+//! (`100 $d 1835-1910 $a Twain, Mark`), which breaks display and
+//! heading matching downstream. A [`SubfieldOrder`] lists the conventional
+//! order of subfield codes for a tag as a sequence of *groups*; reordering
+//! is a stable sort by group, so subfields within one group (such as the
+//! `$v $x $y $z` subdivisions of a subject heading, or the repeating
+//! `$n $p` part pairs of a title) keep their original relative order.
+//!
+//! Codes a spec does not mention stay attached to the subfield that
+//! precedes them, except `$6` and `$8`, which move to the front.
+//!
+//! Built-in specs cover the common access-point, title, edition,
+//! publication, physical description and standard-number fields; see
+//! [`SubfieldOrder::for_tag`].
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::Field;
+//!
+//! let mut field = Field::builder("100".to_string(), '1', ' ')
+//!     .subfield_str('e', "author.")
+//!     .subfield_str('d', "1835-1910,")
+//!     .subfield_str('a', "Twain, Mark,")
+//!     .build();
+//! assert!(field.canonicalize_subfield_order());
+//! let codes: String = field.subfields.iter().map(|sf| sf.code).collect();
+//! assert_eq!(codes, "ade");
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::{MarcError, Result};
+use crate::record::Field;
+
+/// Built-in order for personal name access points (X00).
+const PERSONAL_NAME: &[&str] = &[
+    "a",
+    "b",
+    "c",
+    "q",
+    "d",
+    "g",
+    "j",
+    "e",
+    "tfhklmnoprs",
+    "vxyz",
+    "4",
+    "2",
+    "0",
+    "1",
+];
+/// Built-in order for corporate name access points (X10). `$n` is left
+/// out because it belongs to the name before `$t` and to the title after.
+const CORPORATE_NAME: &[&str] = &[
+    "a",
+    "b",
+    "cdg",
+    "e",
+    "tfhklmoprs",
+    "vxyz",
+    "4",
+    "2",
+    "0",
+    "1",
+];
+/// Built-in order for meeting name access points (X11).
+const MEETING_NAME: &[&str] = &[
+    "a",
+    "q",
+    "e",
+    "cdg",
+    "j",
+    "tfhklmoprs",
+    "vxyz",
+    "4",
+    "2",
+    "0",
+    "1",
+];
+/// Built-in order for uniform title access points (X30).
+const UNIFORM_TITLE: &[&str] = &["a", "dfghklmnoprst", "vxyz", "2", "0", "1"];
+
+/// An ordered list of subfield-code groups for one tag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubfieldOrder {
+    groups: Vec<String>,
+}
+
+impl SubfieldOrder {
+    /// Create an order from groups of subfield codes, e.g.
+    /// `["a", "np", "b", "c"]`.
+    #[must_use]
+    pub fn new<I, S>(groups: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        SubfieldOrder {
+            groups: groups.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// The built-in order for `tag`, if there is one.
+    #[must_use]
+    pub fn for_tag(tag: &str) -> Option<Self> {
+        let groups: &[&str] = match tag {
+            "020" => &["a", "q", "c", "z"],
+            "022" => &["a", "l", "z", "y", "m"],
+            "100" | "600" | "700" | "800" => PERSONAL_NAME,
+            "110" | "610" | "710" | "810" => CORPORATE_NAME,
+            "111" | "611" | "711" | "811" => MEETING_NAME,
+            "130" | "630" | "730" | "830" => UNIFORM_TITLE,
+            "240" => &["a", "dfghklmnoprs", "0", "1"],
+            "245" => &["a", "fgk", "np", "h", "b", "c", "s"],
+            "250" => &["a", "b"],
+            "260" | "264" => &["ab", "c", "efg"],
+            "300" => &["a", "b", "c", "e", "f", "g"],
+            "650" => &["a", "b", "c", "d", "e", "vxyz", "4", "2", "0", "1"],
+            "651" => &["a", "g", "e", "vxyz", "4", "2", "0", "1"],
+            "655" => &["a", "b", "c", "vxyz", "5", "2", "0", "1"],
+            _ => return None,
+        };
+        Some(Self::new(groups.iter().copied()))
+    }
+
+    /// The code groups, in order.
+    #[must_use]
+    pub fn groups(&self) -> &[String] {
+        &self.groups
+    }
+
+    fn rank(&self, code: char) -> Option<usize> {
+        self.groups.iter().position(|group| group.contains(code))
+    }
+
+    /// Sort key of each subfield: its group's rank plus one, the
+    /// predecessor's key for unlisted codes, and 0 for `$6` and `$8`.
+    fn keys(&self, field: &Field) -> Vec<usize> {
+        let mut current = 0;
+        field
+            .subfields
+            .iter()
+            .map(|sf| {
+                if let Some(rank) = self.rank(sf.code) {
+                    current = rank + 1;
+                    current
+                } else if matches!(sf.code, '6' | '8') {
+                    0
+                } else {
+                    current
+                }
+            })
+            .collect()
+    }
+
+    /// Index at which a new `code` subfield belongs in `field`: after every
+    /// subfield of the same or an earlier group. `None` if the order does
+    /// not list `code`.
+    pub(crate) fn insert_position(&self, field: &Field, code: char) -> Option<usize> {
+        let key = self.rank(code)? + 1;
+        let keys = self.keys(field);
+        Some(keys.iter().rposition(|&k| k <= key).map_or(0, |i| i + 1))
+    }
+
+    /// Reorder `field`'s subfields. Returns `true` if the order changed.
+    pub fn apply(&self, field: &mut Field) -> bool {
+        let keys = self.keys(field);
+        if keys.is_sorted() {
+            return false;
+        }
+
+        let mut keyed: Vec<_> = keys.into_iter().zip(field.subfields.drain(..)).collect();
+        keyed.sort_by_key(|(key, _)| *key);
+        field.subfields.extend(keyed.into_iter().map(|(_, sf)| sf));
+        true
+    }
+}
+
+impl FromStr for SubfieldOrder {
+    type Err = MarcError;
+
+    /// Parse whitespace-separated groups, e.g. `"a np b c"`.
+    fn from_str(s: &str) -> Result<Self> {
+        let groups: Vec<&str> = s.split_whitespace().collect();
+        if let Some(bad) = groups
+            .iter()
+            .flat_map(|g| g.chars())
+            .find(|c| !c.is_ascii_alphanumeric())
+        {
+            return Err(MarcError::invalid_field_msg(format!(
+                "Invalid subfield code '{bad}' in subfield order '{s}'"
+            )));
+        }
+        Ok(Self::new(groups))
+    }
+}
+
+impl fmt::Display for SubfieldOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.groups.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(tag: &str, subfields: &[(char, &str)]) -> Field {
+        let mut field = Field::new(tag.to_string(), ' ', ' ');
+        for (code, value) in subfields {
+            field.add_subfield_str(*code, value);
+        }
+        field
+    }
+
+    fn codes(field: &Field) -> String {
+        field.subfields.iter().map(|sf| sf.code).collect()
+    }
+
+    #[test]
+    fn test_title_keeps_part_pairs_together() {
+        let mut f = field(
+            "245",
+            &[
+                ('c', "by X."),
+                ('a', "Works."),
+                ('n', "Part 1,"),
+                ('p', "Poems."),
+                ('n', "Part 2,"),
+                ('p', "Plays /"),
+                ('6', "880-01"),
+            ],
+        );
+        assert!(f.canonicalize_subfield_order());
+        assert_eq!(codes(&f), "6anpnpc");
+        assert_eq!(f.subfields[2].value, "Part 1,");
+        assert!(!f.canonicalize_subfield_order());
+    }
+
+    #[test]
+    fn test_subject_subdivisions_keep_order() {
+        let mut f = field(
+            "650",
+            &[
+                ('0', "http://id.loc.gov/x"),
+                ('a', "Art"),
+                ('z', "France"),
+                ('x', "History"),
+                ('2', "lcsh"),
+            ],
+        );
+        assert!(f.canonicalize_subfield_order());
+        assert_eq!(codes(&f), "azx20");
+    }
+
+    #[test]
+    fn test_unknown_codes_stick_to_predecessor() {
+        let order: SubfieldOrder = "a b c".parse().unwrap();
+        let mut f = field("999", &[('c', "3"), ('x', "after c"), ('a', "1")]);
+        assert!(order.apply(&mut f));
+        assert_eq!(codes(&f), "acx");
+        assert_eq!(order.to_string(), "a b c");
+        assert!("a $b".parse::<SubfieldOrder>().is_err());
+    }
+
+    #[test]
+    fn test_record_canonicalize() {
+        let mut record =
+            crate::Record::new(crate::Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+        record.add_field(field("100", &[('d', "1900-"), ('a', "Smith, J.,")]));
+        record.add_field(field("500", &[('a', "Note")]));
+        record.add_field(field("300", &[('a', "1 v.")]));
+        assert_eq!(record.canonicalize(), 1);
+        assert_eq!(codes(record.get_field("100").unwrap()), "ad");
+    }
+}
@@ -0,0 +1,567 @@
+//! Subject headings as composite strings and back.
+//!
+//! Facets, heading reports and authority matching work on strings such as
+//! `United States--History--Civil War, 1861-1865`, while records code the
+//! same heading as subfields (`651 _0 $a United States $x History $y Civil
+//! War, 1861-1865.`). [`heading_string`] turns a 6XX field into the string
+//! and [`heading_to_field`] goes the other way.
+//!
+//! A string does not say which kind of subdivision each part is, so
+//! [`SubdivisionList`] assigns the codes: terms it has been given
+//! explicitly come first, then these heuristics:
+//!
+//! | Code | Chosen when the subdivision ...                                    |
+//! |------|--------------------------------------------------------------------|
+//! | `$v` | is a common form subdivision (`Periodicals`, `Juvenile fiction`, ...) |
+//! | `$y` | contains a digit or names a century (`To 1500`, `20th century`)    |
+//! | `$z` | is a country or state name from the MARC country list, or follows a `$z` and is capitalized like a place name (`France--Paris`) |
+//! | `$x` | otherwise                                                          |
+//!
+//! [`subject_headings`] (also [`Record::subject_headings`]) goes the
+//! other way for whole records, breaking each 6XX into a typed
+//! [`SubjectHeading`]: its kind, main heading, coded subdivisions and
+//! vocabulary, for facets and heading reports.
+//!
+//! The main heading of a 600 or 610 is split into name subfields with
+//! [`PersonalName::parse`] and [`CorporateName::parse`]; for other tags it
+//! becomes `$a`.
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::subjects::{heading_string, heading_to_field, SubdivisionList};
+//!
+//! let subdivisions = SubdivisionList::default();
+//! let field = heading_to_field(
+//!     "651",
+//!     '0',
+//!     "United States--History--Civil War, 1861-1865--Maps",
+//!     &subdivisions,
+//! );
+//! let codes: Vec<char> = field.subfields.iter().map(|sf| sf.code).collect();
+//! assert_eq!(codes, ['a', 'x', 'y', 'v']);
+//! assert_eq!(field.get_subfield('v'), Some("Maps."));
+//!
+//! assert_eq!(
+//!     heading_string(&field).as_deref(),
+//!     Some("United States--History--Civil War, 1861-1865--Maps")
+//! );
+//! ```
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::authority_flip::heading_subfields;
+use crate::authority_record::SubdivisionType;
+use crate::codes::CodeList;
+use crate::names::{CorporateName, PersonalName};
+use crate::punctuation::{add_field_punctuation, strip_value};
+use crate::record::{Field, Record};
+
+/// Subject heading second indicator values that name their vocabulary,
+/// with its source code. Indicator 0 on an unsubdivided name or title
+/// heading means LC/NAF rather than LCSH.
+pub(crate) const SUBJECT_INDICATOR_SOURCES: [(char, &str); 6] = [
+    ('0', "lcsh"),
+    ('1', "lcshac"),
+    ('2', "mesh"),
+    ('3', "nal"),
+    ('5', "cash"),
+    ('6', "rvm"),
+];
+
+/// Form subdivisions recognized without being listed.
+const FORM_SUBDIVISIONS: &[&str] = &[
+    "abstracts",
+    "atlases",
+    "bibliography",
+    "biography",
+    "case studies",
+    "catalogs",
+    "comic books, strips, etc",
+    "congresses",
+    "correspondence",
+    "databases",
+    "diaries",
+    "dictionaries",
+    "directories",
+    "drama",
+    "early works to 1800",
+    "encyclopedias",
+    "exhibitions",
+    "fiction",
+    "guidebooks",
+    "handbooks, manuals, etc",
+    "indexes",
+    "interviews",
+    "juvenile fiction",
+    "juvenile literature",
+    "maps",
+    "periodicals",
+    "personal narratives",
+    "pictorial works",
+    "poetry",
+    "problems, exercises, etc",
+    "sources",
+    "statistics",
+    "study guides",
+    "textbooks",
+];
+
+fn is_subdivision(code: char) -> bool {
+    matches!(code, 'v' | 'x' | 'y' | 'z')
+}
+
+/// The heading of a 6XX (or other heading) field as a composite string.
+///
+/// Main heading subfields are joined with spaces and subdivisions with
+/// `--`. ISBD punctuation is dropped before each subdivision and at the
+/// end; control subfields (`$0`, `$2`, ...) and relators are left out.
+/// Returns `None` if the field has no heading subfields.
+#[must_use]
+pub fn heading_string(field: &Field) -> Option<String> {
+    let subfields = heading_subfields(field);
+    let mut text = String::new();
+    for (i, subfield) in subfields.iter().enumerate() {
+        let value = match subfields.get(i + 1) {
+            Some(next) if !is_subdivision(next.code) => subfield.value.trim(),
+            _ => strip_value(subfield.value.trim()),
+        };
+        if value.is_empty() {
+            continue;
+        }
+        if !text.is_empty() {
+            text.push_str(if is_subdivision(subfield.code) {
+                "--"
+            } else {
+                " "
+            });
+        }
+        text.push_str(value);
+    }
+    (!text.is_empty()).then_some(text)
+}
+
+/// Build a `tag` field from a composite heading string.
+///
+/// The string is split at `--` (with or without surrounding blanks), the
+/// main heading is coded by tag, each subdivision gets the code chosen by
+/// `subdivisions`, and ISBD punctuation is added.
+#[must_use]
+pub fn heading_to_field(
+    tag: &str,
+    ind2: char,
+    heading: &str,
+    subdivisions: &SubdivisionList,
+) -> Field {
+    let mut parts = heading
+        .split("--")
+        .map(|part| strip_value(part.trim()))
+        .filter(|part| !part.is_empty());
+    let mut field = Field::new(tag.to_string(), ' ', ind2);
+    let Some(main) = parts.next() else {
+        return field;
+    };
+
+    match tag {
+        "600" | "100" | "700" | "800" => {
+            let name = PersonalName::parse(main);
+            field.indicator1 = name.kind.indicator();
+            let entry = match (&name.surname, &name.forename) {
+                (Some(surname), Some(forename)) => format!("{surname}, {forename}"),
+                (Some(entry), None) | (None, Some(entry)) => entry.clone(),
+                (None, None) => main.to_string(),
+            };
+            field.add_subfield('a', entry);
+            for (code, value) in [('b', &name.numeration), ('q', &name.fuller_form)] {
+                if let Some(value) = value {
+                    let value = if code == 'q' {
+                        format!("({value})")
+                    } else {
+                        value.clone()
+                    };
+                    field.add_subfield(code, value);
+                }
+            }
+            for title in &name.titles {
+                field.add_subfield('c', title.clone());
+            }
+            if let Some(dates) = &name.dates {
+                field.add_subfield('d', dates.clone());
+            }
+        },
+        "610" | "110" | "710" | "810" => {
+            let name = CorporateName::parse(main);
+            field.indicator1 = if name.units.is_empty() { '2' } else { '1' };
+            field.add_subfield('a', name.name);
+            for unit in name.units {
+                field.add_subfield('b', unit);
+            }
+        },
+        _ => field.add_subfield('a', main.to_string()),
+    }
+
+    let mut previous = None;
+    for part in parts {
+        let code = subdivisions.code_for(part, previous);
+        field.add_subfield(code, part.to_string());
+        previous = Some(code);
+    }
+    add_field_punctuation(&mut field);
+    field
+}
+
+/// What a subject heading names, from its tag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SubjectKind {
+    /// 600 (and local 696) - Personal name
+    PersonalName,
+    /// 610 (and local 697) - Corporate name
+    CorporateName,
+    /// 611 (and local 698) - Meeting name
+    MeetingName,
+    /// 630 (and local 699) - Uniform title
+    UniformTitle,
+    /// 647 - Named event
+    NamedEvent,
+    /// 648 - Chronological term
+    Chronological,
+    /// 650 (and local 690) - Topical term
+    Topical,
+    /// 651 (and local 691) - Geographic name
+    Geographic,
+    /// 653 - Uncontrolled index term
+    Uncontrolled,
+    /// 654 - Faceted topical terms
+    FacetedTopical,
+    /// 655 - Genre/form
+    GenreForm,
+    /// 656 - Occupation
+    Occupation,
+    /// 657 - Function
+    Function,
+    /// 658 - Curriculum objective
+    CurriculumObjective,
+    /// 662 - Hierarchical place name
+    HierarchicalPlace,
+}
+
+impl SubjectKind {
+    /// The kind of a 6XX tag; `None` for tags that are not subject
+    /// headings.
+    #[must_use]
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        match tag {
+            "600" | "696" => Some(SubjectKind::PersonalName),
+            "610" | "697" => Some(SubjectKind::CorporateName),
+            "611" | "698" => Some(SubjectKind::MeetingName),
+            "630" | "699" => Some(SubjectKind::UniformTitle),
+            "647" => Some(SubjectKind::NamedEvent),
+            "648" => Some(SubjectKind::Chronological),
+            "650" | "690" => Some(SubjectKind::Topical),
+            "651" | "691" => Some(SubjectKind::Geographic),
+            "653" => Some(SubjectKind::Uncontrolled),
+            "654" => Some(SubjectKind::FacetedTopical),
+            "655" => Some(SubjectKind::GenreForm),
+            "656" => Some(SubjectKind::Occupation),
+            "657" => Some(SubjectKind::Function),
+            "658" => Some(SubjectKind::CurriculumObjective),
+            "662" => Some(SubjectKind::HierarchicalPlace),
+            _ => None,
+        }
+    }
+}
+
+/// A 6XX heading broken into its parts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubjectHeading {
+    /// Field tag
+    pub tag: String,
+    /// What the heading names
+    pub kind: SubjectKind,
+    /// Main heading, its subfields joined with spaces and without
+    /// trailing ISBD punctuation
+    pub main: String,
+    /// Subdivisions in field order: `$x` topical, `$v` form, `$y`
+    /// chronological, `$z` geographic
+    pub subdivisions: Vec<(SubdivisionType, String)>,
+    /// Vocabulary source code, from [`heading_source`]
+    pub source: Option<String>,
+}
+
+impl SubjectHeading {
+    /// Parse a subject field; `None` for a tag that is not a subject
+    /// heading or a field without a main heading.
+    #[must_use]
+    pub fn from_field(field: &Field) -> Option<Self> {
+        let kind = SubjectKind::from_tag(&field.tag)?;
+        let subfields = heading_subfields(field);
+        let split = subfields
+            .iter()
+            .position(|s| is_subdivision(s.code))
+            .unwrap_or(subfields.len());
+        let main = heading_string(&Field {
+            subfields: subfields[..split].iter().map(|&s| s.clone()).collect(),
+            ..Field::new(field.tag.clone(), ' ', ' ')
+        })?;
+        let subdivisions = subfields[split..]
+            .iter()
+            .filter_map(|s| {
+                let kind = match s.code {
+                    'x' => SubdivisionType::Topical,
+                    'v' => SubdivisionType::Form,
+                    'y' => SubdivisionType::Chronological,
+                    'z' => SubdivisionType::Geographic,
+                    _ => return None,
+                };
+                let value = strip_value(s.value.trim());
+                (!value.is_empty()).then(|| (kind, value.to_string()))
+            })
+            .collect();
+        Some(SubjectHeading {
+            tag: field.tag.clone(),
+            kind,
+            main,
+            subdivisions,
+            source: heading_source(field),
+        })
+    }
+
+    /// The heading as a composite `main--subdivision--...` string.
+    #[must_use]
+    pub fn to_heading_string(&self) -> String {
+        std::iter::once(self.main.as_str())
+            .chain(self.subdivisions.iter().map(|(_, value)| value.as_str()))
+            .collect::<Vec<_>>()
+            .join("--")
+    }
+}
+
+/// The subject headings of `record` in field order.
+#[must_use]
+pub fn subject_headings(record: &Record) -> Vec<SubjectHeading> {
+    record
+        .fields()
+        .filter_map(SubjectHeading::from_field)
+        .collect()
+}
+
+/// Source code of the vocabulary a 6XX heading comes from: `$2` when the
+/// second indicator is 7, else the code the indicator stands for (`naf`
+/// under indicator 0 for a name or title heading without subdivisions).
+/// Codes are lower-cased without a trailing period; `None` when the
+/// indicator names no vocabulary (4, blank) or `$2` is missing.
+#[must_use]
+pub fn heading_source(field: &Field) -> Option<String> {
+    if field.indicator2 == '7' {
+        let code = field.get_subfield('2')?.trim().trim_end_matches('.');
+        return (!code.is_empty()).then(|| code.to_ascii_lowercase());
+    }
+    let is_name = matches!(field.tag.as_str(), "600" | "610" | "611" | "630")
+        && !field.subfields.iter().any(|s| is_subdivision(s.code));
+    match field.indicator2 {
+        '0' if is_name => Some("naf".to_string()),
+        ind => SUBJECT_INDICATOR_SOURCES
+            .iter()
+            .find(|(i, _)| *i == ind)
+            .map(|(_, code)| (*code).to_string()),
+    }
+}
+
+/// Known subdivision terms and the heuristics for the rest.
+///
+/// Terms are matched ignoring case and trailing punctuation.
+#[derive(Debug, Clone, Default)]
+pub struct SubdivisionList {
+    terms: HashMap<String, char>,
+}
+
+impl SubdivisionList {
+    /// A list with only the built-in heuristics; the same as `default()`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Code `term` as a form subdivision (`$v`).
+    #[must_use]
+    pub fn form(self, term: &str) -> Self {
+        self.with(term, 'v')
+    }
+
+    /// Code `term` as a topical subdivision (`$x`).
+    #[must_use]
+    pub fn topical(self, term: &str) -> Self {
+        self.with(term, 'x')
+    }
+
+    /// Code `term` as a chronological subdivision (`$y`).
+    #[must_use]
+    pub fn chronological(self, term: &str) -> Self {
+        self.with(term, 'y')
+    }
+
+    /// Code `term` as a geographic subdivision (`$z`).
+    #[must_use]
+    pub fn geographic(self, term: &str) -> Self {
+        self.with(term, 'z')
+    }
+
+    fn with(mut self, term: &str, code: char) -> Self {
+        self.terms.insert(key(term), code);
+        self
+    }
+
+    /// The subfield code for subdivision `term`, given the code of the
+    /// subdivision before it.
+    #[must_use]
+    pub fn code_for(&self, term: &str, previous: Option<char>) -> char {
+        let key = key(term);
+        if let Some(code) = self.terms.get(&key) {
+            return *code;
+        }
+        if FORM_SUBDIVISIONS.contains(&key.as_str()) {
+            'v'
+        } else if term.contains(|c: char| c.is_ascii_digit()) || key.ends_with(" century") {
+            'y'
+        } else if CodeList::Country.find_by_name(term).is_some()
+            || (previous == Some('z') && looks_like_place(term))
+        {
+            'z'
+        } else {
+            'x'
+        }
+    }
+}
+
+fn key(term: &str) -> String {
+    strip_value(term.trim()).to_lowercase()
+}
+
+/// Whether every word of `term` outside parentheses is capitalized.
+fn looks_like_place(term: &str) -> bool {
+    let name = term.split(" (").next().unwrap_or(term);
+    name.split_whitespace()
+        .all(|word| word.starts_with(char::is_uppercase))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heading_string() {
+        let field = Field::builder("600".to_string(), '1', '0')
+            .subfield_str('a', "Twain, Mark,")
+            .subfield_str('d', "1835-1910")
+            .subfield_str('v', "Correspondence.")
+            .subfield_str('0', "http://id.loc.gov/authorities/names/n79021164")
+            .build();
+        assert_eq!(
+            heading_string(&field).as_deref(),
+            Some("Twain, Mark, 1835-1910--Correspondence")
+        );
+        let empty = Field::builder("650".to_string(), ' ', '0')
+            .subfield_str('2', "local")
+            .build();
+        assert_eq!(heading_string(&empty), None);
+    }
+
+    #[test]
+    fn test_subject_headings() {
+        let mut record =
+            Record::new(crate::leader::Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+        record.add_field(
+            Field::builder("651".to_string(), ' ', '0')
+                .subfield_str('a', "United States")
+                .subfield_str('x', "History")
+                .subfield_str('y', "Civil War, 1861-1865")
+                .subfield_str('v', "Maps.")
+                .build(),
+        );
+        record.add_field(
+            Field::builder("600".to_string(), '1', '0')
+                .subfield_str('a', "Twain, Mark,")
+                .subfield_str('d', "1835-1910.")
+                .subfield_str('0', "n79021164")
+                .build(),
+        );
+        record.add_field(
+            Field::builder("650".to_string(), ' ', '7')
+                .subfield_str('a', "Whaling.")
+                .subfield_str('2', "FAST.")
+                .build(),
+        );
+        record.add_field(
+            Field::builder("245".to_string(), '1', '0')
+                .subfield_str('a', "Not a subject")
+                .build(),
+        );
+
+        let headings = record.subject_headings();
+        assert_eq!(headings.len(), 3);
+        let place = &headings[0];
+        assert_eq!(place.kind, SubjectKind::Geographic);
+        assert_eq!(place.main, "United States");
+        assert_eq!(
+            place.subdivisions,
+            [
+                (SubdivisionType::Topical, "History".to_string()),
+                (
+                    SubdivisionType::Chronological,
+                    "Civil War, 1861-1865".to_string()
+                ),
+                (SubdivisionType::Form, "Maps".to_string()),
+            ]
+        );
+        assert_eq!(place.source.as_deref(), Some("lcsh"));
+        assert_eq!(
+            place.to_heading_string(),
+            "United States--History--Civil War, 1861-1865--Maps"
+        );
+
+        assert_eq!(headings[1].kind, SubjectKind::PersonalName);
+        assert_eq!(headings[1].main, "Twain, Mark, 1835-1910");
+        assert!(headings[1].subdivisions.is_empty());
+        assert_eq!(headings[1].source.as_deref(), Some("naf"));
+        assert_eq!(headings[2].source.as_deref(), Some("fast"));
+    }
+
+    #[test]
+    fn test_heading_to_field() {
+        let defaults = SubdivisionList::default();
+        let person = heading_to_field(
+            "600",
+            '0',
+            "Twain, Mark, 1835-1910 -- Homes and haunts -- Nevada -- Virginia City",
+            &defaults,
+        );
+        let subfields: Vec<(char, &str)> = person
+            .subfields
+            .iter()
+            .map(|sf| (sf.code, sf.value.as_str()))
+            .collect();
+        assert_eq!(
+            subfields,
+            [
+                ('a', "Twain, Mark,"),
+                ('d', "1835-1910"),
+                ('x', "Homes and haunts"),
+                ('z', "Nevada"),
+                ('z', "Virginia City.")
+            ]
+        );
+        assert_eq!(person.indicator1, '1');
+
+        let body = heading_to_field("610", '0', "United States. Congress--Elections", &defaults);
+        assert_eq!(body.get_subfield_values('b'), ["Congress"]);
+        assert_eq!(body.get_subfield('a'), Some("United States."));
+
+        // A provided list overrides the heuristics.
+        let listed = SubdivisionList::new().form("History");
+        let topic = heading_to_field("650", '0', "Whaling--History", &listed);
+        assert_eq!(topic.get_subfield('v'), Some("History."));
+        assert_eq!(heading_string(&topic).as_deref(), Some("Whaling--History"));
+    }
+}
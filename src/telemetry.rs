@@ -0,0 +1,144 @@
+//! Optional instrumentation through the [`tracing`](https://docs.rs/tracing) crate.
+//!
+//! Build with the `tracing` cargo feature to have mrrc emit spans around
+//! its processing stages and counter events for the records it reads,
+//! writes and skips. Without the feature every hook compiles to nothing.
+//!
+//! # Spans
+//!
+//! All spans are at `DEBUG` level, except the long-lived pipeline
+//! producer span, which is at `INFO`.
+//!
+//! | Span | Covers | Fields |
+//! |------|--------|--------|
+//! | `mrrc.parse` | One ISO 2709 record read by [`MarcReader`](crate::MarcReader) | `record_index` |
+//! | `mrrc.encode` | One record written by [`MarcWriter`](crate::MarcWriter) | `record_index` |
+//! | `mrrc.convert` | One conversion between MARC and another format | `from`, `to` |
+//! | `mrrc.pipeline.producer` | The producer thread of a [`ProducerConsumerPipeline`](crate::producer_consumer_pipeline::ProducerConsumerPipeline) | |
+//! | `mrrc.pipeline.batch` | One chunk scanned, parsed and sent by the producer | `bytes` |
+//! | `mrrc.pipeline.parse_batch` | One [`parse_batch_parallel`](crate::rayon_parser_pool::parse_batch_parallel) call | `records` |
+//!
+//! # Counters
+//!
+//! Counts are `TRACE` events with target `mrrc::metrics`. Their fields use
+//! the `monotonic_counter.` prefix that `tracing-opentelemetry`'s
+//! `MetricsLayer` exports as OpenTelemetry counters, so enabling that
+//! target is enough to chart them:
+//!
+//! - `monotonic_counter.mrrc.records_read`
+//! - `monotonic_counter.mrrc.records_written`
+//! - `monotonic_counter.mrrc.records_skipped`
+//!
+//! ```ignore
+//! use tracing_subscriber::{EnvFilter, prelude::*};
+//!
+//! tracing_subscriber::registry()
+//!     .with(EnvFilter::new("info,mrrc=debug,mrrc::metrics=trace"))
+//!     .with(tracing_opentelemetry::MetricsLayer::new(meter_provider))
+//!     .init();
+//! ```
+
+/// Enter a `DEBUG` span that lasts until the end of the enclosing block.
+///
+/// Takes the span name and optional fields, as for `tracing::debug_span!`.
+macro_rules! stage_span {
+    ($name:literal $(, $($fields:tt)*)?) => {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!($name $(, $($fields)*)?).entered();
+    };
+}
+
+pub(crate) use stage_span;
+
+/// Count `n` records read.
+#[inline]
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+pub(crate) fn records_read(n: u64) {
+    #[cfg(feature = "tracing")]
+    tracing::event!(target: "mrrc::metrics", tracing::Level::TRACE, monotonic_counter.mrrc.records_read = n);
+}
+
+/// Count `n` records written.
+#[inline]
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+pub(crate) fn records_written(n: u64) {
+    #[cfg(feature = "tracing")]
+    tracing::event!(target: "mrrc::metrics", tracing::Level::TRACE, monotonic_counter.mrrc.records_written = n);
+}
+
+/// Count `n` records skipped without being parsed.
+#[inline]
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+pub(crate) fn records_skipped(n: u64) {
+    #[cfg(feature = "tracing")]
+    tracing::event!(target: "mrrc::metrics", tracing::Level::TRACE, monotonic_counter.mrrc.records_skipped = n);
+}
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    /// Records span names and event field names.
+    #[derive(Debug, Default, Clone)]
+    struct Capture(Arc<Mutex<Vec<String>>>);
+
+    impl Visit for Capture {
+        fn record_debug(&mut self, field: &Field, _value: &dyn std::fmt::Debug) {
+            self.0.lock().unwrap().push(field.name().to_string());
+        }
+    }
+
+    impl Subscriber for Capture {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let mut names = self.0.lock().unwrap();
+            names.push(span.metadata().name().to_string());
+            Id::from_u64(names.len() as u64)
+        }
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+        fn event(&self, event: &Event<'_>) {
+            event.record(&mut self.clone());
+        }
+        fn enter(&self, _: &Id) {}
+        fn exit(&self, _: &Id) {}
+    }
+
+    #[test]
+    fn test_spans_and_counters() {
+        use crate::{Field as MarcField, Leader, MarcReader, MarcWriter, Record as MarcRecord};
+
+        let mut record = MarcRecord::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+        record.add_field(
+            MarcField::builder("245".to_string(), '0', '0')
+                .subfield_str('a', "T")
+                .build(),
+        );
+
+        let capture = Capture::default();
+        tracing::subscriber::with_default(capture.clone(), || {
+            let mut bytes = Vec::new();
+            MarcWriter::new(&mut bytes).write_record(&record).unwrap();
+            crate::marcxml::record_to_marcxml(&record).unwrap();
+            MarcReader::new(bytes.as_slice()).read_record().unwrap();
+        });
+        let names = capture.0.lock().unwrap();
+        for expected in [
+            "mrrc.encode",
+            "monotonic_counter.mrrc.records_written",
+            "mrrc.convert",
+            "mrrc.parse",
+            "monotonic_counter.mrrc.records_read",
+        ] {
+            assert!(
+                names.iter().any(|n| n == expected),
+                "{expected} in {names:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,435 @@
+//! Brief records generated from templates and a few metadata values.
+//!
+//! Acquisitions and interlibrary loan create brief records from a handful
+//! of values (title, author, ISBN, date). A [`RecordTemplate`] declares the
+//! record once: its leader, the fields to create, constant values, defaults
+//! and which values are required. [`RecordTemplate::fill`] then produces a
+//! record from a key/value map.
+//!
+//! Field values may contain `{key}` placeholders. A subfield whose
+//! placeholders have no value is dropped, and so is a field left with no
+//! subfields, so optional elements need no special casing. Unless the
+//! template has its own 008, one is generated with today's date entered, a
+//! single date (`s`) when the `date` value contains a year, the `country`
+//! and `language` codes, and the material-specific positions coded `|`.
+//! The 245 first indicator is set to match whether a 1XX was created.
+//!
+//! # Definition format
+//!
+//! Templates load from TOML with the `toml` cargo feature:
+//!
+//! ```toml
+//! name = "ILL brief record"
+//! leader = "00000nam a22000005i 4500"
+//! required = ["title", "request"]
+//!
+//! [defaults]
+//! language = "eng"
+//!
+//! [[field]]
+//! tag = "001"
+//! value = "ill{request}"
+//!
+//! [[field]]
+//! tag = "245"
+//! ind2 = "0"
+//! subfields = [{ code = "a", value = "{title}" }]
+//!
+//! [[field]]
+//! tag = "590"
+//! subfields = [{ code = "a", value = "Interlibrary loan copy." }]
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! use std::collections::HashMap;
+//!
+//! use mrrc::templates::RecordTemplate;
+//!
+//! let values = HashMap::from([
+//!     ("title".to_string(), "Roughing it".to_string()),
+//!     ("author".to_string(), "Twain, Mark".to_string()),
+//!     ("date".to_string(), "c1872.".to_string()),
+//! ]);
+//! let record = RecordTemplate::brief_book().fill(&values)?;
+//!
+//! assert_eq!(record.get_field("245").unwrap().indicator1, '1');
+//! assert_eq!(&record.get_control_field("008").unwrap()[6..11], "s1872");
+//! assert!(record.get_field("020").is_none());
+//! # Ok::<(), mrrc::MarcError>(())
+//! ```
+
+use std::collections::HashMap;
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{MarcError, Result};
+use crate::leader::Leader;
+use crate::record::{Field, Record};
+
+/// Leader used when a template does not give one: new language material
+/// monograph, Unicode, partial (preliminary) level, ISBD punctuation.
+const DEFAULT_LEADER: &str = "00000nam a22000005i 4500";
+
+/// One subfield of a [`TemplateField`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemplateSubfield {
+    /// Subfield code.
+    pub code: char,
+    /// Value, possibly with `{key}` placeholders.
+    pub value: String,
+}
+
+/// A field to create.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemplateField {
+    /// Field tag.
+    pub tag: String,
+    /// First indicator (default blank).
+    #[serde(default = "blank")]
+    pub ind1: char,
+    /// Second indicator (default blank).
+    #[serde(default = "blank")]
+    pub ind2: char,
+    /// Value of a control field (tags below 010), possibly with placeholders.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    /// Subfields of a data field.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub subfields: Vec<TemplateSubfield>,
+}
+
+fn blank() -> char {
+    ' '
+}
+
+impl TemplateField {
+    /// A control field with `value`.
+    #[must_use]
+    pub fn control(tag: &str, value: &str) -> Self {
+        TemplateField {
+            tag: tag.to_string(),
+            ind1: ' ',
+            ind2: ' ',
+            value: Some(value.to_string()),
+            subfields: Vec::new(),
+        }
+    }
+
+    /// A data field with no subfields yet.
+    #[must_use]
+    pub fn data(tag: &str, ind1: char, ind2: char) -> Self {
+        TemplateField {
+            tag: tag.to_string(),
+            ind1,
+            ind2,
+            value: None,
+            subfields: Vec::new(),
+        }
+    }
+
+    /// Add a subfield.
+    #[must_use]
+    pub fn subfield(mut self, code: char, value: &str) -> Self {
+        self.subfields.push(TemplateSubfield {
+            code,
+            value: value.to_string(),
+        });
+        self
+    }
+}
+
+/// A declarative recipe for brief records.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordTemplate {
+    /// Template name, for messages.
+    pub name: String,
+    /// 24-character leader; lengths and base address are recomputed when
+    /// the record is written. Defaults to `00000nam a22000005i 4500`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub leader: Option<String>,
+    /// Keys that must have a value.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required: Vec<String>,
+    /// Values used for keys missing from the map passed to
+    /// [`fill`](RecordTemplate::fill).
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub defaults: IndexMap<String, String>,
+    /// Fields to create, in order.
+    #[serde(default, rename = "field")]
+    pub fields: Vec<TemplateField>,
+}
+
+impl RecordTemplate {
+    /// Create an empty template with the default leader.
+    #[must_use]
+    pub fn new(name: &str) -> Self {
+        RecordTemplate {
+            name: name.to_string(),
+            leader: None,
+            required: Vec::new(),
+            defaults: IndexMap::new(),
+            fields: Vec::new(),
+        }
+    }
+
+    /// Add a field.
+    #[must_use]
+    pub fn field(mut self, field: TemplateField) -> Self {
+        self.fields.push(field);
+        self
+    }
+
+    /// Require a value for `key`.
+    #[must_use]
+    pub fn require(mut self, key: &str) -> Self {
+        self.required.push(key.to_string());
+        self
+    }
+
+    /// Use `value` when no value is given for `key`.
+    #[must_use]
+    pub fn default_value(mut self, key: &str, value: &str) -> Self {
+        self.defaults.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    /// Parse a template from TOML.
+    ///
+    /// The document has a top-level `name`, optional `leader` and
+    /// `required` list, a `[defaults]` table, and a `[[field]]` table per
+    /// field with `tag`, `ind1`, `ind2` and either `value` (control fields)
+    /// or `subfields`, a list of `{ code, value }` tables. Requires the
+    /// `toml` cargo feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MarcError::InvalidField`] if the TOML is malformed or does
+    /// not describe a template.
+    #[cfg(feature = "toml")]
+    pub fn from_toml(source: &str) -> Result<Self> {
+        toml::from_str(source)
+            .map_err(|e| MarcError::invalid_field_msg(format!("Invalid template definition: {e}")))
+    }
+
+    /// A brief book record: 020 `{isbn}`, 100 `{author}`, 245 `{title}`,
+    /// 250 `{edition}`, 264 `{place}` `{publisher}` `{date}` and 300
+    /// `{extent}`. Only `title` is required.
+    #[must_use]
+    pub fn brief_book() -> Self {
+        RecordTemplate::new("Brief book")
+            .require("title")
+            .field(TemplateField::data("020", ' ', ' ').subfield('a', "{isbn}"))
+            .field(TemplateField::data("100", '1', ' ').subfield('a', "{author}"))
+            .field(TemplateField::data("245", '1', '0').subfield('a', "{title}"))
+            .field(TemplateField::data("250", ' ', ' ').subfield('a', "{edition}"))
+            .field(
+                TemplateField::data("264", ' ', '1')
+                    .subfield('a', "{place}")
+                    .subfield('b', "{publisher}")
+                    .subfield('c', "{date}"),
+            )
+            .field(TemplateField::data("300", ' ', ' ').subfield('a', "{extent}"))
+    }
+
+    /// Build a record from `values`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MarcError::InvalidField`] if a required key has no value,
+    /// and a leader error if the template's leader is malformed.
+    pub fn fill(&self, values: &HashMap<String, String>) -> Result<Record> {
+        let lookup = |key: &str| {
+            values
+                .get(key)
+                .or_else(|| self.defaults.get(key))
+                .map(|v| v.trim())
+                .filter(|v| !v.is_empty())
+        };
+        if let Some(missing) = self.required.iter().find(|key| lookup(key).is_none()) {
+            return Err(MarcError::invalid_field_msg(format!(
+                "Template '{}' requires a value for '{missing}'",
+                self.name
+            )));
+        }
+
+        let leader = self.leader.as_deref().unwrap_or(DEFAULT_LEADER);
+        let mut record = Record::new(Leader::from_bytes(leader.as_bytes())?);
+        for template in &self.fields {
+            if let Some(value) = &template.value {
+                if let Some(value) = substitute(value, lookup) {
+                    record.add_control_field(template.tag.clone(), value);
+                }
+                continue;
+            }
+            let mut field = Field::new(template.tag.clone(), template.ind1, template.ind2);
+            for subfield in &template.subfields {
+                if let Some(value) = substitute(&subfield.value, lookup) {
+                    field.add_subfield(subfield.code, value);
+                }
+            }
+            if !field.subfields.is_empty() {
+                record.add_field(field);
+            }
+        }
+
+        if record.get_control_field("008").is_none() {
+            record.add_control_field("008".to_string(), fixed_field(lookup));
+        }
+        let main_entry = ["100", "110", "111", "130"]
+            .iter()
+            .any(|tag| record.get_field(tag).is_some());
+        for field in record.fields_by_tag_mut("245") {
+            field.indicator1 = if main_entry { '1' } else { '0' };
+        }
+        Ok(record)
+    }
+}
+
+/// Replace `{key}` placeholders in `text`; `None` if any has no value.
+/// Braces that do not enclose a key are kept as they are.
+fn substitute<'a>(text: &str, lookup: impl Fn(&str) -> Option<&'a str>) -> Option<String> {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let key_len = after
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(after.len());
+        if key_len > 0 && after[key_len..].starts_with('}') {
+            out.push_str(lookup(&after[..key_len])?);
+            rest = &after[key_len + 1..];
+        } else {
+            out.push('{');
+            rest = after;
+        }
+    }
+    out.push_str(rest);
+    Some(out)
+}
+
+/// A generic 008 from the `date`, `country` and `language` values.
+fn fixed_field<'a>(lookup: impl Fn(&str) -> Option<&'a str>) -> String {
+    let entered = crate::fixed_field::date_entered();
+    let dates = lookup("date")
+        .and_then(year)
+        .map_or_else(|| "nuuuuuuuu".to_string(), |year| format!("s{year}    "));
+    let code = |key, width, fallback: &str| {
+        lookup(key)
+            .filter(|v| v.len() <= width && v.is_ascii())
+            .map_or_else(|| fallback.to_string(), |v| format!("{v:<width$}"))
+    };
+    format!(
+        "{entered}{dates}{}{}{} d",
+        code("country", 3, "xx "),
+        "|".repeat(17),
+        code("language", 3, "und")
+    )
+}
+
+/// The first run of four digits in `date`.
+fn year(date: &str) -> Option<&str> {
+    let bytes = date.as_bytes();
+    (0..bytes.len().saturating_sub(3))
+        .find(|&i| bytes[i..i + 4].iter().all(u8::is_ascii_digit))
+        .map(|i| &date[i..i + 4])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_brief_book() {
+        let record = RecordTemplate::brief_book()
+            .fill(&values(&[
+                ("title", "Roughing it"),
+                ("isbn", "9780520268173"),
+                ("publisher", "American Pub. Co."),
+                ("date", "[1872?]"),
+                ("language", "eng"),
+                ("country", "ctu"),
+            ]))
+            .unwrap();
+        assert_eq!(record.leader.encoding_level, '5');
+        assert_eq!(record.leader.cataloging_form, 'i');
+        let fixed = record.get_control_field("008").unwrap();
+        assert_eq!(fixed.len(), 40);
+        assert_eq!(&fixed[6..15], "s1872    ");
+        assert_eq!(&fixed[15..18], "ctu");
+        assert_eq!(&fixed[35..40], "eng d");
+        assert!(record.get_field("100").is_none());
+        assert_eq!(record.get_field("245").unwrap().indicator1, '0');
+        let imprint = record.get_field("264").unwrap();
+        let codes: Vec<char> = imprint.subfields.iter().map(|sf| sf.code).collect();
+        assert_eq!(codes, ['b', 'c']);
+
+        let err = RecordTemplate::brief_book()
+            .fill(&values(&[("title", "  ")]))
+            .unwrap_err();
+        assert!(err.to_string().contains("requires a value for 'title'"));
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_toml_template() {
+        let template = RecordTemplate::from_toml(
+            r#"
+            name = "ILL"
+            leader = "00000nam a22000003  4500"
+            required = ["request"]
+
+            [defaults]
+            note = "Interlibrary loan copy."
+
+            [[field]]
+            tag = "001"
+            value = "ill{request}"
+
+            [[field]]
+            tag = "008"
+            value = "000000n{missing}"
+
+            [[field]]
+            tag = "245"
+            ind2 = "4"
+            subfields = [{ code = "a", value = "{title} {not a key}" }]
+
+            [[field]]
+            tag = "590"
+            subfields = [{ code = "a", value = "{note}" }]
+            "#,
+        )
+        .unwrap();
+        let record = template
+            .fill(&values(&[("request", "42"), ("title", "The title")]))
+            .unwrap();
+        assert_eq!(record.get_control_field("001"), Some("ill42"));
+        // The template's 008 was dropped for lack of a value, so one was generated.
+        assert_eq!(
+            &record.get_control_field("008").unwrap()[6..15],
+            "nuuuuuuuu"
+        );
+        let title = record.get_field("245").unwrap();
+        assert_eq!(title.get_subfield('a'), Some("The title {not a key}"));
+        assert_eq!((title.indicator1, title.indicator2), ('0', '4'));
+        assert_eq!(
+            record.get_field("590").unwrap().get_subfield('a'),
+            Some("Interlibrary loan copy.")
+        );
+        assert_eq!(record.leader.encoding_level, '3');
+
+        assert!(template.fill(&values(&[])).is_err());
+        assert!(RecordTemplate::from_toml("name = 1").is_err());
+    }
+}
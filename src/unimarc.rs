@@ -0,0 +1,1087 @@
+//! UNIMARC: leader interpretation, mandatory fields, detection and
+//! conversion to and from MARC 21.
+//!
+//! UNIMARC shares the ISO 2709 structure with MARC 21, so the binary
+//! [`MarcReader`](crate::MarcReader) and [`MarcWriter`](crate::MarcWriter)
+//! read and write it unchanged; what differs is the meaning of the leader
+//! and the tags. [`detect_dialect`] tells the two apart from a record's
+//! content, [`UnimarcLeader`] interprets the UNIMARC leader, and
+//! [`Dialect::mandatory_fields`] lists what each format requires.
+//!
+//! [`marc21_to_unimarc`] and [`unimarc_to_marc21`] convert the common
+//! bibliographic fields: identifiers, title, edition, publication,
+//! physical description, series, notes, names, subjects, classification
+//! and electronic location, plus the coded data in 008 and UNIMARC 100.
+//! They are not a full crosswalk. Everything they cannot map is reported
+//! in [`Conversion::unmapped`] rather than silently dropped, so callers
+//! can decide whether a record is good enough to load.
+//!
+//! UNIMARC subfields carry no ISBD punctuation; converting to UNIMARC
+//! strips it (see [`punctuation`](crate::punctuation)), and records
+//! converted to MARC 21 are coded as punctuation omitted (leader/18 `c`).
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::unimarc::{detect_dialect, marc21_to_unimarc, unimarc_to_marc21, Dialect};
+//! use mrrc::{Field, Leader, Record};
+//!
+//! let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 i 4500").unwrap());
+//! record.add_control_field_str("001", "12345");
+//! record.add_control_field_str("008", "850101s1984    nyu           000 1 eng d");
+//! record.add_field(
+//!     Field::builder("245".to_string(), '1', '0')
+//!         .subfield_str('a', "Roughing it :")
+//!         .subfield_str('b', "a memoir /")
+//!         .subfield_str('c', "by Mark Twain.")
+//!         .build(),
+//! );
+//! assert_eq!(detect_dialect(&record), Some(Dialect::Marc21));
+//!
+//! let unimarc = marc21_to_unimarc(&record).record;
+//! assert_eq!(detect_dialect(&unimarc), Some(Dialect::Unimarc));
+//! let title = unimarc.get_field("200").unwrap();
+//! assert_eq!(title.get_subfield('a'), Some("Roughing it"));
+//! assert_eq!(title.get_subfield('e'), Some("a memoir"));
+//! assert_eq!(unimarc.get_field("101").unwrap().get_subfield('a'), Some("eng"));
+//!
+//! let back = unimarc_to_marc21(&unimarc).record;
+//! assert_eq!(back.get_field("245").unwrap().get_subfield('b'), Some("a memoir"));
+//! ```
+
+use std::collections::BTreeSet;
+
+use crate::leader::Leader;
+use crate::punctuation::strip_field_punctuation;
+use crate::record::{Field, Record};
+
+/// A MARC format sharing the ISO 2709 structure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dialect {
+    /// MARC 21 (Library of Congress)
+    Marc21,
+    /// UNIMARC (IFLA)
+    Unimarc,
+}
+
+impl Dialect {
+    /// Tags a bibliographic record must carry in this format.
+    ///
+    /// MARC 21 requires the control number, the fixed-length data elements
+    /// (008) and the title statement. UNIMARC requires the record
+    /// identifier, general processing data (100), language (101; mandatory
+    /// for textual material), title (200) and originating source (801).
+    #[must_use]
+    pub fn mandatory_fields(self) -> &'static [&'static str] {
+        match self {
+            Dialect::Marc21 => &["001", "008", "245"],
+            Dialect::Unimarc => &["001", "100", "101", "200", "801"],
+        }
+    }
+}
+
+/// The mandatory tags of `dialect` that `record` lacks, in tag order.
+#[must_use]
+pub fn missing_mandatory_fields(record: &Record, dialect: Dialect) -> Vec<&'static str> {
+    dialect
+        .mandatory_fields()
+        .iter()
+        .copied()
+        .filter(|tag| record.get_control_field(tag).is_none() && record.get_field(tag).is_none())
+        .collect()
+}
+
+/// Guess whether a record is MARC 21 or UNIMARC from its content.
+///
+/// Weighs the fields only one format uses: MARC 21's 008 and 245 against
+/// UNIMARC's 36-character 100 `$a`, 200, 101 and 801, and the leader
+/// positions the two define differently (MARC 21 leader/09 `a` for
+/// Unicode, UNIMARC leader/08 hierarchical level digits). Returns `None`
+/// when the evidence is absent or balanced.
+#[must_use]
+pub fn detect_dialect(record: &Record) -> Option<Dialect> {
+    let mut marc21 = 0;
+    let mut unimarc = 0;
+
+    if record.get_control_field("008").is_some() {
+        marc21 += 2;
+    }
+    if record.get_field("245").is_some() {
+        marc21 += 2;
+    }
+    if record.get_field("040").is_some() {
+        marc21 += 1;
+    }
+    if record.leader.character_coding == 'a' {
+        marc21 += 1;
+    }
+
+    if record
+        .get_field("100")
+        .and_then(|f| f.get_subfield('a'))
+        .is_some_and(is_general_processing_data)
+    {
+        unimarc += 2;
+    }
+    if record.get_field("200").is_some() {
+        unimarc += 2;
+    }
+    if record.get_field("101").is_some() {
+        unimarc += 1;
+    }
+    if record.get_field("801").is_some() {
+        unimarc += 1;
+    }
+    if matches!(record.leader.control_record_type, '0' | '1' | '2') {
+        unimarc += 1;
+    }
+
+    match marc21.cmp(&unimarc) {
+        std::cmp::Ordering::Greater => Some(Dialect::Marc21),
+        std::cmp::Ordering::Less => Some(Dialect::Unimarc),
+        std::cmp::Ordering::Equal => None,
+    }
+}
+
+/// UNIMARC 100 `$a`: 36 characters starting with an 8-digit entry date.
+fn is_general_processing_data(value: &str) -> bool {
+    value.chars().count() == 36 && value.bytes().take(8).all(|b| b.is_ascii_digit())
+}
+
+/// The leader positions UNIMARC defines, read from a [`Leader`].
+///
+/// [`Leader`]'s field names follow MARC 21; this struct names the same
+/// positions as UNIMARC does. Positions UNIMARC leaves undefined (09, 19)
+/// are not included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnimarcLeader {
+    /// 05 - Record status
+    pub record_status: char,
+    /// 06 - Type of record
+    pub record_type: char,
+    /// 07 - Bibliographic level
+    pub bibliographic_level: char,
+    /// 08 - Hierarchical level code
+    pub hierarchical_level: char,
+    /// 17 - Encoding level
+    pub encoding_level: char,
+    /// 18 - Descriptive cataloguing form
+    pub descriptive_cataloguing_form: char,
+}
+
+impl UnimarcLeader {
+    /// Read the UNIMARC positions of `leader`.
+    #[must_use]
+    pub fn from_leader(leader: &Leader) -> Self {
+        UnimarcLeader {
+            record_status: leader.record_status,
+            record_type: leader.record_type,
+            bibliographic_level: leader.bibliographic_level,
+            hierarchical_level: leader.control_record_type,
+            encoding_level: leader.encoding_level,
+            descriptive_cataloguing_form: leader.cataloging_form,
+        }
+    }
+
+    /// Valid values and their descriptions for a UNIMARC leader position
+    /// (5-8, 17 or 18), as [`Leader::valid_values_at_position`] gives them
+    /// for MARC 21.
+    #[must_use]
+    pub fn valid_values_at_position(position: usize) -> Option<Vec<(&'static str, &'static str)>> {
+        match position {
+            5 => Some(vec![
+                ("c", "Corrected or revised record"),
+                ("d", "Deleted record"),
+                ("n", "New record"),
+                ("o", "Previously issued higher level record"),
+                (
+                    "p",
+                    "Previously issued as an incomplete, pre-publication record",
+                ),
+            ]),
+            6 => Some(vec![
+                ("a", "Language materials, printed"),
+                ("b", "Language materials, manuscript"),
+                ("c", "Music scores, printed"),
+                ("d", "Music scores, manuscript"),
+                ("e", "Cartographic materials, printed"),
+                ("f", "Cartographic materials, manuscript"),
+                ("g", "Projected and video material"),
+                ("i", "Sound recordings, non-musical performance"),
+                ("j", "Sound recordings, musical performance"),
+                ("k", "Two-dimensional graphics"),
+                ("l", "Electronic resources"),
+                ("m", "Multimedia"),
+                ("r", "Three-dimensional artefacts and realia"),
+            ]),
+            7 => Some(vec![
+                ("a", "Analytic (component part)"),
+                ("c", "Collection"),
+                ("i", "Integrating resource"),
+                ("m", "Monographic"),
+                ("s", "Serial"),
+            ]),
+            8 => Some(vec![
+                (" ", "Hierarchical relationship undefined"),
+                ("0", "No hierarchical relationship"),
+                ("1", "Highest level record"),
+                ("2", "Record below highest level"),
+            ]),
+            17 => Some(vec![
+                (" ", "Full level"),
+                ("1", "Sublevel 1 (not examined)"),
+                ("2", "Sublevel 2 (pre-publication)"),
+                ("3", "Sublevel 3 (partial)"),
+            ]),
+            18 => Some(vec![
+                (" ", "Record is in full ISBD form"),
+                ("i", "Record is in partial or incomplete ISBD form"),
+                ("n", "Record is in non-ISBD form"),
+            ]),
+            _ => None,
+        }
+    }
+
+    /// Description of `value` at a UNIMARC leader position, or `None` if
+    /// the value is not valid there.
+    #[must_use]
+    pub fn describe_value(position: usize, value: &str) -> Option<&'static str> {
+        Self::valid_values_at_position(position).and_then(|values| {
+            values
+                .into_iter()
+                .find(|(v, _)| *v == value)
+                .map(|(_, desc)| desc)
+        })
+    }
+
+    /// Leader positions holding values UNIMARC does not define.
+    #[must_use]
+    pub fn invalid_positions(&self) -> Vec<usize> {
+        [
+            (5, self.record_status),
+            (6, self.record_type),
+            (7, self.bibliographic_level),
+            (8, self.hierarchical_level),
+            (17, self.encoding_level),
+            (18, self.descriptive_cataloguing_form),
+        ]
+        .into_iter()
+        .filter(|(position, value)| {
+            Self::describe_value(*position, value.encode_utf8(&mut [0; 4])).is_none()
+        })
+        .map(|(position, _)| position)
+        .collect()
+    }
+}
+
+/// Rewrite a MARC 21 leader with the UNIMARC codes for the same record.
+///
+/// Record length and base address are kept; writers recompute them.
+#[must_use]
+pub fn leader_to_unimarc(leader: &Leader) -> Leader {
+    let mut out = leader.clone();
+    out.record_status = match leader.record_status {
+        'a' => 'c',
+        other => other,
+    };
+    out.record_type = match leader.record_type {
+        't' => 'b',
+        'm' => 'l',
+        'o' | 'p' => 'm',
+        other => other,
+    };
+    out.bibliographic_level = match leader.bibliographic_level {
+        'b' => 'a',
+        'd' => 'm',
+        other => other,
+    };
+    out.control_record_type = ' ';
+    out.character_coding = ' ';
+    out.encoding_level = match leader.encoding_level {
+        ' ' | '4' | 'I' => ' ',
+        '1' => '1',
+        '8' => '2',
+        _ => '3',
+    };
+    out.cataloging_form = match leader.cataloging_form {
+        'a' | 'c' | 'i' => ' ',
+        _ => 'n',
+    };
+    out.multipart_level = ' ';
+    out
+}
+
+/// Rewrite a UNIMARC leader with the MARC 21 codes for the same record.
+///
+/// The result is coded as Unicode (leader/09 `a`) and, since UNIMARC data
+/// carries no ISBD punctuation, as ISBD with punctuation omitted
+/// (leader/18 `c`) unless the UNIMARC record is non-ISBD.
+#[must_use]
+pub fn leader_to_marc21(leader: &Leader) -> Leader {
+    let mut out = leader.clone();
+    out.record_status = match leader.record_status {
+        'o' => 'c',
+        other => other,
+    };
+    out.record_type = match leader.record_type {
+        'b' => 't',
+        'l' => 'm',
+        other => other,
+    };
+    out.control_record_type = ' ';
+    out.character_coding = 'a';
+    out.encoding_level = match leader.encoding_level {
+        '1' => '1',
+        '2' => '8',
+        '3' => '7',
+        _ => ' ',
+    };
+    out.cataloging_form = match leader.cataloging_form {
+        'n' => ' ',
+        _ => 'c',
+    };
+    out.multipart_level = ' ';
+    out
+}
+
+/// The result of converting a record between MARC 21 and UNIMARC.
+#[derive(Debug, Clone)]
+pub struct Conversion {
+    /// The converted record
+    pub record: Record,
+    /// What could not be converted and was left out: a tag (`"246"`) for a
+    /// whole field, or a tag and code (`"245$6"`) for a subfield. Sorted,
+    /// without duplicates.
+    pub unmapped: Vec<String>,
+}
+
+/// How indicators translate for one field mapping.
+#[derive(Clone, Copy)]
+enum Indicators {
+    /// Copied unchanged
+    Keep,
+    /// Set to fixed values in each direction
+    Fixed {
+        marc21: [char; 2],
+        unimarc: [char; 2],
+    },
+    /// A name heading
+    Name(Name),
+    /// A subject heading, optionally a name: MARC 21 ind2 (thesaurus)
+    /// becomes UNIMARC `$2`
+    Subject(Option<Name>),
+}
+
+#[derive(Clone, Copy)]
+enum Name {
+    Personal,
+    Corporate,
+}
+
+struct FieldMap {
+    marc21: &'static str,
+    unimarc: &'static str,
+    /// (MARC 21 code, UNIMARC code)
+    subfields: &'static [(char, char)],
+    indicators: Indicators,
+}
+
+const BLANK: Indicators = Indicators::Fixed {
+    marc21: [' ', ' '],
+    unimarc: [' ', ' '],
+};
+
+const PERSONAL_NAME: &[(char, char)] = &[
+    ('a', 'a'),
+    ('b', 'd'),
+    ('c', 'c'),
+    ('d', 'f'),
+    ('q', 'g'),
+    ('4', '4'),
+];
+const CORPORATE_NAME: &[(char, char)] = &[('a', 'a'), ('b', 'b'), ('4', '4')];
+
+/// Field mappings. Where several entries share a tag, conversion from that
+/// side uses the first.
+const FIELD_MAPS: &[FieldMap] = &[
+    FieldMap {
+        marc21: "020",
+        unimarc: "010",
+        subfields: &[('a', 'a'), ('q', 'b'), ('c', 'd'), ('z', 'z')],
+        indicators: BLANK,
+    },
+    FieldMap {
+        marc21: "022",
+        unimarc: "011",
+        subfields: &[('a', 'a'), ('y', 'z'), ('z', 'y')],
+        indicators: BLANK,
+    },
+    FieldMap {
+        marc21: "035",
+        unimarc: "035",
+        subfields: &[('a', 'a'), ('z', 'z')],
+        indicators: BLANK,
+    },
+    FieldMap {
+        marc21: "040",
+        unimarc: "801",
+        subfields: &[('a', 'b')],
+        indicators: Indicators::Fixed {
+            marc21: [' ', ' '],
+            unimarc: [' ', '0'],
+        },
+    },
+    FieldMap {
+        marc21: "041",
+        unimarc: "101",
+        subfields: &[('a', 'a'), ('h', 'c')],
+        indicators: Indicators::Keep,
+    },
+    FieldMap {
+        marc21: "050",
+        unimarc: "680",
+        subfields: &[('a', 'a'), ('b', 'b')],
+        indicators: Indicators::Fixed {
+            marc21: [' ', '4'],
+            unimarc: [' ', ' '],
+        },
+    },
+    FieldMap {
+        marc21: "082",
+        unimarc: "676",
+        subfields: &[('a', 'a'), ('2', 'v')],
+        indicators: Indicators::Fixed {
+            marc21: ['0', '4'],
+            unimarc: [' ', ' '],
+        },
+    },
+    FieldMap {
+        marc21: "100",
+        unimarc: "700",
+        subfields: PERSONAL_NAME,
+        indicators: Indicators::Name(Name::Personal),
+    },
+    FieldMap {
+        marc21: "110",
+        unimarc: "710",
+        subfields: CORPORATE_NAME,
+        indicators: Indicators::Name(Name::Corporate),
+    },
+    FieldMap {
+        marc21: "245",
+        unimarc: "200",
+        subfields: &[
+            ('a', 'a'),
+            ('h', 'b'),
+            ('b', 'e'),
+            ('c', 'f'),
+            ('n', 'h'),
+            ('p', 'i'),
+        ],
+        indicators: Indicators::Fixed {
+            marc21: ['1', '0'],
+            unimarc: ['1', ' '],
+        },
+    },
+    FieldMap {
+        marc21: "250",
+        unimarc: "205",
+        subfields: &[('a', 'a'), ('b', 'b')],
+        indicators: BLANK,
+    },
+    FieldMap {
+        marc21: "260",
+        unimarc: "210",
+        subfields: &[('a', 'a'), ('b', 'c'), ('c', 'd')],
+        indicators: BLANK,
+    },
+    // Only 264 _1 (publication); see `marc21_to_unimarc`
+    FieldMap {
+        marc21: "264",
+        unimarc: "210",
+        subfields: &[('a', 'a'), ('b', 'c'), ('c', 'd')],
+        indicators: BLANK,
+    },
+    FieldMap {
+        marc21: "300",
+        unimarc: "215",
+        subfields: &[('a', 'a'), ('b', 'c'), ('c', 'd'), ('e', 'e')],
+        indicators: BLANK,
+    },
+    FieldMap {
+        marc21: "490",
+        unimarc: "225",
+        subfields: &[('a', 'a'), ('v', 'v'), ('x', 'x')],
+        indicators: Indicators::Fixed {
+            marc21: ['0', ' '],
+            unimarc: ['1', ' '],
+        },
+    },
+    FieldMap {
+        marc21: "500",
+        unimarc: "300",
+        subfields: &[('a', 'a')],
+        indicators: BLANK,
+    },
+    FieldMap {
+        marc21: "504",
+        unimarc: "320",
+        subfields: &[('a', 'a')],
+        indicators: BLANK,
+    },
+    FieldMap {
+        marc21: "520",
+        unimarc: "330",
+        subfields: &[('a', 'a')],
+        indicators: BLANK,
+    },
+    FieldMap {
+        marc21: "600",
+        unimarc: "600",
+        subfields: &[
+            ('a', 'a'),
+            ('b', 'd'),
+            ('c', 'c'),
+            ('d', 'f'),
+            ('q', 'g'),
+            ('v', 'j'),
+            ('x', 'x'),
+            ('y', 'z'),
+            ('z', 'y'),
+            ('2', '2'),
+        ],
+        indicators: Indicators::Subject(Some(Name::Personal)),
+    },
+    FieldMap {
+        marc21: "610",
+        unimarc: "601",
+        subfields: &[
+            ('a', 'a'),
+            ('b', 'b'),
+            ('v', 'j'),
+            ('x', 'x'),
+            ('y', 'z'),
+            ('z', 'y'),
+            ('2', '2'),
+        ],
+        indicators: Indicators::Subject(Some(Name::Corporate)),
+    },
+    FieldMap {
+        marc21: "650",
+        unimarc: "606",
+        subfields: &[
+            ('a', 'a'),
+            ('v', 'j'),
+            ('x', 'x'),
+            ('y', 'z'),
+            ('z', 'y'),
+            ('2', '2'),
+        ],
+        indicators: Indicators::Subject(None),
+    },
+    FieldMap {
+        marc21: "651",
+        unimarc: "607",
+        subfields: &[
+            ('a', 'a'),
+            ('v', 'j'),
+            ('x', 'x'),
+            ('y', 'z'),
+            ('z', 'y'),
+            ('2', '2'),
+        ],
+        indicators: Indicators::Subject(None),
+    },
+    FieldMap {
+        marc21: "700",
+        unimarc: "701",
+        subfields: PERSONAL_NAME,
+        indicators: Indicators::Name(Name::Personal),
+    },
+    // UNIMARC 702 (secondary responsibility) has no MARC 21 counterpart
+    // other than 700
+    FieldMap {
+        marc21: "700",
+        unimarc: "702",
+        subfields: PERSONAL_NAME,
+        indicators: Indicators::Name(Name::Personal),
+    },
+    FieldMap {
+        marc21: "710",
+        unimarc: "711",
+        subfields: CORPORATE_NAME,
+        indicators: Indicators::Name(Name::Corporate),
+    },
+    FieldMap {
+        marc21: "710",
+        unimarc: "712",
+        subfields: CORPORATE_NAME,
+        indicators: Indicators::Name(Name::Corporate),
+    },
+    FieldMap {
+        marc21: "856",
+        unimarc: "856",
+        subfields: &[('u', 'u'), ('q', 'q'), ('z', 'z')],
+        indicators: Indicators::Keep,
+    },
+];
+
+/// Control fields with the same tag and meaning in both formats.
+const SHARED_CONTROL_FIELDS: &[&str] = &["001", "003", "005"];
+
+/// `$2` value standing for MARC 21 subject ind2 `0` (LCSH).
+const LCSH: &str = "lc";
+
+/// Tracks converted fields and what was left out.
+struct Converter {
+    fields: Vec<Field>,
+    unmapped: BTreeSet<String>,
+}
+
+impl Converter {
+    fn new() -> Self {
+        Converter {
+            fields: Vec::new(),
+            unmapped: BTreeSet::new(),
+        }
+    }
+
+    /// Copy the subfields `map` covers from `source` into `target`, in
+    /// source order.
+    fn map_subfields(
+        &mut self,
+        source: &Field,
+        target: &mut Field,
+        map: impl Fn(char) -> Option<char>,
+    ) {
+        for subfield in &source.subfields {
+            match map(subfield.code) {
+                Some(code) => target.add_subfield(code, subfield.value.clone()),
+                None => {
+                    self.unmapped
+                        .insert(format!("{}${}", source.tag, subfield.code));
+                },
+            }
+        }
+    }
+
+    fn finish(mut self, leader: Leader, control_fields: Vec<(String, String)>) -> Conversion {
+        let mut record = Record::new(leader);
+        for (tag, value) in control_fields {
+            record.add_control_field(tag, value);
+        }
+        self.fields.sort_by(|a, b| a.tag.cmp(&b.tag));
+        for field in self.fields {
+            record.add_field(field);
+        }
+        Conversion {
+            record,
+            unmapped: self.unmapped.into_iter().collect(),
+        }
+    }
+}
+
+fn name_indicators_to_unimarc(name: Name, field: &Field) -> [char; 2] {
+    match name {
+        Name::Personal => [' ', if field.indicator1 == '0' { '0' } else { '1' }],
+        Name::Corporate => ['0', '2'],
+    }
+}
+
+fn name_ind1_to_marc21(name: Name, field: &Field) -> char {
+    match name {
+        Name::Personal => {
+            if field.indicator2 == '0' {
+                '0'
+            } else {
+                '1'
+            }
+        },
+        Name::Corporate => '2',
+    }
+}
+
+/// Convert a MARC 21 bibliographic record to UNIMARC.
+///
+/// See the [module documentation](self) for what is converted. The 008 is
+/// turned into UNIMARC 100 `$a` (with entry dates `yy` read as 19yy from
+/// 50 and 20yy below) and, when there is no 041, into 101 `$a`. ISBD
+/// punctuation is stripped unless leader/18 says it was already omitted.
+#[must_use]
+pub fn marc21_to_unimarc(record: &Record) -> Conversion {
+    let mut converter = Converter::new();
+    let punctuated = !matches!(record.leader.cataloging_form, 'c' | 'n');
+
+    let mut control_fields = Vec::new();
+    for (tag, value) in record.control_fields_iter() {
+        if SHARED_CONTROL_FIELDS.contains(&tag) {
+            control_fields.push((tag.to_string(), value.to_string()));
+        } else if tag != "008" {
+            converter.unmapped.insert(tag.to_string());
+        }
+    }
+
+    let fixed = record.get_control_field("008").unwrap_or("");
+    let mut general = Field::new("100".to_string(), ' ', ' ');
+    general.add_subfield('a', general_processing_data(record, fixed));
+    converter.fields.push(general);
+    if record.get_field("041").is_none()
+        && let Some(language) = fixed
+            .get(35..38)
+            .filter(|l| l.trim_matches(['|', ' ']).len() == 3)
+    {
+        let mut field = Field::new("101".to_string(), '0', ' ');
+        field.add_subfield_str('a', language);
+        converter.fields.push(field);
+    }
+
+    for source in record.fields() {
+        let map = FIELD_MAPS.iter().find(|m| m.marc21 == source.tag);
+        let Some(map) = map.filter(|_| source.tag != "264" || source.indicator2 == '1') else {
+            converter.unmapped.insert(source.tag.clone());
+            continue;
+        };
+        let mut source = source.clone();
+        if punctuated {
+            strip_field_punctuation(&mut source);
+        }
+        let [ind1, ind2] = match map.indicators {
+            Indicators::Keep => [source.indicator1, source.indicator2],
+            Indicators::Fixed { unimarc, .. } => unimarc,
+            Indicators::Name(name) => name_indicators_to_unimarc(name, &source),
+            Indicators::Subject(name) => {
+                name.map_or([' ', ' '], |name| name_indicators_to_unimarc(name, &source))
+            },
+        };
+        let mut target = Field::new(map.unimarc.to_string(), ind1, ind2);
+        converter.map_subfields(&source, &mut target, |code| {
+            map.subfields
+                .iter()
+                .find(|(from, _)| *from == code)
+                .map(|(_, to)| *to)
+        });
+        if matches!(map.indicators, Indicators::Subject(_)) && source.indicator2 == '0' {
+            target.add_subfield_str('2', LCSH);
+        }
+        converter.fields.push(target);
+    }
+
+    converter.finish(leader_to_unimarc(&record.leader), control_fields)
+}
+
+/// Convert a UNIMARC bibliographic record to MARC 21.
+///
+/// See the [module documentation](self) for what is converted. UNIMARC
+/// 100 `$a` and 101 `$a` become the 008; 245 ind1 is set from whether the
+/// result has a 1XX main entry.
+#[must_use]
+pub fn unimarc_to_marc21(record: &Record) -> Conversion {
+    let mut converter = Converter::new();
+
+    let mut control_fields = Vec::new();
+    for (tag, value) in record.control_fields_iter() {
+        if SHARED_CONTROL_FIELDS.contains(&tag) {
+            control_fields.push((tag.to_string(), value.to_string()));
+        } else {
+            converter.unmapped.insert(tag.to_string());
+        }
+    }
+    control_fields.push(("008".to_string(), fixed_length_data(record)));
+
+    for source in record.fields() {
+        if source.tag == "100" {
+            continue;
+        }
+        let Some(map) = FIELD_MAPS.iter().find(|m| m.unimarc == source.tag) else {
+            converter.unmapped.insert(source.tag.clone());
+            continue;
+        };
+        let [ind1, ind2] = match map.indicators {
+            Indicators::Keep => [source.indicator1, source.indicator2],
+            Indicators::Fixed { marc21, .. } => marc21,
+            Indicators::Name(name) => [name_ind1_to_marc21(name, source), ' '],
+            Indicators::Subject(name) => {
+                let ind1 = name.map_or(' ', |name| name_ind1_to_marc21(name, source));
+                let ind2 = match source.get_subfield('2') {
+                    Some(LCSH) => '0',
+                    Some(_) => '7',
+                    None => '4',
+                };
+                [ind1, ind2]
+            },
+        };
+        let mut target = Field::new(map.marc21.to_string(), ind1, ind2);
+        converter.map_subfields(source, &mut target, |code| {
+            map.subfields
+                .iter()
+                .find(|(_, from)| *from == code)
+                .map(|(to, _)| *to)
+        });
+        if ind2 == '0' && matches!(map.indicators, Indicators::Subject(_)) {
+            target.remove_subfields_where(|sf| sf.code == '2' && sf.value == LCSH);
+        }
+        converter.fields.push(target);
+    }
+
+    let has_main_entry = converter
+        .fields
+        .iter()
+        .any(|f| matches!(f.tag.as_str(), "100" | "110" | "111"));
+    if !has_main_entry {
+        for field in converter.fields.iter_mut().filter(|f| f.tag == "245") {
+            field.indicator1 = '0';
+        }
+    }
+
+    converter.finish(leader_to_marc21(&record.leader), control_fields)
+}
+
+/// (MARC 21 008/06, UNIMARC 100/08) type-of-date codes.
+const DATE_TYPES: &[(char, char)] = &[
+    ('c', 'a'),
+    ('d', 'b'),
+    ('u', 'c'),
+    ('s', 'd'),
+    ('r', 'e'),
+    ('q', 'f'),
+    ('m', 'g'),
+    ('t', 'h'),
+    ('p', 'i'),
+    ('e', 'j'),
+    ('n', 'u'),
+];
+
+/// Characters `start..end` of a fixed-length field, padded with `fill`.
+fn slice(value: &str, start: usize, end: usize, fill: char) -> String {
+    let mut out: String = value.chars().skip(start).take(end - start).collect();
+    while out.chars().count() < end - start {
+        out.push(fill);
+    }
+    out
+}
+
+/// UNIMARC 100 `$a` for a MARC 21 record with 008 `fixed`.
+fn general_processing_data(record: &Record, fixed: &str) -> String {
+    let entered = slice(fixed, 0, 6, ' ');
+    let entered = match entered.get(0..2).and_then(|yy| yy.parse::<u8>().ok()) {
+        Some(yy) if entered.bytes().all(|b| b.is_ascii_digit()) => {
+            format!("{}{entered}", if yy >= 50 { "19" } else { "20" })
+        },
+        _ => " ".repeat(8),
+    };
+    let date_type = fixed
+        .chars()
+        .nth(6)
+        .and_then(|code| DATE_TYPES.iter().find(|(marc21, _)| *marc21 == code))
+        .map_or('u', |(_, unimarc)| *unimarc);
+    let cataloguing_language = record
+        .get_field("040")
+        .and_then(|f| f.get_subfield('b'))
+        .filter(|b| b.len() == 3)
+        .unwrap_or("und");
+    format!(
+        "{entered}{date_type}{}{}u  u0{cataloguing_language}y50      ba",
+        slice(fixed, 7, 11, ' '),
+        slice(fixed, 11, 15, ' '),
+    )
+}
+
+/// MARC 21 008 for a UNIMARC record, from its 100 `$a` and 101 `$a`.
+///
+/// Positions with no UNIMARC source are coded `|` (no attempt to code).
+fn fixed_length_data(record: &Record) -> String {
+    let general = record
+        .get_field("100")
+        .and_then(|f| f.get_subfield('a'))
+        .unwrap_or("");
+    let entered = slice(general, 2, 8, ' ');
+    let entered = if entered.bytes().all(|b| b.is_ascii_digit()) {
+        entered
+    } else {
+        " ".repeat(6)
+    };
+    let date_type = general
+        .chars()
+        .nth(8)
+        .and_then(|code| DATE_TYPES.iter().find(|(_, unimarc)| *unimarc == code))
+        .map_or('|', |(marc21, _)| *marc21);
+    let language = record
+        .get_field("101")
+        .and_then(|f| f.get_subfield('a'))
+        .filter(|a| a.len() == 3)
+        .unwrap_or("und");
+    format!(
+        "{entered}{date_type}{}{}xx {}{language}||",
+        slice(general, 9, 13, ' '),
+        slice(general, 13, 17, ' '),
+        "|".repeat(17),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn marc21() -> Record {
+        let mut record = Record::new(Leader::from_bytes(b"00000cam a2200000 i 4500").unwrap());
+        record.add_control_field_str("001", "ocm01234567");
+        record.add_control_field_str("007", "ta");
+        record.add_control_field_str("008", "020805s2002    nyu    j      000 1 eng  ");
+        record.add_field(
+            Field::builder("020".to_string(), ' ', ' ')
+                .subfield_str('a', "0786808772")
+                .build(),
+        );
+        record.add_field(
+            Field::builder("040".to_string(), ' ', ' ')
+                .subfield_str('a', "DLC")
+                .subfield_str('b', "eng")
+                .build(),
+        );
+        record.add_field(
+            Field::builder("100".to_string(), '1', ' ')
+                .subfield_str('a', "Chabon, Michael.")
+                .build(),
+        );
+        record.add_field(
+            Field::builder("245".to_string(), '1', '0')
+                .subfield_str('a', "Summerland /")
+                .subfield_str('c', "Michael Chabon.")
+                .subfield_str('6', "880-01")
+                .build(),
+        );
+        record.add_field(
+            Field::builder("264".to_string(), ' ', '1')
+                .subfield_str('a', "New York :")
+                .subfield_str('b', "Miramax Books,")
+                .subfield_str('c', "2002.")
+                .build(),
+        );
+        record.add_field(
+            Field::builder("264".to_string(), ' ', '4')
+                .subfield_str('c', "©2002")
+                .build(),
+        );
+        record.add_field(
+            Field::builder("650".to_string(), ' ', '0')
+                .subfield_str('a', "Baseball")
+                .subfield_str('v', "Fiction.")
+                .build(),
+        );
+        record.add_field(
+            Field::builder("246".to_string(), '3', ' ')
+                .subfield_str('a', "Summer land")
+                .build(),
+        );
+        record
+    }
+
+    #[test]
+    fn test_marc21_to_unimarc() {
+        let Conversion { record, unmapped } = marc21_to_unimarc(&marc21());
+        assert_eq!(unmapped, ["007", "040$b", "245$6", "246", "264"]);
+        assert_eq!(record.leader.record_status, 'c');
+        assert!(
+            UnimarcLeader::from_leader(&record.leader)
+                .invalid_positions()
+                .is_empty()
+        );
+        assert!(missing_mandatory_fields(&record, Dialect::Unimarc).is_empty());
+
+        let general = record.get_field("100").unwrap().get_subfield('a').unwrap();
+        assert_eq!(general, "20020805d2002    u  u0engy50      ba");
+        assert!(is_general_processing_data(general));
+        assert_eq!(
+            record.get_field("010").unwrap().get_subfield('a'),
+            Some("0786808772")
+        );
+        assert_eq!(
+            record.get_field("101").unwrap().get_subfield('a'),
+            Some("eng")
+        );
+
+        let title = record.get_field("200").unwrap();
+        assert_eq!((title.indicator1, title.indicator2), ('1', ' '));
+        assert_eq!(title.get_subfield('a'), Some("Summerland"));
+        assert_eq!(title.get_subfield('f'), Some("Michael Chabon"));
+
+        let publication = record.get_field("210").unwrap();
+        assert_eq!(
+            publication.get_subfields(&['a', 'c', 'd']),
+            ["New York", "Miramax Books", "2002"]
+        );
+        let author = record.get_field("700").unwrap();
+        assert_eq!((author.indicator1, author.indicator2), (' ', '1'));
+        let subject = record.get_field("606").unwrap();
+        assert_eq!(
+            subject.get_subfields(&['a', 'j', '2']),
+            ["Baseball", "Fiction", "lc"]
+        );
+        assert_eq!(
+            record.get_field("801").unwrap().get_subfield('b'),
+            Some("DLC")
+        );
+
+        // Fields come out in tag order
+        let tags: Vec<&str> = record.fields().map(|f| f.tag.as_str()).collect();
+        let mut sorted = tags.clone();
+        sorted.sort_unstable();
+        assert_eq!(tags, sorted);
+    }
+
+    #[test]
+    fn test_round_trip_to_marc21() {
+        let unimarc = marc21_to_unimarc(&marc21()).record;
+        let Conversion { record, unmapped } = unimarc_to_marc21(&unimarc);
+        assert!(unmapped.is_empty(), "{unmapped:?}");
+        assert_eq!(record.leader.cataloging_form, 'c');
+        assert_eq!(record.leader.character_coding, 'a');
+        assert!(missing_mandatory_fields(&record, Dialect::Marc21).is_empty());
+
+        let fixed = record.get_control_field("008").unwrap();
+        assert_eq!(fixed.len(), 40);
+        assert_eq!(&fixed[0..15], "020805s2002    ");
+        assert_eq!(&fixed[35..38], "eng");
+
+        let title = record.get_field("245").unwrap();
+        assert_eq!((title.indicator1, title.indicator2), ('1', '0'));
+        assert_eq!(
+            title.get_subfields(&['a', 'c']),
+            ["Summerland", "Michael Chabon"]
+        );
+        let author = record.get_field("100").unwrap();
+        assert_eq!(author.indicator1, '1');
+        let subject = record.get_field("650").unwrap();
+        assert_eq!(subject.indicator2, '0');
+        assert_eq!(subject.get_subfield('2'), None);
+        assert_eq!(
+            record.get_field("260").unwrap().get_subfield('b'),
+            Some("Miramax Books")
+        );
+    }
+
+    #[test]
+    fn test_detection_and_leader() {
+        let marc = marc21();
+        assert_eq!(detect_dialect(&marc), Some(Dialect::Marc21));
+        assert_eq!(
+            detect_dialect(&marc21_to_unimarc(&marc).record),
+            Some(Dialect::Unimarc)
+        );
+        let empty = Record::new(Leader::from_bytes(b"00000nam  2200000   4500").unwrap());
+        assert_eq!(detect_dialect(&empty), None);
+        assert_eq!(
+            missing_mandatory_fields(&empty, Dialect::Unimarc),
+            ["001", "100", "101", "200", "801"]
+        );
+
+        let leader =
+            UnimarcLeader::from_leader(&Leader::from_bytes(b"00000aam  22000003  4500").unwrap());
+        assert_eq!(leader.invalid_positions(), [5]);
+        assert_eq!(
+            UnimarcLeader::describe_value(17, "3"),
+            Some("Sublevel 3 (partial)")
+        );
+        assert_eq!(UnimarcLeader::describe_value(6, "t"), None);
+    }
+}
@@ -0,0 +1,604 @@
+//! Electronic location (856) access, URL normalization and link checking.
+//!
+//! [`electronic_locations`] reads every 856 of a record into an
+//! [`ElectronicLocation`] with its indicators decoded into an
+//! [`AccessMethod`] and [`Relationship`]. The maintenance passes that
+//! e-resource batches need all work on `$u`:
+//!
+//! - [`normalize_url`] checks a URL and returns its normalized form:
+//!   surrounding whitespace removed, scheme and host lowercased, `www.`
+//!   addresses given an `http://` scheme, and characters that are not
+//!   allowed in URLs percent-encoded. [`normalize_urls`] applies it to a
+//!   record and [`validate_urls`] reports the URLs it rejects.
+//! - [`ProxyRewriter`] replaces, adds or strips proxy prefixes such as
+//!   `https://proxy.example.edu/login?url=` by rule.
+//! - [`check_links`] and [`check_links_async`] pass each URL to a
+//!   [`LinkChecker`] or [`AsyncLinkChecker`] and collect the HTTP status
+//!   per URL. As with [`enrichment`](crate::enrichment), the checker is
+//!   supplied by the caller, so the crate needs no HTTP client or runtime.
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::urls::{electronic_locations, normalize_urls, ProxyRewriter, Relationship};
+//! use mrrc::{Field, Leader, Record};
+//!
+//! let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+//! record.add_field(
+//!     Field::builder("856".to_string(), '4', '0')
+//!         .subfield_str('u', " HTTP://Old-Proxy.example.edu/login?url=https://Example.org/a b")
+//!         .subfield_str('z', "Campus access only")
+//!         .build(),
+//! );
+//!
+//! normalize_urls(&mut record);
+//! ProxyRewriter::new()
+//!     .rule(
+//!         "http://old-proxy.example.edu/login?url=",
+//!         "https://proxy.example.edu/login?url=",
+//!     )
+//!     .rewrite(&mut record);
+//!
+//! let location = &electronic_locations(&record)[0];
+//! assert_eq!(location.relationship, Relationship::Resource);
+//! assert_eq!(
+//!     location.urls,
+//!     vec!["https://proxy.example.edu/login?url=https://Example.org/a%20b"]
+//! );
+//! assert_eq!(location.public_notes, vec!["Campus access only"]);
+//! ```
+
+use std::fmt;
+use std::fmt::Write;
+use std::future::Future;
+
+use crate::record::{Field, Record};
+
+/// How the resource is accessed (856 first indicator).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AccessMethod {
+    /// No information provided (blank).
+    Unspecified,
+    /// Email (0).
+    Email,
+    /// FTP (1).
+    Ftp,
+    /// Remote login, Telnet (2).
+    RemoteLogin,
+    /// Dial-up (3).
+    DialUp,
+    /// HTTP (4).
+    Http,
+    /// Method given in `$2` (7).
+    InSubfield2,
+    /// Any other indicator value.
+    Other(char),
+}
+
+impl AccessMethod {
+    /// Decode an 856 first indicator.
+    #[must_use]
+    pub fn from_indicator(indicator: char) -> Self {
+        match indicator {
+            ' ' => AccessMethod::Unspecified,
+            '0' => AccessMethod::Email,
+            '1' => AccessMethod::Ftp,
+            '2' => AccessMethod::RemoteLogin,
+            '3' => AccessMethod::DialUp,
+            '4' => AccessMethod::Http,
+            '7' => AccessMethod::InSubfield2,
+            other => AccessMethod::Other(other),
+        }
+    }
+}
+
+/// What the link points to, relative to the described item (856 second
+/// indicator).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Relationship {
+    /// No information provided (blank).
+    Unspecified,
+    /// The resource itself (0).
+    Resource,
+    /// A version of the resource (1).
+    VersionOfResource,
+    /// A related resource, such as a table of contents or review (2).
+    RelatedResource,
+    /// No display constant generated (8).
+    NoDisplayConstant,
+    /// Any other indicator value.
+    Other(char),
+}
+
+impl Relationship {
+    /// Decode an 856 second indicator.
+    #[must_use]
+    pub fn from_indicator(indicator: char) -> Self {
+        match indicator {
+            ' ' => Relationship::Unspecified,
+            '0' => Relationship::Resource,
+            '1' => Relationship::VersionOfResource,
+            '2' => Relationship::RelatedResource,
+            '8' => Relationship::NoDisplayConstant,
+            other => Relationship::Other(other),
+        }
+    }
+}
+
+/// One 856 field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ElectronicLocation {
+    /// Zero-based position among the record's 856 fields.
+    pub occurrence: usize,
+    /// Decoded first indicator.
+    pub access_method: AccessMethod,
+    /// Decoded second indicator.
+    pub relationship: Relationship,
+    /// `$u` URIs.
+    pub urls: Vec<String>,
+    /// `$y` link text.
+    pub link_text: Vec<String>,
+    /// `$z` public notes.
+    pub public_notes: Vec<String>,
+    /// `$3` materials specified.
+    pub materials: Vec<String>,
+}
+
+impl ElectronicLocation {
+    /// Read an 856 field. `occurrence` is recorded as given.
+    #[must_use]
+    pub fn from_field(field: &Field, occurrence: usize) -> Self {
+        let values = |code| {
+            field
+                .get_subfield_values(code)
+                .into_iter()
+                .map(str::to_string)
+                .collect()
+        };
+        ElectronicLocation {
+            occurrence,
+            access_method: AccessMethod::from_indicator(field.indicator1),
+            relationship: Relationship::from_indicator(field.indicator2),
+            urls: values('u'),
+            link_text: values('y'),
+            public_notes: values('z'),
+            materials: values('3'),
+        }
+    }
+}
+
+/// Every 856 of `record`, in field order.
+#[must_use]
+pub fn electronic_locations(record: &Record) -> Vec<ElectronicLocation> {
+    record
+        .fields_by_tag("856")
+        .enumerate()
+        .map(|(occurrence, field)| ElectronicLocation::from_field(field, occurrence))
+        .collect()
+}
+
+/// Why [`normalize_url`] rejected a URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum UrlIssue {
+    /// The value is empty or only whitespace.
+    Empty,
+    /// There is no `scheme:` and the value does not start with `www.`.
+    MissingScheme,
+    /// The scheme contains characters a scheme may not.
+    InvalidScheme(String),
+    /// An `http`, `https` or `ftp` URL has no host.
+    MissingHost,
+}
+
+impl fmt::Display for UrlIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UrlIssue::Empty => write!(f, "empty URL"),
+            UrlIssue::MissingScheme => write!(f, "no scheme"),
+            UrlIssue::InvalidScheme(scheme) => write!(f, "invalid scheme '{scheme}'"),
+            UrlIssue::MissingHost => write!(f, "no host"),
+        }
+    }
+}
+
+/// Check `url` and return its normalized form.
+///
+/// Surrounding whitespace is removed, the scheme and host are lowercased, a
+/// URL starting with `www.` gets `http://`, and characters that cannot
+/// appear in a URL (spaces, quotes, angle brackets, non-ASCII, a `%` not
+/// starting an escape) are percent-encoded. Existing escapes are kept.
+///
+/// # Errors
+///
+/// Returns the [`UrlIssue`] that makes `url` unusable.
+pub fn normalize_url(url: &str) -> Result<String, UrlIssue> {
+    let url = url.trim();
+    if url.is_empty() {
+        return Err(UrlIssue::Empty);
+    }
+    let prefixed;
+    let (scheme, rest) = if url
+        .get(..4)
+        .is_some_and(|head| head.eq_ignore_ascii_case("www."))
+    {
+        prefixed = format!("//{url}");
+        ("http", prefixed.as_str())
+    } else {
+        match url.split_once(':') {
+            Some((scheme, rest)) if !scheme.contains(['/', '?', '#']) => (scheme, rest),
+            _ => return Err(UrlIssue::MissingScheme),
+        }
+    };
+    let valid_scheme = scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    if !valid_scheme {
+        return Err(UrlIssue::InvalidScheme(scheme.to_string()));
+    }
+    let scheme = scheme.to_ascii_lowercase();
+
+    let mut normalized = format!("{scheme}:");
+    match rest.strip_prefix("//") {
+        Some(hierarchical) => {
+            let end = hierarchical
+                .find(['/', '?', '#'])
+                .unwrap_or(hierarchical.len());
+            let (authority, tail) = hierarchical.split_at(end);
+            let (userinfo, host) = match authority.rsplit_once('@') {
+                Some((userinfo, host)) => (Some(userinfo), host),
+                None => (None, authority),
+            };
+            if host.is_empty() && matches!(scheme.as_str(), "http" | "https" | "ftp") {
+                return Err(UrlIssue::MissingHost);
+            }
+            normalized.push_str("//");
+            if let Some(userinfo) = userinfo {
+                encode_into(&mut normalized, userinfo);
+                normalized.push('@');
+            }
+            encode_into(&mut normalized, &host.to_ascii_lowercase());
+            encode_into(&mut normalized, tail);
+        },
+        None if matches!(scheme.as_str(), "http" | "https" | "ftp") => {
+            return Err(UrlIssue::MissingHost);
+        },
+        None => encode_into(&mut normalized, rest),
+    }
+    Ok(normalized)
+}
+
+/// Append `text`, percent-encoding bytes outside the RFC 3986 unreserved
+/// and reserved sets and `%` signs that do not start an escape.
+fn encode_into(out: &mut String, text: &str) {
+    let bytes = text.as_bytes();
+    for (i, &byte) in bytes.iter().enumerate() {
+        let allowed = byte.is_ascii_alphanumeric()
+            || b"-._~:/?#[]@!$&'()*+,;=".contains(&byte)
+            || (byte == b'%'
+                && bytes.get(i + 1).is_some_and(u8::is_ascii_hexdigit)
+                && bytes.get(i + 2).is_some_and(u8::is_ascii_hexdigit));
+        if allowed {
+            out.push(char::from(byte));
+        } else {
+            write!(out, "%{byte:02X}").ok();
+        }
+    }
+}
+
+/// A `$u` value changed by [`normalize_urls`] or [`ProxyRewriter`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UrlChange {
+    /// Zero-based position of the 856 among the record's 856 fields.
+    pub occurrence: usize,
+    /// The value before the change.
+    pub original: String,
+    /// The value after the change.
+    pub updated: String,
+}
+
+/// A `$u` value rejected by [`normalize_url`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UrlProblem {
+    /// Zero-based position of the 856 among the record's 856 fields.
+    pub occurrence: usize,
+    /// The rejected value.
+    pub url: String,
+    /// Why it was rejected.
+    pub issue: UrlIssue,
+}
+
+/// Normalize every 856 `$u` of `record` in place with [`normalize_url`],
+/// returning the values changed. Rejected values are left as they are; see
+/// [`validate_urls`].
+pub fn normalize_urls(record: &mut Record) -> Vec<UrlChange> {
+    rewrite_urls(record, |url| normalize_url(url).ok())
+}
+
+/// The 856 `$u` values of `record` that [`normalize_url`] rejects.
+#[must_use]
+pub fn validate_urls(record: &Record) -> Vec<UrlProblem> {
+    let mut problems = Vec::new();
+    for (occurrence, field) in record.fields_by_tag("856").enumerate() {
+        for url in field.get_subfield_values('u') {
+            if let Err(issue) = normalize_url(url) {
+                problems.push(UrlProblem {
+                    occurrence,
+                    url: url.to_string(),
+                    issue,
+                });
+            }
+        }
+    }
+    problems
+}
+
+/// Apply `rewrite` to every 856 `$u`, recording the values it changes.
+fn rewrite_urls(record: &mut Record, rewrite: impl Fn(&str) -> Option<String>) -> Vec<UrlChange> {
+    let mut changes = Vec::new();
+    let Some(fields) = record.get_fields_mut("856") else {
+        return changes;
+    };
+    for (occurrence, field) in fields.iter_mut().enumerate() {
+        for subfield in field.subfields.iter_mut().filter(|sf| sf.code == 'u') {
+            if let Some(updated) = rewrite(&subfield.value)
+                && updated != subfield.value
+            {
+                let original = std::mem::replace(&mut subfield.value, updated.clone());
+                changes.push(UrlChange {
+                    occurrence,
+                    original,
+                    updated,
+                });
+            }
+        }
+    }
+    changes
+}
+
+/// Rewrites proxy prefixes on 856 `$u` by rule.
+///
+/// Rules are tried in order and the first whose prefix matches a URL
+/// (ignoring ASCII case) replaces that prefix. A rule with an empty
+/// replacement strips the proxy; one with an empty prefix adds a proxy to
+/// every URL it reaches, so put it last.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProxyRewriter {
+    rules: Vec<(String, String)>,
+}
+
+impl ProxyRewriter {
+    /// A rewriter with no rules.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace the prefix `from` with `to`.
+    #[must_use]
+    pub fn rule(mut self, from: &str, to: &str) -> Self {
+        self.rules.push((from.to_string(), to.to_string()));
+        self
+    }
+
+    /// The rewritten form of `url`, or `None` if no rule matches.
+    #[must_use]
+    pub fn rewrite_url(&self, url: &str) -> Option<String> {
+        self.rules.iter().find_map(|(from, to)| {
+            let head = url.get(..from.len())?;
+            if !head.eq_ignore_ascii_case(from) || (from.is_empty() && url.starts_with(to.as_str()))
+            {
+                return None;
+            }
+            Some(format!("{to}{}", &url[from.len()..]))
+        })
+    }
+
+    /// Rewrite every 856 `$u` of `record`, returning the values changed.
+    pub fn rewrite(&self, record: &mut Record) -> Vec<UrlChange> {
+        rewrite_urls(record, |url| self.rewrite_url(url))
+    }
+}
+
+/// Fetches a URL and reports its HTTP status.
+pub trait LinkChecker {
+    /// Error returned when the request cannot be made (DNS failure,
+    /// timeout, refused connection).
+    type Error;
+
+    /// The final HTTP status for `url`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the checker's error if no response was received.
+    fn check(&self, url: &str) -> Result<u16, Self::Error>;
+}
+
+/// Async counterpart of [`LinkChecker`].
+pub trait AsyncLinkChecker {
+    /// Error returned when the request cannot be made.
+    type Error;
+
+    /// The final HTTP status for `url`.
+    fn check(&self, url: &str) -> impl Future<Output = Result<u16, Self::Error>>;
+}
+
+/// The result of checking one `$u`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkStatus<E> {
+    /// Control number (001) of the record, if present.
+    pub record_control_number: Option<String>,
+    /// Zero-based position of the 856 among the record's 856 fields.
+    pub occurrence: usize,
+    /// The URL checked.
+    pub url: String,
+    /// The HTTP status, or the checker's error.
+    pub result: Result<u16, E>,
+}
+
+impl<E> LinkStatus<E> {
+    /// Whether the link failed: no response, or a 4xx or 5xx status.
+    #[must_use]
+    pub fn is_broken(&self) -> bool {
+        !self.result.as_ref().is_ok_and(|status| *status < 400)
+    }
+}
+
+/// The `$u` values to check, with their 856 occurrence.
+fn links(record: &Record) -> Vec<(usize, &str)> {
+    record
+        .fields_by_tag("856")
+        .enumerate()
+        .flat_map(|(occurrence, field)| {
+            field
+                .get_subfield_values('u')
+                .into_iter()
+                .map(move |url| (occurrence, url))
+        })
+        .collect()
+}
+
+/// Check every 856 `$u` of `record` with `checker`. Checker errors are
+/// recorded per link rather than stopping the run.
+pub fn check_links<C: LinkChecker>(record: &Record, checker: &C) -> Vec<LinkStatus<C::Error>> {
+    let control_number = record.get_control_field("001").map(str::to_string);
+    links(record)
+        .into_iter()
+        .map(|(occurrence, url)| LinkStatus {
+            record_control_number: control_number.clone(),
+            occurrence,
+            url: url.to_string(),
+            result: checker.check(url),
+        })
+        .collect()
+}
+
+/// Async version of [`check_links`]. Links are checked one at a time, in
+/// field order.
+pub async fn check_links_async<C: AsyncLinkChecker>(
+    record: &Record,
+    checker: &C,
+) -> Vec<LinkStatus<C::Error>> {
+    let control_number = record.get_control_field("001").map(str::to_string);
+    let mut statuses = Vec::new();
+    for (occurrence, url) in links(record) {
+        statuses.push(LinkStatus {
+            record_control_number: control_number.clone(),
+            occurrence,
+            url: url.to_string(),
+            result: checker.check(url).await,
+        });
+    }
+    statuses
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::leader::Leader;
+
+    fn record(urls: &[&str]) -> Record {
+        let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+        record.add_control_field_str("001", "r1");
+        for url in urls {
+            record.add_field(
+                Field::builder("856".to_string(), '4', '1')
+                    .subfield_str('3', "Preview")
+                    .subfield_str('u', url)
+                    .build(),
+            );
+        }
+        record
+    }
+
+    #[test]
+    fn test_normalize_url() {
+        assert_eq!(
+            normalize_url(" HTTPS://Example.ORG/Path?q=a b&x=\"y\"#Top "),
+            Ok("https://example.org/Path?q=a%20b&x=%22y%22#Top".to_string())
+        );
+        assert_eq!(
+            normalize_url("www.example.org/caf\u{e9}%2F100%"),
+            Ok("http://www.example.org/caf%C3%A9%2F100%25".to_string())
+        );
+        assert_eq!(
+            normalize_url("mailto:Ref@Example.org"),
+            Ok("mailto:Ref@Example.org".to_string())
+        );
+        assert_eq!(normalize_url("  "), Err(UrlIssue::Empty));
+        assert_eq!(normalize_url("example.org/x"), Err(UrlIssue::MissingScheme));
+        assert_eq!(normalize_url("http:/x"), Err(UrlIssue::MissingHost));
+        assert_eq!(
+            normalize_url("1ttp://x"),
+            Err(UrlIssue::InvalidScheme("1ttp".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_locations_normalize_and_validate() {
+        let mut record = record(&["HTTP://A.example/x", "http://b.example/ok", "not a url"]);
+        let locations = electronic_locations(&record);
+        assert_eq!(locations.len(), 3);
+        assert_eq!(locations[0].access_method, AccessMethod::Http);
+        assert_eq!(locations[0].relationship, Relationship::VersionOfResource);
+        assert_eq!(locations[2].materials, vec!["Preview"]);
+
+        let changes = normalize_urls(&mut record);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].updated, "http://a.example/x");
+        let problems = validate_urls(&record);
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].occurrence, 2);
+        assert_eq!(problems[0].issue, UrlIssue::MissingScheme);
+    }
+
+    #[test]
+    fn test_proxy_rules() {
+        let proxy = "https://proxy.example.edu/login?url=";
+        let strip = ProxyRewriter::new().rule(proxy, "");
+        let add = ProxyRewriter::new().rule("", proxy);
+
+        let mut record = record(&["HTTPS://proxy.example.edu/login?url=http://x.example/1"]);
+        assert_eq!(strip.rewrite(&mut record).len(), 1);
+        assert_eq!(
+            electronic_locations(&record)[0].urls,
+            vec!["http://x.example/1"]
+        );
+        add.rewrite(&mut record);
+        let url = electronic_locations(&record)[0].urls[0].clone();
+        assert_eq!(url, format!("{proxy}http://x.example/1"));
+        // Adding is idempotent.
+        assert!(add.rewrite(&mut record).is_empty());
+    }
+
+    struct Fixed;
+
+    impl LinkChecker for Fixed {
+        type Error = String;
+
+        fn check(&self, url: &str) -> Result<u16, String> {
+            match url {
+                "http://ok.example/" => Ok(200),
+                "http://gone.example/" => Ok(404),
+                _ => Err("timeout".to_string()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_check_links() {
+        let record = record(&[
+            "http://ok.example/",
+            "http://gone.example/",
+            "http://slow.example/",
+        ]);
+        let statuses = check_links(&record, &Fixed);
+        let broken: Vec<bool> = statuses.iter().map(LinkStatus::is_broken).collect();
+        assert_eq!(broken, [false, true, true]);
+        assert_eq!(statuses[1].record_control_number.as_deref(), Some("r1"));
+        assert_eq!(statuses[2].result, Err("timeout".to_string()));
+    }
+}
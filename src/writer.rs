@@ -43,15 +43,104 @@
 //! ```
 
 use crate::error::{MarcError, Result};
+use crate::fidelity::{SourceRecord, SourceSlot};
 use crate::formats::FormatWriter;
-use crate::iso2709::{check_directory_field_length, push_zero_padded, validate_directory_tag};
-use crate::record::Record;
+use crate::iso2709::{
+    ISO2709_MAX_FIELD, check_directory_field_length, push_zero_padded, validate_directory_tag,
+};
+use crate::record::{Field, Record};
 use std::io::Write;
 
 const FIELD_TERMINATOR: u8 = 0x1E;
 const SUBFIELD_DELIMITER: u8 = 0x1F;
 const RECORD_TERMINATOR: u8 = 0x1D;
 
+/// How strictly [`MarcWriter`] holds its output to the ISO 2709 and MARC 21
+/// structure.
+///
+/// Both modes refuse what ISO 2709 cannot represent at all: tags that are
+/// not three bytes, fields over 9999 bytes and records over 99999 bytes.
+/// [`Strict`](Conformance::Strict) also refuses output that is representable
+/// but that conforming readers may reject.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Conformance {
+    /// Fields in record order; only unrepresentable records are errors
+    /// (the default).
+    #[default]
+    Standard,
+    /// Directory entries sorted by tag (control fields first, record order
+    /// kept within a tag), and records rejected unless the leader's
+    /// indicator count and subfield code count are 2 with entry map
+    /// `4500`, tags are alphanumeric, indicators are blank, digits or
+    /// lowercase letters, subfield codes are digits or lowercase letters,
+    /// and no value contains a delimiter or terminator byte. Lossless
+    /// source bytes are not reused, so every field is checked.
+    Strict,
+}
+
+/// What [`MarcWriter`] does with a record longer than the 99999 bytes an
+/// ISO 2709 leader can describe.
+#[derive(Debug, Default)]
+pub enum Oversize {
+    /// Return an error, leaving the output unchanged (the default).
+    #[default]
+    Error,
+    /// Drop data fields matching these tag patterns (`"9XX"`, `"505"`,
+    /// `"880"`) until the record fits. Patterns are tried in order, and
+    /// within a pattern the last matching field goes first. If the record
+    /// still does not fit, the original error is returned.
+    DropFields(Vec<String>),
+    /// Shorten subfield values in data fields matching these tag patterns
+    /// (`"5XX"`, `"880"`) until the record fits. The longest values are cut
+    /// first, each to the same length, so short notes survive whole; linkage
+    /// subfields (`$6`, `$8`) are never cut. If emptying every such value
+    /// is not enough, the original error is returned.
+    TruncateFields(Vec<String>),
+    /// Write the record to this writer instead (for example a
+    /// [`MarcxmlWriter`](crate::marcxml::MarcxmlWriter), which has no size
+    /// limit), leaving it out of the ISO 2709 output. The side writer is
+    /// finished with this one.
+    SideChannel(Box<dyn FormatWriter + Send>),
+}
+
+/// What was done with one oversized record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OversizeAction {
+    /// These fields (tags, in the order dropped) were removed and the
+    /// record written at `length` bytes.
+    Dropped {
+        /// Tags of the dropped fields
+        tags: Vec<String>,
+        /// Length of the record as written
+        length: usize,
+    },
+    /// Subfield values in these fields (tags, in record order) were
+    /// shortened and the record written at `length` bytes.
+    Truncated {
+        /// Tags of the shortened fields
+        tags: Vec<String>,
+        /// Length of the record as written
+        length: usize,
+    },
+    /// The record was written to the [`Oversize::SideChannel`] writer.
+    Diverted,
+}
+
+/// An oversized record handled by [`Oversize::DropFields`],
+/// [`Oversize::TruncateFields`] or [`Oversize::SideChannel`]; see
+/// [`MarcWriter::oversize_report`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OversizeEvent {
+    /// 1-based position of the record among those passed to the writer
+    pub record_index: usize,
+    /// The record's 001, if present
+    pub control_number: Option<String>,
+    /// Serialized length the record would have had, in bytes
+    pub length: usize,
+    /// What was done with it
+    pub action: OversizeAction,
+}
+
 /// Writer for ISO 2709 binary MARC format.
 ///
 /// `MarcWriter` serializes [`Record`] instances to ISO 2709 binary format.
@@ -84,6 +173,11 @@ pub struct MarcWriter<W: Write> {
     data_area: Vec<u8>,
     directory: Vec<u8>,
     leader_buf: Vec<u8>,
+    conformance: Conformance,
+    oversize: Oversize,
+    oversize_report: Vec<OversizeEvent>,
+    records_seen: usize,
+    stats: Option<crate::stats::Stats>,
 }
 
 impl<W: Write> MarcWriter<W> {
@@ -108,9 +202,90 @@ impl<W: Write> MarcWriter<W> {
             data_area: Vec::new(),
             directory: Vec::new(),
             leader_buf: Vec::with_capacity(24),
+            conformance: Conformance::Standard,
+            oversize: Oversize::Error,
+            oversize_report: Vec::new(),
+            records_seen: 0,
+            stats: None,
         }
     }
 
+    /// Set how strictly output is held to the ISO 2709 and MARC 21
+    /// structure; see [`Conformance`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mrrc::writer::Conformance;
+    /// use mrrc::{Field, Leader, MarcWriter, Record};
+    ///
+    /// let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+    /// record.add_field(Field::builder("245".to_string(), '1', '0').subfield_str('a', "Title").build());
+    /// record.add_field(Field::builder("100".to_string(), '1', ' ').subfield_str('a', "Author").build());
+    ///
+    /// let mut buffer = Vec::new();
+    /// MarcWriter::new(&mut buffer)
+    ///     .with_conformance(Conformance::Strict)
+    ///     .write_record(&record)?;
+    /// // The 100 now precedes the 245 in the directory.
+    /// assert_eq!(&buffer[24..27], b"100");
+    ///
+    /// record.add_field(Field::builder("500".to_string(), 'X', ' ').subfield_str('a', "Note").build());
+    /// assert!(MarcWriter::new(Vec::new())
+    ///     .with_conformance(Conformance::Strict)
+    ///     .write_record(&record)
+    ///     .is_err());
+    /// # Ok::<(), mrrc::MarcError>(())
+    /// ```
+    #[must_use]
+    pub fn with_conformance(mut self, conformance: Conformance) -> Self {
+        self.conformance = conformance;
+        self
+    }
+
+    /// Set what happens to records too long for ISO 2709; see [`Oversize`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mrrc::writer::{Oversize, OversizeAction};
+    /// use mrrc::{Field, Leader, MarcWriter, Record};
+    ///
+    /// let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+    /// record.add_field(Field::builder("245".to_string(), '1', '0').subfield_str('a', "Title").build());
+    /// for _ in 0..12 {
+    ///     let mut local = Field::new("949".to_string(), ' ', ' ');
+    ///     local.add_subfield_str('a', &"x".repeat(9000));
+    ///     record.add_field(local);
+    /// }
+    ///
+    /// let mut writer = MarcWriter::new(Vec::new())
+    ///     .with_oversize(Oversize::DropFields(vec!["9XX".to_string()]));
+    /// writer.write_record(&record)?;
+    /// let event = &writer.oversize_report()[0];
+    /// assert!(matches!(&event.action, OversizeAction::Dropped { tags, .. } if tags.len() == 1));
+    /// # Ok::<(), mrrc::MarcError>(())
+    /// ```
+    #[must_use]
+    pub fn with_oversize(mut self, oversize: Oversize) -> Self {
+        self.oversize = oversize;
+        self
+    }
+
+    /// Oversized records handled so far by [`Oversize::DropFields`],
+    /// [`Oversize::TruncateFields`] or [`Oversize::SideChannel`], in order.
+    #[must_use]
+    pub fn oversize_report(&self) -> &[OversizeEvent] {
+        &self.oversize_report
+    }
+
+    /// Count records written, bytes written and write errors in `stats`.
+    #[must_use]
+    pub fn with_stats(mut self, stats: crate::stats::Stats) -> Self {
+        self.stats = Some(stats);
+        self
+    }
+
     /// Write a single MARC record.
     ///
     /// Serializes the record to ISO 2709 binary format and writes it to the
@@ -145,19 +320,101 @@ impl<W: Write> MarcWriter<W> {
     /// - The record structure is invalid
     /// - An I/O error occurs during writing
     pub fn write_record(&mut self, record: &Record) -> Result<()> {
+        self.write(record, None)
+    }
+
+    /// Write a record read with
+    /// [`MarcReader::read_lossless`](crate::MarcReader::read_lossless),
+    /// reproducing the bytes it was read from.
+    ///
+    /// An unchanged record is written back byte for byte; a changed one in
+    /// its original field order, with unchanged fields copied from `source`.
+    /// See [`crate::fidelity`]. Under [`Conformance::Strict`], or when the
+    /// record is too long and an [`Oversize`] policy applies, the source is
+    /// ignored and the record is written as by [`Self::write_record`].
+    ///
+    /// # Errors
+    ///
+    /// As for [`Self::write_record`].
+    pub fn write_lossless(&mut self, record: &Record, source: &SourceRecord) -> Result<()> {
+        self.write(record, Some(source))
+    }
+
+    fn write(&mut self, record: &Record, source: Option<&SourceRecord>) -> Result<()> {
         if self.finished {
             return Err(MarcError::WriterError {
                 record_index: None,
                 record_control_number: None,
+                field_tag: None,
                 message: "Cannot write to a finished writer".to_string(),
             });
         }
 
+        self.records_seen += 1;
+        let mut result = None;
+        if !matches!(self.oversize, Oversize::Error) {
+            let (base_address, length) = iso2709_lengths(record);
+            if length > ISO2709_MAX_FIELD || base_address > ISO2709_MAX_FIELD {
+                result = Some(self.write_oversized(record, length));
+            }
+        }
+        let result = result.unwrap_or_else(|| self.encode_record(record, source));
+        if let (Err(_), Some(stats)) = (&result, &self.stats) {
+            stats.add_error();
+        }
+        result
+    }
+
+    /// Drop fields from, shorten or divert a record longer than ISO 2709
+    /// allows.
+    fn write_oversized(&mut self, record: &Record, length: usize) -> Result<()> {
+        let control_number = crate::RecordHelpers::control_number(record).map(String::from);
+        let action = match &mut self.oversize {
+            Oversize::Error => return self.encode_record(record, None),
+            Oversize::SideChannel(side) => {
+                side.write_record(record)?;
+                OversizeAction::Diverted
+            },
+            Oversize::DropFields(patterns) => {
+                let Some((reduced, tags)) = drop_until_fits(record, patterns) else {
+                    // Nothing left to drop: report the size error as usual.
+                    return self.encode_record(record, None);
+                };
+                self.encode_record(&reduced, None)?;
+                OversizeAction::Dropped {
+                    tags,
+                    length: iso2709_lengths(&reduced).1,
+                }
+            },
+            Oversize::TruncateFields(patterns) => {
+                let Some((reduced, tags)) = truncate_until_fits(record, patterns) else {
+                    return self.encode_record(record, None);
+                };
+                self.encode_record(&reduced, None)?;
+                OversizeAction::Truncated {
+                    tags,
+                    length: iso2709_lengths(&reduced).1,
+                }
+            },
+        };
+        self.oversize_report.push(OversizeEvent {
+            record_index: self.records_seen,
+            control_number,
+            length,
+            action,
+        });
+        Ok(())
+    }
+
+    /// Serialize `record` and write it to the underlying writer, reusing
+    /// the bytes of `source` where they still hold.
+    fn encode_record(&mut self, record: &Record, source: Option<&SourceRecord>) -> Result<()> {
         // Snapshot the 1-based output index up front for error context. The
         // 001 control number is fetched lazily (`rcn()`) only on the error
         // paths that need it, so the happy path does not allocate a String
         // per record just to leave it unused.
         let record_index = Some(self.records_written.saturating_add(1));
+        crate::telemetry::stage_span!("mrrc.encode", record_index);
         let rcn = || crate::RecordHelpers::control_number(record).map(String::from);
 
         // Reuse the per-writer scratch buffers across records: clear keeps the
@@ -167,60 +424,49 @@ impl<W: Write> MarcWriter<W> {
         let directory = &mut self.directory;
         data_area.clear();
         directory.clear();
-        let mut current_position = 0;
-
-        // Write control fields first (001-009)
-        for (tag, values) in &record.control_fields {
-            if tag.as_str() < "010" {
-                for value in values {
-                    validate_directory_tag(tag, record_index, rcn().as_deref())?;
-                    let field_data = value.as_bytes();
-                    let field_length = field_data.len() + 1; // +1 for terminator
-                    check_directory_field_length(
-                        tag,
-                        field_length,
-                        record_index,
-                        rcn().as_deref(),
-                    )?;
-
-                    // Add directory entry
-                    directory.extend_from_slice(tag.as_bytes());
-                    push_zero_padded(directory, field_length, 4);
-                    push_zero_padded(directory, current_position, 5);
-
-                    // Add data
-                    data_area.extend_from_slice(field_data);
-                    data_area.push(FIELD_TERMINATOR);
-                    current_position += field_length;
+
+        let source = source.filter(|_| self.conformance == Conformance::Standard);
+        if self.conformance == Conformance::Strict {
+            encode_strict(data_area, directory, record, record_index, &rcn)?;
+        } else if let Some(source) = source {
+            // An unchanged record goes out exactly as it came in.
+            if source.is_unchanged(record) {
+                self.writer.write_all(source.bytes())?;
+                if let Some(stats) = &self.stats {
+                    stats.add_record(source.bytes().len() as u64);
                 }
+                self.records_written += 1;
+                crate::telemetry::records_written(1);
+                return Ok(());
             }
-        }
-
-        // Write data fields (010+). Serialize each field straight into the
-        // shared data area and recover its length from the buffer's growth,
-        // rather than building it in a fresh per-field `Vec` and copying it in.
-        for (tag, fields) in &record.fields {
-            for field in fields {
-                validate_directory_tag(tag, record_index, rcn().as_deref())?;
-                let field_start = data_area.len();
-                data_area.push(field.indicator1 as u8);
-                data_area.push(field.indicator2 as u8);
-
-                for subfield in &field.subfields {
-                    data_area.push(SUBFIELD_DELIMITER);
-                    data_area.push(subfield.code as u8);
-                    data_area.extend_from_slice(subfield.value.as_bytes());
+            source.for_each_slot(record, |slot| match slot {
+                SourceSlot::Raw(tag, bytes) => {
+                    push_directory_entry(directory, tag, bytes.len(), data_area.len());
+                    data_area.extend_from_slice(bytes);
+                    Ok(())
+                },
+                SourceSlot::Control(tag, value) => {
+                    encode_control_field(data_area, directory, tag, value, record_index, &rcn)
+                },
+                SourceSlot::Data(tag, field) => {
+                    encode_data_field(data_area, directory, tag, field, record_index, &rcn)
+                },
+            })?;
+        } else {
+            // Write control fields first (001-009)
+            for (tag, values) in &record.control_fields {
+                if tag.as_str() < "010" {
+                    for value in values {
+                        encode_control_field(data_area, directory, tag, value, record_index, &rcn)?;
+                    }
                 }
+            }
 
-                data_area.push(FIELD_TERMINATOR);
-                let field_length = data_area.len() - field_start;
-                check_directory_field_length(tag, field_length, record_index, rcn().as_deref())?;
-
-                // Add directory entry
-                directory.extend_from_slice(tag.as_bytes());
-                push_zero_padded(directory, field_length, 4);
-                push_zero_padded(directory, current_position, 5);
-                current_position += field_length;
+            // Write data fields (010+)
+            for (tag, fields) in &record.fields {
+                for field in fields {
+                    encode_data_field(data_area, directory, tag, field, record_index, &rcn)?;
+                }
             }
         }
 
@@ -253,12 +499,14 @@ impl<W: Write> MarcWriter<W> {
             u32::try_from(record_length).map_err(|_| MarcError::WriterError {
                 record_index,
                 record_control_number: rcn(),
+                field_tag: None,
                 message: format!("Record length exceeds 4GB limit ({record_length} bytes)"),
             })?;
         leader.data_base_address =
             u32::try_from(base_address).map_err(|_| MarcError::WriterError {
                 record_index,
                 record_control_number: rcn(),
+                field_tag: None,
                 message: format!("Base address exceeds 4GB limit ({base_address} bytes)"),
             })?;
 
@@ -272,7 +520,11 @@ impl<W: Write> MarcWriter<W> {
         self.writer.write_all(data_area)?;
         self.writer.write_all(&[RECORD_TERMINATOR])?;
 
+        if let Some(stats) = &self.stats {
+            stats.add_record(record_length as u64);
+        }
         self.records_written += 1;
+        crate::telemetry::records_written(1);
         Ok(())
     }
 
@@ -284,6 +536,9 @@ impl<W: Write> MarcWriter<W> {
     ///
     /// Returns an error if flushing the underlying writer fails.
     pub fn finish(&mut self) -> Result<()> {
+        if let Oversize::SideChannel(side) = &mut self.oversize {
+            side.finish()?;
+        }
         self.writer.flush()?;
         self.finished = true;
         Ok(())
@@ -296,6 +551,325 @@ impl<W: Write> MarcWriter<W> {
     }
 }
 
+/// Base address and total length `record` would have in ISO 2709, without
+/// serializing it.
+fn iso2709_lengths(record: &Record) -> (usize, usize) {
+    let mut entries = 0;
+    let mut data = 0;
+    for (tag, values) in &record.control_fields {
+        if tag.as_str() < "010" {
+            entries += values.len();
+            data += values.iter().map(|value| value.len() + 1).sum::<usize>();
+        }
+    }
+    for field in record.fields() {
+        entries += 1;
+        data += field_length(field);
+    }
+    let base_address = 24 + entries * 12 + 1;
+    (base_address, base_address + data + 1)
+}
+
+/// Serialized length of a data field, including indicators and terminator.
+fn field_length(field: &Field) -> usize {
+    3 + field
+        .subfields
+        .iter()
+        .map(|subfield| 2 + subfield.value.len())
+        .sum::<usize>()
+}
+
+/// A copy of `record` with fields matching `patterns` removed, in priority
+/// order, until it fits in ISO 2709, and the tags removed; `None` if
+/// removing every matching field is not enough.
+fn drop_until_fits(record: &Record, patterns: &[String]) -> Option<(Record, Vec<String>)> {
+    let fits = |(base_address, length): (usize, usize)| {
+        length <= ISO2709_MAX_FIELD && base_address <= ISO2709_MAX_FIELD
+    };
+    let (mut base_address, mut length) = iso2709_lengths(record);
+    let mut reduced = record.clone();
+    let mut dropped = Vec::new();
+    for pattern in patterns {
+        let mut tags: Vec<String> = reduced
+            .fields
+            .keys()
+            .filter(|tag| crate::field_protection::tag_matches(pattern, tag))
+            .cloned()
+            .collect();
+        while let Some(tag) = tags.pop() {
+            while !fits((base_address, length)) {
+                let Some(fields) = reduced.fields.get_mut(&tag) else {
+                    break;
+                };
+                let Some(field) = fields.pop() else {
+                    break;
+                };
+                if fields.is_empty() {
+                    reduced.fields.shift_remove(&tag);
+                }
+                base_address -= 12;
+                length -= 12 + field_length(&field);
+                dropped.push(tag.clone());
+            }
+        }
+        if fits((base_address, length)) {
+            return Some((reduced, dropped));
+        }
+    }
+    None
+}
+
+/// A copy of `record` with the subfield values of fields matching `patterns`
+/// cut to the longest common length that lets it fit in ISO 2709, and the
+/// tags of the shortened fields; `None` if emptying every such value is not
+/// enough. Linkage subfields (`$6`, `$8`) are left whole.
+fn truncate_until_fits(record: &Record, patterns: &[String]) -> Option<(Record, Vec<String>)> {
+    let (base_address, length) = iso2709_lengths(record);
+    if base_address > ISO2709_MAX_FIELD {
+        return None;
+    }
+    let excess = length.saturating_sub(ISO2709_MAX_FIELD);
+    let truncatable = |tag: &str, code: char| {
+        !matches!(code, '6' | '8')
+            && patterns
+                .iter()
+                .any(|pattern| crate::field_protection::tag_matches(pattern, tag))
+    };
+    let values: Vec<&str> = record
+        .fields()
+        .flat_map(|field| {
+            field
+                .subfields
+                .iter()
+                .filter(|subfield| truncatable(&field.tag, subfield.code))
+                .map(|subfield| subfield.value.as_str())
+        })
+        .collect();
+    let saved = |cap: usize| {
+        values
+            .iter()
+            .map(|value| value.len() - cut_point(value, cap))
+            .sum::<usize>()
+    };
+    if saved(0) < excess {
+        return None;
+    }
+
+    // The largest cap that still saves enough; `saved` only falls as the
+    // cap grows.
+    let (mut low, mut high) = (0, values.iter().map(|value| value.len()).max()?);
+    while low < high {
+        let mid = (low + high).div_ceil(2);
+        if saved(mid) >= excess {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+
+    let mut reduced = record.clone();
+    let mut tags = Vec::new();
+    for field in reduced.fields_mut() {
+        let mut shortened = false;
+        for subfield in &mut field.subfields {
+            if truncatable(&field.tag, subfield.code) && subfield.value.len() > low {
+                let end = cut_point(&subfield.value, low);
+                subfield.value.truncate(end);
+                shortened = true;
+            }
+        }
+        if shortened {
+            tags.push(field.tag.clone());
+        }
+    }
+    Some((reduced, tags))
+}
+
+/// The largest char boundary of `value` at or below `cap` bytes.
+fn cut_point(value: &str, cap: usize) -> usize {
+    if cap >= value.len() {
+        return value.len();
+    }
+    (0..=cap)
+        .rev()
+        .find(|&i| value.is_char_boundary(i))
+        .unwrap_or(0)
+}
+
+/// Encode every field of `record` in tag order, checking each one under
+/// [`Conformance::Strict`].
+fn encode_strict(
+    data_area: &mut Vec<u8>,
+    directory: &mut Vec<u8>,
+    record: &Record,
+    record_index: Option<usize>,
+    rcn: &impl Fn() -> Option<String>,
+) -> Result<()> {
+    check_strict_leader(record).map_err(|message| MarcError::WriterError {
+        record_index,
+        record_control_number: rcn(),
+        field_tag: None,
+        message,
+    })?;
+    let mut control_tags: Vec<&String> = record
+        .control_fields
+        .keys()
+        .filter(|tag| tag.as_str() < "010")
+        .collect();
+    control_tags.sort();
+    for tag in control_tags {
+        for value in &record.control_fields[tag.as_str()] {
+            check_strict_value(tag, value)
+                .map_err(|message| strict_error(tag, message, record_index, rcn))?;
+            encode_control_field(data_area, directory, tag, value, record_index, rcn)?;
+        }
+    }
+    let mut data_tags: Vec<&String> = record.fields.keys().collect();
+    data_tags.sort();
+    for tag in data_tags {
+        for field in &record.fields[tag.as_str()] {
+            check_strict_field(tag, field)
+                .map_err(|message| strict_error(tag, message, record_index, rcn))?;
+            encode_data_field(data_area, directory, tag, field, record_index, rcn)?;
+        }
+    }
+    Ok(())
+}
+
+/// Wrap a [`Conformance::Strict`] violation in field `tag`.
+fn strict_error(
+    tag: &str,
+    message: String,
+    record_index: Option<usize>,
+    rcn: &impl Fn() -> Option<String>,
+) -> MarcError {
+    MarcError::WriterError {
+        record_index,
+        record_control_number: rcn(),
+        field_tag: Some(tag.to_string()),
+        message,
+    }
+}
+
+/// Leader checks for [`Conformance::Strict`].
+fn check_strict_leader(record: &Record) -> std::result::Result<(), String> {
+    let leader = &record.leader;
+    if leader.indicator_count != 2 || leader.subfield_code_count != 2 {
+        return Err(format!(
+            "Leader indicator count and subfield code count must be 2 (got {} and {})",
+            leader.indicator_count, leader.subfield_code_count
+        ));
+    }
+    if leader.reserved != "4500" {
+        return Err(format!(
+            "Leader entry map must be \"4500\" (got {:?})",
+            leader.reserved
+        ));
+    }
+    Ok(())
+}
+
+/// Tag and value checks shared by control and data fields under
+/// [`Conformance::Strict`].
+fn check_strict_value(tag: &str, value: &str) -> std::result::Result<(), String> {
+    if !tag.bytes().all(|b| b.is_ascii_alphanumeric()) {
+        return Err(format!("Field tag {tag:?} is not alphanumeric"));
+    }
+    if let Some(b) = value.bytes().find(|b| {
+        matches!(
+            *b,
+            SUBFIELD_DELIMITER | FIELD_TERMINATOR | RECORD_TERMINATOR
+        )
+    }) {
+        return Err(format!(
+            "Field {tag} contains a structural byte 0x{b:02X} in its data"
+        ));
+    }
+    Ok(())
+}
+
+/// Indicator, subfield code and value checks for a data field under
+/// [`Conformance::Strict`].
+fn check_strict_field(tag: &str, field: &Field) -> std::result::Result<(), String> {
+    check_strict_value(tag, "")?;
+    for (position, indicator) in [(1, field.indicator1), (2, field.indicator2)] {
+        if !(indicator == ' ' || indicator.is_ascii_digit() || indicator.is_ascii_lowercase()) {
+            return Err(format!(
+                "Field {tag} indicator {position} {indicator:?} is not blank, a digit or a lowercase letter"
+            ));
+        }
+    }
+    for subfield in &field.subfields {
+        if !(subfield.code.is_ascii_digit() || subfield.code.is_ascii_lowercase()) {
+            return Err(format!(
+                "Field {tag} subfield code {:?} is not a digit or a lowercase letter",
+                subfield.code
+            ));
+        }
+        check_strict_value(tag, &subfield.value)?;
+    }
+    Ok(())
+}
+
+/// Append a directory entry for a field of `length` bytes at `position` in
+/// the data area.
+fn push_directory_entry(directory: &mut Vec<u8>, tag: &str, length: usize, position: usize) {
+    directory.extend_from_slice(tag.as_bytes());
+    push_zero_padded(directory, length, 4);
+    push_zero_padded(directory, position, 5);
+}
+
+/// Encode a control field (001-009) and its directory entry.
+fn encode_control_field(
+    data_area: &mut Vec<u8>,
+    directory: &mut Vec<u8>,
+    tag: &str,
+    value: &str,
+    record_index: Option<usize>,
+    rcn: &impl Fn() -> Option<String>,
+) -> Result<()> {
+    validate_directory_tag(tag, record_index, rcn().as_deref())?;
+    let field_data = value.as_bytes();
+    let field_length = field_data.len() + 1; // +1 for terminator
+    check_directory_field_length(tag, field_length, record_index, rcn().as_deref())?;
+
+    push_directory_entry(directory, tag, field_length, data_area.len());
+    data_area.extend_from_slice(field_data);
+    data_area.push(FIELD_TERMINATOR);
+    Ok(())
+}
+
+/// Encode a data field (010+) and its directory entry. The field is
+/// serialized straight into the shared data area and its length recovered
+/// from the buffer's growth, rather than built in a fresh per-field `Vec`
+/// and copied in.
+fn encode_data_field(
+    data_area: &mut Vec<u8>,
+    directory: &mut Vec<u8>,
+    tag: &str,
+    field: &Field,
+    record_index: Option<usize>,
+    rcn: &impl Fn() -> Option<String>,
+) -> Result<()> {
+    validate_directory_tag(tag, record_index, rcn().as_deref())?;
+    let field_start = data_area.len();
+    data_area.push(field.indicator1 as u8);
+    data_area.push(field.indicator2 as u8);
+
+    for subfield in &field.subfields {
+        data_area.push(SUBFIELD_DELIMITER);
+        data_area.push(subfield.code as u8);
+        data_area.extend_from_slice(subfield.value.as_bytes());
+    }
+
+    data_area.push(FIELD_TERMINATOR);
+    let field_length = data_area.len() - field_start;
+    check_directory_field_length(tag, field_length, record_index, rcn().as_deref())?;
+
+    push_directory_entry(directory, tag, field_length, field_start);
+    Ok(())
+}
+
 // Implement the FormatWriter trait for MarcWriter
 impl<W: Write + std::fmt::Debug> FormatWriter for MarcWriter<W> {
     fn write_record(&mut self, record: &Record) -> Result<()> {
@@ -585,4 +1159,210 @@ mod tests {
         let fields = read.get_fields("245").unwrap();
         assert_eq!(fields[0].get_subfield('a'), Some(value.as_str()));
     }
+
+    #[test]
+    fn test_strict_conformance_sorts_directory() {
+        use crate::reader::MarcReader;
+        let mut record = Record::new(make_test_leader());
+        record.add_control_field("008".to_string(), "x".repeat(40));
+        record.add_control_field("001".to_string(), "id1".to_string());
+        for tag in ["650", "245", "650", "100"] {
+            let mut field = Field::new(tag.to_string(), ' ', '0');
+            field.add_subfield('a', format!("{tag} value"));
+            record.add_field(field);
+        }
+
+        let mut buffer = Vec::new();
+        MarcWriter::new(&mut buffer)
+            .with_conformance(Conformance::Strict)
+            .write_record(&record)
+            .unwrap();
+        let base = std::str::from_utf8(&buffer[12..17])
+            .unwrap()
+            .parse::<usize>()
+            .unwrap();
+        let tags: Vec<&str> = buffer[24..base - 1]
+            .chunks(12)
+            .map(|entry| std::str::from_utf8(&entry[..3]).unwrap())
+            .collect();
+        assert_eq!(tags, ["001", "008", "100", "245", "650", "650"]);
+        let read = MarcReader::new(Cursor::new(buffer))
+            .read_record()
+            .unwrap()
+            .unwrap();
+        assert_eq!(read.get_fields("650").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_strict_conformance_rejects_invalid_characters() {
+        let strict = |record: &Record| {
+            MarcWriter::new(Vec::new())
+                .with_conformance(Conformance::Strict)
+                .write_record(record)
+        };
+        let with_field = |indicator: char, code: char, value: &str| {
+            let mut record = Record::new(make_test_leader());
+            let mut field = Field::new("500".to_string(), indicator, ' ');
+            field.add_subfield(code, value.to_string());
+            record.add_field(field);
+            record
+        };
+
+        assert!(strict(&with_field('1', 'a', "Fine")).is_ok());
+        for record in [
+            with_field('A', 'a', "Uppercase indicator"),
+            with_field('1', '$', "Bad code"),
+            with_field('1', 'a', "Embedded \u{1e} terminator"),
+        ] {
+            let err = strict(&record).unwrap_err();
+            assert!(
+                matches!(&err, MarcError::WriterError { field_tag: Some(tag), .. } if tag == "500"),
+                "{err}"
+            );
+            // Standard mode writes them as before.
+            assert!(MarcWriter::new(Vec::new()).write_record(&record).is_ok());
+        }
+
+        let mut record = with_field('1', 'a', "Fine");
+        record.leader.reserved = "4600".to_string();
+        assert!(strict(&record).is_err());
+    }
+
+    fn oversized() -> Record {
+        let mut record = Record::new(make_test_leader());
+        record.add_control_field_str("001", "big1");
+        let mut title = Field::new("245".to_string(), '1', '0');
+        title.add_subfield('a', "Large".to_string());
+        record.add_field(title);
+        for tag in ["505", "949"].repeat(6) {
+            let mut field = Field::new(tag.to_string(), ' ', ' ');
+            field.add_subfield('a', "x".repeat(9000));
+            record.add_field(field);
+        }
+        record
+    }
+
+    #[test]
+    fn test_oversize_lengths_match_encoder() {
+        let mut record = oversized();
+        record.fields.shift_remove("949");
+        let mut bytes = Vec::new();
+        MarcWriter::new(&mut bytes).write_record(&record).unwrap();
+        let length: usize = std::str::from_utf8(&bytes[..5]).unwrap().parse().unwrap();
+        assert_eq!(length, bytes.len());
+        assert_eq!(iso2709_lengths(&record).1, bytes.len());
+    }
+
+    #[test]
+    fn test_oversize_drop_fields() {
+        let record = oversized();
+        assert!(MarcWriter::new(Vec::new()).write_record(&record).is_err());
+
+        let mut bytes = Vec::new();
+        let mut writer = MarcWriter::new(&mut bytes).with_oversize(Oversize::DropFields(vec![
+            "9XX".to_string(),
+            "505".to_string(),
+        ]));
+        writer.write_record(&record).unwrap();
+        let event = writer.oversize_report()[0].clone();
+        assert_eq!(event.record_index, 1);
+        assert_eq!(event.control_number.as_deref(), Some("big1"));
+        let OversizeAction::Dropped { tags, length } = event.action else {
+            panic!("expected fields to be dropped");
+        };
+        assert_eq!(tags, ["949"]);
+        drop(writer);
+        assert_eq!(length, bytes.len());
+        let read = crate::MarcReader::new(Cursor::new(bytes))
+            .read_record()
+            .unwrap()
+            .unwrap();
+        assert_eq!(read.get_fields("949").map(<[Field]>::len), Some(5));
+        assert_eq!(read.get_fields("505").map(<[Field]>::len), Some(6));
+
+        let mut writer = MarcWriter::new(Vec::new())
+            .with_oversize(Oversize::DropFields(vec!["245".to_string()]));
+        assert!(writer.write_record(&record).is_err());
+        assert!(writer.oversize_report().is_empty());
+    }
+
+    #[test]
+    fn test_oversize_truncate_fields() {
+        let mut record = oversized();
+        let mut linked = Field::new("880".to_string(), ' ', ' ');
+        linked.add_subfield('6', "505-01/(N".to_string());
+        linked.add_subfield('a', "é".repeat(3000));
+        record.add_field(linked);
+
+        let mut bytes = Vec::new();
+        let mut writer = MarcWriter::new(&mut bytes).with_oversize(Oversize::TruncateFields(vec![
+            "5XX".to_string(),
+            "880".to_string(),
+        ]));
+        writer.write_record(&record).unwrap();
+        let event = writer.oversize_report()[0].clone();
+        let OversizeAction::Truncated { tags, length } = event.action else {
+            panic!("expected fields to be truncated");
+        };
+        assert_eq!(tags, ["505"; 6]);
+        drop(writer);
+        assert_eq!(length, bytes.len());
+        assert!(length <= ISO2709_MAX_FIELD);
+
+        let read = crate::MarcReader::new(Cursor::new(bytes))
+            .read_record()
+            .unwrap()
+            .unwrap();
+        let notes: Vec<usize> = read
+            .get_fields("505")
+            .unwrap()
+            .iter()
+            .map(|f| f.get_subfield('a').unwrap().len())
+            .collect();
+        assert!(
+            notes
+                .iter()
+                .all(|&len| len == notes[0] && len < 9000 && len > 6000)
+        );
+        assert!(
+            read.get_fields("949")
+                .unwrap()
+                .iter()
+                .all(|f| f.get_subfield('a').unwrap().len() == 9000)
+        );
+        let linked = &read.get_fields("880").unwrap()[0];
+        assert_eq!(linked.get_subfield('6'), Some("505-01/(N"));
+        assert_eq!(linked.get_subfield('a').unwrap().len(), 6000);
+
+        let mut writer = MarcWriter::new(Vec::new())
+            .with_oversize(Oversize::TruncateFields(vec!["245".to_string()]));
+        assert!(writer.write_record(&record).is_err());
+        assert!(writer.oversize_report().is_empty());
+    }
+
+    #[test]
+    fn test_cut_point_keeps_char_boundaries() {
+        assert_eq!(cut_point("abc", 5), 3);
+        assert_eq!(cut_point("abc", 2), 2);
+        assert_eq!(cut_point("aéb", 2), 1);
+        assert_eq!(cut_point("éé", 0), 0);
+    }
+
+    #[test]
+    fn test_oversize_side_channel() {
+        let side = crate::marcxml::MarcxmlWriter::new(Vec::new());
+        let mut writer =
+            MarcWriter::new(Vec::new()).with_oversize(Oversize::SideChannel(Box::new(side)));
+        let mut small = Record::new(make_test_leader());
+        small.add_control_field_str("001", "small1");
+        writer.write_record(&small).unwrap();
+        writer.write_record(&oversized()).unwrap();
+        writer.finish().unwrap();
+        assert_eq!(writer.records_written(), 1);
+        let report = writer.oversize_report();
+        assert_eq!(report.len(), 1);
+        assert_eq!(report[0].record_index, 2);
+        assert_eq!(report[0].action, OversizeAction::Diverted);
+        assert!(report[0].length > ISO2709_MAX_FIELD);
+    }
 }
@@ -0,0 +1,250 @@
+//! Hardening options for the XML readers.
+//!
+//! The MARCXML and MODS readers parse with `quick-xml`, which never
+//! fetches external resources and never expands entities declared in a
+//! DTD: only the five predefined entities and numeric character references
+//! are resolved, and any other entity reference is an error. [`XmlOptions`]
+//! adds limits on top of that for documents from untrusted sources:
+//!
+//! - a `<!DOCTYPE>` declaration is rejected unless
+//!   [`allow_doctype`](XmlOptions::allow_doctype) is set (and even then its
+//!   entity declarations are ignored);
+//! - elements may nest at most [`max_depth`](XmlOptions::max_depth) deep;
+//! - a single attribute value may be at most
+//!   [`max_attribute_len`](XmlOptions::max_attribute_len) bytes.
+//!
+//! The remaining fields expose `quick-xml`'s well-formedness checks.
+//!
+//! ```
+//! use mrrc::{ParseOptions, XmlOptions, marcxml};
+//!
+//! let hostile = r#"<?xml version="1.0"?>
+//! <!DOCTYPE r [<!ENTITY x SYSTEM "file:///etc/passwd">]>
+//! <record><leader>00000nam a2200000 a 4500</leader></record>"#;
+//! assert!(marcxml::marcxml_to_record(hostile).is_err());
+//!
+//! let options = ParseOptions {
+//!     xml: XmlOptions::default().with_max_depth(16),
+//!     ..ParseOptions::strict()
+//! };
+//! let xml = "<record><leader>00000nam a2200000 a 4500</leader></record>";
+//! assert!(marcxml::marcxml_to_record_with(xml, options).is_ok());
+//! ```
+
+use std::fmt;
+use std::io::BufRead;
+
+use quick_xml::Decoder;
+use quick_xml::events::{BytesStart, Event};
+
+/// Security and well-formedness settings for the XML readers.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct XmlOptions {
+    /// Accept documents with a `<!DOCTYPE>` declaration (default `false`).
+    /// Entities it declares are never expanded either way.
+    pub allow_doctype: bool,
+    /// Deepest element nesting accepted (default 256).
+    pub max_depth: usize,
+    /// Longest attribute value accepted, in bytes (default 64 KiB).
+    pub max_attribute_len: usize,
+    /// Require end tags to match their start tags (default `true`).
+    pub check_end_names: bool,
+    /// Reject comments containing `--` (default `false`).
+    pub check_comments: bool,
+    /// Tolerate end tags with no open element (default `false`).
+    pub allow_unmatched_ends: bool,
+}
+
+impl Default for XmlOptions {
+    fn default() -> Self {
+        XmlOptions::new()
+    }
+}
+
+impl XmlOptions {
+    /// The default, hardened settings.
+    #[must_use]
+    pub const fn new() -> Self {
+        XmlOptions {
+            allow_doctype: false,
+            max_depth: 256,
+            max_attribute_len: 64 * 1024,
+            check_end_names: true,
+            check_comments: false,
+            allow_unmatched_ends: false,
+        }
+    }
+
+    /// Accept or reject `<!DOCTYPE>` declarations.
+    #[must_use]
+    pub const fn with_allow_doctype(mut self, allow: bool) -> Self {
+        self.allow_doctype = allow;
+        self
+    }
+
+    /// Set the deepest element nesting accepted.
+    #[must_use]
+    pub const fn with_max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = depth;
+        self
+    }
+
+    /// Set the longest attribute value accepted.
+    #[must_use]
+    pub const fn with_max_attribute_len(mut self, len: usize) -> Self {
+        self.max_attribute_len = len;
+        self
+    }
+}
+
+/// Error from a [`GuardedReader`]: either the XML is malformed or it
+/// broke one of the [`XmlOptions`] limits.
+#[derive(Debug)]
+pub(crate) enum XmlReadError {
+    Xml(quick_xml::Error),
+    Limit(String),
+}
+
+impl fmt::Display for XmlReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            XmlReadError::Xml(e) => e.fmt(f),
+            XmlReadError::Limit(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for XmlReadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            XmlReadError::Xml(e) => Some(e),
+            XmlReadError::Limit(_) => None,
+        }
+    }
+}
+
+/// A `quick-xml` reader that enforces [`XmlOptions`] on every event.
+#[derive(Debug)]
+pub(crate) struct GuardedReader<R> {
+    inner: quick_xml::Reader<R>,
+    options: XmlOptions,
+    depth: usize,
+}
+
+impl<'a> GuardedReader<&'a [u8]> {
+    pub(crate) fn from_str(xml: &'a str, options: XmlOptions) -> Self {
+        Self::wrap(quick_xml::Reader::from_str(xml), options)
+    }
+}
+
+impl<R: BufRead> GuardedReader<R> {
+    pub(crate) fn from_reader(reader: R, options: XmlOptions) -> Self {
+        Self::wrap(quick_xml::Reader::from_reader(reader), options)
+    }
+
+    fn wrap(inner: quick_xml::Reader<R>, options: XmlOptions) -> Self {
+        let mut reader = GuardedReader {
+            inner,
+            options,
+            depth: 0,
+        };
+        reader.set_options(options);
+        reader
+    }
+
+    pub(crate) fn set_options(&mut self, options: XmlOptions) {
+        let config = self.inner.config_mut();
+        config.trim_text(false);
+        config.check_end_names = options.check_end_names;
+        config.check_comments = options.check_comments;
+        config.allow_unmatched_ends = options.allow_unmatched_ends;
+        self.options = options;
+    }
+
+    pub(crate) fn decoder(&self) -> Decoder {
+        self.inner.decoder()
+    }
+
+    /// Read the next event, failing if it breaks a limit.
+    pub(crate) fn read_event_into<'b>(
+        &mut self,
+        buf: &'b mut Vec<u8>,
+    ) -> Result<Event<'b>, XmlReadError> {
+        let event = self.inner.read_event_into(buf).map_err(XmlReadError::Xml)?;
+        match &event {
+            Event::DocType(_) if !self.options.allow_doctype => {
+                return Err(XmlReadError::Limit(
+                    "DOCTYPE declarations are not allowed".to_string(),
+                ));
+            },
+            Event::Start(start) => {
+                self.depth += 1;
+                self.check_element(start, self.depth)?;
+            },
+            Event::Empty(start) => self.check_element(start, self.depth + 1)?,
+            Event::End(_) => self.depth = self.depth.saturating_sub(1),
+            _ => {},
+        }
+        Ok(event)
+    }
+
+    fn check_element(&self, start: &BytesStart, depth: usize) -> Result<(), XmlReadError> {
+        if depth > self.options.max_depth {
+            return Err(XmlReadError::Limit(format!(
+                "elements nested deeper than {} levels",
+                self.options.max_depth
+            )));
+        }
+        let longest = start
+            .attributes()
+            .with_checks(false)
+            .filter_map(std::result::Result::ok)
+            .map(|attr| attr.value.len())
+            .max()
+            .unwrap_or(0);
+        if longest > self.options.max_attribute_len {
+            return Err(XmlReadError::Limit(format!(
+                "attribute value of {longest} bytes exceeds the {}-byte limit",
+                self.options.max_attribute_len
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_all(xml: &str, options: XmlOptions) -> Result<(), XmlReadError> {
+        let mut reader = GuardedReader::from_str(xml, options);
+        let mut buf = Vec::new();
+        loop {
+            buf.clear();
+            if matches!(reader.read_event_into(&mut buf)?, Event::Eof) {
+                return Ok(());
+            }
+        }
+    }
+
+    #[test]
+    fn doctype_rejected_unless_allowed() {
+        let xml = r#"<!DOCTYPE a [<!ENTITY e "x">]><a>&e;</a>"#;
+        let err = read_all(xml, XmlOptions::default()).unwrap_err();
+        assert!(err.to_string().contains("DOCTYPE"));
+        assert!(read_all(xml, XmlOptions::default().with_allow_doctype(true)).is_ok());
+    }
+
+    #[test]
+    fn depth_and_attribute_limits() {
+        let deep = format!("{}{}", "<a>".repeat(10), "</a>".repeat(10));
+        assert!(read_all(&deep, XmlOptions::default()).is_ok());
+        assert!(read_all(&deep, XmlOptions::default().with_max_depth(9)).is_err());
+        assert!(read_all("<a><b/></a>", XmlOptions::default().with_max_depth(1)).is_err());
+
+        let wide = format!(r#"<a x="{}"/>"#, "v".repeat(100));
+        assert!(read_all(&wide, XmlOptions::default().with_max_attribute_len(100)).is_ok());
+        assert!(read_all(&wide, XmlOptions::default().with_max_attribute_len(99)).is_err());
+    }
+}
@@ -0,0 +1,355 @@
+//! Layout options for the MARCXML and MODS writers.
+//!
+//! By default the MARCXML functions write each record on a single line and
+//! the MODS functions write two-space indented XML with `mods:`-prefixed
+//! elements. Downstream XSLT, schema tooling and diff-based QA often need a
+//! particular layout instead; an [`XmlStyle`] passed to
+//! [`record_to_marcxml_styled`](crate::marcxml::record_to_marcxml_styled),
+//! [`record_to_mods_xml_styled`](crate::mods::record_to_mods_xml_styled) or
+//! the writers' `with_style` builders chooses:
+//!
+//! - **single-line or pretty** output — [`XmlStyle::single_line`] removes
+//!   all whitespace between elements (collection writers still end each
+//!   record with a newline), [`XmlStyle::pretty`] puts each element on its
+//!   own line, indented by the given number of spaces per level;
+//! - **namespace prefix** — [`XmlNamespace::Native`] keeps the format's
+//!   usual form, [`XmlNamespace::Default`] writes unprefixed elements in a
+//!   default namespace, and [`XmlNamespace::Prefixed`] puts every element
+//!   under a prefix such as `marc:`.
+//!
+//! Whitespace inside element content (for example a subfield value of a
+//! single space) is always preserved.
+//!
+//! # Examples
+//!
+//! ```
+//! use mrrc::marcxml;
+//! use mrrc::xml_style::{XmlNamespace, XmlStyle};
+//! use mrrc::{Field, Leader, Record};
+//!
+//! let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+//! record.add_field(
+//!     Field::builder("245".to_string(), '1', '0')
+//!         .subfield_str('a', "A title")
+//!         .build(),
+//! );
+//!
+//! let style = XmlStyle::pretty(4).with_namespace(XmlNamespace::Prefixed("marc".to_string()));
+//! let xml = marcxml::record_to_marcxml_styled(&record, &style)?;
+//! assert!(xml.contains("<marc:record xmlns:marc=\"http://www.loc.gov/MARC21/slim\">\n"));
+//! assert!(xml.contains("\n        <marc:subfield code=\"a\">A title</marc:subfield>\n"));
+//! # Ok::<(), mrrc::MarcError>(())
+//! ```
+
+use std::fmt::Write;
+
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+
+use crate::error::{MarcError, Result};
+
+/// How elements are namespace-qualified in styled XML output.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum XmlNamespace {
+    /// The form the format normally writes: unprefixed for MARCXML;
+    /// `mods:`-prefixed children under a root declaring both the default
+    /// and `mods` namespaces for MODS
+    #[default]
+    Native,
+    /// Unprefixed elements in a default namespace (`xmlns="..."`)
+    Default,
+    /// Every element under the given prefix (`xmlns:marc="..."`)
+    Prefixed(String),
+}
+
+/// Indentation and namespace layout for XML output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XmlStyle {
+    /// Spaces per nesting level, or `None` for single-line output
+    pub indent: Option<usize>,
+    /// Namespace qualification of elements
+    pub namespace: XmlNamespace,
+}
+
+impl Default for XmlStyle {
+    fn default() -> Self {
+        XmlStyle::pretty(2)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Last {
+    Start,
+    Content,
+    Close,
+}
+
+impl XmlStyle {
+    /// One element per line, indented `indent` spaces per level.
+    #[must_use]
+    pub fn pretty(indent: usize) -> Self {
+        XmlStyle {
+            indent: Some(indent),
+            namespace: XmlNamespace::Native,
+        }
+    }
+
+    /// No whitespace between elements.
+    #[must_use]
+    pub fn single_line() -> Self {
+        XmlStyle {
+            indent: None,
+            namespace: XmlNamespace::Native,
+        }
+    }
+
+    /// Use `namespace` for element qualification.
+    #[must_use]
+    pub fn with_namespace(mut self, namespace: XmlNamespace) -> Self {
+        self.namespace = namespace;
+        self
+    }
+
+    /// The name to write for element `name`.
+    pub(crate) fn element_name(&self, name: &str) -> String {
+        let local = name.rsplit(':').next().unwrap_or(name);
+        match &self.namespace {
+            XmlNamespace::Native => name.to_string(),
+            XmlNamespace::Default => local.to_string(),
+            XmlNamespace::Prefixed(prefix) => format!("{prefix}:{local}"),
+        }
+    }
+
+    /// The declaration of `namespace_uri` to put on a root element, with a
+    /// leading space; empty for [`XmlNamespace::Native`].
+    pub(crate) fn declaration(&self, namespace_uri: &str) -> String {
+        match &self.namespace {
+            XmlNamespace::Native => String::new(),
+            XmlNamespace::Default => format!(" xmlns=\"{namespace_uri}\""),
+            XmlNamespace::Prefixed(prefix) => format!(" xmlns:{prefix}=\"{namespace_uri}\""),
+        }
+    }
+
+    /// Re-lay out `xml` (a document or a single element written by this
+    /// crate), starting at nesting level `depth`.
+    ///
+    /// Declarations of `namespace_uri` are rewritten to match the
+    /// namespace style; other attributes are copied unchanged.
+    pub(crate) fn apply(&self, xml: &str, namespace_uri: &str, depth: usize) -> Result<String> {
+        let mut reader = Reader::from_str(xml);
+        let mut out = String::with_capacity(xml.len() + xml.len() / 4);
+        // Whitespace-only text is layout unless it turns out to be the
+        // content of an element.
+        let mut pending: Option<String> = None;
+        let mut last = Last::Close;
+        let mut level = depth;
+        loop {
+            let event = reader.read_event().map_err(|e| MarcError::XmlError {
+                cause: Box::new(e),
+                record_index: None,
+                byte_offset: None,
+                source_name: None,
+            })?;
+            match event {
+                Event::Eof => break,
+                Event::Start(e) => {
+                    pending = None;
+                    self.new_line(&mut out, level);
+                    self.write_start(&mut out, &e, namespace_uri);
+                    out.push('>');
+                    level += 1;
+                    last = Last::Start;
+                },
+                Event::Empty(e) => {
+                    pending = None;
+                    self.new_line(&mut out, level);
+                    self.write_start(&mut out, &e, namespace_uri);
+                    out.push_str("/>");
+                    last = Last::Close;
+                },
+                Event::End(e) => {
+                    level = level.saturating_sub(1);
+                    if last == Last::Close {
+                        self.new_line(&mut out, level);
+                    } else if let Some(space) = pending.take() {
+                        out.push_str(&space);
+                    }
+                    pending = None;
+                    let name = String::from_utf8_lossy(e.name().as_ref()).into_owned();
+                    write!(out, "</{}>", self.element_name(&name)).ok();
+                    last = Last::Close;
+                },
+                Event::Text(t) => {
+                    let text = String::from_utf8_lossy(&t).into_owned();
+                    if text.trim().is_empty() && last != Last::Content {
+                        pending = Some(text);
+                    } else {
+                        out.push_str(&pending.take().unwrap_or_default());
+                        out.push_str(&text);
+                        last = Last::Content;
+                    }
+                },
+                Event::GeneralRef(r) => {
+                    out.push_str(&pending.take().unwrap_or_default());
+                    write!(out, "&{};", String::from_utf8_lossy(&r)).ok();
+                    last = Last::Content;
+                },
+                Event::CData(c) => {
+                    out.push_str(&pending.take().unwrap_or_default());
+                    write!(out, "<![CDATA[{}]]>", String::from_utf8_lossy(&c)).ok();
+                    last = Last::Content;
+                },
+                Event::Decl(d) => {
+                    write!(out, "<?{}?>", String::from_utf8_lossy(&d)).ok();
+                },
+                Event::PI(p) => {
+                    self.new_line(&mut out, level);
+                    write!(out, "<?{}?>", String::from_utf8_lossy(&p)).ok();
+                    last = Last::Close;
+                },
+                Event::Comment(c) => {
+                    self.new_line(&mut out, level);
+                    write!(out, "<!--{}-->", String::from_utf8_lossy(&c)).ok();
+                    last = Last::Close;
+                },
+                Event::DocType(d) => {
+                    self.new_line(&mut out, level);
+                    write!(out, "<!DOCTYPE {}>", String::from_utf8_lossy(&d)).ok();
+                },
+            }
+        }
+        if self.indent.is_some() && !out.ends_with('\n') {
+            out.push('\n');
+        }
+        Ok(out)
+    }
+
+    /// In pretty mode, end the current line (if any) and indent to `level`.
+    fn new_line(&self, out: &mut String, level: usize) {
+        if let Some(indent) = self.indent {
+            if !out.is_empty() && !out.ends_with('\n') {
+                out.push('\n');
+            }
+            out.extend(std::iter::repeat_n(' ', indent * level));
+        }
+    }
+
+    /// Write `<name attrs` for a start or empty tag.
+    fn write_start(&self, out: &mut String, e: &BytesStart<'_>, namespace_uri: &str) {
+        if self.namespace == XmlNamespace::Native {
+            write!(out, "<{}", String::from_utf8_lossy(e)).ok();
+            return;
+        }
+        let name = String::from_utf8_lossy(e.name().as_ref()).into_owned();
+        let mut declared = false;
+        let mut attributes = String::new();
+        for attribute in e.attributes().with_checks(false).flatten() {
+            let key = attribute.key.as_ref();
+            if (key == b"xmlns" || key.starts_with(b"xmlns:"))
+                && attribute.value.as_ref() == namespace_uri.as_bytes()
+            {
+                declared = true;
+                continue;
+            }
+            write!(
+                attributes,
+                " {}=\"{}\"",
+                String::from_utf8_lossy(key),
+                String::from_utf8_lossy(&attribute.value)
+            )
+            .ok();
+        }
+        write!(out, "<{}", self.element_name(&name)).ok();
+        if declared {
+            out.push_str(&self.declaration(namespace_uri));
+        }
+        out.push_str(&attributes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NS: &str = "urn:example";
+
+    #[test]
+    fn test_pretty_and_single_line() {
+        let xml = "<?xml version=\"1.0\"?><r xmlns=\"urn:example\"><a x=\"1\">t &amp; u</a><b> </b><c/></r>";
+        assert_eq!(
+            XmlStyle::pretty(2).apply(xml, NS, 0).unwrap(),
+            "<?xml version=\"1.0\"?>\n<r xmlns=\"urn:example\">\n  <a x=\"1\">t &amp; u</a>\n  <b> </b>\n  <c/>\n</r>\n"
+        );
+        let pretty = XmlStyle::pretty(2).apply(xml, NS, 0).unwrap();
+        assert_eq!(XmlStyle::single_line().apply(&pretty, NS, 0).unwrap(), xml);
+    }
+
+    #[test]
+    fn test_styled_collection_writers() {
+        use crate::formats::FormatWriter;
+        use crate::leader::Leader;
+        use crate::marcxml::{self, MarcxmlWriter};
+        use crate::mods::{self, ModsWriter};
+        use crate::record::{Field, Record};
+
+        let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+        record.add_control_field_str("001", "ocm1");
+        record.add_field(
+            Field::builder("245".to_string(), '1', '0')
+                .subfield_str('a', "Styled & indented")
+                .build(),
+        );
+
+        let style =
+            XmlStyle::single_line().with_namespace(XmlNamespace::Prefixed("marc".to_string()));
+        let mut writer = MarcxmlWriter::new(Vec::new()).with_style(style);
+        writer.write_record(&record).unwrap();
+        writer.write_record(&record).unwrap();
+        writer.finish().unwrap();
+        let xml = String::from_utf8(writer.into_inner()).unwrap();
+        let lines: Vec<_> = xml.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(
+            lines[1],
+            "<marc:collection xmlns:marc=\"http://www.loc.gov/MARC21/slim\">"
+        );
+        assert!(lines[2].starts_with("<marc:record><marc:leader>"));
+        assert_eq!(lines[4], "</marc:collection>");
+        assert_eq!(marcxml::marcxml_to_records(&xml).unwrap().len(), 2);
+
+        let style = XmlStyle::pretty(4).with_namespace(XmlNamespace::Default);
+        let mut writer = ModsWriter::new(Vec::new()).with_style(style);
+        writer.write_record(&record).unwrap();
+        writer.finish().unwrap();
+        let xml = String::from_utf8(writer.into_inner()).unwrap();
+        assert!(xml.contains("<modsCollection xmlns=\"http://www.loc.gov/mods/v3\" xmlns:xlink="));
+        assert!(xml.contains("\n    <mods>\n        <titleInfo>\n"));
+        assert!(!xml.contains("mods:"));
+        let records = mods::mods_xml_to_records(&xml).unwrap();
+        assert_eq!(
+            records[0].get_field("245").unwrap().get_subfield('a'),
+            Some("Styled & indented")
+        );
+    }
+
+    #[test]
+    fn test_namespace_rewriting() {
+        let xml = "<m:r xmlns=\"urn:example\" xmlns:m=\"urn:example\" xmlns:xlink=\"urn:x\"><m:a xlink:href=\"h\">v</m:a></m:r>";
+        let prefixed = XmlStyle::single_line()
+            .with_namespace(XmlNamespace::Prefixed("p".to_string()))
+            .apply(xml, NS, 0)
+            .unwrap();
+        assert_eq!(
+            prefixed,
+            "<p:r xmlns:p=\"urn:example\" xmlns:xlink=\"urn:x\"><p:a xlink:href=\"h\">v</p:a></p:r>"
+        );
+        let unprefixed = XmlStyle::pretty(1)
+            .with_namespace(XmlNamespace::Default)
+            .apply(xml, NS, 1)
+            .unwrap();
+        assert_eq!(
+            unprefixed,
+            " <r xmlns=\"urn:example\" xmlns:xlink=\"urn:x\">\n  <a xlink:href=\"h\">v</a>\n </r>\n"
+        );
+    }
+}
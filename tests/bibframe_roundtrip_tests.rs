@@ -3,7 +3,9 @@
 //! These tests verify that MARC → BIBFRAME → MARC conversion preserves
 //! essential bibliographic data with acceptable data loss documentation.
 
-use mrrc::bibframe::{BibframeConfig, bibframe_to_marc, marc_to_bibframe};
+use mrrc::bibframe::{
+    BibframeConfig, RdfFormat, RdfGraph, RdfNode, bibframe_to_marc, marc_to_bibframe,
+};
 use mrrc::leader::Leader;
 use mrrc::record::{Field, Record};
 
@@ -129,6 +131,46 @@ fn test_roundtrip_subject_preservation() {
     );
 }
 
+#[test]
+fn test_roundtrip_subject_vocabulary() {
+    let mut record = Record::new(make_test_leader());
+    record.add_control_field("001".to_string(), "subject-source-rt".to_string());
+
+    let mut mesh = Field::new("650".to_string(), ' ', '2');
+    mesh.add_subfield('a', "Neoplasms.".to_string());
+    record.add_field(mesh);
+    let mut fast = Field::new("650".to_string(), ' ', '7');
+    fast.add_subfield('a', "Cats".to_string());
+    fast.add_subfield('2', "fast".to_string());
+    record.add_field(fast);
+
+    let graph = marc_to_bibframe(&record, &make_config());
+    let result_record = bibframe_to_marc(&graph).expect("conversion failed");
+
+    let subjects = result_record.get_fields("650").unwrap_or_default();
+    assert_eq!(subjects.len(), 2);
+    let mesh = subjects.iter().find(|f| f.indicator2 == '2').expect("MeSH");
+    assert_eq!(mesh.get_subfield('a'), Some("Neoplasms."));
+    let fast = subjects.iter().find(|f| f.indicator2 == '7').expect("FAST");
+    assert_eq!(fast.get_subfield('2'), Some("fast"));
+}
+
+#[test]
+fn test_roundtrip_language_in_008() {
+    let mut record = Record::new(make_test_leader());
+    record.add_control_field("001".to_string(), "lang-rt".to_string());
+    record.add_control_field(
+        "008".to_string(),
+        "040520s2001    fr            000 0 fre  ".to_string(),
+    );
+
+    let graph = marc_to_bibframe(&record, &make_config());
+    let result_record = bibframe_to_marc(&graph).expect("conversion failed");
+
+    let fixed = result_record.get_control_field("008").expect("008");
+    assert_eq!(fixed.get(35..38), Some("fre"));
+}
+
 #[test]
 fn test_roundtrip_identifier_preservation() {
     let mut record = Record::new(make_test_leader());
@@ -505,3 +547,36 @@ fn test_documented_loss_authority_links() {
         "Author name preserved (authority link may be lost)"
     );
 }
+
+#[test]
+fn test_rdf_serialization_roundtrip_is_isomorphic() {
+    let mut record = Record::new(make_test_leader());
+    record.add_control_field("001".to_string(), "iso-rt".to_string());
+    let mut f245 = Field::new("245".to_string(), '1', '0');
+    f245.add_subfield('a', "Test Title /".to_string());
+    f245.add_subfield('c', "by Author.".to_string());
+    record.add_field(f245);
+    let mut f650 = Field::new("650".to_string(), ' ', '0');
+    f650.add_subfield('a', "Cataloging.".to_string());
+    record.add_field(f650);
+
+    let graph = marc_to_bibframe(&record, &make_config());
+    for format in [RdfFormat::Turtle, RdfFormat::NTriples, RdfFormat::RdfXml] {
+        let text = graph.serialize(format).expect("serialization failed");
+        let parsed = RdfGraph::parse(&text, format).expect("parsing failed");
+        assert!(
+            graph.isomorphic_to(&parsed),
+            "{format} round-trip changed the graph"
+        );
+        assert!(graph.diff(&parsed).is_empty());
+    }
+
+    let mut edited = record.clone();
+    edited.fields.shift_remove("650");
+    let diff = graph.diff(&marc_to_bibframe(&edited, &make_config()));
+    assert!(!diff.is_empty());
+    assert!(diff.removed.iter().any(|(triple, _)| matches!(
+        &triple.object,
+        RdfNode::Literal { value, .. } if value.starts_with("Cataloging")
+    )));
+}
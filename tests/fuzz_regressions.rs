@@ -8,6 +8,8 @@
 //! Adding a fixture for a NEW target requires adding a new `#[test]`
 //! function that mirrors the relevant fuzz target's contract.
 
+use mrrc::boundary_scanner::RecordBoundaryScanner;
+use mrrc::rayon_parser_pool::parse_batch_parallel;
 use mrrc::{MarcReader, MarcWriter, RecoveryMode, ValidationLevel};
 use std::fs;
 use std::io::Cursor;
@@ -114,3 +116,26 @@ fn error_classification_regressions() {
         }
     }
 }
+
+/// `scan_boundaries` asserts the boundary scanner tiles the buffer up to
+/// its last terminator and that batch-parsing the result does not panic.
+/// Mirrors `fuzz/fuzz_targets/scan_boundaries.rs`.
+#[test]
+fn scan_boundaries_regressions() {
+    for (path, bytes) in fixtures("scan_boundaries") {
+        let mut scanner = RecordBoundaryScanner::new();
+        let count = scanner.count_records(&bytes);
+        let Ok(boundaries) = scanner.scan(&bytes) else {
+            assert_eq!(count, 0, "{}", path.display());
+            continue;
+        };
+        assert_eq!(boundaries.len(), count, "{}", path.display());
+        let mut expected_offset = 0;
+        for &(offset, length) in &boundaries {
+            assert_eq!(offset, expected_offset, "{}", path.display());
+            assert_eq!(bytes[offset + length - 1], 0x1D, "{}", path.display());
+            expected_offset = offset + length;
+        }
+        let _ = parse_batch_parallel(&boundaries, &bytes);
+    }
+}
@@ -0,0 +1,95 @@
+//! Byte-exact round trips of the ISO 2709 test corpus.
+//!
+//! Every `.mrc` file under `tests/data` is read with
+//! `MarcReader::read_lossless` and written back with
+//! `MarcWriter::write_lossless`; the output must equal the input
+//! byte for byte. A record that is changed must keep the directory order of
+//! the file it came from.
+
+use mrrc::{Field, MarcReader, MarcWriter, RecoveryMode};
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+fn corpus() -> Vec<PathBuf> {
+    let data = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data");
+    let mut files = Vec::new();
+    for dir in [data.clone(), data.join("fixtures")] {
+        for entry in std::fs::read_dir(dir).expect("test data directory") {
+            let path = entry.expect("directory entry").path();
+            if path.extension().is_some_and(|ext| ext == "mrc") {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+fn round_trip(original: &[u8], edit: impl Fn(&mut mrrc::Record)) -> Vec<u8> {
+    let mut reader =
+        MarcReader::new(Cursor::new(original)).with_recovery_mode(RecoveryMode::Lenient);
+    let mut output = Vec::new();
+    let mut writer = MarcWriter::new(&mut output);
+    while let Some((mut record, source)) = reader.read_lossless().expect("corpus record reads") {
+        edit(&mut record);
+        writer
+            .write_lossless(&record, &source)
+            .expect("corpus record writes");
+    }
+    output
+}
+
+fn directory_tags(bytes: &[u8]) -> Vec<Vec<String>> {
+    let mut offset = 0;
+    let mut records = Vec::new();
+    while offset < bytes.len() {
+        let record = &bytes[offset..];
+        let length: usize = std::str::from_utf8(&record[..5]).unwrap().parse().unwrap();
+        let base: usize = std::str::from_utf8(&record[12..17])
+            .unwrap()
+            .parse()
+            .unwrap();
+        records.push(
+            record[24..base - 1]
+                .chunks_exact(12)
+                .map(|entry| String::from_utf8_lossy(&entry[..3]).into_owned())
+                .collect(),
+        );
+        offset += length;
+    }
+    records
+}
+
+#[test]
+fn corpus_round_trips_byte_for_byte() {
+    let files = corpus();
+    assert!(!files.is_empty(), "no .mrc files found");
+    for path in files {
+        let original = std::fs::read(&path).expect("corpus file reads");
+        let output = round_trip(&original, |_| {});
+        assert!(
+            output == original,
+            "{} did not round-trip byte for byte",
+            path.display()
+        );
+    }
+}
+
+#[test]
+fn corpus_edits_keep_directory_order() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/multi_records.mrc");
+    let original = std::fs::read(path).expect("corpus file reads");
+    let output = round_trip(&original, |record| {
+        let mut note = Field::new("590".to_string(), ' ', ' ');
+        note.add_subfield('a', "Local note.".to_string());
+        record.add_field(note);
+    });
+
+    let before = directory_tags(&original);
+    let after = directory_tags(&output);
+    assert_eq!(before.len(), after.len());
+    for (before, mut after) in before.into_iter().zip(after) {
+        assert_eq!(after.pop().as_deref(), Some("590"));
+        assert_eq!(after, before);
+    }
+}
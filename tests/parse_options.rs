@@ -0,0 +1,195 @@
+//! `ParseOptions`: strict vs lenient parsing with warnings, across the
+//! ISO 2709, MARCXML and JSON readers.
+
+use mrrc::{Field, Leader, MarcReader, MarcWriter, ParseOptions, Record, json, marcxml};
+use std::io::Cursor;
+
+fn record_with_bad_indicator() -> Vec<u8> {
+    let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+    record.add_control_field("001".to_string(), "rec1".to_string());
+    record.add_field(
+        Field::builder("245".to_string(), '1', '#')
+            .subfield_str('a', "Title")
+            .build(),
+    );
+    record.add_field(
+        Field::builder("500".to_string(), ' ', ' ')
+            .subfield_str('a', "Note")
+            .build(),
+    );
+    let mut bytes = Vec::new();
+    MarcWriter::new(&mut bytes).write_record(&record).unwrap();
+    bytes
+}
+
+#[test]
+fn test_iso2709_lenient_blanks_invalid_indicator() {
+    let bytes = record_with_bad_indicator();
+
+    let mut strict = MarcReader::new(Cursor::new(&bytes)).with_parse_options(ParseOptions {
+        validation_level: mrrc::ValidationLevel::StrictMarc,
+        ..ParseOptions::strict()
+    });
+    assert_eq!(strict.read_record().unwrap_err().code(), "E201");
+
+    let mut lenient =
+        MarcReader::new(Cursor::new(&bytes)).with_parse_options(ParseOptions::lenient());
+    let record = lenient.read_record().unwrap().unwrap();
+    let field = &record.get_fields("245").unwrap()[0];
+    assert_eq!((field.indicator1, field.indicator2), ('1', ' '));
+    assert_eq!(field.get_subfield('a'), Some("Title"));
+    assert!(record.get_fields("500").is_some());
+    assert_eq!(record.warnings().len(), 1);
+    assert_eq!(record.warnings()[0].code(), "E201");
+}
+
+/// Overwrite the leader's record length (bytes 0-4).
+fn set_record_length(bytes: &mut [u8], length: usize) {
+    bytes[..5].copy_from_slice(format!("{length:05}").as_bytes());
+}
+
+#[test]
+fn test_iso2709_lenient_corrects_short_record_length() {
+    let mut first = record_with_bad_indicator();
+    let actual = first.len();
+    set_record_length(&mut first, actual - 10);
+    let mut stream = first.clone();
+    stream.extend(record_with_bad_indicator());
+
+    let mut strict = MarcReader::new(Cursor::new(&stream));
+    assert_eq!(strict.read_record().unwrap_err().code(), "E006");
+
+    let mut lenient =
+        MarcReader::new(Cursor::new(&stream)).with_parse_options(ParseOptions::lenient());
+    let record = lenient.read_record().unwrap().unwrap();
+    assert_eq!(record.leader.record_length as usize, actual);
+    assert_eq!(
+        record.get_fields("500").unwrap()[0].get_subfield('a'),
+        Some("Note")
+    );
+    assert_eq!(record.warnings()[0].code(), "E001");
+    // The stream stays in step: the next record starts where this one ends.
+    let next = lenient.read_record().unwrap().unwrap();
+    assert_eq!(next.get_control_field("001"), Some("rec1"));
+
+    let shared = std::sync::Arc::new(first);
+    let record = mrrc::parse_record_from_shared_bytes(
+        &shared,
+        mrrc::RecoveryMode::Lenient,
+        mrrc::ValidationLevel::Structural,
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(record.leader.record_length as usize, actual);
+    assert_eq!(record.warnings()[0].code(), "E001");
+    assert!(record.get_fields("500").is_some());
+}
+
+#[test]
+fn test_iso2709_lenient_keeps_fields_without_terminator() {
+    // A record whose 001 and 500 fields lack their field terminators, with
+    // the directory lengths to match.
+    let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+    record.add_control_field("001".to_string(), "rec1".to_string());
+    record.add_field(
+        Field::builder("500".to_string(), ' ', ' ')
+            .subfield_str('a', "Note")
+            .build(),
+    );
+    let mut written = Vec::new();
+    MarcWriter::new(&mut written).write_record(&record).unwrap();
+    let base: usize = std::str::from_utf8(&written[12..17])
+        .unwrap()
+        .parse()
+        .unwrap();
+    let directory = written[24..base - 1].to_vec();
+    let mut data = Vec::new();
+    let mut entries = Vec::new();
+    for entry in directory.chunks(12) {
+        let start: usize = std::str::from_utf8(&entry[7..12]).unwrap().parse().unwrap();
+        let length: usize = std::str::from_utf8(&entry[3..7]).unwrap().parse().unwrap();
+        let field = &written[base + start..base + start + length - 1];
+        entries.extend_from_slice(&entry[..3]);
+        entries.extend_from_slice(format!("{:04}{:05}", field.len(), data.len()).as_bytes());
+        data.extend_from_slice(field);
+    }
+    let mut bytes = written[..24].to_vec();
+    bytes.extend_from_slice(&entries);
+    bytes.push(0x1E);
+    bytes.extend_from_slice(&data);
+    bytes.push(0x1D);
+    let length = bytes.len();
+    set_record_length(&mut bytes, length);
+
+    let strict = MarcReader::new(Cursor::new(&bytes))
+        .read_record()
+        .unwrap()
+        .unwrap();
+    assert_eq!(strict.get_control_field("001"), Some("rec"));
+
+    let mut lenient =
+        MarcReader::new(Cursor::new(&bytes)).with_parse_options(ParseOptions::lenient());
+    let record = lenient.read_record().unwrap().unwrap();
+    assert_eq!(record.get_control_field("001"), Some("rec1"));
+    assert_eq!(
+        record.get_fields("500").unwrap()[0].get_subfield('a'),
+        Some("Note")
+    );
+    let messages: Vec<String> = record.warnings().iter().map(ToString::to_string).collect();
+    assert_eq!(messages.len(), 2, "{messages:?}");
+    assert!(messages[0].contains("Field 001 has no field terminator"));
+    assert!(messages[1].contains("Field 500 has no field terminator"));
+}
+
+#[test]
+fn test_warnings_can_be_discarded() {
+    let bytes = record_with_bad_indicator();
+    let mut reader = MarcReader::new(Cursor::new(&bytes)).with_parse_options(ParseOptions {
+        collect_warnings: false,
+        ..ParseOptions::lenient()
+    });
+    let record = reader.read_record().unwrap().unwrap();
+    assert!(record.get_fields("245").is_some());
+    assert!(record.warnings().is_empty());
+}
+
+#[test]
+fn test_marcxml_lenient_repairs_record() {
+    let xml = r#"<record>
+        <leader>00000nam a22</leader>
+        <datafield tag="245" ind1="1" ind2="x">
+            <subfield code="a">Title</subfield>
+            <subfield code="">stray</subfield>
+        </datafield>
+    </record>"#;
+
+    assert!(marcxml::marcxml_to_record(xml).is_err());
+
+    let record = marcxml::marcxml_to_record_with(xml, ParseOptions::lenient()).unwrap();
+    assert_eq!(record.leader.record_type, 'a');
+    assert_eq!(record.leader.indicator_count, 2);
+    let field = &record.get_fields("245").unwrap()[0];
+    assert_eq!(field.indicator2, ' ');
+    assert_eq!(field.subfields.len(), 1);
+    let codes: Vec<_> = record
+        .warnings()
+        .iter()
+        .map(mrrc::MarcError::code)
+        .collect();
+    assert_eq!(codes, ["E002", "E201", "E106"]);
+}
+
+#[test]
+fn test_json_lenient_blanks_missing_indicator() {
+    let value = serde_json::json!([
+        {"leader": "00000nam a2200000 a 4500"},
+        {"245": {"ind1": "1", "subfields": {"a": "Title"}}}
+    ]);
+
+    assert!(json::json_to_record(&value).is_err());
+
+    let record = json::json_to_record_with(&value, ParseOptions::lenient()).unwrap();
+    let field = &record.get_fields("245").unwrap()[0];
+    assert_eq!((field.indicator1, field.indicator2), ('1', ' '));
+    assert_eq!(record.warnings().len(), 1);
+}
@@ -0,0 +1,66 @@
+"""Arrow export of extracted columns via ``MARCReader.to_arrow``.
+
+Columns are extracted in Rust straight into Arrow buffers and exported
+through the Arrow PyCapsule interface. The stream object is checked without
+pyarrow; the ``pyarrow.Table`` tests are skipped when pyarrow is missing.
+"""
+
+import ctypes
+
+import pytest
+
+import mrrc
+
+FIXTURE = "tests/data/multi_records.mrc"
+TITLES = ["The Great Gatsby", "Beethovens Ninth Symphony", "Children's Book"]
+
+
+def test_stream_shape_and_batching():
+    with mrrc.MARCReader(FIXTURE) as reader:
+        stream = reader.to_arrow_stream(["245$a", "008/35-37"], batch_size=2)
+        assert reader.closed
+    assert isinstance(stream, mrrc.ArrowColumnStream)
+    assert stream.column_names == ["245$a", "008/35-37"]
+    assert stream.num_rows == 3
+    assert stream.num_batches == 2
+
+
+def test_stream_exports_arrow_capsule():
+    stream = mrrc.MARCReader(FIXTURE).to_arrow_stream(["245$a"])
+    capsule = stream.__arrow_c_stream__()
+    is_valid = ctypes.pythonapi.PyCapsule_IsValid
+    is_valid.restype = ctypes.c_int
+    is_valid.argtypes = [ctypes.py_object, ctypes.c_char_p]
+    assert is_valid(capsule, b"arrow_array_stream") == 1
+
+
+@pytest.mark.parametrize(
+    "columns",
+    [["24"], {"a": "245$a", "b": "not an expression"}, ["245$a", "245$a"]],
+)
+def test_invalid_columns_raise(columns):
+    with pytest.raises(ValueError):
+        mrrc.MARCReader(FIXTURE).to_arrow_stream(columns)
+
+
+def test_zero_batch_size_raises():
+    with pytest.raises(ValueError, match="batch_size"):
+        mrrc.MARCReader(FIXTURE).to_arrow_stream(["245$a"], batch_size=0)
+
+
+def test_to_arrow_table():
+    pa = pytest.importorskip("pyarrow")
+    table = mrrc.MARCReader(FIXTURE).to_arrow(
+        {"title": "245$a", "lang": "008/35-37", "isbn": "020$a"}, batch_size=2
+    )
+    assert table.column_names == ["title", "lang", "isbn"]
+    assert table.schema.field("title").type == pa.string()
+    assert table.column("title").to_pylist() == TITLES
+    assert table.num_rows == 3
+
+
+def test_to_arrow_matches_record_values():
+    pytest.importorskip("pyarrow")
+    expected = [r["245"]["a"] for r in mrrc.MARCReader(FIXTURE)]
+    table = mrrc.MARCReader(FIXTURE).to_arrow(["245$a"])
+    assert table.column("245$a").to_pylist() == expected
@@ -0,0 +1,83 @@
+"""Context manager and close() support for readers and writers.
+
+Every reader and MARCWriter supports ``with ... as``, an idempotent
+``close()`` and a ``closed`` property. Closing a reader stops iteration;
+closing a writer flushes it and releases a file it opened from a path.
+File objects passed in by the caller are never closed by mrrc.
+"""
+
+import io
+
+import pytest
+
+import mrrc
+
+MULTI_FIXTURE = "tests/data/multi_records.mrc"
+AUTHORITY_FIXTURE = "tests/data/simple_authority.mrc"
+HOLDINGS_FIXTURE = "tests/data/simple_holdings.mrc"
+
+
+@pytest.mark.parametrize(
+    ("reader_class", "fixture"),
+    [
+        (mrrc.MARCReader, MULTI_FIXTURE),
+        (mrrc.AuthorityMARCReader, AUTHORITY_FIXTURE),
+        (mrrc.HoldingsMARCReader, HOLDINGS_FIXTURE),
+    ],
+)
+class TestReaderContextManager:
+    def test_with_closes_reader(self, reader_class, fixture):
+        with reader_class(fixture) as reader:
+            assert not reader.closed
+            assert next(iter(reader)) is not None
+        assert reader.closed
+        with pytest.raises(StopIteration):
+            next(reader)
+
+    def test_close_is_idempotent(self, reader_class, fixture):
+        reader = reader_class(fixture)
+        reader.close()
+        reader.close()
+        assert reader.closed
+        assert list(reader) == []
+
+    def test_exception_propagates_and_closes(self, reader_class, fixture):
+        with pytest.raises(KeyError), reader_class(fixture) as reader:
+            raise KeyError("boom")
+        assert reader.closed
+
+    def test_caller_file_object_left_open(self, reader_class, fixture):
+        with open(fixture, "rb") as handle:
+            with reader_class(handle) as reader:
+                next(iter(reader))
+            assert not handle.closed
+
+
+class TestWriterClose:
+    def _record(self):
+        with mrrc.MARCReader(MULTI_FIXTURE) as reader:
+            return next(reader)
+
+    def test_with_flushes_path_output(self, tmp_path):
+        path = tmp_path / "out.mrc"
+        record = self._record()
+        with mrrc.MARCWriter(str(path)) as writer:
+            writer.write(record)
+            assert not writer.closed
+        assert writer.closed
+        with mrrc.MARCReader(str(path)) as reader:
+            assert len(list(reader)) == 1
+
+    def test_caller_file_object_flushed_not_closed(self):
+        buffer = io.BytesIO()
+        with mrrc.MARCWriter(buffer) as writer:
+            writer.write(self._record())
+        assert not buffer.closed
+        assert buffer.getvalue().endswith(b"\x1d")
+
+    def test_write_after_close_raises(self):
+        writer = mrrc.MARCWriter(io.BytesIO())
+        writer.close()
+        writer.close()
+        with pytest.raises(RuntimeError):
+            writer.write(self._record())
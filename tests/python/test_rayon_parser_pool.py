@@ -16,7 +16,11 @@ import contextlib
 import pytest
 
 from mrrc import MARCReader, RecordBoundaryScanner
-from mrrc.rayon_parser_pool import parse_batch_parallel
+from mrrc.rayon_parser_pool import (
+    parse_batch_parallel,
+    parse_batch_parallel_map,
+    parse_batch_parallel_results,
+)
 
 
 @pytest.fixture
@@ -256,6 +260,51 @@ class TestRayonParserPoolErrorHandling:
             parse_batch_parallel(boundaries, buffer)
 
 
+class TestRayonParserPoolPartialResults:
+    """Per-record results: one bad record does not fail the batch."""
+
+    def test_results_keep_good_records(self, multi_records_bytes):
+        boundaries = RecordBoundaryScanner().scan(multi_records_bytes)
+        end = len(multi_records_bytes)
+        bad = [(end, 10)]  # past the end of the buffer
+        results = parse_batch_parallel_results(
+            boundaries[:1] + bad + boundaries[1:2], multi_records_bytes
+        )
+
+        assert [i for i, _ in results] == [0, 1, 2]
+        assert isinstance(results[1][1], Exception)
+        assert "exceeds buffer" in str(results[1][1])
+        assert results[0][1].title == parse_batch_parallel(
+            boundaries[:1], multi_records_bytes
+        )[0].title
+        assert not isinstance(results[2][1], Exception)
+
+    def test_map_applies_function_and_captures_exceptions(
+        self, multi_records_bytes
+    ):
+        boundaries = RecordBoundaryScanner().scan(multi_records_bytes)[:3]
+
+        def control_number(record):
+            return record.control_field("001")
+
+        expected = [
+            r.control_field("001")
+            for r in parse_batch_parallel(boundaries, multi_records_bytes)
+        ]
+        results = parse_batch_parallel_map(
+            boundaries, multi_records_bytes, control_number
+        )
+        assert [value for _, value in results] == expected
+
+        def fail(record):
+            raise KeyError("boom")
+
+        failures = parse_batch_parallel_map(
+            boundaries, multi_records_bytes, fail
+        )
+        assert all(isinstance(v, KeyError) for _, v in failures)
+
+
 class TestRayonParserPoolPerformance:
     """Test performance characteristics."""
 
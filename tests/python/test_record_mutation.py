@@ -0,0 +1,108 @@
+"""Batch field mutation: remove_fields_where and the update_* methods.
+
+Filters may be query objects, dicts of ``FieldQuery`` criteria, or
+Python callables receiving a :class:`mrrc.Field`.
+"""
+
+from __future__ import annotations
+
+import pytest
+
+import mrrc
+
+
+def _build_record() -> mrrc.Record:
+    """A record with a 245 and four 650s, two of them local (ind2 7)."""
+    record = mrrc.Record()
+    title = mrrc.Field("245", "1", "0")
+    title.add_subfield("a", "Title /")
+    record.add_field(title)
+    for heading, ind2 in (
+        ("Cats.", "0"),
+        ("Dogs.", "7"),
+        ("Birds.", "0"),
+        ("Fish.", "7"),
+    ):
+        subject = mrrc.Field("650", " ", ind2)
+        subject.add_subfield("a", heading)
+        if ind2 == "7":
+            subject.add_subfield("2", "local")
+        record.add_field(subject)
+    return record
+
+
+def _headings(record: mrrc.Record) -> list[str]:
+    return [f["a"] for f in record.get_fields("650")]
+
+
+def test_remove_fields_where_with_dict() -> None:
+    record = _build_record()
+    removed = record.remove_fields_where({"tag": "650", "indicator2": "7"})
+    assert [f["a"] for f in removed] == ["Dogs.", "Fish."]
+    assert _headings(record) == ["Cats.", "Birds."]
+
+
+def test_remove_fields_where_with_query_and_callable() -> None:
+    record = _build_record()
+    query = mrrc.SubfieldValueQuery("650", "a", "Birds.")
+    assert len(record.remove_fields_where(query)) == 1
+
+    removed = record.remove_fields_where(
+        lambda f: f.tag == "650" and f["a"] == "Cats."
+    )
+    assert [f["a"] for f in removed] == ["Cats."]
+    assert _headings(record) == ["Dogs.", "Fish."]
+    assert record.remove_fields_where({"tag": "999"}) == []
+
+
+def test_remove_fields_where_invalidates_handles() -> None:
+    record = _build_record()
+    handle = record.get_fields("650")[3]
+    record.remove_fields_where({"has_subfield": "2"})
+    with pytest.raises(mrrc.StaleFieldError):
+        _ = handle["a"]
+
+
+def test_update_subfield_values_and_where() -> None:
+    record = _build_record()
+    record.update_subfield_values("650", "2", "lcsh")
+    sources = [f["2"] for f in record.get_fields("650") if "2" in f]
+    assert sources == ["lcsh", "lcsh"]
+
+    query = mrrc.FieldQuery().tag("650").indicator2("0")
+    count = record.update_subfields_where(query, "a", "X")
+    assert count == 2
+    assert _headings(record) == ["X", "Dogs.", "X", "Fish."]
+
+
+def test_update_fields_where_in_place_and_by_return() -> None:
+    record = _build_record()
+
+    def set_source(field: mrrc.Field) -> None:
+        field.indicator2 = "0"
+
+    local = {"tag": "650", "indicator2": "7"}
+    assert record.update_fields_where(local, set_source) == 2
+    assert [f.indicator2 for f in record.get_fields("650")] == ["0"] * 4
+
+    def replace_title(field: mrrc.Field) -> mrrc.Field:
+        new = mrrc.Field("245", "0", "0")
+        new.add_subfield("a", "New title")
+        return new
+
+    record.update_fields_where({"tag": "245"}, replace_title)
+    assert record["245"]["a"] == "New title"
+
+
+def test_update_fields_where_rejects_tag_change_and_bad_filters() -> None:
+    record = _build_record()
+
+    def retag(field: mrrc.Field) -> mrrc.Field:
+        return mrrc.Field("690", " ", " ")
+
+    with pytest.raises(ValueError, match="tag"):
+        record.update_fields_where({"tag": "650"}, retag)
+    with pytest.raises(ValueError, match="unknown filter key"):
+        record.remove_fields_where({"subfield": "a"})
+    with pytest.raises(TypeError):
+        record.remove_fields_where(42)
@@ -0,0 +1,131 @@
+"""Every construction path yields the same ``mrrc.Record`` type.
+
+Records from readers, format conversions, the parallel parser, the
+producer-consumer pipeline, BIBFRAME and ``copy.deepcopy`` must all be
+``mrrc.Record`` instances, and every serializer and writer must accept any
+of them. Each conversion path is round-tripped through the others.
+"""
+
+import copy
+import io
+
+import pytest
+
+import mrrc
+
+FIXTURE = "tests/data/multi_records.mrc"
+
+
+def _read_first():
+    with mrrc.MARCReader(FIXTURE) as reader:
+        return next(reader)
+
+
+def _parallel():
+    with open(FIXTURE, "rb") as f:
+        buffer = f.read()
+    boundaries = mrrc.RecordBoundaryScanner().scan(buffer)
+    return mrrc.parse_batch_parallel(boundaries, buffer)[0]
+
+
+def _pipeline():
+    return next(iter(mrrc.ProducerConsumerPipeline.from_file(FIXTURE)))
+
+
+def _constructed():
+    record = mrrc.Record(mrrc.Leader())
+    record.add_control_field("001", "constructed")
+    record.add_field(
+        mrrc.Field("245", "1", "0", subfields=[mrrc.Subfield("a", "A title")])
+    )
+    return record
+
+
+SOURCES = {
+    "reader": _read_first,
+    "constructor": _constructed,
+    "xml": lambda: mrrc.xml_to_record(_read_first().to_xml()),
+    "xml_collection": lambda: mrrc.xml_to_records(
+        mrrc.record_to_xml(_read_first())
+    )[0],
+    "json": lambda: mrrc.json_to_record(_read_first().to_json()),
+    "marcjson": lambda: mrrc.marcjson_to_record(_read_first().to_marcjson()),
+    "parallel": _parallel,
+    "rayon_module": lambda: __import__(
+        "mrrc.rayon_parser_pool", fromlist=["parse_batch_parallel"]
+    ).parse_batch_parallel(
+        mrrc.RecordBoundaryScanner().scan(open(FIXTURE, "rb").read()),
+        open(FIXTURE, "rb").read(),
+    )[0],
+    "pipeline": _pipeline,
+    "deepcopy": lambda: copy.deepcopy(_read_first()),
+}
+
+
+@pytest.mark.parametrize("source", sorted(SOURCES))
+def test_every_source_yields_record(source):
+    record = SOURCES[source]()
+    assert type(record) is mrrc.Record
+
+
+@pytest.mark.parametrize("source", sorted(SOURCES))
+def test_every_serializer_accepts_record(source):
+    record = SOURCES[source]()
+    for serialize in (
+        mrrc.record_to_json,
+        mrrc.record_to_xml,
+        mrrc.record_to_marcjson,
+        mrrc.record_to_mods,
+        mrrc.record_to_dublin_core,
+        mrrc.record_to_csv,
+    ):
+        assert serialize(record)
+    assert mrrc.records_to_csv([record, _read_first()])
+
+    buffer = io.BytesIO()
+    with mrrc.MARCWriter(buffer) as writer:
+        writer.write(record)
+    with mrrc.MARCReader(buffer.getvalue()) as reader:
+        assert next(reader) == record
+
+
+@pytest.mark.parametrize(
+    ("to_text", "from_text"),
+    [
+        (mrrc.record_to_json, mrrc.json_to_record),
+        (mrrc.record_to_xml, mrrc.xml_to_record),
+        (mrrc.record_to_marcjson, mrrc.marcjson_to_record),
+    ],
+)
+def test_conversion_round_trips(to_text, from_text):
+    original = _read_first()
+    once = from_text(to_text(original))
+    twice = from_text(to_text(once))
+    assert once == original
+    assert twice == original
+
+
+def test_leader_edits_reach_module_functions():
+    record = _read_first()
+    record.leader.record_status = "c"
+    assert "<leader>" in mrrc.record_to_xml(record)
+    assert mrrc.xml_to_record(mrrc.record_to_xml(record)).leader.record_status == "c"
+
+
+def test_marc_to_bibframe_accepts_raw_and_wrapped_record():
+    wrapped = _constructed()
+    raw = wrapped._inner
+    assert type(raw) is not mrrc.Record
+
+    config = mrrc.BibframeConfig()
+    config.set_base_uri("http://example.org/")
+    from_wrapped = mrrc.marc_to_bibframe(wrapped, config)
+    from_raw = mrrc.marc_to_bibframe(raw, config)
+    assert len(from_wrapped) > 0
+    assert from_raw.triples() == from_wrapped.triples()
+    assert "A title" in from_wrapped.serialize("ntriples")
+
+
+def test_non_record_is_rejected():
+    with pytest.raises(TypeError, match="expected a Record"):
+        mrrc.record_to_json("not a record")
@@ -0,0 +1,52 @@
+"""Tests for the throughput counters exposed as ``stats`` properties."""
+
+import io
+
+import mrrc
+
+
+def _record(control_number):
+    record = mrrc.Record()
+    record.add_control_field("001", control_number)
+    field = mrrc.Field("245", "0", "0")
+    field.add_subfield("a", "Stats title")
+    record.add_field(field)
+    return record
+
+
+def _marc_bytes(count):
+    buffer = io.BytesIO()
+    with mrrc.MARCWriter(buffer) as writer:
+        for i in range(count):
+            writer.write(_record(f"rec{i}"))
+        stats = writer.stats
+    assert stats.records == count
+    assert stats.errors == 0
+    return buffer.getvalue(), stats.bytes
+
+
+def test_reader_and_writer_stats():
+    data, written_bytes = _marc_bytes(3)
+    assert written_bytes == len(data)
+
+    reader = mrrc.MARCReader(io.BytesIO(data))
+    assert reader.stats.records == 0
+    assert len(list(reader)) == 3
+
+    snapshot = reader.stats.snapshot()
+    assert isinstance(snapshot, mrrc.StatsSnapshot)
+    assert snapshot.records == 3
+    assert snapshot.bytes == len(data)
+    assert snapshot.errors == 0
+    assert snapshot.elapsed >= 0.0
+    assert snapshot.per_record is not None
+    assert "records=3" in repr(snapshot)
+
+
+def test_streaming_writer_stats():
+    out = io.StringIO()
+    with mrrc.MARCXMLWriter(out) as writer:
+        writer.write(_record("x1"))
+        writer.write(_record("x2"))
+    assert writer.stats.records == 2
+    assert writer.stats.snapshot().rate >= 0.0
@@ -0,0 +1,90 @@
+"""Streaming MARCXML, MARCJSON (NDJSON) and MODS writers."""
+
+from __future__ import annotations
+
+import io
+import json
+
+import pytest
+
+import mrrc
+
+
+def _records(n: int = 3) -> list[mrrc.Record]:
+    records = []
+    for i in range(n):
+        record = mrrc.Record()
+        record.add_control_field("001", f"ocm{i}")
+        title = mrrc.Field("245", "1", "0")
+        title.add_subfield("a", f"Title {i}")
+        record.add_field(title)
+        records.append(record)
+    return records
+
+
+def test_marcxml_writer_to_path_round_trips(tmp_path) -> None:
+    path = tmp_path / "out.xml"
+    with mrrc.MARCXMLWriter(path) as writer:
+        for record in _records():
+            writer.write(record)
+        assert writer.records_written == 3
+    assert writer.closed
+    records = mrrc.xml_to_records(path.read_text(encoding="utf-8"))
+    ids = [r.control_field("001") for r in records]
+    assert ids == ["ocm0", "ocm1", "ocm2"]
+
+
+def test_marcjson_writer_emits_one_record_per_line() -> None:
+    buffer = io.BytesIO()
+    writer = mrrc.MARCJSONWriter(buffer)
+    for record in _records():
+        writer.write_record(record)
+    writer.close()
+    lines = buffer.getvalue().decode("utf-8").splitlines()
+    assert len(lines) == 3
+    record = mrrc.marcjson_to_record(lines[1])
+    assert record.control_field("001") == "ocm1"
+    assert all(isinstance(json.loads(line), list) for line in lines)
+
+
+def test_mods_writer_accepts_text_file_objects() -> None:
+    buffer = io.StringIO()
+    with mrrc.MODSWriter(buffer) as writer:
+        writer.write(_records(1)[0])
+    assert not buffer.closed
+    records = mrrc.mods_collection_to_records(buffer.getvalue())
+    assert records[0]["245"]["a"] == "Title 0"
+
+
+def test_xml_writer_layout_options() -> None:
+    buffer = io.StringIO()
+    with mrrc.MARCXMLWriter(buffer, indent=4, namespace_prefix="marc") as writer:
+        writer.write(_records(1)[0])
+    xml = buffer.getvalue()
+    assert '<marc:collection xmlns:marc="http://www.loc.gov/MARC21/slim">' in xml
+    assert "\n    <marc:record>\n        <marc:leader>" in xml
+    assert mrrc.xml_to_records(xml)[0].control_field("001") == "ocm0"
+
+    buffer = io.StringIO()
+    with mrrc.MODSWriter(buffer, single_line=True, namespace_prefix="") as writer:
+        writer.write(_records(1)[0])
+    lines = buffer.getvalue().splitlines()
+    assert lines[2].startswith("<mods><titleInfo><title>Title 0</title>")
+
+
+def test_empty_collection_and_closed_writer() -> None:
+    buffer = io.BytesIO()
+    writer = mrrc.MARCXMLWriter(buffer)
+    writer.close()
+    writer.close()
+    assert b"</collection>" in buffer.getvalue()
+    with pytest.raises(RuntimeError, match="closed"):
+        writer.write(_records(1)[0])
+
+
+def test_write_dispatches_to_streaming_writers(tmp_path) -> None:
+    assert mrrc.write(_records(), tmp_path / "out.ndjson") == 3
+    assert mrrc.write(_records(2), tmp_path / "out.mods", format="mods") == 2
+    assert mrrc.write(_records(1), tmp_path / "out.xml") == 1
+    lines = (tmp_path / "out.ndjson").read_text(encoding="utf-8").splitlines()
+    assert len(lines) == 3
@@ -325,6 +325,15 @@ class TestConvenienceMethods:
         assert phys_desc is not None
         assert "256" in phys_desc
 
+    def test_title_sort_keys(self):
+        """Sort keys skip non-filing characters and fold case/diacritics."""
+        record = Record(Leader())
+        assert record.title_sort_key is None
+        record.add_field(create_field("245", "1", "4", a="The Émigrés /"))
+        record.add_field(create_field("240", "1", "2", a="L'Étranger."))
+        assert record.title_sort_key == "emigres"
+        assert record.uniform_title_sort_key == "etranger"
+
 
 class TestFieldDictLike:
     """Test dictionary-like access to fields (pymarc style)."""
@@ -0,0 +1,68 @@
+//! Integration tests for the `RecordBuilder` bibliographic convenience
+//! constructors.
+
+use mrrc::{MarcWriter, RecordBuilder};
+
+#[test]
+fn test_bibliographic_defaults() {
+    let record = RecordBuilder::bibliographic().build();
+    assert_eq!(
+        record.leader.as_bytes().unwrap(),
+        b"00000nam a2200000 i 4500"
+    );
+
+    let fixed = record.get_control_field("008").unwrap();
+    assert_eq!(fixed.len(), 40);
+    assert!(fixed[0..6].bytes().all(|b| b.is_ascii_digit()));
+    assert_eq!(&fixed[6..18], "nuuuuuuuuxx ");
+    assert_eq!(&fixed[35..40], "und d");
+}
+
+#[test]
+fn test_title_indicator_follows_main_entry() {
+    let record = RecordBuilder::bibliographic()
+        .title("Anonymous work")
+        .build();
+    assert_eq!(record.get_field("245").unwrap().indicator1, '0');
+
+    // author() after title() updates the 245
+    let record = RecordBuilder::bibliographic()
+        .title("Roughing it")
+        .author("Twain, Mark")
+        .build();
+    assert_eq!(record.get_field("245").unwrap().indicator1, '1');
+    assert_eq!(record.get_field("100").unwrap().indicator1, '1');
+
+    let record = RecordBuilder::bibliographic()
+        .author("Homer")
+        .title("Odyssey")
+        .build();
+    assert_eq!(record.get_field("100").unwrap().indicator1, '0');
+    assert_eq!(record.get_field("245").unwrap().indicator1, '1');
+}
+
+#[test]
+fn test_build_validated() {
+    // No 001
+    assert!(
+        RecordBuilder::bibliographic()
+            .title("Untitled")
+            .build_validated()
+            .is_err()
+    );
+
+    let record = RecordBuilder::bibliographic()
+        .control_field_str("001", "12345")
+        .title("Roughing it")
+        .isbn("9780520268173")
+        .build_validated()
+        .unwrap();
+    assert_eq!(
+        record.get_field("020").unwrap().get_subfield('a'),
+        Some("9780520268173")
+    );
+
+    let mut buffer = Vec::new();
+    MarcWriter::new(&mut buffer).write_record(&record).unwrap();
+    assert!(!buffer.is_empty());
+}
@@ -0,0 +1,91 @@
+//! Repeated 006 and 007 control fields survive every reader and writer.
+//!
+//! `Record::control_fields` keeps a `Vec` of values per tag, so a second
+//! 006 or 007 is stored alongside the first rather than replacing it. These
+//! tests pin that down for each serialization, keeping the occurrences in
+//! order.
+
+use mrrc::formats::FormatWriter;
+use mrrc::{Field, Leader, MarcReader, MarcWriter, Record, json, marc_in_json, marcjson, marcxml};
+use std::io::Cursor;
+
+const EXPECTED: [(&str, &str); 6] = [
+    ("001", "ocm00000001"),
+    ("006", "m     o  d        "),
+    ("006", "a     o  d        "),
+    ("007", "cr |||||||||||"),
+    ("007", "ta"),
+    ("008", "850101s1985    nyu           000 0 eng d"),
+];
+
+fn record() -> Record {
+    let mut record = Record::new(Leader::from_bytes(b"00000nam a2200000 a 4500").unwrap());
+    for (tag, value) in EXPECTED {
+        record.add_control_field_str(tag, value);
+    }
+    record.add_field(
+        Field::builder("245".to_string(), '0', '0')
+            .subfield_str('a', "Repeated control fields")
+            .build(),
+    );
+    record
+}
+
+fn assert_control_fields(format: &str, record: &Record) {
+    let found: Vec<(&str, &str)> = record.control_fields_iter().collect();
+    assert_eq!(found, EXPECTED, "{format}");
+    assert_eq!(
+        record.get_control_field("007"),
+        Some("cr |||||||||||"),
+        "{format}"
+    );
+}
+
+#[test]
+fn test_iso2709_round_trip() {
+    let mut bytes = Vec::new();
+    {
+        let mut writer = MarcWriter::new(&mut bytes);
+        writer.write_record(&record()).unwrap();
+    }
+    let read = MarcReader::new(Cursor::new(bytes))
+        .read_record()
+        .unwrap()
+        .unwrap();
+    assert_control_fields("ISO 2709", &read);
+}
+
+#[test]
+fn test_xml_round_trips() {
+    let record = record();
+    let xml = marcxml::record_to_marcxml(&record).unwrap();
+    assert_control_fields("MARCXML", &marcxml::marcxml_to_record(&xml).unwrap());
+
+    let mut writer = marcxml::MarcxmlWriter::new(Vec::new());
+    writer.write_record(&record).unwrap();
+    writer.finish().unwrap();
+    let xml = String::from_utf8(writer.into_inner()).unwrap();
+    assert_control_fields(
+        "MARCXML collection",
+        &marcxml::marcxml_to_records(&xml).unwrap()[0],
+    );
+}
+
+#[test]
+fn test_json_round_trips() {
+    let record = record();
+    let value = json::record_to_json(&record).unwrap();
+    assert_control_fields("JSON", &json::json_to_record(&value).unwrap());
+
+    let value = marcjson::record_to_marcjson(&record).unwrap();
+    assert_control_fields("MARCJSON", &marcjson::marcjson_to_record(&value).unwrap());
+
+    let value = marc_in_json::record_to_marc_in_json(&record).unwrap();
+    assert_control_fields(
+        "MARC-in-JSON",
+        &marc_in_json::marc_in_json_to_record(&value).unwrap(),
+    );
+
+    let text = serde_json::to_string(&record).unwrap();
+    assert_control_fields("serde", &serde_json::from_str::<Record>(&text).unwrap());
+}
@@ -0,0 +1,94 @@
+//! Hostile-document tests for the MARCXML and MODS readers.
+
+use std::fmt::Write;
+
+use mrrc::formats::FormatReader;
+use mrrc::mods::{self, ModsReader};
+use mrrc::{ParseOptions, XmlOptions, marcxml};
+
+const LEADER: &str = "<leader>00000nam a2200000 a 4500</leader>";
+
+fn billion_laughs() -> String {
+    let mut dtd = String::from(r#"<!ENTITY lol0 "lol">"#);
+    for i in 1..10 {
+        let refs = format!("&lol{};", i - 1).repeat(10);
+        write!(dtd, r#"<!ENTITY lol{i} "{refs}">"#).unwrap();
+    }
+    format!(
+        r#"<?xml version="1.0"?><!DOCTYPE record [{dtd}]><record>{LEADER}<datafield tag="245" ind1="0" ind2="0"><subfield code="a">&lol9;</subfield></datafield></record>"#
+    )
+}
+
+fn lenient_doctype() -> ParseOptions {
+    ParseOptions {
+        xml: XmlOptions::default().with_allow_doctype(true),
+        ..ParseOptions::strict()
+    }
+}
+
+#[test]
+fn test_marcxml_rejects_doctype_by_default() {
+    let xxe = format!(
+        r#"<?xml version="1.0"?><!DOCTYPE record [<!ENTITY xxe SYSTEM "file:///etc/passwd">]><record>{LEADER}<controlfield tag="001">&xxe;</controlfield></record>"#
+    );
+    let err = marcxml::marcxml_to_record(&xxe).unwrap_err();
+    assert!(err.to_string().contains("DOCTYPE"), "{err}");
+    assert!(marcxml::marcxml_to_records(&billion_laughs()).is_err());
+}
+
+#[test]
+fn test_marcxml_never_expands_declared_entities() {
+    // With DOCTYPE allowed the declarations are ignored, so a reference to
+    // a declared entity is an unknown entity rather than an expansion.
+    let err = marcxml::marcxml_to_record_with(&billion_laughs(), lenient_doctype()).unwrap_err();
+    assert!(err.to_string().contains("lol9"), "{err}");
+
+    let xml = format!(
+        r#"<!DOCTYPE record><record>{LEADER}<controlfield tag="001">a &amp; b</controlfield></record>"#
+    );
+    let record = marcxml::marcxml_to_record_with(&xml, lenient_doctype()).unwrap();
+    assert_eq!(record.get_control_field("001"), Some("a & b"));
+}
+
+#[test]
+fn test_marcxml_depth_and_attribute_limits() {
+    let deep = format!(
+        "<record>{LEADER}{}{}</record>",
+        "<x>".repeat(300),
+        "</x>".repeat(300)
+    );
+    assert!(marcxml::marcxml_to_record(&deep).is_err());
+
+    let long_tag = format!(
+        r#"<record>{LEADER}<datafield tag="{}" ind1=" " ind2=" "/></record>"#,
+        "9".repeat(70_000)
+    );
+    let err = marcxml::marcxml_to_record(&long_tag).unwrap_err();
+    assert!(err.to_string().contains("attribute"), "{err}");
+
+    let options = ParseOptions {
+        xml: XmlOptions::default().with_max_attribute_len(1 << 20),
+        ..ParseOptions::strict()
+    };
+    assert!(marcxml::marcxml_to_record_with(&long_tag, options).is_ok());
+}
+
+#[test]
+fn test_mods_readers_apply_xml_options() {
+    let doc = r#"<!DOCTYPE mods><mods xmlns="http://www.loc.gov/mods/v3"><titleInfo><title>T</title></titleInfo></mods>"#;
+    assert!(mods::mods_xml_to_record(doc).is_err());
+    let allowed = XmlOptions::default().with_allow_doctype(true);
+    let record = mods::mods_xml_to_record_with(doc, allowed).unwrap();
+    assert!(record.get_field("245").is_some());
+
+    let nested = format!(
+        "<modsCollection><mods>{}{}</mods></modsCollection>",
+        "<note>".repeat(20),
+        "</note>".repeat(20)
+    );
+    let mut reader = ModsReader::new(nested.as_bytes())
+        .with_xml_options(XmlOptions::default().with_max_depth(8));
+    assert!(reader.read_record().is_err());
+    let mut reader = ModsReader::new(nested.as_bytes());
+    assert!(reader.read_record().unwrap().is_some());
+}